@@ -37,6 +37,7 @@ impl PartialOrd for IncomingAction {
 #[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
 pub enum VersionedIncomingActionPayload {
     V1(IncomingActionPayloadV1),
+    V2(IncomingActionPayloadV2),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
@@ -46,6 +47,13 @@ pub enum IncomingActionPayloadV1 {
     Noop,
 }
 
+/// An [`IncomingActionPayloadV1`] that must not be processed after `valid_until` (unix timestamp in milliseconds).
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
+pub struct IncomingActionPayloadV2 {
+    pub valid_until: u64,
+    pub action: IncomingActionPayloadV1,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Encode, Decode)]
 pub struct AssignProcessorPayloadV1 {
     pub job_id: u128,
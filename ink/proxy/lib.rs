@@ -622,11 +622,21 @@ mod proxy {
                         );
                         self.processed_incoming_actions.insert(action.id, &());
 
+                        // Unwrap the action, actions that expired are not executed
+                        let payload = match action.payload {
+                            VersionedIncomingActionPayload::V1(payload) => payload,
+                            VersionedIncomingActionPayload::V2(payload) => {
+                                if payload.valid_until < self.env().block_timestamp() {
+                                    IncomingActionPayloadV1::Noop
+                                } else {
+                                    payload.action
+                                }
+                            }
+                        };
+
                         // Process action
-                        match action.payload {
-                            VersionedIncomingActionPayload::V1(
-                                IncomingActionPayloadV1::AssignJobProcessor(payload),
-                            ) => {
+                        match payload {
+                            IncomingActionPayloadV1::AssignJobProcessor(payload) => {
                                 match JobInformation::decode(self, payload.job_id)? {
                                     JobInformation::V1(mut job) => {
                                         let processor_address = AccountId::from(payload.processor);
@@ -655,9 +665,7 @@ mod proxy {
                                     }
                                 }
                             }
-                            VersionedIncomingActionPayload::V1(
-                                IncomingActionPayloadV1::FinalizeJob(payload),
-                            ) => {
+                            IncomingActionPayloadV1::FinalizeJob(payload) => {
                                 match JobInformation::decode(self, payload.job_id)? {
                                     JobInformation::V1(mut job) => {
                                         // Update job status
@@ -685,7 +693,7 @@ mod proxy {
                                     }
                                 }
                             }
-                            VersionedIncomingActionPayload::V1(IncomingActionPayloadV1::Noop) => {
+                            IncomingActionPayloadV1::Noop => {
                                 // Intentionally do nothing
                                 Ok(())
                             }
@@ -362,5 +362,66 @@ pub mod validator {
 
             assert_eq!(proof_validation, Ok(true));
         }
+
+        /// The proofs generated by `pallet-acurast-hyperdrive-outgoing` for MMRs of various sizes,
+        /// kept in sync with the pallet by its test `should_match_target_chain_proof_test_vectors`.
+        const PROOF_TEST_VECTORS: &str =
+            include_str!("../../pallets/hyperdrive-outgoing/test-vectors/target_chain_proofs.json");
+
+        fn decode_hex(value: &serde_json::Value) -> Vec<u8> {
+            let value = value.as_str().expect("hex string");
+            hex::decode(value.trim_start_matches("0x")).expect("valid hex")
+        }
+
+        fn decode_hash(value: &serde_json::Value) -> [u8; 32] {
+            decode_hex(value).try_into().expect("32 bytes hash")
+        }
+
+        /// Verifies the proofs generated by the pallet, failing for the vectors with a flipped byte.
+        #[ink::test]
+        fn test_verify_proof_test_vectors() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let admin = accounts.alice;
+            let mut validator = Validator::new(admin, 1, vec![admin]);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(admin);
+
+            let vectors: Vec<serde_json::Value> =
+                serde_json::from_str(PROOF_TEST_VECTORS).expect("valid test vectors");
+            assert!(!vectors.is_empty());
+
+            for vector in vectors.iter() {
+                let snapshot_number = validator.current_snapshot();
+                assert_eq!(
+                    validator.submit_root(snapshot_number, decode_hash(&vector["root"])),
+                    Ok(())
+                );
+
+                let proof = MerkleProof {
+                    mmr_size: vector["mmrSize"].as_u64().unwrap(),
+                    proof: vector["items"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(decode_hash)
+                        .collect(),
+                    leaves: vector["leaves"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|leaf| LeafProof {
+                            leaf_index: leaf["position"].as_u64().unwrap(),
+                            data: decode_hex(&leaf["message"]),
+                        })
+                        .collect(),
+                };
+
+                assert_eq!(
+                    validator.verify_proof(snapshot_number, proof),
+                    Ok(vector["valid"].as_bool().unwrap()),
+                    "test vector {}",
+                    vector
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,308 @@
+//! Generates a stable JSON description of the Acurast pallets (calls, events, errors and constants) from runtime metadata.
+//!
+//! The artifact is meant to be consumed by SDKs, so that they do not have to hard-code pallet and call indices or
+//! error names. Pallets are emitted sorted by name, calls/events/errors sorted by their index and constants sorted by name,
+//! so that regenerating the artifact for an unchanged runtime always yields the same output.
+
+use std::{fs, path::Path};
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, META_RESERVED};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// Environment variable that, when set, makes [`assert_snapshot`] overwrite the snapshot instead of comparing against it.
+pub const UPDATE_SNAPSHOT_ENV: &str = "ACURAST_METADATA_UPDATE";
+
+/// The prefix of the pallet names selected by default.
+pub const DEFAULT_PALLET_PREFIX: &str = "Acurast";
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidMetadata(codec::Error),
+    InvalidMagicNumber(u32),
+    UnsupportedVersion(u32),
+    UnknownType(u32),
+    NotAVariant { pallet: String, ty: u32 },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::InvalidMetadata(e) => write!(f, "invalid metadata: {}", e),
+            Error::InvalidMagicNumber(magic) => {
+                write!(f, "invalid metadata magic number {:#x}", magic)
+            }
+            Error::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "unsupported metadata version V{}, only V14 is supported",
+                    version
+                )
+            }
+            Error::UnknownType(id) => write!(f, "type {} not found in the type registry", id),
+            Error::NotAVariant { pallet, ty } => {
+                write!(
+                    f,
+                    "type {} of pallet {} is expected to be a variant",
+                    ty, pallet
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The generated artifact.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub pallets: Vec<Pallet>,
+}
+
+impl Artifact {
+    /// Returns the constant `name` of the pallet `pallet`, e.g. to check it against the runtime's `AcurastConfigApi::limits`.
+    pub fn constant(&self, pallet: &str, name: &str) -> Option<&Constant> {
+        self.pallets
+            .iter()
+            .find(|p| p.name == pallet)?
+            .constants
+            .iter()
+            .find(|constant| constant.name == name)
+    }
+}
+
+/// A single pallet (instance).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Pallet {
+    pub name: String,
+    pub index: u8,
+    pub calls: Vec<Variant>,
+    pub events: Vec<Variant>,
+    pub errors: Vec<Variant>,
+    pub constants: Vec<Constant>,
+}
+
+/// A call, event or error.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    pub index: u8,
+    pub fields: Vec<Field>,
+    pub docs: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Constant {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The SCALE encoded value, hex encoded with `0x` prefix.
+    pub value: String,
+    pub docs: String,
+}
+
+/// Decodes SCALE encoded [`RuntimeMetadataPrefixed`], e.g. the result of a `state_getMetadata` RPC call written to a file.
+pub fn decode(mut bytes: &[u8]) -> Result<RuntimeMetadataPrefixed, Error> {
+    RuntimeMetadataPrefixed::decode(&mut bytes).map_err(Error::InvalidMetadata)
+}
+
+/// Generates the artifact for all pallets of `metadata` whose name is accepted by `filter`.
+///
+/// Every instance of an instantiable pallet is a separate pallet in the metadata and is therefore emitted separately.
+pub fn generate(
+    metadata: &RuntimeMetadataPrefixed,
+    filter: impl Fn(&str) -> bool,
+) -> Result<Artifact, Error> {
+    if metadata.0 != META_RESERVED {
+        return Err(Error::InvalidMagicNumber(metadata.0));
+    }
+    let metadata = match &metadata.1 {
+        RuntimeMetadata::V14(metadata) => metadata,
+        other => return Err(Error::UnsupportedVersion(other.version())),
+    };
+    let registry = &metadata.types;
+
+    let mut pallets = metadata
+        .pallets
+        .iter()
+        .filter(|pallet| filter(&pallet.name))
+        .map(|pallet| {
+            let pallet_variants = |ty: Option<u32>| -> Result<Vec<Variant>, Error> {
+                match ty {
+                    Some(ty) => variants(registry, &pallet.name, ty),
+                    None => Ok(Vec::new()),
+                }
+            };
+            let mut constants = pallet
+                .constants
+                .iter()
+                .map(|constant| {
+                    Ok(Constant {
+                        name: constant.name.clone(),
+                        ty: type_name(registry, constant.ty.id)?,
+                        value: format!("0x{}", hex::encode(&constant.value)),
+                        docs: docs(&constant.docs),
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            constants.sort_by(|a, b| a.name.cmp(&b.name));
+
+            Ok(Pallet {
+                name: pallet.name.clone(),
+                index: pallet.index,
+                calls: pallet_variants(pallet.calls.as_ref().map(|calls| calls.ty.id))?,
+                events: pallet_variants(pallet.event.as_ref().map(|event| event.ty.id))?,
+                errors: pallet_variants(pallet.error.as_ref().map(|error| error.ty.id))?,
+                constants,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    pallets.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Artifact { pallets })
+}
+
+/// Serializes the artifact as pretty printed JSON with a trailing newline.
+pub fn to_json(artifact: &Artifact) -> String {
+    let mut json = serde_json::to_string_pretty(artifact).expect("artifact is always serializable");
+    json.push('\n');
+    json
+}
+
+/// Compares `artifact` against the snapshot stored at `path`, panicking if they differ or if the snapshot is missing.
+///
+/// The snapshot is (re)written instead if [`UPDATE_SNAPSHOT_ENV`] is set.
+pub fn assert_snapshot(artifact: &Artifact, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let json = to_json(artifact);
+    if std::env::var_os(UPDATE_SNAPSHOT_ENV).is_some() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("snapshot directory can be created");
+        }
+        fs::write(path, json).expect("snapshot can be written");
+        return;
+    }
+    let snapshot = fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "metadata snapshot {} cannot be read: {}; run with {}=1 to create it",
+            path.display(),
+            e,
+            UPDATE_SNAPSHOT_ENV
+        )
+    });
+    assert!(
+        snapshot == json,
+        "metadata artifact differs from snapshot {}; rerun with {}=1 to update it",
+        path.display(),
+        UPDATE_SNAPSHOT_ENV
+    );
+}
+
+fn variants(registry: &PortableRegistry, pallet: &str, ty: u32) -> Result<Vec<Variant>, Error> {
+    let TypeDef::Variant(def) = &registry.resolve(ty).ok_or(Error::UnknownType(ty))?.type_def
+    else {
+        return Err(Error::NotAVariant {
+            pallet: pallet.to_string(),
+            ty,
+        });
+    };
+    let mut variants = def
+        .variants
+        .iter()
+        .map(|variant| {
+            Ok(Variant {
+                name: variant.name.clone(),
+                index: variant.index,
+                fields: variant
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        Ok(Field {
+                            name: field.name.clone(),
+                            ty: type_name(registry, field.ty.id)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?,
+                docs: docs(&variant.docs),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    variants.sort_by_key(|variant| variant.index);
+    Ok(variants)
+}
+
+/// Returns a human readable name for the type with the given id, e.g. `BoundedVec<u8, S>` or `[u8; 32]`.
+fn type_name(registry: &PortableRegistry, id: u32) -> Result<String, Error> {
+    let ty = registry.resolve(id).ok_or(Error::UnknownType(id))?;
+    if let Some(name) = ty.path.segments.last() {
+        let params = ty
+            .type_params
+            .iter()
+            .map(|param| match &param.ty {
+                Some(param) => type_name(registry, param.id),
+                None => Ok(param.name.clone()),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        return Ok(if params.is_empty() {
+            name.clone()
+        } else {
+            format!("{}<{}>", name, params.join(", "))
+        });
+    }
+    Ok(match &ty.type_def {
+        TypeDef::Sequence(def) => format!("Vec<{}>", type_name(registry, def.type_param.id)?),
+        TypeDef::Array(def) => {
+            format!("[{}; {}]", type_name(registry, def.type_param.id)?, def.len)
+        }
+        TypeDef::Tuple(def) => format!(
+            "({})",
+            def.fields
+                .iter()
+                .map(|field| type_name(registry, field.id))
+                .collect::<Result<Vec<_>, Error>>()?
+                .join(", ")
+        ),
+        TypeDef::Compact(def) => format!("Compact<{}>", type_name(registry, def.type_param.id)?),
+        TypeDef::Primitive(def) => primitive_name(def).to_string(),
+        TypeDef::BitSequence(_) => "BitVec".to_string(),
+        TypeDef::Composite(_) | TypeDef::Variant(_) => format!("Type{}", id),
+    })
+}
+
+fn primitive_name(primitive: &TypeDefPrimitive) -> &'static str {
+    match primitive {
+        TypeDefPrimitive::Bool => "bool",
+        TypeDefPrimitive::Char => "char",
+        TypeDefPrimitive::Str => "str",
+        TypeDefPrimitive::U8 => "u8",
+        TypeDefPrimitive::U16 => "u16",
+        TypeDefPrimitive::U32 => "u32",
+        TypeDefPrimitive::U64 => "u64",
+        TypeDefPrimitive::U128 => "u128",
+        TypeDefPrimitive::U256 => "u256",
+        TypeDefPrimitive::I8 => "i8",
+        TypeDefPrimitive::I16 => "i16",
+        TypeDefPrimitive::I32 => "i32",
+        TypeDefPrimitive::I64 => "i64",
+        TypeDefPrimitive::I128 => "i128",
+        TypeDefPrimitive::I256 => "i256",
+    }
+}
+
+fn docs(docs: &[String]) -> String {
+    docs.iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,41 @@
+//! Generates the Acurast pallets' SDK artifact from SCALE encoded runtime metadata.
+//!
+//! Usage: `acurast-metadata-gen <metadata.scale> [pallet]...`
+//!
+//! The metadata file can be obtained from a live node, e.g. by writing the (hex decoded) result of the `state_getMetadata` RPC call to a file.
+//! If no pallets are given, all pallets whose name starts with [`DEFAULT_PALLET_PREFIX`] are included. The artifact is written to stdout.
+
+use std::{env, fs, process};
+
+use acurast_metadata_gen::{decode, generate, to_json, DEFAULT_PALLET_PREFIX};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let Some(path) = args.next() else {
+        eprintln!("usage: acurast-metadata-gen <metadata.scale> [pallet]...");
+        process::exit(2);
+    };
+    let pallets: Vec<String> = args.collect();
+
+    let result = fs::read(&path)
+        .map_err(|e| format!("failed to read {}: {}", path, e))
+        .and_then(|bytes| decode(&bytes).map_err(|e| e.to_string()))
+        .and_then(|metadata| {
+            generate(&metadata, |name| {
+                if pallets.is_empty() {
+                    name.starts_with(DEFAULT_PALLET_PREFIX)
+                } else {
+                    pallets.iter().any(|pallet| pallet == name)
+                }
+            })
+            .map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(artifact) => print!("{}", to_json(&artifact)),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
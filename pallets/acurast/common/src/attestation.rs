@@ -19,6 +19,11 @@ pub const CHAIN_MAX_LENGTH: u32 = 5;
 pub const CERT_MAX_LENGTH: u32 = 3000;
 pub type CertificateInput = BoundedVec<u8, ConstU32<CERT_MAX_LENGTH>>;
 pub type CertificateChainInput = BoundedVec<CertificateInput, ConstU32<CHAIN_MAX_LENGTH>>;
+pub const CRL_MAX_LENGTH: u32 = 16_384;
+pub type CrlInput = BoundedVec<u8, ConstU32<CRL_MAX_LENGTH>>;
+pub const TRUSTED_ROOT_MAX_LENGTH: u32 = 1024;
+/// The DER encoded `SubjectPublicKeyInfo` of a trusted attestation root.
+pub type TrustedRoot = BoundedVec<u8, ConstU32<TRUSTED_ROOT_MAX_LENGTH>>;
 
 fn parse_cert(serialized: &[u8]) -> Result<Certificate, ParseError> {
     let data = asn1::parse_single::<Certificate>(serialized)?;
@@ -166,21 +171,31 @@ fn validate<'a>(
     if cert.signature_algorithm.algorithm != cert.tbs_certificate.signature.algorithm {
         return Err(ValidationError::SignatureMismatch);
     }
-    match cert.signature_algorithm.algorithm {
+    validate_signature(
+        &cert.signature_algorithm.algorithm,
+        payload,
+        &cert.signature_value,
+        pbk,
+    )
+}
+
+fn validate_signature(
+    algorithm: &ObjectIdentifier,
+    payload: &[u8],
+    signature: &BitString,
+    pbk: &PublicKey,
+) -> Result<(), ValidationError> {
+    match *algorithm {
         RSA_ALGORITHM => match pbk {
-            PublicKey::RSA(pbk) => validate_rsa(&payload, &cert.signature_value, &pbk),
+            PublicKey::RSA(pbk) => validate_rsa(&payload, signature, &pbk),
             _ => Err(ValidationError::UnsupportedPublicKeyAlgorithm),
         },
         ECDSA_WITH_SHA256_ALGORITHM => match pbk {
-            PublicKey::ECDSA(pbk) => {
-                validate_ecdsa::<sha2::Sha256>(&payload, &cert.signature_value, &pbk)
-            }
+            PublicKey::ECDSA(pbk) => validate_ecdsa::<sha2::Sha256>(&payload, signature, &pbk),
             _ => Err(ValidationError::UnsupportedPublicKeyAlgorithm),
         },
         ECDSA_WITH_SHA384_ALGORITHM => match pbk {
-            PublicKey::ECDSA(pbk) => {
-                validate_ecdsa::<sha2::Sha384>(&payload, &cert.signature_value, &pbk)
-            }
+            PublicKey::ECDSA(pbk) => validate_ecdsa::<sha2::Sha384>(&payload, signature, &pbk),
             _ => Err(ValidationError::UnsupportedPublicKeyAlgorithm),
         },
         _ => Err(ValidationError::UnsupportedSignatureAlgorithm)?,
@@ -272,17 +287,26 @@ pub fn peek_attestation_version(data: &[u8]) -> Result<i64, ParseError> {
     result
 }
 
+/// Parses the DER encoded `SubjectPublicKeyInfo` of a trusted attestation root.
+pub fn parse_trusted_root(root: &[u8]) -> Result<PublicKey, ValidationError> {
+    PublicKey::parse(&asn1::parse_single::<SubjectPublicKeyInfo>(root)?)
+}
+
 /// Validates the chain by ensuring that
 ///
-/// - the chain starts with a self-signed certificate at index 0 that matches one of the known [TRUSTED_ROOT_CERTS]
+/// - the chain starts with a self-signed certificate at index 0 whose public key is one of the `trusted_roots`
 /// - that the root's contained public key signs the next certificate in the chain
 /// - the next certificate's public key signs the next one and so on...
+///
+/// Trusted roots that cannot be parsed with [parse_trusted_root] are ignored.
 pub fn validate_certificate_chain<'a>(
     chain: &'a CertificateChainInput,
+    trusted_roots: &[&[u8]],
 ) -> Result<(Vec<CertificateId>, TBSCertificate<'a>, PublicKey), ValidationError> {
-    let root_pub_key = PublicKey::parse(&asn1::parse_single::<SubjectPublicKeyInfo>(
-        TRUSTED_ROOT_PUB_KEY,
-    )?)?;
+    let root_pub_keys = trusted_roots
+        .iter()
+        .filter_map(|root| parse_trusted_root(root).ok())
+        .collect::<Vec<_>>();
     let mut cert_ids = Vec::<CertificateId>::new();
     let fold_result = chain.iter().try_fold::<_, _, Result<_, ValidationError>>(
         (Option::<PublicKey>::None, Option::<Certificate>::None),
@@ -290,7 +314,7 @@ pub fn validate_certificate_chain<'a>(
             let cert = parse_cert(&cert_data)?;
             let payload = parse_cert_payload(&cert_data)?;
             let current_pbk = PublicKey::parse(&cert.tbs_certificate.subject_public_key_info)?;
-            if prev_pbk.is_none() && current_pbk != root_pub_key {
+            if prev_pbk.is_none() && !root_pub_keys.contains(&current_pbk) {
                 return Err(ValidationError::UntrustedRoot);
             }
 
@@ -315,7 +339,71 @@ pub fn validate_certificate_chain<'a>(
     Ok((cert_ids, last_cert.tbs_certificate, last_cert_pbk))
 }
 
-const TRUSTED_ROOT_PUB_KEY: &'static [u8] = include_bytes!("./__root_key__/public.key");
+/// The content of a [CertificateList] whose signature was successfully validated by [validate_crl].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ValidatedCrl {
+    /// The `thisUpdate` of the CRL in milliseconds since the unix epoch.
+    pub this_update: u64,
+    /// The `nextUpdate` of the CRL in milliseconds since the unix epoch, if present.
+    pub next_update: Option<u64>,
+    /// The serial numbers of the revoked certificates.
+    pub serial_numbers: Vec<Vec<u8>>,
+}
+
+/// Validates the DER encoded [X.509 CRL](https://www.rfc-editor.org/rfc/rfc5280#section-5) by ensuring that
+///
+/// - the CRL is issued by the subject of the DER encoded `issuer` certificate
+/// - the CRL is signed by the public key contained in the `issuer` certificate
+///
+/// Checking the freshness of the returned [ValidatedCrl] is left to the caller.
+pub fn validate_crl(crl: &[u8], issuer: &[u8]) -> Result<ValidatedCrl, ValidationError> {
+    let issuer_cert = parse_cert(issuer)?;
+    let issuer_pbk = PublicKey::parse(&issuer_cert.tbs_certificate.subject_public_key_info)?;
+
+    let crl_payload = asn1::parse_single::<CertificateListRawPayload>(crl)?
+        .tbs_cert_list
+        .full_data();
+    let crl = asn1::parse_single::<CertificateList>(crl)?;
+    if crl.signature_algorithm.algorithm != crl.tbs_cert_list.signature.algorithm {
+        return Err(ValidationError::SignatureMismatch);
+    }
+    let crl_issuer = asn1::write_single(&crl.tbs_cert_list.issuer)
+        .map_err(|_| ValidationError::InvalidIssuer)?;
+    let issuer_subject = asn1::write_single(&issuer_cert.tbs_certificate.subject)
+        .map_err(|_| ValidationError::InvalidIssuer)?;
+    if crl_issuer != issuer_subject {
+        return Err(ValidationError::InvalidIssuer);
+    }
+
+    validate_signature(
+        &crl.signature_algorithm.algorithm,
+        crl_payload,
+        &crl.signature_value,
+        &issuer_pbk,
+    )?;
+
+    let serial_numbers = crl
+        .tbs_cert_list
+        .revoked_certificates
+        .map(|revoked| {
+            revoked
+                .map(|entry| entry.user_certificate.as_bytes().to_vec())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    Ok(ValidatedCrl {
+        this_update: crl.tbs_cert_list.this_update.timestamp_millis(),
+        next_update: crl
+            .tbs_cert_list
+            .next_update
+            .map(|next_update| next_update.timestamp_millis()),
+        serial_numbers,
+    })
+}
+
+/// The public key of Google's hardware attestation root, trusted by default.
+pub const GOOGLE_ROOT_PUB_KEY: &'static [u8] = include_bytes!("./__root_key__/public.key");
 
 #[cfg(test)]
 mod tests {
@@ -328,6 +416,7 @@ mod tests {
 
     use super::{
         asn::KeyDescription, validate_certificate_chain, CertificateChainInput, CertificateInput,
+        GOOGLE_ROOT_PUB_KEY,
     };
 
     pub fn decode_certificate_chain(chain: &Vec<&str>) -> CertificateChainInput {
@@ -372,7 +461,7 @@ mod tests {
             SAMSUNG_KEY_CERT,
         ];
         let decoded_chain = decode_certificate_chain(&chain);
-        let (_, cert, _) = validate_certificate_chain(&decoded_chain)?;
+        let (_, cert, _) = validate_certificate_chain(&decoded_chain, &[GOOGLE_ROOT_PUB_KEY])?;
         let key_description = extract_attestation(cert.extensions)?;
         match &key_description {
             KeyDescription::V100(key_description) => {
@@ -392,7 +481,7 @@ mod tests {
             hex_literal::hex!("308201fe30820185a00302010202106fd912a55cb7563380ddfa2ac2d1eb8c300a06082a8648ce3d040302303f31123010060355040c0c095374726f6e67426f7831293027060355040513203138346464346137643861373133316539386161653465613761663531306461301e170d3231303931353232353831375a170d3331303931333232353831375a303f31123010060355040c0c095374726f6e67426f78312930270603550405132037386235656561343461313465353437373261363862653665623738313462343059301306072a8648ce3d020106082a8648ce3d03010703420004df1afbe9c0d1fb4d291a6573de2cdf95ca9f43e4bd2f33ded358ca8118dd42ea619a9cdbccd4cc32335f5db51774bb5e9ccf08a2d6d3259572a27fe064fd451da3633061301d0603551d0e04160414f40c71002bcb6425158db7b4b552d09820d9e4c9301f0603551d2304183016801448e9cf69260a89c4c584499c6dbad846e5a266b3300f0603551d130101ff040530030101ff300e0603551d0f0101ff040403020204300a06082a8648ce3d0403020367003064023044f91fe977e5ddba9c69a4f0b0d9b7c550e8e117216d6768b3477e4277fb88e9ed1dcae08c538b5452f30f4aabd473c10230312efaac05f998263283e0b718025eaa224ee8e386b6f143b9781c789f3a38abc8480731325893d6efc25b67c473ecf8").to_vec().try_into().unwrap(),
             hex_literal::hex!("308202ba30820260a003020102020101300a06082a8648ce3d040302303f31123010060355040c0c095374726f6e67426f7831293027060355040513203738623565656134346131346535343737326136386265366562373831346234301e170d3730303130313030303030305a170d3439313233313233353935395a301f311d301b06035504031314416e64726f6964204b657973746f7265204b65793059301306072a8648ce3d020106082a8648ce3d03010703420004e203b4ed148733aca6322978ffb9d72dc940919d489d87ae5242cf6eb39b6ae8ab9edf67310b5b88e8ab5e82f7ec6cb7778ad00c480e4846aa6dbbde55303b43a382016b30820167300c0603551d0f0405030307880030820155060a2b06010401d67902011104820145308201410201640a01020201640a0102042052331675bfbad8106ff561287fcb0397307dc4ed8cccc3f7e2ace4a7fe82f19c04003065bf853d080206018c1a85f13ebf85455504533051312b30290424636f6d2e616375726173742e61747465737465642e6578656375746f722e63616e61727902011031220420ec70c2a4e072a0f586552a68357b23697c9d45f1e1257a8c4d29a25ac49824333081a7a10b3109020106020103020102a203020103a30402020100a5053103020100aa03020101bf8377020500bf853e03020100bf85404c304a0420c276f9fcf895a8838c8d6e6ec441494822e69acfca3bb27715790b2951da33980101ff0a010004209b51df228d989cd600b59e307b0bbc1d92013f969d4cebcc0ca3e1556bff6e7bbf854105020301fbd0bf854205020303163fbf854e0602040134b09dbf854f0602040134b09d300a06082a8648ce3d040302034800304502200229006e3a528c45224739b7773731c99ca811fa3ee57121626bbc9279fad3af022100bf47d355feae07bb6a4528e8872a8775248d4960477ce807cf4ee7ce902d42b9").to_vec().try_into().unwrap(),
         ].to_vec().try_into().unwrap();
-        let (_, cert, _) = validate_certificate_chain(&decoded_chain)?;
+        let (_, cert, _) = validate_certificate_chain(&decoded_chain, &[GOOGLE_ROOT_PUB_KEY])?;
         let key_description = extract_attestation(cert.extensions)?;
         match &key_description {
             KeyDescription::V100(key_description) => {
@@ -437,8 +526,8 @@ mod tests {
 
         for chain in chains {
             let decoded_chain = decode_certificate_chain(&chain);
-            let (_, cert, _) =
-                validate_certificate_chain(&decoded_chain).expect("validating chain failed");
+            let (_, cert, _) = validate_certificate_chain(&decoded_chain, &[GOOGLE_ROOT_PUB_KEY])
+                .expect("validating chain failed");
             let key_description = extract_attestation(cert.extensions).map_err(|err| {
                 dbg!(err.clone());
 
@@ -474,7 +563,7 @@ mod tests {
             PIXEL_KEY_CERT_INVALID,
         ];
         let decoded_chain = decode_certificate_chain(&chain);
-        let res = validate_certificate_chain(&decoded_chain);
+        let res = validate_certificate_chain(&decoded_chain, &[GOOGLE_ROOT_PUB_KEY]);
         match res {
             Err(e) => assert_eq!(e, ValidationError::InvalidSignature),
             _ => return Err(()),
@@ -491,7 +580,7 @@ mod tests {
             PIXEL_KEY_CERT_INVALID,
         ];
         let decoded_chain = decode_certificate_chain(&chain);
-        let res = validate_certificate_chain(&decoded_chain);
+        let res = validate_certificate_chain(&decoded_chain, &[GOOGLE_ROOT_PUB_KEY]);
         match res {
             Err(e) => assert_eq!(e, ValidationError::InvalidSignature),
             _ => return Err(()),
@@ -144,6 +144,46 @@ pub struct Extension<'a> {
     pub extn_value: &'a [u8],
 }
 
+#[derive(Asn1Read, Asn1Write)]
+/// Represents the root structure of a [X.509 v2 CRL](https://www.rfc-editor.org/rfc/rfc5280#section-5.1)
+pub struct CertificateList<'a> {
+    pub tbs_cert_list: TBSCertList<'a>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature_value: BitString<'a>,
+}
+
+#[derive(Asn1Read, Asn1Write)]
+/// As CertificateList, represents the root structure of a [X.509 v2 CRL](https://www.rfc-editor.org/rfc/rfc5280#section-5.1).
+/// This version does not decode the payload.
+pub struct CertificateListRawPayload<'a> {
+    pub tbs_cert_list: Tlv<'a>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature_value: BitString<'a>,
+}
+
+#[derive(Asn1Read, Asn1Write)]
+/// [See RFC](https://www.rfc-editor.org/rfc/rfc5280#section-5.1.2)
+pub struct TBSCertList<'a> {
+    // If present, version MUST be v2
+    pub version: Option<u64>,
+    pub signature: AlgorithmIdentifier<'a>,
+    pub issuer: Name<'a>,
+    pub this_update: Time,
+    pub next_update: Option<Time>,
+    pub revoked_certificates: Option<SequenceOf<'a, RevokedCertificate<'a>>>,
+    // If present, version MUST be v2
+    #[explicit(0)]
+    pub crl_extensions: Option<SequenceOf<'a, Extension<'a>>>,
+}
+
+#[derive(Asn1Read, Asn1Write)]
+pub struct RevokedCertificate<'a> {
+    pub user_certificate: asn1::BigUint<'a>,
+    pub revocation_date: Time,
+    // If present, version MUST be v2
+    pub crl_entry_extensions: Option<SequenceOf<'a, Extension<'a>>>,
+}
+
 #[derive(Asn1Read, Asn1Write)]
 pub struct KeyDescriptionV1<'a> {
     /// The [version](https://developer.android.com/training/articles/security-key-attestation#certificate_schema) of the attestation.
@@ -1,6 +1,11 @@
 #![cfg(test)]
 
-use crate::Schedule;
+use codec::{Decode, Encode};
+
+use crate::{
+    derive_job_id, foreign_job_sequence, EthereumAddressBytes, JobId, MultiOrigin, Schedule,
+    TezosAddressBytes,
+};
 
 macro_rules! tests {
     ($property_test_func:ident {
@@ -388,3 +393,83 @@ tests! {
         );
     }
 }
+
+/// One origin per [MultiOrigin] variant, with the Acurast and AlephZero origins sharing the same account.
+///
+/// The exhaustive match fails to compile once a variant is added, so that it gets covered here.
+fn all_origins() -> Vec<MultiOrigin<[u8; 32]>> {
+    let origins = vec![
+        MultiOrigin::Acurast([1; 32]),
+        MultiOrigin::Tezos(TezosAddressBytes::truncate_from(vec![1; 36])),
+        MultiOrigin::Ethereum(EthereumAddressBytes::truncate_from(vec![1; 20])),
+        MultiOrigin::AlephZero([1; 32]),
+    ];
+    for origin in origins.iter() {
+        match origin {
+            MultiOrigin::Acurast(_)
+            | MultiOrigin::Tezos(_)
+            | MultiOrigin::Ethereum(_)
+            | MultiOrigin::AlephZero(_) => {}
+        }
+    }
+    origins
+}
+
+#[test]
+fn test_derive_job_id_without_collisions() {
+    let origins = all_origins();
+    let sequences = [
+        0u128,
+        1,
+        2,
+        u64::MAX as u128,
+        u64::MAX as u128 + 1,
+        u128::MAX,
+    ];
+
+    let mut job_ids: Vec<JobId<[u8; 32]>> = vec![];
+    for origin in origins.iter() {
+        for seq in sequences {
+            let job_id = derive_job_id(origin, seq);
+            assert!(!job_ids.contains(&job_id));
+            // distinct job ids also encode distinctly, as used for storage keys
+            assert!(!job_ids
+                .iter()
+                .any(|other| other.encode() == job_id.encode()));
+            job_ids.push(job_id);
+        }
+    }
+    assert_eq!(job_ids.len(), origins.len() * sequences.len());
+}
+
+#[test]
+fn test_derive_job_id_round_trip() {
+    let origins = all_origins();
+    for origin in origins.iter() {
+        for seq in [0u64, 1, u64::MAX] {
+            let job_id = derive_job_id(origin, seq);
+            assert_eq!(job_id, (origin.clone(), seq as u128));
+
+            let decoded = JobId::<[u8; 32]>::decode(&mut job_id.encode().as_slice()).unwrap();
+            assert_eq!(decoded, job_id);
+            assert_eq!(foreign_job_sequence(&decoded, origin), Some(seq as u128));
+
+            // the job id is not attributed to any other origin
+            for other in origins.iter().filter(|other| *other != origin) {
+                assert_eq!(foreign_job_sequence(&decoded, other), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_derive_job_id_max_u64_sequence() {
+    let origin = MultiOrigin::Tezos(TezosAddressBytes::truncate_from(vec![1; 36]));
+    let job_id = derive_job_id(&origin, u64::MAX);
+    assert_eq!(job_id.1, 18_446_744_073_709_551_615u128);
+    assert_ne!(job_id, derive_job_id(&origin, u64::MAX as u128 + 1));
+    assert_eq!(
+        u64::try_from(foreign_job_sequence(&job_id, &origin).unwrap()),
+        Ok(u64::MAX)
+    );
+}
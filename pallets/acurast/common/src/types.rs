@@ -58,6 +58,25 @@ pub type JobIdSequence = u128;
 /// A Job ID consists of a [MultiOrigin] and a job identifier respective to the source chain.
 pub type JobId<AcurastAccountId> = (MultiOrigin<AcurastAccountId>, JobIdSequence);
 
+/// Derives the [JobId] of a job registered on the `origin` chain under the origin chain's job identifier `foreign_seq`.
+///
+/// This is the canonical mapping used for jobs registered through hyperdrive: the origin chain's identifier is kept unchanged
+/// as [JobIdSequence], so distinct `(origin, foreign_seq)` pairs always derive distinct job ids.
+pub fn derive_job_id<AcurastAccountId: Clone>(
+    origin: &MultiOrigin<AcurastAccountId>,
+    foreign_seq: impl Into<JobIdSequence>,
+) -> JobId<AcurastAccountId> {
+    (origin.clone(), foreign_seq.into())
+}
+
+/// Returns the origin chain's job identifier of `job_id` if it was derived for `origin`, the inverse of [derive_job_id].
+pub fn foreign_job_sequence<AcurastAccountId: PartialEq>(
+    job_id: &JobId<AcurastAccountId>,
+    origin: &MultiOrigin<AcurastAccountId>,
+) -> Option<JobIdSequence> {
+    (&job_id.0 == origin).then_some(job_id.1)
+}
+
 /// The allowed sources update operation.
 #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Copy)]
 pub enum ListUpdateOperation {
@@ -96,7 +115,7 @@ pub struct JobRegistration<AccountId, MaxAllowedSources: Get<u32>, Extra> {
     pub schedule: Schedule,
     /// Maximum memory bytes used during a single execution of the job.
     pub memory: u32,
-    /// Maximum network request used during a single execution of the job.
+    /// Maximum network requests performed during a single execution of the job (not per second).
     pub network_requests: u32,
     /// Maximum storage bytes used during the whole period of the job's executions.
     pub storage: u32,
@@ -106,6 +125,99 @@ pub struct JobRegistration<AccountId, MaxAllowedSources: Get<u32>, Extra> {
     pub extra: Extra,
 }
 
+impl<AccountId, MaxAllowedSources: Get<u32>, Extra>
+    JobRegistration<AccountId, MaxAllowedSources, Extra>
+{
+    /// Splits the registration into the parts stored separately.
+    pub fn split(
+        self,
+    ) -> (
+        JobRegistrationCore<Extra>,
+        JobPayload<AccountId, MaxAllowedSources>,
+    ) {
+        (
+            JobRegistrationCore {
+                allow_only_verified_sources: self.allow_only_verified_sources,
+                schedule: self.schedule,
+                memory: self.memory,
+                network_requests: self.network_requests,
+                storage: self.storage,
+                required_modules: self.required_modules,
+                extra: self.extra,
+            },
+            JobPayload {
+                script: self.script,
+                allowed_sources: self.allowed_sources,
+            },
+        )
+    }
+
+    /// Joins the parts stored separately into the registration.
+    pub fn join(
+        core: JobRegistrationCore<Extra>,
+        payload: JobPayload<AccountId, MaxAllowedSources>,
+    ) -> Self {
+        Self {
+            script: payload.script,
+            allowed_sources: payload.allowed_sources,
+            allow_only_verified_sources: core.allow_only_verified_sources,
+            schedule: core.schedule,
+            memory: core.memory,
+            network_requests: core.network_requests,
+            storage: core.storage,
+            required_modules: core.required_modules,
+            extra: core.extra,
+        }
+    }
+}
+
+/// The fields of a [JobRegistration] read to match, assign and settle the job.
+///
+/// Stored apart from the [JobPayload], so that reading a job's schedule or requirements does not pull its script
+/// and allowed sources into the proof.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+pub struct JobRegistrationCore<Extra> {
+    /// A boolean indicating if only verified sources can fulfill the job. A verified source is one that has provided a valid key attestation.
+    pub allow_only_verified_sources: bool,
+    /// The schedule describing the desired (multiple) execution(s) of the script.
+    pub schedule: Schedule,
+    /// Maximum memory bytes used during a single execution of the job.
+    pub memory: u32,
+    /// Maximum network requests performed during a single execution of the job (not per second).
+    pub network_requests: u32,
+    /// Maximum storage bytes used during the whole period of the job's executions.
+    pub storage: u32,
+    /// The modules required for the job.
+    pub required_modules: JobModules,
+    /// Extra parameters. This type can be configured through [Config::RegistrationExtra].
+    pub extra: Extra,
+}
+
+impl<AccountId, MaxAllowedSources: Get<u32>, Extra: Clone>
+    From<&JobRegistration<AccountId, MaxAllowedSources, Extra>> for JobRegistrationCore<Extra>
+{
+    fn from(registration: &JobRegistration<AccountId, MaxAllowedSources, Extra>) -> Self {
+        Self {
+            allow_only_verified_sources: registration.allow_only_verified_sources,
+            schedule: registration.schedule.clone(),
+            memory: registration.memory,
+            network_requests: registration.network_requests,
+            storage: registration.storage,
+            required_modules: registration.required_modules.clone(),
+            extra: registration.extra.clone(),
+        }
+    }
+}
+
+/// The fields of a [JobRegistration] only needed to run the job and to check its allowed sources.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+pub struct JobPayload<AccountId, MaxAllowedSources: Get<u32>> {
+    /// The script to execute. It is a vector of bytes representing a utf8 string. The string needs to be a ipfs url that points to the script.
+    pub script: Script,
+    /// An optional array of the [AccountId]s allowed to fulfill the job. If the array is [None], then all sources are allowed.
+    pub allowed_sources: Option<AllowedSources<AccountId, MaxAllowedSources>>,
+}
+
 pub const PUB_KEYS_MAX_LENGTH: u32 = 33;
 pub type PubKeyBytes = BoundedVec<u8, ConstU32<PUB_KEYS_MAX_LENGTH>>;
 
@@ -354,3 +466,54 @@ impl<'de, const T: u32> Deserialize<'de> for CU32<T> {
         Ok(CU32::<T>)
     }
 }
+
+/// The current version of [`AcurastLimits`].
+pub const ACURAST_LIMITS_VERSION: u32 = 1;
+
+/// The pallet constants and limits a processor needs to know at startup, aggregated from the Acurast pallets.
+///
+/// Every limit is optional so that runtimes not including one of the pallets can still provide the others.
+/// Changes have to be additive: new limits are appended as optional fields and bump [`ACURAST_LIMITS_VERSION`],
+/// existing fields are never removed or reordered, so that clients can decode all versions up to the one they know.
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AcurastLimits {
+    /// The [`ACURAST_LIMITS_VERSION`] the limits were created with.
+    pub version: u32,
+    /// The maximum number of allowed sources of a job registration.
+    pub max_allowed_sources: Option<u32>,
+    /// The maximum number of slots of a job registration.
+    pub max_slots: Option<u32>,
+    /// The maximum number of executions of a job.
+    pub max_executions_per_job: Option<u64>,
+    /// The tolerance in milliseconds by which the timestamp checked against the schedule of reports may be stale.
+    pub report_tolerance: Option<u64>,
+    /// The maximum number of matches that can be proposed at once.
+    pub max_proposed_matches: Option<u32>,
+    /// The time in milliseconds after which a pairing proof expires.
+    pub pairing_proof_expiration_time: Option<u128>,
+    /// The maximum number of pairing updates in a single call.
+    pub max_pairing_updates: Option<u32>,
+    /// The maximum time in milliseconds processors are expected to let pass between two heartbeats.
+    pub heartbeat_interval: Option<u128>,
+    /// The time in milliseconds after a processor's latest heartbeat before it can unpair itself.
+    pub unpair_cooldown: Option<u128>,
+}
+
+impl Default for AcurastLimits {
+    fn default() -> Self {
+        Self {
+            version: ACURAST_LIMITS_VERSION,
+            max_allowed_sources: None,
+            max_slots: None,
+            max_executions_per_job: None,
+            report_tolerance: None,
+            max_proposed_matches: None,
+            pairing_proof_expiration_time: None,
+            max_pairing_updates: None,
+            heartbeat_interval: None,
+            unpair_cooldown: None,
+        }
+    }
+}
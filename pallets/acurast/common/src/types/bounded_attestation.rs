@@ -55,6 +55,11 @@ pub struct Attestation {
     pub cert_ids: ValidatingCertIds,
     pub key_description: BoundedKeyDescription,
     pub validity: AttestationValidity,
+    /// The timestamp in milliseconds since Unix Epoch at which the attestation was submitted.
+    ///
+    /// [None] for attestations stored before the submission time was recorded and for genesis attestations.
+    #[serde(default)]
+    pub submitted_at: Option<u64>,
 }
 
 #[derive(
@@ -12,7 +12,7 @@ use sp_std::prelude::*;
 use crate::Config;
 use acurast_common::{AttestationChain, JobRegistration, Script};
 
-use crate::utils::validate_and_extract_attestation;
+use crate::utils::{application_id, validate_and_extract_attestation};
 use crate::Pallet as Acurast;
 
 use super::*;
@@ -22,6 +22,8 @@ pub const ROOT_CERT: [u8; 1380] = hex!("3082056030820348a003020102020900e8fa1963
 pub const INT_CERT_1: [u8; 987] = hex!("308203d7308201bfa003020102020a038826676065899685f5300d06092a864886f70d01010b0500301b311930170603550405131066393230303965383533623662303435301e170d3139303830393233303332335a170d3239303830363233303332335a302f31193017060355040513103534663539333730353432663561393531123010060355040c0c095374726f6e67426f783076301006072a8648ce3d020106052b8104002203620004e352276f9bfcea4301a5f0427fa6478e573209ae44fd762cfbc57cbbd4713631509e802ea0e940536e54fa2570ca2846154698075509293b3100b3955b4317768b286bf6fe2651c59af6c6b0db3360090a4647c7860e76ecc3b8a7db5ce57acca381b63081b3301d0603551d0e041604146990b10c3b088aee2af88c3387b42c12dadfc3a6301f0603551d230418301680143661e1007c880509518b446c47ff1a4cc9ea4f12300f0603551d130101ff040530030101ff300e0603551d0f0101ff04040302020430500603551d1f044930473045a043a041863f68747470733a2f2f616e64726f69642e676f6f676c65617069732e636f6d2f6174746573746174696f6e2f63726c2f38463637333443394641353034373839300d06092a864886f70d01010b050003820201005c591327a0b0249ecadc949184c9651ed1f2a617a17516439875429e9bd21f87fd2365d0dcde747022c19410f23ab380fe1cef0f47aebc443c2a4531df3eca4101bf96d6bc30dfd878ed6734653111b5e782a03350cc2605e128b48a57e7ff1fe4bf4104de3f7ca9ace6afb01bdd9205fa10b91837a337257afb8290afa456fa629cfae5477b172b009bf28d43dcd4d31edcbf3dc1b6fcfcca5c38a79773d38b5a9d3ccd8152d51f25f9900701d9fb4fbf1307e17fcf5ddc759409863d2f0fb2e6c24468c9c5d85154e104318cb10ae60ba27bb252080e072645681c39e560e8586a64550867162f4bde9db75645882cb9eaff4efe1b0a312f5bd40224298c91f135061b8e04e8fa4c618c33f7b942c028f00d18113bfb6e55a952ccb5d71ee046f9bfdc85aa083e26d94be354545954b70c812ac4e326fdf07703bb79e536d429ff1d099c81722d81714593c7c2bb56740ccbc801332bb548695e28f2c8ac1452a260cfe57f311adc132e8dda01d638f9a4a31288a623a917f5b6c87e1c8316927129a0d11f384251d2df26b942a76844ab91968f4953e7484f2ecd2d6e187f9772d3b4584ac986e2079bc75f20773f8814ba2d16c7266761d6a3505f939fc316efda8787085a5d4f479df944f9d061d2c99acce73ed31770659297113f94140500306887be1b88082b96b18e123cabfcffbd79b68782a0408748cbf4f02f42");
 pub const INT_CERT_2: [u8; 564] = hex!("30820230308201b7a003020102020a15905857467176635834300a06082a8648ce3d040302302f31193017060355040513103534663539333730353432663561393531123010060355040c0c095374726f6e67426f78301e170d3139303732373031353231395a170d3239303732343031353231395a302f31193017060355040513103937333533373739333664306464373431123010060355040c0c095374726f6e67426f783059301306072a8648ce3d020106082a8648ce3d030107034200047639963abb7d336b5f238d8b355efdb395a22b2ccde67bda24328e4bbf802fefa97f204dd8bdb450332cb5e566f759bdc6ffafb9f3bc78e3747dfce8278e5f02a381ba3081b7301d0603551d0e04160414413e3ca9b34bc7a51cbb0125c0421be651ad7ad8301f0603551d230418301680146990b10c3b088aee2af88c3387b42c12dadfc3a6300f0603551d130101ff040530030101ff300e0603551d0f0101ff04040302020430540603551d1f044d304b3049a047a045864368747470733a2f2f616e64726f69642e676f6f676c65617069732e636f6d2f6174746573746174696f6e2f63726c2f3135393035383537343637313736363335383334300a06082a8648ce3d0403020367003064023017a0df3880a22ea1d4b3dfbdb6c04a4e5655d0ba70bdc8a5ac483b270c1e6d520cda9800b3ad775bae8dfccc7a86ecf802302898f95f24867bb3112f440db5dad27769e42be7db8dc51cf0b2af55aa43c11002e340a24f3965032f9a3a7c83c6bbdb");
 pub const LEAF_CERT: [u8; 672] = hex!("3082029c30820241a003020102020101300c06082a8648ce3d0403020500302f31193017060355040513103937333533373739333664306464373431123010060355040c0c095374726f6e67426f783022180f32303232303730393130353135355a180f32303238303532333233353935395a301f311d301b06035504030c14416e64726f6964204b657973746f7265204b65793059301306072a8648ce3d020106082a8648ce3d03010703420004b20c1d15477662623ecf430104898006e0f81c0db1bae87cb96a87c7777404659e585d3d9057b8a2ff8ae61f401a078fc75cf52c8c4268e810f93798c729e862a382015630820152300e0603551d0f0101ff0404030207803082013e060a2b06010401d6790201110482012e3082012a0201040a01020201290a0102040874657374617364660400306cbf853d0802060181e296611fbf85455c045a305831323030042b636f6d2e7562696e657469632e61747465737465642e6578656375746f722e746573742e746573746e657402010e31220420bdcb4560f6b3c41dad920668169c28be1ef9ea49f23d98cd8eb2f37ae4488ff93081a1a1053103020102a203020103a30402020100a5053103020100aa03020101bf8377020500bf853e03020100bf85404c304a0420879cd3f18ea76e244d4d4ac3bcb9c337c13b4667190b19035afe2536550050f10101ff0a010004203f4136ee3581e6aba8ea337a6b43d703de1eca241f9b7f277ecdfafff7a8dcf1bf854105020301d4c0bf85420502030315debf854e06020401348abdbf854f06020401348abd300c06082a8648ce3d04030205000347003044022033a613cce9a6ed25026a492b651f0ac67c3c0289d4e4743168c6903e2faa0bda0220324cd35c4bf2695d71ad12a28868e69232112922eaf0e3699f6add8133d528d9");
+pub const CRL_ISSUER_CERT: [u8; 298] = hex!("308201263081cda003020102020101300a06082a8648ce3d040302301d311b301906035504030c12416375726173742043524c20497373756572301e170d3233303130313030303030305a170d3333303130313030303030305a301d311b301906035504030c12416375726173742043524c204973737565723059301306072a8648ce3d020106082a8648ce3d030107034200043302cbc90fca461321fab300c7ec096b7b7891deb038d62d8711ebced684718e5c196e3dcdec33c370f373400fcfcf19619952558594495812614af883470bc1300a06082a8648ce3d0403020348003045022100a3bf878b81d03211064ab5b47f23203238c79d8c5552f70470d3c33072df2b010220283a5f651b20096a7c9e40651183f1e063cea7f74ec930e5c35fb2cc58e55f82");
+pub const SIGNED_CRL: [u8; 226] = hex!("3081df308186020101300a06082a8648ce3d040302301d311b301906035504030c12416375726173742043524c20497373756572170d3233303730313030303030305a170d3233303830313030303030305a3038301b020a15905857467176635834170d3233303631353030303030305a301902080123456789abcdef170d3233303631353030303030305a300a06082a8648ce3d0403020348003045022100d1fbd3984b5c830504090d8eaf801d4242939bebe4e8ecc1191bac28e9b43c8a02207bc8ad513a4612ad739049c04a48bf77922862844a3300c516df1532383c1279");
 const SCRIPT_BYTES: [u8; 53] = hex!("697066733A2F2F00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
 
 pub trait BenchmarkHelper<T: Config> {
@@ -135,7 +137,7 @@ benchmarks! {
         }
         let local_job_id = 1;
         let updates: BoundedVec<AllowedSourcesUpdate<T::AccountId>, <T as Config>::MaxAllowedSources> = updates.try_into().unwrap();
-    }: _(RawOrigin::Signed(caller.clone()), local_job_id, updates.clone())
+    }: _(RawOrigin::Signed(caller.clone()), local_job_id, updates.clone(), false)
     verify {
         assert_last_event::<T>(Event::AllowedSourcesUpdated(
             (MultiOrigin::Acurast(caller), 1), job, updates
@@ -151,9 +153,11 @@ benchmarks! {
     }: _(RawOrigin::Signed(processor_account.clone()), attestation_chain.clone())
     verify {
         let attestation = validate_and_extract_attestation::<T>(&processor_account, &attestation_chain).unwrap();
+        let application_id = application_id(&attestation);
         assert_last_event::<T>(Event::AttestationStored(
             attestation,
             processor_account,
+            application_id,
         ).into());
     }
 
@@ -173,6 +177,61 @@ benchmarks! {
         ).into());
     }
 
+    set_crl_issuer {
+        let issuer: CertificateInput = CRL_ISSUER_CERT.to_vec().try_into().unwrap();
+    }: _(RawOrigin::Root, issuer.clone())
+    verify {
+        assert_last_event::<T>(Event::CrlIssuerUpdated(issuer).into());
+    }
+
+    submit_signed_crl {
+        let caller: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0);
+        whitelist_account!(caller);
+        assert_ok!(Acurast::<T>::set_crl_issuer(RawOrigin::Root.into(), CRL_ISSUER_CERT.to_vec().try_into().unwrap()));
+        let timestamp_call = pallet_timestamp::Pallet::<T>::set(T::RuntimeOrigin::none(), 1688169600001u64.into());
+        assert_ok!(timestamp_call);
+        let crl: CrlInput = SIGNED_CRL.to_vec().try_into().unwrap();
+    }: _(RawOrigin::Signed(caller), crl)
+    verify {
+        assert_eq!(Acurast::<T>::last_crl_update(), 1688169600000);
+    }
+
+    update_allowed_application_ids {
+        let x in 0 .. T::MaxAllowedAppIds::get();
+        let application_ids: AllowedApplicationIdsFor<T> = BoundedVec::truncate_from((0..x)
+            .map(|_| AllowedApplicationId {
+                package_name: BoundedVec::truncate_from(vec![0u8; T::MaxAppIdLen::get() as usize]),
+                signer_digest: BoundedVec::truncate_from(vec![0u8; 32]),
+            })
+            .collect());
+    }: _(RawOrigin::Root, application_ids.clone())
+    verify {
+        assert_last_event::<T>(Event::AllowedApplicationIdsUpdated(application_ids).into());
+    }
+
+    add_trusted_root {
+        let roots = (1..T::MaxTrustedRoots::get())
+            .map(|i| BoundedVec::truncate_from(vec![i as u8; TRUSTED_ROOT_MAX_LENGTH as usize]))
+            .collect::<Vec<_>>();
+        <TrustedAttestationRoots<T>>::put(TrustedAttestationRootsFor::<T>::truncate_from(roots));
+        let root: TrustedRoot = GOOGLE_ROOT_PUB_KEY.to_vec().try_into().unwrap();
+    }: _(RawOrigin::Root, root.clone())
+    verify {
+        assert_last_event::<T>(Event::TrustedAttestationRootAdded(root).into());
+    }
+
+    remove_trusted_root {
+        let root: TrustedRoot = GOOGLE_ROOT_PUB_KEY.to_vec().try_into().unwrap();
+        let mut roots = (1..T::MaxTrustedRoots::get())
+            .map(|i| BoundedVec::truncate_from(vec![i as u8; TRUSTED_ROOT_MAX_LENGTH as usize]))
+            .collect::<Vec<_>>();
+        roots.push(root.clone());
+        <TrustedAttestationRoots<T>>::put(TrustedAttestationRootsFor::<T>::truncate_from(roots));
+    }: _(RawOrigin::Root, root.clone())
+    verify {
+        assert_last_event::<T>(Event::TrustedAttestationRootRemoved(root).into());
+    }
+
     set_environment {
         let x in 1 .. T::MaxEnvVars::get();
         let (caller, job) = register_job::<T>(true, false);
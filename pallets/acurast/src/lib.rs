@@ -9,6 +9,8 @@ mod tests;
 mod benchmarking;
 
 mod migration;
+#[cfg(feature = "std")]
+pub mod rpc;
 mod traits;
 pub mod utils;
 pub mod weights;
@@ -25,12 +27,43 @@ pub type JobRegistrationFor<T> = JobRegistration<
     <T as Config>::RegistrationExtra,
 >;
 
+pub type JobRegistrationCoreFor<T> = JobRegistrationCore<<T as Config>::RegistrationExtra>;
+
+pub type JobPayloadFor<T> =
+    JobPayload<<T as frame_system::Config>::AccountId, <T as Config>::MaxAllowedSources>;
+
 pub type EnvironmentFor<T> = Environment<
     <T as Config>::MaxEnvVars,
     <T as Config>::EnvKeyMaxSize,
     <T as Config>::EnvValueMaxSize,
 >;
 
+/// An application attestations can be issued for, see [`AllowedApplicationIds`].
+#[derive(
+    frame_support::RuntimeDebug,
+    codec::Encode,
+    codec::Decode,
+    codec::MaxEncodedLen,
+    scale_info::TypeInfo,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct AllowedApplicationId<MaxAppIdLen: ParameterBound> {
+    /// The package name of the application.
+    pub package_name: frame_support::BoundedVec<u8, MaxAppIdLen>,
+    /// The digest of the certificate the application has to be signed with.
+    pub signer_digest: Digest,
+}
+
+pub type AllowedApplicationIdsFor<T> = frame_support::BoundedVec<
+    AllowedApplicationId<<T as Config>::MaxAppIdLen>,
+    <T as Config>::MaxAllowedAppIds,
+>;
+
+pub type TrustedAttestationRootsFor<T> =
+    frame_support::BoundedVec<acurast_common::TrustedRoot, <T as Config>::MaxTrustedRoots>;
+
 #[frame_support::pallet]
 pub mod pallet {
     #[cfg(feature = "runtime-benchmarks")]
@@ -39,13 +72,16 @@ pub mod pallet {
     use core::ops::AddAssign;
     use frame_support::sp_runtime;
     use frame_support::{
-        dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*, traits::UnixTime,
-        Blake2_128Concat, PalletId,
+        dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*,
+        storage::with_storage_layer, traits::UnixTime, Blake2_128Concat, PalletId,
     };
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
 
-    use crate::{traits::*, utils::*, EnvironmentFor, JobRegistrationFor};
+    use crate::{
+        traits::*, utils::*, AllowedApplicationIdsFor, EnvironmentFor, JobPayloadFor,
+        JobRegistrationCoreFor, JobRegistrationFor, TrustedAttestationRootsFor,
+    };
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -57,6 +93,15 @@ pub mod pallet {
         type MaxAllowedSources: Get<u32> + ParameterBound;
         #[pallet::constant]
         type MaxCertificateRevocationListUpdates: Get<u32>;
+        /// The maximum length of an application id in [`AllowedApplicationIds`].
+        #[pallet::constant]
+        type MaxAppIdLen: Get<u32> + ParameterBound;
+        /// The maximum number of application ids in [`AllowedApplicationIds`].
+        #[pallet::constant]
+        type MaxAllowedAppIds: Get<u32>;
+        /// The maximum number of root keys in [`TrustedAttestationRoots`].
+        #[pallet::constant]
+        type MaxTrustedRoots: Get<u32>;
         /// The maximum allowed slots and therefore maximum length of the planned executions per job.
         #[pallet::constant]
         type MaxSlots: Get<u32> + ParameterBound;
@@ -92,12 +137,20 @@ pub mod pallet {
         ///
         /// This should only be used for test runtime configurations.
         pub attestations: Vec<(T::AccountId, Option<Attestation>)>,
+        /// The public keys of the attestation roots initially in [`TrustedAttestationRoots`].
+        ///
+        /// Defaults to Google's hardware attestation root, test networks can use their own test roots instead.
+        pub trusted_roots: Vec<TrustedRoot>,
     }
 
     impl<T: Config> Default for GenesisConfig<T> {
         fn default() -> Self {
             Self {
                 attestations: vec![],
+                trusted_roots: vec![GOOGLE_ROOT_PUB_KEY
+                    .to_vec()
+                    .try_into()
+                    .expect("Google root public key fits TRUSTED_ROOT_MAX_LENGTH")],
             }
         }
     }
@@ -105,6 +158,13 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
         fn build(&self) {
+            let trusted_roots: TrustedAttestationRootsFor<T> = self
+                .trusted_roots
+                .clone()
+                .try_into()
+                .expect("Genesis trusted roots exceed MaxTrustedRoots");
+            <TrustedAttestationRoots<T>>::put(trusted_roots);
+
             for (who, attestation) in self.attestations.clone() {
                 <StoredAttestation<T>>::insert(
                     &who,
@@ -202,13 +262,14 @@ pub mod pallet {
                             not_before: 0,
                             not_after: 4_102_441_200_000, // 1.1.2100
                         },
+                        submitted_at: None,
                     }),
                 );
             }
         }
     }
 
-    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -219,16 +280,29 @@ pub mod pallet {
     #[pallet::getter(fn job_id_sequence)]
     pub type LocalJobIdSequence<T: Config> = StorageValue<_, JobIdSequence, ValueQuery>;
 
-    /// The storage for [JobRegistration]s. They are stored by the origin chain address and job identifier.
+    /// The storage for the [JobRegistrationCore]s of [JobRegistration]s. They are stored by the origin chain address and job identifier.
+    ///
+    /// Use [`Pallet::stored_job_registration`] to read the full registration.
     #[pallet::storage]
-    #[pallet::getter(fn stored_job_registration)]
     pub type StoredJobRegistration<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         MultiOrigin<T::AccountId>,
         Blake2_128Concat,
         JobIdSequence,
-        JobRegistrationFor<T>,
+        JobRegistrationCoreFor<T>,
+    >;
+
+    /// The storage for the [JobPayload]s of [JobRegistration]s, with the same keys as [StoredJobRegistration].
+    #[pallet::storage]
+    #[pallet::getter(fn stored_job_payload)]
+    pub type StoredJobPayload<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MultiOrigin<T::AccountId>,
+        Blake2_128Concat,
+        JobIdSequence,
+        JobPayloadFor<T>,
     >;
 
     /// Env variables as a map [`JobId`] -> [`AccountId`] `(source)` -> [`EnvVars`].
@@ -255,6 +329,31 @@ pub mod pallet {
     pub type StoredRevokedCertificate<T: Config> =
         StorageMap<_, Blake2_128Concat, SerialNumber, ()>;
 
+    /// The certificate of the authority whose signed CRLs are accepted by [Pallet::submit_signed_crl].
+    #[pallet::storage]
+    #[pallet::getter(fn crl_issuer)]
+    pub type CrlIssuer<T: Config> = StorageValue<_, CertificateInput>;
+
+    /// The `thisUpdate` timestamp of the last CRL applied with [Pallet::submit_signed_crl], in milliseconds.
+    #[pallet::storage]
+    #[pallet::getter(fn last_crl_update)]
+    pub type LastCrlUpdate<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// The applications attestations have to be issued for to be accepted by [Pallet::submit_attestation].
+    ///
+    /// An attestation is accepted if one of its package names is allowed and the application is signed with the
+    /// certificate allowed for that package name. An empty list accepts attestations for any application.
+    #[pallet::storage]
+    #[pallet::getter(fn allowed_application_ids)]
+    pub type AllowedApplicationIds<T: Config> =
+        StorageValue<_, AllowedApplicationIdsFor<T>, ValueQuery>;
+
+    /// The public keys of the root certificates attestation chains submitted with [Pallet::submit_attestation] have to start with.
+    #[pallet::storage]
+    #[pallet::getter(fn trusted_attestation_roots)]
+    pub type TrustedAttestationRoots<T: Config> =
+        StorageValue<_, TrustedAttestationRootsFor<T>, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -268,8 +367,8 @@ pub mod pallet {
             JobRegistrationFor<T>,
             BoundedVec<AllowedSourcesUpdate<T::AccountId>, <T as Config>::MaxAllowedSources>,
         ),
-        /// An attestation was successfully stored. [attestation, who]
-        AttestationStored(Attestation, T::AccountId),
+        /// An attestation was successfully stored. [attestation, who, application_id]
+        AttestationStored(Attestation, T::AccountId, Option<PackageName>),
         /// The certificate revocation list has been updated. [who, updates]
         CertificateRecovationListUpdated(
             T::AccountId,
@@ -277,6 +376,14 @@ pub mod pallet {
         ),
         /// The execution environment has been updated. [job_id, source]
         ExecutionEnvironmentUpdated(JobId<T::AccountId>, T::AccountId),
+        /// The CRL issuer certificate has been updated. [issuer]
+        CrlIssuerUpdated(CertificateInput),
+        /// The allowed application ids have been updated. [application_ids]
+        AllowedApplicationIdsUpdated(AllowedApplicationIdsFor<T>),
+        /// A root was added to the trusted attestation roots. [root]
+        TrustedAttestationRootAdded(TrustedRoot),
+        /// A root was removed from the trusted attestation roots. [root]
+        TrustedAttestationRootRemoved(TrustedRoot),
     }
 
     #[pallet::error]
@@ -289,7 +396,7 @@ pub mod pallet {
         FulfillSourceNotVerified,
         /// The allowed soruces list for a registration exeeded the max length.
         TooManyAllowedSources,
-        /// The allowed soruces list for a registration cannot be empty if provided.
+        /// The allowed soruces list for a registration cannot be empty if provided, nor be emptied by an update without `clear`.
         TooFewAllowedSources,
         /// The provided script value is not valid. The value needs to be and ipfs:// url.
         InvalidScriptValue,
@@ -327,6 +434,30 @@ pub mod pallet {
         AttestationPublicKeyDoesNotMatchSource,
         /// Calling a job hook produced an error.
         JobHookFailed,
+        /// No CRL issuer certificate has been configured with [Pallet::set_crl_issuer].
+        CrlIssuerNotConfigured,
+        /// The submitted CRL could not be parsed or is not signed by the configured CRL issuer.
+        CrlValidationFailed,
+        /// The `thisUpdate` of the submitted CRL lies in the future.
+        CrlNotYetValid,
+        /// The `nextUpdate` of the submitted CRL lies in the past or is missing.
+        CrlExpired,
+        /// The submitted CRL is not newer than the last applied CRL.
+        CrlOutdated,
+        /// The submitted CRL contains more entries than [Config::MaxCertificateRevocationListUpdates].
+        TooManyCrlEntries,
+        /// Cannot get the serial number of a CRL entry.
+        CannotGetCrlSerialNumber,
+        /// The attestation was not issued for one of the [AllowedApplicationIds].
+        ApplicationIdNotAllowed,
+        /// The trusted attestation root is not a DER encoded public key supported for attestation roots.
+        InvalidTrustedRoot,
+        /// The root is already in [TrustedAttestationRoots].
+        TrustedRootAlreadyAdded,
+        /// The root is not in [TrustedAttestationRoots].
+        TrustedRootNotFound,
+        /// Adding the root would exceed [Config::MaxTrustedRoots].
+        TooManyTrustedRoots,
     }
 
     #[pallet::hooks]
@@ -334,6 +465,23 @@ pub mod pallet {
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
             crate::migration::migrate::<T>()
         }
+
+        fn integrity_test() {
+            // empty allowed sources are rejected, so the bound has to allow at least one source
+            assert!(
+                T::MaxAllowedSources::get() > 0,
+                "MaxAllowedSources must allow at least one source"
+            );
+            assert!(
+                T::MaxSlots::get() > 0,
+                "MaxSlots must allow at least one slot"
+            );
+            // without any trusted root no attestation can be submitted
+            assert!(
+                T::MaxTrustedRoots::get() > 0,
+                "MaxTrustedRoots must allow at least one root"
+            );
+        }
     }
 
     #[pallet::call]
@@ -366,6 +514,9 @@ pub mod pallet {
         }
 
         /// Updates the allowed sources list of a [JobRegistration].
+        ///
+        /// An update removing all allowed sources of a job opens it to all sources. Since this widens who can fulfill the job,
+        /// it fails with [Error::TooFewAllowedSources] unless `clear` is set to explicitly confirm it.
         #[pallet::call_index(2)]
         #[pallet::weight(< T as Config >::WeightInfo::update_allowed_sources(updates.len() as u32))]
         pub fn update_allowed_sources(
@@ -375,11 +526,12 @@ pub mod pallet {
                 AllowedSourcesUpdate<T::AccountId>,
                 <T as Config>::MaxAllowedSources,
             >,
+            clear: bool,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let multi_origin = MultiOrigin::Acurast(who.clone());
             let job_id: JobId<T::AccountId> = (multi_origin, local_job_id);
-            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+            let registration = Self::stored_job_registration(&job_id.0, job_id.1)
                 .ok_or(Error::<T>::JobRegistrationNotFound)?;
 
             let mut current_allowed_sources = registration
@@ -402,19 +554,25 @@ pub mod pallet {
                 }
             }
             let allowed_sources = if current_allowed_sources.is_empty() {
+                // a job already open to all sources stays open, otherwise opening it has to be confirmed
+                ensure!(
+                    clear || registration.allowed_sources.is_none(),
+                    Error::<T>::TooFewAllowedSources
+                );
                 None
             } else {
+                Self::ensure_valid_allowed_sources(&current_allowed_sources)?;
                 Some(
                     AllowedSources::try_from(current_allowed_sources)
                         .map_err(|_| Error::<T>::TooManyAllowedSources)?,
                 )
             };
-            <StoredJobRegistration<T>>::insert(
+            <StoredJobPayload<T>>::insert(
                 &job_id.0,
                 &job_id.1,
-                JobRegistration {
+                JobPayload {
+                    script: registration.script.clone(),
                     allowed_sources,
-                    ..registration.clone()
                 },
             );
 
@@ -428,7 +586,8 @@ pub mod pallet {
         /// Submits an attestation given a valid certificate chain.
         ///
         /// - As input a list of binary certificates is expected.
-        /// - The list must be ordered, starting from one of the known [trusted root certificates](https://developer.android.com/training/articles/security-key-attestation#root_certificate).
+        /// - The list must be ordered, starting from a root certificate whose public key is in [TrustedAttestationRoots], by default
+        ///   Google's [hardware attestation root](https://developer.android.com/training/articles/security-key-attestation#root_certificate).
         /// - If the represented chain is valid, the [Attestation] details are stored. An existing attestion for signing account gets overwritten.
         ///
         /// Revocation: Each atttestation is stored with the unique IDs of the certificates on the chain proofing the attestation's validity.
@@ -445,6 +604,7 @@ pub mod pallet {
             );
 
             let attestation = validate_and_extract_attestation::<T>(&who, &attestation_chain)?;
+            let application_id = ensure_application_id_allowed::<T>(&attestation)?;
 
             if !T::KeyAttestationBarrier::accept_attestation_for_origin(&who, &attestation) {
                 #[cfg(not(feature = "runtime-benchmarks"))]
@@ -455,7 +615,7 @@ pub mod pallet {
             ensure_not_revoked::<T>(&attestation)?;
 
             <StoredAttestation<T>>::insert(&who, attestation.clone());
-            Self::deposit_event(Event::AttestationStored(attestation, who));
+            Self::deposit_event(Event::AttestationStored(attestation, who, application_id));
             Ok(().into())
         }
 
@@ -529,9 +689,127 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Sets the certificate of the authority whose signed CRLs are accepted by [Pallet::submit_signed_crl].
+        ///
+        /// Resets the freshness tracking, so the next CRL signed by the new issuer is accepted regardless of previously applied CRLs.
+        #[pallet::weight(<T as Config>::WeightInfo::set_crl_issuer())]
+        #[pallet::call_index(9)]
+        pub fn set_crl_issuer(
+            origin: OriginFor<T>,
+            issuer: CertificateInput,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            <CrlIssuer<T>>::put(&issuer);
+            <LastCrlUpdate<T>>::kill();
+            Self::deposit_event(Event::CrlIssuerUpdated(issuer));
+            Ok(().into())
+        }
+
+        /// Updates the certificate revocation list from a DER encoded X.509 CRL signed by the [CrlIssuer].
+        ///
+        /// The CRL has to be currently valid according to its `thisUpdate` and `nextUpdate` and newer than the last applied CRL.
+        /// All contained serial numbers are added to the revocation list. Since the CRL's signature is verified on chain, any
+        /// signed origin can submit it. [Pallet::update_certificate_revocation_list] remains available for emergencies.
+        #[pallet::weight(<T as Config>::WeightInfo::submit_signed_crl().saturating_add(T::DbWeight::get().writes(T::MaxCertificateRevocationListUpdates::get() as u64)))]
+        #[pallet::call_index(10)]
+        pub fn submit_signed_crl(
+            origin: OriginFor<T>,
+            crl: CrlInput,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let (this_update, serial_numbers) = validate_signed_crl::<T>(&crl)?;
+            let updates: BoundedVec<
+                CertificateRevocationListUpdate,
+                T::MaxCertificateRevocationListUpdates,
+            > = serial_numbers
+                .into_iter()
+                .map(|item| CertificateRevocationListUpdate {
+                    operation: ListUpdateOperation::Add,
+                    item,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyCrlEntries)?;
+            for update in &updates {
+                <StoredRevokedCertificate<T>>::insert(&update.item, ());
+            }
+            <LastCrlUpdate<T>>::put(this_update);
+            let actual_weight = <T as Config>::WeightInfo::submit_signed_crl()
+                .saturating_add(T::DbWeight::get().writes(updates.len() as u64));
+            Self::deposit_event(Event::CertificateRecovationListUpdated(who, updates));
+            Ok(Some(actual_weight).into())
+        }
+
+        /// Replaces the applications (package names and signer certificate digests) attestations have to be issued for to be accepted by [Pallet::submit_attestation].
+        ///
+        /// Passing an empty list accepts attestations for any application. Already stored attestations are not affected.
+        #[pallet::weight(<T as Config>::WeightInfo::update_allowed_application_ids(application_ids.len() as u32))]
+        #[pallet::call_index(11)]
+        pub fn update_allowed_application_ids(
+            origin: OriginFor<T>,
+            application_ids: AllowedApplicationIdsFor<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            <AllowedApplicationIds<T>>::put(&application_ids);
+            Self::deposit_event(Event::AllowedApplicationIdsUpdated(application_ids));
+            Ok(().into())
+        }
+
+        /// Adds the DER encoded `SubjectPublicKeyInfo` of a root certificate to the [TrustedAttestationRoots].
+        ///
+        /// Attestation chains starting with a root certificate with this public key are accepted by [Pallet::submit_attestation].
+        #[pallet::weight(<T as Config>::WeightInfo::add_trusted_root())]
+        #[pallet::call_index(12)]
+        pub fn add_trusted_root(
+            origin: OriginFor<T>,
+            root: TrustedRoot,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            parse_trusted_root(&root).map_err(|_| Error::<T>::InvalidTrustedRoot)?;
+            <TrustedAttestationRoots<T>>::try_mutate(|roots| -> Result<(), Error<T>> {
+                ensure!(!roots.contains(&root), Error::<T>::TrustedRootAlreadyAdded);
+                roots
+                    .try_push(root.clone())
+                    .map_err(|_| Error::<T>::TooManyTrustedRoots)
+            })?;
+            Self::deposit_event(Event::TrustedAttestationRootAdded(root));
+            Ok(().into())
+        }
+
+        /// Removes a root from the [TrustedAttestationRoots].
+        ///
+        /// Already stored attestations are not affected, but no new attestation chains starting with this root are accepted.
+        #[pallet::weight(<T as Config>::WeightInfo::remove_trusted_root())]
+        #[pallet::call_index(13)]
+        pub fn remove_trusted_root(
+            origin: OriginFor<T>,
+            root: TrustedRoot,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            <TrustedAttestationRoots<T>>::try_mutate(|roots| -> Result<(), Error<T>> {
+                let position = roots
+                    .iter()
+                    .position(|r| r == &root)
+                    .ok_or(Error::<T>::TrustedRootNotFound)?;
+                roots.remove(position);
+                Ok(())
+            })?;
+            Self::deposit_event(Event::TrustedAttestationRootRemoved(root));
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Returns the [`AcurastLimits`] configured for this pallet, with the limits of other pallets unset.
+        pub fn limits() -> AcurastLimits {
+            AcurastLimits {
+                max_allowed_sources: Some(T::MaxAllowedSources::get()),
+                max_slots: Some(T::MaxSlots::get()),
+                ..Default::default()
+            }
+        }
+
         /// Get and update the next job identifier in the sequence.
         pub fn next_job_id() -> JobIdSequence {
             <LocalJobIdSequence<T>>::mutate(|job_id_seq| {
@@ -545,35 +823,60 @@ pub mod pallet {
         /// It assumes the caller was already authorized and is intended to be used from
         /// * The [`Self::register`] extrinsic of this pallet
         /// * An inter-chain communication protocol like Hyperdrive
+        ///
+        /// The registration and all storage written by [`JobHooks::register_hook`] are persisted together or not at all,
+        /// even if a caller ignores the returned error, e.g. when executing a cross-chain action.
         pub fn register_for(
             job_id: JobId<T::AccountId>,
-            registration: JobRegistrationFor<T>,
+            mut registration: JobRegistrationFor<T>,
         ) -> DispatchResultWithPostInfo {
-            ensure!(
-                is_valid_script(&registration.script),
-                Error::<T>::InvalidScriptValue
-            );
-            if let Some(allowed_sources) = &registration.allowed_sources {
-                let max_allowed_sources_len = T::MaxAllowedSources::get() as usize;
-                ensure!(allowed_sources.len() > 0, Error::<T>::TooFewAllowedSources);
+            with_storage_layer(|| {
+                <T as Config>::JobHooks::resolve_registration_hook(&job_id.0, &mut registration)?;
+
                 ensure!(
-                    allowed_sources.len() <= max_allowed_sources_len,
-                    Error::<T>::TooManyAllowedSources
+                    is_valid_script(&registration.script),
+                    Error::<T>::InvalidScriptValue
                 );
-            }
+                if let Some(allowed_sources) = &registration.allowed_sources {
+                    Self::ensure_valid_allowed_sources(allowed_sources)?;
+                }
 
-            <StoredJobRegistration<T>>::insert(&job_id.0, &job_id.1, registration.clone());
+                let previous = Self::stored_job_registration(&job_id.0, job_id.1);
+                Self::store_job_registration(&job_id, registration.clone());
 
-            <T as Config>::JobHooks::register_hook(&job_id.0, &job_id, &registration)?;
+                <T as Config>::JobHooks::register_hook(
+                    &job_id.0,
+                    &job_id,
+                    &registration,
+                    previous,
+                )?;
 
-            Self::deposit_event(Event::JobRegistrationStored(registration, job_id.clone()));
-            Ok(().into())
+                Self::deposit_event(Event::JobRegistrationStored(registration, job_id.clone()));
+                Ok(().into())
+            })
+        }
+
+        /// Checks the bounds of a list of allowed sources.
+        ///
+        /// An empty list is invalid since it cannot be told apart from [None], which allows all sources.
+        pub fn ensure_valid_allowed_sources(
+            allowed_sources: &[T::AccountId],
+        ) -> Result<(), Error<T>> {
+            ensure!(
+                !allowed_sources.is_empty(),
+                Error::<T>::TooFewAllowedSources
+            );
+            ensure!(
+                allowed_sources.len() <= T::MaxAllowedSources::get() as usize,
+                Error::<T>::TooManyAllowedSources
+            );
+            Ok(())
         }
 
         pub fn deregister_for(job_id: JobId<T::AccountId>) -> DispatchResultWithPostInfo {
             <T as Config>::JobHooks::deregister_hook(&job_id)?;
             Self::clear_environment_for(&job_id);
-            <StoredJobRegistration<T>>::remove(&job_id.0, &job_id.1);
+            Self::remove_job_registration(&job_id);
             Self::deposit_event(Event::JobRegistrationRemoved(job_id));
             Ok(().into())
         }
@@ -583,8 +886,10 @@ pub mod pallet {
             source: T::AccountId,
             environment: EnvironmentFor<T>,
         ) -> DispatchResultWithPostInfo {
-            let _registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
-                .ok_or(Error::<T>::JobRegistrationNotFound)?;
+            ensure!(
+                <StoredJobRegistration<T>>::contains_key(&job_id.0, &job_id.1),
+                Error::<T>::JobRegistrationNotFound
+            );
             <ExecutionEnvironment<T>>::insert(&job_id, source.clone(), environment);
             Ok(().into())
         }
@@ -592,5 +897,47 @@ pub mod pallet {
         pub fn clear_environment_for(job_id: &JobId<T::AccountId>) {
             let _ = <ExecutionEnvironment<T>>::clear_prefix(job_id, T::MaxSlots::get(), None);
         }
+
+        /// Returns the full [JobRegistration] joined from [StoredJobRegistration] and [StoredJobPayload].
+        ///
+        /// Prefer reading [StoredJobRegistration] if the script and allowed sources are not needed.
+        pub fn stored_job_registration<KArg1, KArg2>(
+            origin: KArg1,
+            job_id_seq: KArg2,
+        ) -> Option<JobRegistrationFor<T>>
+        where
+            KArg1: codec::EncodeLike<MultiOrigin<T::AccountId>> + Clone,
+            KArg2: codec::EncodeLike<JobIdSequence> + Clone,
+        {
+            let core = <StoredJobRegistration<T>>::get(origin.clone(), job_id_seq.clone())?;
+            let payload = <StoredJobPayload<T>>::get(origin, job_id_seq)?;
+            Some(JobRegistration::join(core, payload))
+        }
+
+        /// Stores a [JobRegistration] split into [StoredJobRegistration] and [StoredJobPayload].
+        pub fn store_job_registration(
+            job_id: &JobId<T::AccountId>,
+            registration: JobRegistrationFor<T>,
+        ) {
+            let (core, payload) = registration.split();
+            <StoredJobRegistration<T>>::insert(&job_id.0, &job_id.1, core);
+            <StoredJobPayload<T>>::insert(&job_id.0, &job_id.1, payload);
+        }
+
+        /// Removes a [JobRegistration] from both [StoredJobRegistration] and [StoredJobPayload].
+        pub fn remove_job_registration(job_id: &JobId<T::AccountId>) {
+            <StoredJobRegistration<T>>::remove(&job_id.0, &job_id.1);
+            <StoredJobPayload<T>>::remove(&job_id.0, &job_id.1);
+        }
+    }
+}
+
+sp_api::decl_runtime_apis! {
+    /// API to query the configuration of the Acurast pallets.
+    ///
+    /// Runtimes implement [`AcurastConfigApi::limits`] by starting from [`Pallet::limits`] and letting every other
+    /// included Acurast pallet extend it, e.g. with `pallet_acurast_marketplace::Pallet::extend_limits`.
+    pub trait AcurastConfigApi {
+        fn limits() -> AcurastLimits;
     }
 }
@@ -32,9 +32,120 @@ pub mod v1 {
     }
 }
 
+pub mod v3 {
+    use acurast_common::{AttestationValidity, BoundedKeyDescription, ValidatingCertIds};
+    use frame_support::pallet_prelude::*;
+
+    #[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq)]
+    pub struct Attestation {
+        pub cert_ids: ValidatingCertIds,
+        pub key_description: BoundedKeyDescription,
+        pub validity: AttestationValidity,
+    }
+}
+
+pub mod v4 {
+    use acurast_common::{AllowedSources, JobModules, Schedule, Script};
+    use codec::{Input, Output};
+    use frame_support::pallet_prelude::*;
+    use sp_std::prelude::*;
+
+    use crate::{Config, JobIdSequence, MultiOrigin, Pallet};
+
+    /// The encoded [Config::RegistrationExtra] of a registration, kept as is since its layout is owned by the pallet
+    /// configuring it.
+    ///
+    /// Decodes all remaining input, so it must be the last field of the containing struct.
+    #[derive(RuntimeDebug, TypeInfo, Clone, PartialEq)]
+    pub struct OpaqueExtra(pub Vec<u8>);
+
+    impl Encode for OpaqueExtra {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+            dest.write(&self.0);
+        }
+    }
+
+    impl Decode for OpaqueExtra {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+            let len = input
+                .remaining_len()?
+                .ok_or("remaining length of extra unknown")?;
+            let mut bytes = Vec::new();
+            bytes.resize(len, 0u8);
+            input.read(&mut bytes)?;
+            Ok(Self(bytes))
+        }
+    }
+
+    #[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq)]
+    pub struct JobRegistration<AccountId, MaxAllowedSources: Get<u32>, Extra> {
+        /// The script to execute. It is a vector of bytes representing a utf8 string. The string needs to be a ipfs url that points to the script.
+        pub script: Script,
+        /// An optional array of the [AccountId]s allowed to fulfill the job. If the array is [None], then all sources are allowed.
+        pub allowed_sources: Option<AllowedSources<AccountId, MaxAllowedSources>>,
+        /// A boolean indicating if only verified sources can fulfill the job. A verified source is one that has provided a valid key attestation.
+        pub allow_only_verified_sources: bool,
+        /// The schedule describing the desired (multiple) execution(s) of the script.
+        pub schedule: Schedule,
+        /// Maximum memory bytes used during a single execution of the job.
+        pub memory: u32,
+        /// Maximum network request used during a single execution of the job.
+        pub network_requests: u32,
+        /// Maximum storage bytes used during the whole period of the job's executions.
+        pub storage: u32,
+        /// The modules required for the job.
+        pub required_modules: JobModules,
+        /// Extra parameters. This type can be configured through [Config::RegistrationExtra].
+        pub extra: Extra,
+    }
+
+    /// The registrations before being split into core and payload.
+    #[frame_support::storage_alias]
+    pub type StoredJobRegistration<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        MultiOrigin<<T as frame_system::Config>::AccountId>,
+        Blake2_128Concat,
+        JobIdSequence,
+        JobRegistration<
+            <T as frame_system::Config>::AccountId,
+            <T as Config>::MaxAllowedSources,
+            OpaqueExtra,
+        >,
+    >;
+}
+
+pub mod v5 {
+    use acurast_common::JobRegistrationCore;
+    use frame_support::pallet_prelude::*;
+
+    use super::v4::OpaqueExtra;
+    use crate::{Config, JobIdSequence, MultiOrigin, Pallet};
+
+    /// The registration cores with their extra kept opaque, translated by the pallet configuring the extra.
+    #[frame_support::storage_alias]
+    pub type StoredJobRegistration<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        MultiOrigin<<T as frame_system::Config>::AccountId>,
+        Blake2_128Concat,
+        JobIdSequence,
+        JobRegistrationCore<OpaqueExtra>,
+    >;
+}
+
 pub fn migrate<T: Config>() -> Weight {
-    let migrations: [(u16, &dyn Fn() -> Weight); 2] =
-        [(2, &migrate_to_v2::<T>), (3, &migrate_to_v3::<T>)];
+    let migrations: [(u16, &dyn Fn() -> Weight); 5] = [
+        (2, &migrate_to_v2::<T>),
+        (3, &migrate_to_v3::<T>),
+        (4, &migrate_to_v4::<T>),
+        (5, &migrate_to_v5::<T>),
+        (6, &migrate_to_v6::<T>),
+    ];
 
     let onchain_version = Pallet::<T>::on_chain_storage_version();
     let mut weight: Weight = Default::default();
@@ -49,11 +160,11 @@ pub fn migrate<T: Config>() -> Weight {
 }
 
 fn migrate_to_v2<T: Config>() -> Weight {
-    StoredJobRegistration::<T>::translate::<
-        v1::JobRegistration<T::AccountId, T::MaxAllowedSources, T::RegistrationExtra>,
+    v4::StoredJobRegistration::<T>::translate::<
+        v1::JobRegistration<T::AccountId, T::MaxAllowedSources, v4::OpaqueExtra>,
         _,
     >(|_k1, _k2, job| {
-        Some(JobRegistration {
+        Some(v4::JobRegistration {
             script: job.script,
             allowed_sources: job.allowed_sources,
             allow_only_verified_sources: job.allow_only_verified_sources,
@@ -65,7 +176,7 @@ fn migrate_to_v2<T: Config>() -> Weight {
             extra: job.extra,
         })
     });
-    let count = StoredJobRegistration::<T>::iter().count() as u64;
+    let count = v4::StoredJobRegistration::<T>::iter().count() as u64;
     T::DbWeight::get().reads_writes(count + 1, count + 1)
 }
 
@@ -73,6 +184,69 @@ fn migrate_to_v3<T: Config>() -> Weight {
     let mut count = 0u32;
     // we know they are reasonably few items and we can clear them within a single migration
     count += StoredJobRegistration::<T>::clear(10_000, None).loops;
+    count += StoredJobPayload::<T>::clear(10_000, None).loops;
 
     T::DbWeight::get().writes((count + 1).into())
 }
+
+fn migrate_to_v4<T: Config>() -> Weight {
+    let mut count = 0u64;
+    StoredAttestation::<T>::translate_values::<v3::Attestation, _>(|attestation| {
+        count += 1;
+        // the submission time of existing attestations is unknown
+        Some(Attestation {
+            cert_ids: attestation.cert_ids,
+            key_description: attestation.key_description,
+            validity: attestation.validity,
+            submitted_at: None,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
+
+/// Splits the [JobRegistration]s into [StoredJobRegistration] and [StoredJobPayload].
+///
+/// The extra is moved over unchanged, its layout gets migrated by the pallet configuring [Config::RegistrationExtra].
+fn migrate_to_v5<T: Config>() -> Weight {
+    let mut count = 0u64;
+    v5::StoredJobRegistration::<T>::translate::<
+        v4::JobRegistration<T::AccountId, T::MaxAllowedSources, v4::OpaqueExtra>,
+        _,
+    >(|k1, k2, registration| {
+        count += 1;
+        StoredJobPayload::<T>::insert(
+            &k1,
+            k2,
+            JobPayload {
+                script: registration.script,
+                allowed_sources: registration.allowed_sources,
+            },
+        );
+        Some(JobRegistrationCore {
+            allow_only_verified_sources: registration.allow_only_verified_sources,
+            schedule: registration.schedule,
+            memory: registration.memory,
+            network_requests: registration.network_requests,
+            storage: registration.storage,
+            required_modules: registration.required_modules,
+            extra: registration.extra,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(count + 1, 2 * count + 1)
+}
+
+/// Seeds the [TrustedAttestationRoots] with Google's hardware attestation root, trusted before the roots became configurable.
+fn migrate_to_v6<T: Config>() -> Weight {
+    if TrustedAttestationRoots::<T>::exists() {
+        return T::DbWeight::get().reads(1);
+    }
+    let root: TrustedRoot = GOOGLE_ROOT_PUB_KEY
+        .to_vec()
+        .try_into()
+        .expect("Google root public key fits TRUSTED_ROOT_MAX_LENGTH");
+    TrustedAttestationRoots::<T>::put(TrustedAttestationRootsFor::<T>::truncate_from(vec![root]));
+
+    T::DbWeight::get().reads_writes(1, 1)
+}
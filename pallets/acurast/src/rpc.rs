@@ -0,0 +1,64 @@
+//! Node-specific RPC methods for interaction with pallet-acurast.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use frame_support::sp_runtime::traits::{Block as BlockT, HashingFor};
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+use crate::{AcurastConfigApi, AcurastLimits};
+
+const RUNTIME_ERROR: i32 = 8004;
+
+#[rpc(client, server)]
+pub trait AcurastApi<BlockHash> {
+    /// Retrieves the constants and limits of the Acurast pallets.
+    #[method(name = "acurast_limits")]
+    fn limits(&self) -> RpcResult<AcurastLimits>;
+}
+
+/// RPC methods.
+pub struct Acurast<Client, B> {
+    client: Arc<Client>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> Acurast<C, B> {
+    /// Create new `Acurast` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<Client, Block> AcurastApiServer<HashingFor<Block>> for Acurast<Client, Block>
+where
+    Block: BlockT,
+    Client: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    Client::Api: AcurastConfigApi<Block>,
+{
+    fn limits(&self) -> RpcResult<AcurastLimits> {
+        let api = self.client.runtime_api();
+        let limits = api
+            .limits(self.client.info().best_hash)
+            .map_err(runtime_error_into_rpc_error)?;
+        Ok(limits)
+    }
+}
+
+/// Converts a runtime trap into a [`CallError`].
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> CallError {
+    CallError::Custom(ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Runtime trapped",
+        Some(format!("{:?}", err)),
+    ))
+}
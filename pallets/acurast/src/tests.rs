@@ -1,14 +1,22 @@
 #![cfg(test)]
 
-use frame_support::{assert_err, assert_ok, BoundedVec};
+use frame_support::{
+    assert_err, assert_ok,
+    storage::unhashed,
+    traits::{GetStorageVersion, Hooks, StorageVersion},
+    BoundedVec,
+};
 use hex_literal::hex;
-use sp_runtime::{bounded_vec, AccountId32};
+use sp_runtime::{bounded_vec, AccountId32, DispatchError};
 
-use acurast_common::{Environment, MultiOrigin};
+use acurast_common::{Environment, MultiOrigin, TrustedRoot, GOOGLE_ROOT_PUB_KEY};
 
 use crate::{
-    mock::*, utils::validate_and_extract_attestation, AllowedSourcesUpdate, AttestationChain,
-    CertificateRevocationListUpdate, Error, ListUpdateOperation, SerialNumber,
+    migration,
+    mock::*,
+    utils::{ensure_source_verified_application_id, validate_and_extract_attestation},
+    AcurastLimits, AllowedSourcesUpdate, AttestationChain, CertificateRevocationListUpdate, Error,
+    ListUpdateOperation, SerialNumber, StoredJobRegistration, ACURAST_LIMITS_VERSION,
 };
 
 #[test]
@@ -186,7 +194,8 @@ fn test_update_allowed_sources() {
         assert_ok!(Acurast::update_allowed_sources(
             RuntimeOrigin::signed(alice_account_id()).into(),
             Acurast::job_id_sequence(),
-            updates_1.clone().try_into().unwrap()
+            updates_1.clone().try_into().unwrap(),
+            false
         ));
 
         assert_eq!(
@@ -200,7 +209,8 @@ fn test_update_allowed_sources() {
         assert_ok!(Acurast::update_allowed_sources(
             RuntimeOrigin::signed(alice_account_id()).into(),
             Acurast::job_id_sequence(),
-            updates_2.clone().try_into().unwrap()
+            updates_2.clone().try_into().unwrap(),
+            true
         ));
 
         assert_eq!(
@@ -260,7 +270,8 @@ fn test_update_allowed_sources_failure() {
             Acurast::update_allowed_sources(
                 RuntimeOrigin::signed(alice_account_id()).into(),
                 initial_job_id + 1,
-                updates.clone().try_into().unwrap()
+                updates.clone().try_into().unwrap(),
+                false
             ),
             Error::<Test>::TooManyAllowedSources
         );
@@ -283,6 +294,280 @@ fn test_update_allowed_sources_failure() {
     });
 }
 
+#[test]
+fn test_update_allowed_sources_to_empty_requires_clear() {
+    let registration = job_registration(
+        Some(bounded_vec![alice_account_id(), bob_account_id()]),
+        false,
+    );
+    let updates: BoundedVec<_, _> = vec![
+        AllowedSourcesUpdate {
+            operation: ListUpdateOperation::Remove,
+            item: alice_account_id(),
+        },
+        AllowedSourcesUpdate {
+            operation: ListUpdateOperation::Remove,
+            item: bob_account_id(),
+        },
+    ]
+    .try_into()
+    .unwrap();
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+
+        // removing all sources does not silently open the job to everyone
+        assert_err!(
+            Acurast::update_allowed_sources(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.1,
+                updates.clone(),
+                false
+            ),
+            Error::<Test>::TooFewAllowedSources
+        );
+        assert_eq!(
+            Some(registration.clone()),
+            Acurast::stored_job_registration(&job_id.0, job_id.1)
+        );
+
+        assert_ok!(Acurast::update_allowed_sources(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1,
+            updates.clone(),
+            true
+        ));
+        assert_eq!(
+            Some(job_registration(None, false)),
+            Acurast::stored_job_registration(&job_id.0, job_id.1)
+        );
+
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Acurast(crate::Event::JobRegistrationStored(
+                    registration.clone(),
+                    job_id.clone()
+                )),
+                RuntimeEvent::Acurast(crate::Event::AllowedSourcesUpdated(
+                    job_id,
+                    registration,
+                    updates
+                )),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_update_allowed_sources_partial_removal() {
+    let registration = job_registration(
+        Some(bounded_vec![alice_account_id(), bob_account_id()]),
+        false,
+    );
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration,
+        ));
+
+        // `clear` has no effect as long as sources remain allowed
+        assert_ok!(Acurast::update_allowed_sources(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1,
+            vec![
+                AllowedSourcesUpdate {
+                    operation: ListUpdateOperation::Remove,
+                    item: alice_account_id(),
+                },
+                AllowedSourcesUpdate {
+                    operation: ListUpdateOperation::Add,
+                    item: charlie_account_id(),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+            true
+        ));
+        assert_eq!(
+            Some(job_registration(
+                Some(bounded_vec![bob_account_id(), charlie_account_id()]),
+                false
+            )),
+            Acurast::stored_job_registration(&job_id.0, job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_update_allowed_sources_of_open_job() {
+    let registration = job_registration(None, false);
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+
+        // a job open to all sources stays open without confirmation
+        assert_ok!(Acurast::update_allowed_sources(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1,
+            vec![AllowedSourcesUpdate {
+                operation: ListUpdateOperation::Remove,
+                item: bob_account_id(),
+            }]
+            .try_into()
+            .unwrap(),
+            false
+        ));
+        assert_eq!(
+            Some(registration),
+            Acurast::stored_job_registration(&job_id.0, job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_job_registration_split_storage() {
+    let registration = job_registration(Some(bounded_vec![alice_account_id()]), false);
+    let (core, payload) = registration.clone().split();
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+
+        assert_eq!(
+            Some(core.clone()),
+            StoredJobRegistration::<Test>::get(&job_id.0, job_id.1)
+        );
+        assert_eq!(
+            Some(payload),
+            Acurast::stored_job_payload(&job_id.0, job_id.1)
+        );
+
+        // updating the allowed sources only touches the payload
+        assert_ok!(Acurast::update_allowed_sources(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1,
+            vec![AllowedSourcesUpdate {
+                operation: ListUpdateOperation::Add,
+                item: bob_account_id(),
+            }]
+            .try_into()
+            .unwrap(),
+            false
+        ));
+        assert_eq!(
+            Some(core),
+            StoredJobRegistration::<Test>::get(&job_id.0, job_id.1)
+        );
+        assert_eq!(
+            Some(bounded_vec![alice_account_id(), bob_account_id()]),
+            Acurast::stored_job_payload(&job_id.0, job_id.1)
+                .unwrap()
+                .allowed_sources
+        );
+
+        assert_ok!(Acurast::deregister(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1
+        ));
+        assert_eq!(
+            None,
+            StoredJobRegistration::<Test>::get(&job_id.0, job_id.1)
+        );
+        assert_eq!(None, Acurast::stored_job_payload(&job_id.0, job_id.1));
+    });
+}
+
+#[test]
+fn test_job_registration_migration_to_v5() {
+    let registration = job_registration(Some(bounded_vec![bob_account_id()]), true);
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        StorageVersion::new(4).put::<Acurast>();
+        // a registration stored as a whole before the migration
+        unhashed::put(
+            &StoredJobRegistration::<Test>::hashed_key_for(&job_id.0, job_id.1),
+            &registration,
+        );
+
+        Acurast::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::new(6), Acurast::on_chain_storage_version());
+        let (core, payload) = registration.clone().split();
+        assert_eq!(
+            Some(core),
+            StoredJobRegistration::<Test>::get(&job_id.0, job_id.1)
+        );
+        assert_eq!(
+            Some(payload),
+            Acurast::stored_job_payload(&job_id.0, job_id.1)
+        );
+        assert_eq!(
+            Some(registration),
+            Acurast::stored_job_registration(&job_id.0, job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_job_registration_migration_to_v5_keeps_extra_opaque() {
+    let registration = job_registration(None, false);
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        StorageVersion::new(4).put::<Acurast>();
+        // an extra of a layout unknown to this pallet
+        let extra = migration::v4::OpaqueExtra(vec![1, 2, 3]);
+        migration::v4::StoredJobRegistration::<Test>::insert(
+            &job_id.0,
+            job_id.1,
+            migration::v4::JobRegistration {
+                script: registration.script.clone(),
+                allowed_sources: registration.allowed_sources.clone(),
+                allow_only_verified_sources: registration.allow_only_verified_sources,
+                schedule: registration.schedule.clone(),
+                memory: registration.memory,
+                network_requests: registration.network_requests,
+                storage: registration.storage,
+                required_modules: registration.required_modules.clone(),
+                extra: extra.clone(),
+            },
+        );
+
+        Acurast::on_runtime_upgrade();
+
+        assert_eq!(
+            Some(extra),
+            migration::v5::StoredJobRegistration::<Test>::get(&job_id.0, job_id.1)
+                .map(|core| core.extra)
+        );
+        assert_eq!(
+            Some(registration.split().1),
+            Acurast::stored_job_payload(&job_id.0, job_id.1)
+        );
+    });
+}
+
 #[test]
 fn test_submit_attestation() {
     ExtBuilder::default().build().execute_with(|| {
@@ -295,6 +580,7 @@ fn test_submit_attestation() {
 
         let attestation =
             validate_and_extract_attestation::<Test>(&processor_account_id(), &chain).unwrap();
+        assert_eq!(Some(1657363915001), attestation.submitted_at);
 
         assert_eq!(
             Some(attestation.clone()),
@@ -305,7 +591,8 @@ fn test_submit_attestation() {
             events(),
             [RuntimeEvent::Acurast(crate::Event::AttestationStored(
                 attestation,
-                processor_account_id()
+                processor_account_id(),
+                Some(attestation_application_id())
             ))]
         );
     });
@@ -335,6 +622,295 @@ fn test_submit_attestation_parse_issuer_name() {
     });
 }
 
+#[test]
+fn test_submit_attestation_allowed_application_id() {
+    ExtBuilder::default().build().execute_with(|| {
+        let application_ids = allowed_application_ids(&[
+            (
+                b"com.acurast.attested.executor.testnet",
+                attestation_signer_digest().as_slice(),
+            ),
+            (
+                attestation_application_id().as_slice(),
+                attestation_signer_digest().as_slice(),
+            ),
+        ]);
+        assert_err!(
+            Acurast::update_allowed_application_ids(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                application_ids.clone(),
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Acurast::update_allowed_application_ids(
+            RuntimeOrigin::root(),
+            application_ids.clone(),
+        ));
+        assert_eq!(application_ids, Acurast::allowed_application_ids());
+
+        let chain = attestation_chain();
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915001);
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            chain.clone()
+        ));
+
+        let attestation =
+            validate_and_extract_attestation::<Test>(&processor_account_id(), &chain).unwrap();
+        assert_eq!(
+            Some(attestation_application_id()),
+            ensure_source_verified_application_id::<Test>(&processor_account_id()).unwrap()
+        );
+
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Acurast(crate::Event::AllowedApplicationIdsUpdated(application_ids)),
+                RuntimeEvent::Acurast(crate::Event::AttestationStored(
+                    attestation,
+                    processor_account_id(),
+                    Some(attestation_application_id())
+                )),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_submit_attestation_application_id_not_allowed() {
+    ExtBuilder::default().build().execute_with(|| {
+        let application_ids = allowed_application_ids(&[(
+            b"com.acurast.attested.executor.testnet",
+            attestation_signer_digest().as_slice(),
+        )]);
+        assert_ok!(Acurast::update_allowed_application_ids(
+            RuntimeOrigin::root(),
+            application_ids,
+        ));
+
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915001);
+        assert_err!(
+            Acurast::submit_attestation(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                attestation_chain()
+            ),
+            Error::<Test>::ApplicationIdNotAllowed
+        );
+        assert_eq!(None, Acurast::stored_attestation(processor_account_id()));
+        assert_err!(
+            ensure_source_verified_application_id::<Test>(&processor_account_id()),
+            Error::<Test>::FulfillSourceNotVerified
+        );
+    });
+}
+
+#[test]
+fn test_submit_attestation_application_signed_with_other_certificate() {
+    ExtBuilder::default().build().execute_with(|| {
+        let application_ids =
+            allowed_application_ids(&[(attestation_application_id().as_slice(), &[0u8; 32])]);
+        assert_ok!(Acurast::update_allowed_application_ids(
+            RuntimeOrigin::root(),
+            application_ids,
+        ));
+
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915001);
+        assert_err!(
+            Acurast::submit_attestation(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                attestation_chain()
+            ),
+            Error::<Test>::ApplicationIdNotAllowed
+        );
+        assert_eq!(None, Acurast::stored_attestation(processor_account_id()));
+    });
+}
+
+#[test]
+fn test_submit_attestation_empty_allowed_application_ids() {
+    ExtBuilder::default().build().execute_with(|| {
+        let application_ids = allowed_application_ids(&[(
+            b"com.acurast.attested.executor.testnet",
+            attestation_signer_digest().as_slice(),
+        )]);
+        assert_ok!(Acurast::update_allowed_application_ids(
+            RuntimeOrigin::root(),
+            application_ids,
+        ));
+        assert_ok!(Acurast::update_allowed_application_ids(
+            RuntimeOrigin::root(),
+            Default::default(),
+        ));
+
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915001);
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            attestation_chain()
+        ));
+        assert!(Acurast::stored_attestation(processor_account_id()).is_some());
+    });
+}
+
+#[test]
+fn test_submit_attestation_with_genesis_root() {
+    GenesisBuilder::<Test>::new()
+        .with(&crate::GenesisConfig::<Test> {
+            attestations: vec![],
+            trusted_roots: vec![test_root()],
+        })
+        .build()
+        .execute_with(|| {
+            assert_eq!(
+                vec![test_root()],
+                Acurast::trusted_attestation_roots().into_inner()
+            );
+            let _ = Timestamp::set(RuntimeOrigin::none(), 1_700_000_000_000);
+
+            assert_ok!(Acurast::submit_attestation(
+                RuntimeOrigin::signed(test_root_account_id()).into(),
+                test_root_attestation_chain()
+            ));
+            assert!(Acurast::stored_attestation(test_root_account_id()).is_some());
+
+            // Google's root is only trusted by default
+            assert_err!(
+                Acurast::submit_attestation(
+                    RuntimeOrigin::signed(processor_account_id()).into(),
+                    attestation_chain()
+                ),
+                Error::<Test>::CertificateChainValidationFailed
+            );
+        });
+}
+
+#[test]
+fn test_submit_attestation_rejected_after_root_removed() {
+    ExtBuilder::default().build().execute_with(|| {
+        let google_root: TrustedRoot = GOOGLE_ROOT_PUB_KEY.to_vec().try_into().unwrap();
+        assert_eq!(
+            vec![google_root.clone()],
+            Acurast::trusted_attestation_roots().into_inner()
+        );
+
+        assert_ok!(Acurast::remove_trusted_root(
+            RuntimeOrigin::root(),
+            google_root.clone()
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::Acurast(
+                crate::Event::TrustedAttestationRootRemoved(google_root)
+            )]
+        );
+
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915001);
+        assert_err!(
+            Acurast::submit_attestation(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                attestation_chain()
+            ),
+            Error::<Test>::CertificateChainValidationFailed
+        );
+        assert_eq!(None, Acurast::stored_attestation(processor_account_id()));
+    });
+}
+
+#[test]
+fn test_submit_attestation_with_added_root() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1_700_000_000_000);
+        assert_err!(
+            Acurast::submit_attestation(
+                RuntimeOrigin::signed(test_root_account_id()).into(),
+                test_root_attestation_chain()
+            ),
+            Error::<Test>::CertificateChainValidationFailed
+        );
+
+        assert_ok!(Acurast::add_trusted_root(
+            RuntimeOrigin::root(),
+            test_root()
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::Acurast(
+                crate::Event::TrustedAttestationRootAdded(test_root())
+            )]
+        );
+
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(test_root_account_id()).into(),
+            test_root_attestation_chain()
+        ));
+        let attestation = Acurast::stored_attestation(test_root_account_id()).unwrap();
+        assert_eq!(
+            events(),
+            [RuntimeEvent::Acurast(crate::Event::AttestationStored(
+                attestation,
+                test_root_account_id(),
+                Some(attestation_application_id())
+            ))]
+        );
+    });
+}
+
+#[test]
+fn test_update_trusted_roots_validation() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_err!(
+            Acurast::add_trusted_root(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                test_root()
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            Acurast::add_trusted_root(
+                RuntimeOrigin::root(),
+                TEST_ROOT_CERT[..64].to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::InvalidTrustedRoot
+        );
+        assert_err!(
+            Acurast::add_trusted_root(
+                RuntimeOrigin::root(),
+                GOOGLE_ROOT_PUB_KEY.to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::TrustedRootAlreadyAdded
+        );
+        assert_err!(
+            Acurast::remove_trusted_root(RuntimeOrigin::root(), test_root()),
+            Error::<Test>::TrustedRootNotFound
+        );
+        assert_err!(
+            Acurast::remove_trusted_root(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                GOOGLE_ROOT_PUB_KEY.to_vec().try_into().unwrap()
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn test_trusted_roots_migration_to_v6() {
+    ExtBuilder::default().build().execute_with(|| {
+        crate::TrustedAttestationRoots::<Test>::kill();
+        StorageVersion::new(5).put::<Acurast>();
+
+        Acurast::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::new(6), Acurast::on_chain_storage_version());
+        assert_eq!(
+            vec![GOOGLE_ROOT_PUB_KEY.to_vec()],
+            Acurast::trusted_attestation_roots()
+                .into_iter()
+                .map(|root| root.into_inner())
+                .collect::<Vec<_>>()
+        );
+    });
+}
+
 #[test]
 fn test_submit_attestation_failure_1() {
     ExtBuilder::default().build().execute_with(|| {
@@ -541,7 +1117,8 @@ fn test_update_revocation_list_assign_job() {
             [
                 RuntimeEvent::Acurast(crate::Event::AttestationStored(
                     attestation,
-                    processor_account_id()
+                    processor_account_id(),
+                    Some(attestation_application_id())
                 )),
                 RuntimeEvent::Acurast(crate::Event::JobRegistrationStored(
                     registration.clone(),
@@ -556,6 +1133,163 @@ fn test_update_revocation_list_assign_job() {
     });
 }
 
+#[test]
+fn test_submit_signed_crl() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1688169600001);
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&SIGNED_CRL)
+            ),
+            Error::<Test>::CrlIssuerNotConfigured
+        );
+        assert_err!(
+            Acurast::set_crl_issuer(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                crl_issuer_cert()
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Acurast::set_crl_issuer(
+            RuntimeOrigin::root(),
+            crl_issuer_cert()
+        ));
+
+        assert_ok!(Acurast::submit_signed_crl(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            crl(&SIGNED_CRL)
+        ));
+        assert_eq!(
+            Some(()),
+            Acurast::stored_revoked_certificate::<SerialNumber>(cert_serial_number())
+        );
+        assert_eq!(
+            Some(()),
+            Acurast::stored_revoked_certificate::<SerialNumber>(other_cert_serial_number())
+        );
+        assert_eq!(1688169600000, Acurast::last_crl_update());
+
+        // replaying an already applied CRL is rejected
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&SIGNED_CRL)
+            ),
+            Error::<Test>::CrlOutdated
+        );
+
+        let updates = vec![
+            CertificateRevocationListUpdate {
+                operation: ListUpdateOperation::Add,
+                item: cert_serial_number(),
+            },
+            CertificateRevocationListUpdate {
+                operation: ListUpdateOperation::Add,
+                item: other_cert_serial_number(),
+            },
+        ];
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Acurast(crate::Event::CrlIssuerUpdated(crl_issuer_cert())),
+                RuntimeEvent::Acurast(crate::Event::CertificateRecovationListUpdated(
+                    bob_account_id(),
+                    updates.try_into().unwrap()
+                )),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_submit_signed_crl_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Acurast::set_crl_issuer(
+            RuntimeOrigin::root(),
+            crl_issuer_cert()
+        ));
+
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1688169600001);
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&EXPIRED_CRL)
+            ),
+            Error::<Test>::CrlExpired
+        );
+        assert_eq!(
+            None,
+            Acurast::stored_revoked_certificate::<SerialNumber>(cert_serial_number())
+        );
+    });
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Acurast::set_crl_issuer(
+            RuntimeOrigin::root(),
+            crl_issuer_cert()
+        ));
+
+        // before thisUpdate
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1688169599999);
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&SIGNED_CRL)
+            ),
+            Error::<Test>::CrlNotYetValid
+        );
+    });
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Acurast::set_crl_issuer(
+            RuntimeOrigin::root(),
+            crl_issuer_cert()
+        ));
+
+        // at nextUpdate
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1690848000000);
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&SIGNED_CRL)
+            ),
+            Error::<Test>::CrlExpired
+        );
+    });
+}
+
+#[test]
+fn test_submit_signed_crl_wrongly_signed() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Acurast::set_crl_issuer(
+            RuntimeOrigin::root(),
+            crl_issuer_cert()
+        ));
+
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1688169600001);
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&WRONGLY_SIGNED_CRL)
+            ),
+            Error::<Test>::CrlValidationFailed
+        );
+        assert_err!(
+            Acurast::submit_signed_crl(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                crl(&ROOT_CERT)
+            ),
+            Error::<Test>::CrlValidationFailed
+        );
+        assert_eq!(
+            None,
+            Acurast::stored_revoked_certificate::<SerialNumber>(cert_serial_number())
+        );
+        assert_eq!(0, Acurast::last_crl_update());
+    });
+}
+
 #[test]
 fn test_set_environment() {
     let registration = job_registration(
@@ -607,3 +1341,22 @@ fn test_set_environment() {
         );
     });
 }
+
+#[test]
+fn test_limits() {
+    assert_eq!(
+        Acurast::limits(),
+        AcurastLimits {
+            version: ACURAST_LIMITS_VERSION,
+            max_allowed_sources: Some(4),
+            max_slots: Some(64),
+            max_executions_per_job: None,
+            report_tolerance: None,
+            max_proposed_matches: None,
+            pairing_proof_expiration_time: None,
+            max_pairing_updates: None,
+            heartbeat_interval: None,
+            unpair_cooldown: None,
+        }
+    );
+}
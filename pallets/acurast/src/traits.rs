@@ -1,5 +1,5 @@
 use acurast_common::{Attestation, JobId, MultiOrigin};
-use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::dispatch::{DispatchResult, DispatchResultWithPostInfo};
 use frame_support::weights::Weight;
 use sp_std::prelude::*;
 
@@ -44,14 +44,26 @@ pub trait WeightInfo {
     fn update_certificate_revocation_list() -> Weight;
     fn set_environment(x: u32) -> Weight;
     fn set_environments(envs: u32, vars: u32) -> Weight;
+    fn set_crl_issuer() -> Weight;
+    fn submit_signed_crl() -> Weight;
+    fn update_allowed_application_ids(x: u32) -> Weight;
+    fn add_trusted_root() -> Weight;
+    fn remove_trusted_root() -> Weight;
 }
 
 /// Allows to hook additional logic for various job related extrinsics.
 pub trait JobHooks<T: Config> {
+    /// Called before a job registration gets validated and stored, to complete a sparse `registration` in place.
+    fn resolve_registration_hook(
+        who: &MultiOrigin<T::AccountId>,
+        registration: &mut JobRegistrationFor<T>,
+    ) -> DispatchResult;
+    /// Called when a job gets registered. `previous` contains the registration that gets overwritten, if any.
     fn register_hook(
         who: &MultiOrigin<T::AccountId>,
         job_id: &JobId<<T as frame_system::Config>::AccountId>,
         registration: &JobRegistrationFor<T>,
+        previous: Option<JobRegistrationFor<T>>,
     ) -> DispatchResultWithPostInfo;
     fn deregister_hook(
         job_id: &JobId<<T as frame_system::Config>::AccountId>,
@@ -64,10 +76,17 @@ pub trait JobHooks<T: Config> {
 }
 
 impl<T: Config> JobHooks<T> for () {
+    fn resolve_registration_hook(
+        _who: &MultiOrigin<T::AccountId>,
+        _registration: &mut JobRegistrationFor<T>,
+    ) -> DispatchResult {
+        Ok(())
+    }
     fn register_hook(
         _who: &MultiOrigin<T::AccountId>,
         _job_id: &JobId<<T as frame_system::Config>::AccountId>,
         _registration: &JobRegistrationFor<T>,
+        _previous: Option<JobRegistrationFor<T>>,
     ) -> DispatchResultWithPostInfo {
         Ok(().into())
     }
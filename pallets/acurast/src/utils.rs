@@ -1,20 +1,29 @@
-use acurast_common::{extract_attestation, validate_certificate_chain, ECDSACurve, PublicKey};
+use acurast_common::{
+    extract_attestation, validate_certificate_chain, validate_crl, CrlInput, ECDSACurve, PublicKey,
+};
 use codec::Encode;
 use frame_support::{ensure, traits::UnixTime};
 use sp_std::prelude::*;
 
 use crate::{
-    Attestation, AttestationChain, AttestationValidity, CertId, Config, Error, IssuerName,
-    SerialNumber, StoredAttestation, StoredRevokedCertificate, ValidatingCertIds,
+    AllowedApplicationIds, Attestation, AttestationChain, AttestationValidity,
+    BoundedAttestationApplicationId, CertId, Config, CrlIssuer, Error, IssuerName, LastCrlUpdate,
+    PackageName, SerialNumber, StoredAttestation, StoredRevokedCertificate,
+    TrustedAttestationRoots, ValidatingCertIds,
 };
 
-/// Validates and returns an [Attestation] from the provided chain.
+/// Validates and returns an [Attestation] from the provided chain, recording the current time as its submission time.
 pub fn validate_and_extract_attestation<T: Config>(
     source: &T::AccountId,
     attestation_chain: &AttestationChain,
 ) -> Result<Attestation, Error<T>> {
+    let trusted_roots = <TrustedAttestationRoots<T>>::get();
+    let trusted_roots = trusted_roots
+        .iter()
+        .map(|root| root.as_slice())
+        .collect::<Vec<_>>();
     let (cert_ids, cert, public_key) =
-        validate_certificate_chain(&attestation_chain.certificate_chain)
+        validate_certificate_chain(&attestation_chain.certificate_chain, &trusted_roots)
             .map_err(|_| Error::<T>::CertificateChainValidationFailed)?;
 
     ensure_valid_public_key_for_source(source, &public_key)?;
@@ -47,24 +56,97 @@ pub fn validate_and_extract_attestation<T: Config>(
             .try_into()
             .map_err(|_| Error::<T>::AttestationToBoundedTypeConversionFailed)?,
         validity: attestation_validity,
+        submitted_at: Some(now::<T>()?),
     })
 }
 
 /// Ensures that the provided account id has a valid (not expired and not revoked) key attestation.
-pub fn ensure_source_verified<T: Config>(source: &T::AccountId) -> Result<(), Error<T>> {
+///
+/// Returns the time the attestation was submitted, if known.
+pub fn ensure_source_verified<T: Config>(source: &T::AccountId) -> Result<Option<u64>, Error<T>> {
     let attestation =
         <StoredAttestation<T>>::get(source).ok_or(Error::<T>::FulfillSourceNotVerified)?;
     ensure_not_expired(&attestation)?;
     ensure_not_revoked(&attestation)?;
-    Ok(())
+    Ok(attestation.submitted_at)
 }
 
-/// Ensures the attestation is not expired.
-pub(crate) fn ensure_not_expired<T: Config>(attestation: &Attestation) -> Result<(), Error<T>> {
-    let now: u64 = T::UnixTime::now()
+/// Ensures that the provided account id has a valid (not expired and not revoked) key attestation.
+///
+/// Returns the application id (package name) the attestation was issued for, if it contains one.
+pub fn ensure_source_verified_application_id<T: Config>(
+    source: &T::AccountId,
+) -> Result<Option<PackageName>, Error<T>> {
+    let attestation =
+        <StoredAttestation<T>>::get(source).ok_or(Error::<T>::FulfillSourceNotVerified)?;
+    ensure_not_expired(&attestation)?;
+    ensure_not_revoked(&attestation)?;
+    Ok(application_id(&attestation))
+}
+
+/// Returns the attestation application id, the hardware enforced one taking precedence over the software enforced one.
+fn attestation_application_id(
+    attestation: &Attestation,
+) -> Option<&BoundedAttestationApplicationId> {
+    let key_description = &attestation.key_description;
+    key_description
+        .tee_enforced
+        .attestation_application_id
+        .as_ref()
+        .or(key_description
+            .software_enforced
+            .attestation_application_id
+            .as_ref())
+}
+
+/// Returns the application id (package name) the attestation was issued for, if it contains one.
+///
+/// The hardware enforced attestation application id takes precedence over the software enforced one.
+pub fn application_id(attestation: &Attestation) -> Option<PackageName> {
+    attestation_application_id(attestation)
+        .and_then(|id| id.package_infos.first())
+        .map(|package_info| package_info.package_name.clone())
+}
+
+/// Ensures the attestation was issued for one of the [AllowedApplicationIds], if any are configured.
+///
+/// Every package the attestation was issued for is checked, since applications sharing a user id are attested
+/// together. A package is allowed if its name is allowed and one of the attested signer certificate digests is the one
+/// allowed for that name.
+///
+/// Returns the application id the attestation was issued for, if it contains one. That is the allowed package if
+/// [AllowedApplicationIds] are configured.
+pub(crate) fn ensure_application_id_allowed<T: Config>(
+    attestation: &Attestation,
+) -> Result<Option<PackageName>, Error<T>> {
+    let allowed = <AllowedApplicationIds<T>>::get();
+    if allowed.is_empty() {
+        return Ok(application_id(attestation));
+    }
+    let id = attestation_application_id(attestation).ok_or(Error::<T>::ApplicationIdNotAllowed)?;
+    let package_info = id
+        .package_infos
+        .iter()
+        .find(|package_info| {
+            allowed.iter().any(|allowed_id| {
+                allowed_id.package_name.as_slice() == package_info.package_name.as_slice()
+                    && id.signature_digests.contains(&allowed_id.signer_digest)
+            })
+        })
+        .ok_or(Error::<T>::ApplicationIdNotAllowed)?;
+    Ok(Some(package_info.package_name.clone()))
+}
+
+fn now<T: Config>() -> Result<u64, Error<T>> {
+    T::UnixTime::now()
         .as_millis()
         .try_into()
-        .map_err(|_| Error::<T>::FailedTimestampConversion)?;
+        .map_err(|_| Error::<T>::FailedTimestampConversion)
+}
+
+/// Ensures the attestation is not expired.
+pub(crate) fn ensure_not_expired<T: Config>(attestation: &Attestation) -> Result<(), Error<T>> {
+    let now = now::<T>()?;
 
     if now >= attestation.validity.not_after || now < attestation.validity.not_before {
         return Err(Error::<T>::AttestationCertificateNotValid);
@@ -98,6 +180,39 @@ pub(crate) fn ensure_not_revoked<T: Config>(attestation: &Attestation) -> Result
     Ok(())
 }
 
+/// Validates the CRL against the configured [CrlIssuer] and returns its `thisUpdate` and the revoked serial numbers.
+///
+/// Ensures the CRL is currently valid and newer than the last applied CRL.
+pub fn validate_signed_crl<T: Config>(
+    crl: &CrlInput,
+) -> Result<(u64, Vec<SerialNumber>), Error<T>> {
+    let issuer = <CrlIssuer<T>>::get().ok_or(Error::<T>::CrlIssuerNotConfigured)?;
+    let crl = validate_crl(crl, &issuer).map_err(|_| Error::<T>::CrlValidationFailed)?;
+
+    let now = now::<T>()?;
+    ensure!(crl.this_update <= now, Error::<T>::CrlNotYetValid);
+    ensure!(
+        crl.next_update
+            .map(|next_update| now < next_update)
+            .unwrap_or(false),
+        Error::<T>::CrlExpired
+    );
+    ensure!(
+        crl.this_update > <LastCrlUpdate<T>>::get(),
+        Error::<T>::CrlOutdated
+    );
+
+    let serial_numbers = crl
+        .serial_numbers
+        .into_iter()
+        .map(|serial_number| {
+            SerialNumber::try_from(serial_number).map_err(|_| Error::<T>::CannotGetCrlSerialNumber)
+        })
+        .collect::<Result<Vec<_>, Error<T>>>()?;
+
+    Ok((crl.this_update, serial_numbers))
+}
+
 /// Ensures the provided public key correponds to the provided account id.
 fn ensure_valid_public_key_for_source<T: Config>(
     source: &T::AccountId,
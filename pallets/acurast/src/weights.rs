@@ -55,7 +55,9 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Storage: AcurastMarketplace AssignedProcessors (r:0 w:1)
 	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredJobRegistration (r:0 w:1)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:0 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
 	fn register() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `710`
@@ -64,14 +66,16 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(78_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 35808))
 			.saturating_add(T::DbWeight::get().reads(11))
-			.saturating_add(T::DbWeight::get().writes(9))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
 	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
 	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace JobBudgets (r:1 w:1)
 	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredJobRegistration (r:0 w:1)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:0 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
 	fn deregister() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `290`
@@ -80,39 +84,44 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(23_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 6996))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
-	/// Storage: Acurast StoredJobRegistration (r:1 w:1)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:1 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:0)
 	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1, 1000]`.
 	fn update_allowed_sources(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `637`
-		//  Estimated: `41759`
+		//  Estimated: `44320`
 		// Minimum execution time: 24_000_000 picoseconds.
 		Weight::from_parts(24_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 41759))
+			.saturating_add(Weight::from_parts(0, 44320))
 			// Standard Error: 12_391
 			.saturating_add(Weight::from_parts(1_109_995, 0).saturating_mul(x.into()))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
 	/// Storage: Timestamp Now (r:1 w:0)
 	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Acurast AllowedApplicationIds (r:1 w:0)
+	/// Proof: Acurast AllowedApplicationIds (max_values: Some(1), max_size: Some(653), added: 1148, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredRevokedCertificate (r:4 w:0)
 	/// Proof: Acurast StoredRevokedCertificate (max_values: None, max_size: Some(37), added: 2512, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredAttestation (r:0 w:1)
 	/// Proof: Acurast StoredAttestation (max_values: None, max_size: Some(11622), added: 14097, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
 	fn submit_attestation() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `250`
-		//  Estimated: `12531`
+		//  Estimated: `12663`
 		// Minimum execution time: 9_665_000_000 picoseconds.
 		Weight::from_parts(9_709_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 12531))
-			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(Weight::from_parts(0, 12663))
+			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
 	/// Storage: Acurast StoredRevokedCertificate (r:0 w:1)
@@ -127,37 +136,104 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
 	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
 	/// Storage: Acurast ExecutionEnvironment (r:0 w:1)
 	/// Proof: Acurast ExecutionEnvironment (max_values: None, max_size: Some(10743), added: 13218, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1, 10]`.
 	fn set_environment(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `330`
-		//  Estimated: `38260`
+		//  Estimated: `6203`
 		// Minimum execution time: 27_500_000 picoseconds.
 		Weight::from_parts(28_198_520, 0)
-			.saturating_add(Weight::from_parts(0, 38260))
+			.saturating_add(Weight::from_parts(0, 6203))
 			// Standard Error: 4_614
 			.saturating_add(Weight::from_parts(500_209, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
 	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
 	/// Storage: Acurast ExecutionEnvironment (r:0 w:1)
 	/// Proof: Acurast ExecutionEnvironment (max_values: None, max_size: Some(10743), added: 13218, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1, 10]`.
 	fn set_environments(_envs: u32, vars: u32) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `330`
-		//  Estimated: `38260`
+		//  Estimated: `6203`
 		// Minimum execution time: 27_500_000 picoseconds.
 		Weight::from_parts(28_198_520, 0)
-			.saturating_add(Weight::from_parts(0, 38260))
+			.saturating_add(Weight::from_parts(0, 6203))
 			// Standard Error: 4_614
 			.saturating_add(Weight::from_parts(500_209, 0).saturating_mul(vars.into()))
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: Acurast CrlIssuer (r:0 w:1)
+	/// Proof: Acurast CrlIssuer (max_values: Some(1), max_size: Some(3002), added: 3497, mode: MaxEncodedLen)
+	/// Storage: Acurast LastCrlUpdate (r:0 w:1)
+	/// Proof: Acurast LastCrlUpdate (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn set_crl_issuer() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Acurast CrlIssuer (r:1 w:0)
+	/// Proof: Acurast CrlIssuer (max_values: Some(1), max_size: Some(3002), added: 3497, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Acurast LastCrlUpdate (r:1 w:1)
+	/// Proof: Acurast LastCrlUpdate (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn submit_signed_crl() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `340`
+		//  Estimated: `4487`
+		// Minimum execution time: 118_000_000 picoseconds.
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4487))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Acurast AllowedApplicationIds (r:0 w:1)
+	/// Proof: Acurast AllowedApplicationIds (max_values: Some(1), max_size: Some(653), added: 1148, mode: MaxEncodedLen)
+	/// The range of component `x` is `[0, 4]`.
+	fn update_allowed_application_ids(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(8_512_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			// Standard Error: 3_421
+			.saturating_add(Weight::from_parts(41_250, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Acurast TrustedAttestationRoots (r:1 w:1)
+	/// Proof: Acurast TrustedAttestationRoots (max_values: Some(1), max_size: Some(4105), added: 4600, mode: MaxEncodedLen)
+	fn add_trusted_root() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3208`
+		//  Estimated: `5596`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5596))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Acurast TrustedAttestationRoots (r:1 w:1)
+	/// Proof: Acurast TrustedAttestationRoots (max_values: Some(1), max_size: Some(4105), added: 4600, mode: MaxEncodedLen)
+	fn remove_trusted_root() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3208`
+		//  Estimated: `5596`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5596))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
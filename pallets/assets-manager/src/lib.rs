@@ -9,16 +9,21 @@ mod stub;
 mod tests;
 
 mod migration;
+mod types;
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
 pub mod weights;
 
-use sp_runtime::traits::StaticLookup;
+use core::marker::PhantomData;
+use sp_runtime::traits::{MaybeEquivalence, StaticLookup};
+use xcm::latest::MultiLocation;
+use xcm::prelude::AssetId;
 
 pub use pallet::*;
 use sp_std::prelude::*;
+pub use types::*;
 pub use weights::WeightInfo;
 
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
@@ -28,8 +33,6 @@ pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
-    use xcm::latest::MultiLocation;
-    use xcm::prelude::{AssetId, GeneralIndex, PalletInstance, Parachain, X3};
 
     pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
@@ -52,9 +55,8 @@ pub mod pallet {
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
-        /// Genesis assets: `internal asset ID -> asset ID` (Statemint's general index)
-        // TODO generalize asset ID to any XCM AssetID once structs derive deserialize (merged with XCM-3)
-        pub assets: Vec<(<T as pallet_assets::Config<I>>::AssetId, u32, u8, u128)>,
+        /// Genesis assets: `internal asset ID -> asset ID`
+        pub assets: Vec<(<T as pallet_assets::Config<I>>::AssetId, GenesisAssetId)>,
     }
 
     impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
@@ -66,17 +68,8 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
         fn build(&self) {
-            for (internal_asset_id, parachain, pallet_instance, general_index) in
-                self.assets.clone()
-            {
-                let asset_id = AssetId::Concrete(MultiLocation::new(
-                    1,
-                    X3(
-                        Parachain(parachain),
-                        PalletInstance(pallet_instance),
-                        GeneralIndex(general_index),
-                    ),
-                ));
+            for (internal_asset_id, asset) in self.assets.clone() {
+                let asset_id: AssetId = asset.try_into().expect("Invalid genesis asset id");
                 assert!(
                     !<AssetIndex<T, I>>::contains_key(&internal_asset_id),
                     "Asset internal id already in use"
@@ -113,6 +106,8 @@ pub mod pallet {
         CreationNotAllowed,
         AssetNotIndexed,
         InvalidAssetIndex,
+        /// The asset is indexed by an [`AssetId::Abstract`] identifier and has no location.
+        AbstractAssetHasNoLocation,
     }
 
     #[pallet::hooks]
@@ -245,5 +240,43 @@ pub mod pallet {
 
             Ok(true)
         }
+
+        /// Returns the internal asset ID for the asset indexed by its concrete `location`.
+        pub fn internal_asset_id(
+            location: &MultiLocation,
+        ) -> Result<<T as pallet_assets::Config<I>>::AssetId, Error<T, I>> {
+            <ReverseAssetIndex<T, I>>::get(&AssetId::Concrete(*location))
+                .ok_or(Error::<T, I>::AssetNotIndexed)
+        }
+
+        /// Returns the location of the asset indexed with the internal asset `id`.
+        ///
+        /// Fails with [`Error::AbstractAssetHasNoLocation`] for assets indexed by an [`AssetId::Abstract`] identifier.
+        pub fn asset_location(
+            id: &<T as pallet_assets::Config<I>>::AssetId,
+        ) -> Result<MultiLocation, Error<T, I>> {
+            match <AssetIndex<T, I>>::get(id).ok_or(Error::<T, I>::AssetNotIndexed)? {
+                AssetId::Concrete(location) => Ok(location),
+                AssetId::Abstract(_) => Err(Error::<T, I>::AbstractAssetHasNoLocation),
+            }
+        }
+    }
+}
+
+/// Converts between asset locations and internal asset IDs using the asset index of this pallet.
+///
+/// Suitable as a matcher for assets in XCM configuration, e.g. with `xcm_builder::ConvertedConcreteId`.
+pub struct LocationToAssetId<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static>
+    MaybeEquivalence<MultiLocation, <T as pallet_assets::Config<I>>::AssetId>
+    for LocationToAssetId<T, I>
+{
+    fn convert(location: &MultiLocation) -> Option<<T as pallet_assets::Config<I>>::AssetId> {
+        Pallet::<T, I>::internal_asset_id(location).ok()
+    }
+
+    fn convert_back(id: &<T as pallet_assets::Config<I>>::AssetId) -> Option<MultiLocation> {
+        Pallet::<T, I>::asset_location(id).ok()
     }
 }
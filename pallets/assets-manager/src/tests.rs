@@ -1,7 +1,10 @@
 #![cfg(test)]
 
-use crate::{mock::*, stub::*, Error};
+use crate::{mock::*, stub::*, Error, LocationToAssetId};
 use frame_support::{assert_err, assert_ok};
+use sp_runtime::{traits::MaybeEquivalence, BuildStorage};
+use xcm::latest::MultiLocation;
+use xcm::prelude::{GeneralIndex, GeneralKey, PalletInstance, Parachain, X2, X3};
 
 #[test]
 fn test_create_mapped_asset() {
@@ -62,3 +65,97 @@ fn test_create_mapped_asset_failure_2() {
         assert_err!(call, Error::<Test>::AssetAlreadyIndexed);
     });
 }
+
+#[test]
+fn test_create_mapped_relay_native_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        let location = MultiLocation::parent();
+        assert_ok!(AcurastAssetManager::force_create(
+            RuntimeOrigin::root(),
+            codec::Compact(1),
+            xcm::latest::AssetId::Concrete(location),
+            alice_account_id().into(),
+            true,
+            1,
+        ));
+        assert_eq!(AcurastAssetManager::internal_asset_id(&location), Ok(1));
+        assert_eq!(AcurastAssetManager::asset_location(&1), Ok(location));
+        assert_eq!(LocationToAssetId::<Test>::convert(&location), Some(1));
+        assert_eq!(LocationToAssetId::<Test>::convert_back(&1), Some(location));
+    });
+}
+
+#[test]
+fn test_abstract_asset_has_no_location() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(AcurastAssetManager::create(
+            RuntimeOrigin::signed(alice_account_id()),
+            codec::Compact(0),
+            xcm::latest::AssetId::Abstract([0; 32]),
+            alice_account_id().into(),
+            1,
+        ));
+        assert_eq!(
+            AcurastAssetManager::asset_location(&0),
+            Err(Error::<Test>::AbstractAssetHasNoLocation)
+        );
+        assert_eq!(
+            AcurastAssetManager::asset_location(&1),
+            Err(Error::<Test>::AssetNotIndexed)
+        );
+        assert_eq!(
+            AcurastAssetManager::internal_asset_id(&MultiLocation::parent()),
+            Err(Error::<Test>::AssetNotIndexed)
+        );
+        assert_eq!(LocationToAssetId::<Test>::convert_back(&0), None);
+    });
+}
+
+#[test]
+fn test_genesis_assets_from_json() {
+    let config: crate::GenesisConfig<Test> = serde_json::from_str(
+        r#"{
+            "assets": [
+                [22, { "statemint": { "parachain": 1000, "palletInstance": 50, "generalIndex": 22 } }],
+                [23, "relayNative"],
+                [24, { "concrete": { "parents": 1, "interior": [{ "parachain": 2000 }, { "generalKey": { "length": 1, "data": [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] } }] } }],
+                [25, { "abstract": [2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2] }]
+            ]
+        }"#,
+    )
+    .unwrap();
+    let mut key = [0u8; 32];
+    key[0] = 1;
+
+    let mut ext: sp_io::TestExternalities = config.build_storage().unwrap().into();
+    ext.execute_with(|| {
+        assert_eq!(
+            AcurastAssetManager::internal_asset_id(&MultiLocation::new(
+                1,
+                X3(Parachain(1000), PalletInstance(50), GeneralIndex(22))
+            )),
+            Ok(22)
+        );
+        assert_eq!(
+            AcurastAssetManager::internal_asset_id(&MultiLocation::parent()),
+            Ok(23)
+        );
+        assert_eq!(
+            AcurastAssetManager::asset_location(&24),
+            Ok(MultiLocation::new(
+                1,
+                X2(
+                    Parachain(2000),
+                    GeneralKey {
+                        length: 1,
+                        data: key
+                    }
+                )
+            ))
+        );
+        assert_eq!(
+            AcurastAssetManager::asset_index(25),
+            Some(xcm::latest::AssetId::Abstract([2; 32]))
+        );
+    });
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use xcm::latest::{Junction, Junctions, MultiLocation};
+use xcm::prelude::{AssetId, GeneralIndex, GeneralKey, PalletInstance, Parachain};
+
+/// Helper to specify an [`AssetId`] in the genesis config, since the XCM types do not support (de)serialization.
+#[derive(RuntimeDebug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GenesisAssetId {
+    /// A Statemint-style concrete asset `(parents: 1, X3(Parachain, PalletInstance, GeneralIndex))`.
+    #[serde(rename_all = "camelCase")]
+    Statemint {
+        parachain: u32,
+        pallet_instance: u8,
+        general_index: u128,
+    },
+    /// The relay chain's native asset `(parents: 1, Here)`.
+    RelayNative,
+    /// A concrete asset with an arbitrary location.
+    Concrete {
+        parents: u8,
+        interior: Vec<GenesisJunction>,
+    },
+    /// An abstract asset identifier.
+    Abstract([u8; 32]),
+}
+
+/// Helper to specify a [`Junction`] in the genesis config, supporting the junctions commonly used to identify assets.
+#[derive(RuntimeDebug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GenesisJunction {
+    Parachain(u32),
+    PalletInstance(u8),
+    GeneralIndex(u128),
+    GeneralKey { length: u8, data: [u8; 32] },
+}
+
+impl From<GenesisJunction> for Junction {
+    fn from(junction: GenesisJunction) -> Self {
+        match junction {
+            GenesisJunction::Parachain(id) => Parachain(id),
+            GenesisJunction::PalletInstance(instance) => PalletInstance(instance),
+            GenesisJunction::GeneralIndex(index) => GeneralIndex(index),
+            GenesisJunction::GeneralKey { length, data } => GeneralKey { length, data },
+        }
+    }
+}
+
+impl TryFrom<GenesisAssetId> for AssetId {
+    type Error = &'static str;
+
+    fn try_from(asset: GenesisAssetId) -> Result<Self, Self::Error> {
+        Ok(match asset {
+            GenesisAssetId::Statemint {
+                parachain,
+                pallet_instance,
+                general_index,
+            } => AssetId::Concrete(MultiLocation::new(
+                1,
+                Junctions::X3(
+                    Parachain(parachain),
+                    PalletInstance(pallet_instance),
+                    GeneralIndex(general_index),
+                ),
+            )),
+            GenesisAssetId::RelayNative => AssetId::Concrete(MultiLocation::parent()),
+            GenesisAssetId::Concrete { parents, interior } => {
+                let mut junctions = Junctions::Here;
+                for junction in interior {
+                    junctions
+                        .push(junction.into())
+                        .map_err(|_| "Too many junctions in genesis asset location")?;
+                }
+                AssetId::Concrete(MultiLocation::new(parents, junctions))
+            }
+            GenesisAssetId::Abstract(id) => AssetId::Abstract(id),
+        })
+    }
+}
@@ -13,11 +13,11 @@ benchmarks_instance_pallet! {
 
         let leaves = x as NodeIndex;
         for i in 0..leaves {
-            _ = Pallet::<T, I>::send_message(action(i as u128));
+            _ = Pallet::<T, I>::send_message(action(i as u128), None);
         }
     }: {
         // insert last leave as the benchmarked one
-        _ = Pallet::<T, I>::send_message(action(leaves as u128));
+        _ = Pallet::<T, I>::send_message(action(leaves as u128), None);
     } verify {
         assert_eq!(crate::NumberOfLeaves::<T, I>::get(), leaves+1);
     }
@@ -53,10 +53,21 @@ impl LeafEncoder for TezosEncoder {
     ///
     /// where payload is dependent on `action` and encoded as a sequence of the [`Action`] variants' bodies, e.g.
     /// `[JobIdSequence, Vec<TezosAddressBytes>]` in the case of [`Action::AssignJob`].
+    ///
+    /// Messages with a [`crate::Message::valid_until`] are encoded in version 2 of the format, that appends the expiry:
+    ///
+    /// ```text
+    /// RawMessageV2 {
+    ///     id: u32,
+    ///     action: crate::RawAction,
+    ///     payload: Vec<u8>,
+    ///     valid_until: u64,
+    /// }
+    /// ```
     fn encode(message: &Leaf) -> Result<Vec<u8>, Self::Error> {
         let raw_action: RawAction = (&message.action).into();
         let action_str: &'static str = raw_action.into();
-        let data = data::pair(vec![
+        let mut fields = vec![
             data::int(message.id as i64),
             data::try_string(action_str)?,
             data::bytes(match &message.action {
@@ -78,11 +89,29 @@ impl LeafEncoder for TezosEncoder {
                     ]);
                     Micheline::pack(data, Some(finalize_payload_schema()))
                 }
+                Action::ActionChunk(chunk) => {
+                    let data = data::pair(vec![
+                        data::nat(Nat::from_integer(chunk.correlation)),
+                        data::nat(Nat::from_integer(chunk.index)),
+                        data::nat(Nat::from_integer(chunk.total)),
+                        data::bytes(chunk.bytes.clone()),
+                    ]);
+                    Micheline::pack(data, Some(action_chunk_payload_schema()))
+                }
                 Action::Noop => Ok(Default::default()),
             }?),
-        ]);
+        ];
 
-        Ok(Micheline::pack(data, Some(message_schema()))?)
+        match message.valid_until {
+            None => Ok(Micheline::pack(data::pair(fields), Some(message_schema()))?),
+            Some(valid_until) => {
+                fields.push(data::nat(Nat::from_integer(valid_until)));
+                Ok(Micheline::pack(
+                    data::pair(fields),
+                    Some(message_schema_v2()),
+                )?)
+            }
+        }
     }
 }
 
@@ -102,6 +131,24 @@ fn message_schema() -> &'static Micheline {
     })
 }
 
+#[cfg_attr(rustfmt, rustfmt::skip)]
+fn message_schema_v2() -> &'static Micheline {
+    static MESSAGE_SCHEMA_V2: OnceBox<Micheline> = OnceBox::new();
+    MESSAGE_SCHEMA_V2.get_or_init(|| {
+        let schema: Micheline = pair(vec![
+            // id
+            nat(),
+            // action
+            string(),
+            // payload
+            bytes(),
+            // valid_until
+            nat(),
+        ]);
+        Box::new(schema)
+    })
+}
+
 #[cfg_attr(rustfmt, rustfmt::skip)]
 fn assign_payload_schema() -> &'static Micheline {
     static ASSIGN_PAYLOAD_SCHEMA: OnceBox<Micheline> = OnceBox::new();
@@ -130,6 +177,24 @@ fn finalize_payload_schema() -> &'static Micheline {
     })
 }
 
+#[cfg_attr(rustfmt, rustfmt::skip)]
+fn action_chunk_payload_schema() -> &'static Micheline {
+    static ACTION_CHUNK_PAYLOAD_SCHEMA: OnceBox<Micheline> = OnceBox::new();
+    ACTION_CHUNK_PAYLOAD_SCHEMA.get_or_init(|| {
+        let schema: Micheline = pair(vec![
+            // correlation
+            nat(),
+            // index
+            nat(),
+            // total
+            nat(),
+            // bytes
+            bytes()
+        ]);
+        Box::new(schema)
+    })
+}
+
 pub struct DefaultTezosConfig;
 
 impl TargetChainConfig for DefaultTezosConfig {
@@ -176,6 +241,7 @@ mod tests {
         let encoded = tezos::TezosEncoder::encode(&Message {
             id: 5,
             action: Action::AssignJob(4, p256_public_key()),
+            valid_until: None,
         })?;
 
         let expected = &hex!("05070700050707010000001441535349474e5f4a4f425f50524f434553534f520a0000002005070700040a00000016000292251ea7a095ef710f65258ecd6b7246e209436e");
@@ -188,6 +254,7 @@ mod tests {
         let encoded = tezos::TezosEncoder::encode(&Message {
             id: 2,
             action: Action::FinalizeJob(3, 10),
+            valid_until: None,
         })?;
 
         let expected =
@@ -195,4 +262,18 @@ mod tests {
         assert_eq!(expected, &*encoded);
         Ok(())
     }
+
+    #[test]
+    fn test_pack_finalize_job_with_valid_until() -> Result<(), <TezosEncoder as LeafEncoder>::Error>
+    {
+        let encoded = tezos::TezosEncoder::encode(&Message {
+            id: 2,
+            action: Action::FinalizeJob(3, 10),
+            valid_until: Some(1_671_800_100_000),
+        })?;
+
+        let expected = &hex!("05070700020707010000000c46494e414c495a455f4a4f4207070a000000070507070003000a00a0d2e8f0a761");
+        assert_eq!(expected, &*encoded);
+        Ok(())
+    }
 }
@@ -19,6 +19,13 @@ sol! {
         bytes payload;
     }
 
+    struct EvmMessageV2 {
+        uint16 action;
+        uint128 messageId;
+        bytes payload;
+        uint64 validUntil;
+    }
+
     type JobId is uint128;
 
     struct EvmFinalizeJob {
@@ -30,6 +37,13 @@ sol! {
         uint128 job_id;
         address processor;
     }
+
+    struct EvmActionChunk {
+        uint64 correlation;
+        uint16 index;
+        uint16 total;
+        bytes chunk;
+    }
 }
 
 /// The [`LeafEncoder`] for Evm encoding.
@@ -52,6 +66,8 @@ impl LeafEncoder for EvmEncoder {
     ///
     /// where payload is dependent on `action` and encoded as a sequence of the [`Action`] variants' bodies, e.g.
     /// `[JobIdSequence, PubKey]` in the case of [`Action::AssignJob`].
+    ///
+    /// Messages with a [`crate::Message::valid_until`] are encoded as `EvmMessageV2`, that appends `validUntil: uint64`.
     fn encode(message: &Leaf) -> Result<Vec<u8>, Self::Error> {
         let raw_action: RawAction = (&message.action).into();
         let payload = match &message.action {
@@ -78,15 +94,39 @@ impl LeafEncoder for EvmEncoder {
 
                 EvmFinalizeJob::encode_single(&payload)
             }
+            Action::ActionChunk(chunk) => {
+                let payload = EvmActionChunk {
+                    correlation: chunk.correlation,
+                    index: chunk.index,
+                    total: chunk.total,
+                    chunk: chunk.bytes.clone(),
+                };
+
+                EvmActionChunk::encode_single(&payload)
+            }
             Action::Noop => [].to_vec(),
         };
-        let message = EvmMessage {
-            action: raw_action.into(),
-            messageId: message.id as u128,
-            payload,
-        };
+        match message.valid_until {
+            None => {
+                let message = EvmMessage {
+                    action: raw_action.into(),
+                    messageId: message.id as u128,
+                    payload,
+                };
 
-        Ok(EvmMessage::encode_single(&message))
+                Ok(EvmMessage::encode_single(&message))
+            }
+            Some(valid_until) => {
+                let message = EvmMessageV2 {
+                    action: raw_action.into(),
+                    messageId: message.id as u128,
+                    payload,
+                    validUntil: valid_until,
+                };
+
+                Ok(EvmMessageV2::encode_single(&message))
+            }
+        }
     }
 }
 
@@ -8,12 +8,14 @@ use sp_runtime::RuntimeDebug;
 
 use acurast_core_ink::types::{
     AssignProcessorPayloadV1, FinalizeJobPayloadV1, IncomingAction, IncomingActionPayloadV1,
-    VersionedIncomingActionPayload,
+    IncomingActionPayloadV2, VersionedIncomingActionPayload,
 };
 
 #[derive(RuntimeDebug)]
 pub enum SubstrateValidationError {
     UnexpectedPublicKey,
+    /// The ink! contracts do not support reassembling [`Action::ActionChunk`]s.
+    UnsupportedAction,
 }
 
 /// The [`LeafEncoder`] for Evm encoding.
@@ -36,6 +38,8 @@ impl LeafEncoder for SubstrateEncoder {
     ///
     /// where payload is dependent on `action` and encoded as a sequence of the [`Action`] variants' bodies, e.g.
     /// `[JobIdSequence, PubKey]` in the case of [`Action::AssignJob`].
+    ///
+    /// Messages with a [`crate::Message::valid_until`] are encoded as [`VersionedIncomingActionPayload::V2`].
     fn encode(message: &Leaf) -> Result<Vec<u8>, Self::Error> {
         let payload = match &message.action {
             Action::AssignJob(job_id, processor_public_key) => {
@@ -58,11 +62,19 @@ impl LeafEncoder for SubstrateEncoder {
 
                 IncomingActionPayloadV1::FinalizeJob(payload)
             }
+            Action::ActionChunk(_) => Err(Self::Error::UnsupportedAction)?,
             Action::Noop => IncomingActionPayloadV1::Noop,
         };
+        let payload = match message.valid_until {
+            None => VersionedIncomingActionPayload::V1(payload),
+            Some(valid_until) => VersionedIncomingActionPayload::V2(IncomingActionPayloadV2 {
+                valid_until,
+                action: payload,
+            }),
+        };
         let message = IncomingAction {
             id: message.id,
-            payload: VersionedIncomingActionPayload::V1(payload),
+            payload,
         };
 
         Ok(message.encode())
@@ -25,6 +25,14 @@ pub trait WeightInfo {
             .saturating_add(snapshot_root_hash)
             .saturating_add(snapshot_meta)
     }
+    fn force_snapshot() -> Weight {
+        // message_numbers, last_forced_snapshot_block
+        let check_weight = DbWeight::get().reads(2);
+        let last_forced_snapshot_block = DbWeight::get().writes(1);
+        check_weight
+            .saturating_add(Self::create_snapshot())
+            .saturating_add(last_forced_snapshot_block)
+    }
     fn send_message() -> Weight;
     fn send_message_actual_weight(peaks: NodeIndex) -> Weight {
         // Reading the parent hash.
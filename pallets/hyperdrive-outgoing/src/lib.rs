@@ -22,18 +22,22 @@ use core::ops::AddAssign;
 
 use frame_support::dispatch::{Pays, PostDispatchInfo};
 use frame_support::ensure;
+use frame_support::traits::UnixTime;
+use frame_support::weights::Weight;
+use frame_support::BoundedVec;
 use frame_system::pallet_prelude::{BlockNumberFor, HeaderFor};
 use sp_core::Get;
 use sp_runtime::traits::Block as BlockT;
 use sp_runtime::traits::NumberFor;
 use sp_runtime::traits::Saturating;
+use sp_runtime::SaturatedConversion;
 use sp_std::prelude::*;
 
 use mmr_lib::leaf_index_to_pos;
 pub use pallet::*;
 pub use types::{
-    Action, Leaf, LeafEncoder, LeafIndex, MMRError, Message, NodeIndex, OnNewRoot, Proof,
-    RawAction, SnapshotNumber, TargetChainConfig, TargetChainProof,
+    Action, ActionChunk, Leaf, LeafEncoder, LeafIndex, MMRError, Message, NodeIndex, OnNewRoot,
+    Proof, RawAction, SnapshotNumber, TargetChainConfig, TargetChainProof,
 };
 pub use utils::NodesUtils;
 
@@ -81,6 +85,9 @@ pub(crate) type TargetChainEncoderOf<T, I> =
 /// Encoder error returned by Hasher/Encoder used for this pallet instance.
 pub(crate) type HasherError<T, I> = <TargetChainEncoderOf<T, I> as LeafEncoder>::Error;
 
+/// An identifier chosen by the sender of a message to correlate it with its origin, e.g. an encoded job id.
+pub type CorrelationIdOf<T, I> = BoundedVec<u8, <T as Config<I>>::MaxCorrelationLen>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::pallet_prelude::*;
@@ -111,6 +118,9 @@ pub mod pallet {
         /// latest in block `b + MaximumBlocksBeforeSnapshot` a new snapshot will be taken.
         type MaximumBlocksBeforeSnapshot: Get<BlockNumberFor<Self>>;
 
+        /// The minimum number of blocks between two snapshots forced with [`Pallet::force_snapshot`], to prevent abuse.
+        type MinBlocksBetweenForcedSnapshots: Get<BlockNumberFor<Self>>;
+
         /// A hook to act on the new MMR root.
         ///
         /// For some applications it might be beneficial to make the MMR root available externally
@@ -119,6 +129,33 @@ pub mod pallet {
         /// Clients. Hook complexity should be `O(1)`.
         type OnNewRoot: OnNewRoot<HashOf<Self, I>>;
 
+        /// The time source used to determine if a message with [`Message::valid_until`] expired during proof generation.
+        type UnixTime: UnixTime;
+
+        /// The maximum length of a correlation id passed to [`Pallet::send_message_with_correlation`].
+        #[pallet::constant]
+        type MaxCorrelationLen: Get<u32>;
+
+        /// The number of most recent messages for which the correlation id is kept in [`MessageOrigin`] and
+        /// [`CorrelatedMessages`].
+        ///
+        /// Sending a message prunes the correlation of the message falling out of this window.
+        #[pallet::constant]
+        type CorrelationRetention: Get<LeafIndex>;
+
+        /// The maximum size in bytes of a message's target chain encoding, i.e. of a leaf's payload.
+        ///
+        /// Should not exceed what the target chain contract can process in one operation.
+        #[pallet::constant]
+        type MaxLeafPayloadSize: Get<u32>;
+
+        /// If `true`, messages exceeding [`Config::MaxLeafPayloadSize`] are split into [`Action::ActionChunk`] messages,
+        /// otherwise they are rejected with [`MMRError::PayloadTooLarge`].
+        ///
+        /// Only enable this if the target chain contract supports reassembling chunks.
+        #[pallet::constant]
+        type ChunkOversizedActions: Get<bool>;
+
         /// Weights for this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -212,6 +249,32 @@ pub mod pallet {
     pub type Nodes<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Identity, NodeIndex, HashOf<T, I>, OptionQuery>;
 
+    /// The block in which the latest snapshot was forced with [`Pallet::force_snapshot`].
+    #[pallet::storage]
+    #[pallet::getter(fn last_forced_snapshot_block)]
+    pub type LastForcedSnapshotBlock<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+    /// The correlation ids of messages sent with [`Pallet::send_message_with_correlation`] as a map `message_id -> correlation_id`.
+    ///
+    /// Only kept for the last [`Config::CorrelationRetention`] messages.
+    #[pallet::storage]
+    #[pallet::getter(fn message_origin)]
+    pub type MessageOrigin<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Identity, LeafIndex, CorrelationIdOf<T, I>, OptionQuery>;
+
+    /// Reverse index of [`MessageOrigin`] as a map `correlation_id -> message_id -> ()`.
+    #[pallet::storage]
+    pub type CorrelatedMessages<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        CorrelationIdOf<T, I>,
+        Identity,
+        LeafIndex,
+        (),
+        OptionQuery,
+    >;
+
     #[pallet::hooks]
     impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
         fn on_finalize(current_block: BlockNumberFor<T>) {
@@ -221,16 +284,7 @@ pub mod pallet {
                 && Self::maximum_blocks_before_snapshot_reached(current_block)
             {
                 // there was at least one message since last snapshot and enough blocks passed -> take snapshot
-                let current_snapshot = <NextSnapshotNumber<T, I>>::mutate(|s| {
-                    let current_snapshot = *s;
-                    s.add_assign(1);
-                    current_snapshot
-                });
-                SnapshotMeta::<T, I>::insert(
-                    current_snapshot,
-                    (RootHash::<T, I>::get(), current_block, next_message_number),
-                );
-                MessageNumbers::<T, I>::put((next_message_number, next_message_number));
+                Self::take_snapshot(current_block, next_message_number);
             }
 
             // always update the block-leaf-index (also when not taking a snapshot)
@@ -263,11 +317,17 @@ pub mod pallet {
     pub enum Event<T: Config<I>, I: 'static = ()> {
         /// A message was successfully sent. [JobId, SourceId, Assignment]
         MessageSent(Message),
+        /// A snapshot was forced. [who]
+        SnapshotForced(T::AccountId),
     }
 
     #[pallet::error]
     pub enum Error<T, I = ()> {
         MMRPush,
+        /// There is no message sent since the last snapshot.
+        NothingToSnapshot,
+        /// Less than [`Config::MinBlocksBetweenForcedSnapshots`] passed since the last forced snapshot.
+        ForcedSnapshotTooEarly,
     }
 
     #[pallet::call]
@@ -277,28 +337,103 @@ pub mod pallet {
         pub fn send_test_message(origin: OriginFor<T>) -> DispatchResult {
             ensure_root(origin)?;
 
-            Self::send_message(Action::Noop).map_err(|e| {
+            Self::send_message(Action::Noop, None).map_err(|e| {
                 e.log_error("send_message failed");
                 Error::<T, I>::MMRPush
             })?;
 
             Ok(().into())
         }
+
+        /// Takes a snapshot immediately instead of waiting for [`Config::MaximumBlocksBeforeSnapshot`], e.g. to make an urgent message provable.
+        ///
+        /// Fails if there is no message sent since the last snapshot or the last forced snapshot is more recent than
+        /// [`Config::MinBlocksBetweenForcedSnapshots`].
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::force_snapshot())]
+        pub fn force_snapshot(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let (included_message_number_excl, next_message_number) = Self::message_numbers();
+            ensure!(
+                included_message_number_excl < next_message_number,
+                Error::<T, I>::NothingToSnapshot
+            );
+
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            if let Some(last_forced) = Self::last_forced_snapshot_block() {
+                ensure!(
+                    current_block.saturating_sub(last_forced)
+                        >= T::MinBlocksBetweenForcedSnapshots::get(),
+                    Error::<T, I>::ForcedSnapshotTooEarly
+                );
+            }
+
+            Self::take_snapshot(current_block, next_message_number);
+            <LastForcedSnapshotBlock<T, I>>::put(current_block);
+
+            Self::deposit_event(Event::SnapshotForced(who));
+
+            Ok(().into())
+        }
     }
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
-    /// Sends a message with the given [`Action`] over Hyperdrive.
-    pub fn send_message(action: Action) -> Result<PostDispatchInfo, MMRError> {
+    /// Sends a message with the given [`Action`] over Hyperdrive, returning the assigned message id.
+    ///
+    /// If `valid_until` is provided (unix timestamp in milliseconds), the message is encoded such that target chains
+    /// reject it after that time. Use this for actions that become meaningless after a deadline,
+    /// e.g. the job's start time for an [`Action::AssignJob`].
+    ///
+    /// If the message's target chain encoding exceeds [`Config::MaxLeafPayloadSize`], it is either rejected with
+    /// [`MMRError::PayloadTooLarge`] or, if [`Config::ChunkOversizedActions`] is enabled, sent as consecutive
+    /// [`Action::ActionChunk`] messages. In the latter case the returned id is the one of the first chunk.
+    pub fn send_message(
+        action: Action,
+        valid_until: Option<u64>,
+    ) -> Result<(LeafIndex, PostDispatchInfo), MMRError> {
+        let (_included_message_number_excl, next_message_number) = Self::message_numbers();
+        let message = Message {
+            id: next_message_number,
+            action,
+            valid_until,
+        };
+
+        let encoded = TargetChainEncoderOf::<T, I>::encode(&message)
+            .map_err(|e| MMRError::Push.log_error(e))?;
+        if encoded.len() <= T::MaxLeafPayloadSize::get() as usize {
+            return Self::push_message(message);
+        }
+        ensure!(
+            T::ChunkOversizedActions::get(),
+            MMRError::PayloadTooLarge.log_debug(encoded.len())
+        );
+
+        // validate all chunks before sending any of them
+        let chunks = Self::chunk_message(&message, encoded)?;
+        let mut actual_weight = Weight::zero();
+        for chunk in chunks {
+            let (_, info) = Self::push_message(chunk)?;
+            actual_weight.saturating_accrue(info.actual_weight.unwrap_or_default());
+        }
+        Ok((
+            message.id,
+            PostDispatchInfo {
+                actual_weight: Some(actual_weight),
+                pays_fee: Pays::Yes,
+            },
+        ))
+    }
+
+    /// Appends `message` to the MMR as a new leaf. The message's id must be the next message number.
+    fn push_message(message: Message) -> Result<(LeafIndex, PostDispatchInfo), MMRError> {
         let leaves = Self::number_of_leaves();
         // used to calculate actual weight, see below
         let peaks_before = NodesUtils::new(leaves).number_of_peaks();
 
         let (included_message_number_excl, next_message_number) = Self::message_numbers();
-        let message = Message {
-            id: next_message_number,
-            action,
-        };
+        debug_assert_eq!(message.id, next_message_number);
 
         // append new leaf to MMR
         let mut mmr: ModuleMmr<mmr::storage::RuntimeStorage, T, I> = mmr::Mmr::new(leaves);
@@ -311,17 +446,127 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         <NumberOfLeaves<T, I>>::put(leaves);
         <RootHash<T, I>>::put(root);
         MessageNumbers::<T, I>::put((included_message_number_excl, next_message_number + 1));
+        Self::prune_correlation(message.id);
 
-        Self::deposit_event(Event::MessageSent(message));
+        Self::deposit_event(Event::MessageSent(message.clone()));
 
         // use peaks_after - peaks_before difference to calculate actual weight
         let peaks_after = NodesUtils::new(leaves).number_of_peaks();
-        Ok(PostDispatchInfo {
-            actual_weight: Some(T::WeightInfo::send_message_actual_weight(
-                peaks_before.max(peaks_after),
-            )),
-            pays_fee: Pays::Yes,
-        })
+        Ok((
+            message.id,
+            PostDispatchInfo {
+                actual_weight: Some(
+                    T::WeightInfo::send_message_actual_weight(peaks_before.max(peaks_after))
+                        .saturating_add(T::DbWeight::get().reads_writes(1, 2)),
+                ),
+                pays_fee: Pays::Yes,
+            },
+        ))
+    }
+
+    /// Splits the target chain `encoded` `message` into consecutive [`Action::ActionChunk`] messages starting at the
+    /// message's id, such that each of them fits into [`Config::MaxLeafPayloadSize`].
+    fn chunk_message(message: &Message, encoded: Vec<u8>) -> Result<Vec<Message>, MMRError> {
+        let max_size = T::MaxLeafPayloadSize::get() as usize;
+        let chunk_message = |index: u16, total: u16, bytes: Vec<u8>| Message {
+            id: message.id + index as u64,
+            action: Action::ActionChunk(ActionChunk {
+                correlation: message.id,
+                index,
+                total,
+                bytes,
+            }),
+            valid_until: message.valid_until,
+        };
+        let encoded_len = |message: &Message| {
+            TargetChainEncoderOf::<T, I>::encode(message)
+                .map(|encoded| encoded.len())
+                .map_err(|e| MMRError::Push.log_error(e))
+        };
+
+        // a chunk of a single byte with maximal numbers bounds the overhead of encoding a chunk,
+        // also for encodings that pad the chunk's bytes
+        let overhead =
+            encoded_len(&chunk_message(u16::MAX, u16::MAX, vec![0u8]))?.saturating_sub(1);
+        let chunk_size = max_size.saturating_sub(overhead);
+        ensure!(
+            chunk_size > 0,
+            MMRError::PayloadTooLarge.log_debug(("chunk overhead exceeds maximum", overhead))
+        );
+        let total: u16 = ((encoded.len() + chunk_size - 1) / chunk_size)
+            .try_into()
+            .map_err(|_| MMRError::PayloadTooLarge.log_debug(encoded.len()))?;
+
+        let chunks = encoded
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(index, bytes)| chunk_message(index as u16, total, bytes.to_vec()))
+            .collect::<Vec<Message>>();
+        for chunk in chunks.iter() {
+            ensure!(
+                encoded_len(chunk)? <= max_size,
+                MMRError::PayloadTooLarge.log_debug(("chunk exceeds maximum", chunk.id))
+            );
+        }
+        Ok(chunks)
+    }
+
+    /// Sends a message like [`Self::send_message`] and stores `correlation_id` for the assigned message id,
+    /// so that callers can correlate later failures or acknowledgements with the action's origin.
+    pub fn send_message_with_correlation(
+        action: Action,
+        valid_until: Option<u64>,
+        correlation_id: CorrelationIdOf<T, I>,
+    ) -> Result<(LeafIndex, PostDispatchInfo), MMRError> {
+        let (message_id, mut info) = Self::send_message(action, valid_until)?;
+
+        // all chunks of a chunked message share the correlation id
+        let (_included_message_number_excl, next_message_number) = Self::message_numbers();
+        for id in message_id..next_message_number {
+            <CorrelatedMessages<T, I>>::insert(&correlation_id, id, ());
+            <MessageOrigin<T, I>>::insert(id, correlation_id.clone());
+        }
+
+        info.actual_weight = info.actual_weight.map(|weight| {
+            weight.saturating_add(T::DbWeight::get().writes(2 * (next_message_number - message_id)))
+        });
+        Ok((message_id, info))
+    }
+
+    /// Removes the correlation of the message that fell out of the [`Config::CorrelationRetention`] window when
+    /// `message_id` got sent.
+    fn prune_correlation(message_id: LeafIndex) {
+        let Some(expired_id) = message_id.checked_sub(T::CorrelationRetention::get()) else {
+            return;
+        };
+        if let Some(correlation_id) = <MessageOrigin<T, I>>::take(expired_id) {
+            <CorrelatedMessages<T, I>>::remove(&correlation_id, expired_id);
+        }
+    }
+
+    /// Returns the ids of the messages sent with the given `correlation_id` in ascending order.
+    pub fn message_ids_by_correlation(correlation_id: Vec<u8>) -> Vec<LeafIndex> {
+        let Ok(correlation_id) = CorrelationIdOf::<T, I>::try_from(correlation_id) else {
+            return vec![];
+        };
+        let mut message_ids: Vec<LeafIndex> =
+            <CorrelatedMessages<T, I>>::iter_key_prefix(correlation_id).collect();
+        message_ids.sort();
+        message_ids
+    }
+
+    /// Takes a snapshot of the current [`RootHash`] including all messages up to `next_message_number` (exclusive).
+    fn take_snapshot(current_block: BlockNumberFor<T>, next_message_number: LeafIndex) {
+        let current_snapshot = <NextSnapshotNumber<T, I>>::mutate(|s| {
+            let current_snapshot = *s;
+            s.add_assign(1);
+            current_snapshot
+        });
+        SnapshotMeta::<T, I>::insert(
+            current_snapshot,
+            (RootHash::<T, I>::get(), current_block, next_message_number),
+        );
+        MessageNumbers::<T, I>::put((next_message_number, next_message_number));
     }
 
     /// Build offchain key from `parent_hash` of block that originally added node `pos` to MMR.
@@ -419,10 +664,15 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
     /// Leaves with their leaf index and position are part of the proof structure and contain the message encoded for the target chain.
     ///
     /// This function wraps [`Self::generate_proof`] and converts result to [`TargetChainProof`].
+    ///
+    /// If `skip_expired` is `true`, messages with a [`Message::valid_until`] in the past are excluded from the proof.
+    /// The returned [`TargetChainProof::next_message_number`] is still the exclusive end of the requested range,
+    /// so relayers advance past skipped messages. If all messages in the range expired, the proof contains no leaves.
     pub fn generate_target_chain_proof(
         next_message_number: LeafIndex,
         maximum_messages: Option<u64>,
         latest_known_snapshot_number: SnapshotNumber,
+        skip_expired: bool,
     ) -> Result<Option<TargetChainProof<HashOf<T, I>>>, MMRError> {
         let proof = Self::generate_proof(
             next_message_number,
@@ -431,6 +681,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         )?;
         proof
             .map(|(leaves, proof)| {
+                let next_message_number = next_message_number + leaves.len() as LeafIndex;
+                let (leaves, proof) = if skip_expired {
+                    Self::skip_expired(leaves, proof)?
+                } else {
+                    (leaves, proof)
+                };
                 let mmr_size = NodesUtils::new(proof.leaf_count).size();
                 let leaf_positions: Vec<NodeIndex> = proof
                     .leaf_indices
@@ -449,11 +705,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
                             position: position.to_owned(),
                             message: TargetChainEncoderOf::<T, I>::encode(leaf)
                                 .map_err(|_| MMRError::GenerateProof)?,
+                            valid_until: leaf.valid_until,
                         })
                     })
                     .collect::<Result<Vec<TargetChainProofLeaf>, MMRError>>()?;
                 Ok(TargetChainProof {
                     leaves,
+                    next_message_number,
                     mmr_size,
                     items: proof.items,
                 })
@@ -461,6 +719,39 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             .transpose()
     }
 
+    /// Removes expired leaves from a proof generated by [`Self::generate_proof`],
+    /// regenerating the proof for the remaining leaves against the same MMR size.
+    fn skip_expired(
+        leaves: Vec<Leaf>,
+        proof: Proof<HashOf<T, I>>,
+    ) -> Result<(Vec<Leaf>, Proof<HashOf<T, I>>), MMRError> {
+        let now: u64 = T::UnixTime::now().as_millis().saturated_into();
+        if !leaves.iter().any(|leaf| leaf.is_expired(now)) {
+            return Ok((leaves, proof));
+        }
+
+        let live_indices: Vec<LeafIndex> = proof
+            .leaf_indices
+            .iter()
+            .zip(leaves.iter())
+            .filter(|(_, leaf)| !leaf.is_expired(now))
+            .map(|(leaf_index, _)| *leaf_index)
+            .collect();
+        if live_indices.is_empty() {
+            return Ok((
+                vec![],
+                Proof {
+                    leaf_indices: vec![],
+                    leaf_count: proof.leaf_count,
+                    items: vec![],
+                },
+            ));
+        }
+
+        let mmr: ModuleMmr<mmr::storage::OffchainStorage, T, I> = mmr::Mmr::new(proof.leaf_count);
+        mmr.generate_proof(live_indices)
+    }
+
     /// Returns the snapshot MMR roots from `next_expected_snapshot_number, ...` onwards or an empty vec if no new snapshots.
     pub fn snapshot_roots(
         next_expected_snapshot_number: SnapshotNumber,
@@ -530,6 +821,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 sp_api::decl_runtime_apis! {
     /// API to interact with MMR pallet.
+    ///
+    /// Version 2 added `skip_expired` to `generate_target_chain_proof`.
+    /// Version 3 added `message_ids_by_correlation` and `message_correlation`.
+    #[api_version(3)]
     pub trait HyperdriveApi<MmrHash: codec::Codec> {
         /// Return the number of MMR leaves/messages on-chain.
         fn number_of_leaves(instance: HyperdriveInstance) -> LeafIndex;
@@ -544,15 +839,38 @@ sp_api::decl_runtime_apis! {
 
         fn snapshot_root(instance: HyperdriveInstance, next_expected_snapshot_number: SnapshotNumber) -> Result<Option<(SnapshotNumber, MmrHash)>, MMRError>;
 
+        /// Returns the ids of the messages sent with the given correlation id in ascending order.
+        ///
+        /// Only messages within the [`Config::CorrelationRetention`] window are returned.
+        #[api_version(3)]
+        fn message_ids_by_correlation(instance: HyperdriveInstance, correlation_id: Vec<u8>) -> Vec<LeafIndex>;
+
+        /// Returns the correlation id the message was sent with, if any.
+        #[api_version(3)]
+        fn message_correlation(instance: HyperdriveInstance, message_id: LeafIndex) -> Option<Vec<u8>>;
+
+        /// Generates a self-contained MMR proof for the messages in the range `[next_message_number..last_message_excl]`,
+        /// including expired messages.
+        #[changed_in(2)]
+        fn generate_target_chain_proof(
+            instance: HyperdriveInstance,
+            next_message_number: LeafIndex,
+            maximum_messages: Option<u64>,
+            latest_known_snapshot_number: SnapshotNumber,
+        ) -> Result<Option<TargetChainProof<MmrHash>>, MMRError>;
+
         /// Generates a self-contained MMR proof for the messages in the range `[next_message_number..last_message_excl]`.
         /// Leaves with their leaf index and position are part of the proof structure and contain the message encoded for the target chain.
         ///
+        /// If `skip_expired` is `true`, expired messages are excluded from the proof.
+        ///
         /// This function forwards to [`Pallet::generate_target_chain_proof`].
         fn generate_target_chain_proof(
             instance: HyperdriveInstance,
             next_message_number: LeafIndex,
             maximum_messages: Option<u64>,
             latest_known_snapshot_number: SnapshotNumber,
+            skip_expired: bool,
         ) -> Result<Option<TargetChainProof<MmrHash>>, MMRError>;
     }
 }
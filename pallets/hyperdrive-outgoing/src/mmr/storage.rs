@@ -87,7 +87,7 @@ where
         );
         // Try to retrieve the element from Off-chain DB.
         if let Some(elem) = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key) {
-            return Ok(codec::Decode::decode(&mut &*elem).ok());
+            return Ok(Node::decode_stored(&elem));
         }
 
         // Fall through to searching node using fork-specific key.
@@ -108,7 +108,7 @@ where
         // Retrieve the element from Off-chain DB.
         Ok(
             sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &temp_key)
-                .and_then(|v| codec::Decode::decode(&mut &*v).ok()),
+                .and_then(|v| Node::decode_stored(&v)),
         )
     }
 }
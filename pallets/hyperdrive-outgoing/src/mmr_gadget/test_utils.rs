@@ -364,11 +364,20 @@ sp_api::mock_impl_runtime_apis! {
             Err(MMRError::GenerateProof)
         }
 
+        fn message_ids_by_correlation(_instance: HyperdriveInstance, _correlation_id: Vec<u8>) -> Vec<LeafIndex> {
+            vec![]
+        }
+
+        fn message_correlation(_instance: HyperdriveInstance, _message_id: LeafIndex) -> Option<Vec<u8>> {
+            None
+        }
+
         fn generate_target_chain_proof(
             _instance: HyperdriveInstance,
             _next_message_number: LeafIndex,
             _maximum_messages: Option<u64>,
             _latest_known_snapshot_number: SnapshotNumber,
+            _skip_expired: bool,
         ) -> Result<Option<TargetChainProof<MmrHash>>, MMRError> {
             Err(MMRError::GenerateProof)
         }
@@ -1,6 +1,8 @@
 use frame_support::weights::Weight;
 use frame_support::{
-    parameter_types, traits::ConstU32, weights::constants::RocksDbWeight as DbWeight,
+    parameter_types,
+    traits::{ConstU32, UnixTime},
+    weights::constants::RocksDbWeight as DbWeight,
 };
 use pallet_acurast_hyperdrive::instances::TezosInstance;
 use sp_core::H256;
@@ -50,8 +52,14 @@ impl Config for Test {
     type MMRInfo = TezosInstance;
     type TargetChainConfig = DefaultTezosConfig;
     type OnNewRoot = ();
+    type UnixTime = MockUnixTime;
     type WeightInfo = ();
     type MaximumBlocksBeforeSnapshot = MaximumBlocksBeforeSnapshot;
+    type MinBlocksBetweenForcedSnapshots = MinBlocksBetweenForcedSnapshots;
+    type MaxCorrelationLen = ConstU32<64>;
+    type CorrelationRetention = CorrelationRetention;
+    type MaxLeafPayloadSize = MaxLeafPayloadSize;
+    type ChunkOversizedActions = ChunkOversizedActions;
 }
 
 impl WeightInfo for () {
@@ -64,4 +72,18 @@ parameter_types! {
     pub const BlockHashCount: BlockNumber = 2400;
 
     pub const MaximumBlocksBeforeSnapshot: u64 = 2;
+    pub const MinBlocksBetweenForcedSnapshots: u64 = 3;
+    pub const CorrelationRetention: u64 = 5;
+
+    pub static Now: u64 = 0;
+    pub static MaxLeafPayloadSize: u32 = 1024;
+    pub static ChunkOversizedActions: bool = false;
+}
+
+pub struct MockUnixTime;
+
+impl UnixTime for MockUnixTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_millis(Now::get())
+    }
 }
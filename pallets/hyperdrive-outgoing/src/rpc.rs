@@ -25,7 +25,7 @@ use jsonrpsee::{
     types::error::{CallError, ErrorObject},
 };
 use pallet_acurast_hyperdrive::instances::HyperdriveInstanceName;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
 use sp_runtime::traits::{HashingFor, MaybeSerializeDeserialize};
@@ -75,6 +75,8 @@ pub trait RpcInstance: Send + Sync {
 ///     /// Generates a self-contained MMR proof for the messages in the range `[next_message_number..last_message_excl]`.
 ///     /// Leaves with their leaf index and position are part of the proof structure and contain the message encoded for the target chain.
 ///     ///
+///     /// If `skip_expired` is `true`, messages that expired are excluded from the proof. Defaults to `false`.
+///     ///
 ///     /// This rpc calls into the runtime function [`crate::Pallet::generate_target_chain_proof`].
 ///     /// Optionally via `at`, a block hash at which the runtime should be queried can be specified.
 ///     #[method(name = "generateProof")]
@@ -83,6 +85,7 @@ pub trait RpcInstance: Send + Sync {
 ///         next_message_number: LeafIndex,
 ///         maximum_messages: Option<u64>,
 ///         latest_known_snapshot_number: SnapshotNumber,
+///         skip_expired: Option<bool>,
 ///     ) -> RpcResult<Option<TargetChainProof<MmrHash>>>;
 /// }
 /// ```
@@ -106,6 +109,8 @@ pub trait MmrApiServer<I: RpcInstance, BlockHash, MmrHash: MaybeSerializeDeseria
     #[doc = " Generates a self-contained MMR proof for the messages in the range `[next_message_number..last_message_excl]`."]
     #[doc = " Leaves with their leaf index and position are part of the proof structure and contain the message encoded for the target chain."]
     #[doc = ""]
+    #[doc = " If `skip_expired` is `true`, messages that expired are excluded from the proof. Defaults to `false`."]
+    #[doc = ""]
     #[doc = " This rpc calls into the runtime function [`crate::Pallet::generate_target_chain_proof`]."]
     #[doc = " Optionally via `at`, a block hash at which the runtime should be queried can be specified."]
     fn generate_target_chain_proof(
@@ -113,6 +118,7 @@ pub trait MmrApiServer<I: RpcInstance, BlockHash, MmrHash: MaybeSerializeDeseria
         next_message_number: LeafIndex,
         maximum_messages: Option<u64>,
         latest_known_snapshot_number: SnapshotNumber,
+        skip_expired: Option<bool>,
     ) -> RpcResult<Option<TargetChainProof<MmrHash>>>;
     #[doc = "Collects all the methods and subscriptions defined in the trait and adds them into a single `RpcModule`."]
     fn into_rpc(self) -> jsonrpsee::RpcModule<Self>
@@ -217,94 +223,119 @@ pub trait MmrApiServer<I: RpcInstance, BlockHash, MmrHash: MaybeSerializeDeseria
         }
         {
             let res = rpc.register_method(I::GENERATE_PROOF, |params, context| {
-                let (next_message_number, maximum_messages, latest_known_snapshot_number) =
-                    if params.is_object() {
-                        #[derive(jsonrpsee::core::__reexports::serde::Deserialize)]
-                        #[serde(crate = "jsonrpsee :: core :: __reexports :: serde")]
-                        struct ParamsObject<G0, G1, G2> {
-                            #[serde(alias = "next_message_number", alias = "nextMessageNumber")]
-                            next_message_number: G0,
-                            #[serde(alias = "maximum_messages", alias = "maximumMessages")]
-                            maximum_messages: G1,
-                            #[serde(
-                                alias = "latest_known_snapshot_number",
-                                alias = "latestKnownSnapshotNumber"
-                            )]
-                            latest_known_snapshot_number: G2,
+                let (
+                    next_message_number,
+                    maximum_messages,
+                    latest_known_snapshot_number,
+                    skip_expired,
+                ) = if params.is_object() {
+                    #[derive(jsonrpsee::core::__reexports::serde::Deserialize)]
+                    #[serde(crate = "jsonrpsee :: core :: __reexports :: serde")]
+                    struct ParamsObject<G0, G1, G2, G3> {
+                        #[serde(alias = "next_message_number", alias = "nextMessageNumber")]
+                        next_message_number: G0,
+                        #[serde(alias = "maximum_messages", alias = "maximumMessages")]
+                        maximum_messages: G1,
+                        #[serde(
+                            alias = "latest_known_snapshot_number",
+                            alias = "latestKnownSnapshotNumber"
+                        )]
+                        latest_known_snapshot_number: G2,
+                        #[serde(alias = "skip_expired", alias = "skipExpired")]
+                        skip_expired: G3,
+                    }
+                    let parsed: ParamsObject<LeafIndex, Option<u64>, SnapshotNumber, Option<bool>> =
+                        params.parse().map_err(|e| {
+                            jsonrpsee::tracing::error!(
+                                "Failed to parse JSON-RPC params as object: {}",
+                                e
+                            );
+                            e
+                        })?;
+                    (
+                        parsed.next_message_number,
+                        parsed.maximum_messages,
+                        parsed.latest_known_snapshot_number,
+                        parsed.skip_expired,
+                    )
+                } else {
+                    let mut seq = params.sequence();
+                    let next_message_number: LeafIndex = match seq.next() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            jsonrpsee::tracing::error!(
+                                concat!(
+                                    "Error parsing \"",
+                                    stringify!(next_message_number),
+                                    "\" as \"",
+                                    stringify!(LeafIndex),
+                                    "\": {:?}"
+                                ),
+                                e
+                            );
+                            return Err(e.into());
+                        }
+                    };
+                    let maximum_messages: Option<u64> = match seq.optional_next() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            jsonrpsee::tracing::error!(
+                                concat!(
+                                    "Error parsing optional \"",
+                                    stringify!(maximum_messages),
+                                    "\" as \"",
+                                    stringify!(Option<u64>),
+                                    "\": {:?}"
+                                ),
+                                e
+                            );
+                            return Err(e.into());
+                        }
+                    };
+                    let latest_known_snapshot_number: SnapshotNumber = match seq.next() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            jsonrpsee::tracing::error!(
+                                concat!(
+                                    "Error parsing \"",
+                                    stringify!(latest_known_snapshot_number),
+                                    "\" as \"",
+                                    stringify!(SnapshotNumber),
+                                    "\": {:?}"
+                                ),
+                                e
+                            );
+                            return Err(e.into());
                         }
-                        let parsed: ParamsObject<LeafIndex, Option<u64>, SnapshotNumber> =
-                            params.parse().map_err(|e| {
-                                jsonrpsee::tracing::error!(
-                                    "Failed to parse JSON-RPC params as object: {}",
-                                    e
-                                );
+                    };
+                    let skip_expired: Option<bool> = match seq.optional_next() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            jsonrpsee::tracing::error!(
+                                concat!(
+                                    "Error parsing optional \"",
+                                    stringify!(skip_expired),
+                                    "\" as \"",
+                                    stringify!(Option<bool>),
+                                    "\": {:?}"
+                                ),
                                 e
-                            })?;
-                        (
-                            parsed.next_message_number,
-                            parsed.maximum_messages,
-                            parsed.latest_known_snapshot_number,
-                        )
-                    } else {
-                        let mut seq = params.sequence();
-                        let next_message_number: LeafIndex = match seq.next() {
-                            Ok(v) => v,
-                            Err(e) => {
-                                jsonrpsee::tracing::error!(
-                                    concat!(
-                                        "Error parsing \"",
-                                        stringify!(next_message_number),
-                                        "\" as \"",
-                                        stringify!(LeafIndex),
-                                        "\": {:?}"
-                                    ),
-                                    e
-                                );
-                                return Err(e.into());
-                            }
-                        };
-                        let maximum_messages: Option<u64> = match seq.optional_next() {
-                            Ok(v) => v,
-                            Err(e) => {
-                                jsonrpsee::tracing::error!(
-                                    concat!(
-                                        "Error parsing optional \"",
-                                        stringify!(maximum_messages),
-                                        "\" as \"",
-                                        stringify!(Option<u64>),
-                                        "\": {:?}"
-                                    ),
-                                    e
-                                );
-                                return Err(e.into());
-                            }
-                        };
-                        let latest_known_snapshot_number: SnapshotNumber = match seq.next() {
-                            Ok(v) => v,
-                            Err(e) => {
-                                jsonrpsee::tracing::error!(
-                                    concat!(
-                                        "Error parsing \"",
-                                        stringify!(latest_known_snapshot_number),
-                                        "\" as \"",
-                                        stringify!(SnapshotNumber),
-                                        "\": {:?}"
-                                    ),
-                                    e
-                                );
-                                return Err(e.into());
-                            }
-                        };
-                        (
-                            next_message_number,
-                            maximum_messages,
-                            latest_known_snapshot_number,
-                        )
+                            );
+                            return Err(e.into());
+                        }
                     };
+                    (
+                        next_message_number,
+                        maximum_messages,
+                        latest_known_snapshot_number,
+                        skip_expired,
+                    )
+                };
                 context.generate_target_chain_proof(
                     next_message_number,
                     maximum_messages,
                     latest_known_snapshot_number,
+                    skip_expired,
                 )
             });
             debug_assert!(
@@ -366,6 +397,8 @@ where
     #[doc = " Generates a self-contained MMR proof for the messages in the range `[next_message_number..last_message_excl]`."]
     #[doc = " Leaves with their leaf index and position are part of the proof structure and contain the message encoded for the target chain."]
     #[doc = ""]
+    #[doc = " If `skip_expired` is `true`, messages that expired are excluded from the proof. Defaults to `false`."]
+    #[doc = ""]
     #[doc = " This rpc calls into the runtime function [`crate::Pallet::generate_target_chain_proof`]."]
     #[doc = " Optionally via `at`, a block hash at which the runtime should be queried can be specified."]
     async fn generate_target_chain_proof(
@@ -373,6 +406,7 @@ where
         next_message_number: LeafIndex,
         maximum_messages: Option<u64>,
         latest_known_snapshot_number: SnapshotNumber,
+        skip_expired: Option<bool>,
     ) -> RpcResult<Option<TargetChainProof<MmrHash>>> {
         let params = {
             {
@@ -398,6 +432,13 @@ where
                         err
                     );
                 }
+                if let Err(err) = params.insert(skip_expired) {
+                    panic!(
+                        "Parameter `{}` cannot be serialized: {:?}",
+                        stringify!(skip_expired),
+                        err
+                    );
+                }
                 params
             }
         };
@@ -476,19 +517,42 @@ where
         next_message_number: LeafIndex,
         maximum_messages: Option<u64>,
         latest_known_snapshot_number: SnapshotNumber,
+        skip_expired: Option<bool>,
     ) -> RpcResult<Option<TargetChainProof<MmrHash>>> {
         let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        let skip_expired = skip_expired.unwrap_or(false);
 
-        let proof = api
-            .generate_target_chain_proof(
-                self.client.info().best_hash,
+        let api_version = api
+            .api_version::<dyn HyperdriveApi<Block, MmrHash>>(at)
+            .map_err(runtime_error_into_rpc_error)?
+            .ok_or_else(|| runtime_error_into_rpc_error("HyperdriveApi not supported"))?;
+        let proof = if api_version >= 2 {
+            api.generate_target_chain_proof(
+                at,
                 I::NAME,
                 next_message_number,
                 maximum_messages,
                 latest_known_snapshot_number,
+                skip_expired,
             )
-            .map_err(runtime_error_into_rpc_error)?
-            .map_err(mmr_error_into_rpc_error)?;
+        } else if !skip_expired {
+            #[allow(deprecated)]
+            api.generate_target_chain_proof_before_version_2(
+                at,
+                I::NAME,
+                next_message_number,
+                maximum_messages,
+                latest_known_snapshot_number,
+            )
+        } else {
+            return Err(runtime_error_into_rpc_error(
+                "skipping expired messages not supported by runtime",
+            )
+            .into());
+        }
+        .map_err(runtime_error_into_rpc_error)?
+        .map_err(mmr_error_into_rpc_error)?;
 
         Ok(proof)
     }
@@ -508,6 +572,7 @@ fn mmr_error_into_rpc_error(err: MMRError) -> CallError {
             MMRError::Verify => 8,
             MMRError::LeafNotFound => 9,
             MMRError::InconsistentSnapshotMeta => 10,
+            MMRError::PayloadTooLarge => 11,
         };
 
     CallError::Custom(ErrorObject::owned(
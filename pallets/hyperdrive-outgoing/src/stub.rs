@@ -28,6 +28,7 @@ pub fn message(id: u128) -> Message {
     Message {
         id: id as u64,
         action: Action::AssignJob(id, p256_public_key()),
+        valid_until: None,
     }
 }
 
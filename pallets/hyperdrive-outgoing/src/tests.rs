@@ -1,10 +1,11 @@
-use frame_support::assert_ok;
 use frame_support::pallet_prelude::*;
+use frame_support::{assert_err, assert_ok};
 use hex_literal::hex;
 use mmr_lib::helper;
+use serde::{Deserialize, Serialize};
 use sp_core::{
     offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt},
-    H256,
+    Bytes, H256,
 };
 use sp_runtime::BuildStorage;
 
@@ -66,7 +67,7 @@ fn decode_node(v: Vec<u8>) -> NodeOf<Test, ()> {
 fn send_messages(num: usize) {
     // given
     for id in 0..num {
-        assert_ok!(HyperdriveOutgoing::send_message(action(id as u128)));
+        assert_ok!(HyperdriveOutgoing::send_message(action(id as u128), None));
     }
 }
 
@@ -86,7 +87,7 @@ fn should_start_empty() {
 
         // when
         let weight = next_block();
-        assert_ok!(HyperdriveOutgoing::send_message(action(0)));
+        assert_ok!(HyperdriveOutgoing::send_message(action(0), None));
 
         // then
         assert_eq!(crate::NumberOfLeaves::<Test>::get(), 1);
@@ -114,7 +115,7 @@ fn should_append_to_mmr_when_send_message_is_called() {
     let (parent_b1, parent_b2) = ext.execute_with(|| {
         // when
         next_block();
-        assert_ok!(HyperdriveOutgoing::send_message(action(0)));
+        assert_ok!(HyperdriveOutgoing::send_message(action(0), None));
         let parent_b1 = <frame_system::Pallet<Test>>::parent_hash();
 
         // then
@@ -136,7 +137,7 @@ fn should_append_to_mmr_when_send_message_is_called() {
 
         // when
         next_block();
-        assert_ok!(HyperdriveOutgoing::send_message(action(1)));
+        assert_ok!(HyperdriveOutgoing::send_message(action(1), None));
         let parent_b2 = <frame_system::Pallet<Test>>::parent_hash();
 
         // then
@@ -276,7 +277,7 @@ fn should_generate_verify_proofs_correctly() {
     let num: u64 = 7;
     ext.execute_with(|| {
         for id in 0..num {
-            assert_ok!(HyperdriveOutgoing::send_message(action(id as u128)));
+            assert_ok!(HyperdriveOutgoing::send_message(action(id as u128), None));
 
             // move to next block
             // this makes sure that 3 snapshots are taken, which is after 6 blocks for mock config with MaximumBlocksBeforeSnapshot==2
@@ -308,7 +309,8 @@ fn should_generate_verify_proofs_correctly() {
         let _target_chain_proofs = (0_u64..=num)
             .into_iter()
             .map(|next_message_number| {
-                Pallet::<Test>::generate_target_chain_proof(next_message_number, None, 2).unwrap()
+                Pallet::<Test>::generate_target_chain_proof(next_message_number, None, 2, false)
+                    .unwrap()
             })
             .collect::<Vec<_>>();
 
@@ -533,6 +535,94 @@ fn should_generate_maximum_messages() {
     });
 }
 
+fn send_messages_with_valid_until(valid_until: &[Option<u64>]) {
+    for (id, valid_until) in valid_until.iter().enumerate() {
+        assert_ok!(HyperdriveOutgoing::send_message(
+            action(id as u128),
+            *valid_until
+        ));
+    }
+}
+
+#[test]
+fn should_generate_target_chain_proof_with_expired_messages() {
+    let _ = env_logger::try_init();
+    let mut ext = new_test_ext();
+
+    // Proof generation requires the offchain extensions to be present to retrieve full leaf data.
+    register_offchain_ext(&mut ext);
+
+    // given: messages 1, 3 and 4 expire before messages 0 and 2
+    let valid_until = [None, Some(1_000), Some(5_000), Some(1_000), Some(1_000)];
+    ext.execute_with(|| {
+        send_messages_with_valid_until(&valid_until);
+        // ensure snapshot is taken
+        add_blocks(3);
+        assert_eq!(1, HyperdriveOutgoing::next_snapshot_number());
+    });
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        Now::set(2_000);
+
+        // when not skipping expired messages
+        let proof = Pallet::<Test>::generate_target_chain_proof(0, None, 0, false)
+            .unwrap()
+            .unwrap();
+
+        // then all messages are included and expose their expiry
+        assert_eq!(proof.next_message_number, 5);
+        assert_eq!(
+            proof
+                .leaves
+                .iter()
+                .map(|leaf| leaf.valid_until)
+                .collect::<Vec<_>>(),
+            valid_until.to_vec()
+        );
+
+        // when skipping expired messages
+        let proof = Pallet::<Test>::generate_target_chain_proof(0, None, 0, true)
+            .unwrap()
+            .unwrap();
+
+        // then only live messages are included, but the bookkeeping advances past the expired trailing messages
+        assert_eq!(proof.next_message_number, 5);
+        assert_eq!(
+            proof
+                .leaves
+                .iter()
+                .map(|leaf| leaf.position)
+                .collect::<Vec<_>>(),
+            vec![leaf_index_to_pos(0), leaf_index_to_pos(2)]
+        );
+        let mut live_message = message(2);
+        live_message.valid_until = Some(5_000);
+        assert_eq!(
+            proof.leaves[1].message,
+            TargetChainEncoderOf::<Test, ()>::encode(&live_message).unwrap()
+        );
+        assert!(!proof.items.is_empty());
+
+        // when skipping expired messages and all requested messages expired
+        let proof = Pallet::<Test>::generate_target_chain_proof(3, None, 0, true)
+            .unwrap()
+            .unwrap();
+
+        // then the proof is empty, but the bookkeeping still advances
+        assert_eq!(proof.next_message_number, 5);
+        assert!(proof.leaves.is_empty());
+        assert!(proof.items.is_empty());
+
+        // when not skipping expired messages, the expired messages are still provable
+        let proof = Pallet::<Test>::generate_target_chain_proof(3, None, 0, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.next_message_number, 5);
+        assert_eq!(proof.leaves.len(), 2);
+    });
+}
+
 // #[test]
 // fn should_verify_canonicalized() {
 //     use frame_support::traits::Hooks;
@@ -576,6 +666,32 @@ fn should_generate_maximum_messages() {
 //     });
 // }
 
+#[test]
+fn should_decode_leaf_stored_before_valid_until() {
+    // `Node::Data(Message { id: 1, action: Action::FinalizeJob(2, 10) })` as stored before `valid_until` got added
+    let legacy = hex!(
+        "00010000000000000001020000000000000000000000000000000a000000000000000000000000000000"
+    );
+    assert_eq!(
+        Some(types::Node::Data(Message {
+            id: 1,
+            action: Action::FinalizeJob(2, 10),
+            valid_until: None,
+        })),
+        NodeOf::<Test, ()>::decode_stored(&legacy)
+    );
+
+    let node: NodeOf<Test, ()> = types::Node::Data(Message {
+        id: 1,
+        action: Action::FinalizeJob(2, 10),
+        valid_until: Some(1_000),
+    });
+    assert_eq!(
+        Some(node.clone()),
+        NodeOf::<Test, ()>::decode_stored(&node.encode())
+    );
+}
+
 #[test]
 fn does_not_panic_when_generating_historical_proofs() {
     let _ = env_logger::try_init();
@@ -619,6 +735,8 @@ fn does_not_panic_when_generating_historical_proofs() {
 /// k_index: 1, position: 8, message 05070700050707010000000641535349474e0a000000460507070a000000100000000000000000000000000000000502000000290a00000024747a316834457347756e48325565315432754e73386d664b5a38585a6f516a693348634b
 /// k_index: 0, position: 10, message 05070700060707010000000641535349474e0a000000460507070a000000100000000000000000000000000000000602000000290a00000024747a316834457347756e48325565315432754e73386d664b5a38585a6f516a693348634b
 ///
+/// next_message_number: 7
+///
 /// mmr_size: 11
 ///
 /// items: [0x53db3d426fa99eff2cc6ef1f07a226c2e5b32d9ccc2b67411d52e8d2b0de8d13,
@@ -638,6 +756,7 @@ fn should_serialize_target_chain_proof() {
                          position: 10,
                          message: hex!("05070700060707010000000641535349474e0a000000460507070a000000100000000000000000000000000000000602000000290a00000024747a316834457347756e48325565315432754e73386d664b5a38585a6f516a693348634b").into(),
                      }],
+        next_message_number: 7,
         mmr_size:11,
         items: vec![hex!("53db3d426fa99eff2cc6ef1f07a226c2e5b32d9ccc2b67411d52e8d2b0de8d13"), hex!("bca5ce83486f6bd8be90523d0e9bcefd812fbd451337b584d32f8203dbf340c7")],
     };
@@ -648,6 +767,495 @@ fn should_serialize_target_chain_proof() {
     // then
     assert_eq!(
         actual,
-        r#"{"leaves":[{"kIndex":1,"position":8,"message":[5,7,7,0,5,7,7,1,0,0,0,6,65,83,83,73,71,78,10,0,0,0,70,5,7,7,10,0,0,0,16,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,5,2,0,0,0,41,10,0,0,0,36,116,122,49,104,52,69,115,71,117,110,72,50,85,101,49,84,50,117,78,115,56,109,102,75,90,56,88,90,111,81,106,105,51,72,99,75]},{"kIndex":0,"position":10,"message":[5,7,7,0,6,7,7,1,0,0,0,6,65,83,83,73,71,78,10,0,0,0,70,5,7,7,10,0,0,0,16,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,6,2,0,0,0,41,10,0,0,0,36,116,122,49,104,52,69,115,71,117,110,72,50,85,101,49,84,50,117,78,115,56,109,102,75,90,56,88,90,111,81,106,105,51,72,99,75]}],"mmrSize":11,"items":[[83,219,61,66,111,169,158,255,44,198,239,31,7,162,38,194,229,179,45,156,204,43,103,65,29,82,232,210,176,222,141,19],[188,165,206,131,72,111,107,216,190,144,82,61,14,155,206,253,129,47,189,69,19,55,181,132,211,47,130,3,219,243,64,199]]}"#
+        r#"{"leaves":[{"kIndex":1,"position":8,"message":[5,7,7,0,5,7,7,1,0,0,0,6,65,83,83,73,71,78,10,0,0,0,70,5,7,7,10,0,0,0,16,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,5,2,0,0,0,41,10,0,0,0,36,116,122,49,104,52,69,115,71,117,110,72,50,85,101,49,84,50,117,78,115,56,109,102,75,90,56,88,90,111,81,106,105,51,72,99,75]},{"kIndex":0,"position":10,"message":[5,7,7,0,6,7,7,1,0,0,0,6,65,83,83,73,71,78,10,0,0,0,70,5,7,7,10,0,0,0,16,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,6,2,0,0,0,41,10,0,0,0,36,116,122,49,104,52,69,115,71,117,110,72,50,85,101,49,84,50,117,78,115,56,109,102,75,90,56,88,90,111,81,106,105,51,72,99,75]}],"nextMessageNumber":7,"mmrSize":11,"items":[[83,219,61,66,111,169,158,255,44,198,239,31,7,162,38,194,229,179,45,156,204,43,103,65,29,82,232,210,176,222,141,19],[188,165,206,131,72,111,107,216,190,144,82,61,14,155,206,253,129,47,189,69,19,55,181,132,211,47,130,3,219,243,64,199]]}"#
+    );
+}
+
+#[test]
+fn should_fail_forcing_snapshot_without_pending_messages() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+
+        assert_err!(
+            HyperdriveOutgoing::force_snapshot(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test>::NothingToSnapshot
+        );
+
+        // after a regular snapshot there is again nothing to snapshot
+        send_messages(1);
+        add_blocks(3);
+        assert_eq!(1, HyperdriveOutgoing::next_snapshot_number());
+        assert_err!(
+            HyperdriveOutgoing::force_snapshot(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test>::NothingToSnapshot
+        );
+    });
+}
+
+#[test]
+fn should_force_snapshot_with_pending_messages() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+        send_messages(2);
+
+        assert_ok!(HyperdriveOutgoing::force_snapshot(RuntimeOrigin::signed(
+            alice_account_id()
+        )));
+
+        // snapshot is taken immediately without waiting for the end of the block
+        assert_eq!(1, HyperdriveOutgoing::next_snapshot_number());
+        assert_eq!(
+            Some((HyperdriveOutgoing::root_hash(), 1, 2)),
+            HyperdriveOutgoing::snapshot_meta(0)
+        );
+        assert_eq!((2, 2), HyperdriveOutgoing::message_numbers());
+        assert_eq!(Some(1), HyperdriveOutgoing::last_forced_snapshot_block());
+        assert_eq!(
+            frame_system::Pallet::<Test>::events()
+                .last()
+                .map(|record| record.event.clone()),
+            Some(RuntimeEvent::HyperdriveOutgoing(Event::SnapshotForced(
+                alice_account_id()
+            )))
+        );
+
+        // the hook does not take another snapshot for the same messages
+        next_block();
+        assert_eq!(1, HyperdriveOutgoing::next_snapshot_number());
+    });
+}
+
+#[test]
+fn should_enforce_minimum_gap_between_forced_snapshots() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+        send_messages(1);
+        assert_ok!(HyperdriveOutgoing::force_snapshot(RuntimeOrigin::signed(
+            alice_account_id()
+        )));
+
+        next_block();
+        send_messages(1);
+        assert_err!(
+            HyperdriveOutgoing::force_snapshot(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test>::ForcedSnapshotTooEarly
+        );
+
+        // the pending message still gets included by the regular snapshot at the end of block 3
+        add_blocks(2);
+        assert_eq!(2, HyperdriveOutgoing::next_snapshot_number());
+
+        // block 4 is `MinBlocksBetweenForcedSnapshots` after the last forced snapshot
+        send_messages(1);
+        assert_ok!(HyperdriveOutgoing::force_snapshot(RuntimeOrigin::signed(
+            alice_account_id()
+        )));
+        assert_eq!(3, HyperdriveOutgoing::next_snapshot_number());
+        assert_eq!(Some(4), HyperdriveOutgoing::last_forced_snapshot_block());
+    });
+}
+
+#[test]
+fn should_return_sequential_message_ids() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+        for expected_id in 0..5 {
+            let (message_id, _) = HyperdriveOutgoing::send_message(action(0), None).unwrap();
+            assert_eq!(expected_id, message_id);
+        }
+
+        next_block();
+        let (message_id, _) = HyperdriveOutgoing::send_message(action(0), None).unwrap();
+        assert_eq!(5, message_id);
+        assert_eq!(6, HyperdriveOutgoing::message_numbers().1);
+    });
+}
+
+#[test]
+fn should_resolve_correlated_messages() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+        let correlation_id =
+            |id: &[u8]| -> CorrelationIdOf<Test, ()> { id.to_vec().try_into().unwrap() };
+
+        let (first, _) = HyperdriveOutgoing::send_message_with_correlation(
+            action(0),
+            None,
+            correlation_id(b"job-1"),
+        )
+        .unwrap();
+        let (uncorrelated, _) = HyperdriveOutgoing::send_message(action(1), None).unwrap();
+        let (other, _) = HyperdriveOutgoing::send_message_with_correlation(
+            action(2),
+            None,
+            correlation_id(b"job-2"),
+        )
+        .unwrap();
+        next_block();
+        let (second, _) = HyperdriveOutgoing::send_message_with_correlation(
+            action(3),
+            None,
+            correlation_id(b"job-1"),
+        )
+        .unwrap();
+
+        assert_eq!((0, 1, 2, 3), (first, uncorrelated, other, second));
+        assert_eq!(
+            vec![first, second],
+            HyperdriveOutgoing::message_ids_by_correlation(b"job-1".to_vec())
+        );
+        assert_eq!(
+            vec![other],
+            HyperdriveOutgoing::message_ids_by_correlation(b"job-2".to_vec())
+        );
+        assert!(HyperdriveOutgoing::message_ids_by_correlation(b"job-3".to_vec()).is_empty());
+        // correlation ids exceeding the maximum length were never stored
+        assert!(HyperdriveOutgoing::message_ids_by_correlation(vec![0u8; 65]).is_empty());
+
+        assert_eq!(
+            Some(correlation_id(b"job-1")),
+            HyperdriveOutgoing::message_origin(second)
+        );
+        assert_eq!(None, HyperdriveOutgoing::message_origin(uncorrelated));
+    });
+}
+
+#[test]
+fn should_prune_correlations_outside_retention() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+        let correlation_id: CorrelationIdOf<Test, ()> = b"job-1".to_vec().try_into().unwrap();
+
+        for i in 0..CorrelationRetention::get() {
+            let (message_id, _) = HyperdriveOutgoing::send_message_with_correlation(
+                action(i as u128),
+                None,
+                correlation_id.clone(),
+            )
+            .unwrap();
+            assert_eq!(i, message_id);
+        }
+        assert_eq!(
+            vec![0, 1, 2, 3, 4],
+            HyperdriveOutgoing::message_ids_by_correlation(b"job-1".to_vec())
+        );
+
+        // also uncorrelated messages advance the retention window
+        next_block();
+        let (message_id, _) = HyperdriveOutgoing::send_message(action(5), None).unwrap();
+        assert_eq!(5, message_id);
+        assert_eq!(None, HyperdriveOutgoing::message_origin(0));
+        assert_eq!(Some(correlation_id), HyperdriveOutgoing::message_origin(1));
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            HyperdriveOutgoing::message_ids_by_correlation(b"job-1".to_vec())
+        );
+    });
+}
+
+#[test]
+fn should_reject_oversized_messages() {
+    let _ = env_logger::try_init();
+    new_test_ext().execute_with(|| {
+        next_block();
+        send_messages(1);
+        let size = TargetChainEncoderOf::<Test, ()>::encode(&message(1))
+            .unwrap()
+            .len() as u32;
+
+        // one byte over the limit
+        MaxLeafPayloadSize::set(size - 1);
+        assert_err!(
+            HyperdriveOutgoing::send_message(action(1), None),
+            MMRError::PayloadTooLarge
+        );
+        assert_eq!(1, crate::NumberOfLeaves::<Test>::get());
+        assert_eq!(1, HyperdriveOutgoing::message_numbers().1);
+
+        // exactly at the limit
+        MaxLeafPayloadSize::set(size);
+        let (message_id, _) = HyperdriveOutgoing::send_message(action(1), None).unwrap();
+        assert_eq!(1, message_id);
+        assert_eq!(2, crate::NumberOfLeaves::<Test>::get());
+
+        MaxLeafPayloadSize::set(1024);
+    });
+}
+
+#[test]
+fn should_chunk_oversized_messages() {
+    let _ = env_logger::try_init();
+    let mut ext = new_test_ext();
+    register_offchain_ext(&mut ext);
+
+    let max_size = 64;
+    let valid_until = Some(1_000);
+    let mut chunks: Vec<Message> = vec![];
+    ext.execute_with(|| {
+        next_block();
+        send_messages(1);
+        MaxLeafPayloadSize::set(max_size);
+        ChunkOversizedActions::set(true);
+
+        let original = Message {
+            id: 1,
+            action: action(1),
+            valid_until,
+        };
+        let encoded = TargetChainEncoderOf::<Test, ()>::encode(&original).unwrap();
+        assert!(encoded.len() > max_size as usize);
+
+        let (message_id, _) = HyperdriveOutgoing::send_message(action(1), valid_until).unwrap();
+        assert_eq!(1, message_id);
+
+        chunks = frame_system::Pallet::<Test>::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::HyperdriveOutgoing(crate::Event::MessageSent(message))
+                    if message.id >= message_id =>
+                {
+                    Some(message)
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(chunks.len() > 1);
+        assert_eq!(
+            message_id + chunks.len() as LeafIndex,
+            HyperdriveOutgoing::message_numbers().1
+        );
+
+        // simulate the target chain contract reassembling the chunks
+        let mut reassembled: Vec<u8> = vec![];
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert_eq!(message_id + index as LeafIndex, chunk.id);
+            assert_eq!(valid_until, chunk.valid_until);
+            match &chunk.action {
+                Action::ActionChunk(action_chunk) => {
+                    assert_eq!(message_id, action_chunk.correlation);
+                    assert_eq!(index as u16, action_chunk.index);
+                    assert_eq!(chunks.len() as u16, action_chunk.total);
+                    reassembled.extend_from_slice(&action_chunk.bytes);
+                }
+                _ => panic!("expected an action chunk"),
+            }
+        }
+        assert_eq!(encoded, reassembled);
+
+        // a limit below the overhead of a chunk cannot be satisfied
+        let leaves = crate::NumberOfLeaves::<Test>::get();
+        MaxLeafPayloadSize::set(10);
+        assert_err!(
+            HyperdriveOutgoing::send_message(action(2), None),
+            MMRError::PayloadTooLarge
+        );
+        assert_eq!(leaves, crate::NumberOfLeaves::<Test>::get());
+
+        // ensure snapshot is taken
+        add_blocks(3);
+    });
+    ext.persist_offchain_overlay();
+
+    ext.execute_with(|| {
+        // chunks are proven like any other message
+        let proof = Pallet::<Test>::generate_target_chain_proof(1, None, 0, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(chunks.len(), proof.leaves.len());
+        for (leaf, chunk) in proof.leaves.iter().zip(chunks.iter()) {
+            assert!(leaf.message.len() <= max_size as usize);
+            assert_eq!(
+                leaf.message,
+                TargetChainEncoderOf::<Test, ()>::encode(chunk).unwrap()
+            );
+        }
+
+        MaxLeafPayloadSize::set(1024);
+        ChunkOversizedActions::set(false);
+    });
+}
+
+/// Ensures adding [`Action::ActionChunk`] did not shift the indices of the existing actions.
+#[test]
+fn should_keep_action_indices() {
+    assert_eq!(vec![2], Action::Noop.encode());
+    assert_eq!(
+        3,
+        Action::ActionChunk(ActionChunk {
+            correlation: 0,
+            index: 0,
+            total: 1,
+            bytes: vec![],
+        })
+        .encode()[0]
+    );
+    assert_eq!(255, RawAction::Noop as u16);
+    assert_eq!(2, RawAction::ActionChunk as u16);
+}
+
+/// The test vectors shared with the target chain verifiers, e.g. the `acurast-validator-ink` contract.
+const PROOF_TEST_VECTORS: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/test-vectors/target_chain_proofs.json"
+);
+
+/// If set, [`should_match_target_chain_proof_test_vectors`] rewrites [`PROOF_TEST_VECTORS`] instead of comparing against it.
+const UPDATE_PROOF_TEST_VECTORS_ENV: &str = "ACURAST_TEST_VECTORS_UPDATE";
+
+/// A [`TargetChainProof`] for the messages `[next_message_number..]` of an MMR with `leaf_count` leaves.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ProofTestVector {
+    leaf_count: u64,
+    next_message_number: LeafIndex,
+    maximum_messages: Option<u64>,
+    root: H256,
+    mmr_size: NodeIndex,
+    leaves: Vec<ProofTestVectorLeaf>,
+    items: Vec<H256>,
+    /// `false` for vectors with a flipped byte, which must fail verification.
+    valid: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ProofTestVectorLeaf {
+    k_index: NodeIndex,
+    position: NodeIndex,
+    message: Bytes,
+}
+
+/// The `(next_message_number, maximum_messages)` to generate proofs for in an MMR with `leaf_count` leaves.
+fn proof_test_vector_cases(leaf_count: u64) -> Vec<(LeafIndex, Option<u64>)> {
+    let mut cases = vec![(0, None)];
+    for case in [(leaf_count / 2, None), (leaf_count - 1, None), (1, Some(2))] {
+        let (next_message_number, maximum_messages) = case;
+        if next_message_number < leaf_count
+            && next_message_number + maximum_messages.unwrap_or(0) <= leaf_count
+            && !cases.contains(&case)
+        {
+            cases.push(case);
+        }
+    }
+    cases
+}
+
+fn proof_test_vectors() -> Vec<ProofTestVector> {
+    let mut vectors = vec![];
+    for leaf_count in [1, 2, 3, 4, 5, 6, 7, 8, 11, 15, 16, 21] {
+        let mut ext = new_test_ext();
+        register_offchain_ext(&mut ext);
+        ext.execute_with(|| {
+            send_messages(leaf_count as usize);
+            // ensure snapshot is taken
+            add_blocks(3);
+            assert_eq!(1, HyperdriveOutgoing::next_snapshot_number());
+        });
+        ext.persist_offchain_overlay();
+
+        ext.execute_with(|| {
+            let (root, _, last_message_excl) = HyperdriveOutgoing::snapshot_meta(0).unwrap();
+            assert_eq!(leaf_count, last_message_excl);
+
+            for (next_message_number, maximum_messages) in proof_test_vector_cases(leaf_count) {
+                let proof = Pallet::<Test>::generate_target_chain_proof(
+                    next_message_number,
+                    maximum_messages,
+                    0,
+                    false,
+                )
+                .unwrap()
+                .unwrap();
+                let vector = ProofTestVector {
+                    leaf_count,
+                    next_message_number,
+                    maximum_messages,
+                    root,
+                    mmr_size: proof.mmr_size,
+                    leaves: proof
+                        .leaves
+                        .into_iter()
+                        .map(|leaf| ProofTestVectorLeaf {
+                            k_index: leaf.k_index,
+                            position: leaf.position,
+                            message: Bytes(leaf.message),
+                        })
+                        .collect(),
+                    items: proof.items,
+                    valid: true,
+                };
+                vectors.push(vector.clone());
+
+                // negative vectors flipping a byte of the first leaf's message and of the first proof item
+                if [7, 11, 16].contains(&leaf_count)
+                    && next_message_number == leaf_count / 2
+                    && maximum_messages.is_none()
+                {
+                    let mut flipped = vector.clone();
+                    if let Some(byte) = flipped.leaves[0].message.0.last_mut() {
+                        *byte ^= 1;
+                    }
+                    flipped.valid = false;
+                    vectors.push(flipped);
+
+                    if !vector.items.is_empty() {
+                        let mut flipped = vector;
+                        flipped.items[0].0[0] ^= 1;
+                        flipped.valid = false;
+                        vectors.push(flipped);
+                    }
+                }
+            }
+        });
+    }
+    vectors
+}
+
+/// Ensures the committed test vectors are reproduced by the pallet's proof generation,
+/// so that target chain verifiers tested against them stay compatible.
+#[test]
+fn should_match_target_chain_proof_test_vectors() {
+    let _ = env_logger::try_init();
+    let vectors = proof_test_vectors();
+    let mut json = serde_json::to_string_pretty(&vectors).unwrap();
+    json.push('\n');
+
+    if std::env::var_os(UPDATE_PROOF_TEST_VECTORS_ENV).is_some() {
+        std::fs::write(PROOF_TEST_VECTORS, json).unwrap();
+        return;
+    }
+
+    let committed_json = std::fs::read_to_string(PROOF_TEST_VECTORS).unwrap_or_else(|e| {
+        panic!(
+            "missing test vectors {}: {}; generate them with {}=1",
+            PROOF_TEST_VECTORS, e, UPDATE_PROOF_TEST_VECTORS_ENV
+        )
+    });
+    let committed: Vec<ProofTestVector> = serde_json::from_str(&committed_json).unwrap();
+    assert_eq!(
+        committed.len(),
+        vectors.len(),
+        "generated proofs differ from test vectors {}; rerun with {}=1 to update them",
+        PROOF_TEST_VECTORS,
+        UPDATE_PROOF_TEST_VECTORS_ENV
+    );
+    for (committed, generated) in committed.iter().zip(vectors.iter()) {
+        assert_eq!(
+            committed, generated,
+            "generated proof differs from test vectors {}; rerun with {}=1 to update them",
+            PROOF_TEST_VECTORS, UPDATE_PROOF_TEST_VECTORS_ENV
+        );
+    }
+    // the verifiers read the file as is, so it has to match the generated vectors byte for byte
+    assert_eq!(
+        committed_json, json,
+        "test vectors {} are not formatted as generated; rerun with {}=1 to update them",
+        PROOF_TEST_VECTORS, UPDATE_PROOF_TEST_VECTORS_ENV
     );
 }
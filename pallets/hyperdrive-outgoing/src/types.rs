@@ -2,6 +2,7 @@ use core::fmt::Debug;
 
 #[cfg(not(feature = "std"))]
 use codec::alloc::string::String;
+use codec::DecodeAll;
 use frame_support::pallet_prelude::*;
 pub use mmr_lib;
 use scale_info::TypeInfo;
@@ -50,6 +51,8 @@ pub enum RawAction {
     FinalizeJob,
     #[strum(serialize = "NOOP")]
     Noop = 255,
+    #[strum(serialize = "ACTION_CHUNK")]
+    ActionChunk = 2,
 }
 
 impl From<&Action> for RawAction {
@@ -58,6 +61,7 @@ impl From<&Action> for RawAction {
             Action::AssignJob(_, _) => RawAction::AssignJob,
             Action::FinalizeJob(_, _) => RawAction::FinalizeJob,
             Action::Noop => RawAction::Noop,
+            Action::ActionChunk(_) => RawAction::ActionChunk,
         }
     }
 }
@@ -84,6 +88,25 @@ pub enum Action {
     FinalizeJob(JobIdSequence, u128), // (u128, u128)
     /// A noop action that solely suits the purpose of testing that messages get sent.
     Noop,
+    /// A part of a message too large to be sent as a single leaf, see [`ActionChunk`].
+    #[codec(index = 3)]
+    ActionChunk(ActionChunk),
+}
+
+/// A part of an oversized message, sent as one of [`ActionChunk::total`] consecutive messages.
+///
+/// Target chains concatenate the [`ActionChunk::bytes`] of all chunks in order of [`ActionChunk::index`] to obtain the
+/// target chain encoding of the original message, whose id is [`ActionChunk::correlation`].
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Eq, PartialEq, Clone)]
+pub struct ActionChunk {
+    /// The id of the reassembled message, equal to the id of the message carrying the first chunk.
+    pub correlation: u64,
+    /// The position of this chunk, starting at `0`.
+    pub index: u16,
+    /// The number of chunks the original message was split into.
+    pub total: u16,
+    /// A slice of the target chain encoding of the original message.
+    pub bytes: Vec<u8>,
 }
 
 /// Message that is transferred to target chains.
@@ -91,10 +114,40 @@ pub enum Action {
 pub struct Message {
     pub id: u64,
     pub action: Action,
+    /// The unix timestamp in milliseconds after which the message must no longer be processed on target chain.
+    ///
+    /// `None` means the message never expires.
+    pub valid_until: Option<u64>,
+}
+
+impl Message {
+    /// Returns `true` if the message has a [`Message::valid_until`] in the past of `now` (unix timestamp in milliseconds).
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.valid_until
+            .map(|valid_until| valid_until < now)
+            .unwrap_or(false)
+    }
 }
 
 pub type Leaf = Message;
 
+/// The encoding of a [`Message`] stored as leaf before [`Message::valid_until`] got added.
+#[derive(RuntimeDebug, Encode, Decode, Eq, PartialEq, Clone)]
+pub struct MessageV1 {
+    pub id: u64,
+    pub action: Action,
+}
+
+impl From<MessageV1> for Message {
+    fn from(message: MessageV1) -> Self {
+        Self {
+            id: message.id,
+            action: message.action,
+            valid_until: None,
+        }
+    }
+}
+
 /// An element representing either full data or its hash.
 #[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
 pub enum Node<Hash> {
@@ -104,6 +157,34 @@ pub enum Node<Hash> {
     Hash(Hash),
 }
 
+/// The encoding of a [`Node`] stored before [`Message::valid_until`] got added.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq)]
+pub enum NodeV1<Hash> {
+    Data(MessageV1),
+    Hash(Hash),
+}
+
+impl<Hash> From<NodeV1<Hash>> for Node<Hash> {
+    fn from(node: NodeV1<Hash>) -> Self {
+        match node {
+            NodeV1::Data(message) => Node::Data(message.into()),
+            NodeV1::Hash(hash) => Node::Hash(hash),
+        }
+    }
+}
+
+impl<Hash: Decode> Node<Hash> {
+    /// Decodes a node from the off-chain storage, falling back to [`NodeV1`] for nodes stored before
+    /// [`Message::valid_until`] got added.
+    pub fn decode_stored(encoded: &[u8]) -> Option<Self> {
+        Self::decode_all(&mut &*encoded).ok().or_else(|| {
+            NodeV1::<Hash>::decode_all(&mut &*encoded)
+                .ok()
+                .map(Into::into)
+        })
+    }
+}
+
 impl<H: traits::Hash> From<Leaf> for Node<H> {
     fn from(l: Leaf) -> Self {
         Self::Data(l)
@@ -207,7 +288,13 @@ pub struct Proof<Hash> {
 #[serde(rename_all = "camelCase")]
 pub struct TargetChainProof<Hash> {
     /// The indices of the leaves the proof is for.
+    ///
+    /// Can be empty if all messages in the requested range expired and expired messages were skipped.
     pub leaves: Vec<TargetChainProofLeaf>,
+    /// The next message number to request a proof for after this proof was processed.
+    ///
+    /// This is the exclusive end of the requested range, also if (trailing) expired messages were skipped.
+    pub next_message_number: LeafIndex,
     /// Number of leaves in MMR, when the proof was generated.
     pub mmr_size: NodeIndex,
     /// Proof elements (hashes of siblings of inner nodes on the path to the leaf).
@@ -235,6 +322,9 @@ pub struct TargetChainProofLeaf {
     pub position: NodeIndex,
     /// The encoded message on this leaf.
     pub message: Vec<u8>,
+    /// The [`Message::valid_until`] of the message on this leaf, exposed to let relayers skip expired messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<u64>,
 }
 
 /// Merkle Mountain Range operation error.
@@ -283,6 +373,12 @@ pub enum MMRError {
     /// Leaf not found in the storage.
     #[cfg_attr(feature = "std", error("Leaf was not found"))]
     LeafNotFound,
+    /// The target chain encoding of a message exceeds the maximum leaf payload size and cannot be sent.
+    #[cfg_attr(
+        feature = "std",
+        error("Encoded message exceeds the maximum leaf payload size")
+    )]
+    PayloadTooLarge,
 }
 
 impl MMRError {
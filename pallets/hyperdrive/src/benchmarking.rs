@@ -1,10 +1,11 @@
 use frame_benchmarking::benchmarks_instance_pallet;
 use frame_benchmarking::whitelist_account;
 use frame_benchmarking::whitelisted_caller;
-use frame_support::assert_ok;
+use frame_support::{assert_ok, traits::Currency};
 use frame_system::RawOrigin;
 use sp_core::crypto::AccountId32;
 use sp_core::H256;
+use sp_runtime::Saturating;
 use sp_std::{iter, prelude::*};
 
 use crate::chain::tezos::TezosProof;
@@ -39,7 +40,7 @@ where
     let actions = StateTransmitterUpdates::<T>::try_from(
         iter::repeat(StateTransmitterUpdate::Add(
             caller.clone(),
-            ActivityWindow {
+            ActivityWindow::Blocks {
                 start_block: 0.into(),
                 end_block: 100.into(),
             },
@@ -60,6 +61,20 @@ where
     (caller, actions)
 }
 
+/// Funds the whitelisted caller with twice the minimum transmitter stake and returns it with the minimum stake.
+fn fund_transmitter_helper<T: Config<I>, I: 'static>() -> (T::AccountId, BalanceOf<T, I>) {
+    let caller: T::AccountId = whitelisted_caller();
+    whitelist_account!(caller);
+    let stake = T::MinTransmitterStake::get();
+    T::Currency::make_free_balance_be(
+        &caller,
+        stake
+            .saturating_add(stake)
+            .saturating_add(T::Currency::minimum_balance()),
+    );
+    (caller, stake)
+}
+
 benchmarks_instance_pallet! {
     where_clause {
         where
@@ -80,7 +95,7 @@ benchmarks_instance_pallet! {
         assert_last_event::<T, I>(Event::StateTransmittersUpdate{
                     added: iter::repeat((
                             account.into(),
-                            ActivityWindow {
+                            ActivityWindow::Blocks {
                                 start_block: 0.into(),
                                 end_block: 100.into()
                             }
@@ -122,12 +137,53 @@ benchmarks_instance_pallet! {
         ));
         assert_ok!(AcurastHyperdrive::<T, I>::submit_state_merkle_root(RawOrigin::Signed(caller.clone()).into(), 1.into(), snapshot_root_1.into()));
         let state_owner = StateOwner::try_from(hex!("050a000000160199651cbe1a155a5c8e5af7d6ea5c3f48eebb8c9c00").to_vec()).unwrap();
-        assert_ok!(AcurastHyperdrive::<T, I>::update_target_chain_owner(RawOrigin::Root.into(), state_owner));
+        assert_ok!(AcurastHyperdrive::<T, I>::update_target_chain_owner(RawOrigin::Root.into(), state_owner, 0));
     }: _(RawOrigin::Signed(caller), 1u8.into(), proof)
 
     update_target_chain_owner {
         let owner: StateOwner = state_owner();
-    }: _(RawOrigin::Root, owner)
+    }: _(RawOrigin::Root, owner.clone(), 10u32.into())
+    verify {
+        assert_eq!(AcurastHyperdrive::<T, I>::current_target_chain_owner(), owner);
+    }
+
+    register_transmitter {
+        let (caller, stake) = fund_transmitter_helper::<T, I>();
+    }: _(RawOrigin::Signed(caller.clone()), stake)
+    verify {
+        assert!(<StakedTransmitters<T, I>>::contains_key(&caller));
+    }
+
+    deregister_transmitter {
+        let (caller, stake) = fund_transmitter_helper::<T, I>();
+        assert_ok!(AcurastHyperdrive::<T, I>::register_transmitter(RawOrigin::Signed(caller.clone()).into(), stake));
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(<StakedTransmitters<T, I>>::get(&caller).unwrap().release_block.is_some());
+    }
+
+    release_transmitter_stake {
+        let (caller, stake) = fund_transmitter_helper::<T, I>();
+        assert_ok!(AcurastHyperdrive::<T, I>::register_transmitter(RawOrigin::Signed(caller.clone()).into(), stake));
+        assert_ok!(AcurastHyperdrive::<T, I>::deregister_transmitter(RawOrigin::Signed(caller.clone()).into()));
+        frame_system::Pallet::<T>::set_block_number(
+            frame_system::Pallet::<T>::block_number() + T::TransmitterCooldown::get(),
+        );
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(!<StakedTransmitters<T, I>>::contains_key(&caller));
+    }
+
+    slash_transmitter {
+        let (caller, stake) = fund_transmitter_helper::<T, I>();
+        assert_ok!(AcurastHyperdrive::<T, I>::register_transmitter(RawOrigin::Signed(caller.clone()).into(), stake));
+    }: _(RawOrigin::Root, caller.clone(), stake)
+    verify {
+        assert_last_event::<T, I>(Event::TransmitterSlashed {
+            account: caller,
+            amount: stake,
+        }.into());
+    }
 
     impl_benchmark_test_suite!(AcurastHyperdrive, crate::mock::new_test_ext(), mock::Test);
 }
@@ -1,5 +1,5 @@
 use super::util::evm;
-use crate::{traits, MessageIdentifier, ParsedAction, RawAction};
+use crate::{traits, MessageIdentifier, ParsedAction, RawAction, StateOwner};
 use alloy_sol_types::{sol, SolType};
 use codec::{Decode, Encode};
 use core::marker::PhantomData;
@@ -7,8 +7,8 @@ use derive_more::{Display, From};
 use frame_support::pallet_prelude::ConstU32;
 use frame_support::BoundedVec;
 use pallet_acurast::{
-    AllowedSources, EthereumAddressBytes, JobModule, JobModules, JobRegistration, MultiOrigin,
-    Schedule, Script,
+    derive_job_id, AllowedSources, EthereumAddressBytes, JobModule, JobModules, JobRegistration,
+    MultiOrigin, Schedule, Script,
 };
 use pallet_acurast_marketplace::{
     JobRequirements, PlannedExecution, PlannedExecutions, RegistrationExtra,
@@ -128,7 +128,7 @@ where
 {
     type Error = EthereumValidationError;
 
-    fn calculate_root(self: &Self) -> Result<[u8; 32], Self::Error> {
+    fn calculate_root(self: &Self, owner: &StateOwner) -> Result<[u8; 32], Self::Error> {
         let account_proof: Vec<Vec<u8>> = self
             .account_proof
             .iter()
@@ -141,13 +141,8 @@ where
             .map(|node| node.to_vec())
             .collect();
 
-        // Validate account proof
-        let storage_owner_address = crate::pallet::Pallet::<T, I>::current_target_chain_owner();
-
         // Validate the storage proof against the known
-        let account_path = Keccak256::hash(storage_owner_address.as_ref())
-            .as_bytes()
-            .to_vec();
+        let account_path = Keccak256::hash(owner.as_ref()).as_bytes().to_vec();
         let storage_path = &evm::storage_path(&STORAGE_INDEX, &self.message_id).to_vec();
         let verified_value = evm::validate_storage_proof(
             &account_path,
@@ -214,7 +209,7 @@ where
                     AcurastJobRegistration::decode_single(&decoded.payload, true)
                         .map_err(|_| EthereumValidationError::IllFormattedJobRegistration)?;
 
-                let job_id = (origin, job_registration.jobId.clone());
+                let job_id = derive_job_id(&origin, job_registration.jobId);
 
                 fn convert_job_match<
                     AccountId,
@@ -227,6 +222,7 @@ where
                             m.source.to_vec(),
                         )?,
                         start_delay: m.startDelay,
+                        pricing_version: None,
                     })
                 }
                 let executions: PlannedExecutions<T::AccountId, T::MaxSlots> =
@@ -245,7 +241,17 @@ where
                         slots: job_registration.requirements.slots.into(),
                         reward: T::Balance::from(job_registration.requirements.reward),
                         min_reputation: Some(job_registration.requirements.minReputation),
+                        min_recent_fulfillment_ratio: None,
+                        holdback_release_sla: None,
+                        max_attestation_age_ms: None,
+                        max_fee_per_execution: None,
+                        depends_on: None,
+                        preferred_sources: None,
+                        preference_window_ms: 0,
+                        omitted: None,
                         instant_match: Some(executions),
+                        require_env_ack: false,
+                        auto_renew: None,
                     },
                 }
                 .into();
@@ -295,13 +301,13 @@ where
                 let job_id = JobId::decode_single(&decoded.payload, true)
                     .map_err(|_| EthereumValidationError::CouldNotDecodeDeregisterJobPayload)?;
 
-                Ok(ParsedAction::DeregisterJob((origin, job_id)))
+                Ok(ParsedAction::DeregisterJob(derive_job_id(&origin, job_id)))
             }
             RawAction::FinalizeJob => {
                 let jobs = <sol!(uint128[])>::decode_single(&decoded.payload, true)
                     .map_err(|_| EthereumValidationError::CouldNotDecodeFinalizeJobPayload)?
                     .iter()
-                    .map(|id| (MultiOrigin::Ethereum(origin_address.clone()), *id))
+                    .map(|id| derive_job_id(&origin, *id))
                     .collect();
 
                 Ok(ParsedAction::FinalizeJob(jobs))
@@ -312,10 +318,7 @@ where
                         |_| EthereumValidationError::IllFormattedEnvironmentVariablesPayload,
                     )?;
 
-                let job_id = (
-                    MultiOrigin::Ethereum(origin_address.clone()),
-                    set_job_environments.jobId,
-                );
+                let job_id = derive_job_id(&origin, set_job_environments.jobId);
 
                 let public_key = BoundedVec::truncate_from(set_job_environments.publicKey);
 
@@ -17,14 +17,14 @@ use sp_std::vec;
 use ckb_merkle_mountain_range::{Error as MMRError, Merge, MerkleProof as MMRMerkleProof};
 
 use pallet_acurast::{
-    AllowedSources, Environment, JobModule, JobModules, JobRegistration, MultiOrigin, Schedule,
-    Script, CU32,
+    derive_job_id, AllowedSources, Environment, JobModule, JobModules, JobRegistration,
+    MultiOrigin, Schedule, Script, CU32,
 };
 use pallet_acurast_marketplace::{
     JobRequirements, PlannedExecution, PlannedExecutions, RegistrationExtra,
 };
 
-use crate::{traits, MessageIdentifier, ParsedAction};
+use crate::{traits, MessageIdentifier, ParsedAction, StateOwner};
 use acurast_core_ink::types::{
     OutgoingAction as HyperdriveAction, OutgoingActionPayloadV1 as ActionPayloadV1,
     VersionedOutgoingActionPayload as HyperdriveVersionedActionPauload,
@@ -87,7 +87,7 @@ where
 {
     type Error = SubstrateValidationError;
 
-    fn calculate_root(self: &Self) -> Result<[u8; 32], Self::Error> {
+    fn calculate_root(self: &Self, _owner: &StateOwner) -> Result<[u8; 32], Self::Error> {
         // Prepare proof instance
         let mmr_proof = MMRMerkleProof::<[u8; 32], MergeKeccak>::new(
             self.mmr_size,
@@ -166,6 +166,7 @@ where
                                             &m.source,
                                         )?,
                                         start_delay: m.start_delay,
+                                        pricing_version: None,
                                     })
                                 })
                                 .collect::<Result<Vec<PlannedExecution<T::AccountId>>, Self::Error>>(
@@ -178,7 +179,17 @@ where
                             slots: payload.slots.into(),
                             reward: T::Balance::from(payload.reward),
                             min_reputation: payload.min_reputation,
+                            min_recent_fulfillment_ratio: None,
+                            holdback_release_sla: None,
+                            max_attestation_age_ms: None,
+                            max_fee_per_execution: None,
+                            depends_on: None,
+                            preferred_sources: None,
+                            preference_window_ms: 0,
+                            omitted: None,
                             instant_match: Some(executions),
+                            require_env_ack: false,
+                            auto_renew: None,
                         },
                     }
                     .into();
@@ -221,21 +232,21 @@ where
                         extra: extra,
                     };
 
-                    let job_id = (origin, payload.job_id as u128);
+                    let job_id = derive_job_id(&origin, payload.job_id);
 
                     ParsedAction::RegisterJob(job_id, registration)
                 }
                 ActionPayloadV1::DeregisterJob(job_id) => {
-                    ParsedAction::DeregisterJob((origin, job_id as u128))
+                    ParsedAction::DeregisterJob(derive_job_id(&origin, job_id))
                 }
                 ActionPayloadV1::FinalizeJob(payload) => ParsedAction::FinalizeJob(
                     payload
                         .iter()
-                        .map(|id| (origin.clone(), *id as u128))
+                        .map(|id| derive_job_id(&origin, *id))
                         .collect(),
                 ),
                 ActionPayloadV1::SetJobEnvironment(payload) => {
-                    let job_id = (origin, payload.job_id as u128);
+                    let job_id = derive_job_id(&origin, payload.job_id);
 
                     let variables = payload
                         .processors
@@ -28,8 +28,8 @@ use tezos_michelson::{
 };
 
 use pallet_acurast::{
-    AllowedSources, Environment, EnvironmentFor, JobIdSequence, JobModule, JobRegistration,
-    MultiOrigin, ParameterBound, Schedule, CU32,
+    derive_job_id, AllowedSources, Environment, EnvironmentFor, JobIdSequence, JobModule,
+    JobRegistration, MultiOrigin, ParameterBound, Schedule, CU32,
 };
 use pallet_acurast_marketplace::{
     JobRequirements, PlannedExecution, PlannedExecutions, RegistrationExtra,
@@ -38,7 +38,7 @@ use pallet_acurast_marketplace::{
 use crate::types::{
     derive_proof, MessageParser, RawAction, StateKey, StateOwner, StateProof, StateValue,
 };
-use crate::{traits, CurrentTargetChainOwner, MessageIdentifier, ParsedAction};
+use crate::{traits, MessageIdentifier, ParsedAction};
 
 pub struct TezosParser<T, I, ParsableAccountId>(PhantomData<(T, I, ParsableAccountId)>);
 
@@ -78,8 +78,8 @@ where
                 >(payload.as_slice())?;
 
                 ParsedAction::RegisterJob(
-                    (
-                        MultiOrigin::Tezos(bounded_address(&origin)?),
+                    derive_job_id(
+                        &MultiOrigin::Tezos(bounded_address(&origin)?),
                         job_id_sequence,
                     ),
                     registration,
@@ -89,8 +89,8 @@ where
                 let payload: Vec<u8> = (&payload).into();
                 let job_id_sequence = parse_deregister_job_payload(payload.as_slice())?;
 
-                ParsedAction::DeregisterJob((
-                    MultiOrigin::Tezos(bounded_address(&origin)?),
+                ParsedAction::DeregisterJob(derive_job_id(
+                    &MultiOrigin::Tezos(bounded_address(&origin)?),
                     job_id_sequence,
                 ))
             }
@@ -98,11 +98,11 @@ where
                 let payload: Vec<u8> = (&payload).into();
                 let job_ids = parse_finalize_job_payload(payload.as_slice())?;
 
-                let address = bounded_address(&origin)?;
+                let origin = MultiOrigin::Tezos(bounded_address(&origin)?);
                 ParsedAction::FinalizeJob(
                     job_ids
                         .into_iter()
-                        .map(|job_id_seq| (MultiOrigin::Tezos(address.clone()), job_id_seq))
+                        .map(|job_id_seq| derive_job_id(&origin, job_id_seq))
                         .collect(),
                 )
             }
@@ -111,8 +111,8 @@ where
                 let (job_id_sequence, set_job_environment) =
                     parse_set_job_environment_payload::<T, ParsableAccountId>(payload.as_slice())?;
 
-                let job_id = (
-                    MultiOrigin::Tezos(bounded_address(&origin)?),
+                let job_id = derive_job_id(
+                    &MultiOrigin::Tezos(bounded_address(&origin)?),
                     job_id_sequence,
                 );
 
@@ -409,6 +409,7 @@ where
                 Ok(PlannedExecution {
                     source,
                     start_delay,
+                    pricing_version: None,
                 })
             })?;
 
@@ -532,7 +533,17 @@ where
             slots,
             reward,
             min_reputation,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match,
+            require_env_ack: false,
+            auto_renew: None,
         },
     }
     .into();
@@ -841,12 +852,8 @@ where
 {
     type Error = TezosValidationError;
 
-    fn calculate_root(self: &Self) -> Result<[u8; 32], Self::Error> {
-        let leaf_hash = leaf_hash::<T, I>(
-            <CurrentTargetChainOwner<T, I>>::get(),
-            self.path.clone(),
-            self.value.clone(),
-        );
+    fn calculate_root(self: &Self, owner: &StateOwner) -> Result<[u8; 32], Self::Error> {
+        let leaf_hash = leaf_hash::<T, I>(owner.clone(), self.path.clone(), self.value.clone());
         Ok(derive_proof::<T::TargetChainHashing, _>(self.items.clone(), leaf_hash).into())
     }
 
@@ -877,8 +884,8 @@ where
                 >(payload.as_slice())?;
 
                 ParsedAction::RegisterJob(
-                    (
-                        MultiOrigin::Tezos(bounded_address(&origin)?),
+                    derive_job_id(
+                        &MultiOrigin::Tezos(bounded_address(&origin)?),
                         job_id_sequence,
                     ),
                     registration,
@@ -888,8 +895,8 @@ where
                 let payload: Vec<u8> = (&payload).into();
                 let job_id_sequence = parse_deregister_job_payload(payload.as_slice())?;
 
-                ParsedAction::DeregisterJob((
-                    MultiOrigin::Tezos(bounded_address(&origin)?),
+                ParsedAction::DeregisterJob(derive_job_id(
+                    &MultiOrigin::Tezos(bounded_address(&origin)?),
                     job_id_sequence,
                 ))
             }
@@ -897,11 +904,11 @@ where
                 let payload: Vec<u8> = (&payload).into();
                 let job_ids = parse_finalize_job_payload(payload.as_slice())?;
 
-                let address = bounded_address(&origin)?;
+                let origin = MultiOrigin::Tezos(bounded_address(&origin)?);
                 ParsedAction::FinalizeJob(
                     job_ids
                         .into_iter()
-                        .map(|job_id_seq| (MultiOrigin::Tezos(address.clone()), job_id_seq))
+                        .map(|job_id_seq| derive_job_id(&origin, job_id_seq))
                         .collect(),
                 )
             }
@@ -911,8 +918,8 @@ where
                     parse_set_job_environment_payload::<T, AccountConverter>(payload.as_slice())?;
 
                 ParsedAction::SetJobEnvironment(
-                    (
-                        MultiOrigin::Tezos(bounded_address(&origin)?),
+                    derive_job_id(
+                        &MultiOrigin::Tezos(bounded_address(&origin)?),
                         job_id_sequence,
                     ),
                     set_job_environment,
@@ -997,13 +1004,24 @@ mod tests {
                     slots: 1,
                     reward: 1000,
                     min_reputation: None,
+                    min_recent_fulfillment_ratio: None,
+                    holdback_release_sla: None,
+                    max_attestation_age_ms: None,
+                    max_fee_per_execution: None,
+                    depends_on: None,
+                    preferred_sources: None,
+                    preference_window_ms: 0,
+                    omitted: None,
                     instant_match: Some(bounded_vec![PlannedExecution {
                         source: hex![
                             "1111111111111111111111111111111111111111111111111111111111111111"
                         ]
                         .into(),
                         start_delay: 0,
+                        pricing_version: None,
                     }]),
+                    require_env_ack: false,
+                    auto_renew: None,
                 },
             },
         };
@@ -1078,13 +1096,24 @@ mod tests {
                     slots: 1,
                     reward: 1000000000000,
                     min_reputation: Some(0),
+                    min_recent_fulfillment_ratio: None,
+                    holdback_release_sla: None,
+                    max_attestation_age_ms: None,
+                    max_fee_per_execution: None,
+                    depends_on: None,
+                    preferred_sources: None,
+                    preference_window_ms: 0,
+                    omitted: None,
                     instant_match: Some(bounded_vec![PlannedExecution {
                         source: hex![
                             "d80a8b0d800a3320528693947f7317871b2d51e5f3c8f3d0d4e4f7e6938ed68f"
                         ]
                         .into(),
                         start_delay: 0,
+                        pricing_version: None,
                     }]),
+                    require_env_ack: false,
+                    auto_renew: None,
                 },
             },
         };
@@ -29,14 +29,14 @@ fn test_send_register_job_message() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -46,7 +46,8 @@ fn test_send_register_job_message() {
         let ethereum_contract = StateOwner::try_from(hex!("6a34E1f07B57eD968e72895690f3df41b11487eb").to_vec()).unwrap();
         assert_ok!(EthereumHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            ethereum_contract.clone()
+            ethereum_contract.clone(),
+            0
         ));
 
         assert_eq!(EthereumHyperdrive::current_target_chain_owner(), ethereum_contract);
@@ -132,14 +133,14 @@ fn test_send_noop_message() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -149,7 +150,8 @@ fn test_send_noop_message() {
         let ethereum_contract = StateOwner::try_from(hex!("9b526A28eB683c431411435F2A06632642bCcBE9").to_vec()).unwrap();
         assert_ok!(EthereumHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            ethereum_contract.clone()
+            ethereum_contract.clone(),
+            0
         ));
 
         assert_eq!(EthereumHyperdrive::current_target_chain_owner(), ethereum_contract);
@@ -235,14 +237,14 @@ fn test_send_noop_message2() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -252,7 +254,8 @@ fn test_send_noop_message2() {
         let ethereum_contract = StateOwner::try_from(hex!("047673ed04186d8Fc465B296e52084C1B001915c").to_vec()).unwrap();
         assert_ok!(EthereumHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            ethereum_contract.clone()
+            ethereum_contract.clone(),
+            0
         ));
 
         assert_eq!(EthereumHyperdrive::current_target_chain_owner(), ethereum_contract);
@@ -338,14 +341,14 @@ fn test_send_deregister_job_message() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -355,7 +358,8 @@ fn test_send_deregister_job_message() {
         let ethereum_contract = StateOwner::try_from(hex!("6a34E1f07B57eD968e72895690f3df41b11487eb").to_vec()).unwrap();
         assert_ok!(EthereumHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            ethereum_contract.clone()
+            ethereum_contract.clone(),
+            0
         ));
 
         assert_eq!(EthereumHyperdrive::current_target_chain_owner(), ethereum_contract);
@@ -442,14 +446,14 @@ fn test_send_finalize_job_message() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -459,7 +463,8 @@ fn test_send_finalize_job_message() {
         let ethereum_contract = StateOwner::try_from(hex!("6a34E1f07B57eD968e72895690f3df41b11487eb").to_vec()).unwrap();
         assert_ok!(EthereumHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            ethereum_contract.clone()
+            ethereum_contract.clone(),
+            0
         ));
 
         assert_eq!(EthereumHyperdrive::current_target_chain_owner(), ethereum_contract);
@@ -0,0 +1,400 @@
+//! A runtime combining hyperdrive with `pallet_acurast` and `pallet_acurast_marketplace`,
+//! executing actions the way a production runtime does, to test cross-pallet behaviour end to end.
+
+use frame_support::pallet_prelude::*;
+use frame_support::{
+    parameter_types,
+    traits::{ConstBool, ConstU16, ConstU32, ConstU64},
+    PalletId,
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup, Keccak256},
+    AccountId32, BuildStorage, DispatchError, Permill,
+};
+use sp_std::prelude::*;
+
+use pallet_acurast::{Schedule, Script, CU32};
+use pallet_acurast_marketplace::{
+    JobRequirements, JobRequirementsFor, ManagerProvider, ProcessorLastSeenProvider,
+};
+
+use crate::stub::{alice_account_id, bob_account_id, charlie_account_id, AcurastAccountId};
+use crate::{weights, ActionExecutor, MessageIdentifier, ParsedAction, StateOwner};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type Balance = u128;
+
+parameter_types! {
+    pub TargetChainStateOwner: StateOwner = crate::stub::state_owner();
+    pub const TransmissionRate: u64 = 5;
+    pub const TransmissionQuorum: u8 = 2;
+
+    pub const AcurastPalletId: PalletId = PalletId(*b"acrstpid");
+    pub const HyperdrivePalletId: PalletId = PalletId(*b"hypdrpid");
+    pub const MinimumPeriod: u64 = 2000;
+
+    pub const ExistentialDeposit: Balance = 1;
+    pub const MinTransmitterStake: Balance = 1_000;
+    pub const TransmitterWarmup: u64 = 10;
+    pub const TransmitterCooldown: u64 = 20;
+
+    pub const ReportTolerance: u64 = 12000;
+    pub const JobRegistrationDeposit: Balance = 0;
+    pub const PendingPayoutTimeout: u64 = 600_000;
+    pub const ScheduleChangeTimeout: u64 = 600_000;
+    pub const DeliveryChallengeWindow: u64 = 3_600_000;
+    pub const MinSchedulingWindow: u64 = 3_600_000;
+    pub const ImmediatePayoutRatio: Permill = Permill::from_percent(40);
+    pub const MaxExecutionsPerJob: u64 = 6_308_000;
+}
+
+// Configure a mock runtime including all pallets involved in executing hyperdrive actions.
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Acurast: pallet_acurast::{Pallet, Call, Storage, Event<T>},
+        AcurastMarketplace: pallet_acurast_marketplace::{Pallet, Call, Storage, Event<T>},
+        Hyperdrive: crate,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Block = Block;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId32;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+    type FreezeIdentifier = ();
+    type RuntimeHoldReason = ();
+    type MaxHolds = ConstU32<0>;
+    type MaxFreezes = ConstU32<0>;
+}
+
+impl pallet_acurast::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RegistrationExtra = JobRequirementsFor<Self>;
+    type MaxAllowedSources = CU32<4>;
+    type MaxCertificateRevocationListUpdates = ConstU32<10>;
+    type MaxAppIdLen = ConstU32<128>;
+    type MaxAllowedAppIds = ConstU32<4>;
+    type MaxTrustedRoots = ConstU32<4>;
+    type MaxSlots = CU32<64>;
+    type PalletId = AcurastPalletId;
+    type MaxEnvVars = CU32<10>;
+    type EnvKeyMaxSize = CU32<32>;
+    type EnvValueMaxSize = CU32<1024>;
+    type RevocationListUpdateBarrier = ();
+    type KeyAttestationBarrier = ();
+    type UnixTime = pallet_timestamp::Pallet<Test>;
+    type JobHooks = pallet_acurast_marketplace::Pallet<Test>;
+    type WeightInfo = pallet_acurast::weights::WeightInfo<Test>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestBenchmarkHelper;
+}
+
+pub struct ManagerOf;
+
+impl ManagerProvider<Test> for ManagerOf {
+    fn manager_of(
+        owner: &<Test as frame_system::Config>::AccountId,
+    ) -> Result<<Test as frame_system::Config>::AccountId, DispatchError> {
+        Ok(owner.clone())
+    }
+}
+
+pub struct LastSeenProvider;
+
+impl ProcessorLastSeenProvider<Test> for LastSeenProvider {
+    fn last_seen(_processor: &<Test as frame_system::Config>::AccountId) -> Option<u128> {
+        Some(Timestamp::get().into())
+    }
+}
+
+impl pallet_acurast_marketplace::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxAllowedConsumers = CU32<4>;
+    type MaxProposedMatches = ConstU32<10>;
+    type MaxFinalizeJobs = ConstU32<10>;
+    type MaxSLAHistory = ConstU32<3>;
+    type MaxDependents = ConstU32<4>;
+    type MaxDependencyDepth = ConstU32<2>;
+    type MaxExecutionsPerJob = MaxExecutionsPerJob;
+    type DeliveryChallengeWindow = DeliveryChallengeWindow;
+    type MaxDeliveryChallenges = ConstU32<1>;
+    type MinSchedulingWindow = MinSchedulingWindow;
+    type MaxSchedulingWindowChecks = ConstU32<10>;
+    type RestrictedMatching = ConstBool<false>;
+    type MarginEstimateSampleSize = ConstU32<16>;
+    type MaxAssignedJobsPerSource = ConstU32<64>;
+    type EnableOffchainMatcher = ConstBool<false>;
+    type OffchainMatcher = ();
+    type MaxOffchainMatchScan = ConstU32<16>;
+    type MaxOffchainSourceScan = ConstU32<16>;
+    type MaxOffchainMatchesPerBlock = ConstU32<4>;
+    type RegistrationExtra = JobRequirementsFor<Self>;
+    type PalletId = AcurastPalletId;
+    type HyperdrivePalletId = HyperdrivePalletId;
+    type ReportTolerance = ReportTolerance;
+    type PendingPayoutTimeout = PendingPayoutTimeout;
+    type ScheduleChangeTimeout = ScheduleChangeTimeout;
+    type ImmediatePayoutRatio = ImmediatePayoutRatio;
+    type Balance = Balance;
+    type JobRegistrationDeposit = JobRegistrationDeposit;
+    type ManagerProvider = ManagerOf;
+    type ProcessorLastSeenProvider = LastSeenProvider;
+    type RewardManager = ();
+    type MarketplaceHooks = ();
+    type ModuleAttestationCheck = ();
+    type WeightInfo = pallet_acurast_marketplace::weights::WeightInfo<Test>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestBenchmarkHelper;
+}
+
+impl crate::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type ParsableAccountId = AcurastAccountId;
+    type TargetChainOwner = TargetChainStateOwner;
+    type TargetChainHash = H256;
+    type TargetChainBlockNumber = u64;
+    type Balance = Balance;
+    type MaxTransmittersPerSnapshot = CU32<64>;
+    type MaxMessagesPerSubmission = CU32<16>;
+    type TargetChainHashing = Keccak256;
+    type TransmissionRate = TransmissionRate;
+    type TransmissionQuorum = TransmissionQuorum;
+    type ActionExecutor = RuntimeActionExecutor;
+    type Proof = ActionProof;
+    type Currency = Balances;
+    type PermissionlessTransmitters = ConstBool<false>;
+    type MinTransmitterStake = MinTransmitterStake;
+    type TransmitterWarmup = TransmitterWarmup;
+    type TransmitterCooldown = TransmitterCooldown;
+    type MaxStakedTransmitters = ConstU32<2>;
+    type WeightInfo = weights::WeightInfo<Test>;
+}
+
+/// Executes actions like a runtime including `pallet_acurast` and `pallet_acurast_marketplace` does,
+/// ignoring none of the errors returned by the pallets.
+pub struct RuntimeActionExecutor;
+
+impl ActionExecutor<Test> for RuntimeActionExecutor {
+    fn execute(action: ParsedAction<Test>) -> DispatchResultWithPostInfo {
+        match action {
+            ParsedAction::RegisterJob(job_id, registration) => {
+                Acurast::register_for(job_id, registration)
+            }
+            ParsedAction::DeregisterJob(job_id) => Acurast::deregister_for(job_id),
+            ParsedAction::FinalizeJob(job_ids) => AcurastMarketplace::finalize_jobs_for(job_ids),
+            ParsedAction::SetJobEnvironment(job_id, environments) => {
+                for (source, environment) in environments {
+                    Acurast::set_environment_for(job_id.clone(), source, environment)?;
+                }
+                Ok(().into())
+            }
+            ParsedAction::Noop => Ok(().into()),
+        }
+    }
+}
+
+/// A proof carrying its message in plain, proving it by the hash of its encoding.
+///
+/// Submit [`ActionProof::root`] as the state merkle root before submitting the proof.
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct ActionProof {
+    pub message_id: MessageIdentifier,
+    pub action: Vec<u8>,
+}
+
+impl ActionProof {
+    pub fn new(message_id: MessageIdentifier, action: &ParsedAction<Test>) -> Self {
+        Self {
+            message_id,
+            action: action.encode(),
+        }
+    }
+
+    /// The state merkle root the proof derives.
+    pub fn root(&self) -> H256 {
+        H256(sp_io::hashing::blake2_256(&self.encode()))
+    }
+}
+
+impl crate::Proof<Test, ()> for ActionProof {
+    type Error = codec::Error;
+
+    fn calculate_root(&self, _owner: &StateOwner) -> Result<[u8; 32], Self::Error> {
+        Ok(self.root().0)
+    }
+
+    fn message_id(&self) -> Result<MessageIdentifier, Self::Error> {
+        Ok(self.message_id)
+    }
+
+    fn message(&self) -> Result<ParsedAction<Test>, Self::Error> {
+        ParsedAction::<Test>::decode(&mut self.action.as_slice())
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_acurast::BenchmarkHelper<Test> for TestBenchmarkHelper {
+    fn registration_extra(_instant_match: bool) -> JobRequirementsFor<Test> {
+        job_requirements(1)
+    }
+
+    fn funded_account(index: u32) -> AccountId32 {
+        frame_benchmarking::account("token_account", index, 0)
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_acurast_marketplace::BenchmarkHelper<Test> for TestBenchmarkHelper {
+    fn registration_extra(r: JobRequirementsFor<Test>) -> JobRequirementsFor<Test> {
+        r
+    }
+
+    fn funded_account(index: u32, _amount: Balance) -> AccountId32 {
+        frame_benchmarking::account("token_account", index, 0)
+    }
+}
+
+pub fn script() -> Script {
+    let mut script = b"ipfs://".to_vec();
+    script.resize(53, 0);
+    script.try_into().unwrap()
+}
+
+pub fn job_requirements(slots: u8) -> JobRequirementsFor<Test> {
+    JobRequirements {
+        slots,
+        reward: 1_000,
+        min_reputation: None,
+        min_recent_fulfillment_ratio: None,
+        holdback_release_sla: None,
+        max_attestation_age_ms: None,
+        max_fee_per_execution: None,
+        depends_on: None,
+        preferred_sources: None,
+        preference_window_ms: 0,
+        omitted: None,
+        instant_match: None,
+        require_env_ack: false,
+        auto_renew: None,
+    }
+}
+
+/// A registration of a job with two executions starting at `start_time`.
+pub fn job_registration(start_time: u64, slots: u8) -> pallet_acurast::JobRegistrationFor<Test> {
+    pallet_acurast::JobRegistration {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5_000,
+            start_time,
+            end_time: start_time + 20_000,
+            interval: 10_000,
+            max_start_delay: 0,
+        },
+        memory: 5_000,
+        network_requests: 5,
+        storage: 20_000,
+        required_modules: Default::default(),
+        extra: job_requirements(slots),
+    }
+}
+
+/// Builds the genesis storage with alice and bob as transmitters for the first 100 blocks.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    crate::GenesisConfig::<Test> {
+        transmitters: vec![
+            (
+                alice_account_id(),
+                crate::ActivityWindow::Blocks {
+                    start_block: 0,
+                    end_block: 100,
+                },
+            ),
+            (
+                bob_account_id(),
+                crate::ActivityWindow::Blocks {
+                    start_block: 0,
+                    end_block: 100,
+                },
+            ),
+        ],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (alice_account_id(), 10_000),
+            (bob_account_id(), 10_000),
+            (charlie_account_id(), 10_000),
+        ],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn events() -> Vec<RuntimeEvent> {
+    let evt = System::events()
+        .into_iter()
+        .map(|evt| evt.event)
+        .collect::<Vec<_>>();
+
+    System::reset_events();
+
+    evt
+}
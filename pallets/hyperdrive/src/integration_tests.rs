@@ -0,0 +1,84 @@
+#![cfg(test)]
+
+use frame_support::assert_ok;
+use pallet_acurast::MultiOrigin;
+use pallet_acurast_marketplace::JobStatus;
+
+use crate::integration_mock::*;
+use crate::stub::{alice_account_id, bob_account_id};
+use crate::types::*;
+
+/// Accepts the root of `proof` for the current snapshot and submits the proof.
+fn relay(proof: ActionProof) {
+    let snapshot = Hyperdrive::latest_snapshot();
+    for transmitter in [alice_account_id(), bob_account_id()] {
+        assert_ok!(Hyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(transmitter),
+            snapshot,
+            proof.root()
+        ));
+    }
+    assert_ok!(Hyperdrive::submit_message(
+        RuntimeOrigin::signed(alice_account_id()),
+        snapshot,
+        proof
+    ));
+}
+
+#[test]
+fn relayed_registration_is_stored() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(now);
+        let job_id = (MultiOrigin::Ethereum(Default::default()), 1);
+        let action =
+            ParsedAction::RegisterJob(job_id.clone(), job_registration(now + 3_600_000, 1));
+
+        relay(ActionProof::new(1, &action));
+
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::Hyperdrive(crate::Event::MessageProcessed(
+                ProcessMessageResult::ActionSuccess
+            )))
+            .as_ref()
+        );
+        assert!(Acurast::stored_job_registration(&job_id.0, &job_id.1).is_some());
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(1, Hyperdrive::message_seq_id());
+    });
+}
+
+#[test]
+fn relayed_registration_failing_in_marketplace_is_not_persisted() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    new_test_ext().execute_with(|| {
+        Timestamp::set_timestamp(now);
+        let job_id = (MultiOrigin::Ethereum(Default::default()), 1);
+        // the marketplace hook rejects the registration only after pallet_acurast stored it
+        let action =
+            ParsedAction::RegisterJob(job_id.clone(), job_registration(now + 3_600_000, 0));
+
+        relay(ActionProof::new(1, &action));
+
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::Hyperdrive(crate::Event::MessageProcessed(
+                ProcessMessageResult::ActionFailed(RawAction::RegisterJob)
+            )))
+            .as_ref()
+        );
+        assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        // the message is consumed nevertheless
+        assert_eq!(1, Hyperdrive::message_seq_id());
+    });
+}
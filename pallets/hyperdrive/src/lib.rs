@@ -6,9 +6,15 @@ pub use pallet::*;
 pub use traits::*;
 pub use types::*;
 
+pub(crate) use pallet::STORAGE_VERSION;
+
 #[cfg(test)]
 mod ethereum_tests;
 #[cfg(test)]
+mod integration_mock;
+#[cfg(test)]
+mod integration_tests;
+#[cfg(test)]
 mod mock;
 #[cfg(any(test, feature = "runtime-benchmarks"))]
 mod stub;
@@ -23,6 +29,7 @@ mod traits;
 
 pub mod chain;
 pub mod instances;
+mod migration;
 
 mod types;
 pub mod weights;
@@ -32,17 +39,18 @@ pub mod pallet {
     use core::{fmt::Debug, str::FromStr};
 
     use frame_support::dispatch::PostDispatchInfo;
-    use frame_support::traits::Get;
+    use frame_support::traits::{Currency, Get, ReservableCurrency};
     use frame_support::{
         pallet_prelude::*,
-        sp_runtime::traits::{
-            AtLeast32BitUnsigned, Bounded, CheckEqual, MaybeDisplay, SimpleBitOps,
+        sp_runtime::{
+            traits::{AtLeast32BitUnsigned, Bounded, CheckEqual, MaybeDisplay, SimpleBitOps},
+            SaturatedConversion,
         },
     };
     use frame_support::{transactional, BoundedBTreeSet};
     use frame_system::pallet_prelude::*;
     use pallet_acurast::ParameterBound;
-    use sp_arithmetic::traits::{CheckedRem, Zero};
+    use sp_arithmetic::traits::{CheckedRem, Saturating, Zero};
     use sp_core::H256;
     use sp_runtime::traits::Hash;
     use sp_std::prelude::*;
@@ -50,8 +58,11 @@ pub mod pallet {
 
     use super::*;
 
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     /// A instantiable pallet for receiving secure state synchronizations into Acurast.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
     /// Configures the pallet instance for a specific target chain from which we synchronize state into Acurast.
@@ -110,6 +121,9 @@ pub mod pallet {
         /// The maximum transmitters accepted to submit a state root per snapshot.
         #[pallet::constant]
         type MaxTransmittersPerSnapshot: Get<u32> + ParameterBound;
+        /// The maximum messages accepted in a single [`Pallet::submit_messages`] call.
+        #[pallet::constant]
+        type MaxMessagesPerSubmission: Get<u32> + ParameterBound;
 
         /// The hashing system (algorithm) being used in the runtime (e.g. Blake2).
         type TargetChainHashing: Hash<Output = H256> + TypeInfo;
@@ -122,9 +136,32 @@ pub mod pallet {
 
         type ActionExecutor: ActionExecutor<Self>;
 
+        /// The currency used to reserve the stake of permissionless transmitters.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// If transmitters can register by staking, in addition to the root-managed [`StateTransmitter`]s.
+        ///
+        /// When disabled, no transmitters can register and staked transmitters are not permitted to submit.
+        #[pallet::constant]
+        type PermissionlessTransmitters: Get<bool>;
+        /// The minimum stake of a staked transmitter to be permitted to submit.
+        #[pallet::constant]
+        type MinTransmitterStake: Get<BalanceOf<Self, I>>;
+        /// The number of blocks after registration before a staked transmitter's activity window starts.
+        #[pallet::constant]
+        type TransmitterWarmup: Get<BlockNumberFor<Self>>;
+        /// The number of blocks after deregistration before a staked transmitter's stake can be released.
+        #[pallet::constant]
+        type TransmitterCooldown: Get<BlockNumberFor<Self>>;
+        /// The maximum number of staked transmitters, including those deregistered but not yet released.
+        #[pallet::constant]
+        type MaxStakedTransmitters: Get<u32>;
+
         type WeightInfo: WeightInfo;
     }
 
+    pub type BalanceOf<T, I = ()> =
+        <<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -144,8 +181,32 @@ pub mod pallet {
         },
         TargetChainOwnerUpdated {
             owner: StateOwner,
+            /// The last block in which proofs against the previous owner are still accepted, if any.
+            previous_owner_accepted_until: Option<BlockNumberFor<T>>,
         },
         MessageProcessed(ProcessMessageResult),
+        /// A message was proven against the previous target chain owner during the overlap after a rotation.
+        MessageAcceptedUnderPreviousOwner {
+            message_id: MessageIdentifier,
+            owner: StateOwner,
+        },
+        TransmitterRegistered {
+            account: T::AccountId,
+            stake: BalanceOf<T, I>,
+            activity_window: types::ActivityWindow<BlockNumberFor<T>>,
+        },
+        TransmitterDeregistered {
+            account: T::AccountId,
+            release_block: BlockNumberFor<T>,
+        },
+        TransmitterStakeReleased {
+            account: T::AccountId,
+            stake: BalanceOf<T, I>,
+        },
+        TransmitterSlashed {
+            account: T::AccountId,
+            amount: BalanceOf<T, I>,
+        },
     }
 
     /// This storage field maps the state transmitters to their respective activity window.
@@ -157,6 +218,24 @@ pub mod pallet {
     pub type StateTransmitter<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128, T::AccountId, ActivityWindow<BlockNumberFor<T>>, ValueQuery>;
 
+    /// This storage field maps the transmitters registered by staking to their stake and activity window.
+    ///
+    /// They count towards the quorum like the root-managed [`StateTransmitter`]s as long as their stake is at least [`Config::MinTransmitterStake`].
+    #[pallet::storage]
+    #[pallet::getter(fn staked_transmitter)]
+    pub type StakedTransmitters<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        StakedTransmitter<BalanceOf<T, I>, BlockNumberFor<T>>,
+    >;
+
+    /// The number of entries in [`StakedTransmitters`], bounded by [`Config::MaxStakedTransmitters`].
+    #[pallet::storage]
+    #[pallet::getter(fn staked_transmitter_count)]
+    pub type StakedTransmitterCount<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u32, ValueQuery>;
+
     #[pallet::type_value]
     pub fn FirstSnapshot<T: Config<I>, I: 'static>() -> T::TargetChainBlockNumber {
         1u8.into()
@@ -195,6 +274,14 @@ pub mod pallet {
     pub type CurrentTargetChainOwner<T: Config<I>, I: 'static = ()> =
         StorageValue<_, StateOwner, ValueQuery, FirstTargetChainOwner<T, I>>;
 
+    /// The target chain owner replaced by the last rotation with the last block (inclusive) in which proofs against it are still accepted.
+    ///
+    /// Allows messages produced under the old owner but not yet relayed to be submitted during a contract migration on the target chain.
+    #[pallet::storage]
+    #[pallet::getter(fn previous_target_chain_owner)]
+    pub type PreviousTargetChainOwner<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, (StateOwner, BlockNumberFor<T>)>;
+
     #[pallet::type_value]
     pub fn InitialTransmissionRate<T: Config<I>, I: 'static>() -> T::TargetChainBlockNumber {
         T::TransmissionRate::get()
@@ -205,6 +292,61 @@ pub mod pallet {
     pub type CurrentTransmissionRate<T: Config<I>, I: 'static = ()> =
         StorageValue<_, T::TargetChainBlockNumber, ValueQuery, InitialTransmissionRate<T, I>>;
 
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+        /// The initial [`StateTransmitter`]s with their activity windows, permitted to submit without a prior call to
+        /// [`Pallet::update_state_transmitters`].
+        pub transmitters: Vec<(T::AccountId, ActivityWindow<BlockNumberFor<T>>)>,
+        /// The initial [`CurrentSnapshot`], defaults to [`FirstSnapshot`] if [`None`].
+        pub current_snapshot: Option<<T as Config<I>>::TargetChainBlockNumber>,
+        /// The initial [`MessageSequenceId`], i.e. the identifier of the last message processed before genesis.
+        pub message_sequence_id: MessageIdentifier,
+        /// The initial [`CurrentTargetChainOwner`], defaults to [`Config::TargetChainOwner`] if [`None`].
+        pub target_chain_owner: Option<StateOwner>,
+    }
+
+    impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+        fn default() -> Self {
+            Self {
+                transmitters: vec![],
+                current_snapshot: None,
+                message_sequence_id: 0,
+                target_chain_owner: None,
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
+        fn build(&self) {
+            for (account, activity_window) in &self.transmitters {
+                assert!(
+                    activity_window.is_valid(),
+                    "Genesis transmitter activity window must start before it ends"
+                );
+                assert!(
+                    !<StateTransmitter<T, I>>::contains_key(account),
+                    "Duplicate genesis transmitter"
+                );
+                <StateTransmitter<T, I>>::insert(account, activity_window.clone());
+            }
+            if let Some(snapshot) = self.current_snapshot {
+                <CurrentSnapshot<T, I>>::put(snapshot);
+            }
+            <MessageSequenceId<T, I>>::put(self.message_sequence_id);
+            if let Some(owner) = &self.target_chain_owner {
+                <CurrentTargetChainOwner<T, I>>::put(owner);
+            }
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        fn on_runtime_upgrade() -> frame_support::weights::Weight {
+            crate::migration::migrate::<T, I>()
+        }
+    }
+
     #[pallet::error]
     pub enum Error<T, I = ()> {
         /// A known transmitter submits outside the window of activity he is permissioned to.
@@ -215,6 +357,20 @@ pub mod pallet {
         ProofDoesNotMatch,
         MessageIdDoesNotMatch,
         InvalidMessageId,
+        /// Permissionless transmitters are disabled for this instance.
+        PermissionlessTransmittersDisabled,
+        /// The stake is below [`Config::MinTransmitterStake`].
+        InsufficientTransmitterStake,
+        /// The account is already registered as a staked transmitter.
+        TransmitterAlreadyRegistered,
+        /// The account is not registered as a staked transmitter.
+        TransmitterNotRegistered,
+        /// The staked transmitter already deregistered.
+        TransmitterAlreadyDeregistered,
+        /// The stake cannot be released before deregistering and waiting for [`Config::TransmitterCooldown`].
+        TransmitterStakeLocked,
+        /// The maximum number of staked transmitters is reached.
+        TooManyStakedTransmitters,
     }
 
     #[pallet::call]
@@ -287,12 +443,8 @@ pub mod pallet {
                 Error::<T, I>::UnexpectedSnapshot
             );
 
-            let activity_window = <StateTransmitter<T, I>>::get(&who);
-            let current_block = <frame_system::Pallet<T>>::block_number();
-            // valid window is defined inclusive start_block, exclusive end_block
             ensure!(
-                activity_window.start_block <= current_block
-                    && current_block < activity_window.end_block,
+                Self::is_active_transmitter(&who, expected_snapshot.saturated_into()),
                 Error::<T, I>::SubmitOutsideTransmitterActivityWindow
             );
 
@@ -357,38 +509,70 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let _ = ensure_signed(origin)?;
 
-            let derived_root = proof.calculate_root().map_err(|err| {
-                log::debug!("Failed to validate proof: {:?}", &err);
+            let owner = Self::validate_proof(snapshot, &proof)?;
+            Self::process_message(&proof, owner)?;
 
-                Error::<T, I>::ProofInvalid
-            })?;
-
-            if !Self::validate_state_merkle_root(snapshot, T::TargetChainHash::from(derived_root)) {
-                return Err(Error::<T, I>::ProofDoesNotMatch)?;
-            }
+            Ok(().into())
+        }
 
-            let _message_id = Self::process_message_id(&proof)?;
+        /// Used by any transmitter to submit multiple messages proven against the state at the specified `snapshot` on the target chain.
+        ///
+        /// Messages are processed in the given order, each as in [`Pallet::submit_message`].
+        ///
+        /// # Error behaviour
+        ///
+        /// We fail with a [`DispatchError`] if any of the given `proofs` is invalid or any message arrives out of sequence, reverting all messages.
+        /// Failures decoding payloads or triggering actions emit an event per message but do not fail the extrinsic.
+        #[pallet::call_index(5)]
+        #[pallet::weight(< T as Config<I>>::WeightInfo::submit_messages(proofs.len() as u32, proofs.encoded_size() as u32))]
+        pub fn submit_messages(
+            origin: OriginFor<T>,
+            // The block number at which the state proofs were generated.
+            snapshot: T::TargetChainBlockNumber,
+            // The state proofs, one per message.
+            proofs: BoundedVec<T::Proof, T::MaxMessagesPerSubmission>,
+        ) -> DispatchResultWithPostInfo {
+            let _ = ensure_signed(origin)?;
 
-            // don't fail extrinsic from here onwards
-            if let Err(e) = Self::process_action(&proof) {
-                Self::deposit_event(Event::MessageProcessed(e));
-            } else {
-                Self::deposit_event(Event::MessageProcessed(ProcessMessageResult::ActionSuccess));
+            let owners = proofs
+                .iter()
+                .map(|proof| Self::validate_proof(snapshot, proof))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (proof, owner) in proofs.iter().zip(owners) {
+                Self::process_message(proof, owner)?;
             }
 
             Ok(().into())
         }
 
         /// Updates the target chain owner (contract address) in storage. Can only be called by a privileged/root account.
+        ///
+        /// For `overlap_blocks > 0`, proofs against the replaced owner are still accepted up to and including `overlap_blocks` blocks from now,
+        /// so messages produced under the old owner can still be relayed. Otherwise the replaced owner is rejected immediately.
         #[pallet::call_index(3)]
         #[pallet::weight(< T as Config<I>>::WeightInfo::update_target_chain_owner())]
         pub fn update_target_chain_owner(
             origin: OriginFor<T>,
             owner: StateOwner,
+            overlap_blocks: BlockNumberFor<T>,
         ) -> DispatchResult {
             ensure_root(origin)?;
+            let previous_owner = Self::current_target_chain_owner();
+            let previous_owner_accepted_until =
+                if !overlap_blocks.is_zero() && previous_owner != owner {
+                    let until =
+                        <frame_system::Pallet<T>>::block_number().saturating_add(overlap_blocks);
+                    <PreviousTargetChainOwner<T, I>>::put((previous_owner, until));
+                    Some(until)
+                } else {
+                    <PreviousTargetChainOwner<T, I>>::kill();
+                    None
+                };
             Self::set_target_chain_owner(owner.clone());
-            Self::deposit_event(Event::TargetChainOwnerUpdated { owner });
+            Self::deposit_event(Event::TargetChainOwnerUpdated {
+                owner,
+                previous_owner_accepted_until,
+            });
             Ok(())
         }
 
@@ -403,6 +587,152 @@ pub mod pallet {
             CurrentSnapshot::<T, I>::set(snapshot);
             Ok(())
         }
+
+        /// Registers the caller as a transmitter by reserving `stake`.
+        ///
+        /// The transmitter's activity window starts [`Config::TransmitterWarmup`] blocks from now and is open-ended.
+        #[pallet::call_index(6)]
+        #[pallet::weight(< T as Config<I>>::WeightInfo::register_transmitter())]
+        pub fn register_transmitter(
+            origin: OriginFor<T>,
+            stake: BalanceOf<T, I>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                T::PermissionlessTransmitters::get(),
+                Error::<T, I>::PermissionlessTransmittersDisabled
+            );
+            ensure!(
+                stake >= T::MinTransmitterStake::get(),
+                Error::<T, I>::InsufficientTransmitterStake
+            );
+            ensure!(
+                !<StakedTransmitters<T, I>>::contains_key(&who),
+                Error::<T, I>::TransmitterAlreadyRegistered
+            );
+            let count = Self::staked_transmitter_count();
+            ensure!(
+                count < T::MaxStakedTransmitters::get(),
+                Error::<T, I>::TooManyStakedTransmitters
+            );
+
+            T::Currency::reserve(&who, stake)?;
+
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            let activity_window = ActivityWindow::Blocks {
+                start_block: current_block.saturating_add(T::TransmitterWarmup::get()),
+                end_block: BlockNumberFor::<T>::max_value(),
+            };
+            <StakedTransmitters<T, I>>::insert(
+                &who,
+                StakedTransmitter {
+                    stake,
+                    activity_window: activity_window.clone(),
+                    release_block: None,
+                },
+            );
+            <StakedTransmitterCount<T, I>>::put(count + 1);
+
+            Self::deposit_event(Event::TransmitterRegistered {
+                account: who,
+                stake,
+                activity_window,
+            });
+            Ok(())
+        }
+
+        /// Deregisters the caller as a staked transmitter, closing its activity window immediately.
+        ///
+        /// The stake can be released with [`Pallet::release_transmitter_stake`] after [`Config::TransmitterCooldown`] blocks,
+        /// leaving time to slash misbehaviour of the last submissions.
+        #[pallet::call_index(7)]
+        #[pallet::weight(< T as Config<I>>::WeightInfo::deregister_transmitter())]
+        pub fn deregister_transmitter(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            let release_block = current_block.saturating_add(T::TransmitterCooldown::get());
+            <StakedTransmitters<T, I>>::try_mutate(&who, |transmitter| -> DispatchResult {
+                let transmitter = transmitter
+                    .as_mut()
+                    .ok_or(Error::<T, I>::TransmitterNotRegistered)?;
+                ensure!(
+                    transmitter.release_block.is_none(),
+                    Error::<T, I>::TransmitterAlreadyDeregistered
+                );
+                if let ActivityWindow::Blocks { end_block, .. } = &mut transmitter.activity_window {
+                    *end_block = current_block;
+                }
+                transmitter.release_block = Some(release_block);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::TransmitterDeregistered {
+                account: who,
+                release_block,
+            });
+            Ok(())
+        }
+
+        /// Releases the remaining stake of a deregistered transmitter once its cool-down passed.
+        #[pallet::call_index(8)]
+        #[pallet::weight(< T as Config<I>>::WeightInfo::release_transmitter_stake())]
+        pub fn release_transmitter_stake(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let transmitter = <StakedTransmitters<T, I>>::get(&who)
+                .ok_or(Error::<T, I>::TransmitterNotRegistered)?;
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                transmitter
+                    .release_block
+                    .map_or(false, |release_block| current_block >= release_block),
+                Error::<T, I>::TransmitterStakeLocked
+            );
+
+            T::Currency::unreserve(&who, transmitter.stake);
+            <StakedTransmitters<T, I>>::remove(&who);
+            <StakedTransmitterCount<T, I>>::mutate(|count| *count = count.saturating_sub(1));
+
+            Self::deposit_event(Event::TransmitterStakeReleased {
+                account: who,
+                stake: transmitter.stake,
+            });
+            Ok(())
+        }
+
+        /// Slashes up to `amount` of a staked transmitter's stake for provable misbehaviour. Can only be called by a privileged/root account.
+        ///
+        /// A transmitter whose stake falls below [`Config::MinTransmitterStake`] is no longer permitted to submit.
+        #[pallet::call_index(9)]
+        #[pallet::weight(< T as Config<I>>::WeightInfo::slash_transmitter())]
+        pub fn slash_transmitter(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            amount: BalanceOf<T, I>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let slashed = <StakedTransmitters<T, I>>::try_mutate(
+                &account,
+                |transmitter| -> Result<BalanceOf<T, I>, DispatchError> {
+                    let transmitter = transmitter
+                        .as_mut()
+                        .ok_or(Error::<T, I>::TransmitterNotRegistered)?;
+                    let amount = amount.min(transmitter.stake);
+                    let (_, not_slashed) = T::Currency::slash_reserved(&account, amount);
+                    let slashed = amount.saturating_sub(not_slashed);
+                    transmitter.stake = transmitter.stake.saturating_sub(slashed);
+                    Ok(slashed)
+                },
+            )?;
+
+            Self::deposit_event(Event::TransmitterSlashed {
+                account,
+                amount: slashed,
+            });
+            Ok(())
+        }
     }
 
     impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -417,11 +747,100 @@ pub mod pallet {
                 })
         }
 
+        /// Returns true if `who` is permitted to submit a state merkle root for the target chain `snapshot` at the current block,
+        /// either as a root-managed or as a sufficiently staked transmitter.
+        pub fn is_active_transmitter(who: &T::AccountId, snapshot: u64) -> bool {
+            let current_block = <frame_system::Pallet<T>>::block_number();
+            if <StateTransmitter<T, I>>::get(who).contains(&current_block, snapshot) {
+                return true;
+            }
+            T::PermissionlessTransmitters::get()
+                && <StakedTransmitters<T, I>>::get(who).map_or(false, |transmitter| {
+                    transmitter.stake >= T::MinTransmitterStake::get()
+                        && transmitter
+                            .activity_window
+                            .contains(&current_block, snapshot)
+                })
+        }
+
         /// Sets the target chain owner (contract address) in storage.
         pub fn set_target_chain_owner(owner: StateOwner) {
             <CurrentTargetChainOwner<T, I>>::set(owner);
         }
 
+        /// Returns the target chain owner replaced by the last rotation if proofs against it are still accepted at the current block.
+        pub fn accepted_previous_target_chain_owner() -> Option<StateOwner> {
+            let (owner, until) = Self::previous_target_chain_owner()?;
+            (<frame_system::Pallet<T>>::block_number() <= until).then_some(owner)
+        }
+
+        /// Validates that the `proof` derives a state merkle root accepted for `snapshot`.
+        ///
+        /// The proof is validated against the current target chain owner and, during the overlap after a rotation, against the previous owner.
+        /// Returns the previous owner if only the validation against it succeeded.
+        fn validate_proof(
+            snapshot: T::TargetChainBlockNumber,
+            proof: &T::Proof,
+        ) -> Result<Option<StateOwner>, Error<T, I>> {
+            let result = Self::validate_proof_for_owner(
+                snapshot,
+                proof,
+                &Self::current_target_chain_owner(),
+            );
+            if result.is_ok() {
+                return Ok(None);
+            }
+            if let Some(previous_owner) = Self::accepted_previous_target_chain_owner() {
+                if Self::validate_proof_for_owner(snapshot, proof, &previous_owner).is_ok() {
+                    return Ok(Some(previous_owner));
+                }
+            }
+            result.map(|_| None)
+        }
+
+        /// Validates that the `proof` derives a state merkle root accepted for `snapshot` when proving state of `owner`.
+        fn validate_proof_for_owner(
+            snapshot: T::TargetChainBlockNumber,
+            proof: &T::Proof,
+            owner: &StateOwner,
+        ) -> Result<(), Error<T, I>> {
+            let derived_root = proof.calculate_root(owner).map_err(|err| {
+                log::debug!("Failed to validate proof: {:?}", &err);
+
+                Error::<T, I>::ProofInvalid
+            })?;
+
+            ensure!(
+                Self::validate_state_merkle_root(snapshot, T::TargetChainHash::from(derived_root)),
+                Error::<T, I>::ProofDoesNotMatch
+            );
+
+            Ok(())
+        }
+
+        /// Processes the message of an already validated `proof`, failing only if its message ID is out of sequence.
+        ///
+        /// `previous_owner` is the owner the proof was validated against if it is not the current one.
+        fn process_message(
+            proof: &T::Proof,
+            previous_owner: Option<StateOwner>,
+        ) -> Result<(), Error<T, I>> {
+            let message_id = Self::process_message_id(proof)?;
+
+            if let Some(owner) = previous_owner {
+                Self::deposit_event(Event::MessageAcceptedUnderPreviousOwner { message_id, owner });
+            }
+
+            // don't fail from here onwards
+            if let Err(e) = Self::process_action(proof) {
+                Self::deposit_event(Event::MessageProcessed(e));
+            } else {
+                Self::deposit_event(Event::MessageProcessed(ProcessMessageResult::ActionSuccess));
+            }
+
+            Ok(())
+        }
+
         /// Processes a message with `key` and `payload`.
         ///
         /// **When action processing fails, the message sequence increment above is still persisted, only side-effects produced by the action should be reverted**.
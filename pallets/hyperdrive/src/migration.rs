@@ -0,0 +1,49 @@
+use frame_support::{
+    traits::{GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::Get;
+
+use super::*;
+
+pub mod v0 {
+    use frame_support::pallet_prelude::*;
+
+    /// The transmitter activity window before snapshot based windows were supported.
+    #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+    pub struct ActivityWindow<BlockNumber> {
+        pub start_block: BlockNumber,
+        pub end_block: BlockNumber,
+    }
+}
+
+pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+    let migrations: [(u16, &dyn Fn() -> Weight); 1] = [(1, &migrate_to_v1::<T, I>)];
+
+    let onchain_version = Pallet::<T, I>::on_chain_storage_version();
+    let mut weight: Weight = Default::default();
+    for (i, f) in migrations.into_iter() {
+        if onchain_version < StorageVersion::new(i) {
+            weight += f();
+        }
+    }
+
+    STORAGE_VERSION.put::<Pallet<T, I>>();
+    weight + T::DbWeight::get().writes(1)
+}
+
+/// Wraps the existing block based activity windows in [`ActivityWindow::Blocks`].
+fn migrate_to_v1<T: Config<I>, I: 'static>() -> Weight {
+    let mut count = 0u64;
+    StateTransmitter::<T, I>::translate_values::<v0::ActivityWindow<BlockNumberFor<T>>, _>(
+        |window| {
+            count += 1;
+            Some(ActivityWindow::Blocks {
+                start_block: window.start_block,
+                end_block: window.end_block,
+            })
+        },
+    );
+    T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
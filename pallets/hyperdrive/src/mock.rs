@@ -4,7 +4,7 @@ use derive_more::{Display, From, Into};
 use frame_support::pallet_prelude::*;
 use frame_support::{
     parameter_types,
-    traits::{ConstU16, ConstU64},
+    traits::{ConstBool, ConstU16, ConstU32, ConstU64},
     Deserialize, PalletId, Serialize,
 };
 use frame_system as system;
@@ -24,7 +24,7 @@ use pallet_acurast_marketplace::RegistrationExtra;
 
 use crate::chain::tezos::TezosParser;
 use crate::instances::{AlephZeroInstance, EthereumInstance, TezosInstance};
-use crate::stub::AcurastAccountId;
+use crate::stub::{alice_account_id, bob_account_id, charlie_account_id, AcurastAccountId};
 use crate::types::RawAction;
 use crate::{weights, ActionExecutor, ParsedAction, StateOwner, StateProof, StateProofNode};
 
@@ -37,6 +37,11 @@ parameter_types! {
 
     pub const AcurastPalletId: PalletId = PalletId(*b"acrstpid");
     pub const MinimumPeriod: u64 = 2000;
+
+    pub const ExistentialDeposit: Balance = 1;
+    pub const MinTransmitterStake: Balance = 1_000;
+    pub const TransmitterWarmup: u64 = 10;
+    pub const TransmitterCooldown: u64 = 20;
 }
 
 // Configure a mock runtime to test the pallet.
@@ -44,6 +49,7 @@ frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         Acurast: pallet_acurast::{Pallet, Call, Storage, Event<T>},
         TezosHyperdrive: crate::<Instance1>,
         EthereumHyperdrive: crate::<Instance2>,
@@ -68,7 +74,7 @@ impl system::Config for Test {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -84,6 +90,22 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+    type FreezeIdentifier = ();
+    type RuntimeHoldReason = ();
+    type MaxHolds = ConstU32<0>;
+    type MaxFreezes = ConstU32<0>;
+}
+
 pub type MaxAllowedSources = CU32<4>;
 pub type MaxSlots = CU32<64>;
 
@@ -93,6 +115,9 @@ impl pallet_acurast::Config for Test {
         RegistrationExtra<Balance, <Self as frame_system::Config>::AccountId, Self::MaxSlots>;
     type MaxAllowedSources = MaxAllowedSources;
     type MaxCertificateRevocationListUpdates = frame_support::traits::ConstU32<10>;
+    type MaxAppIdLen = frame_support::traits::ConstU32<128>;
+    type MaxAllowedAppIds = frame_support::traits::ConstU32<4>;
+    type MaxTrustedRoots = frame_support::traits::ConstU32<4>;
     type MaxSlots = MaxSlots;
     type PalletId = AcurastPalletId;
     type MaxEnvVars = CU32<10>;
@@ -115,6 +140,7 @@ impl crate::Config<TezosInstance> for Test {
     type TargetChainBlockNumber = u64;
     type Balance = Balance;
     type MaxTransmittersPerSnapshot = CU32<64>;
+    type MaxMessagesPerSubmission = CU32<16>;
     type TargetChainHashing = Keccak256;
     type TransmissionRate = TransmissionRate;
     type TransmissionQuorum = TransmissionQuorum;
@@ -123,6 +149,12 @@ impl crate::Config<TezosInstance> for Test {
         Self::ParsableAccountId,
         <Self as frame_system::Config>::AccountId,
     >;
+    type Currency = Balances;
+    type PermissionlessTransmitters = ConstBool<true>;
+    type MinTransmitterStake = MinTransmitterStake;
+    type TransmitterWarmup = TransmitterWarmup;
+    type TransmitterCooldown = TransmitterCooldown;
+    type MaxStakedTransmitters = ConstU32<2>;
     type WeightInfo = weights::WeightInfo<Test>;
 }
 
@@ -134,11 +166,18 @@ impl crate::Config<EthereumInstance> for Test {
     type TargetChainBlockNumber = u64;
     type Balance = Balance;
     type MaxTransmittersPerSnapshot = CU32<64>;
+    type MaxMessagesPerSubmission = CU32<16>;
     type TargetChainHashing = Keccak256;
     type TransmissionRate = TransmissionRate;
     type TransmissionQuorum = TransmissionQuorum;
     type ActionExecutor = ();
     type Proof = crate::chain::ethereum::EthereumProof<Self, AcurastAccountId>;
+    type Currency = Balances;
+    type PermissionlessTransmitters = ConstBool<true>;
+    type MinTransmitterStake = MinTransmitterStake;
+    type TransmitterWarmup = TransmitterWarmup;
+    type TransmitterCooldown = TransmitterCooldown;
+    type MaxStakedTransmitters = ConstU32<2>;
     type WeightInfo = weights::WeightInfo<Test>;
 }
 
@@ -150,6 +189,7 @@ impl crate::Config<AlephZeroInstance> for Test {
     type TargetChainBlockNumber = u64;
     type Balance = Balance;
     type MaxTransmittersPerSnapshot = CU32<64>;
+    type MaxMessagesPerSubmission = CU32<16>;
     type TargetChainHashing = Keccak256;
     type TransmissionRate = TransmissionRate;
     type TransmissionQuorum = TransmissionQuorum;
@@ -158,15 +198,37 @@ impl crate::Config<AlephZeroInstance> for Test {
         Self::ParsableAccountId,
         <Self as frame_system::Config>::AccountId,
     >;
+    type Currency = Balances;
+    type PermissionlessTransmitters = ConstBool<false>;
+    type MinTransmitterStake = MinTransmitterStake;
+    type TransmitterWarmup = TransmitterWarmup;
+    type TransmitterCooldown = TransmitterCooldown;
+    type MaxStakedTransmitters = ConstU32<2>;
     type WeightInfo = weights::WeightInfo<Test>;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    let storage = system::GenesisConfig::<Test>::default()
+    new_test_ext_with_tezos_genesis(Default::default())
+}
+
+/// Builds the genesis storage with the given genesis config of the [`TezosInstance`].
+pub fn new_test_ext_with_tezos_genesis(
+    tezos_genesis: crate::GenesisConfig<Test, TezosInstance>,
+) -> sp_io::TestExternalities {
+    let mut storage = system::GenesisConfig::<Test>::default()
         .build_storage()
-        .unwrap()
-        .into();
+        .unwrap();
+    tezos_genesis.assimilate_storage(&mut storage).unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (alice_account_id(), 10_000),
+            (bob_account_id(), 10_000),
+            (charlie_account_id(), 10_000),
+        ],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
 
     let mut ext = sp_io::TestExternalities::new(storage);
     ext.execute_with(|| System::set_block_number(1));
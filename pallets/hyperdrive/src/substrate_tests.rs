@@ -1,14 +1,19 @@
 #![cfg(test)]
 
-use crate::chain::substrate::{MMRProofItems, ProofLeaf, SubstrateProof};
+use crate::chain::substrate::{MMRProofItems, ProofLeaf, SubstrateProof, SubstrateValidationError};
 use crate::instances::AlephZeroInstance;
 use crate::stub::*;
 use crate::types::*;
 use crate::{
     mock::*,
     types::{ActivityWindow, StateTransmitterUpdate},
+    ParsedAction, Proof,
 };
-use frame_support::assert_ok;
+use acurast_core_ink::types::{
+    OutgoingActionPayloadV1, RawOutgoingAction, RegisterJobMatchV1, RegisterJobPayloadV1,
+};
+use codec::Encode;
+use frame_support::{assert_ok, traits::Get};
 use hex_literal::hex;
 use sp_core::H256;
 use sp_runtime::{bounded_vec, AccountId32};
@@ -25,14 +30,14 @@ fn test_send_noop_message() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -102,14 +107,14 @@ fn test_send_noop_message2() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -181,14 +186,14 @@ fn test_send_noop_message3() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -250,3 +255,89 @@ fn test_send_noop_message3() {
         );
     });
 }
+
+/// Encodes a job registration as sent by the proxy, with `allowed_sources` and `instant_match` of the given lengths.
+fn register_job_message(allowed_sources: u32, instant_match: u32) -> Vec<u8> {
+    let payload = RegisterJobPayloadV1 {
+        job_id: 1,
+        allowed_sources: (0..allowed_sources).map(|i| [i as u8; 32]).collect(),
+        allow_only_verified_sources: false,
+        destination: [0; 32],
+        required_modules: vec![],
+        script: b"ipfs://QmUeNeQTQNvUqPqnEaxymY1zzUmY5WoqRmSSGDJh3UyLkG".to_vec(),
+        duration: 5000,
+        start_time: 1_700_000_000_000,
+        end_time: 1_700_000_060_000,
+        interval: 10_000,
+        max_start_delay: 0,
+        memory: 1_000,
+        network_requests: 1,
+        storage: 0,
+        slots: instant_match as u8,
+        reward: 1_000,
+        min_reputation: None,
+        instant_match: (0..instant_match)
+            .map(|i| RegisterJobMatchV1 {
+                source: [i as u8; 32],
+                start_delay: 0,
+            })
+            .collect(),
+        expected_fulfillment_fee: 0,
+    };
+    RawOutgoingAction {
+        id: 1,
+        origin: [0; 32],
+        payload_version: 1,
+        payload: OutgoingActionPayloadV1::RegisterJob(payload).encode(),
+    }
+    .encode()
+}
+
+/// Parses `data` like the Aleph Zero instance does for a message leaf of a submitted proof.
+fn parse_message(data: Vec<u8>) -> Result<ParsedAction<Test>, SubstrateValidationError> {
+    let proof = SubstrateProof::<AcurastAccountId, AccountId32> {
+        mmr_size: 1,
+        proof: bounded_vec![],
+        leaves: vec![ProofLeaf {
+            leaf_index: 0,
+            data,
+        }],
+        marker: PhantomData::default(),
+    };
+    <SubstrateProof<AcurastAccountId, AccountId32> as Proof<Test, AlephZeroInstance>>::message(
+        &proof,
+    )
+}
+
+#[test]
+fn test_register_job_message_at_acurast_bounds() {
+    let max_allowed_sources = MaxAllowedSources::get();
+    let max_slots = MaxSlots::get();
+
+    match parse_message(register_job_message(max_allowed_sources, max_slots)) {
+        Ok(ParsedAction::RegisterJob(_, registration)) => {
+            assert_eq!(
+                Some(max_allowed_sources as usize),
+                registration.allowed_sources.map(|sources| sources.len())
+            );
+            assert_eq!(
+                Some(max_slots as usize),
+                registration
+                    .extra
+                    .requirements
+                    .instant_match
+                    .map(|executions| executions.len())
+            );
+        }
+        other => panic!("unexpected parsing result {:?}", other),
+    }
+
+    assert!(matches!(
+        parse_message(register_job_message(max_allowed_sources + 1, max_slots)),
+        Err(SubstrateValidationError::TooManyAllowedSources)
+    ));
+    assert!(matches!(
+        parse_message(register_job_message(max_allowed_sources, max_slots + 1)),
+        Err(SubstrateValidationError::TooManyPlannedExecutions)
+    ));
+}
@@ -9,7 +9,7 @@ use sp_runtime::AccountId32;
 use std::marker::PhantomData;
 
 use crate::chain::tezos::TezosProof;
-use crate::instances::TezosInstance;
+use crate::instances::{AlephZeroInstance, EthereumInstance, TezosInstance};
 use crate::stub::*;
 use crate::types::*;
 use crate::{
@@ -27,7 +27,7 @@ fn update_single_state_transmitters() {
 
         let actions = vec![StateTransmitterUpdate::Add(
             alice_account_id(),
-            ActivityWindow {
+            ActivityWindow::Blocks {
                 start_block: 0,
                 end_block: 100,
             },
@@ -44,7 +44,7 @@ fn update_single_state_transmitters() {
                 crate::Event::StateTransmittersUpdate {
                     added: vec![(
                         alice_account_id(),
-                        ActivityWindow {
+                        ActivityWindow::Blocks {
                             start_block: 0,
                             end_block: 100
                         }
@@ -65,14 +65,14 @@ fn update_multiple_state_transmitters() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 0,
                     end_block: 100,
                 },
             ),
             StateTransmitterUpdate::Update(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 0,
                     end_block: 100,
                 },
@@ -91,14 +91,14 @@ fn update_multiple_state_transmitters() {
                 crate::Event::StateTransmittersUpdate {
                     added: vec![(
                         alice_account_id(),
-                        ActivityWindow {
+                        ActivityWindow::Blocks {
                             start_block: 0,
                             end_block: 100
                         }
                     )],
                     updated: vec![(
                         alice_account_id(),
-                        ActivityWindow {
+                        ActivityWindow::Blocks {
                             start_block: 0,
                             end_block: 100
                         }
@@ -118,7 +118,7 @@ fn update_state_transmitters_non_root() {
     test.execute_with(|| {
         let actions = vec![StateTransmitterUpdate::Add(
             alice_account_id(),
-            ActivityWindow {
+            ActivityWindow::Blocks {
                 start_block: 0,
                 end_block: 100,
             },
@@ -141,7 +141,7 @@ fn submit_outside_activity_window() {
     test.execute_with(|| {
         let actions = vec![StateTransmitterUpdate::Add(
             alice_account_id(),
-            ActivityWindow {
+            ActivityWindow::Blocks {
                 start_block: 10,
                 end_block: 20,
             },
@@ -188,7 +188,7 @@ fn submit_outside_transmission_rate() {
     test.execute_with(|| {
         let actions = vec![StateTransmitterUpdate::Add(
             alice_account_id(),
-            ActivityWindow {
+            ActivityWindow::Blocks {
                 start_block: 10,
                 end_block: 20,
             },
@@ -219,14 +219,14 @@ fn submit_state_merkle_root() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -275,14 +275,14 @@ fn submit_state_merkle_root() {
                     added: vec![
                         (
                             alice_account_id(),
-                            ActivityWindow {
+                            ActivityWindow::Blocks {
                                 start_block: 10,
                                 end_block: 20
                             }
                         ),
                         (
                             bob_account_id(),
-                            ActivityWindow {
+                            ActivityWindow::Blocks {
                                 start_block: 10,
                                 end_block: 50
                             }
@@ -337,14 +337,14 @@ fn test_send_message_value_parsing_fails() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -354,7 +354,8 @@ fn test_send_message_value_parsing_fails() {
         let tezos_contract = StateOwner::try_from(hex!("050a00000016011ba8a95352a4d7f3c753ca700e10ab46cbf963f400").to_vec()).unwrap();
         assert_ok!(TezosHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            tezos_contract.clone()
+            tezos_contract.clone(),
+            0
         ));
 
         assert_eq!(TezosHyperdrive::current_target_chain_owner(), tezos_contract);
@@ -428,14 +429,14 @@ fn test_send_message() {
         let actions = vec![
             StateTransmitterUpdate::Add(
                 alice_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 20,
                 },
             ),
             StateTransmitterUpdate::Add(
                 bob_account_id(),
-                ActivityWindow {
+                ActivityWindow::Blocks {
                     start_block: 10,
                     end_block: 50,
                 },
@@ -445,7 +446,8 @@ fn test_send_message() {
         let tezos_contract = StateOwner::try_from(hex!("050a000000160199651cbe1a155a5c8e5af7d6ea5c3f48eebb8c9c00").to_vec()).unwrap();
         assert_ok!(TezosHyperdrive::update_target_chain_owner(
             RuntimeOrigin::root().into(),
-            tezos_contract.clone()
+            tezos_contract.clone(),
+            0
         ));
 
         assert_eq!(TezosHyperdrive::current_target_chain_owner(), tezos_contract);
@@ -505,3 +507,697 @@ fn test_send_message() {
         );
     });
 }
+
+/// Builds proofs for three messages with IDs `1, 2, 3` in a single tree, where the second message's value cannot be parsed.
+fn batch_proofs(owner: StateOwner) -> (H256, Vec<TezosProof<AcurastAccountId, AccountId32>>) {
+    let valid_value = StateValue::try_from(hex!("050707010000000c52454749535445525f4a4f4207070a00000016000016e64994c2ddbd293695b63e4cade029d3c8b5e30a000000ec050707030a0707050902000000250a00000020d80a8b0d800a3320528693947f7317871b2d51e5f3c8f3d0d4e4f7e6938ed68f070707070509020000002907070a00000020d80a8b0d800a3320528693947f7317871b2d51e5f3c8f3d0d4e4f7e6938ed68f00000707050900000707008080e898a9bf8d0700010707001d0707000107070001070702000000000707070700b40707070080cfb1eca062070700a0a9070707000000a0a5aaeca06207070a00000035697066733a2f2f516d536e317252737a444b354258634e516d4e367543767a4d376858636548555569426b61777758396b534d474b0000").to_vec()).unwrap();
+    let invalid_value = StateValue::try_from(hex!("050707010000000c52454749535445525f4a4f4207070a0000001600008a8584be3718453e78923713a6966202b05f99c60a000000ee05070703030707050902000000250a00000020000000000000000000000000000000000000000000000000000000000000000007070707000007070509020000002907070a00000020111111111111111111111111111111111111111111111111111111111111111100000707030607070a00000001ff00010707000107070001070700010707020000000200000707070700b0d403070700b4f292aaf36107070098e4030707000000b4b8dba6f36107070a00000035697066733a2f2f516d64484c6942596174626e6150645573544d4d4746574534326353414a43485937426f374144583263644465610001").to_vec()).unwrap();
+    let messages = vec![
+        (hex!("050001").to_vec(), valid_value.clone()),
+        (hex!("050002").to_vec(), invalid_value),
+        (hex!("050003").to_vec(), valid_value),
+    ];
+
+    let leaves: Vec<H256> = messages
+        .iter()
+        .map(|(key, value)| {
+            crate::chain::tezos::leaf_hash::<Test, TezosInstance>(
+                owner.clone(),
+                StateKey::try_from(key.clone()).unwrap(),
+                value.clone(),
+            )
+        })
+        .collect();
+    let hash = |left: H256, right: H256| {
+        <Keccak256 as sp_runtime::traits::Hash>::hash(&[left.as_ref(), right.as_ref()].concat())
+    };
+    let node = hash(leaves[0], leaves[1]);
+    let root = hash(node, leaves[2]);
+
+    let items: Vec<StateProof<H256>> = vec![
+        bounded_vec![
+            StateProofNode::Right(leaves[1]),
+            StateProofNode::Right(leaves[2])
+        ],
+        bounded_vec![
+            StateProofNode::Left(leaves[0]),
+            StateProofNode::Right(leaves[2])
+        ],
+        bounded_vec![StateProofNode::Left(node)],
+    ];
+
+    let proofs = messages
+        .into_iter()
+        .zip(items)
+        .map(
+            |((key, value), items)| TezosProof::<AcurastAccountId, AccountId32> {
+                items,
+                path: StateKey::try_from(key).unwrap(),
+                value,
+                marker: PhantomData::default(),
+            },
+        )
+        .collect();
+
+    (root, proofs)
+}
+
+/// Registers two transmitters that agree on `root` for snapshot `1`.
+fn accept_root(owner: StateOwner, root: H256) {
+    assert_ok!(TezosHyperdrive::update_target_chain_owner(
+        RuntimeOrigin::root().into(),
+        owner,
+        0
+    ));
+    assert_ok!(TezosHyperdrive::update_state_transmitters(
+        RuntimeOrigin::root().into(),
+        StateTransmitterUpdates::<Test>::try_from(vec![
+            StateTransmitterUpdate::Add(
+                alice_account_id(),
+                ActivityWindow::Blocks {
+                    start_block: 10,
+                    end_block: 20,
+                },
+            ),
+            StateTransmitterUpdate::Add(
+                bob_account_id(),
+                ActivityWindow::Blocks {
+                    start_block: 10,
+                    end_block: 50,
+                },
+            ),
+        ])
+        .unwrap()
+    ));
+
+    System::set_block_number(10);
+
+    assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+        RuntimeOrigin::signed(alice_account_id()),
+        1,
+        root
+    ));
+    assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+        RuntimeOrigin::signed(bob_account_id()),
+        1,
+        root
+    ));
+    assert_eq!(TezosHyperdrive::validate_state_merkle_root(1, root), true);
+}
+
+#[test]
+fn test_submit_messages() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        let owner = StateOwner::try_from(
+            hex!("050a000000160199651cbe1a155a5c8e5af7d6ea5c3f48eebb8c9c00").to_vec(),
+        )
+        .unwrap();
+        let (root, proofs) = batch_proofs(owner.clone());
+        accept_root(owner, root);
+        let _ = events();
+
+        assert_ok!(TezosHyperdrive::submit_messages(
+            RuntimeOrigin::signed(alice_account_id()),
+            1,
+            proofs.try_into().unwrap()
+        ));
+
+        // the failing action of the second message does not prevent processing the others
+        assert_eq!(TezosHyperdrive::message_seq_id(), 3);
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::TezosHyperdrive(crate::Event::MessageProcessed(
+                    ProcessMessageResult::ActionSuccess
+                )),
+                RuntimeEvent::TezosHyperdrive(crate::Event::MessageProcessed(
+                    ProcessMessageResult::ParsingValueFailed
+                )),
+                RuntimeEvent::TezosHyperdrive(crate::Event::MessageProcessed(
+                    ProcessMessageResult::ActionSuccess
+                )),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_submit_messages_invalid_proof() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        let owner = StateOwner::try_from(
+            hex!("050a000000160199651cbe1a155a5c8e5af7d6ea5c3f48eebb8c9c00").to_vec(),
+        )
+        .unwrap();
+        let (root, mut proofs) = batch_proofs(owner.clone());
+        accept_root(owner, root);
+        let _ = events();
+
+        proofs[1].items = bounded_vec![
+            StateProofNode::Left(H256::zero()),
+            StateProofNode::Right(H256::zero())
+        ];
+
+        assert_err!(
+            TezosHyperdrive::submit_messages(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                proofs.try_into().unwrap()
+            ),
+            Error::<Test, TezosInstance>::ProofDoesNotMatch
+        );
+
+        // no message of the batch got processed
+        assert_eq!(TezosHyperdrive::message_seq_id(), 0);
+        assert!(events().is_empty());
+    });
+}
+
+/// Accepts roots for the same batch of messages proven under an old owner at snapshot `1` and under a new owner at snapshot `6`,
+/// rotating from the old to the new owner at block `10` with an overlap of `5` blocks.
+fn rotate_owner_with_overlap() -> (
+    StateOwner,
+    Vec<TezosProof<AcurastAccountId, AccountId32>>,
+    Vec<TezosProof<AcurastAccountId, AccountId32>>,
+) {
+    let old_owner = StateOwner::try_from(
+        hex!("050a000000160199651cbe1a155a5c8e5af7d6ea5c3f48eebb8c9c00").to_vec(),
+    )
+    .unwrap();
+    let new_owner = StateOwner::try_from(
+        hex!("050a00000016011ba8a95352a4d7f3c753ca700e10ab46cbf963f400").to_vec(),
+    )
+    .unwrap();
+    let (old_root, old_proofs) = batch_proofs(old_owner.clone());
+    let (new_root, new_proofs) = batch_proofs(new_owner.clone());
+    accept_root(old_owner.clone(), old_root);
+
+    assert_ok!(TezosHyperdrive::update_target_chain_owner(
+        RuntimeOrigin::root().into(),
+        new_owner.clone(),
+        5
+    ));
+    assert_eq!(TezosHyperdrive::current_target_chain_owner(), new_owner);
+    assert_eq!(
+        TezosHyperdrive::previous_target_chain_owner(),
+        Some((old_owner.clone(), 15))
+    );
+
+    assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+        RuntimeOrigin::signed(alice_account_id()),
+        6,
+        new_root
+    ));
+    assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+        RuntimeOrigin::signed(bob_account_id()),
+        6,
+        new_root
+    ));
+    assert_eq!(
+        TezosHyperdrive::validate_state_merkle_root(6, new_root),
+        true
+    );
+
+    (old_owner, old_proofs, new_proofs)
+}
+
+#[test]
+fn test_submit_message_during_owner_rotation_overlap() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        let (old_owner, old_proofs, new_proofs) = rotate_owner_with_overlap();
+        let _ = events();
+
+        // the message produced under the old owner is still accepted within the overlap
+        System::set_block_number(15);
+        assert_ok!(TezosHyperdrive::submit_message(
+            RuntimeOrigin::signed(alice_account_id()),
+            1,
+            old_proofs[0].clone()
+        ));
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::TezosHyperdrive(crate::Event::MessageAcceptedUnderPreviousOwner {
+                    message_id: 1,
+                    owner: old_owner,
+                }),
+                RuntimeEvent::TezosHyperdrive(crate::Event::MessageProcessed(
+                    ProcessMessageResult::ActionSuccess
+                )),
+            ]
+        );
+
+        // the next message is accepted under the new owner
+        assert_ok!(TezosHyperdrive::submit_message(
+            RuntimeOrigin::signed(alice_account_id()),
+            6,
+            new_proofs[1].clone()
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::TezosHyperdrive(
+                crate::Event::MessageProcessed(ProcessMessageResult::ParsingValueFailed)
+            )]
+        );
+        assert_eq!(TezosHyperdrive::message_seq_id(), 2);
+    });
+}
+
+#[test]
+fn test_submit_message_previous_owner_rejected_after_overlap() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        let (_, old_proofs, new_proofs) = rotate_owner_with_overlap();
+        let _ = events();
+
+        System::set_block_number(16);
+        assert_err!(
+            TezosHyperdrive::submit_message(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                old_proofs[0].clone()
+            ),
+            Error::<Test, TezosInstance>::ProofDoesNotMatch
+        );
+        assert_eq!(TezosHyperdrive::message_seq_id(), 0);
+        assert!(events().is_empty());
+
+        assert_ok!(TezosHyperdrive::submit_message(
+            RuntimeOrigin::signed(alice_account_id()),
+            6,
+            new_proofs[0].clone()
+        ));
+        assert_eq!(TezosHyperdrive::message_seq_id(), 1);
+    });
+}
+
+#[test]
+fn submit_outside_snapshot_activity_window() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        let actions = vec![
+            StateTransmitterUpdate::Add(
+                alice_account_id(),
+                ActivityWindow::Blocks {
+                    start_block: 10,
+                    end_block: 20,
+                },
+            ),
+            StateTransmitterUpdate::Add(
+                bob_account_id(),
+                ActivityWindow::Snapshots {
+                    start_snapshot: 1,
+                    end_snapshot: 6,
+                },
+            ),
+        ];
+
+        assert_ok!(TezosHyperdrive::update_state_transmitters(
+            RuntimeOrigin::root().into(),
+            StateTransmitterUpdates::<Test>::try_from(actions).unwrap()
+        ));
+
+        // a snapshot based window accepts the expected snapshot regardless of the current block
+        System::set_block_number(30);
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(bob_account_id()),
+            1,
+            HASH
+        ));
+        // a block based window rejects outside its blocks regardless of the snapshot
+        assert_err!(
+            TezosHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                HASH
+            ),
+            Error::<Test, TezosInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+
+        System::set_block_number(10);
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(alice_account_id()),
+            1,
+            HASH
+        ));
+        assert_eq!(TezosHyperdrive::latest_snapshot(), 6);
+
+        // the next expected snapshot is outside bob's window, even though the block is within alice's window
+        assert_err!(
+            TezosHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(bob_account_id()),
+                6,
+                HASH
+            ),
+            Error::<Test, TezosInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(alice_account_id()),
+            6,
+            HASH
+        ));
+    });
+}
+
+#[test]
+fn staked_transmitter_lifecycle() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        assert_ok!(TezosHyperdrive::update_state_transmitters(
+            RuntimeOrigin::root().into(),
+            StateTransmitterUpdates::<Test>::try_from(vec![StateTransmitterUpdate::Add(
+                bob_account_id(),
+                ActivityWindow::Blocks {
+                    start_block: 0,
+                    end_block: 100,
+                },
+            )])
+            .unwrap()
+        ));
+        let _ = events();
+
+        assert_ok!(TezosHyperdrive::register_transmitter(
+            RuntimeOrigin::signed(alice_account_id()),
+            1_000
+        ));
+        assert_eq!(Balances::reserved_balance(&alice_account_id()), 1_000);
+        assert_eq!(TezosHyperdrive::staked_transmitter_count(), 1);
+        assert!(events().contains(&RuntimeEvent::TezosHyperdrive(
+            crate::Event::TransmitterRegistered {
+                account: alice_account_id(),
+                stake: 1_000,
+                activity_window: ActivityWindow::Blocks {
+                    start_block: 11,
+                    end_block: u64::MAX,
+                },
+            }
+        )));
+
+        // still warming up
+        assert_err!(
+            TezosHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                HASH
+            ),
+            Error::<Test, TezosInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+
+        // a staked and a root-managed transmitter reach the quorum together
+        System::set_block_number(11);
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(alice_account_id()),
+            1,
+            HASH
+        ));
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(bob_account_id()),
+            1,
+            HASH
+        ));
+        assert!(TezosHyperdrive::validate_state_merkle_root(1, HASH));
+
+        System::set_block_number(12);
+        assert_ok!(TezosHyperdrive::deregister_transmitter(
+            RuntimeOrigin::signed(alice_account_id())
+        ));
+        assert_err!(
+            TezosHyperdrive::deregister_transmitter(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test, TezosInstance>::TransmitterAlreadyDeregistered
+        );
+        assert_err!(
+            TezosHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(alice_account_id()),
+                6,
+                HASH
+            ),
+            Error::<Test, TezosInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+
+        // stake is locked during cool-down
+        System::set_block_number(31);
+        assert_err!(
+            TezosHyperdrive::release_transmitter_stake(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test, TezosInstance>::TransmitterStakeLocked
+        );
+
+        System::set_block_number(32);
+        assert_ok!(TezosHyperdrive::release_transmitter_stake(
+            RuntimeOrigin::signed(alice_account_id())
+        ));
+        assert_eq!(Balances::reserved_balance(&alice_account_id()), 0);
+        assert_eq!(Balances::free_balance(&alice_account_id()), 10_000);
+        assert_eq!(
+            TezosHyperdrive::staked_transmitter(alice_account_id()),
+            None
+        );
+        assert_eq!(TezosHyperdrive::staked_transmitter_count(), 0);
+    });
+}
+
+#[test]
+fn slashed_transmitter_loses_submission_rights() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        assert_ok!(TezosHyperdrive::register_transmitter(
+            RuntimeOrigin::signed(alice_account_id()),
+            1_500
+        ));
+        System::set_block_number(11);
+
+        assert_err!(
+            TezosHyperdrive::slash_transmitter(
+                RuntimeOrigin::signed(bob_account_id()),
+                alice_account_id(),
+                600
+            ),
+            BadOrigin
+        );
+        assert_ok!(TezosHyperdrive::slash_transmitter(
+            RuntimeOrigin::root(),
+            alice_account_id(),
+            600
+        ));
+        assert_eq!(
+            events().last(),
+            Some(&RuntimeEvent::TezosHyperdrive(
+                crate::Event::TransmitterSlashed {
+                    account: alice_account_id(),
+                    amount: 600,
+                }
+            ))
+        );
+        assert_eq!(Balances::reserved_balance(&alice_account_id()), 900);
+        assert_eq!(Balances::free_balance(&alice_account_id()), 8_500);
+        assert_eq!(
+            TezosHyperdrive::staked_transmitter(alice_account_id()).map(|t| t.stake),
+            Some(900)
+        );
+
+        // the remaining stake is below the minimum
+        assert_err!(
+            TezosHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                HASH
+            ),
+            Error::<Test, TezosInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+    });
+}
+
+#[test]
+fn register_transmitter_fails() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        assert_err!(
+            TezosHyperdrive::register_transmitter(RuntimeOrigin::signed(alice_account_id()), 999),
+            Error::<Test, TezosInstance>::InsufficientTransmitterStake
+        );
+        assert_err!(
+            AlephZeroHyperdrive::register_transmitter(
+                RuntimeOrigin::signed(alice_account_id()),
+                1_000
+            ),
+            Error::<Test, AlephZeroInstance>::PermissionlessTransmittersDisabled
+        );
+        assert_err!(
+            TezosHyperdrive::release_transmitter_stake(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test, TezosInstance>::TransmitterNotRegistered
+        );
+
+        assert_ok!(TezosHyperdrive::register_transmitter(
+            RuntimeOrigin::signed(alice_account_id()),
+            1_000
+        ));
+        assert_err!(
+            TezosHyperdrive::register_transmitter(RuntimeOrigin::signed(alice_account_id()), 1_000),
+            Error::<Test, TezosInstance>::TransmitterAlreadyRegistered
+        );
+        assert_err!(
+            TezosHyperdrive::release_transmitter_stake(RuntimeOrigin::signed(alice_account_id())),
+            Error::<Test, TezosInstance>::TransmitterStakeLocked
+        );
+
+        assert_ok!(TezosHyperdrive::register_transmitter(
+            RuntimeOrigin::signed(bob_account_id()),
+            1_000
+        ));
+        assert_err!(
+            TezosHyperdrive::register_transmitter(
+                RuntimeOrigin::signed(charlie_account_id()),
+                1_000
+            ),
+            Error::<Test, TezosInstance>::TooManyStakedTransmitters
+        );
+        // instances are independent
+        assert_ok!(EthereumHyperdrive::register_transmitter(
+            RuntimeOrigin::signed(charlie_account_id()),
+            1_000
+        ));
+    });
+}
+
+fn genesis_with_transmitters() -> crate::GenesisConfig<Test, TezosInstance> {
+    crate::GenesisConfig {
+        transmitters: vec![
+            (
+                alice_account_id(),
+                ActivityWindow::Blocks {
+                    start_block: 0,
+                    end_block: 100,
+                },
+            ),
+            (
+                bob_account_id(),
+                ActivityWindow::Snapshots {
+                    start_snapshot: 0,
+                    end_snapshot: 1_000,
+                },
+            ),
+        ],
+        current_snapshot: Some(500),
+        message_sequence_id: 42,
+        target_chain_owner: Some(
+            StateOwner::try_from(hex!("0000000000000000000000000000000000000001").to_vec())
+                .unwrap(),
+        ),
+    }
+}
+
+#[test]
+fn genesis_transmitters_submit_in_first_block() {
+    let mut test = new_test_ext_with_tezos_genesis(genesis_with_transmitters());
+
+    test.execute_with(|| {
+        assert_eq!(1, System::block_number());
+        assert_eq!(500, TezosHyperdrive::latest_snapshot());
+        assert_eq!(42, TezosHyperdrive::message_seq_id());
+        assert_eq!(
+            StateOwner::try_from(hex!("0000000000000000000000000000000000000001").to_vec())
+                .unwrap(),
+            TezosHyperdrive::current_target_chain_owner()
+        );
+
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(alice_account_id()),
+            500,
+            HASH
+        ));
+        assert_ok!(TezosHyperdrive::submit_state_merkle_root(
+            RuntimeOrigin::signed(bob_account_id()),
+            500,
+            HASH
+        ));
+        assert!(TezosHyperdrive::validate_state_merkle_root(500, HASH));
+        assert_eq!(
+            500 + TransmissionRate::get(),
+            TezosHyperdrive::latest_snapshot()
+        );
+
+        // other instances are not affected by the genesis of the tezos instance
+        assert_err!(
+            EthereumHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                HASH
+            ),
+            Error::<Test, EthereumInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+    });
+}
+
+#[test]
+fn default_genesis_keeps_defaults() {
+    let mut test = new_test_ext();
+
+    test.execute_with(|| {
+        assert_eq!(1, TezosHyperdrive::latest_snapshot());
+        assert_eq!(0, TezosHyperdrive::message_seq_id());
+        assert_eq!(
+            TargetChainStateOwner::get(),
+            TezosHyperdrive::current_target_chain_owner()
+        );
+        assert_err!(
+            TezosHyperdrive::submit_state_merkle_root(
+                RuntimeOrigin::signed(alice_account_id()),
+                1,
+                HASH
+            ),
+            Error::<Test, TezosInstance>::SubmitOutsideTransmitterActivityWindow
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "Genesis transmitter activity window must start before it ends")]
+fn genesis_rejects_empty_activity_window() {
+    let mut genesis = genesis_with_transmitters();
+    genesis.transmitters[0].1 = ActivityWindow::Blocks {
+        start_block: 100,
+        end_block: 100,
+    };
+    new_test_ext_with_tezos_genesis(genesis);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate genesis transmitter")]
+fn genesis_rejects_duplicate_transmitter() {
+    let mut genesis = genesis_with_transmitters();
+    genesis.transmitters[1].0 = alice_account_id();
+    new_test_ext_with_tezos_genesis(genesis);
+}
+
+/// Fails if the calls, events, errors or constants of any hyperdrive instance changed without updating the committed snapshot.
+#[test]
+fn metadata_artifact_matches_snapshot() {
+    let artifact =
+        acurast_metadata_gen::generate(&Test::metadata(), |name| name.ends_with("Hyperdrive"))
+            .unwrap();
+    assert_eq!(
+        artifact
+            .pallets
+            .iter()
+            .map(|pallet| pallet.name.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "AlephZeroHyperdrive",
+            "EthereumHyperdrive",
+            "TezosHyperdrive"
+        ]
+    );
+    acurast_metadata_gen::assert_snapshot(
+        &artifact,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/metadata/hyperdrive.json"),
+    );
+}
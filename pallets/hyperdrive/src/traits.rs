@@ -1,4 +1,4 @@
-use crate::{MessageIdentifier, ParsedAction};
+use crate::{MessageIdentifier, ParsedAction, StateOwner};
 use frame_support::weights::Weight;
 use sp_std::fmt::Debug;
 
@@ -8,7 +8,8 @@ where
 {
     type Error: Debug;
 
-    fn calculate_root(self: &Self) -> Result<[u8; 32], Self::Error>;
+    /// Derives the state root from the proof, binding the proven state to the given target chain `owner` where the chain supports it.
+    fn calculate_root(self: &Self, owner: &StateOwner) -> Result<[u8; 32], Self::Error>;
     fn message_id(self: &Self) -> Result<MessageIdentifier, Self::Error>;
     fn message(self: &Self) -> Result<ParsedAction<T>, Self::Error>;
 }
@@ -18,6 +19,11 @@ pub trait WeightInfo {
     fn update_state_transmitters(l: u32) -> Weight;
     fn submit_state_merkle_root() -> Weight;
     fn submit_message() -> Weight;
+    fn submit_messages(n: u32, s: u32) -> Weight;
     fn update_target_chain_owner() -> Weight;
     fn update_current_snapshot() -> Weight;
+    fn register_transmitter() -> Weight;
+    fn deregister_transmitter() -> Weight;
+    fn release_transmitter_stake() -> Weight;
+    fn slash_transmitter() -> Weight;
 }
@@ -2,6 +2,7 @@ use codec::{Decode, Encode};
 use frame_support::{pallet_prelude::*, storage::bounded_vec::BoundedVec};
 use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_core::ConstU32;
 use sp_runtime::traits::Hash;
 use sp_runtime::RuntimeDebug;
@@ -18,23 +19,78 @@ pub type StateTransmitterUpdates<T> =
 pub type StateTransmitterUpdateFor<T> =
     StateTransmitterUpdate<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
 
-/// Defines the transmitter activity window.
-#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
-pub struct ActivityWindow<BlockNumber> {
-    /// From this block on, the transmitter is permitted to submit Merkle roots.
-    pub start_block: BlockNumber,
-    /// From this block on, the transmitter is not permitted to submit any Merkle root.
-    pub end_block: BlockNumber,
+/// Defines the transmitter activity window, either in Acurast blocks or in target chain snapshots.
+///
+/// Both ranges are inclusive start, exclusive end.
+#[derive(
+    RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivityWindow<BlockNumber> {
+    Blocks {
+        /// From this block on, the transmitter is permitted to submit Merkle roots.
+        start_block: BlockNumber,
+        /// From this block on, the transmitter is not permitted to submit any Merkle root.
+        end_block: BlockNumber,
+    },
+    Snapshots {
+        /// From this snapshot on, the transmitter is permitted to submit Merkle roots.
+        start_snapshot: u64,
+        /// From this snapshot on, the transmitter is not permitted to submit any Merkle root.
+        end_snapshot: u64,
+    },
 }
+
 impl<BlockNumber: From<u8>> Default for ActivityWindow<BlockNumber> {
     fn default() -> Self {
-        Self {
+        Self::Blocks {
             start_block: BlockNumber::from(0),
             end_block: BlockNumber::from(0),
         }
     }
 }
 
+impl<BlockNumber: PartialOrd> ActivityWindow<BlockNumber> {
+    /// Returns true if a submission for the target chain `snapshot` at Acurast `block` falls into this window.
+    pub fn contains(&self, block: &BlockNumber, snapshot: u64) -> bool {
+        match self {
+            Self::Blocks {
+                start_block,
+                end_block,
+            } => start_block <= block && block < end_block,
+            Self::Snapshots {
+                start_snapshot,
+                end_snapshot,
+            } => *start_snapshot <= snapshot && snapshot < *end_snapshot,
+        }
+    }
+
+    /// Returns true if the window is not empty, i.e. it starts before it ends.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Blocks {
+                start_block,
+                end_block,
+            } => start_block < end_block,
+            Self::Snapshots {
+                start_snapshot,
+                end_snapshot,
+            } => start_snapshot < end_snapshot,
+        }
+    }
+}
+
+/// A transmitter registered permissionlessly by reserving a stake.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+pub struct StakedTransmitter<Balance, BlockNumber> {
+    /// The reserved stake, reduced by slashing.
+    pub stake: Balance,
+    /// The window in which the transmitter is permitted to submit Merkle roots, starting after the warm-up period.
+    pub activity_window: ActivityWindow<BlockNumber>,
+    /// The block from which on the stake can be released, set when the transmitter deregisters.
+    pub release_block: Option<BlockNumber>,
+}
+
 #[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq)]
 pub enum StateTransmitterUpdate<AccountId, BlockNumber> {
     Add(AccountId, ActivityWindow<BlockNumber>),
@@ -66,6 +66,8 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: AcurastHyperdriveTezos CurrentTargetChainOwner (max_values: Some(1), max_size: Some(66), added: 561, mode: MaxEncodedLen)
 	/// Storage: AcurastHyperdriveTezos StateMerkleRootCount (r:1 w:0)
 	/// Proof: AcurastHyperdriveTezos StateMerkleRootCount (max_values: None, max_size: Some(2098), added: 4573, mode: MaxEncodedLen)
+	/// Storage: AcurastHyperdriveTezos PreviousTargetChainOwner (r:1 w:0)
+	/// Proof: AcurastHyperdriveTezos PreviousTargetChainOwner (max_values: Some(1), max_size: Some(70), added: 565, mode: MaxEncodedLen)
 	fn submit_message() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `295`
@@ -73,20 +75,79 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 20_000_000 picoseconds.
 		Weight::from_parts(21_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 7114))
-			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads(3))
+	}
+	/// Storage: AcurastHyperdriveTezos CurrentTargetChainOwner (r:1 w:0)
+	/// Proof: AcurastHyperdriveTezos CurrentTargetChainOwner (max_values: Some(1), max_size: Some(66), added: 561, mode: MaxEncodedLen)
+	/// Storage: AcurastHyperdriveTezos StateMerkleRootCount (r:1 w:0)
+	/// Proof: AcurastHyperdriveTezos StateMerkleRootCount (max_values: None, max_size: Some(2098), added: 4573, mode: MaxEncodedLen)
+	/// Storage: AcurastHyperdriveTezos PreviousTargetChainOwner (r:1 w:0)
+	/// Proof: AcurastHyperdriveTezos PreviousTargetChainOwner (max_values: Some(1), max_size: Some(70), added: 565, mode: MaxEncodedLen)
+	/// Storage: AcurastHyperdriveTezos MessageSequenceId (r:1 w:1)
+	/// Proof: AcurastHyperdriveTezos MessageSequenceId (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, MaxMessagesPerSubmission]`.
+	/// The range of component `s` is the total encoded size of all proofs in bytes.
+	fn submit_messages(n: u32, s: u32, ) -> Weight {
+		// Not benchmarked yet, estimated from `submit_message` per submitted message.
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 7114))
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(n.into()))
 	}
-	/// Storage: AcurastHyperdriveTezos CurrentTargetChainOwner (r:0 w:1)
+	/// Storage: AcurastHyperdriveTezos CurrentTargetChainOwner (r:1 w:1)
 	/// Proof: AcurastHyperdriveTezos CurrentTargetChainOwner (max_values: Some(1), max_size: Some(66), added: 561, mode: MaxEncodedLen)
+	/// Storage: AcurastHyperdriveTezos PreviousTargetChainOwner (r:0 w:1)
+	/// Proof: AcurastHyperdriveTezos PreviousTargetChainOwner (max_values: Some(1), max_size: Some(70), added: 565, mode: MaxEncodedLen)
 	fn update_target_chain_owner() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 0))
-			.saturating_add(T::DbWeight::get().writes(1))
+		//  Estimated: `1551`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1551))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	fn update_current_snapshot() -> Weight {
 		Weight::from_parts(9_000_000, 0)
 	}
+	/// Storage: AcurastHyperdriveTezos StakedTransmitters (r:1 w:1)
+	/// Storage: AcurastHyperdriveTezos StakedTransmitterCount (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn register_transmitter() -> Weight {
+		// Not benchmarked yet, estimated from comparable staking extrinsics.
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: AcurastHyperdriveTezos StakedTransmitters (r:1 w:1)
+	fn deregister_transmitter() -> Weight {
+		// Not benchmarked yet, estimated from comparable staking extrinsics.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastHyperdriveTezos StakedTransmitters (r:1 w:1)
+	/// Storage: AcurastHyperdriveTezos StakedTransmitterCount (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn release_transmitter_stake() -> Weight {
+		// Not benchmarked yet, estimated from comparable staking extrinsics.
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: AcurastHyperdriveTezos StakedTransmitters (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn slash_transmitter() -> Weight {
+		// Not benchmarked yet, estimated from comparable staking extrinsics.
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3593))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
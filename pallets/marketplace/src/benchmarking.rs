@@ -1,7 +1,7 @@
 use frame_benchmarking::{benchmarks, whitelist_account};
 use frame_support::sp_runtime::{
     traits::{IdentifyAccount, Verify},
-    DispatchError,
+    DispatchError, Permill,
 };
 use frame_support::{assert_ok, traits::IsType};
 use frame_system::RawOrigin;
@@ -60,7 +60,17 @@ pub fn job_registration_with_reward<T: Config>(
         slots,
         reward,
         min_reputation: Some(0),
+        min_recent_fulfillment_ratio: None,
+        holdback_release_sla: None,
+        max_attestation_age_ms: None,
+        max_fee_per_execution: None,
+        depends_on: None,
+        preferred_sources: None,
+        preference_window_ms: 0,
+        omitted: None,
         instant_match: instant_match_processor.map(|m| vec![m].try_into().unwrap()),
+        require_env_ack: false,
+        auto_renew: None,
     };
     let r: <T as Config>::RegistrationExtra = <T as Config>::BenchmarkHelper::registration_extra(r);
     let r: <T as pallet_acurast::Config>::RegistrationExtra = r.into();
@@ -145,6 +155,140 @@ where
     (caller, job, job_id)
 }
 
+/// Registers `x` jobs with the maximum number of slots and returns matches assigning a distinct advertised source to
+/// each slot.
+///
+/// Each `i`-th job for which `rejected(i)` holds only allows its consumer as source, so its match gets rejected by the
+/// source whitelist.
+fn matches_helper<T: Config>(x: u32, rejected: impl Fn(u32) -> bool) -> Vec<MatchFor<T>>
+where
+    T: pallet_balances::Config,
+{
+    let mut registered_jobs: Vec<(T::AccountId, JobRegistrationFor<T>, JobIdSequence)> = vec![];
+    let max_slots = <T as pallet_acurast::Config>::MaxSlots::get();
+    for i in 0..x {
+        let (caller, mut job) = register_helper::<T>(i, max_slots as u8);
+        if rejected(i) {
+            job.allowed_sources = Some(vec![caller.clone()].try_into().unwrap());
+        }
+        assert_ok!(Acurast::<T>::register(
+            RawOrigin::Signed(caller.clone()).into(),
+            job.clone()
+        ));
+        (&mut registered_jobs).push((caller, job, Acurast::<T>::job_id_sequence()));
+    }
+
+    let mut current_account_index: u32 = 1;
+
+    registered_jobs
+        .into_iter()
+        .map(|(account_id, _, job_id)| {
+            let mut processor_ids: Vec<T::AccountId> = vec![];
+            for i in 0..max_slots {
+                let account_index: u32 = current_account_index;
+                current_account_index = current_account_index + 1;
+                let (account_id, _) = advertise_helper::<T>(account_index, true);
+                (&mut processor_ids).push(account_id);
+            }
+            Match {
+                job_id: (MultiOrigin::Acurast(account_id), job_id),
+                sources: processor_ids
+                    .into_iter()
+                    .map(|account_id| PlannedExecution {
+                        source: account_id,
+                        start_delay: 0,
+                        pricing_version: None,
+                    })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Registers a job with `x` slots and assigns it to `x` advertised sources, which all acknowledged their match.
+fn assigned_job_helper<T: Config>(
+    x: u32,
+) -> Result<
+    (
+        T::AccountId,
+        JobRegistrationFor<T>,
+        JobId<T::AccountId>,
+        Vec<T::AccountId>,
+    ),
+    DispatchError,
+>
+where
+    T: pallet_balances::Config,
+{
+    let (consumer, job, job_id) = register_submit_helper::<T>(0, x as u8);
+    let job_id: JobId<T::AccountId> = (MultiOrigin::Acurast(consumer.clone()), job_id);
+    let mut sources: Vec<T::AccountId> = vec![];
+    for i in 0..x {
+        let (source, _) = advertise_helper::<T>(i + 1, true);
+        sources.push(source);
+    }
+    let matcher: T::AccountId =
+        <T as Config>::BenchmarkHelper::funded_account(x + 1, u32::MAX.into());
+    let m = Match {
+        job_id: job_id.clone(),
+        sources: sources
+            .iter()
+            .map(|source| PlannedExecution {
+                source: source.clone(),
+                start_delay: 0,
+                pricing_version: None,
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    };
+    assert_ok!(AcurastMarketplace::<T>::propose_matching(
+        RawOrigin::Signed(matcher).into(),
+        vec![m].try_into().unwrap(),
+    ));
+    for source in sources.iter() {
+        let pub_keys: PubKeys = vec![
+            PubKey::SECP256r1([0u8; 33].to_vec().try_into().unwrap()),
+            PubKey::SECP256k1([0u8; 33].to_vec().try_into().unwrap()),
+        ]
+        .try_into()
+        .unwrap();
+        assert_ok!(AcurastMarketplace::<T>::acknowledge_match(
+            RawOrigin::Signed(source.clone()).into(),
+            job_id.clone(),
+            pub_keys,
+        ));
+    }
+    Ok((consumer, job, job_id, sources))
+}
+
+/// Registers a job pending approval, using the consumer as its own approver.
+fn pending_approval_helper<T: Config>(
+) -> Result<(T::AccountId, JobRegistrationFor<T>, JobId<T::AccountId>), DispatchError>
+where
+    T: pallet_balances::Config,
+{
+    let (consumer, job) = register_helper::<T>(0, 1);
+    assert_ok!(AcurastMarketplace::<T>::set_registration_approval(
+        RawOrigin::Signed(consumer.clone()).into(),
+        0u8.into(),
+        consumer.clone(),
+    ));
+    assert_ok!(Acurast::<T>::register(
+        RawOrigin::Signed(consumer.clone()).into(),
+        job.clone()
+    ));
+    let job_id: JobId<T::AccountId> = (
+        MultiOrigin::Acurast(consumer.clone()),
+        Acurast::<T>::job_id_sequence(),
+    );
+    let status = AcurastMarketplace::<T>::stored_job_status(&job_id.0, job_id.1);
+    assert!(status == Some(JobStatus::PendingApproval));
+    Ok((consumer, job, job_id))
+}
+
 fn acknowledge_match_helper<T: Config>(
     consumer: Option<T::AccountId>,
     processor: Option<T::AccountId>,
@@ -171,6 +315,7 @@ where
         Some(PlannedExecution {
             source: processor.clone(),
             start_delay: 0,
+            pricing_version: None,
         }),
     );
     assert_ok!(Acurast::<T>::register(
@@ -209,6 +354,84 @@ where
     Ok((processor_id, job, job_id))
 }
 
+/// Registers `x` single slot jobs of `consumer`, instantly matched with the advertised `processor` and acknowledged by it.
+///
+/// The schedules are staggered by their execution duration, starting `start_offset` milliseconds after the default
+/// schedule, so that none of their executions overlap.
+fn staggered_assigned_jobs_helper<T: Config>(
+    consumer: &T::AccountId,
+    processor: &T::AccountId,
+    x: u32,
+    start_offset: u64,
+) -> Result<Vec<JobId<T::AccountId>>, DispatchError>
+where
+    T: pallet_balances::Config,
+{
+    let mut job_ids: Vec<JobId<T::AccountId>> = vec![];
+    for i in 0..x {
+        let mut job = job_registration_with_reward::<T>(
+            script(),
+            1,
+            100,
+            1_000_000,
+            Some(PlannedExecution {
+                source: processor.clone(),
+                start_delay: 0,
+                pricing_version: None,
+            }),
+        );
+        let offset = start_offset + i as u64 * job.schedule.duration;
+        job.schedule.start_time += offset;
+        job.schedule.end_time += offset;
+        assert_ok!(Acurast::<T>::register(
+            RawOrigin::Signed(consumer.clone()).into(),
+            job
+        ));
+        let job_id: JobId<T::AccountId> = (
+            MultiOrigin::Acurast(consumer.clone()),
+            Acurast::<T>::job_id_sequence(),
+        );
+        let pub_keys: PubKeys = vec![
+            PubKey::SECP256r1([0u8; 33].to_vec().try_into().unwrap()),
+            PubKey::SECP256k1([0u8; 33].to_vec().try_into().unwrap()),
+        ]
+        .try_into()
+        .unwrap();
+        assert_ok!(AcurastMarketplace::<T>::acknowledge_match(
+            RawOrigin::Signed(processor.clone()).into(),
+            job_id.clone(),
+            pub_keys,
+        ));
+        job_ids.push(job_id);
+    }
+    Ok(job_ids)
+}
+
+fn delivery_commitment_helper<T: Config>(
+) -> Result<(T::AccountId, T::AccountId, JobId<T::AccountId>), DispatchError>
+where
+    T: pallet_balances::Config
+        + pallet_timestamp::Config<Moment = u64>
+        + pallet_acurast_processor_manager::Config,
+{
+    let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+    let (processor, job, job_id) =
+        acknowledge_match_submit_helper::<T>(Some(consumer.clone()), None)?;
+    let manager: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(2, u32::MAX.into());
+    let (manager_id, _) =
+        pallet_acurast_processor_manager::Pallet::<T>::do_get_or_create_manager_id(&manager)?;
+    pallet_acurast_processor_manager::Pallet::<T>::do_add_processor_manager_pairing(
+        &processor, manager_id,
+    )?;
+    pallet_timestamp::Pallet::<T>::set_timestamp(job.schedule.start_time.into());
+    assert_ok!(AcurastMarketplace::<T>::report(
+        RawOrigin::Signed(processor.clone()).into(),
+        job_id.clone(),
+        ExecutionResult::SuccessWithDeliveryCommitment(vec![0u8].try_into().unwrap(), H256::zero()),
+    ));
+    Ok((consumer, processor, job_id))
+}
+
 benchmarks! {
     where_clause {  where
         T: pallet_acurast::Config + pallet_balances::Config + pallet_timestamp::Config<Moment = u64> + pallet_acurast_processor_manager::Config,
@@ -240,39 +463,41 @@ benchmarks! {
         let manager: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(2, u32::MAX.into());
         let (manager_id, _) = pallet_acurast_processor_manager::Pallet::<T>::do_get_or_create_manager_id(&manager)?;
         pallet_acurast_processor_manager::Pallet::<T>::do_add_processor_manager_pairing(&processor, manager_id)?;
+        // requiring the environment acknowledgement additionally reads the environment version
+        pallet_acurast::StoredJobRegistration::<T>::mutate(&job_id.0, &job_id.1, |registration| {
+            let registration = registration.as_mut().unwrap();
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let mut requirements: JobRequirementsFor<T> = e.into();
+            requirements.require_env_ack = true;
+            registration.extra = <T as Config>::BenchmarkHelper::registration_extra(requirements).into();
+        });
+        let environment = pallet_acurast::Environment {
+            public_key: vec![0u8; 33].try_into().unwrap(),
+            variables: vec![].try_into().unwrap(),
+        };
+        assert_ok!(Acurast::<T>::set_environment_for(job_id.clone(), processor.clone(), environment));
+        assert_ok!(AcurastMarketplace::<T>::acknowledge_environment(RawOrigin::Signed(processor.clone()).into(), job_id.clone()));
         pallet_timestamp::Pallet::<T>::set_timestamp(job.schedule.start_time.into());
-    }: _(RawOrigin::Signed(processor), job_id, ExecutionResult::Success(vec![0u8].try_into().unwrap()))
+    }: _(RawOrigin::Signed(processor), job_id, ExecutionResult::SuccessWithDeliveryCommitment(vec![0u8].try_into().unwrap(), H256::zero()))
 
     propose_matching {
         let x in 1 .. T::MaxProposedMatches::get();
         let caller: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, 1_000_000_000_000u64.into());
         whitelist_account!(caller);
-        let mut registered_jobs: Vec<(T::AccountId, JobRegistrationFor<T>, JobIdSequence)> = vec![];
-        let max_slots = <T as pallet_acurast::Config>::MaxSlots::get();
-        for i in 0..x {
-            (&mut registered_jobs).push(register_submit_helper::<T>(i, max_slots as u8));
-        }
-
-        let mut current_account_index: u32 = 1;
-
-        let matches: Vec<MatchFor<T>> = registered_jobs.into_iter().map(|(account_id, _, job_id)| {
-            let mut processor_ids: Vec<T::AccountId> = vec![];
-            for i in 0..max_slots {
-                let account_index: u32 = current_account_index;
-                current_account_index = current_account_index + 1;
-                let (account_id, _) = advertise_helper::<T>(account_index, true);
-                (&mut processor_ids).push(account_id);
-            }
-            Match {
-                job_id: (MultiOrigin::Acurast(account_id), job_id),
-                sources: processor_ids.into_iter().map(|account_id| PlannedExecution {
-                    source: account_id,
-                    start_delay: 0
-                }).collect::<Vec<_>>().try_into().unwrap()
-            }
-        }).collect::<Vec<_>>();
+        let matches = matches_helper::<T>(x, |_| false);
     }: _(RawOrigin::Signed(caller), matches.try_into().unwrap())
 
+    propose_matching_rejected {
+        let x in 1 .. T::MaxProposedMatches::get();
+        let caller: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, 1_000_000_000_000u64.into());
+        whitelist_account!(caller);
+        // the second half of the matches is rejected by the job's allowed sources, failing the proposal at the first
+        // of them before any of the more expensive checks run
+        let matches = matches_helper::<T>(x, |i| i >= x / 2);
+    }: {
+        assert!(AcurastMarketplace::<T>::propose_matching(RawOrigin::Signed(caller).into(), matches.try_into().unwrap()).is_err());
+    }
+
     acknowledge_match {
         let (processor, _, job_id) = acknowledge_match_helper::<T>(None, None)?;
         let pub_keys: PubKeys = vec![PubKey::SECP256r1([0u8; 33].to_vec().try_into().unwrap()), PubKey::SECP256k1([0u8; 33].to_vec().try_into().unwrap())].try_into().unwrap();
@@ -299,7 +524,247 @@ benchmarks! {
             (&mut job_ids).push(job_id.1);
         }
         pallet_timestamp::Pallet::<T>::set_timestamp((1689418800000u64 + 1).into());
-    }: _(RawOrigin::Signed(consumer), job_ids.try_into().unwrap())
+    }: _(RawOrigin::Signed(consumer.clone()), job_ids.clone().try_into().unwrap())
+    verify {
+        // none of the jobs got reported, so they all take the more expensive path of failed jobs
+        for job_id in job_ids {
+            let failed: <T as frame_system::Config>::RuntimeEvent = <T as Config>::RuntimeEvent::from(
+                Event::JobFailed((MultiOrigin::Acurast(consumer.clone()), job_id))
+            ).into();
+            assert!(frame_system::Pallet::<T>::events().iter().any(|record| record.event == failed));
+        }
+    }
+
+    finalize_assigned_jobs {
+        let x in 1 .. T::MaxFinalizeJobs::get();
+        let consumer = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let processor = <T as Config>::BenchmarkHelper::funded_account(1, u32::MAX.into());
+        let manager: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(2, u32::MAX.into());
+        let (manager_id, _) = pallet_acurast_processor_manager::Pallet::<T>::do_get_or_create_manager_id(&manager)?;
+        pallet_acurast_processor_manager::Pallet::<T>::do_add_processor_manager_pairing(&processor, manager_id)?;
+        assert_ok!(AcurastMarketplace::<T>::advertise(RawOrigin::Signed(processor.clone()).into(), advertisement::<T>(1, 1_000_000)));
+        // all jobs are assigned to the same processor, their executions must not overlap
+        let job_ids = staggered_assigned_jobs_helper::<T>(&consumer, &processor, x, 0)?;
+        pallet_timestamp::Pallet::<T>::set_timestamp((1689418800000u64 + x as u64 * 100 + 1).into());
+    }: _(RawOrigin::Signed(processor.clone()), job_ids.try_into().unwrap())
+    verify {
+        assert_last_event::<T>(Event::AssignedJobsFinalized(processor, x, 0).into());
+    }
+
+    claim_pending_payout {
+        let (processor, job, job_id) = acknowledge_match_submit_helper::<T>(None, None)?;
+        let manager: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(2, u32::MAX.into());
+        let (manager_id, _) = pallet_acurast_processor_manager::Pallet::<T>::do_get_or_create_manager_id(&manager)?;
+        pallet_acurast_processor_manager::Pallet::<T>::do_add_processor_manager_pairing(&processor, manager_id)?;
+        pallet_timestamp::Pallet::<T>::set_timestamp(job.schedule.start_time.into());
+        let assignment = AcurastMarketplace::<T>::stored_matches(&processor, &job_id).unwrap();
+        T::RewardManager::escrow_reward(&job_id, assignment.fee_per_execution)?;
+        <PendingPayouts<T>>::insert(&processor, &job_id, PendingPayout {
+            amount: assignment.fee_per_execution,
+            escrowed_at: job.schedule.start_time,
+        });
+    }: _(RawOrigin::Signed(processor), job_id)
+
+    set_default_requirements {
+        let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let defaults = ConsumerDefaults {
+            allow_only_verified_sources: true,
+            required_modules: JobModules::default(),
+            memory: 5_000,
+            network_requests: 5,
+            storage: 20_000,
+            min_reputation: Some(0),
+        };
+    }: _(RawOrigin::Signed(consumer), defaults)
+
+    set_registration_approval {
+        let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let approver: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(1, u32::MAX.into());
+    }: _(RawOrigin::Signed(consumer), 0u8.into(), approver)
+
+    approve_registration {
+        let (consumer, _, job_id) = pending_approval_helper::<T>()?;
+    }: _(RawOrigin::Signed(consumer), job_id)
+
+    reject_registration {
+        let (consumer, _, job_id) = pending_approval_helper::<T>()?;
+    }: _(RawOrigin::Signed(consumer), job_id)
+
+    challenge_delivery {
+        let (consumer, processor, job_id) = delivery_commitment_helper::<T>()?;
+    }: _(RawOrigin::Signed(consumer), job_id, processor, 0)
+
+    resolve_challenge {
+        let (consumer, processor, job_id) = delivery_commitment_helper::<T>()?;
+        assert_ok!(AcurastMarketplace::<T>::challenge_delivery(
+            RawOrigin::Signed(consumer).into(),
+            job_id.clone(),
+            processor.clone(),
+            0,
+        ));
+    }: _(RawOrigin::Root, job_id, processor, 0, false)
+
+    check_scheduling_windows {
+        let x in 1 .. T::MaxSchedulingWindowChecks::get();
+        let caller: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let mut sources = Vec::<T::AccountId>::new();
+        for i in 0..x {
+            let (source, _) = advertise_helper::<T>(i + 1, true);
+            sources.push(source);
+        }
+    }: _(RawOrigin::Signed(caller), sources.try_into().unwrap())
+
+    expire_unmatched_job {
+        let (consumer, job, job_id) = register_submit_helper::<T>(0, 1);
+        let caller: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(1, u32::MAX.into());
+        pallet_timestamp::Pallet::<T>::set_timestamp(job.schedule.end_time.into());
+    }: _(RawOrigin::Signed(caller), (MultiOrigin::Acurast(consumer), job_id))
+
+    repair_job_status {
+        let x in 1 .. <T as pallet_acurast::Config>::MaxSlots::get();
+        let (_, _, job_id, _) = assigned_job_helper::<T>(x)?;
+        <StoredJobStatus<T>>::remove(&job_id.0, &job_id.1);
+        <AcknowledgedSlots<T>>::remove(&job_id);
+    }: _(RawOrigin::Root, job_id.clone())
+    verify {
+        assert_last_event::<T>(Event::JobStatusRepaired(job_id, JobStatus::Assigned(x as u8)).into());
+    }
+
+    add_approved_matcher {
+        let matcher: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+    }: _(RawOrigin::Root, matcher.clone())
+    verify {
+        assert_last_event::<T>(Event::MatcherApproved(matcher).into());
+    }
+
+    remove_approved_matcher {
+        let matcher: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        <ApprovedMatchers<T>>::insert(&matcher, ());
+    }: _(RawOrigin::Root, matcher.clone())
+    verify {
+        assert_last_event::<T>(Event::MatcherApprovalRemoved(matcher).into());
+    }
+
+    propose_schedule_change {
+        let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let (_, job, job_id) = acknowledge_match_submit_helper::<T>(Some(consumer.clone()), None)?;
+        let mut schedule = job.schedule;
+        schedule.end_time = schedule.start_time + (schedule.end_time - schedule.start_time) / 2;
+    }: _(RawOrigin::Signed(consumer), job_id.clone(), schedule.clone())
+    verify {
+        assert_last_event::<T>(Event::ScheduleChangeProposed(job_id, schedule).into());
+    }
+
+    approve_schedule_change {
+        let x in 1 .. <T as pallet_acurast::Config>::MaxSlots::get();
+        let y in 0 .. T::MaxAssignedJobsPerSource::get() - 1;
+        let (consumer, job, job_id, sources) = assigned_job_helper::<T>(x)?;
+        // the approving processor's other assignments are checked for overlaps with the proposed schedule
+        let other_consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(x + 2, u32::MAX.into());
+        staggered_assigned_jobs_helper::<T>(&other_consumer, &sources[0], y, job.schedule.duration)?;
+        let mut schedule = job.schedule;
+        schedule.end_time = schedule.start_time + (schedule.end_time - schedule.start_time) / 2;
+        assert_ok!(AcurastMarketplace::<T>::propose_schedule_change(
+            RawOrigin::Signed(consumer).into(),
+            job_id.clone(),
+            schedule.clone(),
+        ));
+        // all but the benchmarked approval are given already
+        <PendingScheduleChanges<T>>::mutate(&job_id, |proposal| {
+            proposal.as_mut().unwrap().approvals = sources[1..].to_vec().try_into().unwrap();
+        });
+        let processor = sources[0].clone();
+    }: _(RawOrigin::Signed(processor), job_id.clone())
+    verify {
+        assert_last_event::<T>(Event::ScheduleChangeApplied(job_id, schedule).into());
+    }
+
+    cancel_schedule_change {
+        let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let (_, job, job_id) = acknowledge_match_submit_helper::<T>(Some(consumer.clone()), None)?;
+        assert_ok!(AcurastMarketplace::<T>::propose_schedule_change(
+            RawOrigin::Signed(consumer.clone()).into(),
+            job_id.clone(),
+            job.schedule,
+        ));
+    }: _(RawOrigin::Signed(consumer), job_id.clone())
+    verify {
+        assert_last_event::<T>(Event::ScheduleChangeCancelled(job_id).into());
+    }
+
+    acknowledge_environment {
+        let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let (processor, _, job_id) = acknowledge_match_submit_helper::<T>(Some(consumer.clone()), None)?;
+        let environment = pallet_acurast::Environment {
+            public_key: vec![0u8; 33].try_into().unwrap(),
+            variables: vec![].try_into().unwrap(),
+        };
+        assert_ok!(Acurast::<T>::set_environment_for(job_id.clone(), processor.clone(), environment));
+        assert_ok!(AcurastMarketplace::<T>::bump_environment_version(
+            RawOrigin::Signed(consumer).into(),
+            job_id.clone(),
+        ));
+    }: _(RawOrigin::Signed(processor.clone()), job_id.clone())
+    verify {
+        assert_last_event::<T>(Event::EnvironmentAcknowledged(job_id, processor, 1).into());
+    }
+
+    propose_matching_lenient {
+        let x in 1 .. T::MaxProposedMatches::get();
+        let caller: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, 1_000_000_000_000u64.into());
+        whitelist_account!(caller);
+        // every second match is rejected by the job's allowed sources, rolling back its partial assignment
+        let matches = matches_helper::<T>(x, |i| i % 2 == 1);
+    }: _(RawOrigin::Signed(caller), matches.try_into().unwrap())
+
+    bump_environment_version {
+        let consumer: T::AccountId = <T as Config>::BenchmarkHelper::funded_account(0, u32::MAX.into());
+        let (_, _, job_id) = acknowledge_match_submit_helper::<T>(Some(consumer.clone()), None)?;
+    }: _(RawOrigin::Signed(consumer), job_id.clone())
+    verify {
+        assert_last_event::<T>(Event::EnvironmentVersionBumped(job_id, 1).into());
+    }
+
+    renew_job {
+        let (consumer, mut job, job_id) = register_submit_helper::<T>(0, 1);
+        let job_id: JobId<T::AccountId> = (MultiOrigin::Acurast(consumer), job_id);
+        let e: <T as Config>::RegistrationExtra = job.extra.clone().into();
+        let mut requirements: JobRequirementsFor<T> = e.into();
+        requirements.auto_renew = Some(RenewalPolicy {
+            max_renewals: 1,
+            reward_escalation: Permill::zero(),
+        });
+        job.extra = <T as Config>::BenchmarkHelper::registration_extra(requirements).into();
+        // finalized after the start of the shifted schedule, so it gets shifted by two spans
+        pallet_timestamp::Pallet::<T>::set_timestamp((job.schedule.end_time + 1).into());
+        let renewed_job_id = (job_id.0.clone(), Acurast::<T>::job_id_sequence() + 1);
+    }: {
+        assert!(AcurastMarketplace::<T>::renew_job(&job_id, job));
+    }
+    verify {
+        assert_last_event::<T>(Event::JobRenewed(job_id, renewed_job_id, 0).into());
+    }
+
+    resolve_dependent {
+        let (consumer, dependency, dependency_id) = register_submit_helper::<T>(0, 1);
+        let dependency_id: JobId<T::AccountId> = (MultiOrigin::Acurast(consumer.clone()), dependency_id);
+        let mut job = job_registration_with_reward::<T>(script(), 1, 500, 20100, None);
+        let span = job.schedule.end_time - job.schedule.start_time;
+        job.schedule.start_time = dependency.schedule.end_time;
+        job.schedule.end_time = dependency.schedule.end_time + span;
+        let e: <T as Config>::RegistrationExtra = job.extra.clone().into();
+        let mut requirements: JobRequirementsFor<T> = e.into();
+        requirements.depends_on = Some(dependency_id.clone());
+        job.extra = <T as Config>::BenchmarkHelper::registration_extra(requirements).into();
+        assert_ok!(Acurast::<T>::register(RawOrigin::Signed(consumer.clone()).into(), job));
+        let job_id: JobId<T::AccountId> = (MultiOrigin::Acurast(consumer), Acurast::<T>::job_id_sequence());
+    }: {
+        // cancelling is more expensive than unlocking
+        assert_eq!(1, AcurastMarketplace::<T>::resolve_dependents(&dependency_id, false)?);
+    }
+    verify {
+        assert_last_event::<T>(Event::JobCancelled(job_id).into());
+    }
 
     impl_benchmark_test_suite!(AcurastMarketplace, mock::ExtBuilder::default().build(), mock::Test);
 }
@@ -3,7 +3,8 @@ use reputation::BetaParameters;
 use sp_core::Get;
 
 use crate::{
-    AdvertisementFor, AdvertisementRestriction, Config, Error, Pallet, StoredAdvertisementPricing,
+    AdvertisementFor, AdvertisementIndex, AdvertisementRestriction, Config, Error, Pallet,
+    SchedulingWindow, StoredAdvertisementPricing, StoredAdvertisementPricingVersion,
     StoredAdvertisementRestriction, StoredReputation, StoredStorageCapacity,
 };
 
@@ -13,7 +14,7 @@ impl<T: Config> Pallet<T> {
         advertisement: &AdvertisementFor<T>,
     ) -> DispatchResult {
         if let Some(allowed_consumers) = &advertisement.allowed_consumers {
-            let max_allowed_consumers_len = T::MaxAllowedSources::get() as usize;
+            let max_allowed_consumers_len = T::MaxAllowedConsumers::get() as usize;
             ensure!(
                 allowed_consumers.len() > 0,
                 Error::<T>::TooFewAllowedConsumers
@@ -24,6 +25,8 @@ impl<T: Config> Pallet<T> {
             );
         }
 
+        Self::ensure_scheduling_window(&advertisement.pricing.scheduling_window, Self::now()?)?;
+
         // update capacity to save on operations when checking available capacity
         if let Some(old) = <StoredAdvertisementRestriction<T>>::get(processor) {
             // allow capacity to become negative (in which case source remains assigned but does not receive new jobs assigned)
@@ -51,8 +54,10 @@ impl<T: Config> Pallet<T> {
                 available_modules: advertisement.available_modules.clone(),
             },
         );
+        <AdvertisementIndex<T>>::insert(processor, ());
         // update separate pricing index
         <StoredAdvertisementPricing<T>>::insert(processor, advertisement.pricing.clone());
+        <StoredAdvertisementPricingVersion<T>>::mutate(processor, |v| *v = v.saturating_add(1));
         <StoredReputation<T>>::mutate(processor, |r| {
             if r.is_none() {
                 *r = Some(BetaParameters::default());
@@ -61,4 +66,24 @@ impl<T: Config> Pallet<T> {
 
         Ok(().into())
     }
+
+    /// Ensures the scheduling window covers at least [`Config::MinSchedulingWindow`] from `now` on.
+    pub(crate) fn ensure_scheduling_window(
+        scheduling_window: &SchedulingWindow,
+        now: u64,
+    ) -> Result<(), Error<T>> {
+        let remaining = match scheduling_window {
+            SchedulingWindow::Delta(delta) => *delta,
+            SchedulingWindow::End(end) => {
+                ensure!(*end > now, Error::<T>::SchedulingWindowTooShort);
+                end - now
+            }
+        };
+        ensure!(
+            remaining >= T::MinSchedulingWindow::get(),
+            Error::<T>::SchedulingWindowTooShort
+        );
+
+        Ok(())
+    }
 }
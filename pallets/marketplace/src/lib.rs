@@ -1,11 +1,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use functions::*;
+pub use offchain::crypto;
 pub use pallet::*;
 pub use payments::*;
 pub use traits::*;
 pub use types::*;
 
+#[cfg(test)]
+mod matching_tests;
 #[cfg(test)]
 pub mod mock;
 #[cfg(any(test, feature = "runtime-benchmarks"))]
@@ -17,7 +20,11 @@ mod tests;
 mod benchmarking;
 
 mod functions;
+pub mod matching;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 mod migration;
+pub mod offchain;
 pub mod payments;
 #[cfg(feature = "std")]
 pub mod rpc;
@@ -36,25 +43,33 @@ pub use benchmarking::BenchmarkHelper;
 
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
-    use frame_support::sp_runtime::{FixedPointOperand, FixedU128, Permill, SaturatedConversion};
+    use frame_support::sp_runtime::traits::{
+        AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, TrailingZeroInput,
+        UniqueSaturatedInto,
+    };
+    use frame_support::sp_runtime::{
+        FixedPointOperand, FixedU128, PerThing, Permill, TransactionOutcome,
+    };
     use frame_support::traits::tokens::Balance;
     use frame_support::{
-        dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*, traits::UnixTime,
-        Blake2_128, Blake2_128Concat, PalletId,
+        dispatch::DispatchResultWithPostInfo, ensure, pallet_prelude::*,
+        storage::with_storage_layer, storage::with_transaction, traits::UnixTime, Blake2_128,
+        Blake2_128Concat, PalletId,
     };
     use frame_system::pallet_prelude::*;
-    use itertools::Itertools;
     use reputation::{BetaParameters, BetaReputation, ReputationEngine};
+    use sp_core::H256;
     use sp_std::iter::once;
     use sp_std::prelude::*;
 
     use pallet_acurast::utils::ensure_source_verified;
     use pallet_acurast::{
-        AllowedSourcesUpdate, JobHooks, JobId, JobIdSequence, JobRegistrationFor, MultiOrigin,
-        ParameterBound, Schedule, StoredJobRegistration,
+        AcurastLimits, AllowedSourcesUpdate, JobHooks, JobId, JobIdSequence, JobModules,
+        JobRegistrationCoreFor, JobRegistrationFor, MultiOrigin, ParameterBound, Schedule,
+        StoredJobPayload, StoredJobRegistration,
     };
 
+    use crate::matching::*;
     use crate::traits::*;
     use crate::types::*;
     use crate::utils::*;
@@ -73,9 +88,68 @@ pub mod pallet {
         type MaxProposedMatches: Get<u32>;
         #[pallet::constant]
         type MaxFinalizeJobs: Get<u32>;
+        /// The maximum number of [`SLARecord`]s kept per processor in [`SLAHistory`].
+        #[pallet::constant]
+        type MaxSLAHistory: Get<u32>;
+        /// The maximum number of jobs that can depend on a single job, see [`JobRequirements::depends_on`].
+        #[pallet::constant]
+        type MaxDependents: Get<u32>;
+        /// The maximum length of a chain of jobs waiting for their dependency, see [`JobRequirements::depends_on`].
+        ///
+        /// Together with [`Config::MaxDependents`] it bounds the number of jobs cancelled along with a failed job.
+        #[pallet::constant]
+        type MaxDependencyDepth: Get<u32>;
+        /// The maximum number of executions a job's schedule may contain.
+        ///
+        /// Only enforced on registration: lowering it does not affect already registered jobs, since their assignments
+        /// keep the SLA total derived from the schedule at registration time.
+        #[pallet::constant]
+        type MaxExecutionsPerJob: Get<u64>;
+        /// The time in milliseconds after a report during which the consumer can challenge its [`DeliveryCommitment`].
+        #[pallet::constant]
+        type DeliveryChallengeWindow: Get<u64>;
+        /// The maximum number of pending or upheld delivery challenges per assignment.
+        #[pallet::constant]
+        type MaxDeliveryChallenges: Get<u32>;
+        /// The minimum time in milliseconds an advertised [`SchedulingWindow`] has to cover from the time of advertising.
+        ///
+        /// Applies to [`SchedulingWindow::Delta`] directly and to [`SchedulingWindow::End`] as the remaining time until its end.
+        #[pallet::constant]
+        type MinSchedulingWindow: Get<u64>;
+        /// The maximum number of advertisements checked with one call to [`Pallet::check_scheduling_windows`].
+        #[pallet::constant]
+        type MaxSchedulingWindowChecks: Get<u32>;
+        /// If `true`, only matchers in [`ApprovedMatchers`] can call [`Pallet::propose_matching`].
+        ///
+        /// If `false`, the marketplace is open to any matcher.
+        #[pallet::constant]
+        type RestrictedMatching: Get<bool>;
+        /// The maximum number of sources from [`AdvertisementIndex`] sampled per job to estimate its matcher margin in
+        /// [`Pallet::matchable_jobs`].
+        #[pallet::constant]
+        type MarginEstimateSampleSize: Get<u32>;
+        /// The maximum number of jobs a source can be matched with at the same time, bounding [`SourceJobIndex`].
+        #[pallet::constant]
+        type MaxAssignedJobsPerSource: Get<u32>;
+        /// If `true`, the off-chain worker proposes matches for open jobs with the [`Config::OffchainMatcher`].
+        #[pallet::constant]
+        type EnableOffchainMatcher: Get<bool>;
+        /// Proposes the off-chain worker's matches, e.g. [`crate::offchain::SignedOffchainMatcher`] signing them with a
+        /// [`crate::crypto::KEY_TYPE`] key from the node's local keystore.
+        type OffchainMatcher: OffchainMatcher<Self>;
+        /// The maximum number of entries of [`StoredJobStatus`] the off-chain worker scans for open jobs per block.
+        #[pallet::constant]
+        type MaxOffchainMatchScan: Get<u32>;
+        /// The maximum number of sources of [`AdvertisementIndex`] the off-chain worker checks per job.
+        #[pallet::constant]
+        type MaxOffchainSourceScan: Get<u32>;
+        /// The maximum number of match proposals the off-chain worker submits per block.
+        #[pallet::constant]
+        type MaxOffchainMatchesPerBlock: Get<u32>;
         /// Extra structure to include in the registration of a job.
         type RegistrationExtra: IsType<<Self as pallet_acurast::Config>::RegistrationExtra>
-            + Into<JobRequirementsFor<Self>>;
+            + Into<JobRequirementsFor<Self>>
+            + From<JobRequirementsFor<Self>>;
         /// The ID for this pallet
         #[pallet::constant]
         type PalletId: Get<PalletId>;
@@ -89,20 +163,40 @@ pub mod pallet {
         /// would be considered outide of the agreed schedule despite being within schedule.
         #[pallet::constant]
         type ReportTolerance: Get<u64>;
+        /// The time in milliseconds after which a [`PendingPayout`] can be claimed by the processor itself if its manager
+        /// still cannot be resolved.
+        #[pallet::constant]
+        type PendingPayoutTimeout: Get<u64>;
+        /// The time in milliseconds after which a [`ScheduleChangeProposal`] not yet approved by all assigned processors
+        /// expires and can be cancelled by anyone.
+        #[pallet::constant]
+        type ScheduleChangeTimeout: Get<u64>;
+        /// The part of each execution's fee paid out on [`Pallet::report`] for jobs opting into a holdback with
+        /// [`JobRequirements::holdback_release_sla`]. The remainder is held back until the assignment is finalized.
+        #[pallet::constant]
+        type ImmediatePayoutRatio: Get<Permill>;
         type Balance: Parameter + From<u64> + IsType<u128> + Balance + FixedPointOperand;
+        /// The flat deposit reserved from consumers on job registration in addition to the locked reward.
+        ///
+        /// Only charged for jobs registered with [`MultiOrigin::Acurast`]; it is returned on finalization or deregistration
+        /// and burned if the job expires without ever being matched.
+        #[pallet::constant]
+        type JobRegistrationDeposit: Get<<Self as Config>::Balance>;
         type ManagerProvider: ManagerProvider<Self>;
         type ProcessorLastSeenProvider: ProcessorLastSeenProvider<Self>;
         /// Logic for locking and paying tokens for job execution
         type RewardManager: RewardManager<Self>;
         /// Hook to act on marketplace related state transitions.
         type MarketplaceHooks: MarketplaceHooks<Self>;
+        /// Verifies that a source's attestation backs its advertised modules, for the modules it requires this for.
+        type ModuleAttestationCheck: ModuleAttestationCheck<Self>;
         type WeightInfo: WeightInfo;
 
         #[cfg(feature = "runtime-benchmarks")]
         type BenchmarkHelper: crate::benchmarking::BenchmarkHelper<Self>;
     }
 
-    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(20);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -138,6 +232,21 @@ pub mod pallet {
     pub type StoredAdvertisementPricing<T: Config> =
         StorageMap<_, Blake2_128, T::AccountId, PricingFor<T>>;
 
+    /// The sources with an advertisement as a map [`AccountId`] `(source)` -> `()`.
+    ///
+    /// Iterable index into [`StoredAdvertisementRestriction`] and [`StoredAdvertisementPricing`], whose keys are not reversible.
+    #[pallet::storage]
+    #[pallet::getter(fn advertisement_index)]
+    pub type AdvertisementIndex<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    /// The version of each source's pricing as a map [`AccountId`] `(source)` -> `u32`, bumped on every advertisement update.
+    ///
+    /// The version is kept when an advertisement gets deleted so it stays monotonically increasing.
+    #[pallet::storage]
+    #[pallet::getter(fn stored_advertisement_pricing_version)]
+    pub type StoredAdvertisementPricingVersion<T: Config> =
+        StorageMap<_, Blake2_128, T::AccountId, u32, ValueQuery>;
+
     /// The storage for remaining capacity for each source. Can be negative if capacity is reduced beyond the number of jobs currently assigned.
     #[pallet::storage]
     #[pallet::getter(fn stored_storage_capacity)]
@@ -171,6 +280,20 @@ pub mod pallet {
         AssignmentFor<T>,
     >;
 
+    /// The jobs matched with a source in the order they got matched as a map [`AccountId`] `(source)` -> [[`JobId`]].
+    ///
+    /// Kept in sync with [`StoredMatches`], which remains the source of truth for the assignments. Iterated instead of
+    /// [`StoredMatches`] wherever all matches of a source are needed, for an order independent of the storage hasher.
+    #[pallet::storage]
+    #[pallet::getter(fn source_job_index)]
+    pub type SourceJobIndex<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<JobId<T::AccountId>, <T as Config>::MaxAssignedJobsPerSource>,
+        ValueQuery,
+    >;
+
     /// Job matches as a map [`JobId`] -> [`AccountId`] `(source)` -> `()`.
     ///
     /// This map can serve as a reverse index into `StoredMatches` to achieve a mapping [`JobId`] -> [[`AssignmentFor<T>`]] with one assignment per slot that is not yet finalized.
@@ -191,6 +314,174 @@ pub mod pallet {
     pub type JobBudgets<T: Config> =
         StorageMap<_, Blake2_128, JobId<T::AccountId>, T::Balance, ValueQuery>;
 
+    /// The recent SLA history of processors as a map [`AccountId`] `(source)` -> [`SLARecordFor<T>`] with the oldest record first.
+    ///
+    /// Records are written on [`Pallet::finalize_job`] and the oldest record gets evicted when exceeding [`Config::MaxSLAHistory`].
+    #[pallet::storage]
+    #[pallet::getter(fn sla_history)]
+    pub type SLAHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<SLARecordFor<T>, T::MaxSLAHistory>,
+        ValueQuery,
+    >;
+
+    /// Tracks registration deposits held on pallet account as a map [`JobId`] -> [`T::Balance`], separate from [`JobBudgets`].
+    #[pallet::storage]
+    #[pallet::getter(fn job_registration_deposits)]
+    pub type JobRegistrationDeposits<T: Config> =
+        StorageMap<_, Blake2_128, JobId<T::AccountId>, T::Balance>;
+
+    /// Payouts escrowed on reports for which the processor's manager could not be resolved or paying failed, as a map [`AccountId`] `(source)` -> [`JobId`] -> [`PendingPayoutFor<T>`].
+    ///
+    /// The funds are kept on the pallet account (no longer part of [`JobBudgets`]) until claimed with [`Pallet::claim_pending_payout`].
+    #[pallet::storage]
+    #[pallet::getter(fn pending_payouts)]
+    pub type PendingPayouts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        JobId<T::AccountId>,
+        PendingPayoutFor<T>,
+    >;
+
+    /// Jobs waiting for a job to finalize as a map [`JobId`] `(dependency)` -> [[`JobId`]] `(dependents)`.
+    ///
+    /// This is a reverse index of [`JobRequirements::depends_on`] for jobs in status [`JobStatus::WaitingForDependency`]
+    /// or [`JobStatus::PendingApproval`] while their dependency did not finalize.
+    #[pallet::storage]
+    #[pallet::getter(fn dependents)]
+    pub type Dependents<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        JobId<T::AccountId>,
+        BoundedVec<JobId<T::AccountId>, T::MaxDependents>,
+        ValueQuery,
+    >;
+
+    /// The consumers' default requirements as a map [`AccountId`] `(consumer)` -> [`ConsumerDefaults`].
+    ///
+    /// Used to resolve [`JobRequirements::omitted`] fields of sparse job registrations.
+    #[pallet::storage]
+    #[pallet::getter(fn stored_consumer_defaults)]
+    pub type StoredConsumerDefaults<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ConsumerDefaults>;
+
+    /// The consumers' opt-in approval requirements as a map [`AccountId`] `(consumer)` -> [`RegistrationApprovalFor<T>`].
+    #[pallet::storage]
+    #[pallet::getter(fn registration_approvals)]
+    pub type RegistrationApprovals<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, RegistrationApprovalFor<T>>;
+
+    /// The delivery commitments of reported executions as a map [`JobId`] -> `(source, execution index)` -> [`DeliveryCommitment`].
+    ///
+    /// The execution index is the zero-based index of the source's report for the job.
+    #[pallet::storage]
+    #[pallet::getter(fn stored_delivery_commitment)]
+    pub type StoredDeliveryCommitments<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        JobId<T::AccountId>,
+        Blake2_128Concat,
+        (T::AccountId, u64),
+        DeliveryCommitment,
+    >;
+
+    /// The number of entries of [`StoredDeliveryCommitments`] as a map [`JobId`] -> `count`.
+    ///
+    /// Limits the removal of a job's delivery commitments to the entries actually stored.
+    #[pallet::storage]
+    pub type DeliveryCommitmentCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, u32, ValueQuery>;
+
+    /// The number of pending or upheld delivery challenges as a map [`JobId`] -> [`AccountId`] `(source)` -> `count`.
+    #[pallet::storage]
+    #[pallet::getter(fn disputed_executions)]
+    pub type DisputedExecutions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        JobId<T::AccountId>,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
+    /// The matcher rewards paid for jobs as a map [`JobId`] -> `Balance`.
+    ///
+    /// The total is capped at the matcher share computed for a job's first match, so re-matching a job pays no further
+    /// matcher rewards from the consumer's budget.
+    #[pallet::storage]
+    #[pallet::getter(fn matcher_rewards_paid)]
+    pub type MatcherRewardsPaid<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, T::Balance>;
+
+    /// The acknowledged slots of matched jobs as a map [`JobId`] -> `[slot]`, kept sorted.
+    ///
+    /// A slot stays acknowledged after its source finalized, so the count in [`JobStatus::Assigned`] is always derived from this set.
+    #[pallet::storage]
+    #[pallet::getter(fn acknowledged_slots)]
+    pub type AcknowledgedSlots<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        JobId<T::AccountId>,
+        BoundedVec<u8, <T as pallet_acurast::Config>::MaxSlots>,
+        ValueQuery,
+    >;
+
+    /// The time in milliseconds of the first registration of jobs with [`JobRequirements::preferred_sources`] as a map [`JobId`] -> `u64`.
+    ///
+    /// Used to determine if the [`JobRequirements::preference_window_ms`] elapsed. Removed once the job got matched after
+    /// the window elapsed, otherwise once the job is removed.
+    #[pallet::storage]
+    #[pallet::getter(fn job_registration_time)]
+    pub type JobRegistrationTimes<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, u64>;
+
+    /// The matchers approved to propose matches while [`Config::RestrictedMatching`] is enabled, as a map [`AccountId`] -> `()`.
+    #[pallet::storage]
+    #[pallet::getter(fn approved_matcher)]
+    pub type ApprovedMatchers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    /// The performance counters of matchers as a map [`AccountId`] -> [`MatcherPerformance`].
+    #[pallet::storage]
+    #[pallet::getter(fn matcher_stats)]
+    pub type MatcherStats<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, MatcherPerformance, ValueQuery>;
+
+    /// The pending schedule changes of assigned jobs as a map [`JobId`] -> [`ScheduleChangeProposal`].
+    #[pallet::storage]
+    #[pallet::getter(fn pending_schedule_change)]
+    pub type PendingScheduleChanges<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, ScheduleChangeProposalFor<T>>;
+
+    /// The SLAs of the finalized slots of assigned jobs summed up as a map [`JobId`] -> [`SLA`].
+    ///
+    /// Used to detect jobs failing entirely once the last slot gets finalized, see [`Event::JobFailed`].
+    #[pallet::storage]
+    #[pallet::getter(fn finalized_slots_sla)]
+    pub type FinalizedSlotsSLA<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, SLA>;
+
+    /// The version of the jobs' execution environments as a map [`JobId`] -> `version`, bumped by the consumer.
+    ///
+    /// Sources of jobs with [`JobRequirements::require_env_ack`] have to acknowledge the current version before reporting.
+    #[pallet::storage]
+    #[pallet::getter(fn environment_version)]
+    pub type EnvironmentVersion<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, u32, ValueQuery>;
+
+    /// The most recent rejected matching attempt of open jobs as a map [`JobId`] -> [`MatchAttempt`].
+    ///
+    /// Updated at most once per block and job, and cleared once the job gets matched or finalized. Attempts in a failing
+    /// [`Pallet::propose_matching`] are reverted with the call, only [`Pallet::propose_matching_lenient`] persists them.
+    #[pallet::storage]
+    #[pallet::getter(fn last_match_attempt)]
+    pub type LastMatchAttempt<T: Config> =
+        StorageMap<_, Blake2_128Concat, JobId<T::AccountId>, MatchAttempt<BlockNumberFor<T>>>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -210,6 +501,70 @@ pub mod pallet {
         ExecutionFailure(JobId<T::AccountId>, ExecutionFailureMessage),
         /// This event is emitted when a job is finalized.
         JobFinalized(JobId<T::AccountId>),
+        /// The payment for a report was escrowed since the source's manager could not be resolved. [JobId, SourceId, amount]
+        PayoutPending(JobId<T::AccountId>, T::AccountId, T::Balance),
+        /// A pending payout was claimed. [JobId, SourceId, beneficiary, amount]
+        PendingPayoutClaimed(JobId<T::AccountId>, T::AccountId, T::AccountId, T::Balance),
+        /// The held back fees of an assignment were released to the source on finalization. [JobId, SourceId, amount]
+        HoldbackReleased(JobId<T::AccountId>, T::AccountId, T::Balance),
+        /// The held back fees of an assignment were refunded to the consumer since the SLA threshold was not met. [JobId, SourceId, amount]
+        HoldbackForfeited(JobId<T::AccountId>, T::AccountId, T::Balance),
+        /// A job became matchable since its dependency finalized successfully. [JobId]
+        JobUnlocked(JobId<T::AccountId>),
+        /// A job waiting for its dependency was cancelled and refunded since the dependency failed or got deregistered. [JobId]
+        JobCancelled(JobId<T::AccountId>),
+        /// The default requirements of a consumer were stored. [who, defaults]
+        ConsumerDefaultsStored(T::AccountId, ConsumerDefaults),
+        /// The registration approval requirement of a consumer was stored. [who, approval]
+        RegistrationApprovalStored(T::AccountId, RegistrationApprovalFor<T>),
+        /// A registration exceeding the consumer's approval threshold awaits approval. [JobId, approver]
+        RegistrationPendingApproval(JobId<T::AccountId>, T::AccountId),
+        /// A registration pending approval was approved. [JobId]
+        RegistrationApproved(JobId<T::AccountId>),
+        /// A registration pending approval was rejected, refunded and removed. [JobId]
+        RegistrationRejected(JobId<T::AccountId>),
+        /// A delivery commitment was stored for a reported execution. [JobId, SourceId, execution index, commitment]
+        DeliveryCommitted(JobId<T::AccountId>, T::AccountId, u64, H256),
+        /// The consumer challenged a delivery commitment. [JobId, SourceId, execution index]
+        DeliveryChallenged(JobId<T::AccountId>, T::AccountId, u64),
+        /// A delivery challenge was resolved. [JobId, SourceId, execution index, status]
+        DeliveryChallengeResolved(JobId<T::AccountId>, T::AccountId, u64, ChallengeStatus),
+        /// An existing advertisement's scheduling window violates [`Config::MinSchedulingWindow`]. [SourceId, scheduling_window]
+        AdvertisementBelowMinimumWindow(T::AccountId, SchedulingWindow),
+        /// An open job reached the end of its schedule without ever being matched and got refunded. [JobId]
+        JobExpiredUnmatched(JobId<T::AccountId>),
+        /// The missing status of a registered job was reconstructed. [JobId, status]
+        JobStatusRepaired(JobId<T::AccountId>, JobStatus),
+        /// A matcher got approved to propose matches. [matcher]
+        MatcherApproved(T::AccountId),
+        /// A matcher's approval to propose matches got removed. [matcher]
+        MatcherApprovalRemoved(T::AccountId),
+        /// A processor finalized a batch of its assigned jobs. [SourceId, finalized, failed]
+        AssignedJobsFinalized(T::AccountId, u32, u32),
+        /// A source got matched to a job with a maximum fee per execution. [JobId, SourceId, fee_per_execution, max_fee_per_execution]
+        MatchedWithinMaxFee(JobId<T::AccountId>, T::AccountId, T::Balance, T::Balance),
+        /// The consumer proposed to change the schedule of an assigned job. [JobId, schedule]
+        ScheduleChangeProposed(JobId<T::AccountId>, Schedule),
+        /// An assigned processor approved a proposed schedule change. [JobId, SourceId]
+        ScheduleChangeApproved(JobId<T::AccountId>, T::AccountId),
+        /// A schedule change was approved by all assigned processors and replaced the job's schedule. [JobId, schedule]
+        ScheduleChangeApplied(JobId<T::AccountId>, Schedule),
+        /// A proposed schedule change was cancelled. [JobId]
+        ScheduleChangeCancelled(JobId<T::AccountId>),
+        /// An assigned job was finalized without any of its sources meeting an expected execution. [JobId]
+        ///
+        /// Always followed by [`Event::JobFinalized`] for the same job.
+        JobFailed(JobId<T::AccountId>),
+        /// A source acknowledged the receipt of its execution environment. [JobId, SourceId, environment_version]
+        EnvironmentAcknowledged(JobId<T::AccountId>, T::AccountId, u32),
+        /// The consumer bumped the environment version of a job, requiring its sources to acknowledge again. [JobId, environment_version]
+        EnvironmentVersionBumped(JobId<T::AccountId>, u32),
+        /// A proposed match got rejected in a lenient matching proposal. [JobId, reason]
+        MatchRejected(JobId<T::AccountId>, MatchingReasonCode),
+        /// A successfully finalized job got re-registered according to its [`JobRequirements::auto_renew`]. [JobId, renewed JobId, renewals_left]
+        JobRenewed(JobId<T::AccountId>, JobId<T::AccountId>, u32),
+        /// A successfully finalized job could not be renewed, e.g. because its consumer cannot afford the escalated budget. [JobId]
+        JobRenewalFailed(JobId<T::AccountId>),
     }
 
     #[pallet::error]
@@ -220,7 +575,7 @@ pub mod pallet {
         UnexpectedCheckedCalculation,
         /// The job registration must specify non-zero `duration`.
         JobRegistrationZeroDuration,
-        /// The job registration must specify a schedule that contains a maximum of [MAX_EXECUTIONS_PER_JOB] executions.
+        /// The job registration must specify a schedule that contains a maximum of [`Config::MaxExecutionsPerJob`] executions.
         JobRegistrationScheduleExceedsMaximumExecutions,
         /// The job registration must specify a schedule that contains at least one execution.
         JobRegistrationScheduleContainsZeroExecutions,
@@ -288,6 +643,10 @@ pub mod pallet {
         InsufficientReputationInMatch,
         /// Match is invalid due to overlapping schedules.
         ScheduleOverlapInMatch,
+        /// Match is invalid due to insufficient fulfillment ratio in the recent SLA history of a proposed source.
+        InsufficientRecentFulfillmentRatioInMatch,
+        /// Match is invalid due to a source's pricing being updated after the matcher observed it.
+        PricingVersionMismatch,
         /// Received a report from a source that is not assigned.
         ReportFromUnassignedSource,
         /// More reports than expected total.
@@ -302,6 +661,84 @@ pub mod pallet {
         JobNotAssigned,
         /// The job cannot be finalized yet.
         JobCannotBeFinalized,
+        /// No pending payout found for the source and job.
+        PendingPayoutNotFound,
+        /// The pending payout cannot be claimed before a manager exists again or the [`Config::PendingPayoutTimeout`] passed.
+        PendingPayoutNotClaimable,
+        /// Match is invalid due to a source's attestation being older than the job's `max_attestation_age_ms`.
+        AttestationTooOld,
+        /// The job registration's dependency does not exist or already finalized.
+        DependencyNotFound,
+        /// The job registration's dependency was registered by another consumer.
+        DependencyOfOtherConsumer,
+        /// The job registration's must specify `start` not before the dependency's `end`.
+        JobRegistrationStartBeforeDependencyEnd,
+        /// The maximum number of jobs depending on a single job is exceeded.
+        TooManyDependents,
+        /// A job registration with a dependency cannot provide an instant match.
+        InstantMatchWithDependency,
+        /// A sparse job registration was provided but the consumer has no default requirements stored.
+        ConsumerDefaultsNotFound,
+        /// The job registration is not pending approval.
+        RegistrationNotPendingApproval,
+        /// The caller is not the approver configured by the job's consumer.
+        NotRegistrationApprover,
+        /// The caller is not the job's consumer.
+        NotJobConsumer,
+        /// No delivery commitment found for the source's execution.
+        DeliveryCommitmentNotFound,
+        /// The delivery commitment cannot be challenged after [`Config::DeliveryChallengeWindow`] passed.
+        DeliveryChallengeWindowExpired,
+        /// The delivery commitment was already challenged.
+        DeliveryAlreadyChallenged,
+        /// The maximum number of disputed executions per assignment is exceeded.
+        TooManyDeliveryChallenges,
+        /// The delivery commitment has no pending challenge.
+        DeliveryChallengeNotPending,
+        /// Match is invalid due to a source not part of the job's preferred sources during the preference window.
+        OutsidePreferenceWindow,
+        /// The advertised scheduling window is shorter than [`Config::MinSchedulingWindow`] or ends in the past.
+        SchedulingWindowTooShort,
+        /// Match is invalid due to a required module not being backed by the source's attestation.
+        ModuleNotAttested,
+        /// The job's status exists and does not need to be repaired.
+        JobStatusAlreadyExists,
+        /// The matcher is not approved to propose matches while [`Config::RestrictedMatching`] is enabled.
+        MatcherNotApproved,
+        /// The matcher is not in [`ApprovedMatchers`].
+        MatcherApprovalNotFound,
+        /// Match is invalid due to a source's fee exceeding the job's `max_fee_per_execution`.
+        FeeAboveConsumerMaximum,
+        /// The job registration's must specify `max_fee_per_execution` <= `reward`.
+        JobRegistrationMaxFeeExceedsReward,
+        /// The schedule of a job can only be changed while it is assigned.
+        ScheduleChangeJobNotAssigned,
+        /// No schedule change is pending for the job.
+        ScheduleChangeNotFound,
+        /// The schedule change cannot be approved after [`Config::ScheduleChangeTimeout`] passed.
+        ScheduleChangeExpired,
+        /// The schedule change can only be cancelled by others than the consumer after [`Config::ScheduleChangeTimeout`] passed.
+        ScheduleChangeNotExpired,
+        /// The processor already approved the schedule change.
+        ScheduleChangeAlreadyApproved,
+        /// The matcher reward cannot be paid to the zero account or a pallet account.
+        InvalidMatcherBeneficiary,
+        /// The job requires the source to acknowledge its current execution environment before reporting.
+        EnvironmentNotAcknowledged,
+        /// No execution environment is set for the source and job.
+        EnvironmentNotFound,
+        /// The source is already matched with the maximum number of jobs, see [`Config::MaxAssignedJobsPerSource`].
+        TooManyAssignedJobsInMatch,
+        /// The budget of a renewed job exceeds the consumer's approval threshold, see [`Pallet::set_registration_approval`].
+        RenewalExceedsApprovalThreshold,
+        /// The registration requires approval and can therefore not be matched instantly, see [`Pallet::set_registration_approval`].
+        InstantMatchPendingApproval,
+        /// Only jobs registered by an Acurast account can be renewed, since their renewal is paid from the consumer's account.
+        RenewalUnsupportedOrigin,
+        /// The dependency is itself waiting for a chain of dependencies exceeding [`Config::MaxDependencyDepth`].
+        DependencyTooDeep,
+        /// A requirement in parts per million exceeds `1_000_000`.
+        JobRegistrationRatioOutOfRange,
         /// Nested Acurast error.
         PalletAcurast(pallet_acurast::Error<T>),
     }
@@ -314,62 +751,126 @@ pub mod pallet {
 
     impl<T> Error<T> {
         /// Returns true if the error is due to invalid matching proposal, i.e. *not* a hard internal error.
-        fn is_matching_error(self: &Self) -> bool {
+        pub(crate) fn is_matching_error(self: &Self) -> bool {
+            self.matching_reason_code().is_some()
+        }
+
+        /// Returns the [`MatchingReasonCode`] of a matching error, [`None`] for hard internal errors.
+        pub(crate) fn matching_reason_code(self: &Self) -> Option<MatchingReasonCode> {
             match self {
-                Error::OverdueMatch => true,
-                Error::IncorrectSourceCountInMatch => true,
-                Error::DuplicateSourceInMatch => true,
-                Error::UnverifiedSourceInMatch => true,
-                Error::SchedulingWindowExceededInMatch => true,
-                Error::MaxMemoryExceededInMatch => true,
-                Error::NetworkRequestQuotaExceededInMatch => true,
-                Error::InsufficientStorageCapacityInMatch => true,
-                Error::SourceNotAllowedInMatch => true,
-                Error::ConsumerNotAllowedInMatch => true,
-                Error::InsufficientRewardInMatch => true,
-                Error::InsufficientReputationInMatch => true,
-                Error::ScheduleOverlapInMatch => true,
-                Error::ModuleNotAvailableInMatch => true,
+                Error::OverdueMatch => Some(MatchingReasonCode::OverdueMatch),
+                Error::IncorrectSourceCountInMatch => {
+                    Some(MatchingReasonCode::IncorrectSourceCount)
+                }
+                Error::DuplicateSourceInMatch => Some(MatchingReasonCode::DuplicateSource),
+                Error::UnverifiedSourceInMatch => Some(MatchingReasonCode::UnverifiedSource),
+                Error::SchedulingWindowExceededInMatch => {
+                    Some(MatchingReasonCode::SchedulingWindowExceeded)
+                }
+                Error::MaxMemoryExceededInMatch => Some(MatchingReasonCode::MaxMemoryExceeded),
+                Error::NetworkRequestQuotaExceededInMatch => {
+                    Some(MatchingReasonCode::NetworkRequestQuotaExceeded)
+                }
+                Error::InsufficientStorageCapacityInMatch => {
+                    Some(MatchingReasonCode::InsufficientStorageCapacity)
+                }
+                Error::SourceNotAllowedInMatch => Some(MatchingReasonCode::SourceNotAllowed),
+                Error::ConsumerNotAllowedInMatch => Some(MatchingReasonCode::ConsumerNotAllowed),
+                Error::InsufficientRewardInMatch => Some(MatchingReasonCode::InsufficientReward),
+                Error::InsufficientReputationInMatch => {
+                    Some(MatchingReasonCode::InsufficientReputation)
+                }
+                Error::ScheduleOverlapInMatch => Some(MatchingReasonCode::ScheduleOverlap),
+                Error::InsufficientRecentFulfillmentRatioInMatch => {
+                    Some(MatchingReasonCode::InsufficientRecentFulfillmentRatio)
+                }
+                Error::PricingVersionMismatch => Some(MatchingReasonCode::PricingVersionMismatch),
+                Error::ModuleNotAvailableInMatch => Some(MatchingReasonCode::ModuleNotAvailable),
+                Error::AttestationTooOld => Some(MatchingReasonCode::AttestationTooOld),
+                Error::OutsidePreferenceWindow => Some(MatchingReasonCode::OutsidePreferenceWindow),
+                Error::ModuleNotAttested => Some(MatchingReasonCode::ModuleNotAttested),
+                Error::FeeAboveConsumerMaximum => Some(MatchingReasonCode::FeeAboveConsumerMaximum),
                 Error::PalletAcurast(e) => match *e {
-                    pallet_acurast::Error::FulfillSourceNotAllowed => true,
-                    pallet_acurast::Error::FulfillSourceNotVerified => true,
-                    pallet_acurast::Error::AttestationCertificateNotValid => true,
-                    pallet_acurast::Error::AttestationUsageExpired => true,
-                    pallet_acurast::Error::RevokedCertificate => true,
-                    _ => false,
+                    pallet_acurast::Error::FulfillSourceNotAllowed => {
+                        Some(MatchingReasonCode::SourceNotAllowed)
+                    }
+                    pallet_acurast::Error::FulfillSourceNotVerified => {
+                        Some(MatchingReasonCode::UnverifiedSource)
+                    }
+                    pallet_acurast::Error::AttestationCertificateNotValid
+                    | pallet_acurast::Error::AttestationUsageExpired
+                    | pallet_acurast::Error::RevokedCertificate => {
+                        Some(MatchingReasonCode::AttestationInvalid)
+                    }
+                    _ => None,
                 },
-                Error::CapacityNotFound => true,
-
-                Error::CalculationOverflow => false,
-                Error::UnexpectedCheckedCalculation => false,
-                Error::JobRegistrationZeroDuration => false,
-                Error::JobRegistrationScheduleExceedsMaximumExecutions => false,
-                Error::JobRegistrationScheduleContainsZeroExecutions => false,
-                Error::JobRegistrationDurationExceedsInterval => false,
-                Error::JobRegistrationStartInPast => false,
-                Error::JobRegistrationEndBeforeStart => false,
-                Error::JobRegistrationZeroSlots => false,
-                Error::JobStatusNotFound => false,
-                Error::JobRegistrationUnmodifiable => false,
-                Error::CannotFinalizeJob(_) => false,
-                Error::CannotAcknowledgeWhenNotMatched => false,
-                Error::CannotReportWhenNotAcknowledged => false,
-                Error::AdvertisementNotFound => false,
-                Error::AdvertisementPricingNotFound => false,
-                Error::TooManyAllowedConsumers => false,
-                Error::TooFewAllowedConsumers => false,
-                Error::TooManySlots => false,
-                Error::CannotDeleteAdvertisementWhileMatched => false,
-                Error::FailedToPay => false,
-                Error::AssetNotAllowedByBarrier => false,
-                Error::ReportFromUnassignedSource => false,
-                Error::MoreReportsThanExpected => false,
-                Error::ReportOutsideSchedule => false,
-                Error::ReputationNotFound => false,
-                Error::JobNotAssigned => false,
-                Error::JobCannotBeFinalized => false,
-
-                Error::__Ignore(_, _) => false,
+                Error::CapacityNotFound => Some(MatchingReasonCode::CapacityNotFound),
+                Error::TooManyAssignedJobsInMatch => Some(MatchingReasonCode::TooManyAssignedJobs),
+
+                Error::CalculationOverflow => None,
+                Error::UnexpectedCheckedCalculation => None,
+                Error::JobRegistrationZeroDuration => None,
+                Error::JobRegistrationScheduleExceedsMaximumExecutions => None,
+                Error::JobRegistrationScheduleContainsZeroExecutions => None,
+                Error::JobRegistrationDurationExceedsInterval => None,
+                Error::JobRegistrationStartInPast => None,
+                Error::JobRegistrationEndBeforeStart => None,
+                Error::JobRegistrationZeroSlots => None,
+                Error::JobStatusNotFound => None,
+                Error::JobRegistrationUnmodifiable => None,
+                Error::CannotFinalizeJob(_) => None,
+                Error::CannotAcknowledgeWhenNotMatched => None,
+                Error::CannotReportWhenNotAcknowledged => None,
+                Error::AdvertisementNotFound => None,
+                Error::AdvertisementPricingNotFound => None,
+                Error::TooManyAllowedConsumers => None,
+                Error::TooFewAllowedConsumers => None,
+                Error::TooManySlots => None,
+                Error::CannotDeleteAdvertisementWhileMatched => None,
+                Error::FailedToPay => None,
+                Error::AssetNotAllowedByBarrier => None,
+                Error::ReportFromUnassignedSource => None,
+                Error::MoreReportsThanExpected => None,
+                Error::ReportOutsideSchedule => None,
+                Error::ReputationNotFound => None,
+                Error::JobNotAssigned => None,
+                Error::JobCannotBeFinalized => None,
+                Error::PendingPayoutNotFound => None,
+                Error::PendingPayoutNotClaimable => None,
+                Error::DependencyNotFound => None,
+                Error::DependencyOfOtherConsumer => None,
+                Error::JobRegistrationStartBeforeDependencyEnd => None,
+                Error::TooManyDependents => None,
+                Error::InstantMatchWithDependency => None,
+                Error::ConsumerDefaultsNotFound => None,
+                Error::RegistrationNotPendingApproval => None,
+                Error::NotRegistrationApprover => None,
+                Error::NotJobConsumer => None,
+                Error::DeliveryCommitmentNotFound => None,
+                Error::DeliveryChallengeWindowExpired => None,
+                Error::DeliveryAlreadyChallenged => None,
+                Error::TooManyDeliveryChallenges => None,
+                Error::DeliveryChallengeNotPending => None,
+                Error::SchedulingWindowTooShort => None,
+                Error::JobStatusAlreadyExists => None,
+                Error::MatcherNotApproved => None,
+                Error::MatcherApprovalNotFound => None,
+                Error::JobRegistrationMaxFeeExceedsReward => None,
+                Error::ScheduleChangeJobNotAssigned => None,
+                Error::ScheduleChangeNotFound => None,
+                Error::ScheduleChangeExpired => None,
+                Error::ScheduleChangeNotExpired => None,
+                Error::ScheduleChangeAlreadyApproved => None,
+                Error::InvalidMatcherBeneficiary => None,
+                Error::EnvironmentNotAcknowledged => None,
+                Error::EnvironmentNotFound => None,
+                Error::RenewalExceedsApprovalThreshold => None,
+                Error::InstantMatchPendingApproval => None,
+                Error::RenewalUnsupportedOrigin => None,
+                Error::DependencyTooDeep => None,
+                Error::JobRegistrationRatioOutOfRange => None,
+
+                Error::__Ignore(_, _) => None,
             }
         }
     }
@@ -379,6 +880,46 @@ pub mod pallet {
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
             crate::migration::migrate::<T>()
         }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            crate::migration::pre_upgrade::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            crate::migration::post_upgrade::<T>(state)
+        }
+
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            if T::EnableOffchainMatcher::get() {
+                T::OffchainMatcher::offchain_match(block_number);
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(
+            _n: BlockNumberFor<T>,
+        ) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+
+        fn integrity_test() {
+            assert!(
+                T::MaxExecutionsPerJob::get() > 0,
+                "MaxExecutionsPerJob must allow at least one execution"
+            );
+            // the allowed consumers are rejected if empty, so the bound has to allow at least one
+            assert!(
+                T::MaxAllowedConsumers::get() > 0,
+                "MaxAllowedConsumers must allow at least one consumer"
+            );
+            // slots are indexed by `u8` in assignments, matches and acknowledgements
+            assert!(
+                <T as pallet_acurast::Config>::MaxSlots::get() <= u8::MAX as u32,
+                "MaxSlots must not exceed the u8 slot index"
+            );
+        }
     }
 
     #[pallet::call]
@@ -420,6 +961,7 @@ pub mod pallet {
             let _ = <StoredAdvertisementPricing<T>>::remove(&who);
             <StoredStorageCapacity<T>>::remove(&who);
             <StoredAdvertisementRestriction<T>>::remove(&who);
+            <AdvertisementIndex<T>>::remove(&who);
 
             Self::deposit_event(Event::AdvertisementRemoved(who));
             Ok(().into())
@@ -433,13 +975,7 @@ pub mod pallet {
             matches: BoundedVec<MatchFor<T>, <T as Config>::MaxProposedMatches>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-
-            let remaining_rewards = Self::process_matching(&matches)?;
-
-            // pay part of accumulated remaining reward (unspent to consumer) to matcher
-            T::RewardManager::pay_matcher_reward(remaining_rewards, &who)?;
-
-            Ok(().into())
+            Self::do_propose_matching(&who, &matches, &who, false)
         }
 
         /// Acknowledges a matched job. It fails if the origin is not the account that was matched for the job.
@@ -468,15 +1004,22 @@ pub mod pallet {
             )?;
 
             if changed {
+                let acknowledged = Self::acknowledge_slot(&job_id, assignment.slot)?;
                 <StoredJobStatus<T>>::try_mutate(
                     &job_id.0,
                     &job_id.1,
                     |s| -> Result<(), Error<T>> {
                         let status = s.ok_or(Error::<T>::JobStatusNotFound)?;
                         *s = Some(match status {
-                            JobStatus::Open => Err(Error::<T>::CannotAcknowledgeWhenNotMatched)?,
-                            JobStatus::Matched => JobStatus::Assigned(1),
-                            JobStatus::Assigned(count) => JobStatus::Assigned(count + 1),
+                            JobStatus::Open
+                            | JobStatus::WaitingForDependency
+                            | JobStatus::PendingApproval => {
+                                Err(Error::<T>::CannotAcknowledgeWhenNotMatched)?
+                            }
+                            // the count is derived from the acknowledged slots so it cannot drift from the assignments
+                            JobStatus::Matched | JobStatus::Assigned(_) => {
+                                JobStatus::Assigned(acknowledged)
+                            }
                         });
 
                         Ok(())
@@ -499,8 +1042,10 @@ pub mod pallet {
         /// Reward is payed out to source if timing of this call is within expected interval. More precisely,
         /// the report is accepted if `[now, now + tolerance]` overlaps with an execution of the schedule agreed on.
         /// `tolerance` is a pallet config value.
+        ///
+        /// A report with a delivery commitment is charged upfront for the commitment's removal when the job is finalized.
         #[pallet::call_index(4)]
-        #[pallet::weight(< T as Config >::WeightInfo::report())]
+        #[pallet::weight(< T as Config >::WeightInfo::report().saturating_add(T::DbWeight::get().writes(1)))]
         pub fn report(
             origin: OriginFor<T>,
             job_id: JobId<T::AccountId>,
@@ -508,7 +1053,13 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
+            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+
             // find assignment
+            let mut payout: T::Balance = 0u8.into();
             let assignment = <StoredMatches<T>>::try_mutate(
                 &who,
                 &job_id,
@@ -521,6 +1072,14 @@ pub mod pallet {
                             Error::<T>::CannotReportWhenNotAcknowledged
                         );
 
+                        // CHECK that the source received the current environment if required
+                        ensure!(
+                            !requirements.require_env_ack
+                                || assignment.environment_ack
+                                    == Some(<EnvironmentVersion<T>>::get(&job_id)),
+                            Error::<T>::EnvironmentNotAcknowledged
+                        );
+
                         // CHECK that we don't accept more reports than expected
                         ensure!(
                             assignment.sla.met < assignment.sla.total,
@@ -528,6 +1087,14 @@ pub mod pallet {
                         );
 
                         assignment.sla.met += 1;
+
+                        let (immediate, holdback) =
+                            Self::split_execution_fee(&requirements, assignment.fee_per_execution);
+                        payout = immediate;
+                        assignment.holdback = assignment
+                            .holdback
+                            .checked_add(&holdback)
+                            .ok_or(Error::<T>::CalculationOverflow)?;
                         return Ok(assignment.to_owned());
                     } else {
                         return Err(Error::<T>::ReportFromUnassignedSource);
@@ -535,9 +1102,6 @@ pub mod pallet {
                 },
             )?;
 
-            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
-                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
-
             let now = Self::now()?;
             let now_max = now
                 .checked_add(T::ReportTolerance::get())
@@ -559,30 +1123,44 @@ pub mod pallet {
                 Error::<T>::ReportOutsideSchedule
             );
 
-            // pay only after all other steps succeeded without errors because paying reward is not revertable
+            // Ordering invariant: the report is validated and its SLA accounted for before paying. Paying comes last
+            // and never fails the report for payment-side reasons: a failed payment is escrowed as a [`PendingPayout`]
+            // claimable with [`Pallet::claim_pending_payout`], so a valid report is never lost.
+            Self::pay_source(&who, &job_id, payout)?;
 
-            match T::ManagerProvider::manager_of(&who) {
-                Ok(manager) => {
-                    T::RewardManager::pay_reward(
+            match execution_result {
+                ExecutionResult::Success(operation_hash) => {
+                    Self::deposit_event(Event::ExecutionSuccess(job_id.clone(), operation_hash))
+                }
+                ExecutionResult::Failure(message) => {
+                    Self::deposit_event(Event::ExecutionFailure(job_id.clone(), message))
+                }
+                ExecutionResult::SuccessWithDeliveryCommitment(operation_hash, commitment) => {
+                    let execution_index = assignment.sla.met - 1;
+                    <DeliveryCommitmentCount<T>>::mutate(&job_id, |count| {
+                        *count = count.saturating_add(1)
+                    });
+                    <StoredDeliveryCommitments<T>>::insert(
                         &job_id,
-                        assignment.fee_per_execution.clone(),
-                        &manager,
-                    )?;
-
-                    match execution_result {
-                        ExecutionResult::Success(operation_hash) => Self::deposit_event(
-                            Event::ExecutionSuccess(job_id.clone(), operation_hash),
-                        ),
-                        ExecutionResult::Failure(message) => {
-                            Self::deposit_event(Event::ExecutionFailure(job_id.clone(), message))
-                        }
-                    }
-
-                    Self::deposit_event(Event::Reported(job_id, who, assignment.clone()));
-                    Ok(().into())
+                        (&who, execution_index),
+                        DeliveryCommitment {
+                            commitment,
+                            reported_at: now,
+                            challenge: None,
+                        },
+                    );
+                    Self::deposit_event(Event::ExecutionSuccess(job_id.clone(), operation_hash));
+                    Self::deposit_event(Event::DeliveryCommitted(
+                        job_id.clone(),
+                        who.clone(),
+                        execution_index,
+                        commitment,
+                    ));
                 }
-                Err(err_result) => Err(err_result.into()),
             }
+
+            Self::deposit_event(Event::Reported(job_id, who, assignment.clone()));
+            Ok(().into())
         }
 
         /// Called by processors when the assigned job can be finalized.
@@ -594,81 +1172,22 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
-                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
-
-            // find assignment
-            let assignment =
-                <StoredMatches<T>>::get(&who, &job_id).ok_or(Error::<T>::JobNotAssigned)?;
-
-            ensure!(
-                Self::actual_schedule_ended(&registration.schedule, &assignment)?,
-                Error::<T>::JobCannotBeFinalized
-            );
-
-            let unmet: u64 = assignment.sla.total - assignment.sla.met;
-
-            // update reputation since we don't expect further reports for this job
-            // (only update for attested devices!)
-            if ensure_source_verified::<T>(&who).is_ok() {
-                // skip reputation update if reward is 0
-                if assignment.fee_per_execution > 0u8.into() {
-                    let average_reward = <StoredAverageRewardV3<T>>::get().unwrap_or(0);
-                    let total_assigned = <StoredTotalAssignedV3<T>>::get().unwrap_or_default();
-
-                    let total_reward = average_reward
-                        .checked_mul(total_assigned - 1u128)
-                        .ok_or(Error::<T>::CalculationOverflow)?;
-
-                    let new_total_rewards = total_reward
-                        .checked_add(assignment.fee_per_execution.into())
-                        .ok_or(Error::<T>::CalculationOverflow)?;
-
-                    let mut beta_params =
-                        <StoredReputation<T>>::get(&who).ok_or(Error::<T>::ReputationNotFound)?;
-
-                    beta_params = BetaReputation::update(
-                        beta_params,
-                        assignment.sla.met,
-                        unmet,
-                        assignment.fee_per_execution,
-                        average_reward.into(),
-                    )
-                    .ok_or(Error::<T>::CalculationOverflow)?;
-
-                    let new_average_reward = new_total_rewards
-                        .checked_div(total_assigned)
-                        .ok_or(Error::<T>::CalculationOverflow)?;
-
-                    <StoredAverageRewardV3<T>>::set(Some(new_average_reward));
-                    <StoredReputation<T>>::insert(
-                        &who,
-                        BetaParameters {
-                            r: beta_params.r,
-                            s: beta_params.s,
-                        },
-                    );
-                }
-            }
-
-            // only remove storage point indexed by a single processor (corresponding to the completed duties for the assigned slot)
-            <StoredMatches<T>>::remove(&who, &job_id);
-            <AssignedProcessors<T>>::remove(&job_id, &who);
-
-            // increase capacity
-            <StoredStorageCapacity<T>>::mutate(&who, |c| {
-                *c = c.unwrap_or(0).checked_add(registration.storage.into())
-            });
-
-            Self::deposit_event(Event::JobFinalized(job_id));
+            Self::do_finalize_job(&who, &job_id)?;
             Ok(().into())
         }
 
         /// Called by a consumer whenever he wishes to finalizes some of his jobs and get unused rewards refunded.
         ///
         /// For details see [`Pallet<T>::finalize_jobs_for`].
+        ///
+        /// The weight covers the renewal of every job and the maximum number of dependents resolved per job, the weight
+        /// of jobs not renewed and dependents not resolved is refunded.
         #[pallet::call_index(6)]
-        #[pallet::weight(<T as Config>::WeightInfo::finalize_jobs(job_ids.len() as u32))]
+        #[pallet::weight(<T as Config>::WeightInfo::finalize_jobs(job_ids.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::renew_job().saturating_mul(job_ids.len() as u64))
+            .saturating_add(<T as Config>::WeightInfo::resolve_dependent().saturating_mul(
+                (job_ids.len() as u64).saturating_mul(Pallet::<T>::max_resolved_dependents().into())
+            )))]
         pub fn finalize_jobs(
             origin: OriginFor<T>,
             job_ids: BoundedVec<JobIdSequence, T::MaxFinalizeJobs>,
@@ -681,80 +1200,861 @@ pub mod pallet {
                     .map(|job_id_seq| (MultiOrigin::Acurast(who.clone()), job_id_seq)),
             )
         }
-    }
-
-    impl<T: Config> From<Error<T>> for pallet_acurast::Error<T> {
-        fn from(_: Error<T>) -> Self {
-            Self::JobHookFailed
-        }
-    }
 
-    impl<T: Config> JobHooks<T> for Pallet<T> {
-        /// Registers a job in the marketplace by providing a [JobRegistration].
-        /// If a job for the same `(accountId, script)` was previously registered, it will be overwritten.
-        fn register_hook(
-            _who: &MultiOrigin<T::AccountId>,
-            job_id: &JobId<T::AccountId>,
-            registration: &JobRegistrationFor<T>,
+        /// Claims a payout escrowed by [`Pallet::report`] for the calling source.
+        ///
+        /// The payout goes to the source's manager once it can be resolved again, or to the source itself
+        /// after [`Config::PendingPayoutTimeout`] passed since the payout was escrowed.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_pending_payout())]
+        pub fn claim_pending_payout(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
         ) -> DispatchResultWithPostInfo {
-            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
-            let requirements: JobRequirementsFor<T> = e.into();
+            let who = ensure_signed(origin)?;
+
+            let pending =
+                <PendingPayouts<T>>::get(&who, &job_id).ok_or(Error::<T>::PendingPayoutNotFound)?;
+
+            let beneficiary = match T::ManagerProvider::manager_of(&who) {
+                Ok(manager) => manager,
+                Err(_) => {
+                    let claimable_from = pending
+                        .escrowed_at
+                        .checked_add(T::PendingPayoutTimeout::get())
+                        .ok_or(Error::<T>::CalculationOverflow)?;
+                    ensure!(
+                        Self::now()? >= claimable_from,
+                        Error::<T>::PendingPayoutNotClaimable
+                    );
+                    who.clone()
+                }
+            };
+
+            <PendingPayouts<T>>::remove(&who, &job_id);
+            T::RewardManager::pay_escrowed_reward(pending.amount, &beneficiary)?;
+
+            Self::deposit_event(Event::PendingPayoutClaimed(
+                job_id,
+                who,
+                beneficiary,
+                pending.amount,
+            ));
+            Ok(().into())
+        }
+
+        /// Stores the caller's default requirements, used to resolve the omitted fields of sparse job registrations.
+        ///
+        /// Updates only affect registrations made afterwards, already stored registrations are fully resolved.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_default_requirements())]
+        pub fn set_default_requirements(
+            origin: OriginFor<T>,
+            defaults: ConsumerDefaults,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            <StoredConsumerDefaults<T>>::insert(&who, defaults.clone());
+
+            Self::deposit_event(Event::ConsumerDefaultsStored(who, defaults));
+            Ok(().into())
+        }
+
+        /// Requires registrations of the caller locking a total budget above `threshold` to be approved by `approver`.
+        ///
+        /// Such registrations are stored in status [`JobStatus::PendingApproval`] until approved with [`Pallet::approve_registration`]
+        /// or rejected with [`Pallet::reject_registration`].
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_registration_approval())]
+        pub fn set_registration_approval(
+            origin: OriginFor<T>,
+            threshold: T::Balance,
+            approver: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let approval = RegistrationApproval {
+                threshold,
+                approver,
+            };
+            <RegistrationApprovals<T>>::insert(&who, approval.clone());
+
+            Self::deposit_event(Event::RegistrationApprovalStored(who, approval));
+            Ok(().into())
+        }
+
+        /// Approves a registration in status [`JobStatus::PendingApproval`], making it matchable.
+        ///
+        /// Can only be called by the approver configured by the job's consumer.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve_registration())]
+        pub fn approve_registration(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_pending_approval_by(&who, &job_id)?;
+
+            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+            let e: <T as Config>::RegistrationExtra = registration.extra.into();
+            let requirements: JobRequirementsFor<T> = e.into();
+            <StoredJobStatus<T>>::insert(
+                &job_id.0,
+                &job_id.1,
+                Self::approved_status(&job_id, &requirements),
+            );
+
+            Self::deposit_event(Event::RegistrationApproved(job_id));
+            Ok(().into())
+        }
+
+        /// Rejects a registration in status [`JobStatus::PendingApproval`], refunding the consumer and removing the job.
+        ///
+        /// Can only be called by the approver configured by the job's consumer.
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::reject_registration())]
+        pub fn reject_registration(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_pending_approval_by(&who, &job_id)?;
+
+            pallet_acurast::Pallet::<T>::deregister_for(job_id.clone())?;
+
+            Self::deposit_event(Event::RegistrationRejected(job_id));
+            Ok(().into())
+        }
+
+        /// Challenges the [`DeliveryCommitment`] of `source`'s execution with the given `execution_index`.
+        ///
+        /// Can only be called by the job's consumer within [`Config::DeliveryChallengeWindow`] after the report and before
+        /// the source finalized its assignment. Until resolved with [`Pallet::resolve_challenge`], the execution counts as unmet
+        /// in the reputation update on [`Pallet::finalize_job`].
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::challenge_delivery())]
+        pub fn challenge_delivery(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+            source: T::AccountId,
+            execution_index: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
 
             ensure!(
-                registration.schedule.duration > 0,
-                Error::<T>::JobRegistrationZeroDuration
-            );
-            let execution_count = registration.schedule.execution_count();
-            ensure!(
-                execution_count <= MAX_EXECUTIONS_PER_JOB,
-                Error::<T>::JobRegistrationScheduleExceedsMaximumExecutions
+                job_id.0 == MultiOrigin::Acurast(who),
+                Error::<T>::NotJobConsumer
             );
             ensure!(
-                execution_count > 0,
-                Error::<T>::JobRegistrationScheduleContainsZeroExecutions
+                <StoredMatches<T>>::contains_key(&source, &job_id),
+                Error::<T>::JobNotAssigned
             );
+
+            let disputed = <DisputedExecutions<T>>::get(&job_id, &source);
             ensure!(
-                registration.schedule.duration < registration.schedule.interval,
-                Error::<T>::JobRegistrationDurationExceedsInterval
+                disputed < T::MaxDeliveryChallenges::get(),
+                Error::<T>::TooManyDeliveryChallenges
             );
+
+            let now = Self::now()?;
+            <StoredDeliveryCommitments<T>>::try_mutate(
+                &job_id,
+                (&source, execution_index),
+                |commitment| -> Result<(), Error<T>> {
+                    let commitment = commitment
+                        .as_mut()
+                        .ok_or(Error::<T>::DeliveryCommitmentNotFound)?;
+                    ensure!(
+                        commitment.challenge.is_none(),
+                        Error::<T>::DeliveryAlreadyChallenged
+                    );
+                    let window_end = commitment
+                        .reported_at
+                        .checked_add(T::DeliveryChallengeWindow::get())
+                        .ok_or(Error::<T>::CalculationOverflow)?;
+                    ensure!(now < window_end, Error::<T>::DeliveryChallengeWindowExpired);
+                    commitment.challenge = Some(ChallengeStatus::Pending);
+                    Ok(())
+                },
+            )?;
+            <DisputedExecutions<T>>::insert(&job_id, &source, disputed + 1);
+
+            Self::deposit_event(Event::DeliveryChallenged(job_id, source, execution_index));
+            Ok(().into())
+        }
+
+        /// Resolves a pending delivery challenge, either upholding it (the execution stays unmet) or rejecting it (the execution counts as met again).
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::resolve_challenge())]
+        pub fn resolve_challenge(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+            source: T::AccountId,
+            execution_index: u64,
+            upheld: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            let status = if upheld {
+                ChallengeStatus::Upheld
+            } else {
+                ChallengeStatus::Rejected
+            };
+            <StoredDeliveryCommitments<T>>::try_mutate(
+                &job_id,
+                (&source, execution_index),
+                |commitment| -> Result<(), Error<T>> {
+                    let commitment = commitment
+                        .as_mut()
+                        .ok_or(Error::<T>::DeliveryCommitmentNotFound)?;
+                    ensure!(
+                        commitment.challenge == Some(ChallengeStatus::Pending),
+                        Error::<T>::DeliveryChallengeNotPending
+                    );
+                    commitment.challenge = Some(status);
+                    Ok(())
+                },
+            )?;
+            if !upheld {
+                <DisputedExecutions<T>>::mutate_exists(&job_id, &source, |count| {
+                    *count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c > 0);
+                });
+            }
+
+            Self::deposit_event(Event::DeliveryChallengeResolved(
+                job_id,
+                source,
+                execution_index,
+                status,
+            ));
+            Ok(().into())
+        }
+
+        /// Checks the advertisements of the given sources against [`Config::MinSchedulingWindow`].
+        ///
+        /// Emits [`Event::AdvertisementBelowMinimumWindow`] for every advertisement whose scheduling window is too short
+        /// so that it can be fixed by its operator. Advertisements are only flagged, never removed. Sources without an
+        /// advertisement are skipped.
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::check_scheduling_windows(sources.len() as u32))]
+        pub fn check_scheduling_windows(
+            origin: OriginFor<T>,
+            sources: BoundedVec<T::AccountId, <T as Config>::MaxSchedulingWindowChecks>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let now = Self::now()?;
+            for source in sources {
+                if let Some(pricing) = <StoredAdvertisementPricing<T>>::get(&source) {
+                    if Self::ensure_scheduling_window(&pricing.scheduling_window, now).is_err() {
+                        Self::deposit_event(Event::AdvertisementBelowMinimumWindow(
+                            source,
+                            pricing.scheduling_window,
+                        ));
+                    }
+                }
+            }
+
+            Ok(().into())
+        }
+
+        /// Expires an open job that reached the end of its schedule without ever being matched.
+        ///
+        /// Can be called by anyone. The job's budget is refunded to the consumer, its registration deposit gets burned
+        /// and the registration is removed.
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::expire_unmatched_job()
+            .saturating_add(<T as Config>::WeightInfo::resolve_dependent().saturating_mul(Pallet::<T>::max_resolved_dependents().into())))]
+        pub fn expire_unmatched_job(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let job_status = <StoredJobStatus<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(Error::<T>::JobStatusNotFound)?;
             ensure!(
-                registration.schedule.start_time >= Self::now()?,
-                Error::<T>::JobRegistrationStartInPast
+                job_status == JobStatus::Open,
+                Error::<T>::CannotFinalizeJob(job_status)
             );
+            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
             ensure!(
-                registration.schedule.start_time <= registration.schedule.end_time,
-                Error::<T>::JobRegistrationEndBeforeStart
+                Self::now()? >= registration.schedule.end_time,
+                Error::<T>::CannotFinalizeJob(job_status)
+            );
+
+            let resolved = Self::remove_expired_unmatched_job(&job_id, false)?;
+
+            Ok(Some(
+                <T as Config>::WeightInfo::expire_unmatched_job().saturating_add(
+                    <T as Config>::WeightInfo::resolve_dependent().saturating_mul(resolved.into()),
+                ),
+            )
+            .into())
+        }
+
+        /// Reconstructs the missing status of a registered job from its stored matches.
+        ///
+        /// A job with acknowledged matches becomes [`JobStatus::Assigned`], one with unacknowledged matches only
+        /// [`JobStatus::Matched`] and one without any matches [`JobStatus::Open`]. The job's budget is not touched.
+        ///
+        /// The weight is charged for the actual number of assigned processors, up to [`pallet_acurast::Config::MaxSlots`].
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::repair_job_status(<T as pallet_acurast::Config>::MaxSlots::get()))]
+        pub fn repair_job_status(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            ensure!(
+                <StoredJobRegistration<T>>::contains_key(&job_id.0, &job_id.1),
+                pallet_acurast::Error::<T>::JobRegistrationNotFound
+            );
+            ensure!(
+                !<StoredJobStatus<T>>::contains_key(&job_id.0, &job_id.1),
+                Error::<T>::JobStatusAlreadyExists
+            );
+
+            let mut matched = false;
+            let mut acknowledged = 0u8;
+            let mut assignments = 0u32;
+            for (source, _) in <AssignedProcessors<T>>::iter_prefix(&job_id) {
+                assignments += 1;
+                if let Some(assignment) = <StoredMatches<T>>::get(&source, &job_id) {
+                    matched = true;
+                    if assignment.acknowledged {
+                        acknowledged = Self::acknowledge_slot(&job_id, assignment.slot)?;
+                    }
+                }
+            }
+            let job_status = if acknowledged > 0 {
+                JobStatus::Assigned(acknowledged)
+            } else if matched {
+                JobStatus::Matched
+            } else {
+                JobStatus::Open
+            };
+            <StoredJobStatus<T>>::insert(&job_id.0, &job_id.1, job_status);
+
+            Self::deposit_event(Event::JobStatusRepaired(job_id, job_status));
+
+            Ok(Some(<T as Config>::WeightInfo::repair_job_status(assignments)).into())
+        }
+
+        /// Approves a matcher to propose matches while [`Config::RestrictedMatching`] is enabled.
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_approved_matcher())]
+        pub fn add_approved_matcher(
+            origin: OriginFor<T>,
+            matcher: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            <ApprovedMatchers<T>>::insert(&matcher, ());
+
+            Self::deposit_event(Event::MatcherApproved(matcher));
+            Ok(().into())
+        }
+
+        /// Removes a matcher's approval to propose matches, effective for all following proposals.
+        ///
+        /// The matcher's [`MatcherStats`] are kept for review.
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_approved_matcher())]
+        pub fn remove_approved_matcher(
+            origin: OriginFor<T>,
+            matcher: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            ensure!(
+                <ApprovedMatchers<T>>::contains_key(&matcher),
+                Error::<T>::MatcherApprovalNotFound
+            );
+            <ApprovedMatchers<T>>::remove(&matcher);
+
+            Self::deposit_event(Event::MatcherApprovalRemoved(matcher));
+            Ok(().into())
+        }
+
+        /// Called by processors to finalize many of their assigned jobs at once.
+        ///
+        /// Applies [`Pallet::finalize_job`] to every job. A job that cannot be finalized, e.g. since its schedule did not
+        /// end yet, leaves no trace in storage and does not prevent the remaining jobs from being finalized.
+        /// Only successfully finalized jobs are charged the full weight, failed jobs are charged up to where they failed.
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::finalize_assigned_jobs(job_ids.len() as u32))]
+        pub fn finalize_assigned_jobs(
+            origin: OriginFor<T>,
+            job_ids: BoundedVec<JobId<T::AccountId>, T::MaxFinalizeJobs>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut finalized: u32 = 0;
+            let mut failed: u32 = 0;
+            let mut failed_weight = Weight::zero();
+            for job_id in job_ids.iter() {
+                match with_storage_layer(|| Self::do_finalize_job(&who, job_id)) {
+                    Ok(()) => finalized += 1,
+                    Err(e) => {
+                        failed += 1;
+                        failed_weight =
+                            failed_weight.saturating_add(Self::finalize_failure_weight(&e));
+                    }
+                }
+            }
+
+            Self::deposit_event(Event::AssignedJobsFinalized(who, finalized, failed));
+
+            let actual_weight = <T as Config>::WeightInfo::finalize_assigned_jobs(finalized)
+                .saturating_add(failed_weight);
+            Ok(Some(actual_weight).into())
+        }
+
+        /// Proposes to replace the schedule of an assigned job with `schedule`.
+        ///
+        /// Can only be called by the job's consumer. The schedule takes effect once all assigned processors approved it
+        /// with [`Pallet::approve_schedule_change`] within [`Config::ScheduleChangeTimeout`]. A pending proposal gets
+        /// replaced, dropping its approvals.
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_schedule_change())]
+        pub fn propose_schedule_change(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+            schedule: Schedule,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                job_id.0 == MultiOrigin::Acurast(who),
+                Error::<T>::NotJobConsumer
             );
+            ensure!(
+                matches!(
+                    <StoredJobStatus<T>>::get(&job_id.0, &job_id.1),
+                    Some(JobStatus::Assigned(_))
+                ),
+                Error::<T>::ScheduleChangeJobNotAssigned
+            );
+            Self::ensure_valid_schedule(&schedule)?;
+
+            <PendingScheduleChanges<T>>::insert(
+                &job_id,
+                ScheduleChangeProposal {
+                    schedule: schedule.clone(),
+                    proposed_at: Self::now()?,
+                    approvals: Default::default(),
+                },
+            );
+
+            Self::deposit_event(Event::ScheduleChangeProposed(job_id, schedule));
+            Ok(().into())
+        }
+
+        /// Called by an assigned processor to approve the pending schedule change of a job.
+        ///
+        /// The proposed schedule has to fit the processor's advertised scheduling window and its other assignments.
+        /// The approval completing the set of assigned processors applies the change: the job's schedule is replaced,
+        /// the SLA totals of all assignments cover the executions started so far plus the new schedule's executions
+        /// and the budget is locked or refunded for the difference.
+        #[pallet::call_index(21)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve_schedule_change(
+            <T as pallet_acurast::Config>::MaxSlots::get(),
+            <T as Config>::MaxAssignedJobsPerSource::get(),
+        ))]
+        pub fn approve_schedule_change(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let mut proposal = <PendingScheduleChanges<T>>::get(&job_id)
+                .ok_or(Error::<T>::ScheduleChangeNotFound)?;
+            let now = Self::now()?;
+            ensure!(
+                !Self::schedule_change_expired(&proposal, now)?,
+                Error::<T>::ScheduleChangeExpired
+            );
+            let assignment =
+                <StoredMatches<T>>::get(&who, &job_id).ok_or(Error::<T>::JobNotAssigned)?;
+            ensure!(
+                !proposal.approvals.contains(&who),
+                Error::<T>::ScheduleChangeAlreadyApproved
+            );
+            // the start might have passed since the proposal
+            Self::ensure_valid_schedule(&proposal.schedule)?;
+
+            let pricing = <StoredAdvertisementPricing<T>>::get(&who)
+                .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
+            check_scheduling_window(
+                &pricing.scheduling_window,
+                &proposal.schedule,
+                now,
+                assignment.start_delay,
+            )
+            .map_err(Error::<T>::from)?;
+            let matched_schedules = Self::matched_schedules_except(&who, Some(&job_id))?;
+            fits_schedule(
+                &proposal.schedule,
+                assignment.start_delay,
+                &matched_schedules,
+            )
+            .map_err(Error::<T>::from)?;
+
+            proposal
+                .approvals
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::TooManySlots)?;
+            Self::deposit_event(Event::ScheduleChangeApproved(job_id.clone(), who));
+
+            let assigned = <AssignedProcessors<T>>::iter_key_prefix(&job_id).collect::<Vec<_>>();
+            if assigned
+                .iter()
+                .all(|source| proposal.approvals.contains(source))
+            {
+                Self::apply_schedule_change(&job_id, proposal.schedule, now)?;
+                <PendingScheduleChanges<T>>::remove(&job_id);
+            } else {
+                <PendingScheduleChanges<T>>::insert(&job_id, proposal);
+            }
+
+            Ok(Some(<T as Config>::WeightInfo::approve_schedule_change(
+                assigned.len() as u32,
+                matched_schedules.len() as u32,
+            ))
+            .into())
+        }
+
+        /// Cancels the pending schedule change of a job.
+        ///
+        /// Can be called by the job's consumer at any time and by anyone once [`Config::ScheduleChangeTimeout`] passed.
+        #[pallet::call_index(22)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_schedule_change())]
+        pub fn cancel_schedule_change(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let proposal = <PendingScheduleChanges<T>>::get(&job_id)
+                .ok_or(Error::<T>::ScheduleChangeNotFound)?;
+            ensure!(
+                job_id.0 == MultiOrigin::Acurast(who)
+                    || Self::schedule_change_expired(&proposal, Self::now()?)?,
+                Error::<T>::ScheduleChangeNotExpired
+            );
+            <PendingScheduleChanges<T>>::remove(&job_id);
+
+            Self::deposit_event(Event::ScheduleChangeCancelled(job_id));
+            Ok(().into())
+        }
+
+        /// Proposes processors to match with a job like [`Pallet::propose_matching`], but pays the matcher reward to `beneficiary`.
+        ///
+        /// Allows to run matching as a service: the signer still pays the transaction fees and is credited with the
+        /// proposal in [`MatcherStats`]. Fails if `beneficiary` is the zero account or a pallet account.
+        #[pallet::call_index(23)]
+        #[pallet::weight(< T as Config >::WeightInfo::propose_matching(matches.len() as u32))]
+        pub fn propose_matching_with_beneficiary(
+            origin: OriginFor<T>,
+            matches: BoundedVec<MatchFor<T>, <T as Config>::MaxProposedMatches>,
+            beneficiary: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_valid_beneficiary(&beneficiary)?;
+            Self::do_propose_matching(&who, &matches, &beneficiary, false)
+        }
+
+        /// Called by an assigned processor to acknowledge the receipt of its execution environment for a job.
+        ///
+        /// Records the job's current [`EnvironmentVersion`] in the processor's assignment, as required for reporting on
+        /// jobs with [`JobRequirements::require_env_ack`]. Fails if no environment is set for the processor.
+        #[pallet::call_index(24)]
+        #[pallet::weight(<T as Config>::WeightInfo::acknowledge_environment())]
+        pub fn acknowledge_environment(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                <pallet_acurast::ExecutionEnvironment<T>>::contains_key(&job_id, &who),
+                Error::<T>::EnvironmentNotFound
+            );
+            let version = <EnvironmentVersion<T>>::get(&job_id);
+            <StoredMatches<T>>::try_mutate(&who, &job_id, |a| -> Result<(), Error<T>> {
+                let assignment = a.as_mut().ok_or(Error::<T>::JobNotAssigned)?;
+                assignment.environment_ack = Some(version);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::EnvironmentAcknowledged(job_id, who, version));
+            Ok(().into())
+        }
+
+        /// Bumps the [`EnvironmentVersion`] of a job, requiring its sources to acknowledge their environment again.
+        ///
+        /// Can only be called by the job's consumer. Environments updated without bumping the version keep existing
+        /// acknowledgements valid.
+        #[pallet::call_index(25)]
+        #[pallet::weight(<T as Config>::WeightInfo::bump_environment_version())]
+        pub fn bump_environment_version(
+            origin: OriginFor<T>,
+            job_id: JobId<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                job_id.0 == MultiOrigin::Acurast(who),
+                Error::<T>::NotJobConsumer
+            );
+            ensure!(
+                <StoredJobRegistration<T>>::contains_key(&job_id.0, &job_id.1),
+                pallet_acurast::Error::<T>::JobRegistrationNotFound
+            );
+            let version = <EnvironmentVersion<T>>::mutate(&job_id, |version| {
+                *version = version.saturating_add(1);
+                *version
+            });
+
+            Self::deposit_event(Event::EnvironmentVersionBumped(job_id, version));
+            Ok(().into())
+        }
+
+        /// Proposes processors to match with a job like [`Pallet::propose_matching`], but skips invalid matches instead of
+        /// failing the entire call.
+        ///
+        /// A match failing with a matching error is reverted, recorded in [`LastMatchAttempt`] and reported with
+        /// [`Event::MatchRejected`]. Hard internal errors still fail the call.
+        ///
+        /// The weight covers both a proposal with rejected matches and one where every match gets assigned.
+        #[pallet::call_index(26)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_matching_lenient(matches.len() as u32).max(<T as Config>::WeightInfo::propose_matching(matches.len() as u32)))]
+        pub fn propose_matching_lenient(
+            origin: OriginFor<T>,
+            matches: BoundedVec<MatchFor<T>, <T as Config>::MaxProposedMatches>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_propose_matching(&who, &matches, &who, true)
+        }
+
+        /// Proposes processors to match with a job like [`Pallet::propose_matching_lenient`], but pays the matcher reward to
+        /// `beneficiary`.
+        ///
+        /// The signer is credited with the proposal like in [`Pallet::propose_matching_with_beneficiary`]. Fails if
+        /// `beneficiary` is the zero account or a pallet account.
+        #[pallet::call_index(27)]
+        #[pallet::weight(<T as Config>::WeightInfo::propose_matching_lenient(matches.len() as u32).max(<T as Config>::WeightInfo::propose_matching(matches.len() as u32)))]
+        pub fn propose_matching_lenient_with_beneficiary(
+            origin: OriginFor<T>,
+            matches: BoundedVec<MatchFor<T>, <T as Config>::MaxProposedMatches>,
+            beneficiary: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_valid_beneficiary(&beneficiary)?;
+            Self::do_propose_matching(&who, &matches, &beneficiary, true)
+        }
+    }
+
+    impl<T: Config> From<Error<T>> for pallet_acurast::Error<T> {
+        fn from(_: Error<T>) -> Self {
+            Self::JobHookFailed
+        }
+    }
+
+    impl<T: Config> JobHooks<T> for Pallet<T> {
+        /// Resolves the [`JobRequirements::omitted`] fields of a sparse job registration from the consumer's [`ConsumerDefaults`].
+        ///
+        /// Fields not omitted keep their explicit values. Fails if the consumer has no defaults stored.
+        fn resolve_registration_hook(
+            who: &MultiOrigin<T::AccountId>,
+            registration: &mut JobRegistrationFor<T>,
+        ) -> DispatchResult {
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let mut requirements: JobRequirementsFor<T> = e.into();
+            let omitted = match requirements.omitted.take() {
+                Some(omitted) => omitted,
+                None => return Ok(()),
+            };
+
+            let defaults = match who {
+                MultiOrigin::Acurast(who) => Self::stored_consumer_defaults(who),
+                _ => None,
+            }
+            .ok_or(Error::<T>::ConsumerDefaultsNotFound)?;
+
+            if omitted.allow_only_verified_sources {
+                registration.allow_only_verified_sources = defaults.allow_only_verified_sources;
+            }
+            if omitted.required_modules {
+                registration.required_modules = defaults.required_modules;
+            }
+            if omitted.memory {
+                registration.memory = defaults.memory;
+            }
+            if omitted.network_requests {
+                registration.network_requests = defaults.network_requests;
+            }
+            if omitted.storage {
+                registration.storage = defaults.storage;
+            }
+            if omitted.min_reputation {
+                requirements.min_reputation = defaults.min_reputation;
+            }
+
+            let e: <T as Config>::RegistrationExtra = requirements.into();
+            registration.extra = e.into();
+
+            Ok(())
+        }
+
+        /// Registers a job in the marketplace by providing a [JobRegistration].
+        /// If a job for the same `job_id` was previously registered, it will be overwritten as long as it is still
+        /// [`JobStatus::Open`]; the locked budget is reconciled with the `previous` registration's budget.
+        ///
+        /// A job specifying [`JobRequirements::depends_on`] is registered in status [`JobStatus::WaitingForDependency`].
+        fn register_hook(
+            _who: &MultiOrigin<T::AccountId>,
+            job_id: &JobId<T::AccountId>,
+            registration: &JobRegistrationFor<T>,
+            previous: Option<JobRegistrationFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+
+            Self::ensure_valid_schedule(&registration.schedule)?;
             ensure!(requirements.slots > 0, Error::<T>::JobRegistrationZeroSlots);
             ensure!(
                 requirements.slots as u32 <= <T as pallet_acurast::Config>::MaxSlots::get(),
                 Error::<T>::TooManySlots
             );
+            if let Some(max_fee_per_execution) = &requirements.max_fee_per_execution {
+                ensure!(
+                    max_fee_per_execution <= &requirements.reward,
+                    Error::<T>::JobRegistrationMaxFeeExceedsReward
+                );
+            }
+            ensure!(
+                [
+                    requirements.min_reputation,
+                    requirements.min_recent_fulfillment_ratio,
+                    requirements.holdback_release_sla,
+                ]
+                .iter()
+                .flatten()
+                .all(|parts| *parts <= u128::from(Permill::ACCURACY)),
+                Error::<T>::JobRegistrationRatioOutOfRange
+            );
+
+            // reward is understood per slot and execution, so calculate total_reward_amount first
+            let reward_amount = Self::total_reward_amount(&registration.into())?;
+            let approver = Self::registration_approver(job_id, reward_amount);
+            // a job pending approval is not matchable, so its instant match could never be processed
+            ensure!(
+                approver.is_none() || requirements.instant_match.is_none(),
+                Error::<T>::InstantMatchPendingApproval
+            );
+            ensure!(
+                requirements.auto_renew.is_none() || matches!(job_id.0, MultiOrigin::Acurast(_)),
+                Error::<T>::RenewalUnsupportedOrigin
+            );
 
-            if let Some(job_status) = <StoredJobStatus<T>>::get(&job_id.0, &job_id.1) {
+            // the budget locked for the previous registration, only present if overwriting an open job
+            let previous_reward_amount = if let Some(job_status) =
+                <StoredJobStatus<T>>::get(&job_id.0, &job_id.1)
+            {
                 ensure!(
-                    job_status == JobStatus::Open,
+                    job_status == JobStatus::Open || job_status == JobStatus::PendingApproval,
                     Error::<T>::JobRegistrationUnmodifiable
                 );
+                let previous_reward_amount = match &previous {
+                    Some(previous) => {
+                        // the dependency cannot change once registered
+                        let e: <T as Config>::RegistrationExtra = previous.extra.clone().into();
+                        let previous_requirements: JobRequirementsFor<T> = e.into();
+                        ensure!(
+                            previous_requirements.depends_on == requirements.depends_on,
+                            Error::<T>::JobRegistrationUnmodifiable
+                        );
+                        Self::total_reward_amount(&previous.into())?
+                    }
+                    None => 0u8.into(),
+                };
+                // the approval requirement is re-evaluated for the overwritten budget
+                if approver.is_some() {
+                    <StoredJobStatus<T>>::insert(&job_id.0, &job_id.1, JobStatus::PendingApproval);
+                } else if job_status == JobStatus::PendingApproval {
+                    <StoredJobStatus<T>>::insert(
+                        &job_id.0,
+                        &job_id.1,
+                        Self::approved_status(job_id, &requirements),
+                    );
+                }
+                previous_reward_amount
             } else {
-                <StoredJobStatus<T>>::insert(&job_id.0, &job_id.1, JobStatus::default());
+                let job_status = match &requirements.depends_on {
+                    Some(dependency) => {
+                        Self::add_dependent(job_id, dependency, registration, &requirements)?;
+                        JobStatus::WaitingForDependency
+                    }
+                    None => JobStatus::default(),
+                };
+                let job_status = match approver {
+                    Some(_) => JobStatus::PendingApproval,
+                    None => job_status,
+                };
+                <StoredJobStatus<T>>::insert(&job_id.0, &job_id.1, job_status);
+                0u8.into()
+            };
+
+            // the preference window runs from the job's first registration and is not extended by overwriting it
+            if requirements.preferred_sources.is_some()
+                && !<JobRegistrationTimes<T>>::contains_key(job_id)
+            {
+                <JobRegistrationTimes<T>>::insert(job_id, Self::now()?);
             }
 
             match requirements.instant_match {
                 Some(sources) => {
                     // ignore remaining rewards; do not pay out the matcher which is the same as the one registering
-                    let _ = Self::process_matching(once(&Match {
-                        job_id: job_id.clone(),
-                        sources,
-                    }))?;
+                    let remaining_rewards = Self::process_matching(
+                        once(&Match {
+                            job_id: job_id.clone(),
+                            sources,
+                        }),
+                        None,
+                        false,
+                    )?;
+                    // the matcher share of the first match is zero, so neither are later re-matches rewarded
+                    for (job_id, _) in remaining_rewards {
+                        <MatcherRewardsPaid<T>>::insert(&job_id, T::Balance::from(0u64));
+                    }
                 }
                 None => {}
             }
 
-            // - lock only after all other steps succeeded without errors because locking reward is not revertable
-            // - reward is understood per slot and execution, so calculate total_reward_amount first
-            // - lock the complete reward inclusive the matcher share and potential gap to actual fee that will be refunded during job finalization
-            T::RewardManager::lock_reward(&job_id, Self::total_reward_amount(registration)?)?;
+            // - lock only after all other steps succeeded without errors because locking reward is not revertable
+            // - lock the complete reward inclusive the matcher share and potential gap to actual fee that will be refunded during job finalization
+            // - when overwriting, only lock (or unlock) the difference to the previously locked budget
+            if reward_amount > previous_reward_amount {
+                T::RewardManager::lock_reward(&job_id, reward_amount - previous_reward_amount)?;
+            } else if reward_amount < previous_reward_amount {
+                T::RewardManager::unlock_reward(&job_id, previous_reward_amount - reward_amount)?;
+            }
+
+            // the deposit is kept when an open job gets overwritten
+            if !<JobRegistrationDeposits<T>>::contains_key(job_id) {
+                Self::lock_registration_deposit(job_id)?;
+            }
+
+            if let Some(approver) = approver {
+                Self::deposit_event(Event::RegistrationPendingApproval(job_id.clone(), approver));
+            }
 
             Ok(().into())
         }
@@ -768,12 +2068,31 @@ pub mod pallet {
                 .ok_or(Error::<T>::JobStatusNotFound)?;
             match job_status {
                 JobStatus::Open => {
-                    T::MarketplaceHooks::finalize_job(job_id, T::RewardManager::refund(job_id)?)?;
+                    T::MarketplaceHooks::finalize_job(
+                        job_id,
+                        Self::refund_open_job(job_id)?,
+                        false,
+                    )?;
+                }
+                JobStatus::WaitingForDependency | JobStatus::PendingApproval => {
+                    T::MarketplaceHooks::finalize_job(
+                        job_id,
+                        T::RewardManager::refund(job_id)?,
+                        false,
+                    )?;
+
+                    let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                        .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+                    Self::remove_dependent(job_id, &registration);
 
                     <StoredJobStatus<T>>::remove(&job_id.0, &job_id.1);
                 }
                 JobStatus::Matched => {
-                    T::MarketplaceHooks::finalize_job(job_id, T::RewardManager::refund(job_id)?)?;
+                    T::MarketplaceHooks::finalize_job(
+                        job_id,
+                        T::RewardManager::refund(job_id)?,
+                        false,
+                    )?;
 
                     // Get the job requirements
                     let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
@@ -781,7 +2100,7 @@ pub mod pallet {
 
                     // Remove matching data and increase processor capacity
                     for (p, _) in <AssignedProcessors<T>>::iter_prefix(&job_id) {
-                        <StoredMatches<T>>::remove(&p, &job_id);
+                        Self::remove_match(&p, &job_id);
                         // increase capacity
                         <StoredStorageCapacity<T>>::mutate(&p, |c| {
                             *c = c.unwrap_or(0).checked_add(registration.storage.into())
@@ -824,8 +2143,14 @@ pub mod pallet {
                                 Err(err_result) => Err(err_result.into()),
                             }?;
                         }
+                        Self::record_unfinalized_sla(
+                            &processor,
+                            job_id,
+                            &registration.schedule,
+                            &assignment,
+                        )?;
                         // Remove match
-                        <StoredMatches<T>>::remove(&processor, &job_id);
+                        Self::remove_match(&processor, &job_id);
                         // increase capacity
                         <StoredStorageCapacity<T>>::mutate(&processor, |c| {
                             *c = c.unwrap_or(0).checked_add(registration.storage.into())
@@ -833,17 +2158,34 @@ pub mod pallet {
                     }
 
                     // The job creator will only receive the amount that could not be divided between the acknowledged processors
-                    T::MarketplaceHooks::finalize_job(job_id, T::RewardManager::refund(job_id)?)?;
+                    T::MarketplaceHooks::finalize_job(
+                        job_id,
+                        T::RewardManager::refund(job_id)?,
+                        false,
+                    )?;
 
                     let _ = <AssignedProcessors<T>>::clear_prefix(
                         &job_id,
                         <T as pallet_acurast::Config>::MaxSlots::get(),
                         None,
                     );
+                    Self::clear_delivery_commitments(job_id);
                     <StoredJobStatus<T>>::remove(&job_id.0, &job_id.1);
                 }
             }
 
+            Self::release_registration_deposit(job_id)?;
+            <JobRegistrationTimes<T>>::remove(job_id);
+            <AcknowledgedSlots<T>>::remove(job_id);
+            <MatcherRewardsPaid<T>>::remove(job_id);
+            <LastMatchAttempt<T>>::remove(job_id);
+            <PendingScheduleChanges<T>>::remove(job_id);
+            <FinalizedSlotsSLA<T>>::remove(job_id);
+            <EnvironmentVersion<T>>::remove(job_id);
+
+            // a deregistered job never finalizes successfully
+            Self::resolve_dependents(job_id, false)?;
+
             Ok(().into())
         }
 
@@ -857,7 +2199,9 @@ pub mod pallet {
                 .ok_or(Error::<T>::JobStatusNotFound)?;
 
             ensure!(
-                job_status == JobStatus::Open,
+                job_status == JobStatus::Open
+                    || job_status == JobStatus::WaitingForDependency
+                    || job_status == JobStatus::PendingApproval,
                 Error::<T>::JobRegistrationUnmodifiable
             );
 
@@ -865,6 +2209,23 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config + pallet_acurast_processor_manager::Config>
+        pallet_acurast_processor_manager::ProcessorMatchesProvider<T> for Pallet<T>
+    {
+        fn has_active_matches(processor: &T::AccountId) -> bool {
+            Self::has_matches(processor)
+        }
+    }
+
+    impl<T: Config + pallet_acurast_processor_manager::Config>
+        pallet_acurast_processor_manager::ProcessorAttestationProvider<T> for Pallet<T>
+    {
+        /// A processor is attested if its attestation stored in `pallet_acurast` is neither expired nor revoked.
+        fn is_attested(processor: &T::AccountId) -> bool {
+            ensure_source_verified::<T>(processor).is_ok()
+        }
+    }
+
     impl<T: Config> JobBudget<T> for Pallet<T> {
         fn reserve(job_id: &JobId<T::AccountId>, reward: T::Balance) -> Result<(), ()> {
             <JobBudgets<T>>::mutate(job_id, |amount| {
@@ -896,295 +2257,699 @@ pub mod pallet {
         }
     }
 
+    /// Looks up the storage backed inputs of [`validate_slot`] for a source proposed in [`Pallet::process_match`].
+    struct SourceLookup<'a, T: Config> {
+        source: &'a T::AccountId,
+        required_modules: &'a JobModules,
+    }
+
+    impl<'a, T: Config> SlotCandidateLookup<T> for SourceLookup<'a, T> {
+        type Error = Error<T>;
+
+        fn source_attestation(&self) -> Option<Option<u64>> {
+            ensure_source_verified::<T>(self.source).ok()
+        }
+
+        fn modules_attested(&self) -> bool {
+            Pallet::<T>::modules_attested(self.source, self.required_modules)
+        }
+
+        fn reputation(&self) -> Option<BetaParameters<FixedU128>> {
+            <StoredReputation<T>>::get(self.source)
+        }
+
+        fn recent_fulfillment_ratio(&self) -> Option<Permill> {
+            Pallet::<T>::recent_fulfillment_ratio(self.source)
+        }
+
+        fn matched_schedules(&self) -> Result<Vec<(Schedule, u64)>, Error<T>> {
+            Pallet::<T>::matched_schedules(self.source)
+        }
+    }
+
     impl<T: Config> Pallet<T> {
         /// Checks if a Processor - Job match is possible and returns the remaining job rewards by `job_id`.
         ///
         /// If the job is no longer in status [`JobStatus::Open`], the matching is skipped without returning an error.
         /// **The returned vector does not include an entry for skipped matches.**
         ///
-        /// Every other invalidity in a provided [`Match`] fails the entire call.
+        /// Every other invalidity in a provided [`Match`] fails the entire call, unless `lenient` is set: then a match
+        /// failing with a matching error is reverted and skipped, see [`Event::MatchRejected`]. Either way a matching error
+        /// is recorded in [`LastMatchAttempt`].
+        ///
+        /// If a `matcher` is provided, the proposal is counted in its [`MatcherStats`].
         fn process_matching<'a>(
             matching: impl IntoIterator<Item = &'a MatchFor<T>>,
+            matcher: Option<&T::AccountId>,
+            lenient: bool,
         ) -> Result<Vec<(JobId<T::AccountId>, T::Balance)>, DispatchError> {
             let mut remaining_rewards: Vec<(JobId<T::AccountId>, T::Balance)> = Default::default();
+            let mut skipped: u64 = 0;
 
             for m in matching {
-                let job_status = <StoredJobStatus<T>>::get(&m.job_id.0, &m.job_id.1)
-                    .ok_or(Error::<T>::JobStatusNotFound)?;
+                let result = if lenient {
+                    // revert the partial assignments of a rejected match before continuing with the next one
+                    with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+                        match Self::process_match(m) {
+                            Ok(r) => TransactionOutcome::Commit(Ok(Ok(r))),
+                            Err(e) => TransactionOutcome::Rollback(Ok(Err(e))),
+                        }
+                    })?
+                } else {
+                    Self::process_match(m)
+                };
 
-                if job_status != JobStatus::Open {
-                    // skip but don't fail this match
-                    continue;
+                match result {
+                    Ok(Some(remaining_reward)) => remaining_rewards.push(remaining_reward),
+                    Ok(None) => skipped += 1,
+                    Err(e) => {
+                        let reason = match e.matching_reason_code() {
+                            Some(reason) => reason,
+                            None => return Err(e.into()),
+                        };
+                        Self::record_match_attempt(&m.job_id, reason);
+                        if !lenient {
+                            return Err(e.into());
+                        }
+                        skipped += 1;
+                        Self::deposit_event(Event::MatchRejected(m.job_id.clone(), reason));
+                    }
                 }
+            }
 
-                let registration = <StoredJobRegistration<T>>::get(&m.job_id.0, &m.job_id.1)
-                    .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
-                let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
-                let requirements: JobRequirementsFor<T> = e.into();
-
-                let now = Self::now()?;
-                ensure!(
-                    now < registration.schedule.start_time,
-                    Error::<T>::OverdueMatch
-                );
-                let l: u8 = m.sources.len().try_into().unwrap_or(0);
-                ensure!(
-                    // NOTE: we are checking for duplicates while inserting/mutating StoredMatches below
-                    l == requirements.slots,
-                    Error::<T>::IncorrectSourceCountInMatch
-                );
-
-                let reward_amount: <T as Config>::Balance = requirements.reward;
-
-                // keep track of total fee in assignments to check later if it exceeds reward
-                let mut total_fee: <T as Config>::Balance = 0u8.into();
+            if let Some(matcher) = matcher {
+                <MatcherStats<T>>::mutate(matcher, |stats| {
+                    stats.proposals = stats.proposals.saturating_add(1);
+                    stats.accepted_matches = stats
+                        .accepted_matches
+                        .saturating_add(remaining_rewards.len() as u64);
+                    stats.rejected_entries = stats.rejected_entries.saturating_add(skipped);
+                });
+            }
+            return Ok(remaining_rewards);
+        }
 
-                // `slot` is used for detecting duplicate source proposed for distinct slots
-                // TODO: add global (configurable) maximum of jobs assigned. This would limit the weight of `propose_matching` to a constant, since it depends on the number of active matches.
-                for (slot, planned_execution) in m.sources.iter().enumerate() {
-                    // CHECK attestation
-                    ensure!(
-                        !registration.allow_only_verified_sources
-                            || ensure_source_verified::<T>(&planned_execution.source).is_ok(),
-                        Error::<T>::UnverifiedSourceInMatch
-                    );
+        /// Checks and assigns a single match of [`Pallet::process_matching`], returning the remaining job reward.
+        ///
+        /// Returns [`None`] if the job is no longer in status [`JobStatus::Open`].
+        fn process_match(
+            m: &MatchFor<T>,
+        ) -> Result<Option<(JobId<T::AccountId>, T::Balance)>, Error<T>> {
+            let job_status = <StoredJobStatus<T>>::get(&m.job_id.0, &m.job_id.1)
+                .ok_or(Error::<T>::JobStatusNotFound)?;
 
-                    let ad = <StoredAdvertisementRestriction<T>>::get(&planned_execution.source)
-                        .ok_or(Error::<T>::AdvertisementNotFound)?;
+            if job_status != JobStatus::Open {
+                // skip but don't fail this match
+                return Ok(None);
+            }
 
-                    for required_module in &registration.required_modules {
-                        ensure!(
-                            ad.available_modules.contains(required_module),
-                            Error::<T>::ModuleNotAvailableInMatch
-                        );
-                    }
+            let registration = <StoredJobRegistration<T>>::get(&m.job_id.0, &m.job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+            let allowed_sources = <StoredJobPayload<T>>::get(&m.job_id.0, &m.job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?
+                .allowed_sources;
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
 
-                    let pricing = <StoredAdvertisementPricing<T>>::get(&planned_execution.source)
-                        .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
+            let now = Self::now()?;
+            ensure!(
+                now < registration.schedule.start_time,
+                Error::<T>::OverdueMatch
+            );
+            let l: u8 = m.sources.len().try_into().unwrap_or(0);
+            ensure!(
+                // NOTE: we are checking for duplicates while inserting/mutating StoredMatches below
+                l == requirements.slots,
+                Error::<T>::IncorrectSourceCountInMatch
+            );
 
-                    // CHECK the scheduling_window allow to schedule this job
-                    Self::check_scheduling_window(
-                        &pricing.scheduling_window,
-                        &registration.schedule,
+            // keep track of total fee in assignments to check later if it exceeds reward
+            let mut total_fee: <T as Config>::Balance = 0u8.into();
+
+            // `slot` is used for detecting duplicate source proposed for distinct slots
+            // TODO: add global (configurable) maximum of jobs assigned. This would limit the weight of `propose_matching` to a constant, since it depends on the number of active matches.
+            for (slot, planned_execution) in m.sources.iter().enumerate() {
+                let ad = <StoredAdvertisementRestriction<T>>::get(&planned_execution.source)
+                    .ok_or(Error::<T>::AdvertisementNotFound)?;
+                let pricing = <StoredAdvertisementPricing<T>>::get(&planned_execution.source)
+                    .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
+                let capacity = <StoredStorageCapacity<T>>::get(&planned_execution.source)
+                    .ok_or(Error::<T>::CapacityNotFound)?;
+
+                let SlotOutcome {
+                    fee_per_execution,
+                    execution_count,
+                    remaining_capacity,
+                } = validate_slot::<T, _>(
+                    &SlotCandidateInputs {
                         now,
-                        planned_execution.start_delay,
-                    )?;
-
-                    // CHECK memory sufficient
-                    ensure!(
-                        ad.max_memory >= registration.memory,
-                        Error::<T>::MaxMemoryExceededInMatch
-                    );
-
-                    // CHECK network request quota sufficient
-                    Self::check_network_request_quota_sufficient(
-                        &ad,
-                        &registration.schedule,
-                        registration.network_requests,
-                    )?;
-
-                    // CHECK remaining storage capacity sufficient
-                    let capacity = <StoredStorageCapacity<T>>::get(&planned_execution.source)
-                        .ok_or(Error::<T>::CapacityNotFound)?;
-                    ensure!(
-                        capacity >= registration.storage as i64,
-                        Error::<T>::InsufficientStorageCapacityInMatch
-                    );
-
-                    // CHECK source is whitelisted
-                    ensure!(
-                        is_source_whitelisted::<T>(
+                        consumer: &m.job_id.0,
+                        source: &planned_execution.source,
+                        start_delay: planned_execution.start_delay,
+                        expected_pricing_version: planned_execution.pricing_version,
+                        schedule: &registration.schedule,
+                        memory: registration.memory,
+                        network_requests: registration.network_requests,
+                        storage: registration.storage,
+                        required_modules: &registration.required_modules,
+                        allowed_sources: &allowed_sources,
+                        allow_only_verified_sources: registration.allow_only_verified_sources,
+                        requirements: &requirements,
+                        registered_at: <JobRegistrationTimes<T>>::get(&m.job_id),
+                        advertisement: &ad,
+                        pricing: &pricing,
+                        pricing_version: <StoredAdvertisementPricingVersion<T>>::get(
                             &planned_execution.source,
-                            &registration.allowed_sources
                         ),
-                        Error::<T>::SourceNotAllowedInMatch
-                    );
+                        capacity,
+                    },
+                    &SourceLookup::<T> {
+                        source: &planned_execution.source,
+                        required_modules: &registration.required_modules,
+                    },
+                )?;
 
-                    // CHECK consumer is whitelisted
-                    ensure!(
-                        is_consumer_whitelisted::<T>(&m.job_id.0, &ad.allowed_consumers),
-                        Error::<T>::ConsumerNotAllowedInMatch
-                    );
+                total_fee = total_fee
+                    .checked_add(
+                        &fee_per_execution
+                            .checked_mul(&execution_count.into())
+                            .ok_or(Error::<T>::CalculationOverflow)?,
+                    )
+                    .ok_or(Error::<T>::CalculationOverflow)?;
 
-                    // CHECK reputation sufficient
-                    Self::check_min_reputation(
-                        requirements.min_reputation,
-                        &planned_execution.source,
-                    )?;
+                // ASSIGN if not yet assigned (equals to CHECK that no duplicate source in a single mutate operation)
+                <StoredMatches<T>>::try_mutate(
+                    &planned_execution.source,
+                    &m.job_id,
+                    |s| -> Result<(), Error<T>> {
+                        // NOTE: the None case is the "good case", used when there is *no entry yet and thus no duplicate assignment so far*.
+                        match s {
+                            Some(_) => Err(Error::<T>::DuplicateSourceInMatch),
+                            None => {
+                                *s = Some(Assignment {
+                                    slot: slot as u8,
+                                    start_delay: planned_execution.start_delay,
+                                    fee_per_execution,
+                                    acknowledged: false,
+                                    sla: SLA {
+                                        total: execution_count,
+                                        met: 0,
+                                    },
+                                    pub_keys: PubKeys::default(),
+                                    holdback: 0u8.into(),
+                                    environment_ack: None,
+                                });
+                                Ok(())
+                            }
+                        }?;
+                        Ok(())
+                    },
+                )?;
+                <SourceJobIndex<T>>::try_mutate(
+                    &planned_execution.source,
+                    |jobs| -> Result<(), Error<T>> {
+                        jobs.try_push(m.job_id.clone())
+                            .map_err(|_| Error::<T>::TooManyAssignedJobsInMatch)
+                    },
+                )?;
+                <AssignedProcessors<T>>::insert(&m.job_id, &planned_execution.source, ());
+                if let Some(max_fee_per_execution) = requirements.max_fee_per_execution {
+                    Self::deposit_event(Event::MatchedWithinMaxFee(
+                        m.job_id.clone(),
+                        planned_execution.source.clone(),
+                        fee_per_execution,
+                        max_fee_per_execution,
+                    ));
+                }
+                <StoredStorageCapacity<T>>::insert(&planned_execution.source, remaining_capacity);
+            }
 
-                    // CHECK schedule
-                    Self::fits_schedule(
-                        &planned_execution.source,
-                        &registration.schedule,
-                        planned_execution.start_delay,
-                    )?;
+            // CHECK total fee is not exceeding reward
+            let total_reward_amount = Self::total_reward_amount(&registration)?;
+            let diff = total_reward_amount
+                .checked_sub(&total_fee)
+                .ok_or(Error::<T>::InsufficientRewardInMatch)?;
+            // We better check for diff positive <=> total_fee <= total_reward_amount
+            // because we cannot assume that asset amount is an unsigned integer for all future
+            ensure!(diff >= 0u32.into(), Error::<T>::InsufficientRewardInMatch);
+
+            <StoredTotalAssignedV3<T>>::mutate(|t| {
+                *t = Some(t.unwrap_or(0u128).saturating_add(1));
+            });
 
-                    // calculate fee
-                    let fee_per_execution = Self::fee_per_execution(
-                        &registration.schedule,
-                        registration.storage,
-                        &pricing,
-                    )?;
+            <StoredJobStatus<T>>::insert(&m.job_id.0, &m.job_id.1, JobStatus::Matched);
+            // the job could get open for matching again, so the preference window must still apply until it elapsed
+            if preference_window::<T>(&requirements, <JobRegistrationTimes<T>>::get(&m.job_id))
+                .map(|(_, window_end)| now >= window_end)
+                .unwrap_or(false)
+            {
+                <JobRegistrationTimes<T>>::remove(&m.job_id);
+            }
+            <LastMatchAttempt<T>>::remove(&m.job_id);
+            Self::deposit_event(Event::JobRegistrationMatched(m.clone()));
 
-                    // CHECK price not exceeding reward
-                    ensure!(
-                        fee_per_execution <= reward_amount,
-                        Error::<T>::InsufficientRewardInMatch
-                    );
+            Ok(Some((m.job_id.clone(), diff)))
+        }
 
-                    let execution_count = registration.schedule.execution_count();
+        /// Records a rejected matching attempt for `job_id` in [`LastMatchAttempt`], writing at most once per block.
+        fn record_match_attempt(job_id: &JobId<T::AccountId>, reason: MatchingReasonCode) {
+            let block = <frame_system::Pallet<T>>::block_number();
+            let attempt = <LastMatchAttempt<T>>::get(job_id);
+            if attempt.map(|a| a.block == block).unwrap_or(false) {
+                return;
+            }
+            <LastMatchAttempt<T>>::insert(
+                job_id,
+                MatchAttempt {
+                    block,
+                    reason,
+                    attempts: attempt.map(|a| a.attempts).unwrap_or(0).saturating_add(1),
+                },
+            );
+        }
 
-                    total_fee = total_fee
-                        .checked_add(
-                            &fee_per_execution
-                                .checked_mul(&execution_count.into())
-                                .ok_or(Error::<T>::CalculationOverflow)?,
-                        )
-                        .ok_or(Error::<T>::CalculationOverflow)?;
+        /// Processes the `matches` proposed by `who` and pays the matcher reward to `beneficiary`.
+        ///
+        /// Shared by [`Pallet::propose_matching`], [`Pallet::propose_matching_with_beneficiary`],
+        /// [`Pallet::propose_matching_lenient`] and [`Pallet::propose_matching_lenient_with_beneficiary`], the latter two
+        /// setting `lenient`.
+        fn do_propose_matching(
+            who: &T::AccountId,
+            matches: &BoundedVec<MatchFor<T>, <T as Config>::MaxProposedMatches>,
+            beneficiary: &T::AccountId,
+            lenient: bool,
+        ) -> DispatchResultWithPostInfo {
+            if T::RestrictedMatching::get() {
+                ensure!(
+                    <ApprovedMatchers<T>>::contains_key(who),
+                    Error::<T>::MatcherNotApproved
+                );
+            }
 
-                    // ASSIGN if not yet assigned (equals to CHECK that no duplicate source in a single mutate operation)
-                    <StoredMatches<T>>::try_mutate(
-                        &planned_execution.source,
-                        &m.job_id,
-                        |s| -> Result<(), Error<T>> {
-                            // NOTE: the None case is the "good case", used when there is *no entry yet and thus no duplicate assignment so far*.
-                            match s {
-                                Some(_) => Err(Error::<T>::DuplicateSourceInMatch),
-                                None => {
-                                    *s = Some(Assignment {
-                                        slot: slot as u8,
-                                        start_delay: planned_execution.start_delay,
-                                        fee_per_execution,
-                                        acknowledged: false,
-                                        sla: SLA {
-                                            total: execution_count,
-                                            met: 0,
-                                        },
-                                        pub_keys: PubKeys::default(),
-                                    });
-                                    Ok(())
-                                }
-                            }?;
-                            Ok(())
-                        },
-                    )?;
-                    <AssignedProcessors<T>>::insert(&m.job_id, &planned_execution.source, ());
-                    <StoredStorageCapacity<T>>::set(
-                        &planned_execution.source,
-                        capacity.checked_sub(registration.storage.into()),
-                    );
-                }
+            let remaining_rewards = Self::process_matching(matches, Some(who), lenient)?;
 
-                // CHECK total fee is not exceeding reward
-                let total_reward_amount = Self::total_reward_amount(&registration)?;
-                let diff = total_reward_amount
-                    .checked_sub(&total_fee)
-                    .ok_or(Error::<T>::InsufficientRewardInMatch)?;
-                // We better check for diff positive <=> total_fee <= total_reward_amount
-                // because we cannot assume that asset amount is an unsigned integer for all future
-                ensure!(diff >= 0u32.into(), Error::<T>::InsufficientRewardInMatch);
+            // pay part of accumulated remaining reward (unspent to consumer) to matcher
+            let matcher_rewards = Self::cap_matcher_rewards(remaining_rewards);
+            if !matcher_rewards.is_empty() {
+                T::RewardManager::pay_matcher_reward(matcher_rewards, beneficiary)?;
+            }
 
-                remaining_rewards.push((m.job_id.clone(), diff));
+            Ok(().into())
+        }
 
-                <StoredTotalAssignedV3<T>>::mutate(|t| {
-                    *t = Some(t.unwrap_or(0u128).saturating_add(1));
-                });
+        /// Ensures the matcher reward is not redirected to an account nobody controls or the pallets holding job budgets.
+        fn ensure_valid_beneficiary(beneficiary: &T::AccountId) -> Result<(), Error<T>> {
+            let zero_account = T::AccountId::decode(&mut TrailingZeroInput::zeroes())
+                .map_err(|_| Error::<T>::InvalidMatcherBeneficiary)?;
+            let pallet_account: T::AccountId =
+                <T as Config>::PalletId::get().into_account_truncating();
+            let hyperdrive_pallet_account: T::AccountId =
+                <T as Config>::HyperdrivePalletId::get().into_account_truncating();
+            ensure!(
+                *beneficiary != zero_account
+                    && *beneficiary != pallet_account
+                    && *beneficiary != hyperdrive_pallet_account,
+                Error::<T>::InvalidMatcherBeneficiary
+            );
+            Ok(())
+        }
 
-                <StoredJobStatus<T>>::insert(&m.job_id.0, &m.job_id.1, JobStatus::Matched);
-                Self::deposit_event(Event::JobRegistrationMatched(m.clone()));
-            }
-            return Ok(remaining_rewards);
+        /// Converts the `remaining_rewards` of matched jobs into the matcher rewards to pay, capping each job's total at the
+        /// matcher share of its first match and recording it in [`MatcherRewardsPaid`].
+        fn cap_matcher_rewards(
+            remaining_rewards: Vec<(JobId<T::AccountId>, T::Balance)>,
+        ) -> Vec<(JobId<T::AccountId>, T::Balance)> {
+            remaining_rewards
+                .into_iter()
+                .filter_map(|(job_id, remaining_reward)| {
+                    // the cap is the first match's share, which is exhausted by paying it
+                    if <MatcherRewardsPaid<T>>::contains_key(&job_id) {
+                        return None;
+                    }
+                    let reward = T::RewardManager::matcher_reward(remaining_reward);
+                    <MatcherRewardsPaid<T>>::insert(&job_id, reward);
+                    (reward > 0u8.into()).then_some((job_id, reward))
+                })
+                .collect()
         }
 
-        fn check_scheduling_window(
-            scheduling_window: &SchedulingWindow,
-            schedule: &Schedule,
-            now: u64,
-            start_delay: u64,
+        /// Appends a [`SLARecord`] for the given assignment to the source's [`SLAHistory`], evicting the oldest record if full.
+        ///
+        /// Recorded when the source finalizes its slot, or by [`Pallet::record_unfinalized_sla`] when the job gets closed
+        /// without the source finalizing it.
+        fn record_sla(
+            source: &T::AccountId,
+            job_id: &JobId<T::AccountId>,
+            assignment: &AssignmentFor<T>,
         ) -> Result<(), Error<T>> {
-            match scheduling_window {
-                SchedulingWindow::End(end) => {
-                    ensure!(
-                        *end >= schedule
-                            .end_time
-                            .checked_add(start_delay)
-                            .ok_or(Error::<T>::CalculationOverflow)?,
-                        Error::<T>::SchedulingWindowExceededInMatch
-                    );
+            let record = SLARecord {
+                job_id_hash: H256::from(sp_io::hashing::blake2_256(&job_id.encode())),
+                met: assignment.sla.met,
+                total: assignment.sla.total,
+                fee_per_execution: assignment.fee_per_execution,
+                finalized_at: Self::now()?,
+            };
+            <SLAHistory<T>>::mutate(source, |history| {
+                if history.is_full() && !history.is_empty() {
+                    history.remove(0);
                 }
-                SchedulingWindow::Delta(delta) => {
-                    ensure!(
-                        now.checked_add(*delta)
-                            .ok_or(Error::<T>::CalculationOverflow)?
-                            >= schedule
-                                .end_time
-                                .checked_add(start_delay)
-                                .ok_or(Error::<T>::CalculationOverflow)?,
-                        Error::<T>::SchedulingWindowExceededInMatch
-                    );
-                }
-            }
-
+                // only fails if `MaxSLAHistory` is zero, in which case no history is kept
+                let _ = history.try_push(record);
+            });
             Ok(())
         }
 
-        fn check_network_request_quota_sufficient(
-            ad: &AdvertisementRestriction<T::AccountId, T::MaxAllowedConsumers>,
+        /// Records the SLA of a slot closed without its source finalizing it, see [`Pallet::record_sla`].
+        ///
+        /// Only executions started before now count towards the total, so a consumer closing the job early does not charge
+        /// the source with executions it never got the chance to perform. Slots never acknowledged are not recorded.
+        fn record_unfinalized_sla(
+            source: &T::AccountId,
+            job_id: &JobId<T::AccountId>,
             schedule: &Schedule,
-            network_requests: u32,
+            assignment: &AssignmentFor<T>,
         ) -> Result<(), Error<T>> {
-            // CHECK network request quota sufficient
-            ensure!(
-                // duration (s) * network_request_quota >= network_requests (per second)
-                // <=>
-                // duration (ms) / 1000 * network_request_quota >= network_requests (per second)
-                // <=>
-                // duration (ms) * network_request_quota >= network_requests (per second) * 1000
-                schedule
-                    .duration
-                    .checked_mul(ad.network_request_quota.into())
-                    .unwrap_or(0u64)
-                    >= network_requests
-                        .saturated_into::<u64>()
-                        .checked_mul(1000u64)
-                        .unwrap_or(u64::MAX),
-                Error::<T>::NetworkRequestQuotaExceededInMatch
-            );
+            if !assignment.acknowledged {
+                return Ok(());
+            }
+            let started =
+                Self::executions_started_before(schedule, assignment.start_delay, Self::now()?)?;
+            let mut assignment = assignment.clone();
+            assignment.sla.total = started.min(assignment.sla.total).max(assignment.sla.met);
+            Self::record_sla(source, job_id, &assignment)
+        }
+
+        /// Pays `amount` from the job's budget to the source's manager, escrowing it if the manager cannot be resolved or paying fails.
+        fn pay_source(
+            source: &T::AccountId,
+            job_id: &JobId<T::AccountId>,
+            amount: T::Balance,
+        ) -> Result<(), DispatchError> {
+            match T::ManagerProvider::manager_of(source) {
+                Ok(manager) => {
+                    // a failed payment is rolled back entirely before escrowing the amount instead
+                    let paid = with_storage_layer(|| {
+                        T::RewardManager::pay_reward(job_id, amount, &manager)
+                    });
+                    if let Err(e) = paid {
+                        log::warn!(
+                            target: "runtime::acurast_marketplace",
+                            "paying source {:?} for job {:?} failed with {:?}, escrowing payout",
+                            source,
+                            job_id,
+                            e
+                        );
+                        Self::escrow_payout(source, job_id, amount)?;
+                    }
+                    Ok(())
+                }
+                // the manager cannot be resolved (e.g. the pairing was removed mid-job), escrow the payment
+                // instead of failing so the processor does not lose the execution
+                Err(_) => Self::escrow_payout(source, job_id, amount),
+            }
+        }
+
+        /// Splits an execution's fee into the part paid out immediately and the part held back until finalization.
+        ///
+        /// Only jobs specifying [`JobRequirements::holdback_release_sla`] hold back fees.
+        fn split_execution_fee(
+            requirements: &JobRequirementsFor<T>,
+            fee_per_execution: T::Balance,
+        ) -> (T::Balance, T::Balance) {
+            if requirements.holdback_release_sla.is_none() {
+                return (fee_per_execution, 0u8.into());
+            }
+            let immediate = T::ImmediatePayoutRatio::get().mul_floor(fee_per_execution);
+            (immediate, fee_per_execution - immediate)
+        }
+
+        /// Releases the held back fees of a finalized assignment to the source if its SLA meets the job's
+        /// [`JobRequirements::holdback_release_sla`] or refunds them to the consumer otherwise.
+        fn settle_holdback(
+            source: &T::AccountId,
+            job_id: &JobId<T::AccountId>,
+            registration: &JobRegistrationCoreFor<T>,
+            assignment: &AssignmentFor<T>,
+        ) -> Result<(), DispatchError> {
+            if assignment.holdback == 0u8.into() {
+                return Ok(());
+            }
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+            let threshold = permill_from_parts(requirements.holdback_release_sla.unwrap_or(0));
+
+            if Permill::from_rational(assignment.sla.met, assignment.sla.total) >= threshold {
+                Self::pay_source(source, job_id, assignment.holdback)?;
+                Self::deposit_event(Event::HoldbackReleased(
+                    job_id.clone(),
+                    source.clone(),
+                    assignment.holdback,
+                ));
+            } else {
+                T::RewardManager::unlock_reward(job_id, assignment.holdback)?;
+                Self::deposit_event(Event::HoldbackForfeited(
+                    job_id.clone(),
+                    source.clone(),
+                    assignment.holdback,
+                ));
+            }
             Ok(())
         }
 
-        fn check_min_reputation(
-            min_reputation: Option<u128>,
+        /// Moves `amount` out of the job's budget into the source's [`PendingPayouts`], accumulating with earlier escrowed payouts for the same job.
+        fn escrow_payout(
             source: &T::AccountId,
-        ) -> Result<(), Error<T>> {
-            if let Some(min_reputation) = min_reputation {
-                let beta_params =
-                    <StoredReputation<T>>::get(source).ok_or(Error::<T>::ReputationNotFound)?;
+            job_id: &JobId<T::AccountId>,
+            amount: T::Balance,
+        ) -> Result<(), DispatchError> {
+            T::RewardManager::escrow_reward(job_id, amount)?;
 
-                let reputation = BetaReputation::<u128>::normalize(beta_params)
+            let now = Self::now()?;
+            <PendingPayouts<T>>::try_mutate(source, job_id, |p| -> Result<(), Error<T>> {
+                let pending = p.get_or_insert(PendingPayout {
+                    amount: 0u8.into(),
+                    escrowed_at: now,
+                });
+                pending.amount = pending
+                    .amount
+                    .checked_add(&amount)
                     .ok_or(Error::<T>::CalculationOverflow)?;
+                Ok(())
+            })?;
 
-                ensure!(
-                    reputation >= Permill::from_parts(min_reputation as u32),
-                    Error::<T>::InsufficientReputationInMatch
+            Self::deposit_event(Event::PayoutPending(job_id.clone(), source.clone(), amount));
+            Ok(())
+        }
+
+        /// Returns the ratio of met over total executions in the source's [`SLAHistory`] or [`None`] if there is no history.
+        pub fn recent_fulfillment_ratio(source: &T::AccountId) -> Option<Permill> {
+            let (met, total) =
+                <SLAHistory<T>>::get(source)
+                    .iter()
+                    .fold((0u64, 0u64), |(met, total), record| {
+                        (
+                            met.saturating_add(record.met),
+                            total.saturating_add(record.total),
+                        )
+                    });
+            if total == 0 {
+                return None;
+            }
+            Some(Permill::from_rational(met, total))
+        }
+
+        /// Returns the recent SLA history of a source with the aggregated fulfillment ratio.
+        ///
+        /// Intended to be called for providing runtime API.
+        pub fn sla_summary(source: T::AccountId) -> Result<SLASummaryFor<T>, RuntimeApiError> {
+            Ok(SLASummary {
+                records: <SLAHistory<T>>::get(&source).into_inner(),
+                fulfillment_ratio: Self::recent_fulfillment_ratio(&source)
+                    .map(|ratio| ratio.deconstruct().into()),
+            })
+        }
+
+        /// Returns the slots of a job not acknowledged yet, in ascending order.
+        ///
+        /// All slots are unacknowledged before the job got matched.
+        ///
+        /// Intended to be called for providing runtime API, might return corresponding error.
+        pub fn unacknowledged_slots(
+            job_id: JobId<T::AccountId>,
+        ) -> Result<Vec<u8>, RuntimeApiError> {
+            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(RuntimeApiError::UnacknowledgedSlots)?;
+            let extra: <T as Config>::RegistrationExtra = registration.extra.into();
+            let requirements: JobRequirementsFor<T> = extra.into();
+            let acknowledged = <AcknowledgedSlots<T>>::get(&job_id);
+            Ok((0..requirements.slots)
+                .filter(|slot| acknowledged.binary_search(slot).is_err())
+                .collect())
+        }
+
+        /// Returns the most recent rejected matching attempt of a job from [`LastMatchAttempt`], with the block number
+        /// converted to `u64`.
+        ///
+        /// Intended to be called for providing runtime API, might return corresponding error.
+        pub fn last_match_attempt_info(
+            job_id: JobId<T::AccountId>,
+        ) -> Result<Option<MatchAttempt<u64>>, RuntimeApiError> {
+            Ok(
+                <LastMatchAttempt<T>>::get(&job_id).map(|attempt| MatchAttempt {
+                    block: attempt.block.unique_saturated_into(),
+                    reason: attempt.reason,
+                    attempts: attempt.attempts,
+                }),
+            )
+        }
+
+        /// Returns up to `limit` jobs in status [`JobStatus::Open`] that are not overdue yet, with their start time, reward,
+        /// slots and an estimate of the matcher margin.
+        ///
+        /// Stops reading storage as soon as `limit` jobs are collected. Jobs whose margin cannot be estimated, e.g. because
+        /// of inconsistent storage, are logged and skipped.
+        ///
+        /// The [`MatchableJob::margin_estimate`] is the total reward minus the fees of the cheapest eligible sources among
+        /// the first [`Config::MarginEstimateSampleSize`] sources in [`AdvertisementIndex`]. It is imprecise by design:
+        /// cheaper sources outside the sample are not considered, and a sampled source passing the checks for a start
+        /// delay of `0` might still get rejected for the start delay of an actual match.
+        ///
+        /// Intended to be called for providing runtime API, might return corresponding error.
+        pub fn matchable_jobs(limit: u32) -> Result<Vec<MatchableJobFor<T>>, RuntimeApiError> {
+            let now = Self::now().map_err(|e| RuntimeApiError::MatchableJobs.log_error(e))?;
+
+            let jobs = <StoredJobStatus<T>>::iter()
+                .filter(|(_, _, status)| *status == JobStatus::Open)
+                .filter_map(|(origin, job_id_seq, _)| {
+                    let job_id = (origin, job_id_seq);
+                    let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)?;
+                    if registration.schedule.start_time <= now {
+                        return None;
+                    }
+                    let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+                    let requirements: JobRequirementsFor<T> = e.into();
+
+                    // a single inconsistent job must not hide all other jobs from the matchers
+                    let margin_estimate =
+                        match Self::margin_estimate(&job_id, &registration, &requirements) {
+                            Ok(margin_estimate) => margin_estimate,
+                            Err(e) => {
+                                log::warn!(
+                                    target: "runtime::acurast_marketplace",
+                                    "skipping job {:?} in matchable jobs, estimating margin failed with {:?}",
+                                    job_id,
+                                    e
+                                );
+                                return None;
+                            }
+                        };
+
+                    Some(MatchableJob {
+                        margin_estimate,
+                        job_id,
+                        start_time: registration.schedule.start_time,
+                        reward: requirements.reward,
+                        slots: requirements.slots,
+                    })
+                })
+                .take(limit as usize)
+                .collect();
+            Ok(jobs)
+        }
+
+        /// Estimates the remaining reward of matching `job_id` with the cheapest eligible sources in a bounded sample of
+        /// [`AdvertisementIndex`], see [`Pallet::matchable_jobs`].
+        ///
+        /// Returns [`None`] if the sample contains fewer eligible sources than the job has slots.
+        fn margin_estimate(
+            job_id: &JobId<T::AccountId>,
+            registration: &JobRegistrationCoreFor<T>,
+            requirements: &JobRequirementsFor<T>,
+        ) -> Result<Option<T::Balance>, Error<T>> {
+            let partial_registration =
+                Self::partial_registration_of(job_id, registration, requirements)?;
+            let preference =
+                preference_window::<T>(requirements, <JobRegistrationTimes<T>>::get(job_id));
+
+            let mut fees = Vec::new();
+            for source in <AdvertisementIndex<T>>::iter_keys()
+                .take(<T as Config>::MarginEstimateSampleSize::get() as usize)
+            {
+                if Self::check(&partial_registration, &source, Some(&job_id.0), preference).is_err()
+                {
+                    continue;
+                }
+                let pricing = <StoredAdvertisementPricing<T>>::get(&source)
+                    .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
+                fees.push(
+                    fee_per_execution::<T>(&registration.schedule, registration.storage, &pricing)
+                        .map_err(Error::<T>::from)?,
                 );
             }
-            Ok(())
+            if fees.len() < requirements.slots as usize {
+                return Ok(None);
+            }
+            fees.sort();
+
+            let execution_count: T::Balance = registration.schedule.execution_count().into();
+            let mut margin = Self::total_reward_amount(registration)?;
+            for fee in fees.into_iter().take(requirements.slots as usize) {
+                margin = margin
+                    .checked_sub(
+                        &fee.checked_mul(&execution_count)
+                            .ok_or(Error::<T>::CalculationOverflow)?,
+                    )
+                    .ok_or(Error::<T>::CalculationOverflow)?;
+            }
+            Ok(Some(margin))
+        }
+
+        /// Returns the registration of a stored job as [`PartialJobRegistration`] to be evaluated with [`Pallet::check`].
+        pub(crate) fn partial_registration_of(
+            job_id: &JobId<T::AccountId>,
+            registration: &JobRegistrationCoreFor<T>,
+            requirements: &JobRequirementsFor<T>,
+        ) -> Result<PartialJobRegistrationForMarketplace<T>, Error<T>> {
+            Ok(PartialJobRegistration {
+                allowed_sources: <StoredJobPayload<T>>::get(&job_id.0, &job_id.1)
+                    .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?
+                    .allowed_sources,
+                allow_only_verified_sources: registration.allow_only_verified_sources,
+                schedule: Some(registration.schedule.clone()),
+                memory: Some(registration.memory),
+                network_requests: Some(registration.network_requests),
+                storage: Some(registration.storage),
+                required_modules: registration.required_modules.clone(),
+                slots: Some(requirements.slots),
+                reward: requirements.reward,
+                min_reputation: requirements.min_reputation,
+                min_recent_fulfillment_ratio: requirements.min_recent_fulfillment_ratio,
+                max_attestation_age_ms: requirements.max_attestation_age_ms,
+                max_fee_per_execution: requirements.max_fee_per_execution,
+            })
         }
 
         /// Filters the given `sources` by those recently seen and matching partially specified `registration`
         /// and whitelisting `consumer` if specifying a whitelist.
         ///
+        /// The matching sources are returned with their current pricing version to be used in [`PlannedExecution::pricing_version`].
+        ///
         /// Intended to be called for providing runtime API, might return corresponding error.
+        /// Fails if `registration` specifies allowed sources violating [`pallet_acurast::Pallet::ensure_valid_allowed_sources`].
         pub fn filter_matching_sources(
             registration: PartialJobRegistration<T::Balance, T::AccountId, T::MaxAllowedSources>,
             sources: Vec<T::AccountId>,
             consumer: Option<MultiOrigin<T::AccountId>>,
             latest_seen_after: Option<u128>,
-        ) -> Result<Vec<T::AccountId>, RuntimeApiError> {
+        ) -> Result<Vec<(T::AccountId, u32)>, RuntimeApiError> {
+            if let Some(allowed_sources) = &registration.allowed_sources {
+                pallet_acurast::Pallet::<T>::ensure_valid_allowed_sources(allowed_sources)
+                    .map_err(|e| RuntimeApiError::FilterMatchingSources.log_debug(e))?;
+            }
+
             let mut candidates = Vec::new();
             for p in sources {
-                let valid_match = match Self::check(&registration, &p, consumer.as_ref()) {
+                let valid_match = match Self::check(&registration, &p, consumer.as_ref(), None) {
                     Ok(()) => {
                         if let Some(latest_seen_after) = latest_seen_after {
                             T::ProcessorLastSeenProvider::last_seen(&p)
@@ -1204,64 +2969,71 @@ pub mod pallet {
                 };
 
                 if valid_match {
-                    candidates.push(p);
+                    let pricing_version = <StoredAdvertisementPricingVersion<T>>::get(&p);
+                    candidates.push((p, pricing_version));
                 }
             }
             Ok(candidates)
         }
 
-        fn check(
+        /// Checks if `source` could be matched with a partial `registration`.
+        ///
+        /// Follows the cheapest-first order of [`validate_slot`], additionally deferring storage reads until needed.
+        ///
+        /// `preference` holds the preferred sources and the end of the preference window of a stored job, see
+        /// [`preference_window`].
+        pub(crate) fn check(
             registration: &PartialJobRegistrationForMarketplace<T>,
             source: &T::AccountId,
             consumer: Option<&MultiOrigin<T::AccountId>>,
+            preference: Option<(&PreferredSources<T::AccountId>, u64)>,
         ) -> Result<(), Error<T>> {
-            // CHECK attestation
+            Self::check_ordered(registration, source, consumer, preference).map_err(|e| {
+                log::debug!(
+                    target: MATCHING_LOG_TARGET,
+                    "source rejected by check {:?}",
+                    e,
+                );
+                e
+            })
+        }
+
+        fn check_ordered(
+            registration: &PartialJobRegistrationForMarketplace<T>,
+            source: &T::AccountId,
+            consumer: Option<&MultiOrigin<T::AccountId>>,
+            preference: Option<(&PreferredSources<T::AccountId>, u64)>,
+        ) -> Result<(), Error<T>> {
+            // CHECK source is whitelisted
             ensure!(
-                !registration.allow_only_verified_sources
-                    || ensure_source_verified::<T>(&source).is_ok(),
-                Error::<T>::UnverifiedSourceInMatch
+                is_source_whitelisted::<T>(&source, &registration.allowed_sources),
+                Error::<T>::SourceNotAllowedInMatch
             );
 
+            // CHECK source is preferred as long as the preference window did not elapse
+            if preference.is_some() {
+                check_preference_window(preference, source, Self::now()?)?;
+            }
+
             let ad = <StoredAdvertisementRestriction<T>>::get(&source)
                 .ok_or(Error::<T>::AdvertisementNotFound)?;
 
-            for required_module in &registration.required_modules {
+            // CHECK consumer is whitelisted
+            if let Some(consumer) = consumer {
                 ensure!(
-                    ad.available_modules.contains(required_module),
-                    Error::<T>::ModuleNotAvailableInMatch
+                    is_consumer_whitelisted::<T>(&consumer, &ad.allowed_consumers),
+                    Error::<T>::ConsumerNotAllowedInMatch
                 );
             }
 
-            let pricing = <StoredAdvertisementPricing<T>>::get(&source)
-                .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
-
-            if let Some(schedule) = &registration.schedule {
-                let now = Self::now()?;
-                ensure!(now < schedule.start_time, Error::<T>::OverdueMatch);
-
-                // CHECK the scheduling_window allow to schedule this job
-                Self::check_scheduling_window(&pricing.scheduling_window, schedule, now, 0)?;
-
-                // CHECK schedule
-                Self::fits_schedule(&source, &schedule, 0)?;
-
-                // CHECK network request quota sufficient
-                if let Some(network_requests) = registration.network_requests {
-                    Self::check_network_request_quota_sufficient(&ad, &schedule, network_requests)?;
-                }
-
-                // CHECK remaining storage capacity sufficient
-                if let Some(storage) = &registration.storage {
-                    // calculate fee
-                    let fee_per_execution = Self::fee_per_execution(&schedule, *storage, &pricing)?;
-
-                    // CHECK price not exceeding reward
-                    ensure!(
-                        fee_per_execution <= registration.reward.clone(),
-                        Error::<T>::InsufficientRewardInMatch
-                    );
-                }
-            }
+            // CHECK attestation
+            let mut attestation = None;
+            let mut source_attestation =
+                || *attestation.get_or_insert_with(|| ensure_source_verified::<T>(&source).ok());
+            ensure!(
+                !registration.allow_only_verified_sources || source_attestation().is_some(),
+                Error::<T>::UnverifiedSourceInMatch
+            );
 
             // CHECK memory sufficient
             if let Some(memory) = &registration.memory {
@@ -1281,80 +3053,270 @@ pub mod pallet {
                 );
             }
 
-            // CHECK source is whitelisted
-            ensure!(
-                is_source_whitelisted::<T>(&source, &registration.allowed_sources),
-                Error::<T>::SourceNotAllowedInMatch
-            );
+            // CHECK network request quota sufficient
+            if let (Some(schedule), Some(network_requests)) =
+                (&registration.schedule, registration.network_requests)
+            {
+                check_network_request_quota_sufficient::<T>(&ad, &schedule, network_requests)?;
+            }
 
-            // CHECK consumer is whitelisted
-            if let Some(consumer) = consumer {
+            for required_module in &registration.required_modules {
                 ensure!(
-                    is_consumer_whitelisted::<T>(&consumer, &ad.allowed_consumers),
-                    Error::<T>::ConsumerNotAllowedInMatch
+                    ad.available_modules.contains(required_module),
+                    Error::<T>::ModuleNotAvailableInMatch
                 );
             }
+            ensure!(
+                Self::modules_attested(&source, &registration.required_modules),
+                Error::<T>::ModuleNotAttested
+            );
+
+            // CHECK attestation recent enough
+            check_attestation_age(
+                registration.max_attestation_age_ms,
+                || source_attestation().flatten(),
+                Self::now()?,
+            )?;
+
+            let pricing = <StoredAdvertisementPricing<T>>::get(&source)
+                .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
+
+            if let Some(schedule) = &registration.schedule {
+                let now = Self::now()?;
+                ensure!(now < schedule.start_time, Error::<T>::OverdueMatch);
+
+                // CHECK the scheduling_window allow to schedule this job
+                check_scheduling_window(&pricing.scheduling_window, schedule, now, 0)?;
+            }
+
+            // CHECK recent fulfillment ratio sufficient
+            check_min_recent_fulfillment_ratio(registration.min_recent_fulfillment_ratio, || {
+                Self::recent_fulfillment_ratio(&source)
+            })?;
 
             // CHECK reputation sufficient
-            Self::check_min_reputation(registration.min_reputation, &source)?;
+            check_min_reputation(registration.min_reputation, || {
+                <StoredReputation<T>>::get(&source)
+            })?;
+
+            if let Some(schedule) = &registration.schedule {
+                if let Some(storage) = &registration.storage {
+                    // calculate fee
+                    let fee_per_execution = fee_per_execution::<T>(&schedule, *storage, &pricing)?;
+
+                    // CHECK price not exceeding reward
+                    ensure!(
+                        fee_per_execution <= registration.reward.clone(),
+                        Error::<T>::InsufficientRewardInMatch
+                    );
+
+                    // CHECK price not exceeding consumer's maximum
+                    if let Some(max_fee_per_execution) = &registration.max_fee_per_execution {
+                        ensure!(
+                            &fee_per_execution <= max_fee_per_execution,
+                            Error::<T>::FeeAboveConsumerMaximum
+                        );
+                    }
+                }
+
+                // CHECK schedule last since it iterates all matches of the source
+                fits_schedule(&schedule, 0, &Self::matched_schedules(&source)?)?;
+            }
 
             Ok(())
         }
 
         /// Returns true if the source has currently at least one match (not necessarily assigned).
         fn has_matches(source: &T::AccountId) -> bool {
-            // NOTE we use a trick to check if map contains *any* secondary key: we use `any` to short-circuit
-            // whenever we encounter the first - so at least one - element in the iterator.
-            <StoredMatches<T>>::iter_prefix_values(&source).any(|_| true)
+            <SourceJobIndex<T>>::contains_key(source)
         }
 
-        /// Checks of a new job schedule fits with the existing schedule for a processor.
-        fn fits_schedule(
+        /// Removes the match of a source with a job from [`StoredMatches`] and [`SourceJobIndex`], returning the removed assignment.
+        fn remove_match(
             source: &T::AccountId,
-            schedule: &Schedule,
-            start_delay: u64,
-        ) -> Result<(), Error<T>> {
-            for (job_id, assignment) in <StoredMatches<T>>::iter_prefix(&source) {
-                // TODO decide tradeoff: we could save this lookup at the cost of storing the schedule along with the match or even completly move it from StoredJobRegistration into StoredMatches
-                let other = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
-                    .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+            job_id: &JobId<T::AccountId>,
+        ) -> Option<AssignmentFor<T>> {
+            <SourceJobIndex<T>>::mutate_exists(source, |jobs| {
+                if let Some(j) = jobs {
+                    j.retain(|other| other != job_id);
+                    if j.is_empty() {
+                        *jobs = None;
+                    }
+                }
+            });
+            <StoredMatches<T>>::take(source, job_id)
+        }
 
-                // check if the whole schedule periods have an overlap
-                if schedule.start_time >= other.schedule.end_time
-                    || schedule.end_time <= other.schedule.start_time
-                {
-                    // periods don't overlap
+        /// Returns the jobs matched with a source together with their assignments, in the order they got matched.
+        fn ordered_matches(
+            source: &T::AccountId,
+        ) -> impl Iterator<Item = (JobId<T::AccountId>, AssignmentFor<T>)> + '_ {
+            <SourceJobIndex<T>>::get(source)
+                .into_iter()
+                .filter_map(move |job_id| {
+                    let assignment = <StoredMatches<T>>::get(source, &job_id)?;
+                    Some((job_id, assignment))
+                })
+        }
+
+        /// Returns true if the source's attestation backs all of `modules` that [`Config::ModuleAttestationCheck`]
+        /// requires an attestation for.
+        ///
+        /// The attestation is only read if at least one such module is required.
+        fn modules_attested(source: &T::AccountId, modules: &JobModules) -> bool {
+            let mut attestation = None;
+            for module in modules {
+                if !T::ModuleAttestationCheck::requires_attestation(module) {
                     continue;
                 }
+                let attestation = attestation
+                    .get_or_insert_with(|| <pallet_acurast::StoredAttestation<T>>::get(source));
+                match attestation {
+                    Some(attestation)
+                        if T::ModuleAttestationCheck::is_attested(module, attestation) => {}
+                    _ => return false,
+                }
+            }
+            true
+        }
 
-                let it = schedule
-                    .iter(start_delay)
-                    .ok_or(Error::<T>::CalculationOverflow)?
-                    .map(|start| {
-                        let end = start.checked_add(schedule.duration)?;
-                        Some((start, end))
-                    });
-                let other_it = other
-                    .schedule
-                    .iter(assignment.start_delay)
-                    .ok_or(Error::<T>::CalculationOverflow)?
-                    .map(|start| {
-                        let end = start.checked_add(other.schedule.duration)?;
-                        Some((start, end))
-                    });
+        /// Checks the invariants of a job's schedule, as required on registration and for schedule changes.
+        fn ensure_valid_schedule(schedule: &Schedule) -> Result<(), Error<T>> {
+            ensure!(
+                schedule.duration > 0,
+                Error::<T>::JobRegistrationZeroDuration
+            );
+            let execution_count = schedule.execution_count();
+            ensure!(
+                execution_count <= T::MaxExecutionsPerJob::get(),
+                Error::<T>::JobRegistrationScheduleExceedsMaximumExecutions
+            );
+            ensure!(
+                execution_count > 0,
+                Error::<T>::JobRegistrationScheduleContainsZeroExecutions
+            );
+            ensure!(
+                schedule.duration < schedule.interval,
+                Error::<T>::JobRegistrationDurationExceedsInterval
+            );
+            ensure!(
+                schedule.start_time >= Self::now()?,
+                Error::<T>::JobRegistrationStartInPast
+            );
+            ensure!(
+                schedule.start_time <= schedule.end_time,
+                Error::<T>::JobRegistrationEndBeforeStart
+            );
+            Ok(())
+        }
+
+        /// Returns true if the [`Config::ScheduleChangeTimeout`] passed since the schedule change was proposed.
+        fn schedule_change_expired(
+            proposal: &ScheduleChangeProposalFor<T>,
+            now: u64,
+        ) -> Result<bool, Error<T>> {
+            let expires_at = proposal
+                .proposed_at
+                .checked_add(T::ScheduleChangeTimeout::get())
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            Ok(now >= expires_at)
+        }
 
-                it.merge(other_it).try_fold(0u64, |prev_end, bounds| {
-                    let (start, end) = bounds.ok_or(Error::<T>::CalculationOverflow)?;
+        /// Returns the number of executions of `schedule` started before `now`, considering the `start_delay` of an assignment.
+        fn executions_started_before(
+            schedule: &Schedule,
+            start_delay: u64,
+            now: u64,
+        ) -> Result<u64, Error<T>> {
+            let first_start = schedule
+                .start_time
+                .checked_add(start_delay)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            if now <= first_start {
+                return Ok(0);
+            }
+            let started = (now - first_start - 1)
+                .checked_div(schedule.interval)
+                .ok_or(Error::<T>::UnexpectedCheckedCalculation)?
+                .saturating_add(1);
+            Ok(started.min(schedule.execution_count()))
+        }
 
-                    if prev_end > start {
-                        Err(Error::<T>::ScheduleOverlapInMatch)
-                    } else {
-                        Ok(end)
-                    }
+        /// Replaces the schedule of an assigned job, adjusting the SLA totals of its assignments and its locked budget.
+        ///
+        /// Executions started before `now` are kept in each assignment's [`SLA::total`], the remaining executions of
+        /// the previous schedule get replaced by the executions of the new `schedule`.
+        fn apply_schedule_change(
+            job_id: &JobId<T::AccountId>,
+            schedule: Schedule,
+            now: u64,
+        ) -> DispatchResult {
+            let mut registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+
+            let mut previous_total: u64 = 0;
+            let mut total: u64 = 0;
+            for source in <AssignedProcessors<T>>::iter_key_prefix(job_id) {
+                <StoredMatches<T>>::try_mutate(&source, job_id, |assignment| -> DispatchResult {
+                    let assignment = assignment.as_mut().ok_or(Error::<T>::JobNotAssigned)?;
+                    let started = Self::executions_started_before(
+                        &registration.schedule,
+                        assignment.start_delay,
+                        now,
+                    )?;
+                    let sla_total = started
+                        .checked_add(schedule.execution_count())
+                        .ok_or(Error::<T>::CalculationOverflow)?
+                        .max(assignment.sla.met);
+                    previous_total = previous_total.saturating_add(assignment.sla.total);
+                    total = total.saturating_add(sla_total);
+                    assignment.sla.total = sla_total;
+                    Ok(())
                 })?;
             }
 
-            Ok(().into())
+            if total > previous_total {
+                let amount = requirements
+                    .reward
+                    .checked_mul(&(total - previous_total).into())
+                    .ok_or(Error::<T>::CalculationOverflow)?;
+                T::RewardManager::lock_reward(job_id, amount)?;
+            } else if total < previous_total {
+                let amount = requirements
+                    .reward
+                    .checked_mul(&(previous_total - total).into())
+                    .ok_or(Error::<T>::CalculationOverflow)?;
+                T::RewardManager::unlock_reward(job_id, amount)?;
+            }
+
+            registration.schedule = schedule.clone();
+            <StoredJobRegistration<T>>::insert(&job_id.0, &job_id.1, registration);
+
+            Self::deposit_event(Event::ScheduleChangeApplied(job_id.clone(), schedule));
+            Ok(())
+        }
+
+        /// Returns the schedules and start delays of all jobs currently matched with a source.
+        fn matched_schedules(source: &T::AccountId) -> Result<Vec<(Schedule, u64)>, Error<T>> {
+            Self::matched_schedules_except(source, None)
+        }
+
+        /// Returns the schedules and start delays of all jobs currently matched with a source, except the `excluded` job.
+        fn matched_schedules_except(
+            source: &T::AccountId,
+            excluded: Option<&JobId<T::AccountId>>,
+        ) -> Result<Vec<(Schedule, u64)>, Error<T>> {
+            Self::ordered_matches(source)
+                .filter(|(job_id, _)| Some(job_id) != excluded)
+                .map(|(job_id, assignment)| {
+                    // TODO decide tradeoff: we could save this lookup at the cost of storing the schedule along with the match or even completly move it from StoredJobRegistration into StoredMatches
+                    let other = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                        .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+                    Ok((other.schedule, assignment.start_delay))
+                })
+                .collect()
         }
 
         /// Calculates if the job ended considering the given assignment.
@@ -1371,52 +3333,133 @@ pub mod pallet {
             Ok(actual_end.lt(&now))
         }
 
-        /// Calculates if the job ended considering the given assignment.
-        fn schedule_ended(schedule: &Schedule) -> Result<bool, Error<T>> {
-            let now = Self::now()?
-                .checked_add(T::ReportTolerance::get())
-                .ok_or(Error::<T>::CalculationOverflow)?;
-            let (_actual_start, actual_end) = schedule
-                .range(schedule.max_start_delay)
-                .ok_or(Error::<T>::CalculationOverflow)?;
-            Ok(actual_end.lt(&now))
-        }
+        /// Calculates if the job ended considering the given assignment.
+        fn schedule_ended(schedule: &Schedule) -> Result<bool, Error<T>> {
+            let now = Self::now()?
+                .checked_add(T::ReportTolerance::get())
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            let (_actual_start, actual_end) = schedule
+                .range(schedule.max_start_delay)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            Ok(actual_end.lt(&now))
+        }
+
+        /// Calculates the total reward amount.
+        fn total_reward_amount(
+            registration: &JobRegistrationCoreFor<T>,
+        ) -> Result<T::Balance, Error<T>> {
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+
+            Ok(requirements
+                .reward
+                .checked_mul(&((requirements.slots as u128).into()))
+                .ok_or(Error::<T>::CalculationOverflow)?
+                .checked_mul(&registration.schedule.execution_count().into())
+                .ok_or(Error::<T>::CalculationOverflow)?)
+        }
+
+        /// Finalizes the slot of `who` in the assigned job, settling its reputation, SLA and held back fees.
+        ///
+        /// Shared by [`Pallet::finalize_job`] and [`Pallet::finalize_assigned_jobs`].
+        fn do_finalize_job(who: &T::AccountId, job_id: &JobId<T::AccountId>) -> DispatchResult {
+            let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
+                .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
+
+            // find assignment
+            let assignment =
+                <StoredMatches<T>>::get(who, job_id).ok_or(Error::<T>::JobNotAssigned)?;
+
+            ensure!(
+                Self::actual_schedule_ended(&registration.schedule, &assignment)?,
+                Error::<T>::JobCannotBeFinalized
+            );
+
+            // executions with a pending or upheld delivery challenge count as unmet
+            let disputed: u64 = <DisputedExecutions<T>>::take(job_id, who).into();
+            let met = assignment.sla.met.saturating_sub(disputed);
+            let unmet: u64 = assignment.sla.total - met;
+
+            // update reputation since we don't expect further reports for this job
+            // (only update for attested devices!)
+            if ensure_source_verified::<T>(who).is_ok() {
+                // skip reputation update if reward is 0
+                if assignment.fee_per_execution > 0u8.into() {
+                    let average_reward = <StoredAverageRewardV3<T>>::get().unwrap_or(0);
+                    let total_assigned = <StoredTotalAssignedV3<T>>::get().unwrap_or_default();
+
+                    let total_reward = average_reward
+                        .checked_mul(total_assigned - 1u128)
+                        .ok_or(Error::<T>::CalculationOverflow)?;
+
+                    let new_total_rewards = total_reward
+                        .checked_add(assignment.fee_per_execution.into())
+                        .ok_or(Error::<T>::CalculationOverflow)?;
+
+                    let mut beta_params =
+                        <StoredReputation<T>>::get(who).ok_or(Error::<T>::ReputationNotFound)?;
+
+                    beta_params = BetaReputation::update(
+                        beta_params,
+                        met,
+                        unmet,
+                        assignment.fee_per_execution,
+                        average_reward.into(),
+                    )
+                    .ok_or(Error::<T>::CalculationOverflow)?;
+
+                    let new_average_reward = new_total_rewards
+                        .checked_div(total_assigned)
+                        .ok_or(Error::<T>::CalculationOverflow)?;
+
+                    <StoredAverageRewardV3<T>>::set(Some(new_average_reward));
+                    <StoredReputation<T>>::insert(
+                        who,
+                        BetaParameters {
+                            r: beta_params.r,
+                            s: beta_params.s,
+                        },
+                    );
+                }
+            }
+
+            Self::record_sla(who, job_id, &assignment)?;
+            Self::settle_holdback(who, job_id, &registration, &assignment)?;
+            <FinalizedSlotsSLA<T>>::mutate(job_id, |sla| {
+                let sla = sla.get_or_insert(SLA { total: 0, met: 0 });
+                sla.total = sla.total.saturating_add(assignment.sla.total);
+                sla.met = sla.met.saturating_add(met);
+            });
 
-        /// Calculates the total reward amount.
-        fn total_reward_amount(
-            registration: &JobRegistrationFor<T>,
-        ) -> Result<T::Balance, Error<T>> {
-            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
-            let requirements: JobRequirementsFor<T> = e.into();
+            // only remove storage point indexed by a single processor (corresponding to the completed duties for the assigned slot)
+            Self::remove_match(who, job_id);
+            <AssignedProcessors<T>>::remove(job_id, who);
 
-            Ok(requirements
-                .reward
-                .checked_mul(&((requirements.slots as u128).into()))
-                .ok_or(Error::<T>::CalculationOverflow)?
-                .checked_mul(&registration.schedule.execution_count().into())
-                .ok_or(Error::<T>::CalculationOverflow)?)
+            // increase capacity
+            <StoredStorageCapacity<T>>::mutate(who, |c| {
+                *c = c.unwrap_or(0).checked_add(registration.storage.into())
+            });
+
+            Self::deposit_event(Event::JobFinalized(job_id.clone()));
+            Ok(())
         }
 
-        /// Calculates the fee per job execution.
-        fn fee_per_execution(
-            schedule: &Schedule,
-            storage: u32,
-            pricing: &PricingFor<T>,
-        ) -> Result<T::Balance, Error<T>> {
-            Ok(pricing
-                .fee_per_millisecond
-                .checked_mul(&schedule.duration.into())
-                .ok_or(Error::<T>::CalculationOverflow)?
-                .checked_add(
-                    &pricing
-                        .fee_per_storage_byte
-                        .clone()
-                        .checked_mul(&storage.into())
-                        .ok_or(Error::<T>::CalculationOverflow)?,
-                )
-                .ok_or(Error::<T>::CalculationOverflow)?
-                .checked_add(&pricing.base_fee_per_execution)
-                .ok_or(Error::<T>::CalculationOverflow)?)
+        /// Returns the weight [`Self::do_finalize_job`] consumed until it failed with `error`.
+        ///
+        /// The checks preceding any write fail after reading the registration, the assignment and the current time
+        /// respectively. Errors raised later roll back writes that were already performed and are charged as a full
+        /// finalization.
+        fn finalize_failure_weight(error: &DispatchError) -> Weight {
+            let db_weight = T::DbWeight::get();
+            if *error == pallet_acurast::Error::<T>::JobRegistrationNotFound.into() {
+                db_weight.reads(1)
+            } else if *error == Error::<T>::JobNotAssigned.into() {
+                db_weight.reads(2)
+            } else if *error == Error::<T>::JobCannotBeFinalized.into() {
+                db_weight.reads(3)
+            } else {
+                <T as Config>::WeightInfo::finalize_job()
+            }
         }
 
         /// Finalizes jobs and get refunds unused rewards.
@@ -1427,29 +3470,70 @@ pub mod pallet {
         ///
         /// Only valid if for all given jobs provided,
         ///
+        /// * the job was never matched and its schedule's `end_time` has passed (job is in state [`JobStatus::Open`], [`JobStatus::WaitingForDependency`] or [`JobStatus::PendingApproval`]) OR
         /// * the job was **not** acknowledged by any processor (job is in state [`JobStatus::Matched`]) OR
         /// * the job was acknowledged by **at least one** processor (job is in state [`JobStatus::Assigned`]) AND
         ///   * all processors have finalized their corresponding slot OR
         ///   * the latest possible reporting time has passed
         ///
         /// If the call proceeds, it cleans up the remaining storage entries related to the finalized jobs.
+        /// The registration deposit is returned, except for open jobs that expired unmatched where it gets burned.
+        ///
+        /// Assigned jobs whose sources met none of the expected executions over all slots additionally emit [`Event::JobFailed`]
+        /// and get finalized with the `failed` flag of [`MarketplaceHooks::finalize_job`] set.
+        ///
+        /// Jobs depending on a finalized job get unlocked if the job was assigned and cancelled otherwise.
+        ///
+        /// Assigned jobs that did not fail get renewed if their [`JobRequirements::auto_renew`] has renewals left.
         pub fn finalize_jobs_for(
             job_ids: impl IntoIterator<Item = JobId<T::AccountId>>,
         ) -> DispatchResultWithPostInfo {
+            let mut finalized = 0u32;
+            let mut renewals = 0u64;
+            let mut resolved = 0u64;
             for job_id in job_ids {
+                finalized += 1;
                 let job_status = <StoredJobStatus<T>>::get(&job_id.0, &job_id.1)
                     .ok_or(Error::<T>::JobStatusNotFound)?;
 
                 let registration = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
                     .ok_or(pallet_acurast::Error::<T>::JobRegistrationNotFound)?;
 
-                match job_status {
-                    JobStatus::Open => Err(Error::<T>::CannotFinalizeJob(job_status))?,
+                let expired_unmatched = match job_status {
+                    JobStatus::Open => {
+                        let expired = Self::now()? >= registration.schedule.end_time;
+                        if !expired {
+                            Err(Error::<T>::CannotFinalizeJob(job_status))?;
+                        }
+                        resolved = resolved.saturating_add(
+                            Self::remove_expired_unmatched_job(&job_id, true)?.into(),
+                        );
+                        continue;
+                    }
+                    JobStatus::WaitingForDependency => {
+                        let expired = Self::now()? >= registration.schedule.end_time;
+                        if !expired {
+                            Err(Error::<T>::CannotFinalizeJob(job_status))?;
+                        }
+                        Self::remove_dependent(&job_id, &registration);
+                        // the consumer is not to blame for a dependency that never finalized
+                        false
+                    }
+                    JobStatus::PendingApproval => {
+                        let expired = Self::now()? >= registration.schedule.end_time;
+                        if !expired {
+                            Err(Error::<T>::CannotFinalizeJob(job_status))?;
+                        }
+                        Self::remove_dependent(&job_id, &registration);
+                        // the consumer is not to blame for an approver that never approved
+                        false
+                    }
                     JobStatus::Matched => {
                         let match_overdue = Self::now()? >= registration.schedule.start_time;
                         if !match_overdue {
                             Err(Error::<T>::CannotFinalizeJob(job_status))?;
                         }
+                        false
                     }
                     JobStatus::Assigned(_) => {
                         // in the "good case" when all processors finalized their slot we can accept the finalization independent of schedule's latest end
@@ -1459,12 +3543,38 @@ pub mod pallet {
                         if some_assigned && !Self::schedule_ended(&registration.schedule)? {
                             Err(Error::<T>::CannotFinalizeJob(job_status))?;
                         }
+                        false
                     }
-                }
+                };
+
+                // the SLA summed up over all slots, including the ones never finalized by their source
+                let mut sla =
+                    <FinalizedSlotsSLA<T>>::take(&job_id).unwrap_or(SLA { total: 0, met: 0 });
 
                 // removed completed job from remaining storage points
                 for (p, _) in <AssignedProcessors<T>>::iter_prefix(&job_id) {
-                    <StoredMatches<T>>::remove(&p, &job_id);
+                    // held back fees of slots never finalized by their source remain in the budget and get refunded below
+                    if let Some(assignment) = Self::remove_match(&p, &job_id) {
+                        Self::record_unfinalized_sla(
+                            &p,
+                            &job_id,
+                            &registration.schedule,
+                            &assignment,
+                        )?;
+                        let disputed: u64 = <DisputedExecutions<T>>::get(&job_id, &p).into();
+                        sla.total = sla.total.saturating_add(assignment.sla.total);
+                        sla.met = sla
+                            .met
+                            .saturating_add(assignment.sla.met.saturating_sub(disputed));
+
+                        if assignment.holdback > 0u8.into() {
+                            Self::deposit_event(Event::HoldbackForfeited(
+                                job_id.clone(),
+                                p.clone(),
+                                assignment.holdback,
+                            ));
+                        }
+                    }
 
                     // increase capacity
                     <StoredStorageCapacity<T>>::mutate(&p, |c| {
@@ -1476,17 +3586,478 @@ pub mod pallet {
                     <T as pallet_acurast::Config>::MaxSlots::get(),
                     None,
                 );
+                Self::clear_delivery_commitments(&job_id);
+                <AcknowledgedSlots<T>>::remove(&job_id);
+                <MatcherRewardsPaid<T>>::remove(&job_id);
+                <LastMatchAttempt<T>>::remove(&job_id);
+                <JobRegistrationTimes<T>>::remove(&job_id);
+                <PendingScheduleChanges<T>>::remove(&job_id);
+                <EnvironmentVersion<T>>::remove(&job_id);
+
+                // the budget of a failed job was not paid out to any source and gets refunded entirely
+                let failed =
+                    matches!(job_status, JobStatus::Assigned(_)) && sla.total > 0 && sla.met == 0;
+                T::MarketplaceHooks::finalize_job(
+                    &job_id,
+                    T::RewardManager::refund(&job_id)?,
+                    failed,
+                )?;
 
-                T::MarketplaceHooks::finalize_job(&job_id, T::RewardManager::refund(&job_id)?)?;
+                // the registration is needed to renew the job after its removal below
+                let renewal = if matches!(job_status, JobStatus::Assigned(_)) && !failed {
+                    pallet_acurast::Pallet::<T>::stored_job_registration(&job_id.0, job_id.1)
+                } else {
+                    None
+                };
+
+                if expired_unmatched {
+                    Self::burn_registration_deposit(&job_id)?;
+                } else {
+                    Self::release_registration_deposit(&job_id)?;
+                }
 
                 pallet_acurast::Pallet::<T>::clear_environment_for(&job_id);
                 <StoredJobStatus<T>>::remove(&job_id.0, &job_id.1);
-                <StoredJobRegistration<T>>::remove(&job_id.0, &job_id.1);
+                pallet_acurast::Pallet::<T>::remove_job_registration(&job_id);
 
+                if failed {
+                    Self::deposit_event(Event::JobFailed(job_id.clone()));
+                }
                 Self::deposit_event(Event::JobFinalized(job_id.clone()));
+
+                resolved = resolved.saturating_add(
+                    Self::resolve_dependents(
+                        &job_id,
+                        matches!(job_status, JobStatus::Assigned(_)),
+                    )?
+                    .into(),
+                );
+
+                if let Some(registration) = renewal {
+                    if Self::renew_job(&job_id, registration) {
+                        renewals += 1;
+                    }
+                }
             }
 
-            Ok(().into())
+            Ok(Some(
+                <T as Config>::WeightInfo::finalize_jobs(finalized)
+                    .saturating_add(<T as Config>::WeightInfo::renew_job().saturating_mul(renewals))
+                    .saturating_add(
+                        <T as Config>::WeightInfo::resolve_dependent().saturating_mul(resolved),
+                    ),
+            )
+            .into())
+        }
+
+        /// Re-registers a successfully finalized job for another cycle if its [`JobRequirements::auto_renew`] has
+        /// renewals left.
+        ///
+        /// A failing renewal is reverted entirely and reported by [`Event::JobRenewalFailed`] without affecting the
+        /// finalization of the job.
+        ///
+        /// Returns `true` if the renewal was attempted, to charge its weight.
+        pub(crate) fn renew_job(
+            job_id: &JobId<T::AccountId>,
+            registration: JobRegistrationFor<T>,
+        ) -> bool {
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+            let policy = match requirements.auto_renew {
+                Some(policy) if policy.max_renewals > 0 => policy,
+                _ => return false,
+            };
+
+            match with_storage_layer(|| {
+                Self::do_renew_job(job_id, registration, requirements, policy)
+            }) {
+                Ok(renewed_job_id) => Self::deposit_event(Event::JobRenewed(
+                    job_id.clone(),
+                    renewed_job_id,
+                    policy.max_renewals - 1,
+                )),
+                Err(e) => {
+                    log::debug!(
+                        target: "runtime::acurast_marketplace",
+                        "renewing job {:?} failed with {:?}",
+                        job_id,
+                        e
+                    );
+                    Self::deposit_event(Event::JobRenewalFailed(job_id.clone()));
+                }
+            }
+            true
+        }
+
+        /// Registers the renewal of a job under a new [`JobId`] of the same consumer and returns the new [`JobId`].
+        ///
+        /// The renewal's schedule is shifted forward by the smallest whole number of spans of the original schedule
+        /// that lets it start after now, usually a single span unless the job got finalized after the shifted start,
+        /// e.g. when its last execution ends at `end_time`. Its reward is escalated by
+        /// [`RenewalPolicy::reward_escalation`]. The renewal is registered [`JobStatus::Open`] without instant match or
+        /// dependency and fails if its budget would require the approval of the consumer's approver.
+        fn do_renew_job(
+            job_id: &JobId<T::AccountId>,
+            mut registration: JobRegistrationFor<T>,
+            mut requirements: JobRequirementsFor<T>,
+            policy: RenewalPolicy,
+        ) -> Result<JobId<T::AccountId>, DispatchError> {
+            // the renewal's budget is locked from the consumer, which is a shared account for other origins
+            ensure!(
+                matches!(job_id.0, MultiOrigin::Acurast(_)),
+                Error::<T>::RenewalUnsupportedOrigin
+            );
+            let span = registration
+                .schedule
+                .end_time
+                .checked_sub(registration.schedule.start_time)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            // registration rejects empty schedules, a zero span would renew the job into the past
+            ensure!(
+                span > 0,
+                Error::<T>::JobRegistrationScheduleContainsZeroExecutions
+            );
+            let spans = Self::now()?
+                .saturating_sub(registration.schedule.start_time)
+                .checked_div(span)
+                .unwrap_or(0)
+                .saturating_add(1);
+            let shift = span
+                .checked_mul(spans)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            registration.schedule.start_time = registration
+                .schedule
+                .start_time
+                .checked_add(shift)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            registration.schedule.end_time = registration
+                .schedule
+                .end_time
+                .checked_add(shift)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+
+            requirements.reward = requirements
+                .reward
+                .checked_add(&policy.reward_escalation.mul_floor(requirements.reward))
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            requirements.instant_match = None;
+            requirements.depends_on = None;
+            requirements.auto_renew = Some(RenewalPolicy {
+                max_renewals: policy.max_renewals.saturating_sub(1),
+                ..policy
+            });
+            let e: <T as Config>::RegistrationExtra = requirements.into();
+            registration.extra = e.into();
+
+            let renewed_job_id = (job_id.0.clone(), pallet_acurast::Pallet::<T>::next_job_id());
+            let reward_amount = Self::total_reward_amount(&(&registration).into())?;
+            ensure!(
+                Self::registration_approver(&renewed_job_id, reward_amount).is_none(),
+                Error::<T>::RenewalExceedsApprovalThreshold
+            );
+
+            pallet_acurast::Pallet::<T>::register_for(renewed_job_id.clone(), registration)
+                .map_err(|e| e.error)?;
+
+            Ok(renewed_job_id)
+        }
+
+        /// Removes an open job whose schedule ended without it being matched.
+        ///
+        /// The budget is refunded, the registration deposit burned and jobs depending on it cancelled. Shared by
+        /// [`Pallet::expire_unmatched_job`] and [`Pallet::finalize_jobs_for`], the latter passing `finalized` to report
+        /// the removal as [`Event::JobFinalized`] instead of [`Event::JobExpiredUnmatched`].
+        ///
+        /// Returns the number of dependents resolved, see [`Self::resolve_dependents`].
+        fn remove_expired_unmatched_job(
+            job_id: &JobId<T::AccountId>,
+            finalized: bool,
+        ) -> Result<u32, DispatchError> {
+            let refund = Self::refund_open_job(job_id)?;
+            if finalized {
+                T::MarketplaceHooks::finalize_job(job_id, refund, false)?;
+            } else {
+                T::MarketplaceHooks::expire_job(job_id, refund)?;
+            }
+            Self::burn_registration_deposit(job_id)?;
+            <JobRegistrationTimes<T>>::remove(job_id);
+            <MatcherRewardsPaid<T>>::remove(job_id);
+            <LastMatchAttempt<T>>::remove(job_id);
+            <EnvironmentVersion<T>>::remove(job_id);
+
+            pallet_acurast::Pallet::<T>::clear_environment_for(job_id);
+            pallet_acurast::Pallet::<T>::remove_job_registration(job_id);
+
+            Self::deposit_event(if finalized {
+                Event::JobFinalized(job_id.clone())
+            } else {
+                Event::JobExpiredUnmatched(job_id.clone())
+            });
+
+            Self::resolve_dependents(job_id, false)
+        }
+
+        /// Validates the `dependency` of a newly registered job and adds the job to the dependency's [`Dependents`].
+        fn add_dependent(
+            job_id: &JobId<T::AccountId>,
+            dependency: &JobId<T::AccountId>,
+            registration: &JobRegistrationFor<T>,
+            requirements: &JobRequirementsFor<T>,
+        ) -> Result<(), DispatchError> {
+            ensure!(
+                dependency.0 == job_id.0,
+                Error::<T>::DependencyOfOtherConsumer
+            );
+            ensure!(
+                <StoredJobStatus<T>>::contains_key(&dependency.0, &dependency.1),
+                Error::<T>::DependencyNotFound
+            );
+            let dependency_registration =
+                <StoredJobRegistration<T>>::get(&dependency.0, &dependency.1)
+                    .ok_or(Error::<T>::DependencyNotFound)?;
+            ensure!(
+                registration.schedule.start_time >= dependency_registration.schedule.end_time,
+                Error::<T>::JobRegistrationStartBeforeDependencyEnd
+            );
+            ensure!(
+                requirements.instant_match.is_none(),
+                Error::<T>::InstantMatchWithDependency
+            );
+
+            // a failed job cancels all its transitive dependents, so the chains of waiting jobs are bounded
+            let mut depth: u32 = 1;
+            let mut waiting = Self::pending_dependency(dependency, &dependency_registration);
+            while let Some(next) = waiting {
+                depth += 1;
+                ensure!(
+                    depth <= T::MaxDependencyDepth::get(),
+                    Error::<T>::DependencyTooDeep
+                );
+                waiting = <StoredJobRegistration<T>>::get(&next.0, &next.1)
+                    .and_then(|registration| Self::pending_dependency(&next, &registration));
+            }
+            ensure!(
+                depth <= T::MaxDependencyDepth::get(),
+                Error::<T>::DependencyTooDeep
+            );
+
+            <Dependents<T>>::try_mutate(dependency, |dependents| {
+                dependents
+                    .try_push(job_id.clone())
+                    .map_err(|_| Error::<T>::TooManyDependents)
+            })?;
+
+            Ok(())
+        }
+
+        /// Records `slot` of a job as acknowledged and returns the number of acknowledged slots.
+        fn acknowledge_slot(job_id: &JobId<T::AccountId>, slot: u8) -> Result<u8, Error<T>> {
+            <AcknowledgedSlots<T>>::try_mutate(job_id, |slots| {
+                if let Err(index) = slots.binary_search(&slot) {
+                    slots
+                        .try_insert(index, slot)
+                        .map_err(|_| Error::<T>::TooManySlots)?;
+                }
+                u8::try_from(slots.len()).map_err(|_| Error::<T>::TooManySlots)
+            })
+        }
+
+        /// Removes the delivery commitments and challenges of a job leaving the marketplace.
+        fn clear_delivery_commitments(job_id: &JobId<T::AccountId>) {
+            let _ = <DisputedExecutions<T>>::clear_prefix(
+                job_id,
+                <T as pallet_acurast::Config>::MaxSlots::get(),
+                None,
+            );
+            // the removal of every commitment was already charged by its report
+            let _ = <StoredDeliveryCommitments<T>>::clear_prefix(
+                job_id,
+                <DeliveryCommitmentCount<T>>::take(job_id),
+                None,
+            );
+        }
+
+        /// Returns the approver if the consumer of `job_id` requires approval for a registration locking `reward_amount`.
+        fn registration_approver(
+            job_id: &JobId<T::AccountId>,
+            reward_amount: T::Balance,
+        ) -> Option<T::AccountId> {
+            match &job_id.0 {
+                MultiOrigin::Acurast(who) => <RegistrationApprovals<T>>::get(who)
+                    .filter(|approval| reward_amount > approval.threshold)
+                    .map(|approval| approval.approver),
+                _ => None,
+            }
+        }
+
+        /// Returns the status a job pending approval transitions to once approved.
+        ///
+        /// Jobs still waiting for their dependency are [`JobStatus::WaitingForDependency`], all others [`JobStatus::Open`].
+        fn approved_status(
+            job_id: &JobId<T::AccountId>,
+            requirements: &JobRequirementsFor<T>,
+        ) -> JobStatus {
+            match &requirements.depends_on {
+                Some(dependency) if <Dependents<T>>::get(dependency).contains(job_id) => {
+                    JobStatus::WaitingForDependency
+                }
+                _ => JobStatus::Open,
+            }
+        }
+
+        /// Ensures `job_id` is pending approval and `who` is the approver configured by the job's consumer.
+        fn ensure_pending_approval_by(
+            who: &T::AccountId,
+            job_id: &JobId<T::AccountId>,
+        ) -> Result<(), DispatchError> {
+            ensure!(
+                <StoredJobStatus<T>>::get(&job_id.0, &job_id.1) == Some(JobStatus::PendingApproval),
+                Error::<T>::RegistrationNotPendingApproval
+            );
+            let approver = match &job_id.0 {
+                MultiOrigin::Acurast(consumer) => {
+                    <RegistrationApprovals<T>>::get(consumer).map(|approval| approval.approver)
+                }
+                _ => None,
+            };
+            ensure!(
+                approver.as_ref() == Some(who),
+                Error::<T>::NotRegistrationApprover
+            );
+            Ok(())
+        }
+
+        /// Returns the dependency `job_id` is still waiting for, if any.
+        fn pending_dependency(
+            job_id: &JobId<T::AccountId>,
+            registration: &JobRegistrationCoreFor<T>,
+        ) -> Option<JobId<T::AccountId>> {
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+            requirements
+                .depends_on
+                .filter(|dependency| <Dependents<T>>::get(dependency).contains(job_id))
+        }
+
+        /// Returns the maximum number of jobs resolved along with a finalized job, i.e. its dependents up to
+        /// [`Config::MaxDependencyDepth`] levels deep with at most [`Config::MaxDependents`] per job.
+        pub(crate) fn max_resolved_dependents() -> u32 {
+            (0..T::MaxDependencyDepth::get())
+                .fold((0u32, 1u32), |(total, level), _| {
+                    let level = level.saturating_mul(T::MaxDependents::get());
+                    (total.saturating_add(level), level)
+                })
+                .0
+        }
+
+        /// Removes a job waiting for its dependency from the dependency's [`Dependents`].
+        fn remove_dependent(
+            job_id: &JobId<T::AccountId>,
+            registration: &JobRegistrationCoreFor<T>,
+        ) {
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+            if let Some(dependency) = requirements.depends_on {
+                <Dependents<T>>::mutate_exists(&dependency, |dependents| {
+                    if let Some(d) = dependents {
+                        d.retain(|dependent| dependent != job_id);
+                        if d.is_empty() {
+                            *dependents = None;
+                        }
+                    }
+                });
+            }
+        }
+
+        /// Unlocks the jobs waiting for the finalized job `job_id` if it `succeeded` and cancels them otherwise.
+        ///
+        /// Cancelled jobs get their budget and registration deposit refunded and the cancellation propagates to their own dependents.
+        ///
+        /// Returns the number of dependents resolved, which is at most [`Self::max_resolved_dependents`].
+        pub(crate) fn resolve_dependents(
+            job_id: &JobId<T::AccountId>,
+            succeeded: bool,
+        ) -> Result<u32, DispatchError> {
+            let mut resolved = 0u32;
+            let mut dependents = <Dependents<T>>::take(job_id).into_inner();
+            while let Some(dependent) = dependents.pop() {
+                resolved += 1;
+                let status = <StoredJobStatus<T>>::get(&dependent.0, &dependent.1);
+                if status != Some(JobStatus::WaitingForDependency)
+                    && status != Some(JobStatus::PendingApproval)
+                {
+                    continue;
+                }
+
+                if succeeded {
+                    // a job pending approval stays pending, but gets approved into status open
+                    if status == Some(JobStatus::WaitingForDependency) {
+                        <StoredJobStatus<T>>::insert(&dependent.0, &dependent.1, JobStatus::Open);
+                        Self::deposit_event(Event::JobUnlocked(dependent));
+                    }
+                } else {
+                    T::MarketplaceHooks::finalize_job(
+                        &dependent,
+                        T::RewardManager::refund(&dependent)?,
+                        false,
+                    )?;
+                    Self::release_registration_deposit(&dependent)?;
+                    <JobRegistrationTimes<T>>::remove(&dependent);
+
+                    pallet_acurast::Pallet::<T>::clear_environment_for(&dependent);
+                    <StoredJobStatus<T>>::remove(&dependent.0, &dependent.1);
+                    pallet_acurast::Pallet::<T>::remove_job_registration(&dependent);
+
+                    // a cancelled job never finalizes successfully
+                    dependents.extend(<Dependents<T>>::take(&dependent));
+                    Self::deposit_event(Event::JobCancelled(dependent));
+                }
+            }
+            Ok(resolved)
+        }
+
+        /// Reserves the [`Config::JobRegistrationDeposit`] from the consumer of a locally registered job.
+        ///
+        /// Jobs registered from other chains are exempt since spam protection is handled on their origin chain.
+        fn lock_registration_deposit(job_id: &JobId<T::AccountId>) -> Result<(), DispatchError> {
+            let deposit = T::JobRegistrationDeposit::get();
+            if deposit == 0u8.into() {
+                return Ok(());
+            }
+            if let MultiOrigin::Acurast(who) = &job_id.0 {
+                T::RewardManager::lock_deposit(who, deposit)?;
+                <JobRegistrationDeposits<T>>::insert(job_id, deposit);
+            }
+            Ok(())
+        }
+
+        /// Returns the registration deposit (if any) to the consumer.
+        fn release_registration_deposit(job_id: &JobId<T::AccountId>) -> Result<(), DispatchError> {
+            if let Some(deposit) = <JobRegistrationDeposits<T>>::take(job_id) {
+                if let MultiOrigin::Acurast(who) = &job_id.0 {
+                    T::RewardManager::refund_deposit(who, deposit)?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Refunds the full budget of an open job and removes its status.
+        ///
+        /// Shared by deregistration and [`Pallet::expire_unmatched_job`]: since the status is removed, an open job can
+        /// only be refunded once.
+        fn refund_open_job(job_id: &JobId<T::AccountId>) -> Result<T::Balance, DispatchError> {
+            let refund = T::RewardManager::refund(job_id)?;
+            <StoredJobStatus<T>>::remove(&job_id.0, &job_id.1);
+
+            Ok(refund)
+        }
+
+        /// Burns the registration deposit (if any).
+        fn burn_registration_deposit(job_id: &JobId<T::AccountId>) -> Result<(), DispatchError> {
+            if let Some(deposit) = <JobRegistrationDeposits<T>>::take(job_id) {
+                T::RewardManager::burn_deposit(deposit)?;
+            }
+            Ok(())
         }
 
         /// Returns the stored matches for a source.
@@ -1495,10 +4066,11 @@ pub mod pallet {
         pub fn stored_matches_for_source(
             source: T::AccountId,
         ) -> Result<Vec<JobAssignmentFor<T>>, RuntimeApiError> {
-            <StoredMatches<T>>::iter_prefix(source)
+            Self::ordered_matches(&source)
                 .map(|(job_id, assignment)| {
-                    let job = <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1)
-                        .ok_or(RuntimeApiError::MatchedJobs)?;
+                    let job =
+                        pallet_acurast::Pallet::<T>::stored_job_registration(&job_id.0, &job_id.1)
+                            .ok_or(RuntimeApiError::MatchedJobs)?;
                     Ok(JobAssignment {
                         job_id,
                         job,
@@ -1508,6 +4080,52 @@ pub mod pallet {
                 .collect()
         }
 
+        /// Extends `limits` with the limits configured for this pallet.
+        pub fn extend_limits(limits: &mut AcurastLimits) {
+            limits.max_executions_per_job = Some(T::MaxExecutionsPerJob::get());
+            limits.report_tolerance = Some(T::ReportTolerance::get());
+            limits.max_proposed_matches = Some(T::MaxProposedMatches::get());
+        }
+
+        /// Ensures every job registration has a status and every status belongs to a registration, and that
+        /// [`SourceJobIndex`] and [`StoredMatches`] contain the same matches.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            for (origin, job_id_seq, _) in <StoredJobRegistration<T>>::iter() {
+                ensure!(
+                    <StoredJobStatus<T>>::contains_key(&origin, job_id_seq),
+                    "registered job without status"
+                );
+                ensure!(
+                    <StoredJobPayload<T>>::contains_key(&origin, job_id_seq),
+                    "registered job without payload"
+                );
+            }
+            for (origin, job_id_seq, _) in <StoredJobStatus<T>>::iter() {
+                ensure!(
+                    <StoredJobRegistration<T>>::contains_key(&origin, job_id_seq),
+                    "job status without registration"
+                );
+            }
+            for (source, jobs) in <SourceJobIndex<T>>::iter() {
+                ensure!(!jobs.is_empty(), "empty source job index");
+                for job_id in jobs.iter() {
+                    ensure!(
+                        <StoredMatches<T>>::contains_key(&source, job_id),
+                        "source job index entry without match"
+                    );
+                }
+            }
+            for (source, job_id, _) in <StoredMatches<T>>::iter() {
+                ensure!(
+                    <SourceJobIndex<T>>::get(&source).contains(&job_id),
+                    "match missing in source job index"
+                );
+            }
+
+            Ok(())
+        }
+
         /// Returns the current timestamp.
         pub fn now() -> Result<u64, Error<T>> {
             Ok(<T as pallet_acurast::Config>::UnixTime::now()
@@ -1520,14 +4138,34 @@ pub mod pallet {
 
 sp_api::decl_runtime_apis! {
     /// API to interact with Acurast marketplace pallet.
+    ///
+    /// Version 2 added `sla_history`.
+    /// Version 3 returns the pricing version of each source from `filter_matching_sources`.
+    /// Version 4 added `unacknowledged_slots`.
+    /// Version 5 added `matcher_stats`.
+    /// Version 6 added `matchable_jobs`.
+    /// Version 7 added `last_match_attempt`.
+    #[api_version(7)]
     pub trait MarketplaceRuntimeApi<Reward: codec::Codec, AccountId: codec::Codec, Extra: codec::Codec, MaxAllowedSources: ParameterBound, MaxEnvVars: ParameterBound, EnvKeyMaxSize: ParameterBound, EnvValueMaxSize: ParameterBound> {
-         fn filter_matching_sources(
+        /// Returns the sources matching `registration`, without their pricing version.
+        #[changed_in(3)]
+        fn filter_matching_sources(
             registration: PartialJobRegistration<Reward, AccountId, MaxAllowedSources>,
             sources: Vec<AccountId>,
             consumer: Option<MultiOrigin<AccountId>>,
             latest_seen_after: Option<u128>,
         ) -> Result<Vec<AccountId>, RuntimeApiError>;
 
+        /// Returns the sources matching `registration` with their current pricing version.
+        ///
+        /// This function forwards to [`Pallet::filter_matching_sources`].
+        fn filter_matching_sources(
+            registration: PartialJobRegistration<Reward, AccountId, MaxAllowedSources>,
+            sources: Vec<AccountId>,
+            consumer: Option<MultiOrigin<AccountId>>,
+            latest_seen_after: Option<u128>,
+        ) -> Result<Vec<(AccountId, u32)>, RuntimeApiError>;
+
         fn job_environment(
             job_id: JobId<AccountId>,
             source: AccountId,
@@ -1540,5 +4178,30 @@ sp_api::decl_runtime_apis! {
         fn attestation(
             source: AccountId,
         ) -> Result<Option<Attestation>, RuntimeApiError>;
+
+        #[api_version(2)]
+        fn sla_history(
+            source: AccountId,
+        ) -> Result<SLASummary<Reward>, RuntimeApiError>;
+
+        #[api_version(4)]
+        fn unacknowledged_slots(
+            job_id: JobId<AccountId>,
+        ) -> Result<Vec<u8>, RuntimeApiError>;
+
+        #[api_version(5)]
+        fn matcher_stats(
+            matcher: AccountId,
+        ) -> Result<MatcherPerformance, RuntimeApiError>;
+
+        #[api_version(6)]
+        fn matchable_jobs(
+            limit: u32,
+        ) -> Result<Vec<MatchableJob<Reward, AccountId>>, RuntimeApiError>;
+
+        #[api_version(7)]
+        fn last_match_attempt(
+            job_id: JobId<AccountId>,
+        ) -> Result<Option<MatchAttempt<u64>>, RuntimeApiError>;
     }
 }
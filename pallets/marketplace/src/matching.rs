@@ -0,0 +1,513 @@
+use frame_support::{
+    ensure,
+    sp_runtime::{
+        traits::{CheckedAdd, CheckedMul},
+        FixedU128, Permill,
+    },
+    RuntimeDebug,
+};
+use itertools::Itertools;
+use reputation::{BetaParameters, BetaReputation, ReputationEngine};
+use sp_std::{fmt::Debug, prelude::*};
+
+use pallet_acurast::{AllowedSources, JobModules, MultiOrigin, Schedule};
+
+use crate::utils::{is_consumer_whitelisted, is_source_whitelisted, permill_from_parts};
+use crate::{
+    AdvertisementRestriction, Config, Error, JobRequirementsFor, PreferredSources, PricingFor,
+    SchedulingWindow,
+};
+
+/// The reason why a source cannot be matched to a job's slot.
+#[derive(RuntimeDebug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchingReason {
+    UnverifiedSource,
+    ModuleNotAvailable,
+    ModuleNotAttested,
+    PricingVersionMismatch,
+    AttestationTooOld,
+    SchedulingWindowExceeded,
+    MaxMemoryExceeded,
+    NetworkRequestQuotaExceeded,
+    InsufficientStorageCapacity,
+    SourceNotAllowed,
+    OutsidePreferenceWindow,
+    ConsumerNotAllowed,
+    ReputationNotFound,
+    InsufficientReputation,
+    InsufficientRecentFulfillmentRatio,
+    ScheduleOverlap,
+    InsufficientReward,
+    FeeAboveConsumerMaximum,
+    CalculationOverflow,
+}
+
+impl<T: Config> From<MatchingReason> for Error<T> {
+    fn from(reason: MatchingReason) -> Self {
+        match reason {
+            MatchingReason::UnverifiedSource => Error::<T>::UnverifiedSourceInMatch,
+            MatchingReason::ModuleNotAvailable => Error::<T>::ModuleNotAvailableInMatch,
+            MatchingReason::ModuleNotAttested => Error::<T>::ModuleNotAttested,
+            MatchingReason::PricingVersionMismatch => Error::<T>::PricingVersionMismatch,
+            MatchingReason::AttestationTooOld => Error::<T>::AttestationTooOld,
+            MatchingReason::SchedulingWindowExceeded => Error::<T>::SchedulingWindowExceededInMatch,
+            MatchingReason::MaxMemoryExceeded => Error::<T>::MaxMemoryExceededInMatch,
+            MatchingReason::NetworkRequestQuotaExceeded => {
+                Error::<T>::NetworkRequestQuotaExceededInMatch
+            }
+            MatchingReason::InsufficientStorageCapacity => {
+                Error::<T>::InsufficientStorageCapacityInMatch
+            }
+            MatchingReason::SourceNotAllowed => Error::<T>::SourceNotAllowedInMatch,
+            MatchingReason::OutsidePreferenceWindow => Error::<T>::OutsidePreferenceWindow,
+            MatchingReason::ConsumerNotAllowed => Error::<T>::ConsumerNotAllowedInMatch,
+            MatchingReason::ReputationNotFound => Error::<T>::ReputationNotFound,
+            MatchingReason::InsufficientReputation => Error::<T>::InsufficientReputationInMatch,
+            MatchingReason::InsufficientRecentFulfillmentRatio => {
+                Error::<T>::InsufficientRecentFulfillmentRatioInMatch
+            }
+            MatchingReason::ScheduleOverlap => Error::<T>::ScheduleOverlapInMatch,
+            MatchingReason::InsufficientReward => Error::<T>::InsufficientRewardInMatch,
+            MatchingReason::FeeAboveConsumerMaximum => Error::<T>::FeeAboveConsumerMaximum,
+            MatchingReason::CalculationOverflow => Error::<T>::CalculationOverflow,
+        }
+    }
+}
+
+/// The inputs required to validate matching a source to one slot of a job, except for those of [`SlotCandidateLookup`].
+///
+/// The pallet assembles these from storage, so that [`validate_slot`] itself does not access any storage.
+pub struct SlotCandidateInputs<'a, T: Config> {
+    /// The current time in milliseconds since Unix Epoch.
+    pub now: u64,
+    /// The consumer that registered the job.
+    pub consumer: &'a MultiOrigin<T::AccountId>,
+    /// The source proposed for the slot.
+    pub source: &'a T::AccountId,
+    /// The start delay proposed for the source.
+    pub start_delay: u64,
+    /// The pricing version the matcher observed, if provided.
+    pub expected_pricing_version: Option<u32>,
+    /// The job's schedule.
+    pub schedule: &'a Schedule,
+    /// The job's memory requirement in bytes.
+    pub memory: u32,
+    /// The job's network requests per second.
+    pub network_requests: u32,
+    /// The job's storage requirement in bytes.
+    pub storage: u32,
+    /// The modules required by the job.
+    pub required_modules: &'a JobModules,
+    /// The sources allowed by the job.
+    pub allowed_sources: &'a Option<AllowedSources<T::AccountId, T::MaxAllowedSources>>,
+    /// If the job only accepts verified sources.
+    pub allow_only_verified_sources: bool,
+    /// The marketplace specific requirements of the job.
+    pub requirements: &'a JobRequirementsFor<T>,
+    /// The time in milliseconds the job was registered, if recorded for its [`crate::JobRequirements::preference_window_ms`].
+    pub registered_at: Option<u64>,
+    /// The source's advertised restrictions.
+    pub advertisement: &'a AdvertisementRestriction<T::AccountId, T::MaxAllowedConsumers>,
+    /// The source's advertised pricing.
+    pub pricing: &'a PricingFor<T>,
+    /// The current version of the source's pricing.
+    pub pricing_version: u32,
+    /// The source's remaining storage capacity.
+    pub capacity: i64,
+}
+
+/// Provides the inputs of [`validate_slot`] that are expensive to look up.
+///
+/// [`validate_slot`] calls each of them only right before the check using it, so a slot rejected by a cheaper check
+/// never pays for them.
+pub trait SlotCandidateLookup<T: Config> {
+    /// The error of a failing lookup, also holding the [`MatchingReason`] of a rejected slot.
+    type Error: From<MatchingReason> + Debug;
+
+    /// Returns the source's valid attestation, if verified, holding the attestation's submission time if known.
+    fn source_attestation(&self) -> Option<Option<u64>>;
+    /// Returns if the source's attestation backs all required modules subject to [`Config::ModuleAttestationCheck`].
+    fn modules_attested(&self) -> bool;
+    /// Returns the source's reputation parameters, if any.
+    fn reputation(&self) -> Option<BetaParameters<FixedU128>>;
+    /// Returns the source's ratio of met over total executions in its recent SLA history, if any.
+    fn recent_fulfillment_ratio(&self) -> Option<Permill>;
+    /// Returns the schedules and start delays of all jobs currently matched with the source.
+    fn matched_schedules(&self) -> Result<Vec<(Schedule, u64)>, Self::Error>;
+}
+
+/// The outcome of a successfully validated slot.
+#[derive(RuntimeDebug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotOutcome<Reward> {
+    /// The fee owed to the source for each execution.
+    pub fee_per_execution: Reward,
+    /// The number of executions of the job.
+    pub execution_count: u64,
+    /// The source's storage capacity remaining after the assignment.
+    pub remaining_capacity: i64,
+}
+
+/// The log target used for reporting which check rejected a slot candidate.
+///
+/// Enable with `-l runtime::acurast_marketplace::matching=debug` to collect how often each check rejects.
+pub const MATCHING_LOG_TARGET: &str = "runtime::acurast_marketplace::matching";
+
+/// Validates that the source can be assigned to a job's slot, without accessing any storage other than through `lookup`.
+///
+/// The checks run ordered from cheapest to most expensive and short-circuit on the first failing check.
+/// For a slot failing multiple checks, the returned [`MatchingReason`] is the first failing check in this order:
+///
+/// 1. [`MatchingReason::SourceNotAllowed`], [`MatchingReason::OutsidePreferenceWindow`] and [`MatchingReason::ConsumerNotAllowed`]
+/// 2. [`MatchingReason::UnverifiedSource`], [`MatchingReason::PricingVersionMismatch`] and [`MatchingReason::MaxMemoryExceeded`]
+/// 3. [`MatchingReason::InsufficientStorageCapacity`] and [`MatchingReason::NetworkRequestQuotaExceeded`]
+/// 4. [`MatchingReason::ModuleNotAvailable`], [`MatchingReason::ModuleNotAttested`] and [`MatchingReason::AttestationTooOld`]
+/// 5. [`MatchingReason::SchedulingWindowExceeded`]
+/// 6. [`MatchingReason::InsufficientRecentFulfillmentRatio`], [`MatchingReason::ReputationNotFound`] and [`MatchingReason::InsufficientReputation`]
+/// 7. [`MatchingReason::InsufficientReward`] and [`MatchingReason::FeeAboveConsumerMaximum`]
+/// 8. [`MatchingReason::ScheduleOverlap`]
+pub fn validate_slot<T: Config, L: SlotCandidateLookup<T>>(
+    candidate: &SlotCandidateInputs<T>,
+    lookup: &L,
+) -> Result<SlotOutcome<T::Balance>, L::Error> {
+    validate_slot_ordered(candidate, lookup).map_err(|reason| {
+        log::debug!(
+            target: MATCHING_LOG_TARGET,
+            "slot rejected by check {:?}",
+            reason,
+        );
+        reason
+    })
+}
+
+fn validate_slot_ordered<T: Config, L: SlotCandidateLookup<T>>(
+    candidate: &SlotCandidateInputs<T>,
+    lookup: &L,
+) -> Result<SlotOutcome<T::Balance>, L::Error> {
+    // the attestation is looked up at most once, for the first check needing it
+    let mut attestation = None;
+    let mut source_attestation = || *attestation.get_or_insert_with(|| lookup.source_attestation());
+
+    // CHECK source is whitelisted
+    ensure!(
+        is_source_whitelisted::<T>(candidate.source, candidate.allowed_sources),
+        MatchingReason::SourceNotAllowed
+    );
+
+    // CHECK source is preferred as long as the preference window did not elapse
+    check_preference_window(
+        preference_window::<T>(candidate.requirements, candidate.registered_at),
+        candidate.source,
+        candidate.now,
+    )?;
+
+    // CHECK consumer is whitelisted
+    ensure!(
+        is_consumer_whitelisted::<T>(
+            candidate.consumer,
+            &candidate.advertisement.allowed_consumers
+        ),
+        MatchingReason::ConsumerNotAllowed
+    );
+
+    // CHECK attestation
+    ensure!(
+        !candidate.allow_only_verified_sources || source_attestation().is_some(),
+        MatchingReason::UnverifiedSource
+    );
+
+    // CHECK pricing did not change since observed by matcher
+    if let Some(pricing_version) = candidate.expected_pricing_version {
+        ensure!(
+            pricing_version == candidate.pricing_version,
+            MatchingReason::PricingVersionMismatch
+        );
+    }
+
+    // CHECK memory sufficient
+    ensure!(
+        candidate.advertisement.max_memory >= candidate.memory,
+        MatchingReason::MaxMemoryExceeded
+    );
+
+    // CHECK remaining storage capacity sufficient
+    ensure!(
+        candidate.capacity >= candidate.storage as i64,
+        MatchingReason::InsufficientStorageCapacity
+    );
+
+    // CHECK network request quota sufficient
+    check_network_request_quota_sufficient::<T>(
+        candidate.advertisement,
+        candidate.schedule,
+        candidate.network_requests,
+    )?;
+
+    for required_module in candidate.required_modules {
+        ensure!(
+            candidate
+                .advertisement
+                .available_modules
+                .contains(required_module),
+            MatchingReason::ModuleNotAvailable
+        );
+    }
+    ensure!(lookup.modules_attested(), MatchingReason::ModuleNotAttested);
+
+    // CHECK attestation recent enough
+    check_attestation_age(
+        candidate.requirements.max_attestation_age_ms,
+        || source_attestation().flatten(),
+        candidate.now,
+    )?;
+
+    // CHECK the scheduling_window allow to schedule this job
+    check_scheduling_window(
+        &candidate.pricing.scheduling_window,
+        candidate.schedule,
+        candidate.now,
+        candidate.start_delay,
+    )?;
+
+    // CHECK recent fulfillment ratio sufficient
+    check_min_recent_fulfillment_ratio(
+        candidate.requirements.min_recent_fulfillment_ratio,
+        || lookup.recent_fulfillment_ratio(),
+    )?;
+
+    // CHECK reputation sufficient (requires normalization of the reputation's beta parameters)
+    check_min_reputation(candidate.requirements.min_reputation, || {
+        lookup.reputation()
+    })?;
+
+    // calculate fee
+    let fee_per_execution =
+        fee_per_execution::<T>(candidate.schedule, candidate.storage, candidate.pricing)?;
+
+    // CHECK price not exceeding reward
+    ensure!(
+        fee_per_execution <= candidate.requirements.reward,
+        MatchingReason::InsufficientReward
+    );
+
+    // CHECK price not exceeding consumer's maximum
+    if let Some(max_fee_per_execution) = &candidate.requirements.max_fee_per_execution {
+        ensure!(
+            &fee_per_execution <= max_fee_per_execution,
+            MatchingReason::FeeAboveConsumerMaximum
+        );
+    }
+
+    // CHECK schedule last since merging the execution iterators is the most expensive check
+    fits_schedule(
+        candidate.schedule,
+        candidate.start_delay,
+        &lookup.matched_schedules()?,
+    )?;
+
+    Ok(SlotOutcome {
+        fee_per_execution,
+        execution_count: candidate.schedule.execution_count(),
+        remaining_capacity: candidate
+            .capacity
+            .checked_sub(candidate.storage.into())
+            .ok_or(MatchingReason::CalculationOverflow)?,
+    })
+}
+
+/// Returns the preferred sources of a job registered at `registered_at` together with the end of its preference window,
+/// if the job has a [`crate::JobRequirements::preferred_sources`].
+pub(crate) fn preference_window<T: Config>(
+    requirements: &JobRequirementsFor<T>,
+    registered_at: Option<u64>,
+) -> Option<(&PreferredSources<T::AccountId>, u64)> {
+    let preferred_sources = requirements.preferred_sources.as_ref()?;
+    let window_end = registered_at?.saturating_add(requirements.preference_window_ms);
+    Some((preferred_sources, window_end))
+}
+
+/// Checks that `source` is preferred as long as the [`preference_window`] did not elapse at `now`.
+pub(crate) fn check_preference_window<AccountId: PartialEq>(
+    preference: Option<(&PreferredSources<AccountId>, u64)>,
+    source: &AccountId,
+    now: u64,
+) -> Result<(), MatchingReason> {
+    if let Some((preferred_sources, window_end)) = preference {
+        ensure!(
+            now >= window_end || preferred_sources.contains(source),
+            MatchingReason::OutsidePreferenceWindow
+        );
+    }
+    Ok(())
+}
+
+/// Checks that an attestation submitted at `submitted_at` is not older than `max_age` at `now`.
+///
+/// The submission time is only looked up if a `max_age` is required.
+pub(crate) fn check_attestation_age(
+    max_age: Option<u64>,
+    submitted_at: impl FnOnce() -> Option<u64>,
+    now: u64,
+) -> Result<(), MatchingReason> {
+    if let Some(max_age) = max_age {
+        let submitted_at = submitted_at().ok_or(MatchingReason::AttestationTooOld)?;
+        ensure!(
+            now.saturating_sub(submitted_at) <= max_age,
+            MatchingReason::AttestationTooOld
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn check_scheduling_window(
+    scheduling_window: &SchedulingWindow,
+    schedule: &Schedule,
+    now: u64,
+    start_delay: u64,
+) -> Result<(), MatchingReason> {
+    let latest_end = schedule
+        .end_time
+        .checked_add(start_delay)
+        .ok_or(MatchingReason::CalculationOverflow)?;
+    match scheduling_window {
+        SchedulingWindow::End(end) => {
+            ensure!(*end >= latest_end, MatchingReason::SchedulingWindowExceeded);
+        }
+        SchedulingWindow::Delta(delta) => {
+            ensure!(
+                now.checked_add(*delta)
+                    .ok_or(MatchingReason::CalculationOverflow)?
+                    >= latest_end,
+                MatchingReason::SchedulingWindowExceeded
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the source permits the `network_requests` a job performs during each of its executions.
+///
+/// The advertised `network_request_quota` is the number of requests per second the source permits, so a single execution
+/// lasting `schedule.duration` milliseconds may perform up to `network_request_quota * duration / 1000` requests:
+///
+/// network_requests (per execution) <= network_request_quota (per second) * duration (ms) / 1000
+/// <=>
+/// network_requests (per execution) * 1000 <= network_request_quota (per second) * duration (ms)
+///
+/// Shared by [`validate_slot`] and the partial registration check, so both paths apply identical semantics.
+pub(crate) fn check_network_request_quota_sufficient<T: Config>(
+    ad: &AdvertisementRestriction<T::AccountId, T::MaxAllowedConsumers>,
+    schedule: &Schedule,
+    network_requests: u32,
+) -> Result<(), MatchingReason> {
+    let permitted = schedule
+        .duration
+        .checked_mul(ad.network_request_quota.into())
+        .ok_or(MatchingReason::CalculationOverflow)?;
+    let required = u64::from(network_requests)
+        .checked_mul(1000u64)
+        .ok_or(MatchingReason::CalculationOverflow)?;
+    ensure!(
+        required <= permitted,
+        MatchingReason::NetworkRequestQuotaExceeded
+    );
+    Ok(())
+}
+
+/// Checks that the source's `reputation` is at least `min_reputation`, only looking it up if a minimum is required.
+pub(crate) fn check_min_reputation(
+    min_reputation: Option<u128>,
+    reputation: impl FnOnce() -> Option<BetaParameters<FixedU128>>,
+) -> Result<(), MatchingReason> {
+    if let Some(min_reputation) = min_reputation {
+        let beta_params = reputation().ok_or(MatchingReason::ReputationNotFound)?;
+
+        let reputation = BetaReputation::<u128>::normalize(beta_params)
+            .ok_or(MatchingReason::CalculationOverflow)?;
+
+        ensure!(
+            reputation >= permill_from_parts(min_reputation),
+            MatchingReason::InsufficientReputation
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the source's recent fulfillment `ratio` is at least `min_ratio`, only looking it up if a minimum is required.
+pub(crate) fn check_min_recent_fulfillment_ratio(
+    min_ratio: Option<u128>,
+    ratio: impl FnOnce() -> Option<Permill>,
+) -> Result<(), MatchingReason> {
+    if let Some(min_ratio) = min_ratio {
+        let ratio = ratio().ok_or(MatchingReason::InsufficientRecentFulfillmentRatio)?;
+
+        ensure!(
+            ratio >= permill_from_parts(min_ratio),
+            MatchingReason::InsufficientRecentFulfillmentRatio
+        );
+    }
+    Ok(())
+}
+
+/// Checks if a new job schedule fits with the schedules already matched with a source.
+pub(crate) fn fits_schedule(
+    schedule: &Schedule,
+    start_delay: u64,
+    matched_schedules: &[(Schedule, u64)],
+) -> Result<(), MatchingReason> {
+    for (other, other_start_delay) in matched_schedules {
+        // check if the whole schedule periods have an overlap
+        if schedule.start_time >= other.end_time || schedule.end_time <= other.start_time {
+            // periods don't overlap
+            continue;
+        }
+
+        let it = schedule
+            .iter(start_delay)
+            .ok_or(MatchingReason::CalculationOverflow)?
+            .map(|start| {
+                let end = start.checked_add(schedule.duration)?;
+                Some((start, end))
+            });
+        let other_it = other
+            .iter(*other_start_delay)
+            .ok_or(MatchingReason::CalculationOverflow)?
+            .map(|start| {
+                let end = start.checked_add(other.duration)?;
+                Some((start, end))
+            });
+
+        it.merge(other_it).try_fold(0u64, |prev_end, bounds| {
+            let (start, end) = bounds.ok_or(MatchingReason::CalculationOverflow)?;
+
+            if prev_end > start {
+                Err(MatchingReason::ScheduleOverlap)
+            } else {
+                Ok(end)
+            }
+        })?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn fee_per_execution<T: Config>(
+    schedule: &Schedule,
+    storage: u32,
+    pricing: &PricingFor<T>,
+) -> Result<T::Balance, MatchingReason> {
+    Ok(pricing
+        .fee_per_millisecond
+        .checked_mul(&schedule.duration.into())
+        .ok_or(MatchingReason::CalculationOverflow)?
+        .checked_add(
+            &pricing
+                .fee_per_storage_byte
+                .clone()
+                .checked_mul(&storage.into())
+                .ok_or(MatchingReason::CalculationOverflow)?,
+        )
+        .ok_or(MatchingReason::CalculationOverflow)?
+        .checked_add(&pricing.base_fee_per_execution)
+        .ok_or(MatchingReason::CalculationOverflow)?)
+}
@@ -0,0 +1,419 @@
+#![cfg(test)]
+
+use frame_support::sp_runtime::{FixedU128, Permill};
+use pallet_acurast::{AllowedSources, JobModules, MultiOrigin, Schedule, CU32};
+use proptest::prelude::*;
+use reputation::BetaParameters;
+
+use crate::matching::*;
+use crate::{mock::*, stub::*};
+use crate::{AdvertisementRestriction, JobRequirements, Pricing, PricingFor, SchedulingWindow};
+
+prop_compose! {
+    fn schedule()(
+        start_time in 1_000u64..1_000_000,
+        duration in 1u64..10_000,
+        gap in 0u64..10_000,
+        executions in 1u64..50,
+        max_start_delay in 0u64..10_000,
+    ) -> Schedule {
+        let interval = duration + gap + 1;
+        Schedule {
+            duration,
+            start_time,
+            end_time: start_time + interval * executions,
+            interval,
+            max_start_delay,
+        }
+    }
+}
+
+prop_compose! {
+    fn pricing()(
+        fee_per_millisecond in 0u128..1_000,
+        fee_per_storage_byte in 0u128..100,
+        base_fee_per_execution in 0u128..100_000,
+        window_end in any::<bool>(),
+        window in 0u64..3_000_000,
+    ) -> PricingFor<Test> {
+        Pricing {
+            fee_per_millisecond,
+            fee_per_storage_byte,
+            base_fee_per_execution,
+            scheduling_window: if window_end {
+                SchedulingWindow::End(window)
+            } else {
+                SchedulingWindow::Delta(window)
+            },
+        }
+    }
+}
+
+/// The job's side of a slot candidate.
+#[derive(Debug, Clone)]
+struct Job {
+    schedule: Schedule,
+    start_delay: u64,
+    reward: Balance,
+    max_fee_per_execution: Option<Balance>,
+    storage: u32,
+    memory: u32,
+    network_requests: u32,
+}
+
+prop_compose! {
+    fn job()(
+        schedule in schedule(),
+        start_delay in 0u64..10_000,
+        reward in 0u128..20_000_000,
+        max_fee_per_execution in proptest::option::of(0u128..20_000_000),
+        storage in 0u32..100_000,
+        memory in 0u32..10_000,
+        network_requests in 0u32..10,
+    ) -> Job {
+        let start_delay = start_delay % (schedule.max_start_delay + 1);
+        let max_fee_per_execution = max_fee_per_execution.map(|max_fee| max_fee.min(reward));
+        Job { schedule, start_delay, reward, max_fee_per_execution, storage, memory, network_requests }
+    }
+}
+
+/// The source's side of a slot candidate.
+#[derive(Debug, Clone)]
+struct Source {
+    pricing: PricingFor<Test>,
+    capacity: i64,
+    max_memory: u32,
+    network_request_quota: u8,
+    matched_schedules: Vec<(Schedule, u64)>,
+}
+
+prop_compose! {
+    fn source()(
+        pricing in pricing(),
+        capacity in -1_000i64..200_000,
+        max_memory in 0u32..10_000,
+        network_request_quota in 0u8..20,
+        matched_schedules in proptest::collection::vec((schedule(), 0u64..10_000), 0..3),
+    ) -> Source {
+        Source { pricing, capacity, max_memory, network_request_quota, matched_schedules }
+    }
+}
+
+/// Looks up an unverified source without reputation or SLA history, matched with `matched_schedules`.
+struct SourceLookup<'a> {
+    matched_schedules: &'a [(Schedule, u64)],
+}
+
+impl<'a> SlotCandidateLookup<Test> for SourceLookup<'a> {
+    type Error = MatchingReason;
+
+    fn source_attestation(&self) -> Option<Option<u64>> {
+        None
+    }
+
+    fn modules_attested(&self) -> bool {
+        true
+    }
+
+    fn reputation(&self) -> Option<BetaParameters<FixedU128>> {
+        None
+    }
+
+    fn recent_fulfillment_ratio(&self) -> Option<Permill> {
+        None
+    }
+
+    fn matched_schedules(&self) -> Result<Vec<(Schedule, u64)>, MatchingReason> {
+        Ok(self.matched_schedules.to_vec())
+    }
+}
+
+fn validate(now: u64, job: &Job, source: &Source) -> Result<SlotOutcome<Balance>, MatchingReason> {
+    validate_with_allowed_sources(now, job, source, &None)
+}
+
+fn validate_with_allowed_sources(
+    now: u64,
+    job: &Job,
+    source: &Source,
+    allowed_sources: &Option<AllowedSources<AccountId, CU32<4>>>,
+) -> Result<SlotOutcome<Balance>, MatchingReason> {
+    let requirements = JobRequirements {
+        slots: 1,
+        reward: job.reward,
+        min_reputation: None,
+        min_recent_fulfillment_ratio: None,
+        holdback_release_sla: None,
+        max_attestation_age_ms: None,
+        max_fee_per_execution: job.max_fee_per_execution,
+        depends_on: None,
+        preferred_sources: None,
+        preference_window_ms: 0,
+        omitted: None,
+        instant_match: None,
+        require_env_ack: false,
+        auto_renew: None,
+    };
+    let advertisement = AdvertisementRestriction {
+        max_memory: source.max_memory,
+        network_request_quota: source.network_request_quota,
+        storage_capacity: 0,
+        allowed_consumers: None,
+        available_modules: JobModules::default(),
+    };
+
+    validate_slot::<Test, _>(
+        &SlotCandidateInputs {
+            now,
+            consumer: &MultiOrigin::Acurast(alice_account_id()),
+            source: &processor_account_id(),
+            start_delay: job.start_delay,
+            expected_pricing_version: None,
+            schedule: &job.schedule,
+            memory: job.memory,
+            network_requests: job.network_requests,
+            storage: job.storage,
+            required_modules: &JobModules::default(),
+            allowed_sources,
+            allow_only_verified_sources: false,
+            requirements: &requirements,
+            registered_at: None,
+            advertisement: &advertisement,
+            pricing: &source.pricing,
+            pricing_version: 0,
+            capacity: source.capacity,
+        },
+        &SourceLookup {
+            matched_schedules: &source.matched_schedules,
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn accepted_slots_respect_invariants(now in 0u64..1_000_000, job in job(), source in source()) {
+        if let Ok(outcome) = validate(now, &job, &source) {
+            // fee never exceeds the reward
+            prop_assert!(outcome.fee_per_execution <= job.reward);
+            // fee never exceeds the consumer's maximum
+            if let Some(max_fee_per_execution) = job.max_fee_per_execution {
+                prop_assert!(outcome.fee_per_execution <= max_fee_per_execution);
+            }
+
+            // the source's scheduling window covers the job
+            let latest_end = job.schedule.end_time + job.start_delay;
+            match source.pricing.scheduling_window {
+                SchedulingWindow::End(end) => prop_assert!(end >= latest_end),
+                SchedulingWindow::Delta(delta) => prop_assert!(now + delta >= latest_end),
+            }
+
+            // capacity stays non-negative after the assignment
+            prop_assert!(outcome.remaining_capacity >= 0);
+            prop_assert_eq!(outcome.remaining_capacity, source.capacity - job.storage as i64);
+
+            prop_assert_eq!(outcome.execution_count, job.schedule.execution_count());
+        }
+    }
+
+    #[test]
+    fn already_matched_schedule_is_rejected(now in 0u64..1_000_000, job in job(), source in source()) {
+        let mut source = source;
+        source.matched_schedules.push((job.schedule.clone(), job.start_delay));
+
+        prop_assert!(validate(now, &job, &source).is_err());
+    }
+
+    #[test]
+    fn source_not_allowed_is_reported_first(now in 0u64..1_000_000, job in job(), source in source()) {
+        let mut source = source;
+        // make the slot fail the expensive checks as well
+        source.matched_schedules.push((job.schedule.clone(), job.start_delay));
+        source.capacity = -1;
+
+        prop_assert_eq!(
+            validate_with_allowed_sources(
+                now,
+                &job,
+                &source,
+                &Some(vec![bob_account_id()].try_into().unwrap())
+            ),
+            Err(MatchingReason::SourceNotAllowed)
+        );
+    }
+}
+
+#[test]
+fn multiply_failing_slot_reports_first_failing_check() {
+    let schedule = Schedule {
+        duration: 5000,
+        start_time: 1_671_800_400_000,
+        end_time: 1_671_804_000_000,
+        interval: 1_800_000,
+        max_start_delay: 5000,
+    };
+    let job = Job {
+        schedule: schedule.clone(),
+        start_delay: 0,
+        reward: 1,
+        max_fee_per_execution: None,
+        storage: 20_000,
+        memory: 5_000,
+        network_requests: 5,
+    };
+    let mut source = Source {
+        pricing: Pricing {
+            fee_per_millisecond: 1_000,
+            fee_per_storage_byte: 0,
+            base_fee_per_execution: 0,
+            scheduling_window: SchedulingWindow::End(0),
+        },
+        capacity: 0,
+        max_memory: 0,
+        network_request_quota: 0,
+        matched_schedules: vec![(schedule, 0)],
+    };
+
+    // fails memory, capacity, network quota, scheduling window, reward and schedule
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::MaxMemoryExceeded)
+    );
+
+    source.max_memory = 5_000;
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::InsufficientStorageCapacity)
+    );
+
+    source.capacity = 20_000;
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::NetworkRequestQuotaExceeded)
+    );
+
+    source.network_request_quota = 5;
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::SchedulingWindowExceeded)
+    );
+
+    source.pricing.scheduling_window = SchedulingWindow::End(4_133_977_199_000);
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::InsufficientReward)
+    );
+
+    source.pricing.fee_per_millisecond = 0;
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::ScheduleOverlap)
+    );
+}
+
+fn check_network_requests(
+    duration: u64,
+    network_request_quota: u8,
+    network_requests: u32,
+) -> Result<(), MatchingReason> {
+    let advertisement = AdvertisementRestriction {
+        max_memory: 0,
+        network_request_quota,
+        storage_capacity: 0,
+        allowed_consumers: None,
+        available_modules: JobModules::default(),
+    };
+    let schedule = Schedule {
+        duration,
+        start_time: 0,
+        end_time: duration,
+        interval: duration,
+        max_start_delay: 0,
+    };
+    check_network_request_quota_sufficient::<Test>(&advertisement, &schedule, network_requests)
+}
+
+#[test]
+fn network_request_quota_boundaries() {
+    // exactly sufficient: 8 requests per second during 5 seconds permit 40 requests per execution
+    assert_eq!(check_network_requests(5_000, 8, 40), Ok(()));
+    // one request over
+    assert_eq!(
+        check_network_requests(5_000, 8, 41),
+        Err(MatchingReason::NetworkRequestQuotaExceeded)
+    );
+    // fractions of a second count
+    assert_eq!(check_network_requests(1_500, 2, 3), Ok(()));
+    assert_eq!(
+        check_network_requests(1_499, 2, 3),
+        Err(MatchingReason::NetworkRequestQuotaExceeded)
+    );
+    // zero duration only permits jobs without network requests
+    assert_eq!(check_network_requests(0, 8, 0), Ok(()));
+    assert_eq!(
+        check_network_requests(0, 8, 1),
+        Err(MatchingReason::NetworkRequestQuotaExceeded)
+    );
+    // no quota only permits jobs without network requests
+    assert_eq!(check_network_requests(5_000, 0, 0), Ok(()));
+    assert_eq!(
+        check_network_requests(5_000, 0, 1),
+        Err(MatchingReason::NetworkRequestQuotaExceeded)
+    );
+    // overflow is reported instead of being treated as acceptance or rejection
+    assert_eq!(
+        check_network_requests(u64::MAX, 2, 1),
+        Err(MatchingReason::CalculationOverflow)
+    );
+    assert_eq!(check_network_requests(u64::MAX, 1, u32::MAX), Ok(()));
+}
+
+#[test]
+fn max_fee_per_execution_boundaries() {
+    let mut job = Job {
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000,
+            end_time: 1_671_804_000_000,
+            interval: 1_800_000,
+            max_start_delay: 5000,
+        },
+        start_delay: 0,
+        reward: 1_000,
+        max_fee_per_execution: Some(100),
+        storage: 0,
+        memory: 0,
+        network_requests: 0,
+    };
+    let source = Source {
+        pricing: Pricing {
+            fee_per_millisecond: 0,
+            fee_per_storage_byte: 0,
+            base_fee_per_execution: 100,
+            scheduling_window: SchedulingWindow::End(4_133_977_199_000),
+        },
+        capacity: 0,
+        max_memory: 0,
+        network_request_quota: 0,
+        matched_schedules: vec![],
+    };
+
+    // fee exactly at the consumer's maximum
+    assert_eq!(
+        validate(0, &job, &source).map(|outcome| outcome.fee_per_execution),
+        Ok(100)
+    );
+
+    // fee one above the consumer's maximum while still covered by the reward
+    job.max_fee_per_execution = Some(99);
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::FeeAboveConsumerMaximum)
+    );
+
+    // the reward is checked first
+    job.reward = 99;
+    assert_eq!(
+        validate(0, &job, &source),
+        Err(MatchingReason::InsufficientReward)
+    );
+}
@@ -0,0 +1,390 @@
+//! # Marketplace metrics
+//!
+//! Node-side Prometheus metrics of the marketplace intended for ops dashboards, driven by block finality.
+//!
+//! For every finalized block the events are read from storage and decoded to update
+//! - a counter per marketplace event variant,
+//! - histograms of matches, reports and failed matching proposals per block,
+//! - a gauge of the jobs currently open for matching.
+//!
+//! The metrics are only compiled with the `metrics` feature, which the node enables on its dependency on this pallet:
+//!
+//! ```toml
+//! pallet-acurast-marketplace = { path = "...", features = ["metrics"] }
+//! ```
+//!
+//! The node starts the metrics worker with the registry handle of its service configuration:
+//!
+//! ```ignore
+//! if let Some(registry) = config.prometheus_registry() {
+//!     let worker = MarketplaceMetricsWorker::<Runtime, _, _, _>::new(client.clone(), registry)?;
+//!     task_manager
+//!         .spawn_handle()
+//!         .spawn("marketplace-metrics", None, worker.run());
+//! }
+//! ```
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Decode, Encode};
+use frame_support::{
+    sp_runtime::{traits::Block, DispatchError},
+    storage::{storage_prefix, StoragePrefixedMap},
+    traits::PalletInfo,
+};
+use frame_system::EventRecord;
+use futures::StreamExt;
+use log::{debug, warn};
+use prometheus_endpoint::{
+    exponential_buckets, register, CounterVec, Gauge, Histogram, HistogramOpts, Opts,
+    PrometheusError, Registry, U64,
+};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use scale_info::{TypeDef, TypeInfo};
+use sp_core::storage::StorageKey;
+
+use crate::{Config, Error, Event, JobStatus, Pallet, StoredJobStatus};
+
+/// Logging target for the marketplace metrics.
+pub const LOG_TARGET: &str = "marketplace-metrics";
+
+/// The marketplace relevant contents of a single block.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BlockSummary {
+    /// The variant names of all marketplace events in the block.
+    pub events: Vec<&'static str>,
+    /// The number of jobs matched in the block.
+    pub matches: u64,
+    /// The number of reports in the block.
+    pub reports: u64,
+    /// The number of extrinsics that failed in the block with a marketplace error caused by an invalid matching proposal.
+    pub failed_proposals: u64,
+}
+
+impl BlockSummary {
+    /// Summarizes the `events` of a single block.
+    pub fn from_events<T: Config>(
+        events: impl IntoIterator<Item = <T as frame_system::Config>::RuntimeEvent>,
+    ) -> Self
+    where
+        <T as frame_system::Config>::RuntimeEvent:
+            TryInto<Event<T>> + TryInto<frame_system::Event<T>>,
+    {
+        let marketplace_index = <T as frame_system::Config>::PalletInfo::index::<Pallet<T>>();
+
+        let mut summary = Self::default();
+        for event in events {
+            if let Ok(event) = TryInto::<Event<T>>::try_into(event.clone()) {
+                match &event {
+                    Event::JobRegistrationMatched(_) => summary.matches += 1,
+                    Event::Reported(..) => summary.reports += 1,
+                    _ => {}
+                }
+                summary.events.push(event_name(&event));
+            } else if let Ok(frame_system::Event::ExtrinsicFailed {
+                dispatch_error: DispatchError::Module(error),
+                ..
+            }) = TryInto::<frame_system::Event<T>>::try_into(event)
+            {
+                if Some(error.index as usize) != marketplace_index {
+                    continue;
+                }
+                if Error::<T>::decode(&mut &error.error[..])
+                    .map(|e| e.is_matching_error())
+                    .unwrap_or(false)
+                {
+                    summary.failed_proposals += 1;
+                }
+            }
+        }
+        summary
+    }
+}
+
+/// Returns the variant name of a marketplace event, looked up from its type info.
+fn event_name<T: Config>(event: &Event<T>) -> &'static str {
+    let index = event.encode().first().copied();
+    match Event::<T>::type_info().type_def {
+        TypeDef::Variant(def) => def
+            .variants
+            .iter()
+            .find(|variant| Some(variant.index) == index)
+            .map(|variant| variant.name)
+            .unwrap_or("Unknown"),
+        _ => "Unknown",
+    }
+}
+
+/// The Prometheus metrics of the marketplace.
+#[derive(Clone)]
+pub struct MarketplaceMetrics {
+    events: CounterVec<U64>,
+    open_jobs: Gauge<U64>,
+    matches_per_block: Histogram,
+    reports_per_block: Histogram,
+    failed_proposals_per_block: Histogram,
+}
+
+impl MarketplaceMetrics {
+    /// Creates the metrics and registers them with `registry`.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        let per_block = |name: &str, help: &str| -> Result<Histogram, PrometheusError> {
+            register(
+                Histogram::with_opts(
+                    HistogramOpts::new(name, help).buckets(exponential_buckets(1.0, 2.0, 10)?),
+                )?,
+                registry,
+            )
+        };
+
+        Ok(Self {
+            events: register(
+                CounterVec::new(
+                    Opts::new(
+                        "acurast_marketplace_events_total",
+                        "Number of marketplace events in finalized blocks",
+                    ),
+                    &["event"],
+                )?,
+                registry,
+            )?,
+            open_jobs: register(
+                Gauge::new(
+                    "acurast_marketplace_open_jobs",
+                    "Number of jobs open for matching at the last finalized block",
+                )?,
+                registry,
+            )?,
+            matches_per_block: per_block(
+                "acurast_marketplace_matches_per_block",
+                "Number of jobs matched per finalized block",
+            )?,
+            reports_per_block: per_block(
+                "acurast_marketplace_reports_per_block",
+                "Number of reports per finalized block",
+            )?,
+            failed_proposals_per_block: per_block(
+                "acurast_marketplace_failed_proposals_per_block",
+                "Number of failed matching proposals per finalized block",
+            )?,
+        })
+    }
+
+    /// Updates the metrics with the summary of a finalized block.
+    pub fn observe_block(&self, summary: &BlockSummary) {
+        for event in &summary.events {
+            self.events.with_label_values(&[*event]).inc();
+        }
+        self.matches_per_block.observe(summary.matches as f64);
+        self.reports_per_block.observe(summary.reports as f64);
+        self.failed_proposals_per_block
+            .observe(summary.failed_proposals as f64);
+    }
+
+    /// Sets the number of jobs open for matching.
+    pub fn set_open_jobs(&self, open_jobs: u64) {
+        self.open_jobs.set(open_jobs);
+    }
+}
+
+/// Updates the [`MarketplaceMetrics`] for every finalized block.
+pub struct MarketplaceMetricsWorker<T, B, BE, C> {
+    client: Arc<C>,
+    metrics: MarketplaceMetrics,
+
+    _phantom: PhantomData<(T, B, BE)>,
+}
+
+impl<T, B, BE, C> MarketplaceMetricsWorker<T, B, BE, C>
+where
+    T: Config,
+    <T as frame_system::Config>::RuntimeEvent: TryInto<Event<T>> + TryInto<frame_system::Event<T>>,
+    B: Block,
+    BE: Backend<B>,
+    C: BlockchainEvents<B> + StorageProvider<B, BE>,
+{
+    /// Creates the worker, registering its metrics with the node's `registry`.
+    pub fn new(client: Arc<C>, registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            client,
+            metrics: MarketplaceMetrics::register(registry)?,
+            _phantom: Default::default(),
+        })
+    }
+
+    /// Runs the worker until the finality notification stream ends.
+    pub async fn run(self) {
+        let mut finality_notifications = self.client.finality_notification_stream();
+        while let Some(notification) = finality_notifications.next().await {
+            // the tree route contains the implicitly finalized blocks before the notified block
+            for hash in notification
+                .tree_route
+                .iter()
+                .chain(std::iter::once(&notification.hash))
+            {
+                match self.block_events(*hash) {
+                    Ok(events) => self
+                        .metrics
+                        .observe_block(&BlockSummary::from_events::<T>(events)),
+                    Err(e) => warn!(
+                        target: LOG_TARGET,
+                        "Reading events of block {:?} failed: {:?}", hash, e
+                    ),
+                }
+            }
+
+            match self.open_jobs(notification.hash) {
+                Ok(open_jobs) => self.metrics.set_open_jobs(open_jobs),
+                Err(e) => warn!(
+                    target: LOG_TARGET,
+                    "Counting open jobs at block {:?} failed: {:?}", notification.hash, e
+                ),
+            }
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Finality notifications stream closed, stopping marketplace metrics."
+        );
+    }
+
+    fn block_events(
+        &self,
+        hash: B::Hash,
+    ) -> Result<Vec<<T as frame_system::Config>::RuntimeEvent>, sp_blockchain::Error> {
+        let system = <T as frame_system::Config>::PalletInfo::name::<frame_system::Pallet<T>>()
+            .unwrap_or("System");
+        let key = StorageKey(storage_prefix(system.as_bytes(), b"Events").to_vec());
+
+        let Some(data) = self.client.storage(hash, &key)? else {
+            return Ok(Vec::new());
+        };
+        let records = Vec::<
+            EventRecord<
+                <T as frame_system::Config>::RuntimeEvent,
+                <T as frame_system::Config>::Hash,
+            >,
+        >::decode(&mut &data.0[..])
+        .map_err(|e| sp_blockchain::Error::Storage(e.to_string()))?;
+
+        Ok(records.into_iter().map(|record| record.event).collect())
+    }
+
+    fn open_jobs(&self, hash: B::Hash) -> Result<u64, sp_blockchain::Error> {
+        let prefix = StorageKey(<StoredJobStatus<T>>::final_prefix().to_vec());
+
+        let mut open_jobs = 0u64;
+        for (_, data) in self.client.storage_pairs(hash, Some(&prefix), None)? {
+            if matches!(JobStatus::decode(&mut &data.0[..]), Ok(JobStatus::Open)) {
+                open_jobs += 1;
+            }
+        }
+        Ok(open_jobs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use frame_support::sp_runtime::DispatchError;
+    use pallet_acurast::MultiOrigin;
+    use prometheus_endpoint::Registry;
+
+    use crate::{mock::*, stub::*, Assignment, Error, Event, Match, SLA};
+
+    use super::*;
+
+    fn assignment() -> Assignment<Balance> {
+        Assignment {
+            slot: 0,
+            start_delay: 0,
+            fee_per_execution: 1_000,
+            acknowledged: true,
+            sla: SLA { total: 2, met: 1 },
+            pub_keys: Default::default(),
+            holdback: 0,
+            environment_ack: None,
+        }
+    }
+
+    fn extrinsic_failed(error: DispatchError) -> RuntimeEvent {
+        RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+            dispatch_error: error,
+            dispatch_info: Default::default(),
+        })
+    }
+
+    fn block() -> Vec<RuntimeEvent> {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        vec![
+            RuntimeEvent::AcurastMarketplace(Event::JobRegistrationMatched(Match {
+                job_id: job_id.clone(),
+                sources: Default::default(),
+            })),
+            RuntimeEvent::AcurastMarketplace(Event::Reported(
+                job_id.clone(),
+                processor_account_id(),
+                assignment(),
+            )),
+            RuntimeEvent::AcurastMarketplace(Event::Reported(
+                job_id.clone(),
+                processor_2_account_id(),
+                assignment(),
+            )),
+            RuntimeEvent::AcurastMarketplace(Event::JobFinalized(job_id)),
+            // failed matching proposal
+            extrinsic_failed(Error::<Test>::ScheduleOverlapInMatch.into()),
+            // failed with a marketplace error that is not a matching error
+            extrinsic_failed(Error::<Test>::JobStatusNotFound.into()),
+            // failed outside the marketplace
+            extrinsic_failed(DispatchError::BadOrigin),
+        ]
+    }
+
+    #[test]
+    fn summarizes_block_events() {
+        let summary = BlockSummary::from_events::<Test>(block());
+
+        assert_eq!(
+            BlockSummary {
+                events: vec![
+                    "JobRegistrationMatched",
+                    "Reported",
+                    "Reported",
+                    "JobFinalized"
+                ],
+                matches: 1,
+                reports: 2,
+                failed_proposals: 1,
+            },
+            summary
+        );
+    }
+
+    #[test]
+    fn observes_block_summaries() {
+        let registry = Registry::new();
+        let metrics = MarketplaceMetrics::register(&registry).unwrap();
+
+        metrics.observe_block(&BlockSummary::from_events::<Test>(block()));
+        metrics.observe_block(&BlockSummary::from_events::<Test>(vec![]));
+        metrics.set_open_jobs(3);
+
+        assert_eq!(2, metrics.events.with_label_values(&["Reported"]).get());
+        assert_eq!(
+            1,
+            metrics
+                .events
+                .with_label_values(&["JobRegistrationMatched"])
+                .get()
+        );
+        assert_eq!(0, metrics.events.with_label_values(&["JobCancelled"]).get());
+        assert_eq!(2, metrics.matches_per_block.get_sample_count());
+        assert_eq!(1.0, metrics.matches_per_block.get_sample_sum());
+        assert_eq!(2.0, metrics.reports_per_block.get_sample_sum());
+        assert_eq!(1.0, metrics.failed_proposals_per_block.get_sample_sum());
+        assert_eq!(3, metrics.open_jobs.get());
+
+        // all metrics are exposed through the registry
+        assert_eq!(5, registry.gather().len());
+    }
+}
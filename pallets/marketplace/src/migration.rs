@@ -1,10 +1,15 @@
 #![allow(deprecated)]
 
+use codec::DecodeAll;
+#[cfg(any(feature = "try-runtime", test))]
+use frame_support::sp_runtime::TryRuntimeError;
 use frame_support::{
-    traits::{GetStorageVersion, StorageVersion},
+    storage::{migration::put_storage_value, storage_prefix, PrefixIterator},
+    traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
     weights::Weight,
+    BoundedVec,
 };
-use pallet_acurast::JobModules;
+use pallet_acurast::{JobModules, JobRegistrationCore};
 use sp_core::Get;
 
 use super::*;
@@ -28,15 +33,87 @@ pub mod v1 {
     }
 }
 
+/// The layouts of the last released storage version 4.
+pub mod v4 {
+    use frame_support::pallet_prelude::*;
+    use pallet_acurast::{JobId, ParameterBound};
+
+    use crate::{Config, Pallet, PubKeys, SLA};
+
+    /// The job matches holding [`Assignment`]s in the layout of version 4.
+    #[frame_support::storage_alias]
+    pub type StoredMatches<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Blake2_128Concat,
+        JobId<<T as frame_system::Config>::AccountId>,
+        Assignment<<T as Config>::Balance>,
+    >;
+
+    /// An assignment before fees could be held back and environment receipts could be acknowledged.
+    #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+    pub struct Assignment<Reward> {
+        pub slot: u8,
+        pub start_delay: u64,
+        pub fee_per_execution: Reward,
+        pub acknowledged: bool,
+        pub sla: SLA,
+        pub pub_keys: PubKeys,
+    }
+
+    /// A planned execution before the pricing version got checked.
+    #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+    pub struct PlannedExecution<AccountId> {
+        pub source: AccountId,
+        pub start_delay: u64,
+    }
+
+    /// The job requirements before any of the optional requirements were added.
+    #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+    pub struct JobRequirements<Reward, AccountId, MaxSlots: ParameterBound> {
+        pub slots: u8,
+        pub reward: Reward,
+        pub min_reputation: Option<u128>,
+        pub instant_match: Option<BoundedVec<PlannedExecution<AccountId>, MaxSlots>>,
+    }
+}
+
+/// Migrates the marketplace storage to [`STORAGE_VERSION`].
+///
+/// Only storage version 4 got released, so the layouts of the versions in between are not migrated step by step but
+/// translated from the layout of version 4 directly, see [`v4`].
+///
+/// The job requirements are stored in the registrations of `pallet_acurast`, whose migration to its storage version 5
+/// splits them off the registrations' payload. That migration has to run first, so runtimes must list
+/// `pallet_acurast` before this pallet in the executive's migrations (as `construct_runtime!` does for
+/// `AllPalletsWithSystem` if `pallet_acurast` is declared first). Otherwise nothing is migrated and an error is
+/// logged, so that the migration is retried with the next runtime upgrade.
 pub fn migrate<T: Config>() -> Weight {
-    let migrations: [(u16, &dyn Fn() -> Weight); 3] = [
+    let migrations: [(u16, &dyn Fn() -> Weight); 8] = [
         (2, &migrate_to_v2::<T>),
         (3, &migrate_to_v3::<T>),
         (4, &migrate_to_v4::<T>),
+        (5, &migrate_to_v5::<T>),
+        (8, &migrate_to_v8::<T>),
+        (9, &migrate_to_v9::<T>),
+        (10, &migrate_to_v10::<T>),
+        (20, &migrate_to_v20::<T>),
     ];
 
     let onchain_version = Pallet::<T>::on_chain_storage_version();
-    let mut weight: Weight = Default::default();
+    if onchain_version < STORAGE_VERSION
+        && pallet_acurast::Pallet::<T>::on_chain_storage_version() < StorageVersion::new(5)
+    {
+        log::error!(
+            target: "runtime::acurast_marketplace",
+            "pallet_acurast not migrated to storage version 5 yet, skipping migration from {:?}",
+            onchain_version
+        );
+        return T::DbWeight::get().reads(2);
+    }
+
+    let mut weight: Weight = T::DbWeight::get().reads(2);
     for (i, f) in migrations.into_iter() {
         if onchain_version < StorageVersion::new(i) {
             weight += f();
@@ -81,3 +158,256 @@ fn migrate_to_v4<T: Config>() -> Weight {
     // clear again all storages since we want to clear at the same time as pallet acurast for consistent state
     migrate_to_v3::<T>()
 }
+
+fn migrate_to_v5<T: Config>() -> Weight {
+    // pricing keys are not reversible, but every source that ever advertised has a reputation entry
+    let mut reads = 0u64;
+    let mut writes = 0u64;
+    for source in StoredReputation::<T>::iter_keys() {
+        reads += 2;
+        if StoredAdvertisementPricing::<T>::contains_key(&source) {
+            StoredAdvertisementPricingVersion::<T>::insert(&source, 1);
+            writes += 1;
+        }
+    }
+    T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+}
+
+/// Translates the [`StoredMatches`] from the layout of version 4, without fees held back or environment receipts
+/// acknowledged, and records the slots of acknowledged matches in [`AcknowledgedSlots`].
+fn migrate_to_v8<T: Config>() -> Weight {
+    let mut reads = 0u64;
+    let mut writes = 0u64;
+    StoredMatches::<T>::translate::<v4::Assignment<T::Balance>, _>(|_, job_id, assignment| {
+        reads += 1;
+        writes += 1;
+        if assignment.acknowledged {
+            AcknowledgedSlots::<T>::mutate(&job_id, |slots| {
+                if let Err(index) = slots.binary_search(&assignment.slot) {
+                    let _ = slots.try_insert(index, assignment.slot);
+                }
+            });
+            reads += 1;
+            writes += 1;
+        }
+        Some(Assignment {
+            slot: assignment.slot,
+            start_delay: assignment.start_delay,
+            fee_per_execution: assignment.fee_per_execution,
+            acknowledged: assignment.acknowledged,
+            sla: assignment.sla,
+            pub_keys: assignment.pub_keys,
+            holdback: 0u8.into(),
+            environment_ack: None,
+        })
+    });
+    // slots finalized by their source are no longer in `StoredMatches`, so the acknowledged count of the old status
+    // is completed with slots lacking an assignment
+    for (consumer, sequence, status) in StoredJobStatus::<T>::iter() {
+        reads += 1;
+        let JobStatus::Assigned(count) = status else {
+            continue;
+        };
+        let job_id = (consumer, sequence);
+        let mut slots = AcknowledgedSlots::<T>::get(&job_id);
+        let assigned: Vec<u8> = AssignedProcessors::<T>::iter_key_prefix(&job_id)
+            .filter_map(|source| StoredMatches::<T>::get(&source, &job_id))
+            .map(|assignment| assignment.slot)
+            .collect();
+        reads += 1 + 2 * assigned.len() as u64;
+        let mut slot = 0u8;
+        while (slots.len() as u8) < count && slot < u8::MAX {
+            if !assigned.contains(&slot) {
+                if let Err(index) = slots.binary_search(&slot) {
+                    if slots.try_insert(index, slot).is_err() {
+                        break;
+                    }
+                }
+            }
+            slot += 1;
+        }
+        StoredJobStatus::<T>::insert(&job_id.0, &job_id.1, JobStatus::Assigned(slots.len() as u8));
+        AcknowledgedSlots::<T>::insert(&job_id, slots);
+        writes += 2;
+    }
+    T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+}
+
+/// Fills the [`AdvertisementIndex`] from the keys of [`StoredReputation`], which is initialized on every advertisement.
+fn migrate_to_v9<T: Config>() -> Weight {
+    let mut reads = 0u64;
+    let mut writes = 0u64;
+    for source in StoredReputation::<T>::iter_keys() {
+        reads += 2;
+        if StoredAdvertisementRestriction::<T>::contains_key(&source) {
+            AdvertisementIndex::<T>::insert(&source, ());
+            writes += 1;
+        }
+    }
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Builds [`SourceJobIndex`] from the existing matches.
+///
+/// The order the matches got stored in is unknown, so existing matches are indexed in the iteration order of
+/// [`StoredMatches`]. [`Config::MaxAssignedJobsPerSource`] has to cover the matches of every source, which
+/// [`pre_upgrade`] checks. Matches exceeding it nevertheless cannot be indexed and are counted and logged.
+fn migrate_to_v10<T: Config>() -> Weight {
+    let mut reads = 0u64;
+    let mut writes = 0u64;
+    let mut overflow = 0u64;
+    for (source, job_id, _) in StoredMatches::<T>::iter() {
+        reads += 2;
+        writes += 1;
+        SourceJobIndex::<T>::mutate(&source, |jobs| {
+            if jobs.try_push(job_id.clone()).is_err() {
+                overflow += 1;
+                log::error!(
+                    target: "runtime::acurast_marketplace",
+                    "source job index of {:?} full, match of job {:?} not indexed",
+                    source,
+                    job_id
+                );
+            }
+        });
+    }
+    if overflow > 0 {
+        log::error!(
+            target: "runtime::acurast_marketplace",
+            "{} matches exceed MaxAssignedJobsPerSource and were not indexed",
+            overflow
+        );
+    }
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Ensures [`Config::MaxAssignedJobsPerSource`] covers the existing matches of every source before they get indexed
+/// by [`migrate_to_v10`], returning the number of matches to be checked by [`post_upgrade`].
+#[cfg(any(feature = "try-runtime", test))]
+pub fn pre_upgrade<T: Config>() -> Result<sp_std::vec::Vec<u8>, TryRuntimeError> {
+    let mut matches = 0u64;
+    let mut per_source = sp_std::collections::btree_map::BTreeMap::<T::AccountId, u32>::new();
+    for (source, _) in StoredMatches::<T>::iter_keys() {
+        matches += 1;
+        *per_source.entry(source).or_default() += 1;
+    }
+    if Pallet::<T>::on_chain_storage_version() < StorageVersion::new(10) {
+        let max = per_source.values().copied().max().unwrap_or(0);
+        frame_support::ensure!(
+            max <= <T as Config>::MaxAssignedJobsPerSource::get(),
+            "MaxAssignedJobsPerSource does not cover the existing matches of a source"
+        );
+    }
+    Ok(codec::Encode::encode(&matches))
+}
+
+/// Ensures no match got lost by the migration and all of them are indexed.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn post_upgrade<T: Config>(state: sp_std::vec::Vec<u8>) -> Result<(), TryRuntimeError> {
+    let matches =
+        <u64 as codec::Decode>::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+    frame_support::ensure!(
+        StoredMatches::<T>::iter_keys().count() as u64 == matches,
+        "matches lost by the migration"
+    );
+    Pallet::<T>::do_try_state()
+}
+
+/// Translates the requirements in the extra of all [`pallet_acurast::StoredJobRegistration`]s from the layout of
+/// version 4 to the current [`JobRequirements`], without any of the requirements added since.
+///
+/// Expects [`Config::RegistrationExtra`] to encode like the bare requirements, as [`RegistrationExtra`] does, and
+/// `pallet_acurast` to be migrated to its storage version 5 already, see [`migrate`]. Registrations not decoding as
+/// the old layout are left as they are, counted and logged.
+fn migrate_to_v20<T: Config>() -> Weight {
+    let pallet = <pallet_acurast::Pallet<T> as PalletInfoAccess>::name().as_bytes();
+    let item = b"StoredJobRegistration";
+    let prefix = storage_prefix(pallet, item);
+    let registrations: Vec<(Vec<u8>, Vec<u8>)> =
+        PrefixIterator::<_>::new(prefix.to_vec(), prefix.to_vec(), |key, value| {
+            Ok((key.to_vec(), value.to_vec()))
+        })
+        .collect();
+
+    let reads = registrations.len() as u64;
+    let mut writes = 0u64;
+    let mut failures = 0u64;
+    for (key, value) in registrations {
+        let core = match JobRegistrationCore::<
+            v4::JobRequirements<T::Balance, T::AccountId, <T as pallet_acurast::Config>::MaxSlots>,
+        >::decode_all(&mut &value[..])
+        {
+            Ok(core) => core,
+            Err(e) => {
+                failures += 1;
+                log::warn!(
+                    target: "runtime::acurast_marketplace",
+                    "job registration {:?} not migrated, failed to decode its requirements: {:?}",
+                    key,
+                    e
+                );
+                continue;
+            }
+        };
+        let requirements = core.extra;
+        put_storage_value(
+            pallet,
+            item,
+            &key,
+            JobRegistrationCore {
+                allow_only_verified_sources: core.allow_only_verified_sources,
+                schedule: core.schedule,
+                memory: core.memory,
+                network_requests: core.network_requests,
+                storage: core.storage,
+                required_modules: core.required_modules,
+                extra: requirements_extra::<T>(JobRequirements {
+                    slots: requirements.slots,
+                    reward: requirements.reward,
+                    min_reputation: requirements.min_reputation,
+                    min_recent_fulfillment_ratio: None,
+                    holdback_release_sla: None,
+                    max_attestation_age_ms: None,
+                    max_fee_per_execution: None,
+                    depends_on: None,
+                    preferred_sources: None,
+                    preference_window_ms: 0,
+                    omitted: None,
+                    // instant matches of existing jobs are not checked against the pricing version
+                    instant_match: requirements.instant_match.map(|instant_match| {
+                        BoundedVec::truncate_from(
+                            instant_match
+                                .into_iter()
+                                .map(|planned_execution| PlannedExecution {
+                                    source: planned_execution.source,
+                                    start_delay: planned_execution.start_delay,
+                                    pricing_version: None,
+                                })
+                                .collect(),
+                        )
+                    }),
+                    require_env_ack: false,
+                    auto_renew: None,
+                }),
+            },
+        );
+        writes += 1;
+    }
+    if failures > 0 {
+        log::error!(
+            target: "runtime::acurast_marketplace",
+            "{} of {} job registrations failed to decode and were not migrated",
+            failures,
+            reads
+        );
+    }
+    T::DbWeight::get().reads_writes(reads + 1, writes)
+}
+
+/// Converts requirements of the current layout into the configured extra of `pallet_acurast`.
+fn requirements_extra<T: Config>(
+    requirements: JobRequirementsFor<T>,
+) -> <T as pallet_acurast::Config>::RegistrationExtra {
+    let e: <T as Config>::RegistrationExtra = requirements.into();
+    e.into()
+}
@@ -1,13 +1,20 @@
-use frame_support::sp_runtime::traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256};
-use frame_support::sp_runtime::DispatchError;
-use frame_support::sp_runtime::{BuildStorage, Percent};
-use frame_support::{parameter_types, traits::Everything, PalletId};
+use frame_support::sp_runtime::testing::TestXt;
+use frame_support::sp_runtime::traits::{
+    AccountIdConversion, AccountIdLookup, BlakeTwo256, Extrinsic as ExtrinsicT, Verify,
+};
+use frame_support::sp_runtime::{DispatchError, MultiSignature};
+use frame_support::sp_runtime::{Percent, Permill};
+use frame_support::{
+    dispatch::DispatchResultWithPostInfo, parameter_types, traits::Everything, PalletId,
+};
 use sp_core::*;
 use sp_io;
 use sp_std::prelude::*;
 
+use acurast_test_utils::{events_of, GenesisBuilder};
 use pallet_acurast::{
-    CertificateRevocationListUpdate, JobModules, RevocationListUpdateBarrier, CU32,
+    AcurastLimits, CertificateRevocationListUpdate, JobId, JobModules, RevocationListUpdateBarrier,
+    CU32,
 };
 
 use crate::stub::*;
@@ -46,38 +53,24 @@ pub struct ExtBuilder;
 
 impl ExtBuilder {
     pub fn build(self) -> sp_io::TestExternalities {
-        let mut t = frame_system::GenesisConfig::<Test>::default()
-            .build_storage()
-            .unwrap();
-
-        let parachain_info_config = parachain_info::GenesisConfig {
-            parachain_id: 2000.into(),
-            ..Default::default()
-        };
-
-        <parachain_info::GenesisConfig<Test> as BuildStorage>::assimilate_storage(
-            &parachain_info_config,
-            &mut t,
-        )
-        .unwrap();
-
-        pallet_balances::GenesisConfig::<Test> {
-            balances: vec![
-                (alice_account_id(), 100_000_000),
-                (charlie_account_id(), 100_000_000),
-                (pallet_fees_account(), INITIAL_BALANCE),
-                (pallet_acurast_acount(), INITIAL_BALANCE),
-                (bob_account_id(), INITIAL_BALANCE),
-                (processor_account_id(), INITIAL_BALANCE),
-                (processor_2_account_id(), INITIAL_BALANCE),
-            ],
-        }
-        .assimilate_storage(&mut t)
-        .unwrap();
-
-        let mut ext = sp_io::TestExternalities::new(t);
-        ext.execute_with(|| System::set_block_number(1));
-        ext
+        GenesisBuilder::<Test>::new()
+            .with(&parachain_info::GenesisConfig::<Test> {
+                parachain_id: 2000.into(),
+                ..Default::default()
+            })
+            .with(&pallet_acurast::GenesisConfig::<Test>::default())
+            .fund_all([alice_account_id(), charlie_account_id()], 100_000_000)
+            .fund_all(
+                [
+                    pallet_fees_account(),
+                    pallet_acurast_acount(),
+                    bob_account_id(),
+                    processor_account_id(),
+                    processor_2_account_id(),
+                ],
+                INITIAL_BALANCE,
+            )
+            .build()
     }
 }
 
@@ -112,6 +105,23 @@ parameter_types! {
     pub const AcurastPalletId: PalletId = PalletId(*b"acrstpid");
     pub const HyperdrivePalletId: PalletId = PalletId(*b"hypdrpid");
     pub const ReportTolerance: u64 = 12000;
+    pub static JobRegistrationDeposit: Balance = 0;
+    pub const PendingPayoutTimeout: u64 = 600_000;
+    pub const ScheduleChangeTimeout: u64 = 600_000;
+    pub const DeliveryChallengeWindow: u64 = 3_600_000;
+    pub static MinSchedulingWindow: u64 = 3_600_000;
+    pub const ImmediatePayoutRatio: Permill = Permill::from_percent(40);
+    pub static ProcessorsWithoutManager: Vec<AccountId> = vec![];
+    pub static RewardPaymentFails: bool = false;
+    pub static MaxExecutionsPerJob: u64 = 6_308_000; // run a job every 5 seconds for a year
+    pub static AttestedModules: JobModules = Default::default();
+    pub static RestrictedMatching: bool = false;
+    pub static MarginEstimateSampleSize: u32 = 16;
+    pub static EnableOffchainMatcher: bool = false;
+    pub static MaxOffchainMatchesPerBlock: u32 = 4;
+    pub static MaxOffchainMatchScan: u32 = 16;
+    pub static MaxOffchainSourceScan: u32 = 16;
+    pub static FinalizedJobs: Vec<(JobId<AccountId>, Balance, bool)> = vec![];
 }
 
 impl frame_system::Config for Test {
@@ -140,6 +150,35 @@ impl frame_system::Config for Test {
     type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = <MultiSignature as Verify>::Signer;
+    type Signature = MultiSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        _public: Self::Public,
+        _account: AccountId,
+        nonce: u32,
+    ) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (nonce.into(), ())))
+    }
+}
+
 impl pallet_timestamp::Config for Test {
     type Moment = u64;
     type OnTimestampSet = ();
@@ -173,6 +212,9 @@ impl pallet_acurast::Config for Test {
     type RegistrationExtra = JobRequirementsFor<Self>;
     type MaxAllowedSources = CU32<4>;
     type MaxCertificateRevocationListUpdates = frame_support::traits::ConstU32<10>;
+    type MaxAppIdLen = frame_support::traits::ConstU32<128>;
+    type MaxAllowedAppIds = frame_support::traits::ConstU32<4>;
+    type MaxTrustedRoots = frame_support::traits::ConstU32<4>;
     type MaxSlots = CU32<64>;
     type PalletId = AcurastPalletId;
     type MaxEnvVars = CU32<10>;
@@ -198,7 +240,17 @@ impl pallet_acurast::BenchmarkHelper<Test> for TestBenchmarkHelper {
             slots: 1,
             reward: 1,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         }
     }
 
@@ -216,10 +268,104 @@ impl ManagerProvider<Test> for ManagerOf {
     fn manager_of(
         owner: &<Test as frame_system::Config>::AccountId,
     ) -> Result<<Test as frame_system::Config>::AccountId, DispatchError> {
+        if ProcessorsWithoutManager::get().contains(owner) {
+            return Err(DispatchError::Other("no manager"));
+        }
         Ok(owner.clone())
     }
 }
 
+type AssetRewardManagerImpl = AssetRewardManager<FeeManagerImpl, Balances, Pallet<Test>>;
+
+/// Delegates to [`AssetRewardManager`] but fails paying rewards to managers while [`RewardPaymentFails`] is set.
+pub struct MockRewardManager;
+
+impl RewardManager<Test> for MockRewardManager {
+    fn lock_reward(job_id: &JobId<AccountId>, reward: Balance) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::lock_reward(job_id, reward)
+    }
+
+    fn pay_reward(
+        job_id: &JobId<AccountId>,
+        reward: Balance,
+        target: &AccountId,
+    ) -> Result<(), DispatchError> {
+        // escrow first so a failure leaves storage modified, as a failing transfer would
+        <AssetRewardManagerImpl as RewardManager<Test>>::escrow_reward(job_id, reward)?;
+        Self::pay_escrowed_reward(reward, target)
+    }
+
+    fn matcher_reward(remaining_reward: Balance) -> Balance {
+        <AssetRewardManagerImpl as RewardManager<Test>>::matcher_reward(remaining_reward)
+    }
+
+    fn pay_matcher_reward(
+        matcher_rewards: Vec<(JobId<AccountId>, Balance)>,
+        matcher: &AccountId,
+    ) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::pay_matcher_reward(
+            matcher_rewards,
+            matcher,
+        )
+    }
+
+    fn escrow_reward(job_id: &JobId<AccountId>, reward: Balance) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::escrow_reward(job_id, reward)
+    }
+
+    fn pay_escrowed_reward(reward: Balance, target: &AccountId) -> Result<(), DispatchError> {
+        if RewardPaymentFails::get() {
+            return Err(DispatchError::Other("reward payment failed"));
+        }
+        <AssetRewardManagerImpl as RewardManager<Test>>::pay_escrowed_reward(reward, target)
+    }
+
+    fn refund(job_id: &JobId<AccountId>) -> Result<Balance, DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::refund(job_id)
+    }
+
+    fn unlock_reward(job_id: &JobId<AccountId>, amount: Balance) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::unlock_reward(job_id, amount)
+    }
+
+    fn lock_deposit(who: &AccountId, deposit: Balance) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::lock_deposit(who, deposit)
+    }
+
+    fn refund_deposit(who: &AccountId, deposit: Balance) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::refund_deposit(who, deposit)
+    }
+
+    fn burn_deposit(deposit: Balance) -> Result<(), DispatchError> {
+        <AssetRewardManagerImpl as RewardManager<Test>>::burn_deposit(deposit)
+    }
+}
+
+/// Records the calls to [`MarketplaceHooks::finalize_job`] in [`FinalizedJobs`].
+pub struct MockMarketplaceHooks;
+
+impl MarketplaceHooks<Test> for MockMarketplaceHooks {
+    fn assign_job(_job_id: &JobId<AccountId>, _pub_keys: &PubKeys) -> DispatchResultWithPostInfo {
+        Ok(().into())
+    }
+
+    fn finalize_job(
+        job_id: &JobId<AccountId>,
+        refund: Balance,
+        failed: bool,
+    ) -> DispatchResultWithPostInfo {
+        FinalizedJobs::mutate(|jobs| jobs.push((job_id.clone(), refund, failed)));
+        Ok(().into())
+    }
+}
+
+/// Implements [`pallet_acurast::AcurastConfigApi::limits`] like a runtime including both pallets.
+pub fn acurast_limits() -> AcurastLimits {
+    let mut limits = Acurast::limits();
+    AcurastMarketplace::extend_limits(&mut limits);
+    limits
+}
+
 pub struct ProcessorLastSeenProvider;
 
 impl crate::traits::ProcessorLastSeenProvider<Test> for ProcessorLastSeenProvider {
@@ -230,18 +376,40 @@ impl crate::traits::ProcessorLastSeenProvider<Test> for ProcessorLastSeenProvide
 
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
-    type MaxAllowedConsumers = pallet_acurast::CU32<4>;
+    // deliberately differs from `MaxAllowedSources` so that mixing up the bounds fails tests
+    type MaxAllowedConsumers = pallet_acurast::CU32<8>;
     type MaxProposedMatches = frame_support::traits::ConstU32<10>;
     type MaxFinalizeJobs = frame_support::traits::ConstU32<10>;
+    type MaxSLAHistory = frame_support::traits::ConstU32<3>;
+    type MaxDependents = frame_support::traits::ConstU32<4>;
+    type MaxDependencyDepth = frame_support::traits::ConstU32<2>;
+    type MaxExecutionsPerJob = MaxExecutionsPerJob;
+    type DeliveryChallengeWindow = DeliveryChallengeWindow;
+    type MaxDeliveryChallenges = frame_support::traits::ConstU32<1>;
+    type MinSchedulingWindow = MinSchedulingWindow;
+    type MaxSchedulingWindowChecks = frame_support::traits::ConstU32<10>;
+    type RestrictedMatching = RestrictedMatching;
+    type MarginEstimateSampleSize = MarginEstimateSampleSize;
+    type MaxAssignedJobsPerSource = frame_support::traits::ConstU32<64>;
+    type EnableOffchainMatcher = EnableOffchainMatcher;
+    type OffchainMatcher = crate::offchain::SignedOffchainMatcher<crypto::OffchainMatcherId>;
+    type MaxOffchainMatchScan = MaxOffchainMatchScan;
+    type MaxOffchainSourceScan = MaxOffchainSourceScan;
+    type MaxOffchainMatchesPerBlock = MaxOffchainMatchesPerBlock;
     type RegistrationExtra = JobRequirementsFor<Self>;
     type PalletId = AcurastPalletId;
     type HyperdrivePalletId = HyperdrivePalletId;
     type ReportTolerance = ReportTolerance;
+    type PendingPayoutTimeout = PendingPayoutTimeout;
+    type ScheduleChangeTimeout = ScheduleChangeTimeout;
+    type ImmediatePayoutRatio = ImmediatePayoutRatio;
     type Balance = Balance;
+    type JobRegistrationDeposit = JobRegistrationDeposit;
     type ManagerProvider = ManagerOf;
-    type RewardManager = AssetRewardManager<FeeManagerImpl, Balances, Pallet<Self>>;
+    type RewardManager = MockRewardManager;
     type ProcessorLastSeenProvider = ProcessorLastSeenProvider;
-    type MarketplaceHooks = ();
+    type MarketplaceHooks = MockMarketplaceHooks;
+    type ModuleAttestationCheck = AttestedKeyModuleCheck<AttestedModules>;
     type WeightInfo = weights::WeightInfo<Test>;
     #[cfg(feature = "runtime-benchmarks")]
     type BenchmarkHelper = TestBenchmarkHelper;
@@ -262,14 +430,7 @@ impl crate::benchmarking::BenchmarkHelper<Test> for TestBenchmarkHelper {
 }
 
 pub fn events() -> Vec<RuntimeEvent> {
-    let evt = System::events()
-        .into_iter()
-        .map(|evt| evt.event)
-        .collect::<Vec<_>>();
-
-    System::reset_events();
-
-    evt
+    events_of!(Test)
 }
 
 pub fn pallet_fees_account() -> <Test as frame_system::Config>::AccountId {
@@ -280,6 +441,10 @@ pub fn pallet_acurast_acount() -> <Test as frame_system::Config>::AccountId {
     PalletId(*b"acrstpid").into_account_truncating()
 }
 
+pub fn pallet_hyperdrive_account() -> <Test as frame_system::Config>::AccountId {
+    HyperdrivePalletId::get().into_account_truncating()
+}
+
 pub fn advertisement(
     fee_per_millisecond: u128,
     fee_per_storage_byte: u128,
@@ -0,0 +1,236 @@
+use core::marker::PhantomData;
+
+use frame_support::{
+    pallet_prelude::*,
+    sp_runtime::offchain::{
+        storage::StorageValueRef,
+        storage_lock::{BlockAndTime, StorageLock},
+        Duration,
+    },
+};
+use frame_system::{
+    offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+    pallet_prelude::BlockNumberFor,
+};
+use pallet_acurast::{JobId, JobRegistrationCoreFor, StoredJobRegistration};
+use sp_std::prelude::*;
+
+use crate::{
+    matching::{fee_per_execution, preference_window},
+    AdvertisementIndex, Call, Config, Error, JobRegistrationTimes, JobRequirementsFor, JobStatus,
+    Match, OffchainMatcher, Pallet, PlannedExecution, StoredAdvertisementPricing,
+    StoredAdvertisementPricingVersion, StoredJobStatus,
+};
+
+/// The crypto of the keys signing the off-chain worker's match proposals, see [`SignedOffchainMatcher`].
+pub mod crypto {
+    use frame_support::sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+    use sp_core::crypto::KeyTypeId;
+
+    /// The key type of the off-chain matcher in the node's local keystore.
+    pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"amat");
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    pub struct OffchainMatcherId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OffchainMatcherId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
+const LOG_TARGET: &str = "runtime::acurast_marketplace::offchain";
+
+/// The local storage key of the raw [`StoredJobStatus`] key after which the next scan for open jobs resumes.
+const SCAN_CURSOR_KEY: &[u8] = b"acurast_marketplace::offchain_scan_cursor";
+
+/// The prefix of the local storage keys locking a job after a match proposal got submitted for it.
+const MATCH_LOCK_PREFIX: &[u8] = b"acurast_marketplace::offchain_match::";
+/// The number of blocks a job stays locked after submitting a match proposal for it.
+const MATCH_LOCK_BLOCKS: u32 = 5;
+/// The time in milliseconds a job stays locked after submitting a match proposal for it.
+const MATCH_LOCK_TIMEOUT: u64 = 60_000;
+
+/// An [`OffchainMatcher`] signing its match proposals with a `MatcherId` key from the node's local keystore, see
+/// [`Pallet::offchain_match`].
+///
+/// Only runtimes configuring it have to support signed transactions of the marketplace calls.
+pub struct SignedOffchainMatcher<MatcherId>(PhantomData<MatcherId>);
+
+impl<T, MatcherId> OffchainMatcher<T> for SignedOffchainMatcher<MatcherId>
+where
+    T: Config + CreateSignedTransaction<Call<T>>,
+    MatcherId: AppCrypto<T::Public, T::Signature>,
+{
+    fn offchain_match(block_number: BlockNumberFor<T>) {
+        Pallet::<T>::offchain_match::<MatcherId>(block_number);
+    }
+}
+
+impl<T: Config + CreateSignedTransaction<Call<T>>> Pallet<T> {
+    /// Proposes matches for open single-slot jobs, called by the off-chain worker through [`SignedOffchainMatcher`]
+    /// if [`Config::EnableOffchainMatcher`].
+    ///
+    /// Scans up to [`Config::MaxOffchainMatchScan`] entries of [`StoredJobStatus`] for open jobs with a single slot
+    /// and no preferred sources, and matches each with the advertised source charging the lowest fee among those passing
+    /// [`Pallet::check`]. Each match is submitted as a separate signed [`Pallet::propose_matching`], up to
+    /// [`Config::MaxOffchainMatchesPerBlock`] per block.
+    ///
+    /// The scan resumes after the last scanned entry of the previous block's worker, tracked in the node's local
+    /// storage, and starts over once all entries got scanned.
+    ///
+    /// A job is locked in the node's local storage once its match got submitted, so that workers of the following
+    /// blocks do not propose it again while the transaction is pending.
+    pub(crate) fn offchain_match<MatcherId: AppCrypto<T::Public, T::Signature>>(
+        block_number: BlockNumberFor<T>,
+    ) {
+        let signer = Signer::<T, MatcherId>::any_account();
+        if !signer.can_sign() {
+            log::warn!(
+                target: LOG_TARGET,
+                "offchain matcher enabled but no key of type {:?} in local keystore",
+                crypto::KEY_TYPE,
+            );
+            return;
+        }
+
+        let now = match Self::now() {
+            Ok(now) => now,
+            Err(e) => {
+                log::warn!(target: LOG_TARGET, "offchain matcher failed: {:?}", e);
+                return;
+            }
+        };
+
+        let cursor = StorageValueRef::persistent(SCAN_CURSOR_KEY);
+        let mut job_statuses = match cursor.get::<Vec<u8>>() {
+            Ok(Some(last_key)) => <StoredJobStatus<T>>::iter_from(last_key),
+            _ => <StoredJobStatus<T>>::iter(),
+        };
+        let max_scan = T::MaxOffchainMatchScan::get();
+        let mut scanned = 0u32;
+        let mut submitted = 0u32;
+        while scanned < max_scan && submitted < T::MaxOffchainMatchesPerBlock::get() {
+            let Some((origin, job_id_seq, status)) = job_statuses.next() else {
+                // all entries got scanned, the next scan starts over
+                cursor.clear();
+                return;
+            };
+            scanned += 1;
+            cursor.set(&job_statuses.last_raw_key());
+            if status != JobStatus::Open {
+                continue;
+            }
+            let job_id = (origin, job_id_seq);
+            let registration = match <StoredJobRegistration<T>>::get(&job_id.0, &job_id.1) {
+                Some(registration) => registration,
+                None => continue,
+            };
+            if registration.schedule.start_time <= now {
+                continue;
+            }
+            let e: <T as Config>::RegistrationExtra = registration.extra.clone().into();
+            let requirements: JobRequirementsFor<T> = e.into();
+            if requirements.slots != 1 || requirements.preferred_sources.is_some() {
+                continue;
+            }
+
+            let planned_execution =
+                match Self::cheapest_source(&job_id, &registration, &requirements) {
+                    Ok(Some(planned_execution)) => planned_execution,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::warn!(
+                            target: LOG_TARGET,
+                            "offchain matcher skipped job {:?}: {:?}",
+                            job_id,
+                            e,
+                        );
+                        continue;
+                    }
+                };
+
+            let lock_key = [MATCH_LOCK_PREFIX, &job_id.encode()].concat();
+            let mut lock =
+                StorageLock::<BlockAndTime<frame_system::Pallet<T>>>::with_block_and_time_deadline(
+                    &lock_key,
+                    MATCH_LOCK_BLOCKS,
+                    Duration::from_millis(MATCH_LOCK_TIMEOUT),
+                );
+            let guard = match lock.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => continue,
+            };
+
+            let m = Match {
+                job_id: job_id.clone(),
+                sources: match vec![planned_execution].try_into() {
+                    Ok(sources) => sources,
+                    Err(_) => continue,
+                },
+            };
+            let matches: BoundedVec<_, _> = match vec![m].try_into() {
+                Ok(matches) => matches,
+                Err(_) => return,
+            };
+            match signer.send_signed_transaction(|_| Call::propose_matching {
+                matches: matches.clone(),
+            }) {
+                Some((_, Ok(()))) => {
+                    // keep the job locked until the lock expires
+                    guard.forget();
+                    submitted += 1;
+                }
+                _ => {
+                    log::warn!(
+                        target: LOG_TARGET,
+                        "offchain matcher failed to submit match for job {:?} at block {:?}",
+                        job_id,
+                        block_number,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the advertised source with the lowest fee for a single-slot job among the first
+    /// [`Config::MaxOffchainSourceScan`] sources of [`AdvertisementIndex`] passing [`Pallet::check`].
+    fn cheapest_source(
+        job_id: &JobId<T::AccountId>,
+        registration: &JobRegistrationCoreFor<T>,
+        requirements: &JobRequirementsFor<T>,
+    ) -> Result<Option<PlannedExecution<T::AccountId>>, Error<T>> {
+        let partial_registration =
+            Self::partial_registration_of(job_id, registration, requirements)?;
+        let preference =
+            preference_window::<T>(requirements, <JobRegistrationTimes<T>>::get(job_id));
+
+        let mut cheapest: Option<(T::Balance, T::AccountId)> = None;
+        for source in
+            <AdvertisementIndex<T>>::iter_keys().take(T::MaxOffchainSourceScan::get() as usize)
+        {
+            if Self::check(&partial_registration, &source, Some(&job_id.0), preference).is_err() {
+                continue;
+            }
+            let pricing = <StoredAdvertisementPricing<T>>::get(&source)
+                .ok_or(Error::<T>::AdvertisementPricingNotFound)?;
+            let fee =
+                fee_per_execution::<T>(&registration.schedule, registration.storage, &pricing)
+                    .map_err(Error::<T>::from)?;
+            if cheapest.as_ref().map_or(true, |(lowest, _)| fee < *lowest) {
+                cheapest = Some((fee, source));
+            }
+        }
+
+        Ok(cheapest.map(|(_, source)| PlannedExecution {
+            pricing_version: Some(<StoredAdvertisementPricingVersion<T>>::get(&source)),
+            source,
+            start_delay: 0,
+        }))
+    }
+}
@@ -1,7 +1,7 @@
 use core::marker::PhantomData;
 
 use frame_support::sp_runtime::SaturatedConversion;
-use frame_support::traits::tokens::Preservation;
+use frame_support::traits::tokens::{Fortitude, Precision, Preservation};
 use frame_support::{
     pallet_prelude::Member,
     sp_runtime::{
@@ -29,11 +29,38 @@ pub trait RewardManager<T: frame_system::Config + Config> {
         reward: <T as Config>::Balance,
         target: &T::AccountId,
     ) -> Result<(), DispatchError>;
+    /// Returns the matcher's share of the `remaining_reward` left in a job's budget after matching it.
+    fn matcher_reward(remaining_reward: <T as Config>::Balance) -> <T as Config>::Balance;
+    /// Pays the `matcher_rewards` computed with [`RewardManager::matcher_reward`] to `matcher`, unreserving each from its job's budget.
     fn pay_matcher_reward(
-        remaining_rewards: Vec<(JobId<T::AccountId>, <T as Config>::Balance)>,
+        matcher_rewards: Vec<(JobId<T::AccountId>, <T as Config>::Balance)>,
         matcher: &T::AccountId,
     ) -> Result<(), DispatchError>;
+    /// Unreserves exactly `reward` from the job's budget but keeps the funds on the pallet account for a later [`RewardManager::pay_escrowed_reward`].
+    fn escrow_reward(
+        job_id: &JobId<T::AccountId>,
+        reward: <T as Config>::Balance,
+    ) -> Result<(), DispatchError>;
+    /// Pays a reward previously escrowed with [`RewardManager::escrow_reward`] to `target`.
+    fn pay_escrowed_reward(
+        reward: <T as Config>::Balance,
+        target: &T::AccountId,
+    ) -> Result<(), DispatchError>;
     fn refund(job_id: &JobId<T::AccountId>) -> Result<T::Balance, DispatchError>;
+    /// Unlocks exactly `amount` of the job's budget and sends it back to the job creator.
+    fn unlock_reward(
+        job_id: &JobId<T::AccountId>,
+        amount: <T as Config>::Balance,
+    ) -> Result<(), DispatchError>;
+    fn lock_deposit(
+        who: &T::AccountId,
+        deposit: <T as Config>::Balance,
+    ) -> Result<(), DispatchError>;
+    fn refund_deposit(
+        who: &T::AccountId,
+        deposit: <T as Config>::Balance,
+    ) -> Result<(), DispatchError>;
+    fn burn_deposit(deposit: <T as Config>::Balance) -> Result<(), DispatchError>;
 }
 
 impl<T: frame_system::Config + Config> RewardManager<T> for () {
@@ -52,16 +79,59 @@ impl<T: frame_system::Config + Config> RewardManager<T> for () {
         Ok(())
     }
 
+    fn matcher_reward(_remaining_reward: <T as Config>::Balance) -> <T as Config>::Balance {
+        0u8.into()
+    }
+
     fn pay_matcher_reward(
-        _remaining_rewards: Vec<(JobId<T::AccountId>, <T as Config>::Balance)>,
+        _matcher_rewards: Vec<(JobId<T::AccountId>, <T as Config>::Balance)>,
         _matcher: &T::AccountId,
     ) -> Result<(), DispatchError> {
         Ok(())
     }
 
+    fn escrow_reward(
+        _job_id: &JobId<T::AccountId>,
+        _reward: <T as Config>::Balance,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn pay_escrowed_reward(
+        _reward: <T as Config>::Balance,
+        _target: &T::AccountId,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
     fn refund(_job_id: &JobId<T::AccountId>) -> Result<T::Balance, DispatchError> {
         Ok(0u8.into())
     }
+
+    fn unlock_reward(
+        _job_id: &JobId<T::AccountId>,
+        _amount: <T as Config>::Balance,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn lock_deposit(
+        _who: &T::AccountId,
+        _deposit: <T as Config>::Balance,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn refund_deposit(
+        _who: &T::AccountId,
+        _deposit: <T as Config>::Balance,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn burn_deposit(_deposit: <T as Config>::Balance) -> Result<(), DispatchError> {
+        Ok(())
+    }
 }
 
 // This trait provives methods for managing the fees.
@@ -131,10 +201,22 @@ where
         job_id: &JobId<T::AccountId>,
         reward: T::Balance,
         target: &T::AccountId,
+    ) -> Result<(), DispatchError> {
+        <Self as RewardManager<T>>::escrow_reward(job_id, reward)?;
+        <Self as RewardManager<T>>::pay_escrowed_reward(reward, target)
+    }
+
+    fn escrow_reward(
+        job_id: &JobId<T::AccountId>,
+        reward: T::Balance,
     ) -> Result<(), DispatchError> {
         Budget::unreserve(&job_id, reward)
             .map_err(|_| DispatchError::Other("Severe Error: JobBudget::unreserve failed"))?;
 
+        Ok(())
+    }
+
+    fn pay_escrowed_reward(reward: T::Balance, target: &T::AccountId) -> Result<(), DispatchError> {
         let pallet_account: T::AccountId = <T as Config>::PalletId::get().into_account_truncating();
 
         // Extract fee from the processor reward
@@ -164,15 +246,17 @@ where
         Ok(())
     }
 
+    fn matcher_reward(remaining_reward: T::Balance) -> T::Balance {
+        let matcher_fee_percentage = AssetSplit::get_matcher_percentage(); // TODO: fee will be indexed by version in the future
+        matcher_fee_percentage.mul_floor(remaining_reward)
+    }
+
     fn pay_matcher_reward(
-        remaining_rewards: Vec<(JobId<T::AccountId>, T::Balance)>,
+        matcher_rewards: Vec<(JobId<T::AccountId>, T::Balance)>,
         matcher: &T::AccountId,
     ) -> Result<(), DispatchError> {
-        let matcher_fee_percentage = AssetSplit::get_matcher_percentage(); // TODO: fee will be indexed by version in the future
-
         let mut matcher_reward: T::Balance = 0u8.into();
-        for (job_id, remaining_reward) in remaining_rewards.into_iter() {
-            let matcher_fee = matcher_fee_percentage.mul_floor(remaining_reward);
+        for (job_id, matcher_fee) in matcher_rewards.into_iter() {
             Budget::unreserve(&job_id, matcher_fee)
                 .map_err(|_| DispatchError::Other("Severe Error: JobBudget::unreserve failed"))?;
             matcher_reward += matcher_fee;
@@ -234,6 +318,74 @@ where
 
         Ok(remaining)
     }
+
+    fn unlock_reward(
+        job_id: &JobId<T::AccountId>,
+        amount: T::Balance,
+    ) -> Result<(), DispatchError> {
+        Budget::unreserve(&job_id, amount)
+            .map_err(|_| DispatchError::Other("Severe Error: JobBudget::unreserve failed"))?;
+
+        let pallet_account: T::AccountId = <T as Config>::PalletId::get().into_account_truncating();
+        let hyperdrive_pallet_account: T::AccountId =
+            <T as Config>::HyperdrivePalletId::get().into_account_truncating();
+        match &job_id.0 {
+            MultiOrigin::Acurast(who) => {
+                Currency::transfer(
+                    &pallet_account,
+                    who,
+                    amount.saturated_into(),
+                    Preservation::Preserve,
+                )?;
+            }
+            MultiOrigin::Tezos(_) | MultiOrigin::Ethereum(_) | MultiOrigin::AlephZero(_) => {
+                Currency::transfer(
+                    &pallet_account,
+                    &hyperdrive_pallet_account,
+                    amount.saturated_into(),
+                    Preservation::Preserve,
+                )?;
+            }
+        };
+
+        Ok(())
+    }
+
+    fn lock_deposit(who: &T::AccountId, deposit: T::Balance) -> Result<(), DispatchError> {
+        let pallet_account: T::AccountId = <T as Config>::PalletId::get().into_account_truncating();
+        Currency::transfer(
+            who,
+            &pallet_account,
+            deposit.saturated_into(),
+            Preservation::Preserve,
+        )?;
+
+        Ok(())
+    }
+
+    fn refund_deposit(who: &T::AccountId, deposit: T::Balance) -> Result<(), DispatchError> {
+        let pallet_account: T::AccountId = <T as Config>::PalletId::get().into_account_truncating();
+        Currency::transfer(
+            &pallet_account,
+            who,
+            deposit.saturated_into(),
+            Preservation::Preserve,
+        )?;
+
+        Ok(())
+    }
+
+    fn burn_deposit(deposit: T::Balance) -> Result<(), DispatchError> {
+        let pallet_account: T::AccountId = <T as Config>::PalletId::get().into_account_truncating();
+        Currency::burn_from(
+            &pallet_account,
+            deposit.saturated_into(),
+            Precision::Exact,
+            Fortitude::Polite,
+        )?;
+
+        Ok(())
+    }
 }
 
 /// Manages each job's budget by reserving/unreserving rewards that are externally strored, e.g. on a pallet account in `pallet_balances`.
@@ -2,21 +2,59 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use crate::{JobAssignment, MarketplaceRuntimeApi, PartialJobRegistration, RuntimeApiError};
+use crate::{
+    runtime_decl_for_marketplace_runtime_api, JobAssignment, MarketplaceRuntimeApi, MatchAttempt,
+    MatchableJob, MatcherPerformance, PartialJobRegistration, RuntimeApiError, SLASummary,
+};
 use codec::Codec;
 use frame_support::sp_runtime::traits::{Block as BlockT, HashingFor, MaybeSerializeDeserialize};
 use jsonrpsee::{
     core::{async_trait, RpcResult},
     proc_macros::rpc,
-    types::error::{CallError, ErrorObject},
+    types::error::{CallError, ErrorObject, ErrorObjectOwned},
 };
 use pallet_acurast::{Attestation, Environment, JobId, MultiOrigin, ParameterBound};
-use sp_api::ProvideRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiError, Core, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 
-const RUNTIME_ERROR: i32 = 8001;
-const MARKETPLACE_ERROR: i32 = 8011;
+/// The runtime API call failed to encode a parameter or decode the result, usually caused by a client bug.
+pub const DECODE_ERROR: i32 = -32100;
+/// The runtime API call trapped on this node.
+pub const RUNTIME_TRAPPED: i32 = -32101;
+/// The runtime at the node's best block does not provide the marketplace runtime API.
+pub const RUNTIME_API_MISSING: i32 = -32102;
+/// The request asks for more than [`MAX_RESULT_LENGTH`] items.
+pub const RESULT_LIMIT_EXCEEDED: i32 = -32103;
+/// [`RuntimeApiError::FilterMatchingSources`], e.g. for a registration specifying invalid allowed sources.
+pub const FILTER_MATCHING_SOURCES_FAILED: i32 = -32104;
+/// [`RuntimeApiError::MatchedJobs`].
+pub const MATCHED_JOBS_FAILED: i32 = -32105;
+/// [`RuntimeApiError::UnacknowledgedSlots`].
+pub const UNACKNOWLEDGED_SLOTS_FAILED: i32 = -32106;
+/// [`RuntimeApiError::MatchableJobs`].
+pub const MATCHABLE_JOBS_FAILED: i32 = -32107;
+
+/// The maximum number of items requested in a single call, limiting the `sources` of
+/// [`MarketplaceApiServer::filter_matching_sources`] and the `limit` of [`MarketplaceApiServer::matchable_jobs`].
+pub const MAX_RESULT_LENGTH: u32 = 1_000;
 
+/// The marketplace RPC methods.
+///
+/// # Errors
+///
+/// Failures are returned with one of the following codes and [`RpcErrorData`] as `data`:
+///
+/// | Code     | Constant                           | Retriable | Meaning                                                        |
+/// |----------|------------------------------------|-----------|----------------------------------------------------------------|
+/// | `-32100` | [`DECODE_ERROR`]                   | no        | parameter or result could not be converted, a client bug       |
+/// | `-32101` | [`RUNTIME_TRAPPED`]                | yes       | the runtime API call failed on this node                       |
+/// | `-32102` | [`RUNTIME_API_MISSING`]            | yes       | this node's runtime lacks the marketplace API, retry elsewhere |
+/// | `-32103` | [`RESULT_LIMIT_EXCEEDED`]          | no        | more than [`MAX_RESULT_LENGTH`] items requested                |
+/// | `-32104` | [`FILTER_MATCHING_SOURCES_FAILED`] | no        | the partial registration is invalid                            |
+/// | `-32105` | [`MATCHED_JOBS_FAILED`]            | no        | the matched jobs could not be retrieved                        |
+/// | `-32106` | [`UNACKNOWLEDGED_SLOTS_FAILED`]    | no        | the unacknowledged slots could not be retrieved                |
+/// | `-32107` | [`MATCHABLE_JOBS_FAILED`]          | no        | the matchable jobs could not be retrieved                      |
 #[rpc(client, server)]
 pub trait MarketplaceApi<
     BlockHash,
@@ -31,6 +69,8 @@ pub trait MarketplaceApi<
 {
     /// Filters the given `sources` by those recently seen and matching partially specified `registration`
     /// and whitelisting `consumer` if specifying a whitelist.
+    ///
+    /// Returns the matching sources with their current pricing version.
     #[method(name = "filterMatchingSources")]
     fn filter_matching_sources(
         &self,
@@ -38,7 +78,7 @@ pub trait MarketplaceApi<
         sources: Vec<AccountId>,
         consumer: Option<MultiOrigin<AccountId>>,
         latest_seen_after: Option<u128>,
-    ) -> RpcResult<Vec<AccountId>>;
+    ) -> RpcResult<Vec<(AccountId, u32)>>;
 
     /// Retrieves the job environment.
     #[method(name = "orchestrator_jobEnvironment")]
@@ -62,6 +102,26 @@ pub trait MarketplaceApi<
     /// Retrieves a processor's attestation.
     #[method(name = "orchestrator_is_attested")]
     fn is_attested(&self, source: AccountId) -> RpcResult<bool>;
+
+    /// Retrieves a processor's recent SLA history together with the aggregated fulfillment ratio.
+    #[method(name = "slaHistory")]
+    fn sla_history(&self, source: AccountId) -> RpcResult<SLASummary<Reward>>;
+
+    /// Retrieves the slots of a job not acknowledged yet.
+    #[method(name = "unacknowledgedSlots")]
+    fn unacknowledged_slots(&self, job_id: JobId<AccountId>) -> RpcResult<Vec<u8>>;
+
+    /// Retrieves a matcher's performance counters.
+    #[method(name = "matcherStats")]
+    fn matcher_stats(&self, matcher: AccountId) -> RpcResult<MatcherPerformance>;
+
+    /// Retrieves up to `limit` jobs open for matching with their start time, reward, slots and estimated matcher margin.
+    #[method(name = "matchableJobs")]
+    fn matchable_jobs(&self, limit: u32) -> RpcResult<Vec<MatchableJob<Reward, AccountId>>>;
+
+    /// Retrieves the most recent rejected matching attempt of a job, to find out why matchers skip it.
+    #[method(name = "lastMatchAttempt")]
+    fn last_match_attempt(&self, job_id: JobId<AccountId>) -> RpcResult<Option<MatchAttempt<u64>>>;
 }
 
 /// RPC methods.
@@ -105,16 +165,17 @@ impl<
 where
     Block: BlockT,
     Client: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    Client::Api: MarketplaceRuntimeApi<
-        Block,
-        Reward,
-        AccountId,
-        Extra,
-        MaxAllowedSources,
-        MaxEnvVars,
-        EnvKeyMaxSize,
-        EnvValueMaxSize,
-    >,
+    Client::Api: Core<Block>
+        + MarketplaceRuntimeApi<
+            Block,
+            Reward,
+            AccountId,
+            Extra,
+            MaxAllowedSources,
+            MaxEnvVars,
+            EnvKeyMaxSize,
+            EnvValueMaxSize,
+        >,
     Reward: MaybeSerializeDeserialize + Codec + Send + Sync + 'static,
     AccountId: MaybeSerializeDeserialize + Codec + Send + Sync + 'static,
     Extra: MaybeSerializeDeserialize + Codec + Send + Sync + 'static,
@@ -129,19 +190,11 @@ where
         sources: Vec<AccountId>,
         consumer: Option<MultiOrigin<AccountId>>,
         latest_seen_after: Option<u128>,
-    ) -> RpcResult<Vec<AccountId>> {
-        let api = self.client.runtime_api();
-        let roots = api
-            .filter_matching_sources(
-                self.client.info().best_hash,
-                registration,
-                sources,
-                consumer,
-                latest_seen_after,
-            )
-            .map_err(runtime_error_into_rpc_error)?
-            .map_err(marketplace_error_into_rpc_error)?;
-        Ok(roots)
+    ) -> RpcResult<Vec<(AccountId, u32)>> {
+        ensure_result_length(sources.len())?;
+        self.call_at_best(|api, at| {
+            api.filter_matching_sources(at, registration, sources, consumer, latest_seen_after)
+        })
     }
 
     fn job_environment(
@@ -149,60 +202,289 @@ where
         job_id: JobId<AccountId>,
         source: AccountId,
     ) -> RpcResult<Option<Environment<MaxEnvVars, EnvKeyMaxSize, EnvValueMaxSize>>> {
-        let api = self.client.runtime_api();
-        let env = api
-            .job_environment(self.client.info().best_hash, job_id, source)
-            .map_err(runtime_error_into_rpc_error)?
-            .map_err(marketplace_error_into_rpc_error)?;
-        Ok(env)
+        self.call_at_best(|api, at| api.job_environment(at, job_id, source))
     }
 
     fn matched_jobs(
         &self,
         source: AccountId,
     ) -> RpcResult<Vec<JobAssignment<Reward, AccountId, MaxAllowedSources, Extra>>> {
-        let api = self.client.runtime_api();
-        let jobs = api
-            .matched_jobs(self.client.info().best_hash, source)
-            .map_err(runtime_error_into_rpc_error)?
-            .map_err(marketplace_error_into_rpc_error)?;
-        Ok(jobs)
+        self.call_at_best(|api, at| api.matched_jobs(at, source))
     }
 
     fn attestation(&self, source: AccountId) -> RpcResult<Option<Attestation>> {
-        let api = self.client.runtime_api();
-        let attestation = api
-            .attestation(self.client.info().best_hash, source)
-            .map_err(runtime_error_into_rpc_error)?
-            .map_err(marketplace_error_into_rpc_error)?;
-        Ok(attestation)
+        self.call_at_best(|api, at| api.attestation(at, source))
     }
 
     fn is_attested(&self, source: AccountId) -> RpcResult<bool> {
         Ok(self.attestation(source)?.is_some())
     }
+
+    fn sla_history(&self, source: AccountId) -> RpcResult<SLASummary<Reward>> {
+        self.call_at_best(|api, at| api.sla_history(at, source))
+    }
+
+    fn unacknowledged_slots(&self, job_id: JobId<AccountId>) -> RpcResult<Vec<u8>> {
+        self.call_at_best(|api, at| api.unacknowledged_slots(at, job_id))
+    }
+
+    fn matcher_stats(&self, matcher: AccountId) -> RpcResult<MatcherPerformance> {
+        self.call_at_best(|api, at| api.matcher_stats(at, matcher))
+    }
+
+    fn matchable_jobs(&self, limit: u32) -> RpcResult<Vec<MatchableJob<Reward, AccountId>>> {
+        ensure_result_length(limit as usize)?;
+        self.call_at_best(|api, at| api.matchable_jobs(at, limit))
+    }
+
+    fn last_match_attempt(&self, job_id: JobId<AccountId>) -> RpcResult<Option<MatchAttempt<u64>>> {
+        self.call_at_best(|api, at| api.last_match_attempt(at, job_id))
+    }
 }
 
-/// Converts an marketplace-specific error into a [`CallError`].
-fn marketplace_error_into_rpc_error(err: RuntimeApiError) -> CallError {
-    let error_code = MARKETPLACE_ERROR
-        + match err {
-            RuntimeApiError::FilterMatchingSources => 1,
-            RuntimeApiError::MatchedJobs => 3,
+/// The `data` of all errors returned by the marketplace RPC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcErrorData {
+    /// If the same request might succeed when retried later or on another node.
+    pub retriable: bool,
+    /// The message of the underlying error.
+    pub message: String,
+}
+
+/// The failure classes of the marketplace RPC, each mapping to a designated error code.
+#[derive(Debug)]
+pub enum MarketplaceRpcError {
+    /// See [`DECODE_ERROR`].
+    Decode(String),
+    /// See [`RUNTIME_TRAPPED`].
+    RuntimeTrapped(String),
+    /// See [`RUNTIME_API_MISSING`].
+    RuntimeApiMissing,
+    /// See [`RESULT_LIMIT_EXCEEDED`].
+    ResultLimitExceeded(usize),
+    /// A [`RuntimeApiError`] returned by the runtime.
+    RuntimeApi(RuntimeApiError),
+}
+
+impl MarketplaceRpcError {
+    /// Classifies an error of calling the runtime API. `api_missing` is only called for errors not caused by decoding.
+    pub fn from_api_error(err: ApiError, api_missing: impl FnOnce() -> bool) -> Self {
+        match err {
+            ApiError::FailedToConvertParameter { .. }
+            | ApiError::FailedToConvertReturnValue { .. }
+            | ApiError::FailedToDecodeReturnValue { .. } => Self::Decode(err.to_string()),
+            _ => {
+                if api_missing() {
+                    Self::RuntimeApiMissing
+                } else {
+                    Self::RuntimeTrapped(err.to_string())
+                }
+            }
+        }
+    }
+
+    /// The error code, see [`MarketplaceApiServer`] for a list.
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::Decode(_) => DECODE_ERROR,
+            Self::RuntimeTrapped(_) => RUNTIME_TRAPPED,
+            Self::RuntimeApiMissing => RUNTIME_API_MISSING,
+            Self::ResultLimitExceeded(_) => RESULT_LIMIT_EXCEEDED,
+            Self::RuntimeApi(RuntimeApiError::FilterMatchingSources) => {
+                FILTER_MATCHING_SOURCES_FAILED
+            }
+            Self::RuntimeApi(RuntimeApiError::MatchedJobs) => MATCHED_JOBS_FAILED,
+            Self::RuntimeApi(RuntimeApiError::UnacknowledgedSlots) => UNACKNOWLEDGED_SLOTS_FAILED,
+            Self::RuntimeApi(RuntimeApiError::MatchableJobs) => MATCHABLE_JOBS_FAILED,
+        }
+    }
+
+    /// If the same request might succeed when retried later or on another node.
+    pub fn retriable(&self) -> bool {
+        match self {
+            Self::RuntimeTrapped(_) | Self::RuntimeApiMissing => true,
+            Self::Decode(_) | Self::ResultLimitExceeded(_) | Self::RuntimeApi(_) => false,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Decode(message) | Self::RuntimeTrapped(message) => message.clone(),
+            Self::RuntimeApiMissing => "Marketplace runtime API not available".to_string(),
+            Self::ResultLimitExceeded(requested) => format!(
+                "Requested {} items, at most {} allowed",
+                requested, MAX_RESULT_LENGTH
+            ),
+            Self::RuntimeApi(err) => err.to_string(),
+        }
+    }
+}
+
+impl From<MarketplaceRpcError> for ErrorObjectOwned {
+    fn from(err: MarketplaceRpcError) -> Self {
+        let message = match &err {
+            MarketplaceRpcError::Decode(_) => "Decoding failed",
+            MarketplaceRpcError::RuntimeTrapped(_) => "Runtime trapped",
+            MarketplaceRpcError::RuntimeApiMissing => "Runtime API missing",
+            MarketplaceRpcError::ResultLimitExceeded(_) => "Result limit exceeded",
+            MarketplaceRpcError::RuntimeApi(_) => "Marketplace runtime API failed",
         };
+        ErrorObject::owned(
+            err.code(),
+            message,
+            Some(RpcErrorData {
+                retriable: err.retriable(),
+                message: err.message(),
+            }),
+        )
+    }
+}
+
+impl From<MarketplaceRpcError> for CallError {
+    fn from(err: MarketplaceRpcError) -> Self {
+        CallError::Custom(err.into())
+    }
+}
+
+impl<C, B> Marketplace<C, B>
+where
+    B: BlockT,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+    C::Api: Core<B>,
+{
+    /// Calls the runtime API at the best block, mapping failures to [`MarketplaceRpcError`]s.
+    fn call_at_best<T>(
+        &self,
+        call: impl FnOnce(&C::Api, B::Hash) -> Result<Result<T, RuntimeApiError>, ApiError>,
+    ) -> RpcResult<T> {
+        let api = self.client.runtime_api();
+        let at = self.client.info().best_hash;
+        into_rpc_result(call(&api, at), || {
+            api.version(at)
+                .map(|version| {
+                    !version.has_api_with(&runtime_decl_for_marketplace_runtime_api::ID, |_| true)
+                })
+                .unwrap_or(false)
+        })
+    }
+}
 
-    CallError::Custom(ErrorObject::owned(
-        error_code,
-        err.to_string(),
-        Some(format!("{:?}", err)),
-    ))
+/// Maps the result of a runtime API call into an [`RpcResult`], see [`MarketplaceRpcError::from_api_error`].
+pub fn into_rpc_result<T>(
+    result: Result<Result<T, RuntimeApiError>, ApiError>,
+    api_missing: impl FnOnce() -> bool,
+) -> RpcResult<T> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(err)) => Err(CallError::from(MarketplaceRpcError::RuntimeApi(err)).into()),
+        Err(err) => {
+            Err(CallError::from(MarketplaceRpcError::from_api_error(err, api_missing)).into())
+        }
+    }
+}
+
+/// Fails with [`MarketplaceRpcError::ResultLimitExceeded`] if more than [`MAX_RESULT_LENGTH`] items are requested.
+pub fn ensure_result_length(requested: usize) -> Result<(), CallError> {
+    if requested > MAX_RESULT_LENGTH as usize {
+        return Err(MarketplaceRpcError::ResultLimitExceeded(requested).into());
+    }
+    Ok(())
 }
 
-/// Converts a runtime trap into a [`CallError`].
-fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> CallError {
-    CallError::Custom(ErrorObject::owned(
-        RUNTIME_ERROR,
-        "Runtime trapped",
-        Some(format!("{:?}", err)),
-    ))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the code and data of a failed marketplace RPC result.
+    fn rpc_error_of<T: std::fmt::Debug>(
+        result: jsonrpsee::core::RpcResult<T>,
+    ) -> (i32, RpcErrorData) {
+        match result.unwrap_err() {
+            jsonrpsee::core::Error::Call(CallError::Custom(err)) => (
+                err.code(),
+                serde_json::from_str(err.data().unwrap().get()).unwrap(),
+            ),
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_rpc_runtime_api_errors_map_to_codes() {
+        for (err, code) in [
+            (RuntimeApiError::FilterMatchingSources, -32104),
+            (RuntimeApiError::MatchedJobs, -32105),
+            (RuntimeApiError::UnacknowledgedSlots, -32106),
+            (RuntimeApiError::MatchableJobs, -32107),
+        ] {
+            let message = err.to_string();
+            assert_eq!(
+                (
+                    code,
+                    RpcErrorData {
+                        retriable: false,
+                        message
+                    }
+                ),
+                rpc_error_of(into_rpc_result::<Vec<u8>>(Ok(Err(err)), || {
+                    panic!("runtime API errors do not check the API availability")
+                }))
+            );
+        }
+    }
+
+    #[test]
+    fn test_rpc_api_errors_map_to_codes() {
+        let (code, data) = rpc_error_of(into_rpc_result::<Vec<u8>>(
+            Err(ApiError::FailedToConvertParameter {
+                function: "matchable_jobs",
+                parameter: "limit",
+                error: codec::Error::from("invalid"),
+            }),
+            || panic!("decode errors do not check the API availability"),
+        ));
+        assert_eq!(DECODE_ERROR, code);
+        assert!(!data.retriable);
+        assert!(data.message.contains("matchable_jobs"));
+
+        let (code, data) = rpc_error_of(into_rpc_result::<Vec<u8>>(
+            Err(ApiError::Application("wasm trap".into())),
+            || false,
+        ));
+        assert_eq!(RUNTIME_TRAPPED, code);
+        assert_eq!(
+            RpcErrorData {
+                retriable: true,
+                message: "wasm trap".to_string()
+            },
+            data
+        );
+
+        let (code, data) = rpc_error_of(into_rpc_result::<Vec<u8>>(
+            Err(ApiError::Application("method not found".into())),
+            || true,
+        ));
+        assert_eq!(RUNTIME_API_MISSING, code);
+        assert!(data.retriable);
+    }
+
+    #[test]
+    fn test_rpc_result_limit() {
+        assert!(ensure_result_length(MAX_RESULT_LENGTH as usize).is_ok());
+        let (code, data) = rpc_error_of::<()>(
+            ensure_result_length(MAX_RESULT_LENGTH as usize + 1).map_err(Into::into),
+        );
+        assert_eq!(RESULT_LIMIT_EXCEEDED, code);
+        assert!(!data.retriable);
+    }
+
+    #[test]
+    fn test_rpc_success_unaffected() {
+        assert_eq!(
+            vec![1u8, 2],
+            into_rpc_result::<Vec<u8>>(Ok(Ok(vec![1, 2])), || {
+                panic!("successful calls do not check the API availability")
+            })
+            .unwrap()
+        );
+    }
 }
@@ -3,7 +3,7 @@
 use frame_support::sp_runtime::AccountId32;
 use hex_literal::hex;
 
-use pallet_acurast::{AttestationChain, Script, SerialNumber};
+use pallet_acurast::{AttestationChain, PackageName, Script, SerialNumber};
 
 use crate::*;
 
@@ -59,6 +59,14 @@ pub fn attestation_chain() -> AttestationChain {
     }
 }
 
+/// The application id (package name) the attestation in [attestation_chain] was issued for.
+pub fn attestation_application_id() -> PackageName {
+    b"com.ubinetic.attested.executor.test.testnet"
+        .to_vec()
+        .try_into()
+        .unwrap()
+}
+
 pub fn attestation_chain_processor_2() -> AttestationChain {
     AttestationChain {
         certificate_chain: vec![
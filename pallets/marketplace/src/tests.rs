@@ -1,21 +1,42 @@
 #![cfg(test)]
 
-use frame_support::sp_runtime::{bounded_vec, MultiAddress, Permill};
-use frame_support::{assert_err, assert_ok, traits::Hooks};
+use codec::{Decode, Encode};
+use frame_support::sp_runtime::{
+    bounded_vec, traits::Dispatchable, DispatchError, MultiAddress, Permill,
+};
+use frame_support::{
+    assert_err, assert_ok,
+    dispatch::DispatchResultWithPostInfo,
+    traits::{Get, GetStorageVersion, Hooks, StorageVersion},
+};
+use parking_lot::RwLock;
+use sp_core::{
+    offchain::{
+        testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+        OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+    },
+    H256,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use std::sync::Arc;
 
 use pallet_acurast::{
-    utils::validate_and_extract_attestation, JobModules, JobRegistrationFor, Schedule,
+    utils::validate_and_extract_attestation, AcurastLimits, JobId, JobModules, JobRegistrationCore,
+    JobRegistrationFor, Schedule, ACURAST_LIMITS_VERSION,
 };
-use pallet_acurast::{Attestation, MultiOrigin};
-use reputation::{BetaReputation, ReputationEngine};
+use pallet_acurast::{Attestation, AttestationSecurityLevel, JobModule, MultiOrigin};
+use reputation::{BetaParameters, BetaReputation, ReputationEngine};
 
 use crate::payments::JobBudget;
+use crate::{matching::preference_window, JobRequirements, JobRequirementsFor, PlannedExecution};
 use crate::{
-    mock::*, AdvertisementRestriction, Assignment, Error, ExecutionResult, JobStatus, Match,
-    PlannedExecutions, SLA,
+    mock::*, AdvertisementFor, AdvertisementRestriction, Assignment, ChallengeStatus,
+    ConsumerDefaults, DeliveryCommitment, Error, ExecutionResult, JobStatus, Match, MatchAttempt,
+    MatchFor, MatchableJob, MatcherPerformance, MatchingReasonCode, OmittedFields, PendingPayout,
+    PlannedExecutions, RegistrationApproval, RenewalPolicy, RewardManager, RuntimeApiError,
+    SchedulingWindow, SLA,
 };
 use crate::{stub::*, PubKeys};
-use crate::{JobRequirements, PlannedExecution};
 
 /// Job is not assigned and gets deregistered successfully.
 #[test]
@@ -41,7 +62,17 @@ fn test_valid_deregister() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -153,16 +184,28 @@ fn test_deregister_on_matched_job() {
             slots: 2,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: Some(bounded_vec![
                 PlannedExecution {
                     source: processor_account_id(),
                     start_delay: 0,
+                    pricing_version: None,
                 },
                 PlannedExecution {
                     source: processor_2_account_id(),
                     start_delay: 0,
+                    pricing_version: None,
                 }
             ]),
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -246,10 +289,12 @@ fn test_deregister_on_matched_job() {
                         PlannedExecution {
                             source: processor_account_id(),
                             start_delay: 0,
+                            pricing_version: None,
                         },
                         PlannedExecution {
                             source: processor_2_account_id(),
                             start_delay: 0,
+                            pricing_version: None,
                         }
                     ],
                 })),
@@ -300,16 +345,28 @@ fn test_deregister_on_assigned_job() {
             slots: 2,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: Some(bounded_vec![
                 PlannedExecution {
                     source: processor_account_id(),
                     start_delay: 0,
+                    pricing_version: None,
                 },
                 PlannedExecution {
                     source: processor_2_account_id(),
                     start_delay: 0,
+                    pricing_version: None,
                 }
             ]),
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -427,10 +484,12 @@ fn test_deregister_on_assigned_job() {
                         PlannedExecution {
                             source: processor_account_id(),
                             start_delay: 0,
+                            pricing_version: None,
                         },
                         PlannedExecution {
                             source: processor_2_account_id(),
                             start_delay: 0,
+                            pricing_version: None,
                         }
                     ],
                 })),
@@ -452,7 +511,9 @@ fn test_deregister_on_assigned_job() {
                         fee_per_execution: 5020000,
                         acknowledged: true,
                         sla: SLA { total: 2, met: 0 },
-                        pub_keys: PubKeys::default()
+                        pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
                 RuntimeEvent::Balances(pallet_balances::Event::Transfer {
@@ -503,7 +564,17 @@ fn test_match() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
     let registration2 = JobRegistrationFor::<Test> {
@@ -525,7 +596,17 @@ fn test_match() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -592,6 +673,7 @@ fn test_match() {
             sources: bounded_vec![PlannedExecution {
                 source: processor_account_id(),
                 start_delay: 0,
+                pricing_version: None,
             }],
         };
         let job_match2 = Match {
@@ -599,6 +681,7 @@ fn test_match() {
             sources: bounded_vec![PlannedExecution {
                 source: processor_account_id(),
                 start_delay: 5_000,
+                pricing_version: None,
             }],
         };
 
@@ -660,6 +743,8 @@ fn test_match() {
                 acknowledged: true,
                 sla: SLA { total: 2, met: 1 },
                 pub_keys: PubKeys::default(),
+                holdback: 0,
+                environment_ack: None,
             }),
             AcurastMarketplace::stored_matches(processor_account_id(), job_id1.clone()),
         );
@@ -742,7 +827,8 @@ fn test_match() {
             [
                 RuntimeEvent::Acurast(pallet_acurast::Event::AttestationStored(
                     attestation,
-                    processor_account_id()
+                    processor_account_id(),
+                    Some(attestation_application_id())
                 )),
                 RuntimeEvent::AcurastMarketplace(crate::Event::AdvertisementStored(
                     ad.clone(),
@@ -788,6 +874,8 @@ fn test_match() {
                         acknowledged: true,
                         sla: SLA { total: 2, met: 0 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
                 RuntimeEvent::Balances(pallet_balances::Event::Transfer {
@@ -814,6 +902,8 @@ fn test_match() {
                         acknowledged: true,
                         sla: SLA { total: 2, met: 1 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
                 RuntimeEvent::Balances(pallet_balances::Event::Transfer {
@@ -840,6 +930,8 @@ fn test_match() {
                         acknowledged: true,
                         sla: SLA { total: 2, met: 2 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
                 RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id1.clone())),
@@ -879,7 +971,17 @@ fn test_multi_assignments() {
             slots: 4,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -956,6 +1058,7 @@ fn test_multi_assignments() {
                 .map(|(processor, _)| PlannedExecution {
                     source: processor.clone(),
                     start_delay: 0,
+                    pricing_version: None,
                 })
                 .collect::<Vec<PlannedExecution<AccountId>>>()
                 .try_into()
@@ -1023,6 +1126,8 @@ fn test_multi_assignments() {
                         acknowledged: true,
                         sla: SLA { total: 12, met: 0 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }),
                     AcurastMarketplace::stored_matches(processor, job_id1.clone()),
                 );
@@ -1039,6 +1144,8 @@ fn test_multi_assignments() {
                         acknowledged: true,
                         sla: SLA { total: 12, met: 1 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }),
                     AcurastMarketplace::stored_matches(processor, job_id1.clone()),
                 );
@@ -1108,7 +1215,17 @@ fn test_no_match_schedule_overlap() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -1131,7 +1248,17 @@ fn test_no_match_schedule_overlap() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -1173,6 +1300,7 @@ fn test_no_match_schedule_overlap() {
             sources: bounded_vec![PlannedExecution {
                 source: processor_account_id(),
                 start_delay: 0,
+                pricing_version: None,
             }],
         };
         assert_ok!(AcurastMarketplace::propose_matching(
@@ -1186,6 +1314,7 @@ fn test_no_match_schedule_overlap() {
             sources: bounded_vec![PlannedExecution {
                 source: processor_account_id(),
                 start_delay: 0,
+                pricing_version: None,
             }],
         };
         assert_err!(
@@ -1263,7 +1392,17 @@ fn test_no_match_insufficient_reputation() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: Some(1_000_000),
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -1294,6 +1433,7 @@ fn test_no_match_insufficient_reputation() {
             sources: bounded_vec![PlannedExecution {
                 source: processor_account_id(),
                 start_delay: 0,
+                pricing_version: None,
             }],
         };
         assert_err!(
@@ -1351,7 +1491,17 @@ fn test_more_reports_than_expected() {
             slots: 1,
             reward: 3_000_000 * 2,
             min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
             instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
         },
     };
 
@@ -1386,6 +1536,7 @@ fn test_more_reports_than_expected() {
             sources: bounded_vec![PlannedExecution {
                 source: processor_account_id(),
                 start_delay: 0,
+                pricing_version: None,
             }],
         };
         assert_ok!(AcurastMarketplace::propose_matching(
@@ -1467,6 +1618,8 @@ fn test_more_reports_than_expected() {
                         acknowledged: true,
                         sla: SLA { total: 2, met: 0 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
                 RuntimeEvent::Balances(pallet_balances::Event::Transfer {
@@ -1493,6 +1646,8 @@ fn test_more_reports_than_expected() {
                         acknowledged: true,
                         sla: SLA { total: 2, met: 1 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
                 RuntimeEvent::Balances(pallet_balances::Event::Transfer {
@@ -1519,6 +1674,8 @@ fn test_more_reports_than_expected() {
                         acknowledged: true,
                         sla: SLA { total: 2, met: 2 },
                         pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
                     }
                 )),
             ]
@@ -1526,25 +1683,5959 @@ fn test_more_reports_than_expected() {
     });
 }
 
-fn next_block() {
-    if System::block_number() >= 1 {
-        // pallet_acurast_marketplace::on_finalize(System::block_number());
-        Timestamp::on_finalize(System::block_number());
-    }
-    System::set_block_number(System::block_number() + 1);
-    Timestamp::on_initialize(System::block_number());
+#[test]
+fn test_registration_deposit_returned_on_finalization() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 0,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: Some(bounded_vec![PlannedExecution {
+                source: processor_account_id(),
+                start_delay: 0,
+                pricing_version: None,
+            }]),
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        JobRegistrationDeposit::set(1_000_000);
+        let initial_job_id = Acurast::job_id_sequence();
+
+        // pretend current time
+        later(now);
+
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            ad.clone(),
+        ));
+
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        // the deposit is tracked separately from the job budget
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(
+            Some(1_000_000),
+            AcurastMarketplace::job_registration_deposits(&job_id)
+        );
+        assert_eq!(Balances::free_balance(&alice_account_id()), 87_000_000);
+
+        // the match was never acknowledged, so the job can be finalized once the start time passed
+        later(registration.schedule.start_time + 1);
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            bounded_vec![job_id.1],
+        ));
+
+        assert_eq!(None, AcurastMarketplace::job_registration_deposits(&job_id));
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 100_000_000);
+    });
 }
 
-/// A helper function to move time on in tests. It ensures `Timestamp::set` is only called once per block by advancing the block otherwise.
-fn later(now: u64) {
-    // If this is not the very first timestamp ever set, we always advance the block before setting new time
-    // this is because setting it twice in a block is not legal
-    if Timestamp::get() > 0 {
-        // pretend block was finalized
-        let b = System::block_number();
-        next_block(); // we cannot set time twice in same block
-        assert_eq!(b + 1, System::block_number());
-    }
-    // pretend time moved on
-    assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+#[test]
+fn test_registration_deposit_burned_on_expiry() {
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 0,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        JobRegistrationDeposit::set(1_000_000);
+        let initial_job_id = Acurast::job_id_sequence();
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 87_000_000);
+
+        // an open job cannot be finalized before its end
+        assert_err!(
+            AcurastMarketplace::finalize_jobs(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                bounded_vec![job_id.1],
+            ),
+            Error::<Test>::CannotFinalizeJob(JobStatus::Open)
+        );
+
+        let total_issuance = Balances::total_issuance();
+        later(registration.schedule.end_time);
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            bounded_vec![job_id.1],
+        ));
+
+        // the reward got refunded but the deposit got burned
+        assert_eq!(None, AcurastMarketplace::job_registration_deposits(&job_id));
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 99_000_000);
+        assert_eq!(Balances::total_issuance(), total_issuance - 1_000_000);
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_finalize_expired_open_job_clears_storage() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let end_time = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+            .unwrap()
+            .schedule
+            .end_time;
+
+        later(end_time);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            bounded_vec![job_id.1],
+        ));
+
+        assert_eq!(None, AcurastMarketplace::job_registration_time(&job_id));
+        assert_eq!(None, AcurastMarketplace::job_registration_deposits(&job_id));
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::AcurastMarketplace(
+                crate::Event::JobFinalized(job_id)
+            ))
+            .as_ref()
+        );
+    });
+}
+
+#[test]
+fn test_registration_deposit_exempt_for_tezos_origin() {
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 0,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        JobRegistrationDeposit::set(1_000_000);
+        let _ = Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            MultiAddress::Id(pallet_hyperdrive_account()),
+            100_000_000,
+        );
+
+        let job_id = (MultiOrigin::Tezos(bounded_vec![1, 2, 3]), 1);
+
+        assert_ok!(Acurast::register_for(job_id.clone(), registration.clone()));
+
+        // only the reward got locked
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(None, AcurastMarketplace::job_registration_deposits(&job_id));
+        assert_eq!(
+            Balances::free_balance(&pallet_hyperdrive_account()),
+            88_000_000
+        );
+
+        assert_ok!(Acurast::deregister_for(job_id.clone()));
+        assert_eq!(
+            Balances::free_balance(&pallet_hyperdrive_account()),
+            100_000_000
+        );
+    });
+}
+
+#[test]
+fn test_overwrite_open_job_reconciles_budget() {
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 0,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+
+        assert_ok!(Acurast::register_for(job_id.clone(), registration.clone()));
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 88_000_000);
+
+        // reward increase locks the difference
+        let mut increased = registration.clone();
+        increased.extra.reward = 3_000_000 * 3;
+        assert_ok!(Acurast::register_for(job_id.clone(), increased.clone()));
+        assert_eq!(18_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 82_000_000);
+
+        // reward decrease refunds the difference
+        let mut decreased = registration.clone();
+        decreased.extra.reward = 3_000_000;
+        assert_ok!(Acurast::register_for(job_id.clone(), decreased.clone()));
+        assert_eq!(6_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 94_000_000);
+
+        // schedule change with more executions (4 instead of 2) locks the difference
+        let mut rescheduled = decreased.clone();
+        rescheduled.schedule.end_time = 1_671_807_600_000; // 23.12.2022 15:00 (two hours later)
+        assert_ok!(Acurast::register_for(job_id.clone(), rescheduled.clone()));
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 88_000_000);
+        assert_eq!(
+            Some(rescheduled),
+            Acurast::stored_job_registration(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+
+        assert_ok!(Acurast::deregister_for(job_id.clone()));
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 100_000_000);
+    });
+}
+
+#[test]
+fn test_sla_history_over_several_finalizations() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let first_start_time = 1_671_800_400_000; // 23.12.2022 13:00
+    let two_hours = 7_200_000;
+
+    let ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    let registration = |i: u64, min_recent_fulfillment_ratio: Option<u128>, instant_match| {
+        JobRegistrationFor::<Test> {
+            script: script(),
+            allowed_sources: None,
+            allow_only_verified_sources: false,
+            schedule: Schedule {
+                duration: 5000,
+                start_time: first_start_time + i * two_hours,
+                end_time: first_start_time + i * two_hours + 3_600_000, // one hour later
+                interval: 1_800_000,                                    // 30min
+                max_start_delay: 0,
+            },
+            memory: 5_000u32,
+            network_requests: 5,
+            storage: 20_000u32,
+            required_modules: JobModules::default(),
+            extra: JobRequirements {
+                slots: 1,
+                reward: 3_000_000 * 2,
+                min_reputation: None,
+                min_recent_fulfillment_ratio,
+                holdback_release_sla: None,
+                max_attestation_age_ms: None,
+                max_fee_per_execution: None,
+                depends_on: None,
+                preferred_sources: None,
+                preference_window_ms: 0,
+                omitted: None,
+                instant_match,
+                require_env_ack: false,
+                auto_renew: None,
+            },
+        }
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        let initial_job_id = Acurast::job_id_sequence();
+
+        // pretend current time
+        later(now);
+
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            ad.clone(),
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::recent_fulfillment_ratio(&processor_account_id())
+        );
+
+        // the number of reports for each of the 4 jobs (each expecting 2 reports)
+        let reports = [2usize, 1, 0, 2];
+        for (i, reports) in reports.into_iter().enumerate() {
+            let registration = registration(
+                i as u64,
+                None,
+                Some(bounded_vec![PlannedExecution {
+                    source: processor_account_id(),
+                    start_delay: 0,
+                    pricing_version: None,
+                }]),
+            );
+            let job_id = (
+                MultiOrigin::Acurast(alice_account_id()),
+                initial_job_id + 1 + i as u128,
+            );
+
+            assert_ok!(Acurast::register(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                registration.clone(),
+            ));
+            assert_ok!(AcurastMarketplace::acknowledge_match(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+                PubKeys::default(),
+            ));
+
+            for start in registration.schedule.iter(0).unwrap().take(reports) {
+                later(start + 1000);
+                assert_ok!(AcurastMarketplace::report(
+                    RuntimeOrigin::signed(processor_account_id()).into(),
+                    job_id.clone(),
+                    ExecutionResult::Success(operation_hash())
+                ));
+            }
+
+            later(registration.schedule.range(0).unwrap().1 + 1000);
+            assert_ok!(AcurastMarketplace::finalize_job(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ));
+        }
+
+        // the history is limited to the 3 most recent jobs
+        let history = AcurastMarketplace::sla_history(processor_account_id());
+        assert_eq!(3, history.len());
+        assert_eq!(
+            vec![(1, 2), (0, 2), (2, 2)],
+            history
+                .iter()
+                .map(|record| (record.met, record.total))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            sp_core::H256::from(sp_io::hashing::blake2_256(
+                &(MultiOrigin::Acurast(alice_account_id()), initial_job_id + 2).encode()
+            )),
+            history[0].job_id_hash
+        );
+        assert_eq!(5_020_000, history[0].fee_per_execution);
+        assert_eq!(
+            Some(Permill::from_percent(50)),
+            AcurastMarketplace::recent_fulfillment_ratio(&processor_account_id())
+        );
+        assert_eq!(
+            Some(500_000),
+            AcurastMarketplace::sla_summary(processor_account_id())
+                .unwrap()
+                .fulfillment_ratio
+        );
+
+        // a job requiring a higher fulfillment ratio cannot be matched
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 5);
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration(4, Some(600_000), None),
+        ));
+        let m = Match {
+            job_id: job_id.clone(),
+            sources: bounded_vec![PlannedExecution {
+                source: processor_account_id(),
+                start_delay: 0,
+                pricing_version: None,
+            }],
+        };
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![m.clone()].try_into().unwrap(),
+            ),
+            Error::<Test>::InsufficientRecentFulfillmentRatioInMatch
+        );
+
+        // a job requiring exactly the current fulfillment ratio can be matched
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 6);
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration(5, Some(500_000), None),
+        ));
+        let m = Match {
+            job_id: job_id.clone(),
+            sources: bounded_vec![PlannedExecution {
+                source: processor_account_id(),
+                start_delay: 0,
+                pricing_version: None,
+            }],
+        };
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![m.clone()].try_into().unwrap(),
+        ));
+    });
+}
+
+#[test]
+fn test_report_escrows_payout_without_manager() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, None);
+        let mut iter = registration.schedule.iter(0).unwrap();
+
+        // the processor's pairing got removed mid-job
+        ProcessorsWithoutManager::set(vec![processor_account_id()]);
+
+        let report_time = iter.next().unwrap() + 1000;
+        later(report_time);
+        let _ = events();
+        let budget_before = AcurastMarketplace::job_budgets(&job_id);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+
+        // the report is counted as met and the payment escrowed outside of the job's budget
+        assert_eq!(
+            1,
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+                .unwrap()
+                .sla
+                .met
+        );
+        assert_eq!(
+            Some(PendingPayout {
+                amount: 5_020_000,
+                escrowed_at: report_time,
+            }),
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+        assert_eq!(
+            budget_before - 5_020_000,
+            AcurastMarketplace::job_budgets(&job_id)
+        );
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::AcurastMarketplace(crate::Event::PayoutPending(
+                    job_id.clone(),
+                    processor_account_id(),
+                    5_020_000
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::ExecutionSuccess(
+                    job_id.clone(),
+                    operation_hash()
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::Reported(
+                    job_id.clone(),
+                    processor_account_id(),
+                    Assignment {
+                        slot: 0,
+                        start_delay: 0,
+                        fee_per_execution: 5_020_000,
+                        acknowledged: true,
+                        sla: SLA { total: 2, met: 1 },
+                        pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
+                    }
+                )),
+            ]
+        );
+
+        // neither a manager exists again nor the timeout passed
+        assert_err!(
+            AcurastMarketplace::claim_pending_payout(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::PendingPayoutNotClaimable
+        );
+
+        // the processor got paired again
+        ProcessorsWithoutManager::set(vec![]);
+        assert_ok!(AcurastMarketplace::claim_pending_payout(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: pallet_fees_account(),
+                    amount: 1_506_000
+                }),
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: processor_account_id(),
+                    amount: 3_514_000
+                }),
+                RuntimeEvent::AcurastMarketplace(crate::Event::PendingPayoutClaimed(
+                    job_id.clone(),
+                    processor_account_id(),
+                    processor_account_id(),
+                    5_020_000
+                )),
+            ]
+        );
+
+        assert_err!(
+            AcurastMarketplace::claim_pending_payout(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::PendingPayoutNotFound
+        );
+    });
+}
+
+#[test]
+fn test_report_escrows_payout_on_failing_payment() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, None);
+        let mut iter = registration.schedule.iter(0).unwrap();
+
+        RewardPaymentFails::set(true);
+
+        let report_time = iter.next().unwrap() + 1000;
+        later(report_time);
+        let _ = events();
+        let budget_before = AcurastMarketplace::job_budgets(&job_id);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+
+        // the failed payment does not fail the report, it is counted as met and the payment escrowed
+        assert_eq!(
+            1,
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+                .unwrap()
+                .sla
+                .met
+        );
+        assert_eq!(
+            Some(PendingPayout {
+                amount: 5_020_000,
+                escrowed_at: report_time,
+            }),
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+        // the failed payment attempt got rolled back, the amount left the budget only once
+        assert_eq!(
+            budget_before - 5_020_000,
+            AcurastMarketplace::job_budgets(&job_id)
+        );
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::AcurastMarketplace(crate::Event::PayoutPending(
+                    job_id.clone(),
+                    processor_account_id(),
+                    5_020_000
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::ExecutionSuccess(
+                    job_id.clone(),
+                    operation_hash()
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::Reported(
+                    job_id.clone(),
+                    processor_account_id(),
+                    Assignment {
+                        slot: 0,
+                        start_delay: 0,
+                        fee_per_execution: 5_020_000,
+                        acknowledged: true,
+                        sla: SLA { total: 2, met: 1 },
+                        pub_keys: PubKeys::default(),
+                        holdback: 0,
+                        environment_ack: None,
+                    }
+                )),
+            ]
+        );
+
+        // claiming keeps the payout pending while paying still fails
+        assert_err!(
+            AcurastMarketplace::claim_pending_payout(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ),
+            DispatchError::Other("reward payment failed")
+        );
+        assert!(AcurastMarketplace::pending_payouts(processor_account_id(), &job_id).is_some());
+
+        RewardPaymentFails::set(false);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::claim_pending_payout(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: pallet_fees_account(),
+                    amount: 1_506_000
+                }),
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: processor_account_id(),
+                    amount: 3_514_000
+                }),
+                RuntimeEvent::AcurastMarketplace(crate::Event::PendingPayoutClaimed(
+                    job_id.clone(),
+                    processor_account_id(),
+                    processor_account_id(),
+                    5_020_000
+                )),
+            ]
+        );
+
+        // subsequent reports are paid directly again
+        later(iter.next().unwrap() + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+        assert_eq!(
+            2,
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+                .unwrap()
+                .sla
+                .met
+        );
+    });
+}
+
+#[test]
+fn test_claim_pending_payout_after_timeout() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, None);
+        let mut iter = registration.schedule.iter(0).unwrap();
+
+        ProcessorsWithoutManager::set(vec![processor_account_id()]);
+
+        // both reports get escrowed and accumulate
+        let first_report_time = iter.next().unwrap() + 1000;
+        later(first_report_time);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+        later(iter.next().unwrap() + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+        assert_eq!(
+            Some(PendingPayout {
+                amount: 10_040_000,
+                escrowed_at: first_report_time,
+            }),
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+
+        // the timeout passed since the first escrow, so the processor gets paid directly
+        later(first_report_time + PendingPayoutTimeout::get());
+        let _ = events();
+        assert_ok!(AcurastMarketplace::claim_pending_payout(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::pending_payouts(processor_account_id(), &job_id)
+        );
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: pallet_fees_account(),
+                    amount: 3_012_000
+                }),
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: processor_account_id(),
+                    amount: 7_028_000
+                }),
+                RuntimeEvent::AcurastMarketplace(crate::Event::PendingPayoutClaimed(
+                    job_id.clone(),
+                    processor_account_id(),
+                    processor_account_id(),
+                    10_040_000
+                )),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_holdback_released_on_finalization() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, Some(1_000_000));
+        let reserved = AcurastMarketplace::reserved(&job_id);
+        let processor_balance = Balances::free_balance(&processor_account_id());
+
+        for execution in registration.schedule.iter(0).unwrap() {
+            later(execution + 1000);
+            assert_ok!(AcurastMarketplace::report(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+                ExecutionResult::Success(operation_hash())
+            ));
+        }
+
+        // only 40% of each execution's fee got paid, the rest is held back in the job's budget
+        assert_eq!(
+            Balances::free_balance(&processor_account_id()),
+            processor_balance + 2 * 1_405_600
+        );
+        assert_eq!(
+            AcurastMarketplace::reserved(&job_id),
+            reserved - 2 * 2_008_000
+        );
+        assert_eq!(
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+                .unwrap()
+                .holdback,
+            6_024_000
+        );
+
+        later(registration.schedule.end_time + 1);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: pallet_fees_account(),
+                    amount: 1_807_200
+                }),
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: processor_account_id(),
+                    amount: 4_216_800
+                }),
+                RuntimeEvent::AcurastMarketplace(crate::Event::HoldbackReleased(
+                    job_id.clone(),
+                    processor_account_id(),
+                    6_024_000
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id.clone())),
+            ]
+        );
+        assert_eq!(
+            Balances::free_balance(&processor_account_id()),
+            processor_balance + 7_028_000
+        );
+        assert_eq!(
+            AcurastMarketplace::reserved(&job_id),
+            reserved - 2 * 5_020_000
+        );
+    });
+}
+
+#[test]
+fn test_holdback_forfeited_on_partial_sla() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, Some(1_000_000));
+        let reserved = AcurastMarketplace::reserved(&job_id);
+        let consumer_balance = Balances::free_balance(&alice_account_id());
+
+        // only the first of two executions gets reported
+        later(registration.schedule.iter(0).unwrap().next().unwrap() + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+
+        later(registration.schedule.end_time + 1);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer {
+                    from: pallet_acurast_acount(),
+                    to: alice_account_id(),
+                    amount: 3_012_000
+                }),
+                RuntimeEvent::AcurastMarketplace(crate::Event::HoldbackForfeited(
+                    job_id.clone(),
+                    processor_account_id(),
+                    3_012_000
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id.clone())),
+            ]
+        );
+        assert_eq!(
+            Balances::free_balance(&alice_account_id()),
+            consumer_balance + 3_012_000
+        );
+        assert_eq!(AcurastMarketplace::reserved(&job_id), reserved - 5_020_000);
+    });
+}
+
+#[test]
+fn test_holdback_reconciles_with_budget_on_consumer_finalization() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let pallet_balance = Balances::free_balance(&pallet_acurast_acount());
+        let (job_id, registration) = setup_acknowledged_job(now, Some(500_000));
+        let reserved = AcurastMarketplace::reserved(&job_id);
+        let consumer_balance = Balances::free_balance(&alice_account_id());
+
+        later(registration.schedule.iter(0).unwrap().next().unwrap() + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+
+        // the processor never finalizes its slot, so its holdback is forfeited with the consumer's finalization
+        later(registration.schedule.end_time + 1);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            vec![job_id.1].try_into().unwrap(),
+        ));
+        assert!(events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::HoldbackForfeited(job_id.clone(), processor_account_id(), 3_012_000)
+        )));
+
+        // everything but the immediately paid part got refunded
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(
+            Balances::free_balance(&alice_account_id()),
+            consumer_balance + reserved - 2_008_000
+        );
+        assert_eq!(
+            Balances::free_balance(&pallet_acurast_acount()),
+            pallet_balance
+        );
+    });
+}
+
+/// Registers a job with two executions, matches it with `processor_account_id()` and acknowledges the match.
+#[test]
+fn test_finalize_assigned_jobs() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id1, registration1) = setup_acknowledged_job(now, None);
+
+        // a second job assigned to the same processor, scheduled one day later
+        let mut registration2 = registration1.clone();
+        registration2.schedule.start_time += 86_400_000;
+        registration2.schedule.end_time += 86_400_000;
+        let job_id2 = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration2.clone(),
+        ));
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![Match {
+                job_id: job_id2.clone(),
+                sources: bounded_vec![PlannedExecution {
+                    source: processor_account_id(),
+                    start_delay: 0,
+                    pricing_version: None,
+                }],
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id2.clone(),
+            PubKeys::default(),
+        ));
+
+        let unknown_job_id = (MultiOrigin::Acurast(alice_account_id()), 1_000);
+
+        later(registration1.schedule.end_time + 1);
+        let _ = events();
+        let post_info = AcurastMarketplace::finalize_assigned_jobs(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            vec![job_id1.clone(), job_id2.clone(), unknown_job_id]
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap();
+
+        // the failed jobs are only charged the reads preceding their failure
+        assert_eq!(
+            Some(
+                <crate::weights::WeightInfo<Test> as crate::WeightInfo>::finalize_assigned_jobs(1)
+            ),
+            post_info.actual_weight
+        );
+
+        // only the job whose schedule ended got finalized
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id1)
+        );
+        assert!(AcurastMarketplace::stored_matches(processor_account_id(), &job_id2).is_some());
+        assert_eq!(
+            Some(JobStatus::Assigned(1)),
+            AcurastMarketplace::stored_job_status(&job_id2.0, &job_id2.1)
+        );
+
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id1.clone())),
+                RuntimeEvent::AcurastMarketplace(crate::Event::AssignedJobsFinalized(
+                    processor_account_id(),
+                    1,
+                    2
+                )),
+            ]
+        );
+
+        // the remaining job can be finalized in a later batch
+        later(registration2.schedule.end_time + 1);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::finalize_assigned_jobs(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            vec![job_id2.clone()].try_into().unwrap(),
+        ));
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id2.clone())),
+                RuntimeEvent::AcurastMarketplace(crate::Event::AssignedJobsFinalized(
+                    processor_account_id(),
+                    1,
+                    0
+                )),
+            ]
+        );
+    });
+}
+
+fn setup_acknowledged_job(
+    now: u64,
+    holdback_release_sla: Option<u128>,
+) -> (JobId<AccountId>, JobRegistrationFor<Test>) {
+    let ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 5000,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+
+    let initial_job_id = Acurast::job_id_sequence();
+    let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+
+    assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+    assert_ok!(AcurastMarketplace::advertise(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        ad,
+    ));
+    assert_ok!(Acurast::register(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        registration.clone(),
+    ));
+    assert_ok!(AcurastMarketplace::propose_matching(
+        RuntimeOrigin::signed(charlie_account_id()).into(),
+        vec![Match {
+            job_id: job_id.clone(),
+            sources: bounded_vec![PlannedExecution {
+                source: processor_account_id(),
+                start_delay: 0,
+                pricing_version: None,
+            }],
+        }]
+        .try_into()
+        .unwrap(),
+    ));
+    assert_ok!(AcurastMarketplace::acknowledge_match(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        job_id.clone(),
+        PubKeys::default(),
+    ));
+
+    (job_id, registration)
+}
+
+#[test]
+fn test_match_rejected_on_pricing_version_mismatch() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 5000,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+    let partial_registration = crate::PartialJobRegistration {
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: None,
+        memory: None,
+        network_requests: None,
+        storage: None,
+        required_modules: JobModules::default(),
+        slots: None,
+        reward: 0,
+        min_reputation: None,
+        min_recent_fulfillment_ratio: None,
+        max_attestation_age_ms: None,
+        max_fee_per_execution: None,
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        let initial_job_id = Acurast::job_id_sequence();
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            ad.clone(),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+
+        // matcher observes the current pricing version
+        let observed = AcurastMarketplace::filter_matching_sources(
+            partial_registration.clone(),
+            vec![processor_account_id()],
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(vec![(processor_account_id(), 1)], observed);
+
+        // processor updates its pricing before the matcher proposes
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1100, 1, 100_000, 50_000, 8),
+        ));
+        assert_eq!(
+            2,
+            AcurastMarketplace::stored_advertisement_pricing_version(processor_account_id())
+        );
+
+        let planned_match = |pricing_version| Match {
+            job_id: job_id.clone(),
+            sources: bounded_vec![PlannedExecution {
+                source: processor_account_id(),
+                start_delay: 0,
+                pricing_version: Some(pricing_version),
+            }],
+        };
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![planned_match(observed[0].1)].try_into().unwrap(),
+            ),
+            Error::<Test>::PricingVersionMismatch
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+
+        // matching with the fresh version succeeds
+        let observed = AcurastMarketplace::filter_matching_sources(
+            partial_registration.clone(),
+            vec![processor_account_id()],
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(vec![(processor_account_id(), 2)], observed);
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![planned_match(observed[0].1)].try_into().unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_match_rejected_on_old_attestation() {
+    let attested = 1_671_789_600_000; // 23.12.2022 10:00;
+    let now = attested + 2_678_400_000; // 31 days later, 23.01.2023 10:00
+
+    let ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    let registration = JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: true,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: now + 10_800_000, // 23.01.2023 13:00
+            end_time: now + 14_400_000,   // 23.01.2023 14:00 (one hour later)
+            interval: 1_800_000,          // 30min
+            max_start_delay: 5000,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: Some(2_592_000_000), // 30 days
+            depends_on: None,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    };
+    let partial_registration = crate::PartialJobRegistration {
+        allowed_sources: None,
+        allow_only_verified_sources: true,
+        schedule: None,
+        memory: None,
+        network_requests: None,
+        storage: None,
+        required_modules: JobModules::default(),
+        slots: None,
+        reward: 0,
+        min_reputation: None,
+        min_recent_fulfillment_ratio: None,
+        max_attestation_age_ms: Some(2_592_000_000),
+        max_fee_per_execution: None,
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        let initial_job_id = Acurast::job_id_sequence();
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+        let planned_match = Match {
+            job_id: job_id.clone(),
+            sources: bounded_vec![PlannedExecution {
+                source: processor_account_id(),
+                start_delay: 0,
+                pricing_version: None,
+            }],
+        };
+
+        later(attested);
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            attestation_chain()
+        ));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            ad.clone(),
+        ));
+
+        later(now);
+        assert_eq!(
+            Some(attested),
+            Acurast::stored_attestation(processor_account_id())
+                .unwrap()
+                .submitted_at
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+
+        // the source's attestation is older than required
+        assert_eq!(
+            Vec::<(AccountId, u32)>::new(),
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration.clone(),
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+            .unwrap()
+        );
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![planned_match.clone()].try_into().unwrap(),
+            ),
+            Error::<Test>::AttestationTooOld
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+
+        // the source re-attests and can be matched
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            attestation_chain()
+        ));
+        assert_eq!(
+            vec![(processor_account_id(), 1)],
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration.clone(),
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+            .unwrap()
+        );
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![planned_match].try_into().unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+/// A job registration for alice with two executions starting at `start_time` and depending on `depends_on`.
+fn dependent_registration(
+    start_time: u64,
+    depends_on: Option<JobId<AccountId>>,
+) -> JobRegistrationFor<Test> {
+    JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time,
+            end_time: start_time + 3_600_000, // one hour later
+            interval: 1_800_000,              // 30min
+            max_start_delay: 5000,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on,
+            preferred_sources: None,
+            preference_window_ms: 0,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    }
+}
+
+/// A job registration for alice with one slot, preferring `preferred` during `preference_window_ms` after registration.
+fn preferred_registration(
+    preferred: Vec<AccountId>,
+    preference_window_ms: u64,
+) -> JobRegistrationFor<Test> {
+    JobRegistrationFor::<Test> {
+        script: script(),
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: Schedule {
+            duration: 5000,
+            start_time: 1_671_800_400_000, // 23.12.2022 13:00
+            end_time: 1_671_804_000_000,   // 23.12.2022 14:00 (one hour later)
+            interval: 1_800_000,           // 30min
+            max_start_delay: 5000,
+        },
+        memory: 5_000u32,
+        network_requests: 5,
+        storage: 20_000u32,
+        required_modules: JobModules::default(),
+        extra: JobRequirements {
+            slots: 1,
+            reward: 3_000_000 * 2,
+            min_reputation: None,
+            min_recent_fulfillment_ratio: None,
+            holdback_release_sla: None,
+            max_attestation_age_ms: None,
+            max_fee_per_execution: None,
+            depends_on: None,
+            preferred_sources: Some(preferred.try_into().unwrap()),
+            preference_window_ms,
+            omitted: None,
+            instant_match: None,
+            require_env_ack: false,
+            auto_renew: None,
+        },
+    }
+}
+
+/// Registers a [`preferred_registration`] preferring [`processor_account_id`] for 10 minutes, with both
+/// [`processor_account_id`] and [`processor_2_account_id`] advertising.
+fn setup_preferred_job(now: u64) -> JobId<AccountId> {
+    let initial_job_id = Acurast::job_id_sequence();
+    let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+
+    assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+    for source in [processor_account_id(), processor_2_account_id()] {
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(source).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+    }
+    assert_ok!(Acurast::register(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        preferred_registration(vec![processor_account_id()], 600_000),
+    ));
+    assert_eq!(
+        Some(now),
+        AcurastMarketplace::job_registration_time(&job_id)
+    );
+
+    job_id
+}
+
+fn single_source_match(job_id: &JobId<AccountId>, source: AccountId) -> MatchFor<Test> {
+    Match {
+        job_id: job_id.clone(),
+        sources: bounded_vec![PlannedExecution {
+            source,
+            start_delay: 0,
+            pricing_version: None,
+        }],
+    }
+}
+
+#[test]
+fn test_match_rejected_outside_preferred_sources_within_window() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+
+        later(now + 599_999);
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, processor_2_account_id())]
+                    .try_into()
+                    .unwrap(),
+            ),
+            Error::<Test>::OutsidePreferenceWindow
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_match_accepted_after_preference_window() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+
+        later(now + 600_000);
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(None, AcurastMarketplace::job_registration_time(&job_id));
+    });
+}
+
+#[test]
+fn test_match_preferred_sources() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        // within the window
+        let job_id = setup_preferred_job(now);
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        // kept until the preference window elapsed, in case the job gets open for matching again
+        assert_eq!(
+            Some(now),
+            AcurastMarketplace::job_registration_time(&job_id)
+        );
+
+        // instant matches proposed by a preferred source are accepted during registration
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), job_id.1 + 1);
+        let instant_registration = |source| {
+            let mut registration = preferred_registration(vec![processor_2_account_id()], 600_000);
+            registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+                source,
+                start_delay: 0,
+                pricing_version: None,
+            }]);
+            registration
+        };
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_3_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_err!(
+            Acurast::register(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                instant_registration(processor_3_account_id()),
+            ),
+            Error::<Test>::OutsidePreferenceWindow
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            instant_registration(processor_2_account_id()),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            Some(now),
+            AcurastMarketplace::job_registration_time(&job_id)
+        );
+    });
+}
+
+#[test]
+fn test_check_preference_window() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let registration = Acurast::stored_job_registration(&job_id.0, &job_id.1).unwrap();
+        let requirements: JobRequirementsFor<Test> = registration.extra.clone().into();
+        let partial_registration =
+            AcurastMarketplace::partial_registration_of(&job_id, &registration, &requirements)
+                .unwrap();
+        let check = |source| {
+            AcurastMarketplace::check(
+                &partial_registration,
+                &source,
+                Some(&job_id.0),
+                preference_window::<Test>(
+                    &requirements,
+                    AcurastMarketplace::job_registration_time(&job_id),
+                ),
+            )
+        };
+
+        later(now + 599_999);
+        assert_ok!(check(processor_account_id()));
+        assert_err!(
+            check(processor_2_account_id()),
+            Error::<Test>::OutsidePreferenceWindow
+        );
+
+        later(now + 600_000);
+        assert_ok!(check(processor_2_account_id()));
+    });
+}
+
+/// A [`preferred_registration`] without preference, capping the fee per execution at `max_fee_per_execution`.
+fn capped_registration(max_fee_per_execution: Option<u128>) -> JobRegistrationFor<Test> {
+    let mut registration = preferred_registration(vec![], 0);
+    registration.extra.preferred_sources = None;
+    registration.extra.max_fee_per_execution = max_fee_per_execution;
+    registration
+}
+
+#[test]
+fn test_max_fee_per_execution_registration_validation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+
+        // the cap must not exceed the reward
+        assert_err!(
+            Acurast::register_for(job_id.clone(), capped_registration(Some(3_000_000 * 2 + 1))),
+            Error::<Test>::JobRegistrationMaxFeeExceedsReward
+        );
+        assert_ok!(Acurast::register_for(
+            job_id.clone(),
+            capped_registration(Some(3_000_000 * 2)),
+        ));
+    });
+}
+
+#[test]
+fn test_ratio_requirements_registration_validation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+
+        // a ratio above one would otherwise be truncated into a lower Permill
+        let mut registration = capped_registration(None);
+        registration.extra.holdback_release_sla = Some(u32::MAX as u128 + 2);
+        assert_err!(
+            Acurast::register_for(job_id.clone(), registration.clone()),
+            Error::<Test>::JobRegistrationRatioOutOfRange
+        );
+        registration.extra.holdback_release_sla = None;
+        registration.extra.min_reputation = Some(1_000_001);
+        assert_err!(
+            Acurast::register_for(job_id.clone(), registration.clone()),
+            Error::<Test>::JobRegistrationRatioOutOfRange
+        );
+        registration.extra.min_reputation = None;
+        registration.extra.min_recent_fulfillment_ratio = Some(1_000_001);
+        assert_err!(
+            Acurast::register_for(job_id.clone(), registration.clone()),
+            Error::<Test>::JobRegistrationRatioOutOfRange
+        );
+        registration.extra.min_recent_fulfillment_ratio = Some(1_000_000);
+        assert_ok!(Acurast::register_for(job_id.clone(), registration));
+    });
+}
+
+#[test]
+fn test_match_max_fee_per_execution_boundary() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+                                 // fee_per_millisecond * duration + fee_per_storage_byte * storage = 1000 * 5000 + 1 * 20_000
+    let fee_per_execution = 5_020_000;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let initial_job_id = Acurast::job_id_sequence();
+        let capped_job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 2);
+
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            capped_registration(Some(fee_per_execution - 1)),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            capped_registration(Some(fee_per_execution)),
+        ));
+
+        // fee one above the cap while still covered by the reward
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&capped_job_id, processor_account_id())]
+                    .try_into()
+                    .unwrap(),
+            ),
+            Error::<Test>::FeeAboveConsumerMaximum
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&capped_job_id.0, &capped_job_id.1)
+        );
+
+        // fee exactly at the cap
+        let _ = events();
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert!(events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::MatchedWithinMaxFee(
+                job_id.clone(),
+                processor_account_id(),
+                fee_per_execution,
+                fee_per_execution,
+            )
+        )));
+    });
+}
+
+#[test]
+fn test_dependent_job_unlocked_on_dependency_finalization() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (dependency_id, registration) = setup_acknowledged_job(now, None);
+
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        let dependent = dependent_registration(
+            1_671_807_600_000, // 23.12.2022 15:00
+            Some(dependency_id.clone()),
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            dependent.clone(),
+        ));
+
+        // the budget is locked but the job is not matchable yet
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(
+            Some(JobStatus::WaitingForDependency),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            vec![job_id.clone()],
+            AcurastMarketplace::dependents(&dependency_id).into_inner()
+        );
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![Match {
+                job_id: job_id.clone(),
+                sources: bounded_vec![PlannedExecution {
+                    source: processor_account_id(),
+                    start_delay: 0,
+                    pricing_version: None,
+                }],
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::WaitingForDependency),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+        );
+
+        // the dependency gets finalized successfully
+        later(registration.schedule.end_time + 1);
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            dependency_id.clone(),
+        ));
+        let _ = events();
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            vec![dependency_id.1].try_into().unwrap(),
+        ));
+        assert!(events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::JobUnlocked(job_id.clone())
+        )));
+
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert!(AcurastMarketplace::dependents(&dependency_id).is_empty());
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+
+        // the unlocked job can be matched
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![Match {
+                job_id: job_id.clone(),
+                sources: bounded_vec![PlannedExecution {
+                    source: processor_account_id(),
+                    start_delay: 0,
+                    pricing_version: None,
+                }],
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_dependent_jobs_cancelled_on_dependency_deregistration() {
+    ExtBuilder::default().build().execute_with(|| {
+        JobRegistrationDeposit::set(1_000_000);
+        let first = (MultiOrigin::Acurast(alice_account_id()), 1);
+        let second = (MultiOrigin::Acurast(alice_account_id()), 2);
+        let third = (MultiOrigin::Acurast(alice_account_id()), 3);
+
+        assert_ok!(Acurast::register_for(
+            first.clone(),
+            dependent_registration(1_671_800_400_000, None), // 23.12.2022 13:00
+        ));
+        assert_ok!(Acurast::register_for(
+            second.clone(),
+            dependent_registration(1_671_804_000_000, Some(first.clone())), // 23.12.2022 14:00
+        ));
+        assert_ok!(Acurast::register_for(
+            third.clone(),
+            dependent_registration(1_671_807_600_000, Some(second.clone())), // 23.12.2022 15:00
+        ));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 61_000_000);
+
+        let _ = events();
+        assert_ok!(Acurast::deregister_for(first.clone()));
+
+        // the cancellation propagated to all (transitive) dependents
+        let events = events();
+        for job_id in [&second, &third] {
+            assert!(events.contains(&RuntimeEvent::AcurastMarketplace(
+                crate::Event::JobCancelled(job_id.clone())
+            )));
+            assert_eq!(
+                None,
+                AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+            );
+            assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+            assert_eq!(0, AcurastMarketplace::reserved(job_id));
+            assert!(AcurastMarketplace::dependents(job_id).is_empty());
+        }
+        assert!(AcurastMarketplace::dependents(&first).is_empty());
+
+        // budgets and deposits got refunded
+        assert_eq!(Balances::free_balance(&alice_account_id()), 100_000_000);
+    });
+}
+
+#[test]
+fn test_dependency_chain_depth_limited() {
+    ExtBuilder::default().build().execute_with(|| {
+        let first = (MultiOrigin::Acurast(alice_account_id()), 1);
+        let second = (MultiOrigin::Acurast(alice_account_id()), 2);
+        let third = (MultiOrigin::Acurast(alice_account_id()), 3);
+        let fourth = (MultiOrigin::Acurast(alice_account_id()), 4);
+
+        assert_ok!(Acurast::register_for(
+            first.clone(),
+            dependent_registration(1_671_800_400_000, None), // 23.12.2022 13:00
+        ));
+        assert_ok!(Acurast::register_for(
+            second.clone(),
+            dependent_registration(1_671_804_000_000, Some(first.clone())), // 23.12.2022 14:00
+        ));
+        assert_ok!(Acurast::register_for(
+            third.clone(),
+            dependent_registration(1_671_807_600_000, Some(second.clone())), // 23.12.2022 15:00
+        ));
+
+        // the chain of jobs waiting for each other would exceed `MaxDependencyDepth`
+        assert_err!(
+            Acurast::register_for(
+                fourth.clone(),
+                dependent_registration(1_671_811_200_000, Some(third.clone())), // 23.12.2022 16:00
+            ),
+            Error::<Test>::DependencyTooDeep
+        );
+
+        // depending on a job waiting for a shorter chain is fine
+        assert_ok!(Acurast::register_for(
+            fourth.clone(),
+            dependent_registration(1_671_807_600_000, Some(second.clone())), // 23.12.2022 15:00
+        ));
+        assert_eq!(20, AcurastMarketplace::max_resolved_dependents());
+
+        // the cancelled dependents are charged when the first job expires unmatched
+        later(1_671_804_000_000);
+        let post_info = AcurastMarketplace::expire_unmatched_job(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            first.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            Some(
+                <crate::weights::WeightInfo<Test> as crate::WeightInfo>::expire_unmatched_job()
+                    .saturating_add(
+                        <crate::weights::WeightInfo<Test> as crate::WeightInfo>::resolve_dependent(
+                        )
+                        .saturating_mul(3)
+                    )
+            ),
+            post_info.actual_weight
+        );
+        for job_id in [&second, &third, &fourth] {
+            assert_eq!(
+                None,
+                AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+            );
+        }
+    });
+}
+
+#[test]
+fn test_dependent_job_registration_validation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let dependency = (MultiOrigin::Acurast(alice_account_id()), 1);
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 2);
+
+        // dependency has to exist
+        assert_err!(
+            Acurast::register_for(
+                job_id.clone(),
+                dependent_registration(1_671_804_000_000, Some(dependency.clone())),
+            ),
+            Error::<Test>::DependencyNotFound
+        );
+
+        assert_ok!(Acurast::register_for(
+            dependency.clone(),
+            dependent_registration(1_671_800_400_000, None), // 23.12.2022 13:00 - 14:00
+        ));
+
+        // dependency has to belong to the same consumer
+        assert_err!(
+            Acurast::register_for(
+                (MultiOrigin::Acurast(bob_account_id()), 1),
+                dependent_registration(1_671_804_000_000, Some(dependency.clone())),
+            ),
+            Error::<Test>::DependencyOfOtherConsumer
+        );
+
+        // schedule has to start after the dependency's schedule ended
+        assert_err!(
+            Acurast::register_for(
+                job_id.clone(),
+                dependent_registration(1_671_803_999_999, Some(dependency.clone())),
+            ),
+            Error::<Test>::JobRegistrationStartBeforeDependencyEnd
+        );
+        assert_ok!(Acurast::register_for(
+            job_id.clone(),
+            dependent_registration(1_671_804_000_000, Some(dependency.clone())),
+        ));
+        assert_eq!(
+            Some(JobStatus::WaitingForDependency),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+
+        // the dependent job gets removed from the index when deregistered
+        assert_ok!(Acurast::deregister_for(job_id.clone()));
+        assert!(AcurastMarketplace::dependents(&dependency).is_empty());
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&dependency.0, &dependency.1)
+        );
+    });
+}
+
+#[test]
+fn test_sparse_registration_resolved_from_consumer_defaults() {
+    ExtBuilder::default().build().execute_with(|| {
+        let defaults = ConsumerDefaults {
+            allow_only_verified_sources: true,
+            required_modules: JobModules::default(),
+            memory: 1_000,
+            network_requests: 2,
+            storage: 10_000,
+            min_reputation: Some(500_000),
+        };
+        assert_ok!(AcurastMarketplace::set_default_requirements(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            defaults.clone(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::ConsumerDefaultsStored(alice_account_id(), defaults)
+            )]
+        );
+
+        // memory, storage and min_reputation are omitted, the other fields are explicit
+        let mut sparse = dependent_registration(1_671_800_400_000, None);
+        sparse.network_requests = 5;
+        sparse.extra.omitted = Some(OmittedFields {
+            memory: true,
+            storage: true,
+            min_reputation: true,
+            ..Default::default()
+        });
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_ok!(Acurast::register_for(job_id.clone(), sparse));
+
+        // explicit values take precedence over defaults
+        let mut resolved = dependent_registration(1_671_800_400_000, None);
+        resolved.memory = 1_000;
+        resolved.network_requests = 5;
+        resolved.storage = 10_000;
+        resolved.extra.min_reputation = Some(500_000);
+        assert_eq!(
+            Some(resolved.clone()),
+            Acurast::stored_job_registration(&job_id.0, &job_id.1)
+        );
+        assert!(events().contains(&RuntimeEvent::Acurast(
+            pallet_acurast::Event::JobRegistrationStored(resolved.clone(), job_id.clone())
+        )));
+
+        // updating the profile does not change already stored registrations
+        assert_ok!(AcurastMarketplace::set_default_requirements(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            ConsumerDefaults {
+                allow_only_verified_sources: false,
+                required_modules: JobModules::default(),
+                memory: 2_000,
+                network_requests: 3,
+                storage: 15_000,
+                min_reputation: None,
+            },
+        ));
+        assert_eq!(
+            Some(resolved),
+            Acurast::stored_job_registration(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_sparse_registration_without_consumer_defaults() {
+    ExtBuilder::default().build().execute_with(|| {
+        let mut sparse = dependent_registration(1_671_800_400_000, None);
+        sparse.extra.omitted = Some(OmittedFields {
+            memory: true,
+            ..Default::default()
+        });
+
+        assert_err!(
+            Acurast::register_for(
+                (MultiOrigin::Acurast(alice_account_id()), 1),
+                sparse.clone()
+            ),
+            Error::<Test>::ConsumerDefaultsNotFound
+        );
+
+        // defaults of another consumer are not used
+        assert_ok!(AcurastMarketplace::set_default_requirements(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            ConsumerDefaults {
+                allow_only_verified_sources: false,
+                required_modules: JobModules::default(),
+                memory: 1_000,
+                network_requests: 2,
+                storage: 10_000,
+                min_reputation: None,
+            },
+        ));
+        assert_err!(
+            Acurast::register_for((MultiOrigin::Acurast(alice_account_id()), 1), sparse),
+            Error::<Test>::ConsumerDefaultsNotFound
+        );
+    });
+}
+
+#[test]
+fn test_registration_below_approval_threshold_is_open() {
+    ExtBuilder::default().build().execute_with(|| {
+        // total reward of registration is 3_000_000 * 2 * 2 executions = 12_000_000
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            12_000_000,
+            bob_account_id(),
+        ));
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_ok!(Acurast::register_for(
+            job_id.clone(),
+            dependent_registration(1_671_800_400_000, None)
+        ));
+
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert!(!events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::RegistrationPendingApproval(job_id, bob_account_id())
+        )));
+    });
+}
+
+#[test]
+fn test_registration_above_approval_threshold_approved() {
+    ExtBuilder::default().build().execute_with(|| {
+        let approval = RegistrationApproval {
+            threshold: 10_000_000,
+            approver: bob_account_id(),
+        };
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            approval.threshold,
+            approval.approver.clone(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::RegistrationApprovalStored(alice_account_id(), approval)
+            )]
+        );
+
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_ok!(Acurast::register_for(
+            job_id.clone(),
+            dependent_registration(1_671_800_400_000, None)
+        ));
+        assert_eq!(
+            Some(JobStatus::PendingApproval),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        // the budget is locked while pending approval
+        assert_eq!(12_000_000, AcurastMarketplace::job_budgets(&job_id));
+        assert!(events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::RegistrationPendingApproval(job_id.clone(), bob_account_id())
+        )));
+
+        assert_ok!(AcurastMarketplace::approve_registration(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::RegistrationApproved(job_id.clone())
+            )]
+        );
+
+        // approving twice fails
+        assert_err!(
+            AcurastMarketplace::approve_registration(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                job_id,
+            ),
+            Error::<Test>::RegistrationNotPendingApproval
+        );
+    });
+}
+
+#[test]
+fn test_instant_match_rejected_above_approval_threshold() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            10_000_000,
+            bob_account_id(),
+        ));
+
+        let mut registration = dependent_registration(1_671_800_400_000, None);
+        registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+            source: processor_account_id(),
+            start_delay: 0,
+            pricing_version: None,
+        }]);
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_err!(
+            Acurast::register_for(job_id.clone(), registration),
+            Error::<Test>::InstantMatchPendingApproval
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_registration_deposit_refunded_on_expiry_pending_approval() {
+    ExtBuilder::default().build().execute_with(|| {
+        JobRegistrationDeposit::set(1_000_000);
+        let initial_balance = Balances::free_balance(&alice_account_id());
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            10_000_000,
+            bob_account_id(),
+        ));
+        let registration = dependent_registration(1_671_800_400_000, None);
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_ok!(Acurast::register_for(job_id.clone(), registration.clone()));
+        assert_eq!(
+            Some(JobStatus::PendingApproval),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            Balances::free_balance(&alice_account_id()),
+            initial_balance - 12_000_000 - 1_000_000
+        );
+
+        // the approver never approved, which is not to be blamed on the consumer
+        let total_issuance = Balances::total_issuance();
+        later(registration.schedule.end_time);
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            bounded_vec![job_id.1],
+        ));
+
+        assert_eq!(None, AcurastMarketplace::job_registration_deposits(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), initial_balance);
+        assert_eq!(Balances::total_issuance(), total_issuance);
+    });
+}
+
+#[test]
+fn test_registration_above_approval_threshold_rejected() {
+    ExtBuilder::default().build().execute_with(|| {
+        let initial_balance = Balances::free_balance(&alice_account_id());
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            10_000_000,
+            bob_account_id(),
+        ));
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_ok!(Acurast::register_for(
+            job_id.clone(),
+            dependent_registration(1_671_800_400_000, None)
+        ));
+        assert_eq!(
+            initial_balance - 12_000_000,
+            Balances::free_balance(&alice_account_id())
+        );
+
+        assert_ok!(AcurastMarketplace::reject_registration(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            job_id.clone(),
+        ));
+
+        assert_eq!(initial_balance, Balances::free_balance(&alice_account_id()));
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+        assert!(events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::RegistrationRejected(job_id)
+        )));
+    });
+}
+
+#[test]
+fn test_registration_approval_by_other_account_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            10_000_000,
+            bob_account_id(),
+        ));
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_ok!(Acurast::register_for(
+            job_id.clone(),
+            dependent_registration(1_671_800_400_000, None)
+        ));
+
+        // neither the consumer nor any other account can approve or reject
+        for who in [alice_account_id(), charlie_account_id()] {
+            assert_err!(
+                AcurastMarketplace::approve_registration(
+                    RuntimeOrigin::signed(who.clone()).into(),
+                    job_id.clone(),
+                ),
+                Error::<Test>::NotRegistrationApprover
+            );
+            assert_err!(
+                AcurastMarketplace::reject_registration(
+                    RuntimeOrigin::signed(who).into(),
+                    job_id.clone(),
+                ),
+                Error::<Test>::NotRegistrationApprover
+            );
+        }
+
+        // a pending job is not matchable
+        assert_eq!(
+            Some(JobStatus::PendingApproval),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+/// Runs both executions of an acknowledged job reporting delivery commitments, optionally challenging the first and
+/// resolving the challenge, and returns the processor's normalized reputation after finalization.
+fn reputation_after_delivery_challenge(challenge: bool, resolution: Option<bool>) -> Permill {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let mut reputation = Permill::zero();
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, None);
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            attestation_chain()
+        ));
+
+        for (i, execution) in registration.schedule.iter(0).unwrap().enumerate() {
+            later(execution + 1000);
+            assert_ok!(AcurastMarketplace::report(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+                ExecutionResult::SuccessWithDeliveryCommitment(
+                    operation_hash(),
+                    H256::repeat_byte(i as u8)
+                )
+            ));
+        }
+
+        if challenge {
+            assert_ok!(AcurastMarketplace::challenge_delivery(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                0,
+            ));
+        }
+        if let Some(upheld) = resolution {
+            assert_ok!(AcurastMarketplace::resolve_challenge(
+                RuntimeOrigin::root().into(),
+                job_id.clone(),
+                processor_account_id(),
+                0,
+                upheld,
+            ));
+        }
+
+        later(registration.schedule.end_time + 1);
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            0,
+            AcurastMarketplace::disputed_executions(&job_id, processor_account_id())
+        );
+
+        reputation = BetaReputation::<u128>::normalize(
+            AcurastMarketplace::stored_reputation(processor_account_id()).unwrap(),
+        )
+        .unwrap();
+    });
+    reputation
+}
+
+#[test]
+fn test_delivery_challenge_reputation_impact() {
+    let unchallenged = reputation_after_delivery_challenge(false, None);
+
+    // a rejected challenge does not affect the reputation
+    assert_eq!(
+        unchallenged,
+        reputation_after_delivery_challenge(true, Some(false))
+    );
+    // upheld and unresolved challenges count as unmet
+    let upheld = reputation_after_delivery_challenge(true, Some(true));
+    assert!(upheld < unchallenged);
+    assert_eq!(upheld, reputation_after_delivery_challenge(true, None));
+}
+
+#[test]
+fn test_delivery_challenge() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, None);
+        let executions: Vec<u64> = registration.schedule.iter(0).unwrap().collect();
+
+        later(executions[0] + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::SuccessWithDeliveryCommitment(operation_hash(), H256::repeat_byte(1))
+        ));
+        assert_eq!(
+            Some(DeliveryCommitment {
+                commitment: H256::repeat_byte(1),
+                reported_at: executions[0] + 1000,
+                challenge: None,
+            }),
+            AcurastMarketplace::stored_delivery_commitment(&job_id, (processor_account_id(), 0u64))
+        );
+        assert!(events().contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::DeliveryCommitted(
+                job_id.clone(),
+                processor_account_id(),
+                0,
+                H256::repeat_byte(1)
+            )
+        )));
+
+        assert_err!(
+            AcurastMarketplace::challenge_delivery(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                0,
+            ),
+            Error::<Test>::NotJobConsumer
+        );
+        assert_err!(
+            AcurastMarketplace::challenge_delivery(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                1,
+            ),
+            Error::<Test>::DeliveryCommitmentNotFound
+        );
+        assert_err!(
+            AcurastMarketplace::resolve_challenge(
+                RuntimeOrigin::root().into(),
+                job_id.clone(),
+                processor_account_id(),
+                0,
+                true,
+            ),
+            Error::<Test>::DeliveryChallengeNotPending
+        );
+
+        later(executions[1] + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::SuccessWithDeliveryCommitment(operation_hash(), H256::repeat_byte(2))
+        ));
+
+        assert_ok!(AcurastMarketplace::challenge_delivery(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.clone(),
+            processor_account_id(),
+            1,
+        ));
+        assert_eq!(
+            1,
+            AcurastMarketplace::disputed_executions(&job_id, processor_account_id())
+        );
+        // the mock allows a single disputed execution per assignment
+        assert_err!(
+            AcurastMarketplace::challenge_delivery(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                0,
+            ),
+            Error::<Test>::TooManyDeliveryChallenges
+        );
+
+        assert_err!(
+            AcurastMarketplace::resolve_challenge(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                1,
+                false,
+            ),
+            frame_support::error::BadOrigin
+        );
+        assert_ok!(AcurastMarketplace::resolve_challenge(
+            RuntimeOrigin::root().into(),
+            job_id.clone(),
+            processor_account_id(),
+            1,
+            false,
+        ));
+        assert_eq!(
+            0,
+            AcurastMarketplace::disputed_executions(&job_id, processor_account_id())
+        );
+        assert_eq!(
+            Some(ChallengeStatus::Rejected),
+            AcurastMarketplace::stored_delivery_commitment(&job_id, (processor_account_id(), 1u64))
+                .unwrap()
+                .challenge
+        );
+
+        // the first execution's challenge window passed
+        later(executions[0] + 1000 + DeliveryChallengeWindow::get());
+        assert_err!(
+            AcurastMarketplace::challenge_delivery(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                0,
+            ),
+            Error::<Test>::DeliveryChallengeWindowExpired
+        );
+        assert_err!(
+            AcurastMarketplace::challenge_delivery(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                processor_account_id(),
+                1,
+            ),
+            Error::<Test>::DeliveryAlreadyChallenged
+        );
+        assert_eq!(2, crate::DeliveryCommitmentCount::<Test>::get(&job_id));
+
+        // the commitments are removed with the finalized job
+        later(registration.schedule.end_time + 1);
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            vec![job_id.1].try_into().unwrap(),
+        ));
+        for execution_index in [0u64, 1] {
+            assert_eq!(
+                None,
+                AcurastMarketplace::stored_delivery_commitment(
+                    &job_id,
+                    (processor_account_id(), execution_index)
+                )
+            );
+        }
+        assert_eq!(0, crate::DeliveryCommitmentCount::<Test>::get(&job_id));
+    });
+}
+
+fn next_block() {
+    if System::block_number() >= 1 {
+        // pallet_acurast_marketplace::on_finalize(System::block_number());
+        Timestamp::on_finalize(System::block_number());
+    }
+    System::set_block_number(System::block_number() + 1);
+    Timestamp::on_initialize(System::block_number());
+}
+
+/// A helper function to move time on in tests. It ensures `Timestamp::set` is only called once per block by advancing the block otherwise.
+fn later(now: u64) {
+    // If this is not the very first timestamp ever set, we always advance the block before setting new time
+    // this is because setting it twice in a block is not legal
+    if Timestamp::get() > 0 {
+        // pretend block was finalized
+        let b = System::block_number();
+        next_block(); // we cannot set time twice in same block
+        assert_eq!(b + 1, System::block_number());
+    }
+    // pretend time moved on
+    assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+}
+
+/// Fails if the calls, events, errors or constants exposed to SDKs changed without updating the committed snapshot.
+#[test]
+fn metadata_artifact_matches_snapshot() {
+    let artifact = acurast_metadata_gen::generate(&Test::metadata(), |name| {
+        ["Acurast", "AcurastMarketplace"].contains(&name)
+    })
+    .unwrap();
+    assert_eq!(
+        artifact
+            .pallets
+            .iter()
+            .map(|pallet| pallet.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Acurast", "AcurastMarketplace"]
+    );
+    acurast_metadata_gen::assert_snapshot(
+        &artifact,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/metadata/marketplace.json"),
+    );
+}
+
+#[test]
+fn test_registration_exceeding_max_executions_per_job() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxExecutionsPerJob::set(1);
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+
+        // the registration's schedule contains 2 executions
+        assert_err!(
+            Acurast::register_for(
+                job_id.clone(),
+                dependent_registration(1_671_800_400_000, None)
+            ),
+            Error::<Test>::JobRegistrationScheduleExceedsMaximumExecutions
+        );
+
+        MaxExecutionsPerJob::set(2);
+        assert_ok!(Acurast::register_for(
+            job_id,
+            dependent_registration(1_671_800_400_000, None)
+        ));
+        MaxExecutionsPerJob::set(6_308_000);
+    });
+}
+
+/// Lowering [`Config::MaxExecutionsPerJob`] below the executions of an already registered job does not affect it.
+#[test]
+fn test_lowered_max_executions_per_job_keeps_existing_jobs() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, registration) = setup_acknowledged_job(now, None);
+        MaxExecutionsPerJob::set(1);
+
+        for execution in registration.schedule.iter(0).unwrap() {
+            later(execution + 1000);
+            assert_ok!(AcurastMarketplace::report(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+                ExecutionResult::Success(operation_hash())
+            ));
+        }
+        assert_eq!(
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+                .unwrap()
+                .sla,
+            SLA { total: 2, met: 2 }
+        );
+
+        later(registration.schedule.end_time + 1);
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        MaxExecutionsPerJob::set(6_308_000);
+    });
+}
+
+#[test]
+fn test_max_executions_per_job_in_metadata() {
+    let artifact =
+        acurast_metadata_gen::generate(&Test::metadata(), |name| name == "AcurastMarketplace")
+            .unwrap();
+    let constant = artifact.pallets[0]
+        .constants
+        .iter()
+        .find(|constant| constant.name == "MaxExecutionsPerJob")
+        .unwrap();
+
+    assert_eq!(constant.ty, "u64");
+    assert_eq!(
+        constant.value,
+        format!(
+            "0x{}",
+            6_308_000u64
+                .encode()
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>()
+        )
+    );
+}
+
+#[test]
+fn test_limits() {
+    let limits = acurast_limits();
+    assert_eq!(
+        limits,
+        AcurastLimits {
+            version: ACURAST_LIMITS_VERSION,
+            max_allowed_sources: Some(4),
+            max_slots: Some(64),
+            max_executions_per_job: Some(MaxExecutionsPerJob::get()),
+            report_tolerance: Some(ReportTolerance::get()),
+            max_proposed_matches: Some(10),
+            pairing_proof_expiration_time: None,
+            max_pairing_updates: None,
+            heartbeat_interval: None,
+            unpair_cooldown: None,
+        }
+    );
+
+    // the limits match the constants exposed in the metadata
+    let artifact = acurast_metadata_gen::generate(&Test::metadata(), |name| {
+        name == "Acurast" || name == "AcurastMarketplace"
+    })
+    .unwrap();
+    let hex = |value: &[u8]| {
+        format!(
+            "0x{}",
+            value
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>()
+        )
+    };
+    for (pallet, name, value) in [
+        (
+            "Acurast",
+            "MaxAllowedSources",
+            limits.max_allowed_sources.encode(),
+        ),
+        ("Acurast", "MaxSlots", limits.max_slots.encode()),
+        (
+            "AcurastMarketplace",
+            "MaxExecutionsPerJob",
+            limits.max_executions_per_job.encode(),
+        ),
+        (
+            "AcurastMarketplace",
+            "ReportTolerance",
+            limits.report_tolerance.encode(),
+        ),
+        (
+            "AcurastMarketplace",
+            "MaxProposedMatches",
+            limits.max_proposed_matches.encode(),
+        ),
+    ] {
+        // skip the encoded `Some` variant
+        assert_eq!(
+            artifact.constant(pallet, name).unwrap().value,
+            hex(&value[1..]),
+            "{}::{}",
+            pallet,
+            name
+        );
+    }
+}
+
+/// Registers a [`dependent_registration`] without dependency performing `network_requests` per execution of 5 seconds,
+/// with [`processor_account_id`] advertising 8 network requests per second.
+fn register_with_network_requests(
+    now: u64,
+    duration: u64,
+    network_requests: u32,
+) -> DispatchResultWithPostInfo {
+    assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+    assert_ok!(AcurastMarketplace::advertise(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        advertisement(1000, 1, 100_000, 50_000, 8),
+    ));
+    let mut registration = dependent_registration(1_671_800_400_000, None);
+    registration.schedule.duration = duration;
+    registration.network_requests = network_requests;
+    Acurast::register(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        registration,
+    )
+}
+
+#[test]
+fn test_match_network_request_quota_boundary() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    for (network_requests, expected) in [
+        (40, Ok(JobStatus::Matched)),
+        (41, Err(Error::<Test>::NetworkRequestQuotaExceededInMatch)),
+    ] {
+        ExtBuilder::default().build().execute_with(|| {
+            let initial_job_id = Acurast::job_id_sequence();
+            let job_id = (MultiOrigin::Acurast(alice_account_id()), initial_job_id + 1);
+            assert_ok!(register_with_network_requests(now, 5000, network_requests));
+
+            let result = AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, processor_account_id())]
+                    .try_into()
+                    .unwrap(),
+            );
+            match expected {
+                Ok(status) => {
+                    assert_ok!(result);
+                    assert_eq!(
+                        Some(status),
+                        AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+                    );
+                }
+                Err(error) => assert_err!(result, error),
+            }
+        });
+    }
+}
+
+#[test]
+fn test_zero_duration_registration_rejected_before_network_request_quota() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        // a zero duration would not permit any network request, but the registration is rejected upfront
+        assert_err!(
+            register_with_network_requests(now, 0, 5),
+            Error::<Test>::JobRegistrationZeroDuration
+        );
+        assert_err!(
+            register_with_network_requests(now, 0, 0),
+            Error::<Test>::JobRegistrationZeroDuration
+        );
+    });
+}
+
+/// Registers a two-slot job for alice instantly matched with [`processor_account_id`] (slot 0) and [`processor_2_account_id`] (slot 1).
+fn setup_two_slot_job(now: u64) -> JobId<AccountId> {
+    let mut registration = dependent_registration(1_671_800_400_000, None);
+    registration.extra.slots = 2;
+    registration.extra.instant_match = Some(bounded_vec![
+        PlannedExecution {
+            source: processor_account_id(),
+            start_delay: 0,
+            pricing_version: None,
+        },
+        PlannedExecution {
+            source: processor_2_account_id(),
+            start_delay: 0,
+            pricing_version: None,
+        }
+    ]);
+
+    later(now);
+    let _ = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        MultiAddress::Id(alice_account_id()),
+        100_000_000,
+    );
+    for processor in [processor_account_id(), processor_2_account_id()] {
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+    }
+    let job_id = (
+        MultiOrigin::Acurast(alice_account_id()),
+        Acurast::job_id_sequence() + 1,
+    );
+    assert_ok!(Acurast::register(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        registration,
+    ));
+    assert_eq!(
+        Some(JobStatus::Matched),
+        AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+    );
+    job_id
+}
+
+#[test]
+fn test_acknowledge_match_records_slots() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        assert_eq!(
+            Ok(vec![0, 1]),
+            AcurastMarketplace::unacknowledged_slots(job_id.clone())
+        );
+
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+        assert_eq!(
+            vec![1],
+            AcurastMarketplace::acknowledged_slots(&job_id).into_inner()
+        );
+        assert_eq!(
+            Ok(vec![0]),
+            AcurastMarketplace::unacknowledged_slots(job_id.clone())
+        );
+
+        // acknowledging the same slot again does not drift the count
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Assigned(1)),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+        assert_eq!(
+            vec![0, 1],
+            AcurastMarketplace::acknowledged_slots(&job_id).into_inner()
+        );
+        assert_eq!(
+            Some(JobStatus::Assigned(2)),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            Ok(vec![]),
+            AcurastMarketplace::unacknowledged_slots(job_id.clone())
+        );
+    });
+}
+
+#[test]
+fn test_acknowledged_slots_kept_on_slot_finalization() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        for processor in [processor_account_id(), processor_2_account_id()] {
+            assert_ok!(AcurastMarketplace::acknowledge_match(
+                RuntimeOrigin::signed(processor).into(),
+                job_id.clone(),
+                PubKeys::default(),
+            ));
+        }
+
+        later(1_671_804_000_000 + 10_000);
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        // the finalized slot neither counts as unacknowledged nor reduces the acknowledged count
+        assert_eq!(
+            Some(JobStatus::Assigned(2)),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            Ok(vec![]),
+            AcurastMarketplace::unacknowledged_slots(job_id.clone())
+        );
+
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            vec![job_id.1].try_into().unwrap(),
+        ));
+        assert!(AcurastMarketplace::acknowledged_slots(&job_id).is_empty());
+        assert_eq!(
+            Err(RuntimeApiError::UnacknowledgedSlots),
+            AcurastMarketplace::unacknowledged_slots(job_id.clone())
+        );
+    });
+}
+
+#[test]
+fn test_acknowledged_slots_cleared_on_deregister() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+        assert_eq!(
+            vec![0],
+            AcurastMarketplace::acknowledged_slots(&job_id).into_inner()
+        );
+
+        assert_ok!(Acurast::deregister(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1
+        ));
+        assert!(AcurastMarketplace::acknowledged_slots(&job_id).is_empty());
+    });
+}
+
+/// Simulates the expiry of an unacknowledged match by reverting the job to [`JobStatus::Open`] and releasing the source's capacity.
+fn expire_match(job_id: &JobId<AccountId>, source: &AccountId, storage: u32) {
+    crate::StoredMatches::<Test>::remove(source, job_id);
+    crate::SourceJobIndex::<Test>::mutate_exists(source, |jobs| {
+        if let Some(j) = jobs {
+            j.retain(|other| other != job_id);
+            if j.is_empty() {
+                *jobs = None;
+            }
+        }
+    });
+    crate::AssignedProcessors::<Test>::remove(job_id, source);
+    crate::StoredStorageCapacity::<Test>::mutate(source, |c| {
+        *c = c.map(|c| c + storage as i64);
+    });
+    crate::StoredJobStatus::<Test>::insert(&job_id.0, &job_id.1, JobStatus::Open);
+}
+
+#[test]
+fn test_matcher_reward_capped_on_rematch() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            dependent_registration(1_671_800_400_000, None),
+        ));
+        assert_eq!(12_000_000, AcurastMarketplace::reserved(&job_id));
+
+        let matcher_balance = Balances::free_balance(&charlie_account_id());
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        // 10% of the 1_960_000 not spent on fees of 2 * 5_020_000, paid out after 30% fee
+        assert_eq!(
+            Some(196_000),
+            AcurastMarketplace::matcher_rewards_paid(&job_id)
+        );
+        assert_eq!(11_804_000, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(
+            matcher_balance + 137_200,
+            Balances::free_balance(&charlie_account_id())
+        );
+
+        // the cap from the first match is exhausted, so no re-match extracts further matcher rewards
+        for _ in 0..2 {
+            expire_match(&job_id, &processor_account_id(), 20_000);
+            assert_ok!(AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, processor_account_id())]
+                    .try_into()
+                    .unwrap(),
+            ));
+            assert_eq!(
+                Some(JobStatus::Matched),
+                AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+            );
+            assert_eq!(
+                Some(196_000),
+                AcurastMarketplace::matcher_rewards_paid(&job_id)
+            );
+            assert_eq!(11_804_000, AcurastMarketplace::reserved(&job_id));
+            assert_eq!(
+                matcher_balance + 137_200,
+                Balances::free_balance(&charlie_account_id())
+            );
+        }
+
+        assert_ok!(Acurast::deregister(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1
+        ));
+        assert_eq!(None, AcurastMarketplace::matcher_rewards_paid(&job_id));
+    });
+}
+
+#[test]
+fn test_matcher_reward_not_paid_on_rematch_of_instant_match() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        assert_eq!(Some(0), AcurastMarketplace::matcher_rewards_paid(&job_id));
+        let reserved = AcurastMarketplace::reserved(&job_id);
+
+        expire_match(&job_id, &processor_account_id(), 20_000);
+        expire_match(&job_id, &processor_2_account_id(), 20_000);
+        let matcher_balance = Balances::free_balance(&charlie_account_id());
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![Match {
+                job_id: job_id.clone(),
+                sources: bounded_vec![
+                    PlannedExecution {
+                        source: processor_account_id(),
+                        start_delay: 0,
+                        pricing_version: None,
+                    },
+                    PlannedExecution {
+                        source: processor_2_account_id(),
+                        start_delay: 0,
+                        pricing_version: None,
+                    }
+                ],
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+        assert_eq!(reserved, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(
+            matcher_balance,
+            Balances::free_balance(&charlie_account_id())
+        );
+    });
+}
+
+fn advertisement_with_window(scheduling_window: SchedulingWindow) -> AdvertisementFor<Test> {
+    let mut ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    ad.pricing.scheduling_window = scheduling_window;
+    ad
+}
+
+#[test]
+fn test_advertise_rejects_delta_window_below_minimum() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+
+        assert_err!(
+            AcurastMarketplace::advertise(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                advertisement_with_window(SchedulingWindow::Delta(3_599_999)),
+            ),
+            Error::<Test>::SchedulingWindowTooShort
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_advertisement_pricing(processor_account_id())
+        );
+
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement_with_window(SchedulingWindow::Delta(3_600_000)),
+        ));
+    });
+}
+
+#[test]
+fn test_advertise_rejects_end_window_below_minimum() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+
+        for end in [now - 1, now, now + 3_599_999] {
+            assert_err!(
+                AcurastMarketplace::advertise(
+                    RuntimeOrigin::signed(processor_account_id()).into(),
+                    advertisement_with_window(SchedulingWindow::End(end)),
+                ),
+                Error::<Test>::SchedulingWindowTooShort
+            );
+        }
+
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement_with_window(SchedulingWindow::End(now + 3_600_000)),
+        ));
+    });
+}
+
+#[test]
+fn test_check_scheduling_windows_flags_without_removing() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+
+        let end_window = SchedulingWindow::End(now + 7_200_000);
+        let delta_window = SchedulingWindow::Delta(7_200_000);
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement_with_window(end_window),
+        ));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            advertisement_with_window(delta_window),
+        ));
+        let sources = vec![
+            processor_account_id(),
+            processor_2_account_id(),
+            // has no advertisement and is skipped
+            alice_account_id(),
+        ];
+
+        // the end window shrinks below the minimum over time, the delta window does not
+        later(now + 3_600_001);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::check_scheduling_windows(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            sources.clone().try_into().unwrap(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::AdvertisementBelowMinimumWindow(processor_account_id(), end_window)
+            )]
+        );
+
+        // raising the minimum flags the delta window as well
+        MinSchedulingWindow::set(7_200_001);
+        assert_ok!(AcurastMarketplace::check_scheduling_windows(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            sources.try_into().unwrap(),
+        ));
+        assert_eq!(
+            events(),
+            [
+                RuntimeEvent::AcurastMarketplace(crate::Event::AdvertisementBelowMinimumWindow(
+                    processor_account_id(),
+                    end_window
+                )),
+                RuntimeEvent::AcurastMarketplace(crate::Event::AdvertisementBelowMinimumWindow(
+                    processor_2_account_id(),
+                    delta_window
+                )),
+            ]
+        );
+        MinSchedulingWindow::set(3_600_000);
+
+        // advertisements are only flagged, never removed
+        assert!(AcurastMarketplace::stored_advertisement_pricing(processor_account_id()).is_some());
+        assert!(
+            AcurastMarketplace::stored_advertisement_pricing(processor_2_account_id()).is_some()
+        );
+    });
+}
+
+#[test]
+fn test_expire_unmatched_job() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let registration = dependent_registration(1_671_800_400_000, None);
+
+    ExtBuilder::default().build().execute_with(|| {
+        JobRegistrationDeposit::set(1_000_000);
+        later(now);
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 87_000_000);
+
+        later(registration.schedule.end_time - 1);
+        assert_err!(
+            AcurastMarketplace::expire_unmatched_job(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::CannotFinalizeJob(JobStatus::Open)
+        );
+
+        let total_issuance = Balances::total_issuance();
+        later(registration.schedule.end_time);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::expire_unmatched_job(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            job_id.clone(),
+        ));
+
+        // the reward got refunded but the deposit got burned
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_eq!(None, AcurastMarketplace::job_registration_deposits(&job_id));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 99_000_000);
+        assert_eq!(Balances::total_issuance(), total_issuance - 1_000_000);
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::AcurastMarketplace(
+                crate::Event::JobExpiredUnmatched(job_id)
+            ))
+            .as_ref()
+        );
+        JobRegistrationDeposit::set(0);
+    });
+}
+
+#[test]
+fn test_expire_unmatched_job_refunds_once() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let registration = dependent_registration(1_671_800_400_000, None);
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration.clone(),
+        ));
+
+        later(registration.schedule.end_time);
+        assert_ok!(AcurastMarketplace::expire_unmatched_job(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(Balances::free_balance(&alice_account_id()), 100_000_000);
+
+        // neither a second expiry nor a deregistration refund the budget again
+        assert_err!(
+            AcurastMarketplace::expire_unmatched_job(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::JobStatusNotFound
+        );
+        assert!(
+            Acurast::deregister(RuntimeOrigin::signed(alice_account_id()).into(), job_id.1)
+                .is_err()
+        );
+        assert_eq!(Balances::free_balance(&alice_account_id()), 100_000_000);
+    });
+}
+
+#[test]
+fn test_expire_unmatched_job_rejects_matched_job() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        let registration =
+            Acurast::stored_job_registration(&job_id.0, &job_id.1).expect("job is registered");
+
+        later(registration.schedule.end_time);
+        assert_err!(
+            AcurastMarketplace::expire_unmatched_job(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::CannotFinalizeJob(JobStatus::Matched)
+        );
+    });
+}
+
+#[test]
+fn test_match_requires_attested_module() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let mut ad = advertisement(1000, 1, 100_000, 50_000, 8);
+    ad.available_modules = bounded_vec![JobModule::DataEncryption];
+    let mut registration = dependent_registration(1_671_800_400_000, None);
+    registration.required_modules = bounded_vec![JobModule::DataEncryption];
+    let partial_registration = crate::PartialJobRegistration {
+        allowed_sources: None,
+        allow_only_verified_sources: false,
+        schedule: None,
+        memory: None,
+        network_requests: None,
+        storage: None,
+        required_modules: bounded_vec![JobModule::DataEncryption],
+        slots: None,
+        reward: 0,
+        min_reputation: None,
+        min_recent_fulfillment_ratio: None,
+        max_attestation_age_ms: None,
+        max_fee_per_execution: None,
+    };
+    let set_key = |security_level: AttestationSecurityLevel, purpose: Vec<u8>| {
+        pallet_acurast::StoredAttestation::<Test>::mutate(processor_account_id(), |attestation| {
+            let key_description = &mut attestation.as_mut().unwrap().key_description;
+            key_description.attestation_security_level = security_level;
+            key_description.tee_enforced.purpose = Some(purpose.try_into().unwrap());
+        });
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        AttestedModules::set(bounded_vec![JobModule::DataEncryption]);
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        let planned_match = single_source_match(&job_id, processor_account_id());
+
+        later(now);
+        assert_ok!(Acurast::submit_attestation(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            attestation_chain()
+        ));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            ad,
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration,
+        ));
+
+        // the attested key cannot be used for key agreement
+        set_key(AttestationSecurityLevel::StrongBox, vec![2, 3]);
+        assert_eq!(
+            Vec::<(AccountId, u32)>::new(),
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration.clone(),
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+            .unwrap()
+        );
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![planned_match.clone()].try_into().unwrap(),
+            ),
+            Error::<Test>::ModuleNotAttested
+        );
+
+        // the attested key is not hardware-backed
+        set_key(AttestationSecurityLevel::Software, vec![2, 6]);
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![planned_match.clone()].try_into().unwrap(),
+            ),
+            Error::<Test>::ModuleNotAttested
+        );
+
+        set_key(AttestationSecurityLevel::StrongBox, vec![2, 6]);
+        assert_eq!(
+            vec![(processor_account_id(), 1)],
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration,
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+            .unwrap()
+        );
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![planned_match].try_into().unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        AttestedModules::set(Default::default());
+    });
+}
+
+#[test]
+fn test_failed_registration_is_not_persisted_when_error_is_ignored() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let registration = dependent_registration(now - 1, None);
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+
+        // a cross-chain executor processing the registration does not propagate the hook's error
+        let _ = Acurast::register_for(job_id.clone(), registration);
+
+        assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+        assert_ok!(AcurastMarketplace::do_try_state());
+    });
+}
+
+#[test]
+fn test_repair_job_status() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+
+        crate::StoredJobStatus::<Test>::remove(&job_id.0, &job_id.1);
+        assert!(AcurastMarketplace::do_try_state().is_err());
+
+        assert_err!(
+            AcurastMarketplace::repair_job_status(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                job_id.clone(),
+            ),
+            DispatchError::BadOrigin
+        );
+        let _ = events();
+        assert_ok!(AcurastMarketplace::repair_job_status(
+            RuntimeOrigin::root(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Assigned(1)),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::AcurastMarketplace(
+                crate::Event::JobStatusRepaired(job_id.clone(), JobStatus::Assigned(1))
+            ))
+            .as_ref()
+        );
+        assert_ok!(AcurastMarketplace::do_try_state());
+
+        assert_err!(
+            AcurastMarketplace::repair_job_status(RuntimeOrigin::root(), job_id.clone()),
+            Error::<Test>::JobStatusAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn test_restricted_matching_requires_approved_matcher() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        RestrictedMatching::set(true);
+        let job_id = setup_preferred_job(now);
+        let matches = vec![single_source_match(&job_id, processor_2_account_id())];
+
+        later(now + 600_000);
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                matches.clone().try_into().unwrap(),
+            ),
+            Error::<Test>::MatcherNotApproved
+        );
+
+        assert_err!(
+            AcurastMarketplace::add_approved_matcher(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                charlie_account_id(),
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(AcurastMarketplace::add_approved_matcher(
+            RuntimeOrigin::root(),
+            charlie_account_id(),
+        ));
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            matches.try_into().unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        RestrictedMatching::set(false);
+    });
+}
+
+#[test]
+fn test_matcher_stats_accumulate() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let matches = vec![single_source_match(&job_id, processor_2_account_id())];
+        assert_eq!(
+            MatcherPerformance::default(),
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+
+        // the open marketplace accepts any matcher
+        later(now + 600_000);
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            matches.clone().try_into().unwrap(),
+        ));
+        assert_eq!(
+            MatcherPerformance {
+                proposals: 1,
+                accepted_matches: 1,
+                rejected_entries: 0,
+            },
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+
+        // the job is no longer open, so the entry is skipped
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            matches.try_into().unwrap(),
+        ));
+        assert_eq!(
+            MatcherPerformance {
+                proposals: 2,
+                accepted_matches: 1,
+                rejected_entries: 1,
+            },
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+        assert_eq!(
+            MatcherPerformance::default(),
+            AcurastMarketplace::matcher_stats(bob_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_removed_matcher_is_rejected_immediately() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        RestrictedMatching::set(true);
+        let job_id = setup_preferred_job(now);
+
+        assert_ok!(AcurastMarketplace::add_approved_matcher(
+            RuntimeOrigin::root(),
+            charlie_account_id(),
+        ));
+        let _ = events();
+        assert_ok!(AcurastMarketplace::remove_approved_matcher(
+            RuntimeOrigin::root(),
+            charlie_account_id(),
+        ));
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::AcurastMarketplace(
+                crate::Event::MatcherApprovalRemoved(charlie_account_id())
+            ))
+            .as_ref()
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::approved_matcher(charlie_account_id())
+        );
+
+        later(now + 600_000);
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, processor_2_account_id())]
+                    .try_into()
+                    .unwrap(),
+            ),
+            Error::<Test>::MatcherNotApproved
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_err!(
+            AcurastMarketplace::remove_approved_matcher(
+                RuntimeOrigin::root(),
+                charlie_account_id()
+            ),
+            Error::<Test>::MatcherApprovalNotFound
+        );
+        RestrictedMatching::set(false);
+    });
+}
+
+/// Returns a match proposing both processors for the single slot of the job from [`setup_preferred_job`].
+fn two_source_match(job_id: &JobId<AccountId>) -> MatchFor<Test> {
+    let mut m = single_source_match(job_id, processor_account_id());
+    m.sources
+        .try_push(PlannedExecution {
+            source: processor_2_account_id(),
+            start_delay: 0,
+            pricing_version: None,
+        })
+        .unwrap();
+    m
+}
+
+#[test]
+fn test_last_match_attempt_records_rejected_proposals() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        assert_eq!(None, AcurastMarketplace::last_match_attempt(&job_id));
+
+        later(now + 1000);
+        let first_block = System::block_number();
+        let _ = events();
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::MatchRejected(
+                    job_id.clone(),
+                    MatchingReasonCode::OutsidePreferenceWindow
+                )
+            )]
+        );
+        assert_eq!(
+            Some(MatchAttempt {
+                block: first_block,
+                reason: MatchingReasonCode::OutsidePreferenceWindow,
+                attempts: 1,
+            }),
+            AcurastMarketplace::last_match_attempt(&job_id)
+        );
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            MatcherPerformance {
+                proposals: 1,
+                accepted_matches: 0,
+                rejected_entries: 1,
+            },
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+
+        // a failing strict proposal is reverted together with its record
+        later(now + 2000);
+        assert_err!(
+            AcurastMarketplace::propose_matching(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![two_source_match(&job_id)].try_into().unwrap(),
+            ),
+            Error::<Test>::IncorrectSourceCountInMatch
+        );
+        assert_eq!(
+            Some(MatchAttempt {
+                block: first_block,
+                reason: MatchingReasonCode::OutsidePreferenceWindow,
+                attempts: 1,
+            }),
+            AcurastMarketplace::last_match_attempt(&job_id)
+        );
+
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![two_source_match(&job_id)].try_into().unwrap(),
+        ));
+        assert_eq!(
+            Some(MatchAttempt {
+                block: System::block_number(),
+                reason: MatchingReasonCode::IncorrectSourceCount,
+                attempts: 2,
+            }),
+            AcurastMarketplace::last_match_attempt(&job_id)
+        );
+
+        // a successful match clears the record
+        later(now + 600_000);
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(None, AcurastMarketplace::last_match_attempt(&job_id));
+    });
+}
+
+#[test]
+fn test_last_match_attempt_written_once_per_block() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+
+        later(now + 1000);
+        let block = System::block_number();
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        // further rejections within the same block, also in the same proposal, leave the record untouched
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            vec![two_source_match(&job_id), two_source_match(&job_id)]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            Some(MatchAttempt {
+                block,
+                reason: MatchingReasonCode::OutsidePreferenceWindow,
+                attempts: 1,
+            }),
+            AcurastMarketplace::last_match_attempt(&job_id)
+        );
+        assert_eq!(
+            2,
+            AcurastMarketplace::matcher_stats(bob_account_id()).rejected_entries
+        );
+    });
+}
+
+#[test]
+fn test_lenient_matching_fails_on_hard_errors() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+
+        later(now + 600_000);
+        // the source has no advertisement, which is not a matching error
+        assert_err!(
+            AcurastMarketplace::propose_matching_lenient(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, dave_account_id())]
+                    .try_into()
+                    .unwrap(),
+            ),
+            Error::<Test>::AdvertisementNotFound
+        );
+        assert_eq!(None, AcurastMarketplace::last_match_attempt(&job_id));
+        assert_eq!(
+            MatcherPerformance::default(),
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_last_match_attempt_cleared_on_expiry() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+
+        later(now + 1000);
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert!(AcurastMarketplace::last_match_attempt(&job_id).is_some());
+        assert_eq!(
+            Ok(Some(MatchAttempt {
+                block: System::block_number(),
+                reason: MatchingReasonCode::OutsidePreferenceWindow,
+                attempts: 1,
+            })),
+            AcurastMarketplace::last_match_attempt_info(job_id.clone())
+        );
+
+        later(1_671_804_000_000);
+        assert_ok!(AcurastMarketplace::expire_unmatched_job(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(None, AcurastMarketplace::last_match_attempt(&job_id));
+    });
+}
+
+/// Registers a job with two slots instantly matched with both processors and acknowledged by both.
+fn setup_assigned_two_slot_job(now: u64) -> JobId<AccountId> {
+    let job_id = setup_two_slot_job(now);
+    for processor in [processor_account_id(), processor_2_account_id()] {
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+    }
+    assert_eq!(
+        Some(JobStatus::Assigned(2)),
+        AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+    );
+    job_id
+}
+
+/// The schedule of `job_id` extended to end `hours` after its start.
+fn extended_schedule(job_id: &JobId<AccountId>, hours: u64) -> Schedule {
+    let mut schedule = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+        .unwrap()
+        .schedule;
+    schedule.end_time = schedule.start_time + hours * 3_600_000;
+    schedule
+}
+
+#[test]
+fn test_schedule_change_applied_on_full_approval() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_assigned_two_slot_job(now);
+        let previous_count = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+            .unwrap()
+            .schedule
+            .execution_count();
+        let budget_before = AcurastMarketplace::job_budgets(&job_id);
+        let consumer_balance_before = Balances::free_balance(alice_account_id());
+        let schedule = extended_schedule(&job_id, 2);
+        let _ = events();
+
+        assert_err!(
+            AcurastMarketplace::propose_schedule_change(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                job_id.clone(),
+                schedule.clone(),
+            ),
+            Error::<Test>::NotJobConsumer
+        );
+        let mut invalid = schedule.clone();
+        invalid.duration = invalid.interval;
+        assert_err!(
+            AcurastMarketplace::propose_schedule_change(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                job_id.clone(),
+                invalid,
+            ),
+            Error::<Test>::JobRegistrationDurationExceedsInterval
+        );
+
+        assert_ok!(AcurastMarketplace::propose_schedule_change(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.clone(),
+            schedule.clone(),
+        ));
+        assert_ok!(AcurastMarketplace::approve_schedule_change(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_err!(
+            AcurastMarketplace::approve_schedule_change(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::ScheduleChangeAlreadyApproved
+        );
+        assert_err!(
+            AcurastMarketplace::approve_schedule_change(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::JobNotAssigned
+        );
+        // not applied before all assigned processors approved
+        assert_eq!(
+            previous_count,
+            Acurast::stored_job_registration(&job_id.0, &job_id.1)
+                .unwrap()
+                .schedule
+                .execution_count()
+        );
+
+        assert_ok!(AcurastMarketplace::approve_schedule_change(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            job_id.clone(),
+        ));
+
+        assert_eq!(
+            schedule,
+            Acurast::stored_job_registration(&job_id.0, &job_id.1)
+                .unwrap()
+                .schedule
+        );
+        assert_eq!(None, AcurastMarketplace::pending_schedule_change(&job_id));
+        let count = schedule.execution_count();
+        assert!(count > previous_count);
+        for processor in [processor_account_id(), processor_2_account_id()] {
+            assert_eq!(
+                SLA {
+                    total: count,
+                    met: 0
+                },
+                AcurastMarketplace::stored_matches(&processor, &job_id)
+                    .unwrap()
+                    .sla
+            );
+        }
+        // the reward of 6_000_000 per execution is locked for both slots
+        let delta = 6_000_000 * 2 * (count - previous_count) as u128;
+        assert_eq!(
+            budget_before + delta,
+            AcurastMarketplace::job_budgets(&job_id)
+        );
+        assert_eq!(
+            consumer_balance_before - delta,
+            Balances::free_balance(alice_account_id())
+        );
+
+        let events = events();
+        for event in [
+            crate::Event::ScheduleChangeProposed(job_id.clone(), schedule.clone()),
+            crate::Event::ScheduleChangeApproved(job_id.clone(), processor_account_id()),
+            crate::Event::ScheduleChangeApproved(job_id.clone(), processor_2_account_id()),
+        ] {
+            assert!(events.contains(&RuntimeEvent::AcurastMarketplace(event)));
+        }
+        assert_eq!(
+            events.last(),
+            Some(RuntimeEvent::AcurastMarketplace(
+                crate::Event::ScheduleChangeApplied(job_id.clone(), schedule)
+            ))
+            .as_ref()
+        );
+    });
+}
+
+#[test]
+fn test_schedule_change_partial_approval_timeout() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_assigned_two_slot_job(now);
+        let previous_schedule = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+            .unwrap()
+            .schedule;
+        let budget_before = AcurastMarketplace::job_budgets(&job_id);
+
+        assert_ok!(AcurastMarketplace::propose_schedule_change(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.clone(),
+            extended_schedule(&job_id, 2),
+        ));
+        assert_ok!(AcurastMarketplace::approve_schedule_change(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_err!(
+            AcurastMarketplace::cancel_schedule_change(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::ScheduleChangeNotExpired
+        );
+
+        later(now + ScheduleChangeTimeout::get());
+        assert_err!(
+            AcurastMarketplace::approve_schedule_change(
+                RuntimeOrigin::signed(processor_2_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::ScheduleChangeExpired
+        );
+        assert_ok!(AcurastMarketplace::cancel_schedule_change(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::AcurastMarketplace(
+                crate::Event::ScheduleChangeCancelled(job_id.clone())
+            ))
+            .as_ref()
+        );
+        assert_eq!(None, AcurastMarketplace::pending_schedule_change(&job_id));
+        assert_err!(
+            AcurastMarketplace::approve_schedule_change(
+                RuntimeOrigin::signed(processor_2_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::ScheduleChangeNotFound
+        );
+
+        // the job is unchanged
+        assert_eq!(
+            previous_schedule,
+            Acurast::stored_job_registration(&job_id.0, &job_id.1)
+                .unwrap()
+                .schedule
+        );
+        assert_eq!(budget_before, AcurastMarketplace::job_budgets(&job_id));
+        assert_eq!(
+            previous_schedule.execution_count(),
+            AcurastMarketplace::stored_matches(&processor_account_id(), &job_id)
+                .unwrap()
+                .sla
+                .total
+        );
+    });
+}
+
+#[test]
+fn test_schedule_change_approval_conflicting_with_other_assignment() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_assigned_two_slot_job(now);
+        let schedule = extended_schedule(&job_id, 3);
+
+        // a second job of the first processor, starting after the current schedule but within the proposed one
+        let mut registration = dependent_registration(schedule.start_time + 7_200_000, None);
+        registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+            source: processor_account_id(),
+            start_delay: 0,
+            pricing_version: None,
+        }]);
+        let other_job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            registration,
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&other_job_id.0, &other_job_id.1)
+        );
+
+        assert_ok!(AcurastMarketplace::propose_schedule_change(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.clone(),
+            schedule,
+        ));
+        assert_err!(
+            AcurastMarketplace::approve_schedule_change(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::ScheduleOverlapInMatch
+        );
+        // the other processor is not affected by the conflict
+        assert_ok!(AcurastMarketplace::approve_schedule_change(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            vec![processor_2_account_id()],
+            AcurastMarketplace::pending_schedule_change(&job_id)
+                .unwrap()
+                .approvals
+                .into_inner()
+        );
+    });
+}
+
+/// Finalizes an assigned two slot job after its sources reported the given number of executions each.
+///
+/// Only the first source finalizes its slot, the second one is left for the job's finalization.
+/// Returns the job's budget left before finalizing the job.
+fn finalize_assigned_two_slot_job(reports: [u64; 2]) -> (JobId<AccountId>, Balance) {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let job_id = setup_assigned_two_slot_job(now);
+    let schedule = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+        .unwrap()
+        .schedule;
+
+    for execution in 0..schedule.execution_count() {
+        later(schedule.start_time + execution * schedule.interval + 3000);
+        for (processor, count) in [processor_account_id(), processor_2_account_id()]
+            .into_iter()
+            .zip(reports)
+        {
+            if execution < count {
+                assert_ok!(AcurastMarketplace::report(
+                    RuntimeOrigin::signed(processor).into(),
+                    job_id.clone(),
+                    ExecutionResult::Success(operation_hash())
+                ));
+            }
+        }
+    }
+
+    later(schedule.end_time + 60_000);
+    assert_ok!(AcurastMarketplace::finalize_job(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        job_id.clone(),
+    ));
+    assert_eq!(
+        Some(SLA {
+            total: 2,
+            met: reports[0]
+        }),
+        AcurastMarketplace::finalized_slots_sla(&job_id)
+    );
+
+    let reserved = AcurastMarketplace::reserved(&job_id);
+    let _ = events();
+    FinalizedJobs::set(vec![]);
+    assert_ok!(AcurastMarketplace::finalize_jobs(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        vec![job_id.1].try_into().unwrap(),
+    ));
+    assert_eq!(None, AcurastMarketplace::finalized_slots_sla(&job_id));
+    (job_id, reserved)
+}
+
+#[test]
+fn test_job_failed_when_all_executions_missed() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, reserved) = finalize_assigned_two_slot_job([0, 0]);
+
+        let events = events();
+        let failed = RuntimeEvent::AcurastMarketplace(crate::Event::JobFailed(job_id.clone()));
+        let finalized =
+            RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id.clone()));
+        let failed_at = events.iter().position(|e| e == &failed).unwrap();
+        assert_eq!(Some(&finalized), events.get(failed_at + 1));
+
+        // no source got paid, so the consumer gets back the budget left after paying the matcher
+        assert_eq!(vec![(job_id.clone(), reserved, true)], FinalizedJobs::get());
+        assert_eq!(0, AcurastMarketplace::reserved(&job_id));
+    });
+}
+
+#[test]
+fn test_job_not_failed_when_some_executions_met() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, reserved) = finalize_assigned_two_slot_job([0, 1]);
+
+        let events = events();
+        assert!(
+            !events.contains(&RuntimeEvent::AcurastMarketplace(crate::Event::JobFailed(
+                job_id.clone()
+            )))
+        );
+        assert!(events.contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::JobFinalized(job_id.clone())
+        )));
+        assert_eq!(vec![(job_id, reserved, false)], FinalizedJobs::get());
+    });
+}
+
+#[test]
+fn test_job_not_failed_when_all_executions_met() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, reserved) = finalize_assigned_two_slot_job([2, 2]);
+
+        let events = events();
+        assert!(
+            !events.contains(&RuntimeEvent::AcurastMarketplace(crate::Event::JobFailed(
+                job_id.clone()
+            )))
+        );
+        assert!(events.contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::JobFinalized(job_id.clone())
+        )));
+        assert_eq!(vec![(job_id, reserved, false)], FinalizedJobs::get());
+    });
+}
+
+#[test]
+fn test_sla_recorded_for_slot_not_finalized_by_source() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (_, _) = finalize_assigned_two_slot_job([2, 1]);
+
+        // the first source finalized its slot, the second one got recorded on the job's finalization
+        for (source, met) in [(processor_account_id(), 2), (processor_2_account_id(), 1)] {
+            let history = AcurastMarketplace::sla_history(source);
+            assert_eq!(
+                vec![(met, 2)],
+                history
+                    .iter()
+                    .map(|record| (record.met, record.total))
+                    .collect::<Vec<_>>()
+            );
+        }
+    });
+}
+
+#[test]
+fn test_sla_recorded_on_deregistration_of_assigned_job() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_assigned_two_slot_job(now);
+        let schedule = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+            .unwrap()
+            .schedule;
+
+        later(schedule.start_time + 3000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+        assert_ok!(Acurast::deregister(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1,
+        ));
+
+        // only the first execution started before the deregistration counts towards the total
+        for (source, met) in [(processor_account_id(), 1), (processor_2_account_id(), 0)] {
+            let history = AcurastMarketplace::sla_history(source);
+            assert_eq!(
+                vec![(met, 1)],
+                history
+                    .iter()
+                    .map(|record| (record.met, record.total))
+                    .collect::<Vec<_>>()
+            );
+        }
+    });
+}
+
+/// Registers a single slot job of alice with a one hour schedule, see [`setup_renewable_registration`].
+fn setup_renewable_job(now: u64, auto_renew: RenewalPolicy) -> JobId<AccountId> {
+    setup_renewable_registration(
+        now,
+        dependent_registration(1_671_800_400_000, None),
+        auto_renew,
+    )
+}
+
+/// Registers `registration` as a single slot job of alice renewing itself according to `auto_renew`, matched instantly
+/// with the processor.
+fn setup_renewable_registration(
+    now: u64,
+    mut registration: JobRegistrationFor<Test>,
+    auto_renew: RenewalPolicy,
+) -> JobId<AccountId> {
+    registration.extra.auto_renew = Some(auto_renew);
+    registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+        source: processor_account_id(),
+        start_delay: 0,
+        pricing_version: None,
+    }]);
+
+    later(now);
+    let _ = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        MultiAddress::Id(alice_account_id()),
+        100_000_000,
+    );
+    assert_ok!(AcurastMarketplace::advertise(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        advertisement(1000, 1, 100_000, 50_000, 8),
+    ));
+    let job_id = (
+        MultiOrigin::Acurast(alice_account_id()),
+        Acurast::job_id_sequence() + 1,
+    );
+    assert_ok!(Acurast::register(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        registration,
+    ));
+    job_id
+}
+
+/// Runs a single slot job through one cycle and returns the events of its finalization by the consumer.
+///
+/// The job gets matched with the processor unless already matched, which reports `reports` executions and finalizes
+/// its slot before the job is finalized.
+fn run_renewable_job(job_id: &JobId<AccountId>, reports: usize) -> Vec<RuntimeEvent> {
+    if AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1) == Some(JobStatus::Open) {
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(job_id, processor_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+    }
+    assert_ok!(AcurastMarketplace::acknowledge_match(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        job_id.clone(),
+        PubKeys::default(),
+    ));
+
+    let schedule = Acurast::stored_job_registration(&job_id.0, &job_id.1)
+        .unwrap()
+        .schedule;
+    for start in schedule.iter(0).unwrap().take(reports) {
+        later(start + 1000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+    }
+    later(schedule.range(5000).unwrap().1 + 1000);
+    assert_ok!(AcurastMarketplace::finalize_job(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        job_id.clone(),
+    ));
+
+    let _ = events();
+    assert_ok!(AcurastMarketplace::finalize_jobs(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        vec![job_id.1].try_into().unwrap(),
+    ));
+    events()
+}
+
+#[test]
+fn test_job_renewed_over_two_cycles() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let policy = RenewalPolicy {
+        max_renewals: 2,
+        reward_escalation: Permill::from_percent(10),
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_renewable_job(now, policy);
+        let registration = Acurast::stored_job_registration(&job_id.0, &job_id.1).unwrap();
+
+        // first cycle renews the job with the schedule shifted by its span of one hour
+        let events = run_renewable_job(&job_id, 2);
+        let renewed_job_id = (job_id.0.clone(), job_id.1 + 1);
+        let finalized =
+            RuntimeEvent::AcurastMarketplace(crate::Event::JobFinalized(job_id.clone()));
+        let renewed = RuntimeEvent::AcurastMarketplace(crate::Event::JobRenewed(
+            job_id.clone(),
+            renewed_job_id.clone(),
+            1,
+        ));
+        let finalized_at = events.iter().position(|e| e == &finalized).unwrap();
+        let renewed_at = events.iter().position(|e| e == &renewed).unwrap();
+        assert!(finalized_at < renewed_at);
+        assert_eq!(None, Acurast::stored_job_registration(&job_id.0, &job_id.1));
+
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&renewed_job_id.0, &renewed_job_id.1)
+        );
+        let renewal =
+            Acurast::stored_job_registration(&renewed_job_id.0, &renewed_job_id.1).unwrap();
+        assert_eq!(
+            registration.schedule.start_time + 3_600_000,
+            renewal.schedule.start_time
+        );
+        assert_eq!(
+            registration.schedule.end_time + 3_600_000,
+            renewal.schedule.end_time
+        );
+        assert_eq!(6_600_000, renewal.extra.reward);
+        assert_eq!(None, renewal.extra.instant_match);
+        assert_eq!(
+            Some(RenewalPolicy {
+                max_renewals: 1,
+                ..policy
+            }),
+            renewal.extra.auto_renew
+        );
+        // the escalated budget of both executions got locked
+        assert_eq!(13_200_000, AcurastMarketplace::job_budgets(&renewed_job_id));
+
+        // second cycle renews the renewal with the last renewal left
+        let events = run_renewable_job(&renewed_job_id, 2);
+        let last_job_id = (job_id.0.clone(), renewed_job_id.1 + 1);
+        assert!(
+            events.contains(&RuntimeEvent::AcurastMarketplace(crate::Event::JobRenewed(
+                renewed_job_id.clone(),
+                last_job_id.clone(),
+                0,
+            )))
+        );
+        let last = Acurast::stored_job_registration(&last_job_id.0, &last_job_id.1).unwrap();
+        assert_eq!(
+            registration.schedule.start_time + 2 * 3_600_000,
+            last.schedule.start_time
+        );
+        assert_eq!(7_260_000, last.extra.reward);
+
+        // no renewals left
+        let events = run_renewable_job(&last_job_id, 2);
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            RuntimeEvent::AcurastMarketplace(
+                crate::Event::JobRenewed(..) | crate::Event::JobRenewalFailed(..)
+            )
+        )));
+        assert_eq!(last_job_id.1, Acurast::job_id_sequence());
+    });
+}
+
+#[test]
+fn test_job_renewal_skips_elapsed_spans() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        // the last execution ends exactly at `end_time`, so the job is finalized after the start shifted by one span
+        let mut registration = dependent_registration(1_671_800_400_000, None);
+        registration.schedule.end_time = registration.schedule.start_time + 1_800_000 + 5000;
+        let span = registration.schedule.end_time - registration.schedule.start_time;
+        let job_id = setup_renewable_registration(
+            now,
+            registration.clone(),
+            RenewalPolicy {
+                max_renewals: 1,
+                reward_escalation: Permill::zero(),
+            },
+        );
+
+        let events = run_renewable_job(&job_id, 2);
+        let renewed_job_id = (job_id.0.clone(), job_id.1 + 1);
+        assert!(
+            events.contains(&RuntimeEvent::AcurastMarketplace(crate::Event::JobRenewed(
+                job_id.clone(),
+                renewed_job_id.clone(),
+                0,
+            )))
+        );
+        let renewal =
+            Acurast::stored_job_registration(&renewed_job_id.0, &renewed_job_id.1).unwrap();
+        assert_eq!(
+            registration.schedule.start_time + 2 * span,
+            renewal.schedule.start_time
+        );
+        assert_eq!(
+            registration.schedule.end_time + 2 * span,
+            renewal.schedule.end_time
+        );
+        assert!(renewal.schedule.start_time > AcurastMarketplace::now().unwrap());
+    });
+}
+
+#[test]
+fn test_job_renewal_refused_above_approval_threshold() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let policy = RenewalPolicy {
+        max_renewals: 2,
+        reward_escalation: Permill::from_percent(10),
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        // the initial budget of 12_000_000 is within the threshold, the escalated one of 13_200_000 is not
+        assert_ok!(AcurastMarketplace::set_registration_approval(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            12_000_000,
+            bob_account_id(),
+        ));
+        let job_id = setup_renewable_job(now, policy);
+        let job_id_sequence = Acurast::job_id_sequence();
+
+        let events = run_renewable_job(&job_id, 2);
+        assert!(events.contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::JobFinalized(job_id.clone())
+        )));
+        assert!(events.contains(&RuntimeEvent::AcurastMarketplace(
+            crate::Event::JobRenewalFailed(job_id.clone())
+        )));
+
+        // the failed renewal left no trace, not even a consumed job id
+        assert_eq!(job_id_sequence, Acurast::job_id_sequence());
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, job_id_sequence + 1)
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_failed_job_not_renewed() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_renewable_job(
+            now,
+            RenewalPolicy {
+                max_renewals: 2,
+                reward_escalation: Permill::zero(),
+            },
+        );
+        let job_id_sequence = Acurast::job_id_sequence();
+
+        let events = run_renewable_job(&job_id, 0);
+        assert!(
+            events.contains(&RuntimeEvent::AcurastMarketplace(crate::Event::JobFailed(
+                job_id.clone()
+            )))
+        );
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            RuntimeEvent::AcurastMarketplace(
+                crate::Event::JobRenewed(..) | crate::Event::JobRenewalFailed(..)
+            )
+        )));
+        assert_eq!(job_id_sequence, Acurast::job_id_sequence());
+    });
+}
+
+#[test]
+fn test_renewable_job_with_empty_schedule_rejected() {
+    ExtBuilder::default().build().execute_with(|| {
+        let mut registration = dependent_registration(1_671_800_400_000, None);
+        registration.schedule.end_time = registration.schedule.start_time;
+        registration.extra.auto_renew = Some(RenewalPolicy {
+            max_renewals: 2,
+            reward_escalation: Permill::zero(),
+        });
+
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        assert_err!(
+            Acurast::register_for(job_id, registration),
+            Error::<Test>::JobRegistrationScheduleContainsZeroExecutions
+        );
+    });
+}
+
+#[test]
+fn test_job_renewal_rejected_for_non_acurast_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        let mut registration = dependent_registration(1_671_800_400_000, None);
+        registration.extra.auto_renew = Some(RenewalPolicy {
+            max_renewals: 2,
+            reward_escalation: Permill::zero(),
+        });
+        let _ = Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            MultiAddress::Id(pallet_hyperdrive_account()),
+            100_000_000,
+        );
+
+        let job_id = (MultiOrigin::Tezos(bounded_vec![1, 2, 3]), 1);
+        assert_err!(
+            Acurast::register_for(job_id.clone(), registration),
+            Error::<Test>::RenewalUnsupportedOrigin
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_propose_matching_with_beneficiary_pays_beneficiary() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let matcher_balance = Balances::free_balance(&charlie_account_id());
+        assert_eq!(0, Balances::free_balance(&dave_account_id()));
+
+        later(now + 600_000);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::propose_matching_with_beneficiary(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+            dave_account_id(),
+        ));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+
+        // the matcher reward is paid to the beneficiary only
+        let rewards = events()
+            .into_iter()
+            .filter_map(|event| match event {
+                RuntimeEvent::Balances(pallet_balances::Event::Transfer { from, to, amount })
+                    if from == pallet_acurast_acount()
+                        && (to == dave_account_id() || to == charlie_account_id()) =>
+                {
+                    Some((to, amount))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(1, rewards.len());
+        let (to, amount) = rewards[0].clone();
+        assert_eq!(dave_account_id(), to);
+        assert!(amount > 0);
+        assert_eq!(amount, Balances::free_balance(&dave_account_id()));
+        assert_eq!(
+            matcher_balance,
+            Balances::free_balance(&charlie_account_id())
+        );
+
+        // the proposal is attributed to the signer
+        assert_eq!(
+            MatcherPerformance {
+                proposals: 1,
+                accepted_matches: 1,
+                rejected_entries: 0,
+            },
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+        assert_eq!(
+            MatcherPerformance::default(),
+            AcurastMarketplace::matcher_stats(dave_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_propose_matching_without_beneficiary_pays_signer() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let matcher_balance = Balances::free_balance(&charlie_account_id());
+
+        later(now + 600_000);
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert!(Balances::free_balance(&charlie_account_id()) > matcher_balance);
+        assert_eq!(0, Balances::free_balance(&dave_account_id()));
+    });
+}
+
+#[test]
+fn test_propose_matching_with_invalid_beneficiary() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+
+        later(now + 600_000);
+        // the zero account, which is alice's in this mock, and the pallet accounts are rejected
+        for beneficiary in [
+            AccountId::new([0u8; 32]),
+            pallet_acurast_acount(),
+            pallet_hyperdrive_account(),
+        ] {
+            assert_err!(
+                AcurastMarketplace::propose_matching_with_beneficiary(
+                    RuntimeOrigin::signed(charlie_account_id()).into(),
+                    vec![single_source_match(&job_id, processor_2_account_id())]
+                        .try_into()
+                        .unwrap(),
+                    beneficiary,
+                ),
+                Error::<Test>::InvalidMatcherBeneficiary
+            );
+        }
+        assert_eq!(
+            Some(JobStatus::Open),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert_eq!(
+            MatcherPerformance::default(),
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_propose_matching_lenient_with_beneficiary_pays_beneficiary() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let matcher_balance = Balances::free_balance(&charlie_account_id());
+
+        later(now + 600_000);
+        assert_err!(
+            AcurastMarketplace::propose_matching_lenient_with_beneficiary(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, processor_2_account_id())]
+                    .try_into()
+                    .unwrap(),
+                pallet_acurast_acount(),
+            ),
+            Error::<Test>::InvalidMatcherBeneficiary
+        );
+
+        assert_ok!(
+            AcurastMarketplace::propose_matching_lenient_with_beneficiary(
+                RuntimeOrigin::signed(charlie_account_id()).into(),
+                vec![single_source_match(&job_id, processor_2_account_id())]
+                    .try_into()
+                    .unwrap(),
+                dave_account_id(),
+            )
+        );
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert!(Balances::free_balance(&dave_account_id()) > 0);
+        assert_eq!(
+            matcher_balance,
+            Balances::free_balance(&charlie_account_id())
+        );
+        assert_eq!(
+            MatcherPerformance {
+                proposals: 1,
+                accepted_matches: 1,
+                rejected_entries: 0,
+            },
+            AcurastMarketplace::matcher_stats(charlie_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_filter_matching_sources_rejects_empty_allowed_sources() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let partial_registration = |allowed_sources| crate::PartialJobRegistration {
+        allowed_sources,
+        allow_only_verified_sources: false,
+        schedule: None,
+        memory: None,
+        network_requests: None,
+        storage: None,
+        required_modules: JobModules::default(),
+        slots: None,
+        reward: 0,
+        min_reputation: None,
+        min_recent_fulfillment_ratio: None,
+        max_attestation_age_ms: None,
+        max_fee_per_execution: None,
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+
+        // an empty list is ambiguous with an open job and is rejected like on registration
+        assert_eq!(
+            Err(RuntimeApiError::FilterMatchingSources),
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration(Some(bounded_vec![])),
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+        );
+        assert_eq!(
+            vec![(processor_account_id(), 1)],
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration(Some(bounded_vec![processor_account_id()])),
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            vec![(processor_account_id(), 1)],
+            AcurastMarketplace::filter_matching_sources(
+                partial_registration(None),
+                vec![processor_account_id()],
+                None,
+                None,
+            )
+            .unwrap()
+        );
+    });
+}
+
+/// Registers a job instantly matched with the processor, acknowledges the match and returns the job's ID with its schedule.
+fn setup_assigned_env_job(now: u64, require_env_ack: bool) -> (JobId<AccountId>, Schedule) {
+    let mut registration = dependent_registration(1_671_800_400_000, None);
+    registration.extra.require_env_ack = require_env_ack;
+    registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+        source: processor_account_id(),
+        start_delay: 0,
+        pricing_version: None,
+    }]);
+    let schedule = registration.schedule.clone();
+
+    later(now);
+    let _ = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        MultiAddress::Id(alice_account_id()),
+        100_000_000,
+    );
+    assert_ok!(AcurastMarketplace::advertise(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        advertisement(1000, 1, 100_000, 50_000, 8),
+    ));
+    let job_id = (
+        MultiOrigin::Acurast(alice_account_id()),
+        Acurast::job_id_sequence() + 1,
+    );
+    assert_ok!(Acurast::register(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        registration,
+    ));
+    assert_ok!(AcurastMarketplace::acknowledge_match(
+        RuntimeOrigin::signed(processor_account_id()).into(),
+        job_id.clone(),
+        PubKeys::default(),
+    ));
+    (job_id, schedule)
+}
+
+fn set_processor_environment(job_id: &JobId<AccountId>, value: &[u8]) {
+    assert_ok!(Acurast::set_environment(
+        RuntimeOrigin::signed(alice_account_id()).into(),
+        job_id.1,
+        processor_account_id(),
+        pallet_acurast::Environment {
+            public_key: bounded_vec![0u8; 33],
+            variables: bounded_vec![(bounded_vec![1u8], value.to_vec().try_into().unwrap())],
+        },
+    ));
+}
+
+#[test]
+fn test_report_requires_environment_ack() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, schedule) = setup_assigned_env_job(now, true);
+
+        later(schedule.start_time + 3000);
+        assert_err!(
+            AcurastMarketplace::report(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+                ExecutionResult::Success(operation_hash())
+            ),
+            Error::<Test>::EnvironmentNotAcknowledged
+        );
+
+        // the environment has to be set before it can be acknowledged
+        assert_err!(
+            AcurastMarketplace::acknowledge_environment(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::EnvironmentNotFound
+        );
+        set_processor_environment(&job_id, b"secret");
+
+        let _ = events();
+        assert_ok!(AcurastMarketplace::acknowledge_environment(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::EnvironmentAcknowledged(job_id.clone(), processor_account_id(), 0)
+            )]
+        );
+        assert_eq!(
+            Some(0),
+            AcurastMarketplace::stored_matches(processor_account_id(), job_id.clone())
+                .unwrap()
+                .environment_ack
+        );
+
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+    });
+}
+
+#[test]
+fn test_report_without_environment_ack_requirement() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, schedule) = setup_assigned_env_job(now, false);
+        set_processor_environment(&job_id, b"secret");
+
+        later(schedule.start_time + 3000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_matches(processor_account_id(), job_id)
+                .unwrap()
+                .environment_ack
+        );
+    });
+}
+
+#[test]
+fn test_environment_reack_required_on_version_bump() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, schedule) = setup_assigned_env_job(now, true);
+        set_processor_environment(&job_id, b"secret");
+        assert_ok!(AcurastMarketplace::acknowledge_environment(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+
+        // updating the environment without bumping the version keeps the acknowledgement valid
+        set_processor_environment(&job_id, b"updated");
+        later(schedule.start_time + 3000);
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+
+        assert_err!(
+            AcurastMarketplace::bump_environment_version(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                job_id.clone(),
+            ),
+            Error::<Test>::NotJobConsumer
+        );
+        let _ = events();
+        assert_ok!(AcurastMarketplace::bump_environment_version(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::EnvironmentVersionBumped(job_id.clone(), 1)
+            )]
+        );
+
+        later(schedule.start_time + schedule.interval + 3000);
+        assert_err!(
+            AcurastMarketplace::report(
+                RuntimeOrigin::signed(processor_account_id()).into(),
+                job_id.clone(),
+                ExecutionResult::Success(operation_hash())
+            ),
+            Error::<Test>::EnvironmentNotAcknowledged
+        );
+        assert_ok!(AcurastMarketplace::acknowledge_environment(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert_eq!(
+            Some(1),
+            AcurastMarketplace::stored_matches(processor_account_id(), job_id.clone())
+                .unwrap()
+                .environment_ack
+        );
+        assert_ok!(AcurastMarketplace::report(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            ExecutionResult::Success(operation_hash())
+        ));
+    });
+}
+
+/// Marks `pallet_acurast` as migrated, which the marketplace migration expects to run first.
+fn put_acurast_storage_version() {
+    Acurast::current_storage_version().put::<Acurast>();
+}
+
+#[test]
+fn test_assignment_migration() {
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        let assignment = crate::migration::v4::Assignment::<Balance> {
+            slot: 1,
+            start_delay: 0,
+            fee_per_execution: 1_000,
+            acknowledged: true,
+            sla: SLA { total: 2, met: 1 },
+            pub_keys: PubKeys::default(),
+        };
+        put_acurast_storage_version();
+        StorageVersion::new(4).put::<AcurastMarketplace>();
+        crate::migration::v4::StoredMatches::<Test>::insert(
+            processor_account_id(),
+            &job_id,
+            assignment,
+        );
+
+        AcurastMarketplace::on_runtime_upgrade();
+
+        assert_eq!(
+            StorageVersion::new(20),
+            AcurastMarketplace::on_chain_storage_version()
+        );
+        assert_eq!(
+            Some(Assignment {
+                slot: 1,
+                start_delay: 0,
+                fee_per_execution: 1_000,
+                acknowledged: true,
+                sla: SLA { total: 2, met: 1 },
+                pub_keys: PubKeys::default(),
+                holdback: 0,
+                environment_ack: None,
+            }),
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+        );
+        assert_eq!(
+            vec![1],
+            crate::AcknowledgedSlots::<Test>::get(&job_id).into_inner()
+        );
+    });
+}
+
+/// Stores the requirements of `job_id`'s registration in the layout of storage version 4.
+fn put_v4_requirements(job_id: &JobId<AccountId>) {
+    let core = pallet_acurast::StoredJobRegistration::<Test>::get(&job_id.0, job_id.1).unwrap();
+    let requirements: JobRequirementsFor<Test> = core.extra;
+    frame_support::storage::unhashed::put(
+        &pallet_acurast::StoredJobRegistration::<Test>::hashed_key_for(&job_id.0, job_id.1),
+        &JobRegistrationCore {
+            allow_only_verified_sources: core.allow_only_verified_sources,
+            schedule: core.schedule,
+            memory: core.memory,
+            network_requests: core.network_requests,
+            storage: core.storage,
+            required_modules: core.required_modules,
+            extra: crate::migration::v4::JobRequirements::<
+                Balance,
+                AccountId,
+                <Test as pallet_acurast::Config>::MaxSlots,
+            > {
+                slots: requirements.slots,
+                reward: requirements.reward,
+                min_reputation: requirements.min_reputation,
+                instant_match: requirements.instant_match.map(|instant_match| {
+                    frame_support::BoundedVec::truncate_from(
+                        instant_match
+                            .into_iter()
+                            .map(|planned_execution| crate::migration::v4::PlannedExecution {
+                                source: planned_execution.source,
+                                start_delay: planned_execution.start_delay,
+                            })
+                            .collect(),
+                    )
+                }),
+            },
+        },
+    );
+}
+
+#[test]
+fn test_job_requirements_migrations() {
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        let mut registration = dependent_registration(1_671_800_400_000, None);
+        registration.extra.min_reputation = Some(500_000);
+        registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+            source: processor_account_id(),
+            start_delay: 10,
+            pricing_version: None,
+        }]);
+        Acurast::store_job_registration(&job_id, registration.clone());
+        put_v4_requirements(&job_id);
+        put_acurast_storage_version();
+        StorageVersion::new(4).put::<AcurastMarketplace>();
+
+        AcurastMarketplace::on_runtime_upgrade();
+
+        assert_eq!(
+            StorageVersion::new(20),
+            AcurastMarketplace::on_chain_storage_version()
+        );
+        assert_eq!(
+            Some(registration),
+            Acurast::stored_job_registration(&job_id.0, job_id.1)
+        );
+    });
+}
+
+#[test]
+fn test_job_requirements_migration_leaves_undecodable_registrations() {
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        let registration = dependent_registration(1_671_800_400_000, None);
+        Acurast::store_job_registration(&job_id, registration.clone());
+        // already in the current layout, so it does not decode as the old one
+        let key =
+            pallet_acurast::StoredJobRegistration::<Test>::hashed_key_for(&job_id.0, job_id.1);
+        let raw = frame_support::storage::unhashed::get_raw(&key).unwrap();
+        put_acurast_storage_version();
+        StorageVersion::new(4).put::<AcurastMarketplace>();
+
+        AcurastMarketplace::on_runtime_upgrade();
+
+        assert_eq!(
+            StorageVersion::new(20),
+            AcurastMarketplace::on_chain_storage_version()
+        );
+        assert_eq!(Some(raw), frame_support::storage::unhashed::get_raw(&key));
+    });
+}
+
+#[test]
+fn test_migration_waits_for_acurast_migration() {
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (MultiOrigin::Acurast(alice_account_id()), 1);
+        Acurast::store_job_registration(&job_id, dependent_registration(1_671_800_400_000, None));
+        put_v4_requirements(&job_id);
+        StorageVersion::new(4).put::<Acurast>();
+        StorageVersion::new(4).put::<AcurastMarketplace>();
+
+        AcurastMarketplace::on_runtime_upgrade();
+
+        assert_eq!(
+            StorageVersion::new(4),
+            AcurastMarketplace::on_chain_storage_version()
+        );
+    });
+}
+
+#[test]
+fn test_matchable_jobs_margin_matches_actual_match() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            advertisement(500, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            dependent_registration(1_671_800_400_000, None),
+        ));
+
+        // the margin is estimated with the cheaper processor: 2 * 6_000_000 - 2 * (500 * 5000 + 20_000)
+        assert_eq!(
+            vec![MatchableJob {
+                job_id: job_id.clone(),
+                start_time: 1_671_800_400_000,
+                reward: 6_000_000,
+                slots: 1,
+                margin_estimate: Some(6_960_000),
+            }],
+            AcurastMarketplace::matchable_jobs(10).unwrap()
+        );
+
+        assert_ok!(AcurastMarketplace::propose_matching(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_2_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+
+        let assignment =
+            AcurastMarketplace::stored_matches(processor_2_account_id(), &job_id).unwrap();
+        let remaining_reward =
+            2 * 6_000_000 - assignment.fee_per_execution * assignment.sla.total as u128;
+        assert_eq!(6_960_000, remaining_reward);
+        assert_eq!(
+            Some(MockRewardManager::matcher_reward(remaining_reward)),
+            AcurastMarketplace::matcher_rewards_paid(&job_id)
+        );
+
+        // matched jobs are no longer listed
+        assert_eq!(
+            Vec::<MatchableJob<Balance, AccountId>>::new(),
+            AcurastMarketplace::matchable_jobs(10).unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_matchable_jobs_bounds() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        for _ in 0..2 {
+            assert_ok!(Acurast::register(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                dependent_registration(1_671_800_400_000, None),
+            ));
+        }
+
+        assert_eq!(2, AcurastMarketplace::matchable_jobs(10).unwrap().len());
+        assert_eq!(1, AcurastMarketplace::matchable_jobs(1).unwrap().len());
+        assert!(AcurastMarketplace::matchable_jobs(0).unwrap().is_empty());
+        assert!(AcurastMarketplace::matchable_jobs(10)
+            .unwrap()
+            .iter()
+            .all(|job| job.margin_estimate == Some(2 * 6_000_000 - 2 * 5_020_000)));
+
+        // jobs failing to estimate their margin are skipped instead of failing the call
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence(),
+        );
+        let payload = pallet_acurast::StoredJobPayload::<Test>::take(&job_id.0, &job_id.1);
+        let jobs = AcurastMarketplace::matchable_jobs(10).unwrap();
+        assert_eq!(1, jobs.len());
+        assert_ne!(job_id, jobs[0].job_id);
+        pallet_acurast::StoredJobPayload::<Test>::set(&job_id.0, &job_id.1, payload);
+        assert_eq!(2, AcurastMarketplace::matchable_jobs(10).unwrap().len());
+
+        // no source sampled
+        MarginEstimateSampleSize::set(0);
+        assert!(AcurastMarketplace::matchable_jobs(10)
+            .unwrap()
+            .iter()
+            .all(|job| job.margin_estimate.is_none()));
+        MarginEstimateSampleSize::set(16);
+
+        // deleted advertisements are no longer sampled
+        assert_ok!(AcurastMarketplace::delete_advertisement(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+        ));
+        assert_eq!(
+            None,
+            AcurastMarketplace::advertisement_index(processor_account_id())
+        );
+        assert!(AcurastMarketplace::matchable_jobs(10)
+            .unwrap()
+            .iter()
+            .all(|job| job.margin_estimate.is_none()));
+
+        // overdue jobs are no longer listed
+        later(1_671_800_400_000);
+        assert!(AcurastMarketplace::matchable_jobs(10).unwrap().is_empty());
+    });
+}
+
+#[test]
+fn test_advertisement_index_migration_to_v9() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        // a source with reputation but without an advertisement is not indexed
+        crate::StoredReputation::<Test>::insert(
+            processor_2_account_id(),
+            BetaParameters::default(),
+        );
+        crate::AdvertisementIndex::<Test>::remove(processor_account_id());
+        put_acurast_storage_version();
+        StorageVersion::new(8).put::<AcurastMarketplace>();
+
+        AcurastMarketplace::on_runtime_upgrade();
+
+        assert_eq!(
+            StorageVersion::new(20),
+            AcurastMarketplace::on_chain_storage_version()
+        );
+        assert_eq!(
+            Some(()),
+            AcurastMarketplace::advertisement_index(processor_account_id())
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::advertisement_index(processor_2_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_advertise_call_encoding_at_allowed_consumers_bound() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+    let max_allowed_consumers = <Test as crate::Config>::MaxAllowedConsumers::get();
+    assert_ne!(
+        max_allowed_consumers,
+        <Test as pallet_acurast::Config>::MaxAllowedSources::get()
+    );
+    let consumers = |count: u32| {
+        (0..count)
+            .map(|i| MultiOrigin::Acurast(AccountId::new([i as u8 + 10; 32])))
+            .collect::<Vec<_>>()
+    };
+
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        let mut ad = advertisement(1000, 1, 100_000, 50_000, 8);
+        ad.allowed_consumers = Some(consumers(max_allowed_consumers).try_into().unwrap());
+
+        // a call forwarded with the maximum number of consumers decodes and is accepted on the receiving side
+        let call = RuntimeCall::AcurastMarketplace(crate::Call::advertise {
+            advertisement: ad.clone(),
+        });
+        let decoded = RuntimeCall::decode(&mut call.encode().as_slice()).unwrap();
+        assert_eq!(call, decoded);
+        match decoded {
+            RuntimeCall::AcurastMarketplace(crate::Call::advertise { advertisement }) => {
+                assert_ok!(AcurastMarketplace::advertise(
+                    RuntimeOrigin::signed(processor_account_id()).into(),
+                    advertisement,
+                ));
+            }
+            _ => panic!("unexpected call {:?}", decoded),
+        }
+
+        // one consumer more than the bound is not decodable
+        let exceeding = (
+            ad.pricing.clone(),
+            ad.max_memory,
+            ad.network_request_quota,
+            ad.storage_capacity,
+            Some(consumers(max_allowed_consumers + 1)),
+            ad.available_modules.clone(),
+        )
+            .encode();
+        assert!(AdvertisementFor::<Test>::decode(&mut exceeding.as_slice()).is_err());
+    });
+}
+
+#[test]
+fn test_source_job_index_in_sync_through_lifecycle() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        for processor in [processor_account_id(), processor_2_account_id()] {
+            assert_eq!(
+                vec![job_id.clone()],
+                AcurastMarketplace::source_job_index(&processor).into_inner()
+            );
+        }
+        assert_ok!(AcurastMarketplace::do_try_state());
+
+        for processor in [processor_account_id(), processor_2_account_id()] {
+            assert_ok!(AcurastMarketplace::acknowledge_match(
+                RuntimeOrigin::signed(processor).into(),
+                job_id.clone(),
+                PubKeys::default(),
+            ));
+        }
+        assert_ok!(AcurastMarketplace::do_try_state());
+
+        later(1_671_804_000_000 + 10_000);
+        assert_ok!(AcurastMarketplace::finalize_job(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+        ));
+        assert!(!crate::SourceJobIndex::<Test>::contains_key(
+            processor_account_id()
+        ));
+        assert_eq!(
+            vec![job_id.clone()],
+            AcurastMarketplace::source_job_index(processor_2_account_id()).into_inner()
+        );
+        assert_ok!(AcurastMarketplace::do_try_state());
+
+        assert_ok!(AcurastMarketplace::finalize_jobs(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            vec![job_id.1].try_into().unwrap(),
+        ));
+        assert!(!crate::SourceJobIndex::<Test>::contains_key(
+            processor_2_account_id()
+        ));
+        assert_ok!(AcurastMarketplace::do_try_state());
+    });
+}
+
+#[test]
+fn test_source_job_index_cleared_on_deregister() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_two_slot_job(now);
+        assert_ok!(AcurastMarketplace::acknowledge_match(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            job_id.clone(),
+            PubKeys::default(),
+        ));
+
+        assert_ok!(Acurast::deregister(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            job_id.1
+        ));
+        for processor in [processor_account_id(), processor_2_account_id()] {
+            assert!(!crate::SourceJobIndex::<Test>::contains_key(processor));
+        }
+        assert_ok!(AcurastMarketplace::do_try_state());
+    });
+}
+
+#[test]
+fn test_source_job_index_keeps_match_order() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        later(now);
+        let _ = Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            MultiAddress::Id(alice_account_id()),
+            100_000_000,
+        );
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        // staggered by 10 minutes so that the schedules do not overlap
+        let job_ids: Vec<JobId<AccountId>> = (0..3)
+            .map(|i| {
+                let mut registration =
+                    dependent_registration(1_671_800_400_000 + i * 600_000, None);
+                registration.extra.instant_match = Some(bounded_vec![PlannedExecution {
+                    source: processor_account_id(),
+                    start_delay: 0,
+                    pricing_version: None,
+                }]);
+                let job_id = (
+                    MultiOrigin::Acurast(alice_account_id()),
+                    Acurast::job_id_sequence() + 1,
+                );
+                assert_ok!(Acurast::register(
+                    RuntimeOrigin::signed(alice_account_id()).into(),
+                    registration,
+                ));
+                job_id
+            })
+            .collect();
+
+        assert_eq!(
+            job_ids,
+            AcurastMarketplace::source_job_index(processor_account_id()).into_inner()
+        );
+        assert_eq!(
+            Ok(job_ids.clone()),
+            AcurastMarketplace::stored_matches_for_source(processor_account_id()).map(
+                |assignments| assignments
+                    .into_iter()
+                    .map(|a| a.job_id)
+                    .collect::<Vec<_>>()
+            )
+        );
+
+        // removing a match keeps the order of the remaining ones
+        expire_match(&job_ids[1], &processor_account_id(), 20_000);
+        assert_eq!(
+            vec![job_ids[0].clone(), job_ids[2].clone()],
+            AcurastMarketplace::source_job_index(processor_account_id()).into_inner()
+        );
+        assert_ok!(AcurastMarketplace::do_try_state());
+    });
+}
+
+#[test]
+fn test_match_rejected_when_source_job_index_full() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let job_id = setup_preferred_job(now);
+        let max_assigned_jobs = <Test as crate::Config>::MaxAssignedJobsPerSource::get();
+        let full: Vec<JobId<AccountId>> = (0..max_assigned_jobs)
+            .map(|i| (MultiOrigin::Acurast(bob_account_id()), i as u128))
+            .collect();
+        crate::SourceJobIndex::<Test>::insert(
+            processor_account_id(),
+            frame_support::BoundedVec::<_, _>::try_from(full).unwrap(),
+        );
+
+        later(now + 1000);
+        let _ = events();
+        assert_ok!(AcurastMarketplace::propose_matching_lenient(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            vec![single_source_match(&job_id, processor_account_id())]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(
+            events(),
+            [RuntimeEvent::AcurastMarketplace(
+                crate::Event::MatchRejected(
+                    job_id.clone(),
+                    MatchingReasonCode::TooManyAssignedJobs
+                )
+            )]
+        );
+        assert_eq!(
+            None,
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id)
+        );
+    });
+}
+
+#[test]
+fn test_source_job_index_migration_to_v10() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, _) = setup_acknowledged_job(now, None);
+        crate::SourceJobIndex::<Test>::remove(processor_account_id());
+        put_v4_requirements(&job_id);
+        put_acurast_storage_version();
+        StorageVersion::new(9).put::<AcurastMarketplace>();
+
+        let state = crate::migration::pre_upgrade::<Test>().unwrap();
+        AcurastMarketplace::on_runtime_upgrade();
+        assert_ok!(crate::migration::post_upgrade::<Test>(state));
+
+        assert_eq!(
+            StorageVersion::new(20),
+            AcurastMarketplace::on_chain_storage_version()
+        );
+        assert_eq!(
+            vec![job_id],
+            AcurastMarketplace::source_job_index(processor_account_id()).into_inner()
+        );
+        assert_ok!(AcurastMarketplace::do_try_state());
+    });
+}
+
+#[test]
+fn test_source_job_index_migration_rejects_exceeding_matches() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    ExtBuilder::default().build().execute_with(|| {
+        let (job_id, _) = setup_acknowledged_job(now, None);
+        let assignment =
+            AcurastMarketplace::stored_matches(processor_account_id(), &job_id).unwrap();
+        crate::SourceJobIndex::<Test>::remove(processor_account_id());
+        let max_assigned_jobs = <Test as crate::Config>::MaxAssignedJobsPerSource::get();
+        for i in 0..max_assigned_jobs {
+            crate::StoredMatches::<Test>::insert(
+                processor_account_id(),
+                (MultiOrigin::Acurast(bob_account_id()), i as u128),
+                assignment.clone(),
+            );
+        }
+        StorageVersion::new(9).put::<AcurastMarketplace>();
+
+        // the job matched during setup exceeds the bound
+        assert!(crate::migration::pre_upgrade::<Test>().is_err());
+
+        // a match fewer fits the index
+        crate::StoredMatches::<Test>::remove(
+            processor_account_id(),
+            (MultiOrigin::Acurast(bob_account_id()), 0),
+        );
+        assert_ok!(crate::migration::pre_upgrade::<Test>());
+    });
+}
+
+/// Registers the offchain, transaction pool and keystore extensions with an off-chain matcher key.
+fn register_offchain_matcher_ext(ext: &mut sp_io::TestExternalities) -> Arc<RwLock<PoolState>> {
+    let (offchain, _offchain_state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let keystore = MemoryKeystore::new();
+    keystore
+        .sr25519_generate_new(crate::crypto::KEY_TYPE, None)
+        .unwrap();
+    ext.register_extension(OffchainDbExt::new(offchain.clone()));
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    ext.register_extension(KeystoreExt::new(keystore));
+    pool_state
+}
+
+/// Returns the calls of the transactions submitted to the pool.
+fn submitted_calls(pool_state: &Arc<RwLock<PoolState>>) -> Vec<RuntimeCall> {
+    pool_state
+        .read()
+        .transactions
+        .iter()
+        .map(|tx| {
+            let tx = Extrinsic::decode(&mut &tx[..]).unwrap();
+            assert!(tx.signature.is_some());
+            tx.call
+        })
+        .collect()
+}
+
+#[test]
+fn test_offchain_matcher_proposes_cheapest_source() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let mut ext = ExtBuilder::default().build();
+    let pool_state = register_offchain_matcher_ext(&mut ext);
+    ext.execute_with(|| {
+        EnableOffchainMatcher::set(true);
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            advertisement(500, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            dependent_registration(1_671_800_400_000, None),
+        ));
+
+        AcurastMarketplace::offchain_worker(1);
+
+        let expected_call: RuntimeCall = crate::Call::propose_matching {
+            matches: vec![Match {
+                job_id: job_id.clone(),
+                sources: bounded_vec![PlannedExecution {
+                    source: processor_2_account_id(),
+                    start_delay: 0,
+                    pricing_version: Some(
+                        AcurastMarketplace::stored_advertisement_pricing_version(
+                            processor_2_account_id(),
+                        ),
+                    ),
+                }],
+            }]
+            .try_into()
+            .unwrap(),
+        }
+        .into();
+        let calls = submitted_calls(&pool_state);
+        assert_eq!(vec![expected_call], calls);
+
+        // the proposed match is accepted on-chain
+        assert_ok!(calls[0]
+            .clone()
+            .dispatch(RuntimeOrigin::signed(charlie_account_id())));
+        assert_eq!(
+            Some(JobStatus::Matched),
+            AcurastMarketplace::stored_job_status(&job_id.0, &job_id.1)
+        );
+        assert!(AcurastMarketplace::stored_matches(processor_2_account_id(), &job_id).is_some());
+    });
+}
+
+#[test]
+fn test_offchain_matcher_lock_prevents_duplicate_proposals() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let mut ext = ExtBuilder::default().build();
+    let pool_state = register_offchain_matcher_ext(&mut ext);
+    ext.execute_with(|| {
+        EnableOffchainMatcher::set(true);
+        MaxOffchainMatchesPerBlock::set(1);
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        for _ in 0..2 {
+            assert_ok!(Acurast::register(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                dependent_registration(1_671_800_400_000, None),
+            ));
+        }
+
+        // the per-block cap limits the first run to one proposal
+        AcurastMarketplace::offchain_worker(1);
+        assert_eq!(1, submitted_calls(&pool_state).len());
+
+        // the already proposed job stays locked while its transaction is pending
+        System::set_block_number(2);
+        AcurastMarketplace::offchain_worker(2);
+        let calls = submitted_calls(&pool_state);
+        assert_eq!(2, calls.len());
+        assert_ne!(calls[0], calls[1]);
+
+        System::set_block_number(3);
+        AcurastMarketplace::offchain_worker(3);
+        assert_eq!(2, submitted_calls(&pool_state).len());
+    });
+}
+
+#[test]
+fn test_offchain_matcher_resumes_scan() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let mut ext = ExtBuilder::default().build();
+    let pool_state = register_offchain_matcher_ext(&mut ext);
+    ext.execute_with(|| {
+        EnableOffchainMatcher::set(true);
+        MaxOffchainMatchScan::set(1);
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        for _ in 0..2 {
+            assert_ok!(Acurast::register(
+                RuntimeOrigin::signed(alice_account_id()).into(),
+                dependent_registration(1_671_800_400_000, None),
+            ));
+        }
+
+        AcurastMarketplace::offchain_worker(1);
+        assert_eq!(1, submitted_calls(&pool_state).len());
+
+        // the scan continues after the job scanned in the previous block instead of stopping at the locked one
+        System::set_block_number(2);
+        AcurastMarketplace::offchain_worker(2);
+        let calls = submitted_calls(&pool_state);
+        assert_eq!(2, calls.len());
+        assert_ne!(calls[0], calls[1]);
+
+        // all jobs got scanned and the next scan starts over, with both jobs still locked
+        System::set_block_number(3);
+        AcurastMarketplace::offchain_worker(3);
+        System::set_block_number(4);
+        AcurastMarketplace::offchain_worker(4);
+        assert_eq!(2, submitted_calls(&pool_state).len());
+    });
+}
+
+#[test]
+fn test_offchain_matcher_source_scan_cap() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let mut ext = ExtBuilder::default().build();
+    let pool_state = register_offchain_matcher_ext(&mut ext);
+    ext.execute_with(|| {
+        EnableOffchainMatcher::set(true);
+        MaxOffchainSourceScan::set(1);
+        let job_id = (
+            MultiOrigin::Acurast(alice_account_id()),
+            Acurast::job_id_sequence() + 1,
+        );
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_2_account_id()).into(),
+            advertisement(500, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            dependent_registration(1_671_800_400_000, None),
+        ));
+
+        AcurastMarketplace::offchain_worker(1);
+
+        // only the first source of the index is checked, regardless of the cheaper one
+        let source = crate::AdvertisementIndex::<Test>::iter_keys()
+            .next()
+            .unwrap();
+        let expected_call: RuntimeCall = crate::Call::propose_matching {
+            matches: vec![Match {
+                job_id,
+                sources: bounded_vec![PlannedExecution {
+                    source: source.clone(),
+                    start_delay: 0,
+                    pricing_version: Some(
+                        AcurastMarketplace::stored_advertisement_pricing_version(source),
+                    ),
+                }],
+            }]
+            .try_into()
+            .unwrap(),
+        }
+        .into();
+        assert_eq!(vec![expected_call], submitted_calls(&pool_state));
+    });
+}
+
+#[test]
+fn test_offchain_matcher_disabled() {
+    let now = 1_671_789_600_000; // 23.12.2022 10:00;
+
+    let mut ext = ExtBuilder::default().build();
+    let pool_state = register_offchain_matcher_ext(&mut ext);
+    ext.execute_with(|| {
+        assert_ok!(Timestamp::set(RuntimeOrigin::none(), now));
+        assert_ok!(AcurastMarketplace::advertise(
+            RuntimeOrigin::signed(processor_account_id()).into(),
+            advertisement(1000, 1, 100_000, 50_000, 8),
+        ));
+        assert_ok!(Acurast::register(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            dependent_registration(1_671_800_400_000, None),
+        ));
+
+        AcurastMarketplace::offchain_worker(1);
+        assert!(submitted_calls(&pool_state).is_empty());
+    });
 }
@@ -1,4 +1,8 @@
-use frame_support::{pallet_prelude::DispatchError, weights::Weight};
+use core::marker::PhantomData;
+
+use frame_support::{pallet_prelude::DispatchError, traits::Get, weights::Weight};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_acurast::{Attestation, AttestationSecurityLevel, JobModule, JobModules};
 
 /// Trait used to lookup the manager of a given processor account.
 pub trait ManagerProvider<T: frame_system::Config> {
@@ -10,13 +14,100 @@ pub trait ProcessorLastSeenProvider<T: frame_system::Config> {
     fn last_seen(processor: &T::AccountId) -> Option<u128>;
 }
 
+/// Trait used to verify that a source's claim of providing a module is backed by its attestation.
+///
+/// Only modules for which [`ModuleAttestationCheck::requires_attestation`] holds are checked, so the attestation is
+/// not even read when matching jobs that require none of them.
+pub trait ModuleAttestationCheck<T: frame_system::Config> {
+    /// Returns true if claims of providing `module` have to be backed by the source's attestation.
+    fn requires_attestation(module: &JobModule) -> bool;
+    /// Returns true if `attestation` supports the claim of providing `module`.
+    fn is_attested(module: &JobModule, attestation: &Attestation) -> bool;
+}
+
+impl<T: frame_system::Config> ModuleAttestationCheck<T> for () {
+    fn requires_attestation(_module: &JobModule) -> bool {
+        false
+    }
+
+    fn is_attested(_module: &JobModule, _attestation: &Attestation) -> bool {
+        true
+    }
+}
+
+/// Trait used by the off-chain worker to propose matches for open jobs.
+pub trait OffchainMatcher<T: frame_system::Config> {
+    /// Proposes matches for the open jobs at `block_number`.
+    fn offchain_match(block_number: BlockNumberFor<T>);
+}
+
+impl<T: frame_system::Config> OffchainMatcher<T> for () {
+    fn offchain_match(_block_number: BlockNumberFor<T>) {}
+}
+
+/// The key purpose `AGREE_KEY` as defined by the Android KeyMint HAL.
+pub const KEY_PURPOSE_AGREE_KEY: u8 = 6;
+
+/// A [`ModuleAttestationCheck`] requiring the modules in `Modules` to be backed by the attested key.
+///
+/// [`JobModule::DataEncryption`] requires a hardware-backed key (TEE or StrongBox) with hardware-enforced
+/// [`KEY_PURPOSE_AGREE_KEY`] purpose, since the encryption keys are derived by key agreement with it.
+pub struct AttestedKeyModuleCheck<Modules>(PhantomData<Modules>);
+
+impl<T: frame_system::Config, Modules: Get<JobModules>> ModuleAttestationCheck<T>
+    for AttestedKeyModuleCheck<Modules>
+{
+    fn requires_attestation(module: &JobModule) -> bool {
+        Modules::get().contains(module)
+    }
+
+    fn is_attested(module: &JobModule, attestation: &Attestation) -> bool {
+        let key_description = &attestation.key_description;
+        match module {
+            JobModule::DataEncryption => {
+                matches!(
+                    key_description.attestation_security_level,
+                    AttestationSecurityLevel::TrustedEnvironemnt
+                        | AttestationSecurityLevel::StrongBox
+                ) && key_description
+                    .tee_enforced
+                    .purpose
+                    .as_ref()
+                    .map_or(false, |purpose| purpose.contains(&KEY_PURPOSE_AGREE_KEY))
+            }
+        }
+    }
+}
+
 /// Weight functions needed for pallet_acurast_marketplace.
 pub trait WeightInfo {
     fn advertise() -> Weight;
     fn delete_advertisement() -> Weight;
     fn report() -> Weight;
     fn propose_matching(x: u32) -> Weight;
+    fn propose_matching_rejected(x: u32) -> Weight;
     fn acknowledge_match() -> Weight;
     fn finalize_job() -> Weight;
     fn finalize_jobs(x: u32) -> Weight;
+    fn renew_job() -> Weight;
+    fn resolve_dependent() -> Weight;
+    fn claim_pending_payout() -> Weight;
+    fn set_default_requirements() -> Weight;
+    fn set_registration_approval() -> Weight;
+    fn approve_registration() -> Weight;
+    fn reject_registration() -> Weight;
+    fn challenge_delivery() -> Weight;
+    fn resolve_challenge() -> Weight;
+    fn check_scheduling_windows(x: u32) -> Weight;
+    fn expire_unmatched_job() -> Weight;
+    fn repair_job_status(x: u32) -> Weight;
+    fn add_approved_matcher() -> Weight;
+    fn remove_approved_matcher() -> Weight;
+    fn finalize_assigned_jobs(x: u32) -> Weight;
+    fn propose_schedule_change() -> Weight;
+    fn approve_schedule_change(x: u32, y: u32) -> Weight;
+    fn cancel_schedule_change() -> Weight;
+    fn acknowledge_environment() -> Weight;
+    fn bump_environment_version() -> Weight;
+    fn propose_matching_lenient(x: u32) -> Weight;
 }
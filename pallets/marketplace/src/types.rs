@@ -1,4 +1,6 @@
-use frame_support::{pallet_prelude::*, storage::bounded_vec::BoundedVec, PalletError};
+use frame_support::{
+    pallet_prelude::*, sp_runtime::Permill, storage::bounded_vec::BoundedVec, PalletError,
+};
 use sp_std::prelude::*;
 
 use pallet_acurast::{
@@ -7,17 +9,19 @@ use pallet_acurast::{
 
 use core::fmt::Debug;
 use serde::{Deserialize, Serialize};
+use sp_core::H256;
 
 use crate::Config;
 
-pub(crate) const MAX_EXECUTIONS_PER_JOB: u64 = 6_308_000; // run a job every 5 seconds for a year
-
 pub(crate) const EXECUTION_OPERATION_HASH_MAX_LENGTH: u32 = 256;
 pub(crate) const EXECUTION_FAILURE_MESSAGE_MAX_LENGTH: u32 = 1024;
+pub const MAX_PREFERRED_SOURCES: u32 = 16;
 
 pub type ExecutionOperationHash = BoundedVec<u8, ConstU32<EXECUTION_OPERATION_HASH_MAX_LENGTH>>;
 pub type ExecutionFailureMessage = BoundedVec<u8, ConstU32<EXECUTION_FAILURE_MESSAGE_MAX_LENGTH>>;
 pub type PlannedExecutions<AccountId, MaxSlots> = BoundedVec<PlannedExecution<AccountId>, MaxSlots>;
+pub type MaxPreferredSources = ConstU32<MAX_PREFERRED_SOURCES>;
+pub type PreferredSources<AccountId> = BoundedVec<AccountId, MaxPreferredSources>;
 
 pub type JobRegistrationForMarketplace<T> = JobRegistration<
     <T as frame_system::Config>::AccountId,
@@ -60,6 +64,14 @@ impl<Reward, AccountId, MaxSlots: ParameterBound>
     }
 }
 
+impl<Reward, AccountId, MaxSlots: ParameterBound> From<JobRequirements<Reward, AccountId, MaxSlots>>
+    for RegistrationExtra<Reward, AccountId, MaxSlots>
+{
+    fn from(requirements: JobRequirements<Reward, AccountId, MaxSlots>) -> Self {
+        Self { requirements }
+    }
+}
+
 /// The resource advertisement by a source containing pricing and capacity announcements.
 #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
 pub struct Advertisement<AccountId, Reward, MaxAllowedConsumers: Get<u32>> {
@@ -67,7 +79,10 @@ pub struct Advertisement<AccountId, Reward, MaxAllowedConsumers: Get<u32>> {
     pub pricing: Pricing<Reward>,
     /// Maximum memory in bytes not to be exceeded during any job's execution.
     pub max_memory: u32,
-    /// Maximum network requests per second not to be exceeded.
+    /// Maximum network requests per second of execution the source permits.
+    ///
+    /// A job performing [`JobRegistration::network_requests`] per execution is only accepted if
+    /// `network_requests * 1000 <= network_request_quota * schedule.duration` (duration in milliseconds).
     pub network_request_quota: u8,
     /// Storage capacity in bytes not to be exceeded in matching. The associated fee is listed in [pricing].
     pub storage_capacity: u32,
@@ -88,7 +103,10 @@ pub type AdvertisementFor<T> = Advertisement<
 pub struct AdvertisementRestriction<AccountId, MaxAllowedConsumers: ParameterBound> {
     /// Maximum memory in bytes not to be exceeded during any job's execution.
     pub max_memory: u32,
-    /// Maximum network requests per second not to be exceeded.
+    /// Maximum network requests per second of execution the source permits.
+    ///
+    /// A job performing [`JobRegistration::network_requests`] per execution is only accepted if
+    /// `network_requests * 1000 <= network_request_quota * schedule.duration` (duration in milliseconds).
     pub network_request_quota: u8,
     /// Storage capacity in bytes not to be exceeded in matching. The associated fee is listed in [pricing].
     pub storage_capacity: u32,
@@ -148,6 +166,10 @@ pub struct Assignment<Reward> {
     pub sla: SLA,
     /// Processor Pub Keys
     pub pub_keys: PubKeys,
+    /// The accrued part of the fees for reported executions held back until finalization, see [`JobRequirements::holdback_release_sla`].
+    pub holdback: Reward,
+    /// The version of the job's environment the source acknowledged to have received, see [`JobRequirements::require_env_ack`].
+    pub environment_ack: Option<u32>,
 }
 
 #[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq)]
@@ -196,8 +218,16 @@ pub enum JobStatus {
     /// Status after a valid match for a job got submitted.
     Matched,
     /// Status after a number of acknowledgments were submitted by sources.
+    ///
+    /// The count equals the number of slots in [`crate::AcknowledgedSlots`].
     Assigned(u8),
+    /// Status after a job depending on another job got registered. The job becomes [`JobStatus::Open`] once its dependency finalized successfully.
+    WaitingForDependency,
+    /// Status after a job exceeding the consumer's [`RegistrationApproval::threshold`] got registered.
+    /// The job's budget is locked but it is not matchable until approved by the consumer's [`RegistrationApproval::approver`].
+    PendingApproval,
     // The implicit final status leads to removal of job from status storage.
+    // Assigned jobs whose sources met none of the expected executions end as failed, see `Event::JobFailed`.
 }
 
 impl Default for JobStatus {
@@ -217,6 +247,198 @@ pub struct SLA {
     pub met: u64,
 }
 
+/// A compact record of a processor's SLA for a finalized job.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SLARecord<Balance> {
+    /// The hash of the finalized job's [`JobId`].
+    pub job_id_hash: H256,
+    /// The number of executions reported.
+    pub met: u64,
+    /// The number of executions expected.
+    pub total: u64,
+    /// The fee per execution agreed on in the assignment.
+    pub fee_per_execution: Balance,
+    /// The timestamp of the finalization in milliseconds.
+    pub finalized_at: u64,
+}
+
+pub type SLARecordFor<T> = SLARecord<<T as Config>::Balance>;
+
+/// The recent SLA history of a processor together with the aggregated fulfillment ratio over it.
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SLASummary<Balance> {
+    pub records: Vec<SLARecord<Balance>>,
+    /// The ratio of met over total executions in the window, in parts per million, `r ∈ [0, 1_000_000]`.
+    /// [`None`] if there is no history yet.
+    pub fulfillment_ratio: Option<u128>,
+}
+
+pub type SLASummaryFor<T> = SLASummary<<T as Config>::Balance>;
+
+/// A job open for matching together with what a matcher needs to prioritize it, see [`crate::Pallet::matchable_jobs`].
+#[derive(RuntimeDebug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MatchableJob<Reward, AccountId> {
+    pub job_id: JobId<AccountId>,
+    /// The start time of the job's schedule in milliseconds since Unix Epoch, after which a match is overdue.
+    pub start_time: u64,
+    /// The reward offered for each slot and scheduled execution.
+    pub reward: Reward,
+    pub slots: u8,
+    /// The estimated remaining reward of a match, out of which the matcher reward is paid.
+    ///
+    /// Computed from a bounded sample of eligible sources only, see [`crate::Pallet::matchable_jobs`].
+    /// [`None`] if the sample contains fewer eligible sources than the job has slots.
+    pub margin_estimate: Option<Reward>,
+}
+
+pub type MatchableJobFor<T> =
+    MatchableJob<<T as Config>::Balance, <T as frame_system::Config>::AccountId>;
+
+/// The performance counters of a matcher, tracked for the governance review of approved matchers.
+#[derive(
+    RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Default,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MatcherPerformance {
+    /// The number of successful matching proposals.
+    pub proposals: u64,
+    /// The number of proposed matches that got accepted.
+    pub accepted_matches: u64,
+    /// The number of proposed matches that got skipped since their job was no longer open for matching, or that got
+    /// rejected in a lenient proposal.
+    pub rejected_entries: u64,
+}
+
+/// The reason a proposed match got rejected, one per matching error of [`crate::Error`].
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MatchingReasonCode {
+    OverdueMatch,
+    IncorrectSourceCount,
+    DuplicateSource,
+    UnverifiedSource,
+    SchedulingWindowExceeded,
+    MaxMemoryExceeded,
+    NetworkRequestQuotaExceeded,
+    InsufficientStorageCapacity,
+    SourceNotAllowed,
+    ConsumerNotAllowed,
+    InsufficientReward,
+    InsufficientReputation,
+    ScheduleOverlap,
+    InsufficientRecentFulfillmentRatio,
+    PricingVersionMismatch,
+    ModuleNotAvailable,
+    AttestationTooOld,
+    OutsidePreferenceWindow,
+    ModuleNotAttested,
+    FeeAboveConsumerMaximum,
+    CapacityNotFound,
+    AttestationInvalid,
+    TooManyAssignedJobs,
+}
+
+/// The most recent rejected matching attempt of a job, see [`crate::LastMatchAttempt`].
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MatchAttempt<BlockNumber> {
+    /// The block of the most recent recorded rejection.
+    pub block: BlockNumber,
+    pub reason: MatchingReasonCode,
+    /// The number of recorded rejections since the job was last matched, counting at most one per block.
+    pub attempts: u32,
+}
+
+/// A consumer's opt-in dual control for expensive jobs: registrations locking a total budget above `threshold`
+/// have to be approved by `approver` before they become matchable.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub struct RegistrationApproval<Balance, AccountId> {
+    /// The total budget of a registration above which approval is required.
+    pub threshold: Balance,
+    /// The account approving or rejecting registrations above the threshold.
+    pub approver: AccountId,
+}
+
+pub type RegistrationApprovalFor<T> =
+    RegistrationApproval<<T as Config>::Balance, <T as frame_system::Config>::AccountId>;
+
+/// A consumer's profile of default requirements, used to resolve the [`OmittedFields`] of sparse job registrations.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub struct ConsumerDefaults {
+    /// Default for [`JobRegistration::allow_only_verified_sources`].
+    pub allow_only_verified_sources: bool,
+    /// Default for [`JobRegistration::required_modules`].
+    pub required_modules: JobModules,
+    /// Default for [`JobRegistration::memory`].
+    pub memory: u32,
+    /// Default for [`JobRegistration::network_requests`].
+    pub network_requests: u32,
+    /// Default for [`JobRegistration::storage`].
+    pub storage: u32,
+    /// Default for [`JobRequirements::min_reputation`].
+    pub min_reputation: Option<u128>,
+}
+
+/// The fields omitted in a sparse job registration, to be resolved from the consumer's [`ConsumerDefaults`].
+///
+/// Fields not flagged are taken from the registration as provided, so explicit values take precedence over defaults.
+#[derive(
+    RuntimeDebug,
+    Encode,
+    Decode,
+    MaxEncodedLen,
+    TypeInfo,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct OmittedFields {
+    pub allow_only_verified_sources: bool,
+    pub required_modules: bool,
+    pub memory: bool,
+    pub network_requests: bool,
+    pub storage: bool,
+    pub min_reputation: bool,
+}
+
+/// A payout escrowed for a source on report because its manager could not be resolved.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub struct PendingPayout<Balance> {
+    /// The accumulated amount escrowed.
+    pub amount: Balance,
+    /// The timestamp in milliseconds when the first payment was escrowed.
+    pub escrowed_at: u64,
+}
+
+pub type PendingPayoutFor<T> = PendingPayout<<T as Config>::Balance>;
+
+/// A consumer's proposal to change the schedule of an assigned job, taking effect once all assigned processors approved.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub struct ScheduleChangeProposal<AccountId, MaxSlots: ParameterBound> {
+    /// The schedule replacing the registration's schedule.
+    pub schedule: Schedule,
+    /// The timestamp in milliseconds of the proposal, starting the [`crate::Config::ScheduleChangeTimeout`].
+    pub proposed_at: u64,
+    /// The assigned processors that approved the proposal so far.
+    pub approvals: BoundedVec<AccountId, MaxSlots>,
+}
+
+pub type ScheduleChangeProposalFor<T> = ScheduleChangeProposal<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_acurast::Config>::MaxSlots,
+>;
+
 pub type JobRequirementsFor<T> = JobRequirements<
     <T as Config>::Balance,
     <T as frame_system::Config>::AccountId,
@@ -243,9 +465,66 @@ pub struct JobRequirements<Reward, AccountId, MaxSlots: ParameterBound> {
     pub reward: Reward,
     /// Minimum reputation required to process job, in parts per million, `r ∈ [0, 1_000_000]`.
     pub min_reputation: Option<u128>,
+    /// Minimum ratio of met over total executions in a processor's recent SLA history, in parts per million, `r ∈ [0, 1_000_000]`.
+    /// Processors without any history do not satisfy this requirement.
+    pub min_recent_fulfillment_ratio: Option<u128>,
+    /// Opts into holding back part of each execution's fee until the assignment is finalized, see [`Config::ImmediatePayoutRatio`].
+    ///
+    /// The holdback is released to the source if its ratio of met over total executions reaches this threshold,
+    /// in parts per million, `r ∈ [0, 1_000_000]`, and refunded to the consumer otherwise.
+    pub holdback_release_sla: Option<u128>,
+    /// Maximum age in milliseconds of a source's key attestation at matching time, measured from its submission.
+    /// Sources without an attestation or with an attestation of unknown submission time do not satisfy this requirement.
+    pub max_attestation_age_ms: Option<u64>,
+    /// Optional maximum fee per execution the consumer accepts from a single source, in addition to the
+    /// [`JobRequirements::reward`] bounding it. Must not exceed the reward if both are set.
+    pub max_fee_per_execution: Option<Reward>,
+    /// Optional job of the same consumer that has to finalize successfully before this job becomes matchable.
+    /// The job's schedule must not start before the dependency's schedule ends.
+    pub depends_on: Option<JobId<AccountId>>,
+    /// Optional sources getting the first right of refusal: during the [`JobRequirements::preference_window_ms`]
+    /// after the job's registration, only matches with all sources in this list are accepted.
+    pub preferred_sources: Option<PreferredSources<AccountId>>,
+    /// The time in milliseconds after the job's registration during which matching is exclusive to the
+    /// [`JobRequirements::preferred_sources`]. Afterwards the job is open to all sources.
+    pub preference_window_ms: u64,
+    /// Marks the registration as sparse: the flagged fields are omitted and get resolved from the consumer's
+    /// [`ConsumerDefaults`] during registration. Stored registrations are always fully resolved and have this set to `None`.
+    pub omitted: Option<OmittedFields>,
     /// Optional match provided with the job requirements. If provided, it gets processed instantaneously during
     /// registration call and validation errors lead to abortion of the call.
     pub instant_match: Option<PlannedExecutions<AccountId, MaxSlots>>,
+    /// Requires assigned sources to acknowledge the receipt of their execution environment before reporting.
+    ///
+    /// Reports are rejected until the source acknowledged the job's current environment version.
+    pub require_env_ack: bool,
+    /// Optionally re-registers the job after each successful finalization, see [`RenewalPolicy`].
+    pub auto_renew: Option<RenewalPolicy>,
+}
+
+/// The policy of a job re-registering itself for another cycle after its successful finalization.
+///
+/// The renewed job is registered under a new [`JobId`] with the schedule shifted forward by as many of the original
+/// schedule's spans as needed to start in the future, the reward escalated by [`RenewalPolicy::reward_escalation`] and
+/// one renewal less left.
+#[derive(
+    RuntimeDebug,
+    Encode,
+    Decode,
+    MaxEncodedLen,
+    TypeInfo,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct RenewalPolicy {
+    /// The number of renewals left, the job is not renewed again once this reaches zero.
+    pub max_renewals: u32,
+    /// The increase of the reward per slot and execution applied with each renewal.
+    pub reward_escalation: Permill,
 }
 
 /// A (one-sided) matching of a job to sources such that the requirements of both sides, consumer and source, are met.
@@ -283,6 +562,12 @@ pub struct PartialJobRegistration<Reward, AccountId, MaxAllowedSources: Get<u32>
     pub reward: Reward,
     /// Job requirements: Minimum reputation required to process job, in parts per million, `r ∈ [0, 1_000_000]`.
     pub min_reputation: Option<u128>,
+    /// Job requirements: Minimum ratio of met over total executions in a processor's recent SLA history, in parts per million, `r ∈ [0, 1_000_000]`.
+    pub min_recent_fulfillment_ratio: Option<u128>,
+    /// Job requirements: Maximum age in milliseconds of a source's key attestation, measured from its submission.
+    pub max_attestation_age_ms: Option<u64>,
+    /// Job requirements: Maximum fee per execution accepted from a single source.
+    pub max_fee_per_execution: Option<Reward>,
 }
 
 /// The details for a single planned slot execution with the delay.
@@ -303,6 +588,11 @@ pub struct PlannedExecution<AccountId> {
     pub source: AccountId,
     /// The start delay for the first execution and all the following executions.
     pub start_delay: u64,
+    /// The version of the source's pricing observed by the matcher, see [`crate::StoredAdvertisementPricingVersion`].
+    ///
+    /// The match is rejected if the pricing got updated in the meantime. [`None`] skips the check,
+    /// e.g. for instant matches provided from target chains that cannot observe the pricing version.
+    pub pricing_version: Option<u32>,
 }
 
 #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
@@ -311,19 +601,62 @@ pub enum ExecutionResult {
     Success(ExecutionOperationHash),
     /// Failure with message.
     Failure(ExecutionFailureMessage),
+    /// Success with operation hash and a commitment to the data delivered off-chain, e.g. the hash of the delivered payload.
+    ///
+    /// The commitment is stored as a [`DeliveryCommitment`] and can be challenged by the consumer with [`crate::Pallet::challenge_delivery`].
+    SuccessWithDeliveryCommitment(ExecutionOperationHash, H256),
+}
+
+/// A processor's commitment to the data delivered in a reported execution.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub struct DeliveryCommitment {
+    /// The commitment provided with [`ExecutionResult::SuccessWithDeliveryCommitment`].
+    pub commitment: H256,
+    /// The timestamp of the report, starting the [`crate::Config::DeliveryChallengeWindow`].
+    pub reported_at: u64,
+    /// The status of the consumer's challenge, if challenged.
+    pub challenge: Option<ChallengeStatus>,
+}
+
+/// The status of a consumer's challenge of a [`DeliveryCommitment`].
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeStatus {
+    /// The challenge awaits resolution. The execution counts as unmet in the finalization's reputation update.
+    Pending,
+    /// The challenge was resolved in favour of the consumer. The execution counts as unmet.
+    Upheld,
+    /// The challenge was resolved in favour of the processor. The execution counts as met.
+    Rejected,
 }
 
 /// Allows to hook additional logic for marketplace related state transitions.
+///
+/// Implementations forwarding a job id back to its target chain should recover the chain's
+/// sequence number with [`pallet_acurast::foreign_job_sequence`].
 pub trait MarketplaceHooks<T: Config> {
     fn assign_job(
         job_id: &JobId<<T as frame_system::Config>::AccountId>,
         pub_keys: &PubKeys,
     ) -> DispatchResultWithPostInfo;
 
+    /// Called when a job gets finalized or deregistered, refunding `refund` to its creator.
+    ///
+    /// `failed` is `true` for assigned jobs whose sources together met none of the expected executions.
     fn finalize_job(
         job_id: &JobId<<T as frame_system::Config>::AccountId>,
         refund: T::Balance,
+        failed: bool,
     ) -> DispatchResultWithPostInfo;
+
+    /// Called when a job reached its end without ever being matched, see [`crate::Pallet::expire_unmatched_job`].
+    ///
+    /// Defaults to [`MarketplaceHooks::finalize_job`].
+    fn expire_job(
+        job_id: &JobId<<T as frame_system::Config>::AccountId>,
+        refund: T::Balance,
+    ) -> DispatchResultWithPostInfo {
+        Self::finalize_job(job_id, refund, false)
+    }
 }
 
 impl<T: Config> MarketplaceHooks<T> for () {
@@ -337,6 +670,7 @@ impl<T: Config> MarketplaceHooks<T> for () {
     fn finalize_job(
         _job_id: &JobId<<T as frame_system::Config>::AccountId>,
         _refund: T::Balance,
+        _failed: bool,
     ) -> DispatchResultWithPostInfo {
         Ok(().into())
     }
@@ -352,6 +686,12 @@ pub enum RuntimeApiError {
     /// Error when retrieving matched jobs.
     #[cfg_attr(feature = "std", error("Retriving matched jobs failed."))]
     MatchedJobs,
+    /// Error when retrieving the unacknowledged slots of a job.
+    #[cfg_attr(feature = "std", error("Retrieving unacknowledged slots failed."))]
+    UnacknowledgedSlots,
+    /// Error when retrieving the matchable jobs.
+    #[cfg_attr(feature = "std", error("Retrieving matchable jobs failed."))]
+    MatchableJobs,
 }
 
 impl RuntimeApiError {
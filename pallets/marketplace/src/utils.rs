@@ -1,5 +1,5 @@
 use crate::Config;
-use frame_support::BoundedVec;
+use frame_support::{sp_runtime::Permill, BoundedVec};
 use pallet_acurast::{AllowedSources, MultiOrigin};
 
 /// Checks if a consumer is whitelisted/
@@ -31,3 +31,10 @@ pub fn is_source_whitelisted<T: Config>(
         })
         .unwrap_or(true)
 }
+
+/// Converts a ratio in parts per million into a [`Permill`], saturating at one instead of truncating.
+///
+/// Registration rejects ratios above one, but jobs stored before that check might still hold them.
+pub(crate) fn permill_from_parts(parts: u128) -> Permill {
+    Permill::from_parts(parts.try_into().unwrap_or(u32::MAX))
+}
@@ -40,6 +40,8 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: AcurastMarketplace StoredStorageCapacity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredAdvertisementPricing (r:0 w:1)
 	/// Proof: AcurastMarketplace StoredAdvertisementPricing (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AdvertisementIndex (r:0 w:1)
+	/// Proof: AcurastMarketplace AdvertisementIndex (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
 	fn advertise() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `42`
@@ -48,7 +50,7 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(17_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 10840))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().writes(5))
 	}
 	/// Storage: AcurastMarketplace StoredAdvertisementRestriction (r:1 w:1)
 	/// Proof: AcurastMarketplace StoredAdvertisementRestriction (max_values: None, max_size: Some(3830), added: 6305, mode: MaxEncodedLen)
@@ -58,6 +60,8 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: AcurastMarketplace StoredStorageCapacity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredAdvertisementPricing (r:0 w:1)
 	/// Proof: AcurastMarketplace StoredAdvertisementPricing (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AdvertisementIndex (r:0 w:1)
+	/// Proof: AcurastMarketplace AdvertisementIndex (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
 	fn delete_advertisement() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `194`
@@ -66,12 +70,14 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(19_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 10991))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: AcurastMarketplace StoredMatches (r:1 w:1)
 	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace EnvironmentVersion (r:1 w:0)
+	/// Proof: AcurastMarketplace EnvironmentVersion (max_values: None, max_size: Some(102), added: 2577, mode: MaxEncodedLen)
 	/// Storage: Timestamp Now (r:1 w:0)
 	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: AcurastProcessorManager ProcessorToManagerIdIndex (r:1 w:0)
@@ -86,20 +92,27 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: AcurastFeeManager FeePercentage (max_values: None, max_size: Some(17), added: 2492, mode: MaxEncodedLen)
 	/// Storage: System Account (r:3 w:3)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace DeliveryCommitmentCount (r:1 w:1)
+	/// Proof: AcurastMarketplace DeliveryCommitmentCount (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredDeliveryCommitments (r:0 w:1)
+	/// Proof: AcurastMarketplace StoredDeliveryCommitments (max_values: None, max_size: Some(174), added: 2649, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
 	fn report() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `2615`
-		//  Estimated: `67822`
-		// Minimum execution time: 86_000_000 picoseconds.
-		Weight::from_parts(87_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 67822))
-			.saturating_add(T::DbWeight::get().reads(11))
-			.saturating_add(T::DbWeight::get().writes(5))
+		//  Measured:  `2717`
+		//  Estimated: `40902`
+		// Minimum execution time: 91_000_000 picoseconds.
+		Weight::from_parts(92_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 40902))
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	/// Storage: AcurastMarketplace StoredJobStatus (r:10 w:10)
 	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredJobRegistration (r:10 w:0)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:10 w:0)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
 	/// Storage: Timestamp Now (r:1 w:0)
 	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredAdvertisementRestriction (r:640 w:0)
@@ -128,21 +141,80 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace AssignedProcessors (r:0 w:640)
 	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace LastMatchAttempt (r:0 w:10)
+	/// Proof: AcurastMarketplace LastMatchAttempt (max_values: None, max_size: Some(107), added: 2582, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:640 w:640)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1, 10]`.
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
 	fn propose_matching(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1021 + x * (14901 ±0)`
-		//  Estimated: `27048 + x * (1278702 ±0)`
+		//  Estimated: `27048 + x * (1281263 ±0)`
 		// Minimum execution time: 1_472_000_000 picoseconds.
 		Weight::from_parts(1_482_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 27048))
 			// Standard Error: 8_200_029
 			.saturating_add(Weight::from_parts(1_476_890_801, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(8))
-			.saturating_add(T::DbWeight::get().reads((387_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((452_u64).saturating_mul(x.into())))
 			.saturating_add(T::DbWeight::get().writes(3))
-			.saturating_add(T::DbWeight::get().writes((194_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 1278702).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().writes((259_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 1281263).saturating_mul(x.into()))
+	}
+	/// Storage: AcurastMarketplace StoredJobStatus (r:6 w:5)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:6 w:0)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:6 w:0)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredAdvertisementRestriction (r:321 w:0)
+	/// Proof: AcurastMarketplace StoredAdvertisementRestriction (max_values: None, max_size: Some(3830), added: 6305, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredAdvertisementPricing (r:321 w:0)
+	/// Proof: AcurastMarketplace StoredAdvertisementPricing (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredStorageCapacity (r:321 w:320)
+	/// Proof: AcurastMarketplace StoredStorageCapacity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredReputation (r:321 w:0)
+	/// Proof: AcurastMarketplace StoredReputation (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredMatches (r:641 w:320)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredTotalAssignedV3 (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredTotalAssignedV3 (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: AcurastMatcherFeeManager Version (r:1 w:0)
+	/// Proof: AcurastMatcherFeeManager Version (max_values: Some(1), max_size: Some(2), added: 497, mode: MaxEncodedLen)
+	/// Storage: AcurastMatcherFeeManager FeePercentage (r:1 w:0)
+	/// Proof: AcurastMatcherFeeManager FeePercentage (max_values: None, max_size: Some(17), added: 2492, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobBudgets (r:6 w:5)
+	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: AcurastFeeManager Version (r:1 w:0)
+	/// Proof: AcurastFeeManager Version (max_values: Some(1), max_size: Some(2), added: 497, mode: MaxEncodedLen)
+	/// Storage: AcurastFeeManager FeePercentage (r:1 w:0)
+	/// Proof: AcurastFeeManager FeePercentage (max_values: None, max_size: Some(17), added: 2492, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AssignedProcessors (r:0 w:320)
+	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace LastMatchAttempt (r:0 w:5)
+	/// Proof: AcurastMarketplace LastMatchAttempt (max_values: None, max_size: Some(107), added: 2582, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:321 w:320)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 10]`.
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn propose_matching_rejected(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1021 + x * (7451 ±0)`
+		//  Estimated: `27048 + x * (640632 ±0)`
+		// Minimum execution time: 1_476_000_000 picoseconds.
+		Weight::from_parts(1_486_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 27048))
+			// Standard Error: 4_100_015
+			.saturating_add(Weight::from_parts(738_445_400, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((226_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes((130_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 640632).saturating_mul(x.into()))
 	}
 	/// Storage: AcurastMarketplace StoredMatches (r:1 w:1)
 	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
@@ -159,7 +231,7 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredMatches (r:1 w:1)
 	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
 	/// Storage: Timestamp Now (r:1 w:0)
@@ -168,22 +240,29 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: Acurast StoredAttestation (max_values: None, max_size: Some(11622), added: 14097, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredStorageCapacity (r:1 w:1)
 	/// Proof: AcurastMarketplace StoredStorageCapacity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace FinalizedSlotsSLA (r:1 w:1)
+	/// Proof: AcurastMarketplace FinalizedSlotsSLA (max_values: None, max_size: Some(98), added: 2573, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace AssignedProcessors (r:0 w:1)
 	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:1 w:1)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
 	fn finalize_job() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1102`
-		//  Estimated: `62025`
+		//  Estimated: `32541`
 		// Minimum execution time: 36_000_000 picoseconds.
 		Weight::from_parts(37_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 62025))
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(Weight::from_parts(0, 32541))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
 	}
 	/// Storage: AcurastMarketplace StoredJobStatus (r:10 w:10)
 	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
 	/// Storage: Acurast StoredJobRegistration (r:10 w:10)
-	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(34795), added: 37270, mode: MaxEncodedLen)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:0 w:10)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace AssignedProcessors (r:20 w:10)
 	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
 	/// Storage: Timestamp Now (r:1 w:0)
@@ -194,19 +273,474 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
 	/// Storage: AcurastMarketplace StoredMatches (r:0 w:10)
 	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace FinalizedSlotsSLA (r:10 w:10)
+	/// Proof: AcurastMarketplace FinalizedSlotsSLA (max_values: None, max_size: Some(98), added: 2573, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:10 w:10)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
 	/// The range of component `x` is `[1, 10]`.
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
 	fn finalize_jobs(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `657 + x * (431 ±0)`
-		//  Estimated: `6443 + x * (49971 ±0)`
+		//  Estimated: `6443 + x * (20487 ±0)`
 		// Minimum execution time: 58_000_000 picoseconds.
 		Weight::from_parts(13_348_701, 0)
 			.saturating_add(Weight::from_parts(0, 6443))
 			// Standard Error: 62_399
 			.saturating_add(Weight::from_parts(47_230_935, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(x.into())))
-			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 49971).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 20487).saturating_mul(x.into()))
+	}
+	/// Storage: AcurastMarketplace PendingPayouts (r:1 w:1)
+	/// Proof: AcurastMarketplace PendingPayouts (max_values: None, max_size: Some(183), added: 2658, mode: MaxEncodedLen)
+	/// Storage: AcurastProcessorManager ProcessorToManagerIdIndex (r:1 w:0)
+	/// Proof: AcurastProcessorManager ProcessorToManagerIdIndex (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: Uniques Asset (r:1 w:0)
+	/// Proof: Uniques Asset (max_values: None, max_size: Some(146), added: 2621, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastFeeManager Version (r:1 w:0)
+	/// Proof: AcurastFeeManager Version (max_values: Some(1), max_size: Some(2), added: 497, mode: MaxEncodedLen)
+	/// Storage: AcurastFeeManager FeePercentage (r:1 w:0)
+	/// Proof: AcurastFeeManager FeePercentage (max_values: None, max_size: Some(17), added: 2492, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_pending_payout() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1102`
+		//  Estimated: `17624`
+		// Minimum execution time: 62_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 17624))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: AcurastMarketplace StoredConsumerDefaults (r:0 w:1)
+	/// Proof: AcurastMarketplace StoredConsumerDefaults (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	fn set_default_requirements() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastMarketplace RegistrationApprovals (r:0 w:1)
+	/// Proof: AcurastMarketplace RegistrationApprovals (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	fn set_registration_approval() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace RegistrationApprovals (r:1 w:0)
+	/// Proof: AcurastMarketplace RegistrationApprovals (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
+	/// Proof Skipped: Acurast StoredJobRegistration (max_values: None, max_size: None, mode: Measured)
+	/// Storage: AcurastMarketplace Dependents (r:1 w:0)
+	/// Proof: AcurastMarketplace Dependents (max_values: None, max_size: Some(482), added: 2957, mode: MaxEncodedLen)
+	fn approve_registration() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `812`
+		//  Estimated: `4277`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4277))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace RegistrationApprovals (r:1 w:0)
+	/// Proof: AcurastMarketplace RegistrationApprovals (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:1 w:1)
+	/// Proof Skipped: Acurast StoredJobRegistration (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Acurast StoredJobPayload (r:0 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobBudgets (r:1 w:1)
+	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn reject_registration() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1034`
+		//  Estimated: `6196`
+		// Minimum execution time: 58_000_000 picoseconds.
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: AcurastMarketplace StoredMatches (r:1 w:0)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(234), added: 2709, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredDeliveryCommitments (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredDeliveryCommitments (max_values: None, max_size: Some(147), added: 2622, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace DisputedExecutions (r:1 w:1)
+	/// Proof: AcurastMarketplace DisputedExecutions (max_values: None, max_size: Some(110), added: 2585, mode: MaxEncodedLen)
+	fn challenge_delivery() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `687`
+		//  Estimated: `3699`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3699))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: AcurastMarketplace StoredDeliveryCommitments (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredDeliveryCommitments (max_values: None, max_size: Some(147), added: 2622, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace DisputedExecutions (r:1 w:1)
+	/// Proof: AcurastMarketplace DisputedExecutions (max_values: None, max_size: Some(110), added: 2585, mode: MaxEncodedLen)
+	fn resolve_challenge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3612`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3612))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredAdvertisementPricing (r:10 w:0)
+	/// Proof: AcurastMarketplace StoredAdvertisementPricing (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 10]`.
+	fn check_scheduling_windows(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `150 + x * (120 ±0)`
+		//  Estimated: `1493 + x * (2548 ±0)`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(6_412_337, 0)
+			.saturating_add(Weight::from_parts(0, 1493))
+			// Standard Error: 9_811
+			.saturating_add(Weight::from_parts(4_105_226, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 2548).saturating_mul(x.into()))
+	}
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:1 w:1)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:0 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobBudgets (r:1 w:1)
+	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobRegistrationDeposits (r:1 w:1)
+	/// Proof: AcurastMarketplace JobRegistrationDeposits (max_values: None, max_size: Some(137), added: 2612, mode: MaxEncodedLen)
+	/// Storage: Acurast ExecutionEnvironment (r:0 w:1)
+	/// Proof: Acurast ExecutionEnvironment (max_values: None, max_size: Some(40012), added: 42487, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace Dependents (r:1 w:1)
+	/// Proof: AcurastMarketplace Dependents (max_values: None, max_size: Some(405), added: 2880, mode: MaxEncodedLen)
+	fn expire_unmatched_job() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1120`
+		//  Estimated: `6203`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(54_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6203))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AssignedProcessors (r:65 w:0)
+	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(162), added: 2637, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredMatches (r:64 w:0)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AcknowledgedSlots (r:1 w:1)
+	/// Proof: AcurastMarketplace AcknowledgedSlots (max_values: None, max_size: Some(147), added: 2622, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 64]`.
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn repair_job_status(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1120 + x * (393 ±0)`
+		//  Estimated: `8825 + x * (5343 ±0)`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(33_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8825))
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 5343).saturating_mul(x.into()))
+	}
+	/// Storage: AcurastMarketplace ApprovedMatchers (r:0 w:1)
+	/// Proof: AcurastMarketplace ApprovedMatchers (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn add_approved_matcher() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastMarketplace ApprovedMatchers (r:1 w:1)
+	/// Proof: AcurastMarketplace ApprovedMatchers (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	fn remove_approved_matcher() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `124`
+		//  Estimated: `3513`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3513))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Acurast StoredJobRegistration (r:10 w:0)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredMatches (r:10 w:10)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredAttestation (r:1 w:0)
+	/// Proof: Acurast StoredAttestation (max_values: None, max_size: Some(11622), added: 14097, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredStorageCapacity (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredStorageCapacity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AssignedProcessors (r:0 w:10)
+	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:10 w:10)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 10]`.
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn finalize_assigned_jobs(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `694 + x * (408 ±0)`
+		//  Estimated: `15587 + x * (7919 ±0)`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(9_871_402, 0)
+			.saturating_add(Weight::from_parts(0, 15587))
+			// Standard Error: 41_206
+			.saturating_add(Weight::from_parts(31_642_118, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 7919).saturating_mul(x.into()))
+	}
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:0)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace PendingScheduleChanges (r:0 w:1)
+	/// Proof: AcurastMarketplace PendingScheduleChanges (max_values: None, max_size: Some(2183), added: 4658, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn propose_schedule_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `3579`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3579))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastMarketplace PendingScheduleChanges (r:1 w:1)
+	/// Proof: AcurastMarketplace PendingScheduleChanges (max_values: None, max_size: Some(2183), added: 4658, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredMatches (r:128 w:64)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredAdvertisementPricing (r:1 w:0)
+	/// Proof: AcurastMarketplace StoredAdvertisementPricing (max_values: None, max_size: Some(2686), added: 5161, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:1 w:0)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:65 w:1)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AssignedProcessors (r:65 w:0)
+	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 64]`.
+	/// The range of component `y` is `[0, 63]`.
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn approve_schedule_change(x: u32, y: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1802 + x * (226 ±0) + y * (435 ±0)`
+		//  Estimated: `17077 + x * (2706 ±0) + y * (7919 ±0)`
+		// Minimum execution time: 62_000_000 picoseconds.
+		Weight::from_parts(58_412_903, 0)
+			.saturating_add(Weight::from_parts(0, 17077))
+			.saturating_add(Weight::from_parts(6_104_226, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(4_800_000, 0).saturating_mul(y.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(y.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 2706).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(0, 7919).saturating_mul(y.into()))
+	}
+	/// Storage: AcurastMarketplace PendingScheduleChanges (r:1 w:1)
+	/// Proof: AcurastMarketplace PendingScheduleChanges (max_values: None, max_size: Some(2183), added: 4658, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn cancel_schedule_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `286`
+		//  Estimated: `5648`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5648))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Acurast ExecutionEnvironment (r:1 w:0)
+	/// Proof: Acurast ExecutionEnvironment (max_values: None, max_size: Some(10743), added: 13218, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace EnvironmentVersion (r:1 w:0)
+	/// Proof: AcurastMarketplace EnvironmentVersion (max_values: None, max_size: Some(102), added: 2577, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredMatches (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(236), added: 2711, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn acknowledge_environment() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `712`
+		//  Estimated: `14208`
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 14208))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Acurast StoredJobRegistration (r:1 w:0)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace EnvironmentVersion (r:1 w:1)
+	/// Proof: AcurastMarketplace EnvironmentVersion (max_values: None, max_size: Some(102), added: 2577, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn bump_environment_version() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `498`
+		//  Estimated: `6203`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6203))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: AcurastMarketplace StoredJobStatus (r:10 w:10)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:10 w:0)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:10 w:0)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredAdvertisementRestriction (r:640 w:0)
+	/// Proof: AcurastMarketplace StoredAdvertisementRestriction (max_values: None, max_size: Some(3830), added: 6305, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredAdvertisementPricing (r:640 w:0)
+	/// Proof: AcurastMarketplace StoredAdvertisementPricing (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredStorageCapacity (r:640 w:640)
+	/// Proof: AcurastMarketplace StoredStorageCapacity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredReputation (r:640 w:0)
+	/// Proof: AcurastMarketplace StoredReputation (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredMatches (r:1280 w:640)
+	/// Proof: AcurastMarketplace StoredMatches (max_values: None, max_size: Some(231), added: 2706, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredTotalAssignedV3 (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredTotalAssignedV3 (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: AcurastMatcherFeeManager Version (r:1 w:0)
+	/// Proof: AcurastMatcherFeeManager Version (max_values: Some(1), max_size: Some(2), added: 497, mode: MaxEncodedLen)
+	/// Storage: AcurastMatcherFeeManager FeePercentage (r:1 w:0)
+	/// Proof: AcurastMatcherFeeManager FeePercentage (max_values: None, max_size: Some(17), added: 2492, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobBudgets (r:10 w:10)
+	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: AcurastFeeManager Version (r:1 w:0)
+	/// Proof: AcurastFeeManager Version (max_values: Some(1), max_size: Some(2), added: 497, mode: MaxEncodedLen)
+	/// Storage: AcurastFeeManager FeePercentage (r:1 w:0)
+	/// Proof: AcurastFeeManager FeePercentage (max_values: None, max_size: Some(17), added: 2492, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace AssignedProcessors (r:0 w:640)
+	/// Proof: AcurastMarketplace AssignedProcessors (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace LastMatchAttempt (r:10 w:10)
+	/// Proof: AcurastMarketplace LastMatchAttempt (max_values: None, max_size: Some(107), added: 2582, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace SourceJobIndex (r:640 w:640)
+	/// Proof: AcurastMarketplace SourceJobIndex (max_values: None, max_size: Some(3186), added: 5661, mode: MaxEncodedLen)
+	/// The range of component `x` is `[1, 10]`.
+	fn propose_matching_lenient(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1021 + x * (14901 ±0)`
+		//  Estimated: `27048 + x * (1281263 ±0)`
+		// Minimum execution time: 1_480_000_000 picoseconds.
+		Weight::from_parts(1_490_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 27048))
+			// Standard Error: 8_200_029
+			.saturating_add(Weight::from_parts(1_484_102_713, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((453_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes((259_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 1281263).saturating_mul(x.into()))
+	}
+	/// Storage: Acurast LocalJobIdSequence (r:1 w:1)
+	/// Proof: Acurast LocalJobIdSequence (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace RegistrationApprovals (r:1 w:0)
+	/// Proof: AcurastMarketplace RegistrationApprovals (max_values: None, max_size: Some(80), added: 2555, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:1 w:1)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:0 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobBudgets (r:1 w:1)
+	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobRegistrationTimes (r:0 w:1)
+	/// Proof: AcurastMarketplace JobRegistrationTimes (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn renew_job() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1120`
+		//  Estimated: `8993`
+		// Minimum execution time: 60_000_000 picoseconds.
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8993))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	/// Storage: AcurastMarketplace Dependents (r:2 w:2)
+	/// Proof: AcurastMarketplace Dependents (max_values: None, max_size: Some(394), added: 2869, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace StoredJobStatus (r:1 w:1)
+	/// Proof: AcurastMarketplace StoredJobStatus (max_values: None, max_size: Some(114), added: 2589, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobBudgets (r:1 w:1)
+	/// Proof: AcurastMarketplace JobBudgets (max_values: None, max_size: Some(32), added: 2507, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobRegistrationDeposits (r:1 w:1)
+	/// Proof: AcurastMarketplace JobRegistrationDeposits (max_values: None, max_size: Some(89), added: 2564, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: AcurastMarketplace JobRegistrationTimes (r:0 w:1)
+	/// Proof: AcurastMarketplace JobRegistrationTimes (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobRegistration (r:0 w:1)
+	/// Proof: Acurast StoredJobRegistration (max_values: None, max_size: Some(2738), added: 5213, mode: MaxEncodedLen)
+	/// Storage: Acurast StoredJobPayload (r:0 w:1)
+	/// Proof: Acurast StoredJobPayload (max_values: None, max_size: Some(32143), added: 34618, mode: MaxEncodedLen)
+	// TODO: regenerate with `benchmark pallet`, execution time and proof size are estimated from the storage accesses.
+	fn resolve_dependent() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1024`
+		//  Estimated: `8338`
+		// Minimum execution time: 44_000_000 picoseconds.
+		Weight::from_parts(46_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8338))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
 }
@@ -126,5 +126,32 @@ benchmarks! {
         };
     }: _(RawOrigin::Signed(caller), update_info, processors.try_into().unwrap())
 
+    set_require_attestation_for_pairing {
+        let caller: T::AccountId = alice_account_id().into();
+        whitelist_account!(caller);
+    }: _(RawOrigin::Signed(caller), true)
+
+    unpair {
+        let caller: T::AccountId = alice_account_id().into();
+        let update = generate_pairing_update_add::<T>(0);
+        Pallet::<T>::update_processor_pairings(RawOrigin::Signed(caller.clone()).into(), vec![update.clone()].try_into().unwrap())?;
+    }: _(RawOrigin::Signed(update.item.account))
+
+    import_processor_manifest {
+        let x in 1 .. T::MaxManifestSize::get();
+        let caller: T::AccountId = alice_account_id().into();
+        whitelist_account!(caller);
+        let entries = (0..x).map(|i| ProcessorManifestEntry {
+            account: generate_account(i).into(),
+            signature: T::BenchmarkHelper::dummy_proof(),
+        }).collect::<Vec<_>>();
+        let manifest = ProcessorManifestFor::<T> {
+            nonce: 1u8.into(),
+            expiry: 1657363915002u128,
+            entries: entries.try_into().unwrap(),
+            signature: T::BenchmarkHelper::dummy_proof(),
+        };
+    }: _(RawOrigin::Signed(caller), manifest)
+
     impl_benchmark_test_suite!(Pallet, mock::ExtBuilder::default().build(), mock::Test);
 }
@@ -9,7 +9,7 @@ use frame_support::{
 
 use crate::{
     Config, Error, LastManagerId, ManagedProcessors, ManagerIdProvider, Pallet,
-    ProcessorToManagerIdIndex,
+    ProcessorAttestationProvider, ProcessorToManagerIdIndex, RequireAttestationForPairing,
 };
 
 impl<T: Config> Pallet<T>
@@ -42,7 +42,7 @@ where
     }
 
     /// Adds a pairing between the given processor account and manager id. It fails if the manager id does not exists of
-    /// if the processor account was already paired.
+    /// if the processor account was already paired, or if the manager requires an attestation the processor does not have.
     pub fn do_add_processor_manager_pairing(
         processor_account: &T::AccountId,
         manager_id: T::ManagerId,
@@ -53,6 +53,11 @@ where
             }
             return Err(Error::<T>::ProcessorPairedWithAnotherManager)?;
         }
+        if <RequireAttestationForPairing<T>>::get(manager_id)
+            && !T::AttestationProvider::is_attested(processor_account)
+        {
+            return Err(Error::<T>::ProcessorNotAttested)?;
+        }
         <ManagedProcessors<T>>::insert(manager_id, &processor_account, ());
         <ProcessorToManagerIdIndex<T>>::insert(&processor_account, manager_id);
 
@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod functions;
+mod migration;
 mod traits;
 mod types;
 
@@ -26,11 +27,20 @@ pub use pallet::*;
 pub use traits::*;
 pub use types::*;
 
+pub(crate) use pallet::STORAGE_VERSION;
+
 pub type ProcessorPairingFor<T> =
     ProcessorPairing<<T as frame_system::Config>::AccountId, <T as Config>::Proof>;
 pub type ProcessorPairingUpdateFor<T> =
     ProcessorPairingUpdate<<T as frame_system::Config>::AccountId, <T as Config>::Proof>;
 
+pub type ProcessorManifestFor<T> = ProcessorManifest<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::Proof,
+    <T as Config>::Counter,
+    <T as Config>::MaxManifestSize,
+>;
+
 pub type ProcessorUpdatesFor<T> =
     BoundedVec<ProcessorPairingUpdateFor<T>, <T as Config>::MaxPairingUpdates>;
 pub type ProcessorList<T> =
@@ -40,8 +50,8 @@ pub type ProcessorList<T> =
 pub mod pallet {
     #[cfg(feature = "runtime-benchmarks")]
     use crate::benchmarking::BenchmarkHelper;
-    use acurast_common::ListUpdateOperation;
-    use codec::MaxEncodedLen;
+    use acurast_common::{AcurastLimits, ListUpdateOperation, ParameterBound};
+    use codec::{DecodeAll, MaxEncodedLen};
     use frame_support::sp_runtime;
     use frame_support::{
         dispatch::DispatchResultWithPostInfo,
@@ -50,12 +60,15 @@ pub mod pallet {
         traits::{Get, UnixTime},
         Blake2_128, Parameter,
     };
-    use frame_system::{ensure_root, ensure_signed, pallet_prelude::OriginFor};
+    use frame_system::{
+        ensure_root, ensure_signed,
+        pallet_prelude::{BlockNumberFor, OriginFor},
+    };
     use sp_std::prelude::*;
 
     use crate::{
-        traits::*, BinaryHash, ProcessorList, ProcessorPairingFor, ProcessorUpdatesFor, UpdateInfo,
-        Version,
+        traits::*, BinaryHash, LastSeen, LastSeenFor, ProcessorList, ProcessorManifestFor,
+        ProcessorPairingFor, ProcessorUpdatesFor, UpdateInfo, Version,
     };
 
     /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -68,11 +81,30 @@ pub mod pallet {
         type ManagerIdProvider: ManagerIdProvider<Self>;
         type ProcessorAssetRecovery: ProcessorAssetRecovery<Self>;
         type MaxPairingUpdates: Get<u32>;
+        /// The maximum number of processors in a manifest imported with [`Pallet::import_processor_manifest`].
+        #[pallet::constant]
+        type MaxManifestSize: Get<u32> + ParameterBound;
         type MaxProcessorsInSetUpdateInfo: Get<u32>;
         type Counter: Parameter + Member + MaxEncodedLen + Copy + CheckedAdd + Ord + From<u8>;
         type PairingProofExpirationTime: Get<u128>;
         type Advertisement: Parameter + Member;
         type AdvertisementHandler: AdvertisementHandler<Self>;
+        /// Provides the attestation status of processors, checked on pairing for managers requiring it.
+        ///
+        /// Runtimes including `pallet_acurast_marketplace` use its pallet, which checks the processor's attestation stored
+        /// in `pallet_acurast`. The `()` implementation accepts every processor and makes the requirement ineffective.
+        type AttestationProvider: ProcessorAttestationProvider<Self>;
+        /// Provides whether processors have active matches, which prevents them from unpairing themselves.
+        type ProcessorMatchesProvider: ProcessorMatchesProvider<Self>;
+        /// The time in milliseconds after a processor's latest heartbeat before it can unpair itself with [`Pallet::unpair`].
+        #[pallet::constant]
+        type UnpairCooldown: Get<u128>;
+        /// The maximum time in milliseconds processors are expected to let pass between two heartbeats.
+        ///
+        /// Not enforced by this pallet, but processors not seen for longer should be considered offline by consumers of
+        /// [`Pallet::processor_last_seen`].
+        #[pallet::constant]
+        type HeartbeatInterval: Get<u128>;
         /// Timestamp
         type UnixTime: UnixTime;
         /// Weight Info for extrinsics.
@@ -145,9 +177,10 @@ pub mod pallet {
     #[pallet::getter(fn counter_for_manager)]
     pub(super) type ManagerCounter<T: Config> = StorageMap<_, Blake2_128, T::AccountId, T::Counter>;
 
+    /// The processors' latest heartbeats, read with [`Pallet::processor_last_seen`] or [`Pallet::processor_last_seen_record`].
     #[pallet::storage]
-    #[pallet::getter(fn processor_last_seen)]
-    pub(super) type ProcessorHeartbeat<T: Config> = StorageMap<_, Blake2_128, T::AccountId, u128>;
+    pub(super) type ProcessorHeartbeat<T: Config> =
+        StorageMap<_, Blake2_128, T::AccountId, LastSeenFor<T>>;
 
     #[pallet::storage]
     #[pallet::getter(fn processor_version)]
@@ -164,7 +197,16 @@ pub mod pallet {
     pub(super) type ProcessorUpdateInfo<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, UpdateInfo>;
 
+    /// Managers requiring processors to be attested when paired as a map [`ManagerId`] -> `bool`.
+    #[pallet::storage]
+    #[pallet::getter(fn require_attestation_for_pairing)]
+    pub(super) type RequireAttestationForPairing<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::ManagerId, bool, ValueQuery>;
+
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::event]
@@ -188,6 +230,19 @@ pub mod pallet {
         BinaryHashUpdated(Version, Option<BinaryHash>),
         /// Set update info for processor. [manager_account_id, update_info]
         ProcessorUpdateInfoSet(T::AccountId, UpdateInfo),
+        /// Attestation requirement for pairing set. [manager_account_id, require_attestation]
+        RequireAttestationForPairingSet(T::AccountId, bool),
+        /// Processor unpaired itself from its manager. [processor_account_id, manager_id]
+        ProcessorUnpaired(T::AccountId, T::ManagerId),
+        /// Processor manifest imported. [manager_account_id, paired, skipped]
+        ProcessorManifestImported(T::AccountId, u32, u32),
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> frame_support::weights::Weight {
+            crate::migration::migrate::<T>()
+        }
     }
 
     // Errors inform users that something went wrong.
@@ -201,6 +256,12 @@ pub mod pallet {
         CounterOverflow,
         PairingProofExpired,
         UnknownProcessorVersion,
+        ProcessorNotAttested,
+        ProcessorHasActiveMatches,
+        UnpairCooldownNotElapsed,
+        ManifestExpired,
+        InvalidManifestNonce,
+        InvalidManifestSignature,
     }
 
     impl<T: Config> Pallet<T> {
@@ -218,6 +279,50 @@ pub mod pallet {
 
             Ok(manager_id)
         }
+
+        /// Extends `limits` with the limits configured for this pallet.
+        pub fn extend_limits(limits: &mut AcurastLimits) {
+            limits.pairing_proof_expiration_time = Some(T::PairingProofExpirationTime::get());
+            limits.max_pairing_updates = Some(T::MaxPairingUpdates::get());
+            limits.heartbeat_interval = Some(T::HeartbeatInterval::get());
+            limits.unpair_cooldown = Some(T::UnpairCooldown::get());
+        }
+
+        fn record_heartbeat(processor: &T::AccountId) {
+            <ProcessorHeartbeat<T>>::insert(
+                processor,
+                LastSeen {
+                    timestamp_ms: T::UnixTime::now().as_millis(),
+                    block: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+        }
+
+        /// Returns the timestamp in milliseconds of the processor's latest heartbeat.
+        pub fn processor_last_seen(processor: &T::AccountId) -> Option<u128> {
+            Self::processor_last_seen_record(processor).map(|last_seen| last_seen.timestamp_ms)
+        }
+
+        /// Returns the record of the processor's latest heartbeat.
+        ///
+        /// As long as the migration to [`LastSeen`] records did not run, entries still holding the previous `u128`
+        /// timestamp are decoded as such, with an unknown (zero) block.
+        pub fn processor_last_seen_record(processor: &T::AccountId) -> Option<LastSeenFor<T>> {
+            if Self::on_chain_storage_version() >= STORAGE_VERSION {
+                return <ProcessorHeartbeat<T>>::get(processor);
+            }
+            let raw = frame_support::storage::unhashed::get_raw(
+                &<ProcessorHeartbeat<T>>::hashed_key_for(processor),
+            )?;
+            LastSeenFor::<T>::decode_all(&mut raw.as_slice())
+                .or_else(|_| {
+                    u128::decode_all(&mut raw.as_slice()).map(|timestamp_ms| LastSeen {
+                        timestamp_ms,
+                        block: 0u8.into(),
+                    })
+                })
+                .ok()
+        }
     }
 
     #[pallet::call]
@@ -336,7 +441,7 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             _ = Self::manager_id_for_processor(&who).ok_or(Error::<T>::ProcessorHasNoManager)?;
 
-            <ProcessorHeartbeat<T>>::insert(&who, T::UnixTime::now().as_millis());
+            Self::record_heartbeat(&who);
 
             Self::deposit_event(Event::<T>::ProcessorHeartbeat(who));
 
@@ -374,7 +479,7 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             _ = Self::manager_id_for_processor(&who).ok_or(Error::<T>::ProcessorHasNoManager)?;
 
-            <ProcessorHeartbeat<T>>::insert(&who, T::UnixTime::now().as_millis());
+            Self::record_heartbeat(&who);
             <ProcessorVersion<T>>::insert(&who, version.clone());
 
             Self::deposit_event(Event::<T>::ProcessorHeartbeatWithVersion(who, version));
@@ -423,6 +528,119 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Sets if processors are required to have a valid attestation to be paired with the caller's manager id.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::set_require_attestation_for_pairing())]
+        pub fn set_require_attestation_for_pairing(
+            origin: OriginFor<T>,
+            require_attestation: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let (manager_id, created) = Self::do_get_or_create_manager_id(&who)?;
+            if created {
+                Self::deposit_event(Event::<T>::ManagerCreated(who.clone(), manager_id));
+            }
+
+            if require_attestation {
+                <RequireAttestationForPairing<T>>::insert(manager_id, true);
+            } else {
+                <RequireAttestationForPairing<T>>::remove(manager_id);
+            }
+
+            Self::deposit_event(Event::<T>::RequireAttestationForPairingSet(
+                who,
+                require_attestation,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Removes the pairing of the calling processor with its manager.
+        ///
+        /// Fails as long as the processor has active matches or if its latest heartbeat is more recent than
+        /// [`Config::UnpairCooldown`].
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::unpair())]
+        pub fn unpair(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let manager_id =
+                Self::manager_id_for_processor(&who).ok_or(Error::<T>::ProcessorHasNoManager)?;
+
+            ensure!(
+                !T::ProcessorMatchesProvider::has_active_matches(&who),
+                Error::<T>::ProcessorHasActiveMatches
+            );
+            if let Some(last_seen) = Self::processor_last_seen(&who) {
+                ensure!(
+                    T::UnixTime::now().as_millis()
+                        >= last_seen.saturating_add(T::UnpairCooldown::get()),
+                    Error::<T>::UnpairCooldownNotElapsed
+                );
+            }
+
+            Self::do_remove_processor_manager_pairing(&who, manager_id)?;
+
+            Self::deposit_event(Event::<T>::ProcessorUnpaired(who, manager_id));
+
+            Ok(().into())
+        }
+
+        /// Pairs the processors of a manifest signed by the caller with the caller's manager id.
+        ///
+        /// Both the caller's signature over the whole manifest and each processor's signature over its entry are validated
+        /// before pairing any processor. The manifest's nonce has to be the next value of the caller's [`ManagerCounter`]
+        /// and is consumed by the manifest as a whole. Entries listed more than once or of processors already paired are
+        /// skipped and counted in [`Event::ProcessorManifestImported`].
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::import_processor_manifest(manifest.entries.len() as u32))]
+        pub fn import_processor_manifest(
+            origin: OriginFor<T>,
+            manifest: ProcessorManifestFor<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            if !manifest.validate_expiry::<T>() {
+                #[cfg(not(feature = "runtime-benchmarks"))]
+                return Err(Error::<T>::ManifestExpired)?;
+            }
+            let counter = Self::counter_for_manager(&who)
+                .unwrap_or(0u8.into())
+                .checked_add(&1u8.into())
+                .ok_or(Error::<T>::CounterOverflow)?;
+            ensure!(manifest.nonce == counter, Error::<T>::InvalidManifestNonce);
+            if !manifest.validate_signature(&who) {
+                #[cfg(not(feature = "runtime-benchmarks"))]
+                return Err(Error::<T>::InvalidManifestSignature)?;
+            }
+            if !manifest.validate_entry_signatures(&who) {
+                #[cfg(not(feature = "runtime-benchmarks"))]
+                return Err(Error::<T>::InvalidPairingProof)?;
+            }
+
+            let (manager_id, created) = Self::do_get_or_create_manager_id(&who)?;
+            if created {
+                Self::deposit_event(Event::<T>::ManagerCreated(who.clone(), manager_id));
+            }
+
+            let mut paired: u32 = 0;
+            let mut skipped: u32 = 0;
+            for entry in &manifest.entries {
+                // duplicate entries are paired by their first occurrence already
+                if Self::manager_id_for_processor(&entry.account).is_some() {
+                    skipped += 1;
+                    continue;
+                }
+                Self::do_add_processor_manager_pairing(&entry.account, manager_id)?;
+                paired += 1;
+            }
+            <ManagerCounter<T>>::insert(&who, counter);
+
+            Self::deposit_event(Event::<T>::ProcessorManifestImported(who, paired, skipped));
+
+            Ok(().into())
+        }
     }
 }
 
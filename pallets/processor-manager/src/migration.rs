@@ -0,0 +1,35 @@
+use frame_support::{
+    traits::{GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use sp_core::Get;
+
+use super::*;
+
+pub fn migrate<T: Config>() -> Weight {
+    let migrations: [(u16, &dyn Fn() -> Weight); 1] = [(1, &migrate_to_v1::<T>)];
+
+    let onchain_version = Pallet::<T>::on_chain_storage_version();
+    let mut weight: Weight = Default::default();
+    for (i, f) in migrations.into_iter() {
+        if onchain_version < StorageVersion::new(i) {
+            weight += f();
+        }
+    }
+
+    STORAGE_VERSION.put::<Pallet<T>>();
+    weight + T::DbWeight::get().writes(1)
+}
+
+/// Converts the heartbeat timestamps into [`LastSeen`] records with an unknown (zero) block.
+fn migrate_to_v1<T: Config>() -> Weight {
+    let mut count = 0u64;
+    ProcessorHeartbeat::<T>::translate_values::<u128, _>(|timestamp_ms| {
+        count += 1;
+        Some(LastSeen {
+            timestamp_ms,
+            block: 0u8.into(),
+        })
+    });
+    T::DbWeight::get().reads_writes(count + 1, count + 1)
+}
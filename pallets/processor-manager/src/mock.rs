@@ -1,8 +1,11 @@
+use acurast_common::CU32;
+use acurast_test_utils::{events_of, GenesisBuilder};
 use frame_support::traits::tokens::{Fortitude, Precision, Preservation};
 use frame_support::{
+    parameter_types,
     sp_runtime::{
         traits::{AccountIdLookup, BlakeTwo256, ConstU128, ConstU32},
-        BuildStorage, MultiSignature,
+        MultiSignature,
     },
     traits::{
         fungible::{Inspect, Mutate},
@@ -22,23 +25,12 @@ pub struct ExtBuilder;
 
 impl ExtBuilder {
     pub fn build(self) -> sp_io::TestExternalities {
-        let mut t = frame_system::GenesisConfig::<Test>::default()
-            .build_storage()
-            .unwrap();
-
-        pallet_balances::GenesisConfig::<Test> {
-            balances: vec![
-                (alice_account_id(), INITIAL_BALANCE),
-                (bob_account_id(), INITIAL_BALANCE),
-                (processor_account_id(), INITIAL_BALANCE),
-            ],
-        }
-        .assimilate_storage(&mut t)
-        .unwrap();
-
-        let mut ext = sp_io::TestExternalities::new(t);
-        ext.execute_with(|| System::set_block_number(1));
-        ext
+        GenesisBuilder::<Test>::new()
+            .fund_all(
+                [alice_account_id(), bob_account_id(), processor_account_id()],
+                INITIAL_BALANCE,
+            )
+            .build()
     }
 }
 
@@ -139,12 +131,17 @@ impl Config for Test {
     type ManagerIdProvider = AcurastManagerIdProvider;
     type ProcessorAssetRecovery = AcurastProcessorAssetRecovery;
     type MaxPairingUpdates = ConstU32<5>;
+    type MaxManifestSize = CU32<8>;
     type MaxProcessorsInSetUpdateInfo = ConstU32<100>;
     type Counter = u64;
     type PairingProofExpirationTime = ConstU128<600000>;
     type UnixTime = pallet_timestamp::Pallet<Test>;
     type Advertisement = ();
     type AdvertisementHandler = ();
+    type AttestationProvider = AttestationProvider;
+    type ProcessorMatchesProvider = MatchesProvider;
+    type UnpairCooldown = ConstU128<600000>;
+    type HeartbeatInterval = ConstU128<300000>;
     type WeightInfo = weights::WeightInfo<Self>;
 
     #[cfg(feature = "runtime-benchmarks")]
@@ -216,13 +213,31 @@ impl ProcessorAssetRecovery<Test> for AcurastProcessorAssetRecovery {
     }
 }
 
-pub fn events() -> Vec<RuntimeEvent> {
-    let evt = System::events()
-        .into_iter()
-        .map(|evt| evt.event)
-        .collect::<Vec<_>>();
+parameter_types! {
+    /// The processors with an attestation as tuples of account id and the end of the attestation's validity in milliseconds.
+    pub static AttestedProcessors: Vec<(AccountId, u64)> = vec![];
+}
+
+pub struct AttestationProvider;
+impl ProcessorAttestationProvider<Test> for AttestationProvider {
+    fn is_attested(processor: &<Test as frame_system::Config>::AccountId) -> bool {
+        AttestedProcessors::get()
+            .iter()
+            .any(|(account, not_after)| account == processor && Timestamp::get() < *not_after)
+    }
+}
+
+parameter_types! {
+    pub static ProcessorsWithMatches: Vec<AccountId> = vec![];
+}
 
-    System::reset_events();
+pub struct MatchesProvider;
+impl ProcessorMatchesProvider<Test> for MatchesProvider {
+    fn has_active_matches(processor: &<Test as frame_system::Config>::AccountId) -> bool {
+        ProcessorsWithMatches::get().contains(processor)
+    }
+}
 
-    evt
+pub fn events() -> Vec<RuntimeEvent> {
+    events_of!(Test)
 }
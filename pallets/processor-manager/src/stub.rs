@@ -108,3 +108,41 @@ pub fn generate_signature(
     .concat();
     signer.sign(&message).into()
 }
+
+#[cfg(feature = "std")]
+pub fn generate_manifest_signature(
+    manager: &sr25519::Pair,
+    manager_account: &AccountId,
+    nonce: u64,
+    expiry: u128,
+    accounts: &[AccountId],
+) -> MultiSignature {
+    let message = [
+        b"<Bytes>".to_vec(),
+        manager_account.encode(),
+        nonce.encode(),
+        expiry.encode(),
+        sp_core::blake2_256(&accounts.encode()).to_vec(),
+        b"</Bytes>".to_vec(),
+    ]
+    .concat();
+    manager.sign(&message).into()
+}
+
+#[cfg(feature = "std")]
+pub fn generate_manifest_entry_signature(
+    processor: &sr25519::Pair,
+    manager_account: &AccountId,
+    nonce: u64,
+    index: u32,
+) -> MultiSignature {
+    let message = [
+        b"<Bytes>".to_vec(),
+        manager_account.encode(),
+        nonce.encode(),
+        index.encode(),
+        b"</Bytes>".to_vec(),
+    ]
+    .concat();
+    processor.sign(&message).into()
+}
@@ -1,13 +1,19 @@
 #![cfg(test)]
 
 use crate::{
-    mock::*, stub::*, BinaryLocation, Error, Event, ProcessorPairingFor, ProcessorPairingUpdateFor,
-    UpdateInfo, Version,
+    mock::*, stub::*, BinaryLocation, Error, Event, LastSeen, ProcessorHeartbeat,
+    ProcessorManifestEntry, ProcessorManifestFor, ProcessorPairingFor, ProcessorPairingUpdateFor,
+    UpdateInfo, Version, STORAGE_VERSION,
 };
-use acurast_common::ListUpdateOperation;
+use acurast_common::{AcurastLimits, ListUpdateOperation};
 use frame_support::error::BadOrigin;
 use frame_support::sp_runtime::DispatchError;
-use frame_support::{assert_err, assert_ok, traits::fungible::Inspect};
+use frame_support::{
+    assert_err, assert_ok,
+    storage::unhashed,
+    traits::{fungible::Inspect, GetStorageVersion, Hooks, StorageVersion},
+};
+use sp_core::sr25519;
 
 fn paired_manager_processor() -> (AccountId, AccountId) {
     let (signer, manager_account) = generate_pair_account();
@@ -428,6 +434,13 @@ fn test_heartbeat_success() {
         )));
 
         assert!(AcurastProcessorManager::processor_last_seen(&processor_account).is_some());
+        assert_eq!(
+            Some(LastSeen {
+                timestamp_ms: 1657363915010,
+                block: System::block_number(),
+            }),
+            AcurastProcessorManager::processor_last_seen_record(&processor_account)
+        );
 
         let last_events = events();
         assert_eq!(
@@ -733,3 +746,614 @@ fn set_processor_update_info_failure_4() {
         );
     });
 }
+
+#[test]
+fn test_pair_with_manager_requiring_attestation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (signer, manager_account) = generate_pair_account();
+        let (_, processor_account) = generate_pair_account();
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915010);
+
+        assert_ok!(
+            AcurastProcessorManager::set_require_attestation_for_pairing(
+                RuntimeOrigin::signed(manager_account.clone()),
+                true,
+            )
+        );
+        assert!(AcurastProcessorManager::require_attestation_for_pairing(1));
+        let last_events = events();
+        assert_eq!(
+            last_events[(last_events.len() - 2)..],
+            vec![
+                RuntimeEvent::AcurastProcessorManager(Event::ManagerCreated(
+                    manager_account.clone(),
+                    1
+                )),
+                RuntimeEvent::AcurastProcessorManager(Event::RequireAttestationForPairingSet(
+                    manager_account.clone(),
+                    true
+                )),
+            ]
+        );
+
+        let timestamp = 1657363915002u128;
+        let signature = generate_signature(&signer, &manager_account, timestamp, 1);
+        let update = ProcessorPairingFor::<Test>::new_with_proof(
+            manager_account.clone(),
+            timestamp,
+            signature,
+        );
+        assert_err!(
+            AcurastProcessorManager::pair_with_manager(
+                RuntimeOrigin::signed(processor_account.clone()),
+                update.clone(),
+            ),
+            Error::<Test>::ProcessorNotAttested
+        );
+
+        AttestedProcessors::set(vec![(processor_account.clone(), 1657363925010)]);
+        assert_ok!(AcurastProcessorManager::pair_with_manager(
+            RuntimeOrigin::signed(processor_account.clone()),
+            update.clone(),
+        ));
+        assert_eq!(
+            Some(1),
+            AcurastProcessorManager::manager_id_for_processor(&processor_account)
+        );
+    });
+}
+
+#[test]
+fn test_update_processor_pairings_requiring_attestation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (signer, processor_account) = generate_pair_account();
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915010);
+        let timestamp = 1657363915002u128;
+        let signature = generate_signature(&signer, &alice_account_id(), timestamp, 1);
+        let updates = vec![ProcessorPairingUpdateFor::<Test> {
+            operation: ListUpdateOperation::Add,
+            item: ProcessorPairingFor::<Test>::new_with_proof(
+                processor_account.clone(),
+                timestamp,
+                signature,
+            ),
+        }];
+
+        assert_ok!(
+            AcurastProcessorManager::set_require_attestation_for_pairing(
+                RuntimeOrigin::signed(alice_account_id()),
+                true,
+            )
+        );
+        assert_err!(
+            AcurastProcessorManager::update_processor_pairings(
+                RuntimeOrigin::signed(alice_account_id()),
+                updates.clone().try_into().unwrap(),
+            ),
+            Error::<Test>::ProcessorNotAttested
+        );
+
+        // disabling the requirement allows pairing processors without attestation
+        assert_ok!(
+            AcurastProcessorManager::set_require_attestation_for_pairing(
+                RuntimeOrigin::signed(alice_account_id()),
+                false,
+            )
+        );
+        assert!(!AcurastProcessorManager::require_attestation_for_pairing(1));
+        assert_ok!(AcurastProcessorManager::update_processor_pairings(
+            RuntimeOrigin::signed(alice_account_id()),
+            updates.clone().try_into().unwrap(),
+        ));
+        assert_eq!(
+            Some(1),
+            AcurastProcessorManager::manager_id_for_processor(&processor_account)
+        );
+    });
+}
+
+#[test]
+fn test_update_processor_pairings_with_expired_attestation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (signer, processor_account) = generate_pair_account();
+        let _ = Timestamp::set(RuntimeOrigin::none(), 1657363915010);
+        let timestamp = 1657363915002u128;
+        let signature = generate_signature(&signer, &alice_account_id(), timestamp, 1);
+        let updates = vec![ProcessorPairingUpdateFor::<Test> {
+            operation: ListUpdateOperation::Add,
+            item: ProcessorPairingFor::<Test>::new_with_proof(
+                processor_account.clone(),
+                timestamp,
+                signature,
+            ),
+        }];
+
+        assert_ok!(
+            AcurastProcessorManager::set_require_attestation_for_pairing(
+                RuntimeOrigin::signed(alice_account_id()),
+                true,
+            )
+        );
+        // attestation expired before now
+        AttestedProcessors::set(vec![(processor_account.clone(), 1657363915000)]);
+        assert_err!(
+            AcurastProcessorManager::update_processor_pairings(
+                RuntimeOrigin::signed(alice_account_id()),
+                updates.clone().try_into().unwrap(),
+            ),
+            Error::<Test>::ProcessorNotAttested
+        );
+        assert_eq!(
+            None,
+            AcurastProcessorManager::manager_id_for_processor(&processor_account)
+        );
+    });
+}
+
+#[test]
+fn test_heartbeat_fallback_before_migration() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (_, processor_account) = paired_manager_processor();
+        StorageVersion::new(0).put::<AcurastProcessorManager>();
+        // a heartbeat stored before the migration to `LastSeen`
+        unhashed::put(
+            &ProcessorHeartbeat::<Test>::hashed_key_for(&processor_account),
+            &1657363915000u128,
+        );
+
+        assert_eq!(
+            Some(1657363915000),
+            AcurastProcessorManager::processor_last_seen(&processor_account)
+        );
+        assert_eq!(
+            Some(LastSeen {
+                timestamp_ms: 1657363915000,
+                block: 0,
+            }),
+            AcurastProcessorManager::processor_last_seen_record(&processor_account)
+        );
+
+        // heartbeats sent before the migration ran are stored in the new format and still readable
+        assert_ok!(AcurastProcessorManager::heartbeat(RuntimeOrigin::signed(
+            processor_account.clone()
+        )));
+        assert_eq!(
+            Some(LastSeen {
+                timestamp_ms: 1657363915010,
+                block: System::block_number(),
+            }),
+            AcurastProcessorManager::processor_last_seen_record(&processor_account)
+        );
+    });
+}
+
+#[test]
+fn test_heartbeat_migration() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (_, processor_account) = paired_manager_processor();
+        StorageVersion::new(0).put::<AcurastProcessorManager>();
+        unhashed::put(
+            &ProcessorHeartbeat::<Test>::hashed_key_for(&processor_account),
+            &1657363915000u128,
+        );
+
+        AcurastProcessorManager::on_runtime_upgrade();
+
+        assert_eq!(
+            STORAGE_VERSION,
+            AcurastProcessorManager::on_chain_storage_version()
+        );
+        assert_eq!(
+            Some(LastSeen {
+                timestamp_ms: 1657363915000,
+                block: 0,
+            }),
+            ProcessorHeartbeat::<Test>::get(&processor_account)
+        );
+        assert_eq!(
+            Some(1657363915000),
+            AcurastProcessorManager::processor_last_seen(&processor_account)
+        );
+
+        // once migrated, entries are only decoded as `LastSeen`
+        unhashed::put(
+            &ProcessorHeartbeat::<Test>::hashed_key_for(&processor_account),
+            &1657363915000u128,
+        );
+        assert_eq!(
+            None,
+            AcurastProcessorManager::processor_last_seen(&processor_account)
+        );
+    });
+}
+
+#[test]
+fn test_extend_limits() {
+    let mut limits = AcurastLimits::default();
+    AcurastProcessorManager::extend_limits(&mut limits);
+    assert_eq!(
+        limits,
+        AcurastLimits {
+            pairing_proof_expiration_time: Some(600000),
+            max_pairing_updates: Some(5),
+            heartbeat_interval: Some(300000),
+            unpair_cooldown: Some(600000),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_unpair_with_active_matches() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (_, unpaired_account) = generate_pair_account();
+        assert_err!(
+            AcurastProcessorManager::unpair(RuntimeOrigin::signed(unpaired_account)),
+            Error::<Test>::ProcessorHasNoManager,
+        );
+
+        let (_, processor_account) = paired_manager_processor();
+        ProcessorsWithMatches::set(vec![processor_account.clone()]);
+        assert_err!(
+            AcurastProcessorManager::unpair(RuntimeOrigin::signed(processor_account.clone())),
+            Error::<Test>::ProcessorHasActiveMatches,
+        );
+        assert_eq!(
+            Some(1),
+            AcurastProcessorManager::manager_id_for_processor(&processor_account)
+        );
+
+        ProcessorsWithMatches::set(vec![]);
+        assert_ok!(AcurastProcessorManager::unpair(RuntimeOrigin::signed(
+            processor_account.clone()
+        )));
+        assert_eq!(
+            None,
+            AcurastProcessorManager::manager_id_for_processor(&processor_account)
+        );
+        assert!(AcurastProcessorManager::managed_processors(1, &processor_account).is_none());
+        assert_eq!(
+            events().last(),
+            Some(RuntimeEvent::AcurastProcessorManager(
+                Event::ProcessorUnpaired(processor_account, 1)
+            ))
+            .as_ref()
+        );
+    });
+}
+
+#[test]
+fn test_unpair_after_cooldown_and_pair_with_new_manager() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (_, processor_account) = paired_manager_processor();
+        assert_ok!(AcurastProcessorManager::heartbeat(RuntimeOrigin::signed(
+            processor_account.clone()
+        )));
+
+        let _ = Timestamp::set_timestamp(1657363915010 + 599_999);
+        assert_err!(
+            AcurastProcessorManager::unpair(RuntimeOrigin::signed(processor_account.clone())),
+            Error::<Test>::UnpairCooldownNotElapsed,
+        );
+
+        let _ = Timestamp::set_timestamp(1657363915010 + 600_000);
+        assert_ok!(AcurastProcessorManager::unpair(RuntimeOrigin::signed(
+            processor_account.clone()
+        )));
+
+        let (signer, new_manager_account) = generate_pair_account();
+        let timestamp = 1657364515002u128;
+        let signature = generate_signature(&signer, &new_manager_account, timestamp, 1);
+        let update = ProcessorPairingFor::<Test>::new_with_proof(
+            new_manager_account.clone(),
+            timestamp,
+            signature,
+        );
+        assert_ok!(AcurastProcessorManager::pair_with_manager(
+            RuntimeOrigin::signed(processor_account.clone()),
+            update,
+        ));
+        assert_eq!(
+            Some(2),
+            AcurastProcessorManager::manager_id_for_processor(&processor_account)
+        );
+        assert_eq!(
+            Some(new_manager_account),
+            AcurastProcessorManager::manager_for_processor(&processor_account)
+        );
+        assert!(AcurastProcessorManager::managed_processors(1, &processor_account).is_none());
+    });
+}
+
+const MANIFEST_NOW: u64 = 1657363915010;
+
+/// Builds a manifest of `processors` signed by the manager and each processor for the manager's next counter value.
+fn signed_manifest(
+    manager: &sr25519::Pair,
+    manager_account: &AccountId,
+    processors: &[(sr25519::Pair, AccountId)],
+    expiry: u128,
+) -> ProcessorManifestFor<Test> {
+    let nonce = AcurastProcessorManager::counter_for_manager(manager_account).unwrap_or(0) + 1;
+    let accounts = processors
+        .iter()
+        .map(|(_, account)| account.clone())
+        .collect::<Vec<_>>();
+    ProcessorManifestFor::<Test> {
+        nonce,
+        expiry,
+        entries: processors
+            .iter()
+            .enumerate()
+            .map(|(index, (pair, account))| ProcessorManifestEntry {
+                account: account.clone(),
+                signature: generate_manifest_entry_signature(
+                    pair,
+                    manager_account,
+                    nonce,
+                    index as u32,
+                ),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+        signature: generate_manifest_signature(manager, manager_account, nonce, expiry, &accounts),
+    }
+}
+
+#[test]
+fn test_import_processor_manifest() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set_timestamp(MANIFEST_NOW);
+        let (manager, manager_account) = generate_pair_account();
+        let processors = (0..3).map(|_| generate_pair_account()).collect::<Vec<_>>();
+        let manifest = signed_manifest(
+            &manager,
+            &manager_account,
+            &processors,
+            MANIFEST_NOW as u128 + 600_000,
+        );
+
+        assert_ok!(AcurastProcessorManager::import_processor_manifest(
+            RuntimeOrigin::signed(manager_account.clone()),
+            manifest.clone(),
+        ));
+        for (_, processor) in &processors {
+            assert_eq!(
+                Some(manager_account.clone()),
+                AcurastProcessorManager::manager_for_processor(processor)
+            );
+        }
+        assert_eq!(
+            Some(1),
+            AcurastProcessorManager::counter_for_manager(&manager_account)
+        );
+        let last_events = events();
+        assert_eq!(
+            last_events[(last_events.len() - 2)..],
+            vec![
+                RuntimeEvent::AcurastProcessorManager(Event::ManagerCreated(
+                    manager_account.clone(),
+                    1
+                )),
+                RuntimeEvent::AcurastProcessorManager(Event::ProcessorManifestImported(
+                    manager_account.clone(),
+                    3,
+                    0
+                )),
+            ]
+        );
+
+        // the manifest cannot be replayed since it consumed its nonce
+        assert_err!(
+            AcurastProcessorManager::import_processor_manifest(
+                RuntimeOrigin::signed(manager_account.clone()),
+                manifest,
+            ),
+            Error::<Test>::InvalidManifestNonce
+        );
+    });
+}
+
+#[test]
+fn test_import_processor_manifest_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set_timestamp(MANIFEST_NOW);
+        let (manager, manager_account) = generate_pair_account();
+        let processors = vec![generate_pair_account()];
+        let manifest = signed_manifest(
+            &manager,
+            &manager_account,
+            &processors,
+            MANIFEST_NOW as u128,
+        );
+
+        assert_err!(
+            AcurastProcessorManager::import_processor_manifest(
+                RuntimeOrigin::signed(manager_account.clone()),
+                manifest,
+            ),
+            Error::<Test>::ManifestExpired
+        );
+        assert_eq!(
+            None,
+            AcurastProcessorManager::manager_id_for_processor(&processors[0].1)
+        );
+        assert_eq!(
+            None,
+            AcurastProcessorManager::counter_for_manager(&manager_account)
+        );
+    });
+}
+
+#[test]
+fn test_import_processor_manifest_tampered_entry() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set_timestamp(MANIFEST_NOW);
+        let (manager, manager_account) = generate_pair_account();
+        let processors = (0..2).map(|_| generate_pair_account()).collect::<Vec<_>>();
+        let manifest = signed_manifest(
+            &manager,
+            &manager_account,
+            &processors,
+            MANIFEST_NOW as u128 + 600_000,
+        );
+
+        // an entry replaced after the manager signed the manifest
+        let (_, intruder) = generate_pair_account();
+        let mut tampered = manifest.clone();
+        tampered.entries[1].account = intruder.clone();
+        assert_err!(
+            AcurastProcessorManager::import_processor_manifest(
+                RuntimeOrigin::signed(manager_account.clone()),
+                tampered,
+            ),
+            Error::<Test>::InvalidManifestSignature
+        );
+
+        // the device signatures are bound to their entry's index
+        let mut tampered = manifest.clone();
+        let signature = tampered.entries[0].signature.clone();
+        tampered.entries[0].signature = tampered.entries[1].signature.clone();
+        tampered.entries[1].signature = signature;
+        assert_err!(
+            AcurastProcessorManager::import_processor_manifest(
+                RuntimeOrigin::signed(manager_account.clone()),
+                tampered,
+            ),
+            Error::<Test>::InvalidPairingProof
+        );
+
+        for account in [intruder, processors[0].1.clone(), processors[1].1.clone()] {
+            assert_eq!(
+                None,
+                AcurastProcessorManager::manager_id_for_processor(&account)
+            );
+        }
+    });
+}
+
+#[test]
+fn test_import_processor_manifest_skips_duplicate_and_paired_entries() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set_timestamp(MANIFEST_NOW);
+        let paired = generate_pair_account();
+        let timestamp = 1657363915002u128;
+        let signature = generate_signature(&paired.0, &alice_account_id(), timestamp, 1);
+        assert_ok!(AcurastProcessorManager::update_processor_pairings(
+            RuntimeOrigin::signed(alice_account_id()),
+            vec![ProcessorPairingUpdateFor::<Test> {
+                operation: ListUpdateOperation::Add,
+                item: ProcessorPairingFor::<Test>::new_with_proof(
+                    paired.1.clone(),
+                    timestamp,
+                    signature,
+                ),
+            }]
+            .try_into()
+            .unwrap(),
+        ));
+
+        let (manager, manager_account) = generate_pair_account();
+        let fresh = generate_pair_account();
+        let other = generate_pair_account();
+        let manifest = signed_manifest(
+            &manager,
+            &manager_account,
+            &[fresh.clone(), paired.clone(), fresh.clone(), other.clone()],
+            MANIFEST_NOW as u128 + 600_000,
+        );
+        let _ = events();
+
+        assert_ok!(AcurastProcessorManager::import_processor_manifest(
+            RuntimeOrigin::signed(manager_account.clone()),
+            manifest,
+        ));
+        assert_eq!(
+            events().last(),
+            Some(&RuntimeEvent::AcurastProcessorManager(
+                Event::ProcessorManifestImported(manager_account.clone(), 2, 2)
+            ))
+        );
+        for processor in [&fresh.1, &other.1] {
+            assert_eq!(
+                Some(manager_account.clone()),
+                AcurastProcessorManager::manager_for_processor(processor)
+            );
+        }
+        assert_eq!(
+            Some(alice_account_id()),
+            AcurastProcessorManager::manager_for_processor(&paired.1)
+        );
+    });
+}
+
+#[test]
+fn test_pairing_after_import_processor_manifest_uses_next_counter() {
+    ExtBuilder::default().build().execute_with(|| {
+        let _ = Timestamp::set_timestamp(MANIFEST_NOW);
+        let (manager, manager_account) = generate_pair_account();
+        let manifest = signed_manifest(
+            &manager,
+            &manager_account,
+            &[generate_pair_account(), generate_pair_account()],
+            MANIFEST_NOW as u128 + 600_000,
+        );
+        assert_ok!(AcurastProcessorManager::import_processor_manifest(
+            RuntimeOrigin::signed(manager_account.clone()),
+            manifest,
+        ));
+
+        // the manifest consumed a single counter value regardless of its number of entries
+        let (signer, processor_account) = generate_pair_account();
+        let timestamp = 1657363915002u128;
+        let pairing = |counter| {
+            ProcessorPairingFor::<Test>::new_with_proof(
+                manager_account.clone(),
+                timestamp,
+                generate_signature(&manager, &manager_account, timestamp, counter),
+            )
+        };
+        assert_err!(
+            AcurastProcessorManager::pair_with_manager(
+                RuntimeOrigin::signed(processor_account.clone()),
+                pairing(1),
+            ),
+            Error::<Test>::InvalidPairingProof
+        );
+        assert_ok!(AcurastProcessorManager::pair_with_manager(
+            RuntimeOrigin::signed(processor_account.clone()),
+            pairing(2),
+        ));
+        assert_eq!(
+            Some(2),
+            AcurastProcessorManager::counter_for_manager(&manager_account)
+        );
+
+        // a subsequent manifest has to use the counter value following the pairing
+        let manifest = signed_manifest(
+            &manager,
+            &manager_account,
+            &[(signer, processor_account)],
+            MANIFEST_NOW as u128 + 600_000,
+        );
+        assert_eq!(3, manifest.nonce);
+        let mut stale = manifest.clone();
+        stale.nonce = 2;
+        assert_err!(
+            AcurastProcessorManager::import_processor_manifest(
+                RuntimeOrigin::signed(manager_account.clone()),
+                stale,
+            ),
+            Error::<Test>::InvalidManifestNonce
+        );
+        assert_ok!(AcurastProcessorManager::import_processor_manifest(
+            RuntimeOrigin::signed(manager_account.clone()),
+            manifest,
+        ));
+        assert_eq!(
+            Some(3),
+            AcurastProcessorManager::counter_for_manager(&manager_account)
+        );
+    });
+}
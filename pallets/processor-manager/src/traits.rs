@@ -29,6 +29,32 @@ impl<T: Config> AdvertisementHandler<T> for () {
     }
 }
 
+/// Provides the attestation status of processors, implemented by `pallet_acurast_marketplace` based on
+/// `pallet_acurast::utils::ensure_source_verified`.
+pub trait ProcessorAttestationProvider<T: Config> {
+    /// Returns true if the processor has a stored attestation that is currently valid.
+    fn is_attested(processor: &T::AccountId) -> bool;
+}
+
+/// Considers every processor attested, for runtimes without attestations.
+impl<T: Config> ProcessorAttestationProvider<T> for () {
+    fn is_attested(_processor: &T::AccountId) -> bool {
+        true
+    }
+}
+
+/// Provides whether processors are currently matched with jobs, e.g. implemented by `pallet_acurast_marketplace`.
+pub trait ProcessorMatchesProvider<T: Config> {
+    /// Returns true if the processor has any active match.
+    fn has_active_matches(processor: &T::AccountId) -> bool;
+}
+
+impl<T: Config> ProcessorMatchesProvider<T> for () {
+    fn has_active_matches(_processor: &T::AccountId) -> bool {
+        false
+    }
+}
+
 /// Weight functions needed for pallet_acurast_processor_manager.
 pub trait WeightInfo {
     fn update_processor_pairings(x: u32) -> Weight;
@@ -39,4 +65,7 @@ pub trait WeightInfo {
     fn advertise_for() -> Weight;
     fn update_binary_hash() -> Weight;
     fn set_processor_update_info(x: u32) -> Weight;
+    fn set_require_attestation_for_pairing() -> Weight;
+    fn unpair() -> Weight;
+    fn import_processor_manifest(x: u32) -> Weight;
 }
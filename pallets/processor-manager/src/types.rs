@@ -1,4 +1,4 @@
-use acurast_common::ListUpdate;
+use acurast_common::{ListUpdate, ParameterBound};
 use core::fmt::Debug;
 use frame_support::{
     pallet_prelude::*,
@@ -8,6 +8,7 @@ use frame_support::{
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_core::H256;
+use sp_std::prelude::*;
 
 use crate::Config;
 
@@ -95,6 +96,89 @@ where
 pub type ProcessorPairingUpdate<AccountId, Signature> =
     ListUpdate<ProcessorPairing<AccountId, Signature>>;
 
+/// A processor's entry in a [`ProcessorManifest`].
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+pub struct ProcessorManifestEntry<AccountId, Signature> {
+    pub account: AccountId,
+    /// The processor's signature over the manager, the manifest's nonce and the entry's index in the manifest.
+    pub signature: Signature,
+}
+
+impl<AccountId, Signature> ProcessorManifestEntry<AccountId, Signature>
+where
+    AccountId: IsType<<<Signature as Verify>::Signer as IdentifyAccount>::AccountId>,
+    AccountId: Parameter + Member + MaybeSerializeDeserialize + MaybeDisplay + Ord,
+    Signature: Parameter + Member + Verify,
+{
+    pub fn validate_signature<Counter: Encode>(
+        &self,
+        manager: &AccountId,
+        nonce: &Counter,
+        index: u32,
+    ) -> bool {
+        let message = [
+            b"<Bytes>".to_vec(),
+            manager.encode(),
+            nonce.encode(),
+            index.encode(),
+            b"</Bytes>".to_vec(),
+        ]
+        .concat();
+        self.signature
+            .verify(message.as_ref(), &self.account.clone().into())
+    }
+}
+
+/// A list of processors to pair with a manager in a single call, signed by the manager as a whole.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
+#[scale_info(skip_type_params(MaxManifestSize))]
+pub struct ProcessorManifest<AccountId, Signature, Counter, MaxManifestSize: ParameterBound> {
+    /// The next value of the manager's pairing counter, consumed by the manifest as a whole.
+    pub nonce: Counter,
+    /// The time in milliseconds from which on the manifest can no longer be imported.
+    pub expiry: u128,
+    pub entries: BoundedVec<ProcessorManifestEntry<AccountId, Signature>, MaxManifestSize>,
+    /// The manager's signature over the nonce, the expiry and the hash of the entries' accounts.
+    pub signature: Signature,
+}
+
+impl<AccountId, Signature, Counter, MaxManifestSize>
+    ProcessorManifest<AccountId, Signature, Counter, MaxManifestSize>
+where
+    AccountId: IsType<<<Signature as Verify>::Signer as IdentifyAccount>::AccountId>,
+    AccountId: Parameter + Member + MaybeSerializeDeserialize + MaybeDisplay + Ord,
+    Signature: Parameter + Member + Verify,
+    Counter: Encode,
+    MaxManifestSize: ParameterBound,
+{
+    pub fn validate_expiry<T: Config>(&self) -> bool {
+        T::UnixTime::now().as_millis() < self.expiry
+    }
+
+    pub fn validate_signature(&self, manager: &AccountId) -> bool {
+        let accounts: Vec<&AccountId> = self.entries.iter().map(|entry| &entry.account).collect();
+        let message = [
+            b"<Bytes>".to_vec(),
+            manager.encode(),
+            self.nonce.encode(),
+            self.expiry.encode(),
+            frame_support::sp_io::hashing::blake2_256(&accounts.encode()).to_vec(),
+            b"</Bytes>".to_vec(),
+        ]
+        .concat();
+        self.signature
+            .verify(message.as_ref(), &manager.clone().into())
+    }
+
+    /// Validates the signatures of all entries, see [`ProcessorManifestEntry::validate_signature`].
+    pub fn validate_entry_signatures(&self, manager: &AccountId) -> bool {
+        self.entries
+            .iter()
+            .enumerate()
+            .all(|(index, entry)| entry.validate_signature(manager, &self.nonce, index as u32))
+    }
+}
+
 #[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
@@ -105,6 +189,17 @@ pub struct Version {
     pub build_number: u32,
 }
 
+/// The record of a processor's latest heartbeat.
+#[derive(RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq)]
+pub struct LastSeen<BlockNumber> {
+    /// The timestamp of the heartbeat in milliseconds.
+    pub timestamp_ms: u128,
+    /// The block of the heartbeat, zero for heartbeats migrated from the timestamp only storage.
+    pub block: BlockNumber,
+}
+
+pub type LastSeenFor<T> = LastSeen<frame_system::pallet_prelude::BlockNumberFor<T>>;
+
 pub type BinaryHash = H256;
 pub(crate) const MAX_LOCATION_LENGTH: u32 = 200;
 pub type BinaryLocation = BoundedVec<u8, ConstU32<MAX_LOCATION_LENGTH>>;
@@ -118,6 +118,11 @@ benchmarks_instance_pallet! {
         assert!(s_after >= s_before);
     }
 
+    set_stall_credit {
+    }: _(RawOrigin::Root, 10u32.into())
+    verify {
+        assert_eq!(Pallet::<T, I>::stall_credit(), 10u32.into());
+    }
 
     impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Test);
 }
@@ -1,9 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub(crate) use pallet::STORAGE_VERSION;
 pub use pallet::*;
+pub use payments::*;
 pub use traits::*;
 pub use types::*;
 
+mod migration;
+mod payments;
 mod traits;
 mod types;
 
@@ -22,18 +26,21 @@ pub mod pallet {
     use core::ops::Div;
 
     use codec::MaxEncodedLen;
-    use frame_support::sp_runtime::Saturating;
+    use frame_support::sp_runtime::{SaturatedConversion, Saturating};
     use frame_support::traits::tokens::Balance;
     use frame_support::{
-        dispatch::DispatchResultWithPostInfo, pallet_prelude::*, traits::Get, Parameter,
+        dispatch::DispatchResultWithPostInfo,
+        pallet_prelude::*,
+        traits::{Get, UnixTime},
+        Parameter,
     };
     use frame_system::{
-        ensure_signed,
+        ensure_root, ensure_signed,
         pallet_prelude::{BlockNumberFor, OriginFor},
     };
     use sp_arithmetic::traits::EnsureAddAssign;
     use sp_arithmetic::Perbill;
-    use sp_runtime::traits::{CheckedAdd, CheckedMul, CheckedSub};
+    use sp_runtime::traits::{CheckedAdd, CheckedMul, CheckedSub, Zero};
     use sp_std::prelude::*;
 
     use crate::*;
@@ -48,11 +55,25 @@ pub mod pallet {
         ///
         /// A valid exit call that claims the full reward has to occur within `[cooldown end, now + DivestTolerance]`.
         /// Since the `now` timestmap is behind the current time up to the block time, the actual tolerance is sometimes higher than the configured.
+        ///
+        /// The tolerance is extended by the [`StallCredit`] accumulated during a vester's cooldown.
         type DivestTolerance: Get<<Self as Config<I>>::BlockNumber>;
+        /// The expected time between two blocks in milliseconds, used to detect stalls of the chain.
+        ///
+        /// Every full multiple of this block time that passes between two blocks without a block being produced adds one block to the [`StallCredit`].
+        #[pallet::constant]
+        type ExpectedBlockTime: Get<u64>;
+        /// Timestamp provider used to detect stalls of the chain.
+        type UnixTime: UnixTime;
         /// The maximum locking period in number of blocks. Vesting powers are linearly raised with [`Vesting`]`::locking_period / MaximumLockingPeriod`.
         #[pallet::constant]
         type MaximumLockingPeriod: Get<<Self as Config<I>>::BlockNumber>;
+        /// The balance of stakes, also used for the pallet's internal reward accounting.
         type Balance: Parameter + IsType<u128> + Div + Balance + MaybeSerializeDeserialize;
+        /// The balance of the reward asset distributed with [`Pallet::distribute_reward`] and paid out with [`VestingBalance::pay_accrued`] and [`VestingBalance::pay_kicker`].
+        ///
+        /// Equals [`Config::Balance`] for instances rewarding in the staked currency.
+        type RewardBalance: Parameter + IsType<u128> + Balance + MaybeSerializeDeserialize;
         #[pallet::constant]
         type BalanceUnit: Get<<Self as Config<I>>::Balance>;
         type BlockNumber: Parameter
@@ -64,7 +85,7 @@ pub mod pallet {
             + Into<u128>
             + IsType<BlockNumberFor<Self>>
             + MaybeSerializeDeserialize;
-        type VestingBalance: VestingBalance<Self::AccountId, Self::Balance>;
+        type VestingBalance: VestingBalance<Self::AccountId, Self::Balance, Self::RewardBalance>;
         /// Weight Info for extrinsics.
         type WeightInfo: WeightInfo;
     }
@@ -107,9 +128,108 @@ pub mod pallet {
     pub(super) type VesterStates<T: Config<I>, I: 'static = ()> =
         StorageMap<_, Blake2_128Concat, T::AccountId, VesterStateFor<T, I>>;
 
+    /// The timestamp of the previous block in milliseconds, as observed in [`Hooks::on_initialize`].
+    #[pallet::storage]
+    #[pallet::getter(fn last_block_timestamp)]
+    pub(super) type LastBlockTimestamp<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u64, ValueQuery>;
+
+    /// The largest observed time between two blocks in milliseconds.
+    #[pallet::storage]
+    #[pallet::getter(fn largest_block_time_gap)]
+    pub(super) type LargestBlockTimeGap<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, u64, ValueQuery>;
+
+    /// Counter of the blocks that were not produced because the chain was stalled, accumulated over the chain's lifetime.
+    ///
+    /// The credit accumulated during a vester's cooldown, i.e. the difference to the snapshot in [`CooldownStallCredit`],
+    /// extends the vester's divest tolerance, clamped to the stalls overlapping the vester's cooldown and tolerance.
+    #[pallet::storage]
+    #[pallet::getter(fn stall_credit)]
+    pub(super) type StallCredit<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// The most recent detected stalls as `(detected_at, missed_blocks)`, oldest first.
+    ///
+    /// Used to only count the stalls overlapping a vester's cooldown and tolerance in [`Pallet::divest_tolerance`].
+    /// Credit of stalls no longer in the history, as well as credit granted by [`Pallet::set_stall_credit`], is counted for
+    /// every vester in cooldown.
+    #[pallet::storage]
+    #[pallet::getter(fn stall_history)]
+    pub(super) type StallHistory<T: Config<I>, I: 'static = ()> = StorageValue<
+        _,
+        BoundedVec<(BlockNumberFor<T>, BlockNumberFor<T>), ConstU32<MAX_STALL_HISTORY>>,
+        ValueQuery,
+    >;
+
+    /// The [`StallCredit`] at the time a vester started the cooldown.
+    #[pallet::storage]
+    #[pallet::getter(fn cooldown_stall_credit)]
+    pub(super) type CooldownStallCredit<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
+
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    /// The maximum number of stalls kept in [`StallHistory`].
+    pub const MAX_STALL_HISTORY: u32 = 16;
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        /// Detects stalls of the chain by comparing the time between blocks to the [`Config::ExpectedBlockTime`].
+        ///
+        /// Since the timestamp of the current block is only set by the timestamp inherent, this observes the timestamp
+        /// of the previous block and therefore detects a stall one block after it ended.
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            let now: u64 = T::UnixTime::now().as_millis().saturated_into();
+            let last = <LastBlockTimestamp<T, I>>::get();
+            if now == last {
+                return T::DbWeight::get().reads(1);
+            }
+            <LastBlockTimestamp<T, I>>::put(now);
+
+            let expected_block_time = T::ExpectedBlockTime::get();
+            if last == 0 || expected_block_time == 0 {
+                return T::DbWeight::get().reads_writes(1, 1);
+            }
+
+            let gap = now.saturating_sub(last);
+            if gap > <LargestBlockTimeGap<T, I>>::get() {
+                <LargestBlockTimeGap<T, I>>::put(gap);
+            }
+            // the first expected block time is covered by the block that ended the stall
+            let missed_blocks = (gap / expected_block_time).saturating_sub(1);
+            if missed_blocks > 0 {
+                let missed_blocks: BlockNumberFor<T> = missed_blocks.saturated_into();
+                let stall_credit = <StallCredit<T, I>>::mutate(|credit| {
+                    credit.saturating_accrue(missed_blocks);
+                    *credit
+                });
+                <StallHistory<T, I>>::mutate(|history| {
+                    if history.is_full() {
+                        history.remove(0);
+                    }
+                    _ = history.try_push((block_number, missed_blocks));
+                });
+                Self::deposit_event(Event::<T, I>::StallDetected(gap, stall_credit));
+            }
+
+            T::DbWeight::get().reads_writes(4, 4)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            crate::migration::migrate::<T, I>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -124,7 +244,11 @@ pub mod pallet {
         /// A vester that exceeded his divest tolerance got kicked out. [vester, kicker, vester_state_before_kicked_out, reward_cut]
         KickedOut(T::AccountId, T::AccountId, VesterStateFor<T, I>),
         /// A reward got distributed. [amount]
-        RewardDistributed(T::Balance),
+        RewardDistributed(T::RewardBalance),
+        /// A stall of the chain was detected. [block_time_gap, stall_credit]
+        StallDetected(u64, BlockNumberFor<T>),
+        /// The stall credit was set by root. [stall_credit]
+        StallCreditUpdated(BlockNumberFor<T>),
     }
 
     // Errors inform users that something went wrong.
@@ -196,13 +320,15 @@ pub mod pallet {
                     Self::accrue(state)?;
 
                     state.cooldown_started = Some(<frame_system::Pallet<T>>::block_number().into());
+                    <CooldownStallCredit<T, I>>::insert(&who, <StallCredit<T, I>>::get());
 
                     // punish divest with half the power during cooldown
                     state.power /= 2u128.into();
 
                     <Pool<T, I>>::try_mutate(|pool| -> Result<(), Error<T, I>> {
                         // due to rounding we need to substract the difference and not the new power!
-                        pool.total_power
+                        pool.total_power = pool
+                            .total_power
                             .checked_sub(
                                 &state_before
                                     .power
@@ -251,7 +377,7 @@ pub mod pallet {
                     if cooldown_started
                         .checked_add(&state.locking_period)
                         .ok_or(Error::<T, I>::CalculationOverflow)?
-                        .checked_add(&<T as Config<I>>::DivestTolerance::get().into())
+                        .checked_add(&Self::divest_tolerance(&who, state)?)
                         .ok_or(Error::<T, I>::CalculationOverflow)?
                         < current_block.into()
                     {
@@ -260,13 +386,15 @@ pub mod pallet {
 
                     Self::accrue(state)?;
                     let divest_state = *state;
+                    Self::remove_from_pool(&divest_state)?;
 
                     *state_ = None;
+                    <CooldownStallCredit<T, I>>::remove(&who);
                     Ok(divest_state)
                 },
             )?;
 
-            T::VestingBalance::pay_accrued(&who, vester_state.accrued)?;
+            T::VestingBalance::pay_accrued(&who, Self::reward_amount(vester_state.accrued))?;
             T::VestingBalance::unlock_stake(&who, vester_state.stake)?;
 
             Self::deposit_event(Event::<T, I>::Divested(who, vester_state));
@@ -292,7 +420,7 @@ pub mod pallet {
                     if cooldown_started
                         .checked_add(&state.locking_period)
                         .ok_or(Error::<T, I>::CalculationOverflow)?
-                        .checked_add(&<T as Config<I>>::DivestTolerance::get().into())
+                        .checked_add(&Self::divest_tolerance(&vester, state)?)
                         .ok_or(Error::<T, I>::CalculationOverflow)?
                         >= current_block.into()
                     {
@@ -301,21 +429,43 @@ pub mod pallet {
 
                     Self::accrue(state)?;
                     let before_kicked_out_state = *state;
+                    Self::remove_from_pool(&before_kicked_out_state)?;
 
                     *state_ = None;
+                    <CooldownStallCredit<T, I>>::remove(&vester);
 
                     Ok(before_kicked_out_state)
                 },
             )?;
 
             // give accrued to kicker (or part of it)
-            T::VestingBalance::pay_kicker(&who, vester_state.accrued)?;
+            T::VestingBalance::pay_kicker(&who, Self::reward_amount(vester_state.accrued))?;
             T::VestingBalance::unlock_stake(&vester, vester_state.stake)?;
 
             Self::deposit_event(Event::<T, I>::KickedOut(vester, who, vester_state));
 
             Ok(().into())
         }
+
+        /// Sets the [`StallCredit`] after reviewing an incident, e.g. to grant credit for an outage that was not detected as a stall
+        /// or to reset credit that was accumulated wrongly.
+        ///
+        /// The credit extends the divest tolerance of vesters by the difference to the credit at their cooldown start,
+        /// so lowering it below a vester's snapshot removes any extension for that vester.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_stall_credit())]
+        pub fn set_stall_credit(
+            origin: OriginFor<T>,
+            stall_credit: BlockNumberFor<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            <StallCredit<T, I>>::put(stall_credit);
+
+            Self::deposit_event(Event::<T, I>::StallCreditUpdated(stall_credit));
+
+            Ok(().into())
+        }
     }
 
     impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -403,6 +553,7 @@ pub mod pallet {
 
                     // recalculate the power
                     let power_before = state.power;
+                    let stake_before = state.stake;
                     let power = Self::calculate_power(&vesting)?;
 
                     state.locking_period = vesting.locking_period;
@@ -411,16 +562,28 @@ pub mod pallet {
                     // record global s upper bound at time of revest
                     state.s = <Pool<T, I>>::get().s.1;
                     state.cooldown_started = None;
+                    <CooldownStallCredit<T, I>>::remove(&who);
 
                     <Pool<T, I>>::try_mutate(|pool| -> Result<(), Error<T, I>> {
-                        // due to rounding we need to substract the difference and not the new power!
-                        pool.total_power.saturating_add(
-                            // the new power is always greater than the old power, so check_sub should never fail
-                            state
-                                .power
-                                .checked_sub(&power_before)
-                                .ok_or(Error::<T, I>::CalculationOverflow)?,
-                        );
+                        // due to rounding we need to add the difference and not the new power!
+                        pool.total_power
+                            .ensure_add_assign(
+                                // the new power is always greater than the old power, so check_sub should never fail
+                                state
+                                    .power
+                                    .checked_sub(&power_before)
+                                    .ok_or(Error::<T, I>::CalculationOverflow)?,
+                            )
+                            .map_err(|_| Error::<T, I>::CalculationOverflow)?;
+                        // the new stake is never less than the old stake
+                        pool.total_stake
+                            .ensure_add_assign(
+                                state
+                                    .stake
+                                    .checked_sub(&stake_before)
+                                    .ok_or(Error::<T, I>::CalculationOverflow)?,
+                            )
+                            .map_err(|_| Error::<T, I>::CalculationOverflow)?;
                         Ok(())
                     })?;
 
@@ -429,10 +592,71 @@ pub mod pallet {
             )
         }
 
+        /// Removes the power and stake of an exiting vester from the pool totals.
+        fn remove_from_pool(state: &VesterStateFor<T, I>) -> Result<(), Error<T, I>> {
+            <Pool<T, I>>::try_mutate(|pool| -> Result<(), Error<T, I>> {
+                pool.total_power = pool
+                    .total_power
+                    .checked_sub(&state.power)
+                    .ok_or(Error::<T, I>::CalculationOverflow)?;
+                pool.total_stake = pool
+                    .total_stake
+                    .checked_sub(&state.stake)
+                    .ok_or(Error::<T, I>::CalculationOverflow)?;
+                Ok(())
+            })
+        }
+
+        /// Sums up the power and stake of all [`VesterStates`], returned along with the number of vesters.
+        fn vester_totals() -> (T::Balance, T::Balance, u64) {
+            <VesterStates<T, I>>::iter_values().fold(
+                (0u128.into(), 0u128.into(), 0u64),
+                |(power, stake, count), state| {
+                    (
+                        power.saturating_add(state.power),
+                        stake.saturating_add(state.stake),
+                        count + 1,
+                    )
+                },
+            )
+        }
+
+        /// Recomputes the pool's `total_power` and `total_stake` from all [`VesterStates`], repairing totals that drifted.
+        ///
+        /// Returns the number of vester states read.
+        pub(crate) fn recompute_pool_totals() -> u64 {
+            let (total_power, total_stake, count) = Self::vester_totals();
+            <Pool<T, I>>::mutate(|pool| {
+                pool.total_power = total_power;
+                pool.total_stake = total_stake;
+            });
+            count
+        }
+
+        /// Checks that the pool totals equal the sums over all [`VesterStates`].
+        #[cfg(any(feature = "try-runtime", test))]
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let (total_power, total_stake, _) = Self::vester_totals();
+            let pool = <Pool<T, I>>::get();
+            ensure!(
+                pool.total_power == total_power,
+                "pool total_power differs from the sum of the vesters' power"
+            );
+            ensure!(
+                pool.total_stake == total_stake,
+                "pool total_stake differs from the sum of the vesters' stake"
+            );
+            Ok(())
+        }
+
         /// Distributes a reward to the entire pool according to current power distribution.
         ///
         /// Assumes that the reward was already minted and users of this pallet ensure only minted rewards are payed out in [`VestingBalance::pay_accrued`] and [`VestingBalance::pay_kicker`].
-        pub fn distribute_reward(reward: T::Balance) -> DispatchResult {
+        ///
+        /// The `reward` is an amount of the reward asset, see [`Config::RewardBalance`].
+        pub fn distribute_reward(reward: T::RewardBalance) -> DispatchResult {
+            let reward_amount = reward;
+            let reward: T::Balance = Into::<u128>::into(reward).into();
             // s = s + reward / total_power = s + reward * MaximumLockingPeriod / total_power_numerator
 
             <Pool<T, I>>::try_mutate(|state| -> Result<(), DispatchError> {
@@ -465,11 +689,64 @@ pub mod pallet {
                 Ok(())
             })?;
 
-            Self::deposit_event(Event::<T, I>::RewardDistributed(reward));
+            Self::deposit_event(Event::<T, I>::RewardDistributed(reward_amount));
 
             Ok(().into())
         }
 
+        /// The tolerance after the cooldown of `who` ended, extended by the [`StallCredit`] accumulated since the cooldown started.
+        ///
+        /// Stalls in [`StallHistory`] only count if they overlap the vester's cooldown or the (extended) tolerance after it.
+        /// Since a stall is detected in the second block after it started (see [`Hooks::on_initialize`]), a stall detected at
+        /// block `b` lasted from block `b - 2` to `b - 1`.
+        pub fn divest_tolerance(
+            who: &T::AccountId,
+            state: &VesterStateFor<T, I>,
+        ) -> Result<<T as Config<I>>::BlockNumber, Error<T, I>> {
+            let tolerance = <T as Config<I>>::DivestTolerance::get();
+            let Some(cooldown_started) = state.cooldown_started else {
+                return Ok(tolerance);
+            };
+            let cooldown_started: BlockNumberFor<T> = cooldown_started.into();
+            let cooldown_ended = cooldown_started.saturating_add(state.locking_period.into());
+
+            // stalls detected up to the cooldown start are already contained in the snapshot
+            let history: Vec<_> = <StallHistory<T, I>>::get()
+                .into_iter()
+                .filter(|(detected_at, _)| *detected_at > cooldown_started)
+                .collect();
+            let mut stall_credit = <StallCredit<T, I>>::get()
+                .saturating_sub(<CooldownStallCredit<T, I>>::get(who).unwrap_or_default())
+                .saturating_sub(history.iter().fold(
+                    Zero::zero(),
+                    |sum: BlockNumberFor<T>, (_, missed_blocks)| sum.saturating_add(*missed_blocks),
+                ));
+            for (detected_at, missed_blocks) in history {
+                let stall_started = detected_at.saturating_sub(2u8.into());
+                let tolerance_ended = cooldown_ended
+                    .saturating_add(tolerance.into())
+                    .saturating_add(stall_credit);
+                // the stall ended before the cooldown started
+                if stall_started < cooldown_started {
+                    continue;
+                }
+                // the stall started after the tolerance ended, and so did all later ones
+                if stall_started >= tolerance_ended {
+                    break;
+                }
+                stall_credit.saturating_accrue(missed_blocks);
+            }
+
+            tolerance
+                .checked_add(&stall_credit.into())
+                .ok_or(Error::<T, I>::CalculationOverflow)
+        }
+
+        /// Converts an amount accrued in the pallet's internal accounting to the reward asset's balance.
+        fn reward_amount(accrued: T::Balance) -> T::RewardBalance {
+            Into::<u128>::into(accrued).into()
+        }
+
         fn accrue(state: &mut VesterStateFor<T, I>) -> Result<(), Error<T, I>> {
             let pool = Self::pool();
             // reward = self.data.power * (self.model.data.s - self.data.s)
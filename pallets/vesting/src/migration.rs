@@ -0,0 +1,27 @@
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+
+use super::*;
+
+pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+    let migrations: [(u16, &dyn Fn() -> Weight); 1] = [(1, &migrate_to_v1::<T, I>)];
+
+    let onchain_version = Pallet::<T, I>::on_chain_storage_version();
+    let mut weight: Weight = Default::default();
+    for (i, f) in migrations.into_iter() {
+        if onchain_version < StorageVersion::new(i) {
+            weight += f();
+        }
+    }
+
+    STORAGE_VERSION.put::<Pallet<T, I>>();
+    weight + T::DbWeight::get().writes(1)
+}
+
+/// Repairs the pool totals, which were not updated on revest, cooldown, divest and kick out before.
+fn migrate_to_v1<T: Config<I>, I: 'static>() -> Weight {
+    let count = Pallet::<T, I>::recompute_pool_totals();
+    T::DbWeight::get().reads_writes(count + 1, 1)
+}
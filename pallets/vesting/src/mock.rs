@@ -1,12 +1,14 @@
 use std::marker::PhantomData;
 
+use frame_support::traits::fungibles;
 use frame_support::{
+    instances::Instance1,
     parameter_types,
     sp_runtime::{
         traits::{AccountIdLookup, BlakeTwo256},
         BuildStorage,
     },
-    traits::Everything,
+    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, Everything},
 };
 use sp_arithmetic::Perbill;
 use sp_runtime::DispatchError;
@@ -21,10 +23,18 @@ pub struct ExtBuilder;
 
 impl ExtBuilder {
     pub fn build(self) -> sp_io::TestExternalities {
-        let t = frame_system::GenesisConfig::<Test>::default()
+        let mut t = frame_system::GenesisConfig::<Test>::default()
             .build_storage()
             .unwrap();
 
+        pallet_assets::GenesisConfig::<Test> {
+            assets: vec![(REWARD_ASSET_ID, reward_pot_account_id(), true, 1)],
+            metadata: vec![],
+            accounts: vec![(REWARD_ASSET_ID, reward_pot_account_id(), 1_000 * UNIT)],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
         let mut ext = sp_io::TestExternalities::new(t);
         ext.execute_with(|| System::set_block_number(1));
         ext
@@ -40,7 +50,11 @@ impl Default for ExtBuilder {
 frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>} = 0,
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
         AcurastVesting: crate::{Pallet, Call, Storage, Event<T>},
+        AssetVesting: crate::<Instance1>::{Pallet, Call, Storage, Event<T>},
         MockPallet: mock_pallet::{Pallet, Event<T>}
     }
 );
@@ -71,23 +85,92 @@ impl frame_system::Config for Test {
     type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ConstU32<0>;
+    type MaxFreezes = ConstU32<0>;
+}
+
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type AssetIdParameter = codec::Compact<AssetId>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+    type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type AssetDeposit = ConstU128<0>;
+    type AssetAccountDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type MetadataDepositPerByte = ConstU128<0>;
+    type ApprovalDeposit = ConstU128<0>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ();
+    type CallbackHandle = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
 parameter_types! {
     pub const DivestTolerance: BlockNumber = 2;
+    pub const ExpectedBlockTime: u64 = 12_000;
     pub const MaximumLockingPeriod: BlockNumber = 100;
     pub const BalanceUnit: u128 = UNIT;
+    pub const RewardAssetId: AssetId = REWARD_ASSET_ID;
+    pub RewardPot: AccountId = reward_pot_account_id();
 }
 
+/// The instance rewarding in the staked (native) currency.
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type DivestTolerance = DivestTolerance;
+    type ExpectedBlockTime = ExpectedBlockTime;
+    type UnixTime = Timestamp;
     type MaximumLockingPeriod = MaximumLockingPeriod;
     type Balance = Balance;
+    type RewardBalance = Balance;
     type BalanceUnit = BalanceUnit;
     type BlockNumber = BlockNumber;
     type VestingBalance = MockVestingBalance<Self>;
     type WeightInfo = ();
 }
 
+/// The instance staking the native currency but rewarding in the asset [`REWARD_ASSET_ID`].
+impl Config<Instance1> for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type DivestTolerance = DivestTolerance;
+    type ExpectedBlockTime = ExpectedBlockTime;
+    type UnixTime = Timestamp;
+    type MaximumLockingPeriod = MaximumLockingPeriod;
+    type Balance = Balance;
+    type RewardBalance = <Assets as fungibles::Inspect<AccountId>>::Balance;
+    type BalanceUnit = BalanceUnit;
+    type BlockNumber = BlockNumber;
+    type VestingBalance =
+        AssetRewardVestingBalance<MockVestingBalance<Self>, Assets, RewardAssetId, RewardPot>;
+    type WeightInfo = ();
+}
+
 impl mock_pallet::Config for Test {
     type RuntimeEvent = RuntimeEvent;
 }
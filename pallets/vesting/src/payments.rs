@@ -0,0 +1,91 @@
+use core::marker::PhantomData;
+
+use frame_support::{
+    sp_runtime::{traits::Zero, DispatchError},
+    traits::{
+        fungibles::{Inspect, Mutate},
+        tokens::Preservation,
+        Get,
+    },
+};
+use sp_arithmetic::Perbill;
+
+use crate::VestingBalance;
+
+/// A [`VestingBalance`] paying out accrued rewards in a fungible asset, e.g. an asset of `pallet_assets`,
+/// while delegating all stake handling to `Stake`.
+///
+/// Rewards are transferred from the `RewardPot` account, which has to be funded with at least the rewards passed to [`crate::Pallet::distribute_reward`].
+pub struct AssetRewardVestingBalance<Stake, Assets, RewardAssetId, RewardPot>(
+    PhantomData<(Stake, Assets, RewardAssetId, RewardPot)>,
+);
+
+/// Transfers `amount` of the reward asset from the reward pot to `target`.
+fn pay_reward<AccountId, Assets, RewardAssetId, RewardPot>(
+    target: &AccountId,
+    amount: <Assets as Inspect<AccountId>>::Balance,
+) -> Result<(), DispatchError>
+where
+    Assets: Mutate<AccountId>,
+    RewardAssetId: Get<<Assets as Inspect<AccountId>>::AssetId>,
+    RewardPot: Get<AccountId>,
+{
+    if amount.is_zero() {
+        return Ok(());
+    }
+    Assets::transfer(
+        RewardAssetId::get(),
+        &RewardPot::get(),
+        target,
+        amount,
+        Preservation::Expendable,
+    )?;
+    Ok(())
+}
+
+impl<AccountId, Balance, Stake, Assets, RewardAssetId, RewardPot>
+    VestingBalance<AccountId, Balance, <Assets as Inspect<AccountId>>::Balance>
+    for AssetRewardVestingBalance<Stake, Assets, RewardAssetId, RewardPot>
+where
+    Assets: Mutate<AccountId>,
+    RewardAssetId: Get<<Assets as Inspect<AccountId>>::AssetId>,
+    RewardPot: Get<AccountId>,
+    Stake: VestingBalance<AccountId, Balance>,
+{
+    fn lock_stake(target: &AccountId, stake: Balance) -> Result<(), DispatchError> {
+        Stake::lock_stake(target, stake)
+    }
+
+    fn adjust_lock(acc: &AccountId, stake: Balance) {
+        Stake::adjust_lock(acc, stake)
+    }
+
+    fn pay_accrued(
+        target: &AccountId,
+        accrued: <Assets as Inspect<AccountId>>::Balance,
+    ) -> Result<(), DispatchError> {
+        pay_reward::<AccountId, Assets, RewardAssetId, RewardPot>(target, accrued)
+    }
+
+    fn pay_kicker(
+        target: &AccountId,
+        accrued: <Assets as Inspect<AccountId>>::Balance,
+    ) -> Result<(), DispatchError> {
+        pay_reward::<AccountId, Assets, RewardAssetId, RewardPot>(target, accrued)
+    }
+
+    fn unlock_stake(target: &AccountId, stake: Balance) -> Result<(), DispatchError> {
+        Stake::unlock_stake(target, stake)
+    }
+
+    fn power_decreased(target: &AccountId, perbill: Perbill) -> Result<(), DispatchError> {
+        Stake::power_decreased(target, perbill)
+    }
+
+    fn power_increased(
+        target: &AccountId,
+        reciprocal_perbill: Perbill,
+    ) -> Result<(), DispatchError> {
+        Stake::power_increased(target, reciprocal_perbill)
+    }
+}
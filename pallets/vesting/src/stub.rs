@@ -8,6 +8,10 @@ pub type Balance = u128;
 pub type AccountId = AccountId32;
 // needs to be same as frame_system::mocking::MockBlock used in tests
 pub type BlockNumber = u64;
+pub type AssetId = u32;
+
+/// The asset paid out as reward by the vesting instance rewarding in a non-native asset.
+pub const REWARD_ASSET_ID: AssetId = 42;
 
 pub const UNIT: Balance = 1_000_000;
 pub const MILLIUNIT: Balance = UNIT / 1_000;
@@ -36,3 +40,7 @@ pub const fn dave_account_id() -> AccountId {
 pub const fn eve_account_id() -> AccountId {
     AccountId32::new([4u8; 32])
 }
+
+pub const fn reward_pot_account_id() -> AccountId {
+    AccountId32::new([5u8; 32])
+}
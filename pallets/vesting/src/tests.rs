@@ -1,9 +1,14 @@
 #![cfg(test)]
 
-use frame_support::{assert_err, assert_ok};
+use frame_support::{
+    assert_err, assert_ok,
+    instances::Instance1,
+    traits::{GetStorageVersion, Hooks, StorageVersion},
+};
 use sp_arithmetic::Perbill;
+use sp_runtime::DispatchError;
 
-use crate::{mock::*, stub::*, types::*, Error, Event};
+use crate::{mock::*, stub::*, types::*, Error, Event, Pool};
 
 #[test]
 fn test_single_vest_no_rewards() {
@@ -180,7 +185,7 @@ fn test_single_vest_rewards() {
                 RuntimeEvent::AcurastVesting(Event::RewardDistributed(44 * UNIT)),
                 RuntimeEvent::MockPallet(mock_pallet::Event::PayAccrued(
                     alice_account_id(),
-                    61000005,
+                    83000005,
                 )),
                 RuntimeEvent::MockPallet(mock_pallet::Event::UnlockStake(
                     alice_account_id(),
@@ -192,8 +197,8 @@ fn test_single_vest_rewards() {
                         locking_period: 100,
                         power: 5_000_000,
                         stake: 10 * UNIT,
-                        accrued: 61_000_005,
-                        s: 10799998,
+                        accrued: 83_000_005,
+                        s: 15199998,
                         cooldown_started: Some(26),
                     }
                 )),
@@ -318,8 +323,8 @@ fn test_single_revest_in_cooldown() {
                         locking_period: 100,
                         power: 10_000_000,
                         stake: 10 * UNIT,
-                        accrued: 61000005,
-                        s: 10799998,
+                        accrued: 83000005,
+                        s: 15199998,
                         cooldown_started: None,
                     },
                     true
@@ -335,14 +340,14 @@ fn test_single_revest_in_cooldown() {
                         locking_period: 100,
                         power: 5_000_000,
                         stake: 10 * UNIT,
-                        accrued: 85000025,
-                        s: 16199997,
+                        accrued: 107000025,
+                        s: 20599997,
                         cooldown_started: Some(30),
                     }
                 )),
                 RuntimeEvent::MockPallet(mock_pallet::Event::PayAccrued(
                     alice_account_id(),
-                    85000025,
+                    107000025,
                 )),
                 RuntimeEvent::MockPallet(mock_pallet::Event::UnlockStake(
                     alice_account_id(),
@@ -354,8 +359,8 @@ fn test_single_revest_in_cooldown() {
                         locking_period: 100,
                         power: 5_000_000,
                         stake: 10 * UNIT,
-                        accrued: 85000025,
-                        s: 16199997,
+                        accrued: 107000025,
+                        s: 20599997,
                         cooldown_started: Some(30),
                     }
                 )),
@@ -858,3 +863,511 @@ fn test_maximum_locking_period_exceeded() {
         assert_eq!(events(), []);
     });
 }
+
+#[test]
+fn test_asset_reward_instance_pays_accrued_in_reward_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(10);
+        assert_ok!(AssetVesting::vest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            Vesting {
+                stake: 10u128 * UNIT,
+                locking_period: 100u64,
+            }
+        ));
+        // the native instance is not affected by the asset instance's vesters
+        assert_ok!(AcurastVesting::vest(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            Vesting {
+                stake: 10u128 * UNIT,
+                locking_period: 100u64,
+            }
+        ));
+
+        System::set_block_number(20);
+        assert_ok!(AssetVesting::distribute_reward(44 * UNIT));
+        assert_ok!(AcurastVesting::distribute_reward(22 * UNIT));
+
+        System::set_block_number(26);
+        assert_ok!(AssetVesting::cooldown(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+        ));
+        assert_ok!(AcurastVesting::cooldown(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+        ));
+
+        System::set_block_number(126);
+        events();
+        assert_ok!(AssetVesting::divest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+        ));
+        assert_ok!(AcurastVesting::divest(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+        ));
+
+        // the asset instance's reward lands in the reward asset, the stake is unlocked natively
+        assert_eq!(
+            44 * UNIT,
+            Assets::balance(REWARD_ASSET_ID, alice_account_id())
+        );
+        assert_eq!(
+            956 * UNIT,
+            Assets::balance(REWARD_ASSET_ID, reward_pot_account_id())
+        );
+        // the native instance's reward does not touch the reward asset
+        assert_eq!(0, Assets::balance(REWARD_ASSET_ID, bob_account_id()));
+
+        let events = events();
+        assert!(
+            !events.contains(&RuntimeEvent::MockPallet(mock_pallet::Event::PayAccrued(
+                alice_account_id(),
+                44 * UNIT
+            )))
+        );
+        assert!(
+            events.contains(&RuntimeEvent::MockPallet(mock_pallet::Event::UnlockStake(
+                alice_account_id(),
+                10 * UNIT
+            )))
+        );
+        assert!(
+            events.contains(&RuntimeEvent::MockPallet(mock_pallet::Event::PayAccrued(
+                bob_account_id(),
+                22 * UNIT
+            )))
+        );
+        assert!(
+            events.contains(&RuntimeEvent::MockPallet(mock_pallet::Event::UnlockStake(
+                bob_account_id(),
+                10 * UNIT
+            )))
+        );
+    });
+}
+
+#[test]
+fn test_asset_reward_instance_pays_kicker_in_reward_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(10);
+        assert_ok!(AssetVesting::vest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            Vesting {
+                stake: 10u128 * UNIT,
+                locking_period: 100u64,
+            }
+        ));
+
+        System::set_block_number(20);
+        assert_ok!(AssetVesting::distribute_reward(44 * UNIT));
+        assert!(events().contains(&RuntimeEvent::AssetVesting(
+            Event::<Test, Instance1>::RewardDistributed(44 * UNIT)
+        )));
+
+        System::set_block_number(26);
+        assert_ok!(AssetVesting::cooldown(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+        ));
+
+        System::set_block_number(129);
+        assert_ok!(AssetVesting::kick_out(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            alice_account_id()
+        ));
+
+        assert_eq!(
+            44 * UNIT,
+            Assets::balance(REWARD_ASSET_ID, bob_account_id())
+        );
+        assert_eq!(0, Assets::balance(REWARD_ASSET_ID, alice_account_id()));
+        assert!(
+            events().contains(&RuntimeEvent::MockPallet(mock_pallet::Event::UnlockStake(
+                alice_account_id(),
+                10 * UNIT
+            )))
+        );
+    });
+}
+
+/// Simulates a chain stall at block 30 by jumping the timestamp 11 expected block times ahead, which credits 10 missed blocks.
+fn stall_chain() {
+    let start = 1_700_000_000_000;
+    System::set_block_number(29);
+    Timestamp::set_timestamp(start);
+    AcurastVesting::on_initialize(29);
+    assert_eq!(0, AcurastVesting::stall_credit());
+
+    System::set_block_number(30);
+    Timestamp::set_timestamp(start + 11 * 12_000);
+    AcurastVesting::on_initialize(30);
+    assert_eq!(10, AcurastVesting::stall_credit());
+    assert_eq!(11 * 12_000, AcurastVesting::largest_block_time_gap());
+    assert!(
+        events().contains(&RuntimeEvent::AcurastVesting(Event::StallDetected(
+            11 * 12_000,
+            10
+        )))
+    );
+}
+
+fn divest_tolerance(who: AccountId) -> u64 {
+    AcurastVesting::divest_tolerance(&who, &AcurastVesting::vester_states(&who).unwrap()).unwrap()
+}
+
+fn vest_and_cooldown(who: AccountId, cooldown_at: u64) {
+    System::set_block_number(10);
+    assert_ok!(AcurastVesting::vest(
+        RuntimeOrigin::signed(who.clone()).into(),
+        Vesting {
+            stake: 10u128 * UNIT,
+            locking_period: 100u64,
+        }
+    ));
+
+    System::set_block_number(cooldown_at);
+    assert_ok!(AcurastVesting::cooldown(RuntimeOrigin::signed(who).into()));
+}
+
+#[test]
+fn test_divest_after_stall_within_extended_tolerance() {
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+        stall_chain();
+
+        // cooldown ended at 126, tolerance of 2 blocks extended by a stall credit of 10 blocks
+        assert_eq!(12, divest_tolerance(alice_account_id()));
+        System::set_block_number(138);
+        assert_ok!(AcurastVesting::divest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+        ));
+        assert_eq!(
+            None,
+            AcurastVesting::cooldown_stall_credit(alice_account_id())
+        );
+    });
+
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+        stall_chain();
+
+        System::set_block_number(139);
+        assert_err!(
+            AcurastVesting::divest(RuntimeOrigin::signed(alice_account_id()).into()),
+            Error::<Test>::CannotDivestWhenToleranceEnded
+        );
+    });
+}
+
+#[test]
+fn test_kick_out_delayed_by_stall() {
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+        stall_chain();
+
+        // without the stall, alice could be kicked out from block 129 on
+        System::set_block_number(129);
+        assert_err!(
+            AcurastVesting::kick_out(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                alice_account_id()
+            ),
+            Error::<Test>::CannotKickoutBeforeCooldownToleranceEnded
+        );
+        System::set_block_number(138);
+        assert_err!(
+            AcurastVesting::kick_out(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                alice_account_id()
+            ),
+            Error::<Test>::CannotKickoutBeforeCooldownToleranceEnded
+        );
+
+        System::set_block_number(139);
+        assert_ok!(AcurastVesting::kick_out(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            alice_account_id()
+        ));
+        assert_eq!(
+            None,
+            AcurastVesting::cooldown_stall_credit(alice_account_id())
+        );
+    });
+}
+
+#[test]
+fn test_stall_before_cooldown_does_not_extend_tolerance() {
+    ExtBuilder::default().build().execute_with(|| {
+        stall_chain();
+        vest_and_cooldown(alice_account_id(), 31);
+        assert_eq!(
+            Some(10),
+            AcurastVesting::cooldown_stall_credit(alice_account_id())
+        );
+        assert_eq!(2, divest_tolerance(alice_account_id()));
+
+        System::set_block_number(134);
+        assert_ok!(AcurastVesting::kick_out(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            alice_account_id()
+        ));
+    });
+}
+
+#[test]
+fn test_stall_after_tolerance_does_not_extend_tolerance() {
+    ExtBuilder::default().build().execute_with(|| {
+        // cooldown ended at 120 and tolerance at 122, long before the stall
+        vest_and_cooldown(alice_account_id(), 20);
+
+        let start = 1_700_000_000_000;
+        System::set_block_number(199);
+        Timestamp::set_timestamp(start);
+        AcurastVesting::on_initialize(199);
+        System::set_block_number(200);
+        Timestamp::set_timestamp(start + 11 * 12_000);
+        AcurastVesting::on_initialize(200);
+        assert_eq!(10, AcurastVesting::stall_credit());
+        assert_eq!(vec![(200, 10)], AcurastVesting::stall_history().to_vec());
+
+        assert_eq!(2, divest_tolerance(alice_account_id()));
+        assert_ok!(AcurastVesting::kick_out(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            alice_account_id()
+        ));
+    });
+}
+
+#[test]
+fn test_stall_ending_in_cooldown_block_does_not_extend_tolerance() {
+    ExtBuilder::default().build().execute_with(|| {
+        // the stall detected at block 30 ended with block 29, before the cooldown started
+        vest_and_cooldown(alice_account_id(), 29);
+        stall_chain();
+        assert_eq!(
+            Some(0),
+            AcurastVesting::cooldown_stall_credit(alice_account_id())
+        );
+
+        assert_eq!(2, divest_tolerance(alice_account_id()));
+    });
+
+    ExtBuilder::default().build().execute_with(|| {
+        // the stall detected at block 30 started with block 28, during the cooldown
+        vest_and_cooldown(alice_account_id(), 28);
+        stall_chain();
+
+        assert_eq!(12, divest_tolerance(alice_account_id()));
+    });
+}
+
+#[test]
+fn test_set_stall_credit() {
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+
+        assert_err!(
+            AcurastVesting::set_stall_credit(RuntimeOrigin::signed(alice_account_id()).into(), 5),
+            DispatchError::BadOrigin
+        );
+
+        // grant credit for an undetected outage
+        assert_ok!(AcurastVesting::set_stall_credit(RuntimeOrigin::root(), 5));
+        assert_eq!(7, divest_tolerance(alice_account_id()));
+        System::set_block_number(133);
+        assert_err!(
+            AcurastVesting::kick_out(
+                RuntimeOrigin::signed(bob_account_id()).into(),
+                alice_account_id()
+            ),
+            Error::<Test>::CannotKickoutBeforeCooldownToleranceEnded
+        );
+
+        // reset the credit after incident review
+        assert_ok!(AcurastVesting::set_stall_credit(RuntimeOrigin::root(), 0));
+        assert_eq!(2, divest_tolerance(alice_account_id()));
+        assert_ok!(AcurastVesting::kick_out(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            alice_account_id()
+        ));
+
+        let events = events();
+        assert!(events.contains(&RuntimeEvent::AcurastVesting(Event::StallCreditUpdated(5))));
+        assert!(events.contains(&RuntimeEvent::AcurastVesting(Event::StallCreditUpdated(0))));
+    });
+}
+
+#[test]
+fn test_revest_increases_pool_totals() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(10);
+        assert_ok!(AcurastVesting::vest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            Vesting {
+                stake: 10u128 * UNIT,
+                locking_period: 100u64,
+            }
+        ));
+        assert_eq!(10 * UNIT, AcurastVesting::pool().total_power);
+        assert_eq!(10 * UNIT, AcurastVesting::pool().total_stake);
+
+        System::set_block_number(20);
+        assert_ok!(AcurastVesting::revest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+            Vesting {
+                stake: 20u128 * UNIT,
+                locking_period: 100u64,
+            }
+        ));
+        assert_eq!(20 * UNIT, AcurastVesting::pool().total_power);
+        assert_eq!(20 * UNIT, AcurastVesting::pool().total_stake);
+        assert_ok!(AcurastVesting::do_try_state());
+    });
+}
+
+#[test]
+fn test_cooldown_halves_pool_power() {
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+
+        assert_eq!(5 * UNIT, AcurastVesting::pool().total_power);
+        assert_eq!(10 * UNIT, AcurastVesting::pool().total_stake);
+        assert_ok!(AcurastVesting::do_try_state());
+    });
+}
+
+#[test]
+fn test_exit_removes_vester_from_pool() {
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+        vest_and_cooldown(bob_account_id(), 26);
+
+        System::set_block_number(126);
+        assert_ok!(AcurastVesting::divest(
+            RuntimeOrigin::signed(alice_account_id()).into(),
+        ));
+        assert_eq!(5 * UNIT, AcurastVesting::pool().total_power);
+        assert_eq!(10 * UNIT, AcurastVesting::pool().total_stake);
+
+        System::set_block_number(129);
+        assert_ok!(AcurastVesting::kick_out(
+            RuntimeOrigin::signed(charlie_account_id()).into(),
+            bob_account_id()
+        ));
+        assert_eq!(0, AcurastVesting::pool().total_power);
+        assert_eq!(0, AcurastVesting::pool().total_stake);
+        assert_ok!(AcurastVesting::do_try_state());
+    });
+}
+
+/// A linear congruential generator, so that the generated operation sequences are reproducible.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+#[test]
+fn test_pool_totals_match_vesters_after_operation_sequences() {
+    let accounts = [
+        alice_account_id(),
+        bob_account_id(),
+        charlie_account_id(),
+        dave_account_id(),
+    ];
+    for seed in 0..32u64 {
+        ExtBuilder::default().build().execute_with(|| {
+            let mut rng = Lcg(seed);
+            let mut block = 1u64;
+            for _ in 0..64 {
+                block += rng.next(40);
+                System::set_block_number(block);
+
+                let who = accounts[rng.next(accounts.len() as u64) as usize].clone();
+                let origin = RuntimeOrigin::signed(who.clone());
+                let vesting = Vesting {
+                    stake: (1 + rng.next(50) as u128) * UNIT,
+                    locking_period: 1 + rng.next(100),
+                };
+                let asset_instance = rng.next(2) == 0;
+                // failing operations are part of the generated sequences, only the invariant is asserted
+                let _ = match (rng.next(6), asset_instance) {
+                    (0, false) => AcurastVesting::vest(origin.into(), vesting),
+                    (0, true) => AssetVesting::vest(origin.into(), vesting),
+                    (1, false) => AcurastVesting::revest(origin.into(), vesting),
+                    (1, true) => AssetVesting::revest(origin.into(), vesting),
+                    (2, false) => AcurastVesting::cooldown(origin.into()),
+                    (2, true) => AssetVesting::cooldown(origin.into()),
+                    (3, false) => AcurastVesting::divest(origin.into()),
+                    (3, true) => AssetVesting::divest(origin.into()),
+                    (4, false) => AcurastVesting::kick_out(
+                        RuntimeOrigin::signed(eve_account_id()).into(),
+                        who,
+                    ),
+                    (4, true) => {
+                        AssetVesting::kick_out(RuntimeOrigin::signed(eve_account_id()).into(), who)
+                    }
+                    (_, false) => AcurastVesting::distribute_reward(UNIT),
+                    (_, true) => AssetVesting::distribute_reward(UNIT),
+                };
+
+                assert_ok!(AcurastVesting::do_try_state());
+                assert_ok!(AssetVesting::do_try_state());
+            }
+        });
+    }
+}
+
+#[test]
+fn test_migration_recomputes_drifted_pool_totals() {
+    ExtBuilder::default().build().execute_with(|| {
+        vest_and_cooldown(alice_account_id(), 26);
+        System::set_block_number(30);
+        assert_ok!(AcurastVesting::vest(
+            RuntimeOrigin::signed(bob_account_id()).into(),
+            Vesting {
+                stake: 20u128 * UNIT,
+                locking_period: 50u64,
+            }
+        ));
+
+        // simulate the totals drifted by the previous accounting
+        let pool = AcurastVesting::pool();
+        Pool::<Test>::put(PoolState {
+            total_power: 30 * UNIT,
+            total_stake: 40 * UNIT,
+            ..pool.clone()
+        });
+        StorageVersion::new(0).put::<AcurastVesting>();
+        assert!(AcurastVesting::do_try_state().is_err());
+
+        AcurastVesting::on_runtime_upgrade();
+
+        assert_eq!(pool, AcurastVesting::pool());
+        assert_eq!(15 * UNIT, AcurastVesting::pool().total_power);
+        assert_eq!(30 * UNIT, AcurastVesting::pool().total_stake);
+        assert_eq!(
+            StorageVersion::new(1),
+            AcurastVesting::on_chain_storage_version()
+        );
+        assert_ok!(AcurastVesting::do_try_state());
+    });
+}
+
+/// Fails if the calls, events, errors or constants of either vesting instance changed without updating the committed snapshot.
+#[test]
+fn metadata_artifact_matches_snapshot() {
+    let artifact = acurast_metadata_gen::generate(&Test::metadata(), |name| {
+        ["AcurastVesting", "AssetVesting"].contains(&name)
+    })
+    .unwrap();
+    assert_eq!(artifact.pallets.len(), 2);
+    acurast_metadata_gen::assert_snapshot(
+        &artifact,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/metadata/vesting.json"),
+    );
+}
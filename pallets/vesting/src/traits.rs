@@ -2,7 +2,10 @@ use frame_support::{sp_runtime::DispatchError, weights::Weight};
 use sp_arithmetic::Perbill;
 
 /// Trait used to manage vesting stakes and accrued rewards.
-pub trait VestingBalance<AccountId, Balance> {
+///
+/// Stakes are handled in `Balance` while accrued rewards are paid out in `RewardBalance`,
+/// allowing an instance to distribute rewards in an asset different from the staked currency.
+pub trait VestingBalance<AccountId, Balance, RewardBalance = Balance> {
     /// Tracks a stake being locked on an account.
     ///
     /// Can be implemented by setting a lock/freeze on `pallet_balances`.
@@ -12,13 +15,15 @@ pub trait VestingBalance<AccountId, Balance> {
     /// Pays out the accrued amount to an individual account.
     /// It depends on the implementation if `accrued` was already minted or still has to be.
     ///
-    /// Can be implemented by transfering the amount from a pallet account to `target` on `pallet_balances`.
-    fn pay_accrued(target: &AccountId, accrued: Balance) -> Result<(), DispatchError>;
+    /// Can be implemented by transfering the amount from a pallet account to `target` on `pallet_balances`
+    /// or, for rewards in a non-native asset, by [`crate::AssetRewardVestingBalance`].
+    fn pay_accrued(target: &AccountId, accrued: RewardBalance) -> Result<(), DispatchError>;
     /// Pays out the accrued amount to an individual account of the kicker.
     /// It depends on the implementation if `accrued` was already minted or still has to be.
     ///
-    /// Can be implemented by transfering the amount from a pallet account to `target` on `pallet_balances`.
-    fn pay_kicker(target: &AccountId, accrued: Balance) -> Result<(), DispatchError>;
+    /// Can be implemented by transfering the amount from a pallet account to `target` on `pallet_balances`
+    /// or, for rewards in a non-native asset, by [`crate::AssetRewardVestingBalance`].
+    fn pay_kicker(target: &AccountId, accrued: RewardBalance) -> Result<(), DispatchError>;
     /// Pays out the staked amount to an individual account.
     /// It depends on the implementation if `stake` was burned when staked and has to be minted again.
     ///
@@ -38,6 +43,7 @@ pub trait WeightInfo {
     fn cooldown() -> Weight;
     fn kick_out() -> Weight;
     fn distribute_reward() -> Weight;
+    fn set_stall_credit() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -64,4 +70,8 @@ impl WeightInfo for () {
     fn distribute_reward() -> Weight {
         Weight::from_parts(10_000, 0)
     }
+
+    fn set_stall_credit() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
 }
@@ -31,6 +31,7 @@ pub struct VesterState<Balance, BlockNumber> {
     pub locking_period: BlockNumber,
     pub power: Balance,
     pub stake: Balance,
+    /// The reward accrued so far, paid out in the reward asset on divest.
     pub accrued: Balance,
     pub s: Balance,
     pub cooldown_started: Option<BlockNumber>,
@@ -0,0 +1,43 @@
+use hex_literal::hex;
+use sp_runtime::AccountId32;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+pub const UNIT: Balance = 1_000_000;
+pub const MILLIUNIT: Balance = UNIT / 1_000;
+pub const MICROUNIT: Balance = UNIT / 1_000_000;
+pub const EXISTENTIAL_DEPOSIT: Balance = MILLIUNIT;
+
+/// The account of the processor that submitted the attestation in [`crate::attestation_chain`].
+pub fn processor_account_id() -> AccountId {
+    hex!("b8bc25a2b4c0386b8892b43e435b71fe11fa50533935f027949caf04bcce4694").into()
+}
+
+pub fn processor_2_account_id() -> AccountId {
+    hex!("b7dc9063baaa77ec997a8f216daa6b48ef8458c7f36f96379f68b042e078592f").into()
+}
+
+pub const fn alice_account_id() -> AccountId {
+    AccountId32::new([0u8; 32])
+}
+
+pub const fn bob_account_id() -> AccountId {
+    AccountId32::new([1u8; 32])
+}
+
+pub const fn charlie_account_id() -> AccountId {
+    AccountId32::new([2u8; 32])
+}
+
+pub const fn dave_account_id() -> AccountId {
+    AccountId32::new([3u8; 32])
+}
+
+pub const fn eve_account_id() -> AccountId {
+    AccountId32::new([4u8; 32])
+}
+
+pub const fn account_id(i: u8) -> AccountId {
+    AccountId32::new([i; 32])
+}
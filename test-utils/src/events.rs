@@ -0,0 +1,32 @@
+/// Returns the events deposited by `$runtime` since the last call and resets them.
+///
+/// ```ignore
+/// assert_eq!(events_of!(Test), [RuntimeEvent::Acurast(Event::JobRegistrationStored(..))]);
+/// ```
+#[macro_export]
+macro_rules! events_of {
+    ($runtime:ty) => {{
+        let events = $crate::frame_system::Pallet::<$runtime>::events()
+            .into_iter()
+            .map(|record| record.event)
+            .collect::<Vec<_>>();
+        $crate::frame_system::Pallet::<$runtime>::reset_events();
+        events
+    }};
+}
+
+/// Asserts that `$event` is the last event deposited by `$runtime`.
+///
+/// The event can be given as pallet event and gets converted into the runtime event.
+#[macro_export]
+macro_rules! assert_last_event {
+    ($runtime:ty, $event:expr) => {{
+        let expected: <$runtime as $crate::frame_system::Config>::RuntimeEvent = $event.into();
+        assert_eq!(
+            $crate::frame_system::Pallet::<$runtime>::events()
+                .last()
+                .map(|record| record.event.clone()),
+            Some(expected)
+        );
+    }};
+}
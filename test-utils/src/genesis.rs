@@ -0,0 +1,61 @@
+use sp_runtime::{BuildStorage, Storage};
+
+/// Builds the genesis of a mock runtime from the genesis configs of the pallets it includes.
+///
+/// The resulting externalities start at block `1`, so that events get deposited.
+pub struct GenesisBuilder<T: pallet_balances::Config> {
+    storage: Storage,
+    balances: Vec<(T::AccountId, T::Balance)>,
+}
+
+impl<T: pallet_balances::Config> GenesisBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            storage: frame_system::GenesisConfig::<T>::default()
+                .build_storage()
+                .unwrap(),
+            balances: vec![],
+        }
+    }
+
+    /// Funds `account` with `amount` in the [`pallet_balances`] genesis.
+    pub fn fund(mut self, account: T::AccountId, amount: T::Balance) -> Self {
+        self.balances.push((account, amount));
+        self
+    }
+
+    /// Funds all `accounts` with `amount` each.
+    pub fn fund_all(
+        mut self,
+        accounts: impl IntoIterator<Item = T::AccountId>,
+        amount: T::Balance,
+    ) -> Self {
+        self.balances
+            .extend(accounts.into_iter().map(|account| (account, amount)));
+        self
+    }
+
+    /// Adds the genesis config of another pallet included in the runtime.
+    pub fn with<G: BuildStorage>(mut self, genesis: &G) -> Self {
+        genesis.assimilate_storage(&mut self.storage).unwrap();
+        self
+    }
+
+    pub fn build(mut self) -> sp_io::TestExternalities {
+        pallet_balances::GenesisConfig::<T> {
+            balances: self.balances,
+        }
+        .assimilate_storage(&mut self.storage)
+        .unwrap();
+
+        let mut ext = sp_io::TestExternalities::new(self.storage);
+        ext.execute_with(|| frame_system::Pallet::<T>::set_block_number(1u32.into()));
+        ext
+    }
+}
+
+impl<T: pallet_balances::Config> Default for GenesisBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
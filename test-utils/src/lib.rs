@@ -0,0 +1,19 @@
+//! Building blocks for the mock runtimes of the Acurast pallets' tests.
+//!
+//! Only to be used as dev-dependency: the mock runtimes compose these primitives instead of re-implementing
+//! accounts, genesis, time and event helpers, so that the mocks of different pallets stay compatible when assembled
+//! into a cross-pallet runtime.
+
+pub mod accounts;
+mod events;
+pub mod fixtures;
+pub mod genesis;
+pub mod time;
+
+pub use accounts::*;
+pub use fixtures::*;
+pub use genesis::*;
+pub use time::*;
+
+#[doc(hidden)]
+pub use frame_system;
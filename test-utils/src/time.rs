@@ -0,0 +1,36 @@
+use core::time::Duration;
+
+use frame_support::{storage::unhashed, traits::UnixTime};
+
+/// The storage key of the current time in milliseconds, see [`MockTime`].
+const NOW_KEY: &[u8] = b":acurast_test_utils:now";
+
+/// A [`UnixTime`] whose current time is kept in storage, so that it is reset with each test's externalities.
+///
+/// The time starts at `0` and only changes by calling [`MockTime::set`] or [`MockTime::advance`].
+pub struct MockTime;
+
+impl MockTime {
+    /// Returns the current time in milliseconds.
+    pub fn get() -> u64 {
+        unhashed::get_or_default(NOW_KEY)
+    }
+
+    /// Sets the current time in milliseconds.
+    pub fn set(now: u64) {
+        unhashed::put(NOW_KEY, &now);
+    }
+
+    /// Advances the current time by `delta` milliseconds and returns the new time.
+    pub fn advance(delta: u64) -> u64 {
+        let now = Self::get().saturating_add(delta);
+        Self::set(now);
+        now
+    }
+}
+
+impl UnixTime for MockTime {
+    fn now() -> Duration {
+        Duration::from_millis(Self::get())
+    }
+}